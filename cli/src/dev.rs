@@ -0,0 +1,97 @@
+//! Genesis-less bootstrap of a single peer network for local development.
+//!
+//! The genesis is generated in memory and signed with a throwaway key, while the
+//! faucet account is derived from a well-known seed, so anyone can spend its funds.
+//! This is why the mode has to be explicitly allowed by `genesis.allow_insecure_dev_mode`.
+
+use std::path::Path;
+
+use error_stack::{Report, Result, ResultExt};
+use iroha_config::parameters::actual::Root as Config;
+use iroha_crypto::{Algorithm, KeyPair};
+use iroha_data_model::prelude::*;
+use iroha_genesis::{GenesisBlock, GenesisBuilder, GENESIS_DOMAIN_ID};
+
+use crate::ConfigError;
+
+/// Seed of the well-known faucet key pair.
+const FAUCET_SEED: &[u8] = b"iroha development faucet";
+/// Amount of [`coin_id`] minted to the faucet account.
+const FAUCET_BALANCE: u32 = 1_000_000_000;
+
+/// Key pair of the faucet account. It is the same on every run.
+pub fn faucet_key_pair() -> KeyPair {
+    KeyPair::from_seed(FAUCET_SEED.to_vec(), Algorithm::Ed25519)
+}
+
+/// Domain created by the development genesis and owned by the faucet account.
+pub fn domain_id() -> DomainId {
+    "dev".parse().expect("valid domain name")
+}
+
+/// Account holding all the funds of the development network.
+pub fn faucet_account_id() -> AccountId {
+    AccountId::new(domain_id(), faucet_key_pair().into_parts().0)
+}
+
+/// Asset minted to the faucet account.
+pub fn coin_id() -> AssetDefinitionId {
+    AssetDefinitionId::new(domain_id(), "coin".parse().expect("valid asset name"))
+}
+
+/// Generate and sign the genesis of a development network consisting of this peer only.
+///
+/// The genesis public key of `config` is replaced with the one of the generated key pair.
+///
+/// # Errors
+/// - If the development mode is not allowed by the configuration
+/// - If a genesis file is configured at the same time
+/// - If failed to read the executor
+pub(crate) fn genesis(config: &mut Config, executor: &Path) -> Result<GenesisBlock, ConfigError> {
+    if !config.genesis.allow_insecure_dev_mode {
+        return Err(Report::new(ConfigError::DevModeNotAllowed).attach_printable(
+            "set `genesis.allow_insecure_dev_mode` to `true` to use `--dev`, but never do it in production",
+        ));
+    }
+    if let Some(file) = &config.genesis.file {
+        return Err(Report::new(ConfigError::DevModeWithGenesisFile)
+            .attach_printable(file.clone().into_attachment().display_path()));
+    }
+
+    let executor = std::fs::read(executor)
+        .map(|wasm| Executor::new(WasmSmartContract::from_compiled(wasm)))
+        .change_context(ConfigError::ReadGenesis)
+        .attach_printable_lazy(|| format!("executor path: {}", executor.display()))?;
+
+    let genesis_key_pair = KeyPair::random();
+    let genesis_account_id = AccountId::new(
+        GENESIS_DOMAIN_ID.clone(),
+        genesis_key_pair.public_key().clone(),
+    );
+    let faucet_account_id = faucet_account_id();
+
+    let genesis = GenesisBuilder::default()
+        .domain(domain_id().name)
+        .account(faucet_account_id.signatory().clone())
+        .asset(coin_id().name, AssetType::Numeric(NumericSpec::default()))
+        .finish_domain()
+        .append_instruction(Mint::asset_numeric(
+            FAUCET_BALANCE,
+            AssetId::new(coin_id(), faucet_account_id.clone()),
+        ))
+        .append_instruction(Transfer::domain(
+            genesis_account_id,
+            domain_id(),
+            faucet_account_id,
+        ))
+        .build_and_sign(
+            executor,
+            config.common.chain.clone(),
+            &genesis_key_pair,
+            vec![config.common.peer.clone()],
+        );
+
+    config.genesis.public_key = genesis_key_pair.into_parts().0;
+
+    Ok(genesis)
+}
@@ -0,0 +1,337 @@
+//! Preflight checks of the peer configuration and environment, see `--doctor`.
+//!
+//! Each check reports its own status, so a single run lists every problem found
+//! instead of stopping at the first one. Checks never modify the peer storage.
+
+use std::{
+    fmt,
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use iroha_config::parameters::actual::Root as Config;
+use iroha_core::{
+    kura::BlockStore,
+    smartcontracts::wasm::{
+        create_engine,
+        engine::{Backend as _, Selected},
+        load_module,
+    },
+};
+use iroha_crypto::Signature;
+use iroha_data_model::block::SignedBlock;
+
+/// Time to wait for a trusted peer to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+/// Free disk space below which block storage is likely to run out of space soon
+const MIN_FREE_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+/// Maximum tolerated difference between the local clock and the clocks of other peers
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5);
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Nothing to worry about
+    Ok,
+    /// Peer can start, but is likely to misbehave
+    Warning,
+    /// Peer can't work properly with this configuration or environment
+    Failure,
+    /// Check can't be performed in this environment
+    Skipped,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Warning => "warn",
+            Self::Failure => "fail",
+            Self::Skipped => "skip",
+        })
+    }
+}
+
+/// Result of a single check
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// What is checked
+    pub name: &'static str,
+    /// Outcome of the check
+    pub status: Status,
+    /// Human-readable explanation of the outcome
+    pub details: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            details: details.into(),
+        }
+    }
+}
+
+/// Results of all checks performed by [`diagnose`]
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Checks in the order they were performed
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// Whether none of the checks failed
+    pub fn is_healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != Status::Failure)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(
+                f,
+                "[{:>4}] {:<24} {}",
+                check.status, check.name, check.details
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Run every check against `config`.
+///
+/// Configuration errors which prevent the peer from starting at all are reported
+/// by [`read_config_and_genesis`](crate::read_config_and_genesis) before this is called.
+pub fn diagnose(config: &Config) -> Report {
+    let mut checks = vec![check_key_pair(config), check_trusted_peers(config)];
+    checks.extend(check_peers_reachability(config));
+    checks.push(check_disk_space(config));
+    checks.push(check_clock_skew(config));
+    checks.push(check_wasm_runtime());
+
+    Report { checks }
+}
+
+fn check_key_pair(config: &Config) -> Check {
+    const NAME: &str = "key pair";
+
+    let key_pair = &config.common.key_pair;
+    let payload = b"iroha doctor";
+    if Signature::new(key_pair.private_key(), payload)
+        .verify(key_pair.public_key(), payload)
+        .is_err()
+    {
+        return Check::new(
+            NAME,
+            Status::Failure,
+            "signature made with `private_key` doesn't match `public_key`",
+        );
+    }
+    if config.common.peer.public_key() != key_pair.public_key() {
+        return Check::new(NAME, Status::Failure, "peer id doesn't match `public_key`");
+    }
+
+    Check::new(
+        NAME,
+        Status::Ok,
+        format!("signing with {}", key_pair.public_key()),
+    )
+}
+
+fn check_trusted_peers(config: &Config) -> Check {
+    const NAME: &str = "trusted peers";
+
+    let trusted_peers = config.sumeragi.trusted_peers.value();
+    if trusted_peers.myself != config.common.peer {
+        return Check::new(
+            NAME,
+            Status::Failure,
+            "this peer isn't the one in `sumeragi.trusted_peers`",
+        );
+    }
+    if let Some(peer) = trusted_peers
+        .others
+        .iter()
+        .find(|peer| peer.address() == trusted_peers.myself.address())
+    {
+        return Check::new(
+            NAME,
+            Status::Failure,
+            format!("`{peer}` has the same address as this peer"),
+        );
+    }
+    if trusted_peers.others.is_empty() {
+        return Check::new(
+            NAME,
+            Status::Warning,
+            "this peer is the only one in the network",
+        );
+    }
+
+    Check::new(
+        NAME,
+        Status::Ok,
+        format!("{} other peers", trusted_peers.others.len()),
+    )
+}
+
+fn check_peers_reachability(config: &Config) -> Vec<Check> {
+    const NAME: &str = "peer reachability";
+
+    config
+        .sumeragi
+        .trusted_peers
+        .value()
+        .others
+        .iter()
+        .map(|peer| {
+            let connected = peer.address().to_socket_addrs().map(|mut addrs| {
+                addrs.any(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok())
+            });
+            match connected {
+                Ok(true) => Check::new(
+                    NAME,
+                    Status::Ok,
+                    format!("`{}` is reachable", peer.address()),
+                ),
+                // Peers of a network being set up are expected to start one after another
+                Ok(false) => Check::new(
+                    NAME,
+                    Status::Warning,
+                    format!("`{}` is unreachable", peer.address()),
+                ),
+                Err(error) => Check::new(
+                    NAME,
+                    Status::Failure,
+                    format!("`{}` can't be resolved: {error}", peer.address()),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn check_disk_space(config: &Config) -> Check {
+    const NAME: &str = "kura disk space";
+
+    let store_dir = config.kura.store_dir.resolve_relative_path();
+    match available_space(&store_dir) {
+        None => Check::new(
+            NAME,
+            Status::Skipped,
+            "free disk space can't be determined on this platform",
+        ),
+        Some(Err(error)) => Check::new(
+            NAME,
+            Status::Failure,
+            format!("`{}` is inaccessible: {error}", store_dir.display()),
+        ),
+        Some(Ok(available)) if available < MIN_FREE_DISK_SPACE => Check::new(
+            NAME,
+            Status::Warning,
+            format!("only {} MiB left", available / 1024 / 1024),
+        ),
+        Some(Ok(available)) => Check::new(
+            NAME,
+            Status::Ok,
+            format!("{} MiB available", available / 1024 / 1024),
+        ),
+    }
+}
+
+/// Free space available on the file system of `path` or of its closest existing ancestor
+#[cfg(unix)]
+fn available_space(path: &Path) -> Option<std::io::Result<u64>> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or_else(|| Path::new("."));
+    Some(
+        rustix::fs::statvfs(existing)
+            .map(|stats| stats.f_bavail.saturating_mul(stats.f_frsize))
+            .map_err(Into::into),
+    )
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> Option<std::io::Result<u64>> {
+    None
+}
+
+fn check_clock_skew(config: &Config) -> Check {
+    const NAME: &str = "clock skew";
+
+    let store_dir = config.kura.store_dir.resolve_relative_path();
+    let latest_block = match read_latest_block(&store_dir) {
+        Ok(Some(block)) => block,
+        Ok(None) => {
+            return Check::new(
+                NAME,
+                Status::Skipped,
+                "no stored blocks to compare the local clock with",
+            )
+        }
+        Err(error) => return Check::new(NAME, Status::Failure, error),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Current time should be after the UNIX epoch");
+    // Blocks are created by other peers, so a block from the future means that
+    // the local clock is behind. A clock which is ahead can't be told apart from
+    // a network which hasn't produced blocks lately.
+    let created_at = latest_block.header().creation_time();
+    match created_at.checked_sub(now) {
+        Some(skew) if skew > MAX_CLOCK_SKEW => Check::new(
+            NAME,
+            Status::Failure,
+            format!(
+                "local clock is {} ms behind the latest block",
+                skew.as_millis()
+            ),
+        ),
+        _ => Check::new(
+            NAME,
+            Status::Ok,
+            "local clock isn't behind the latest block",
+        ),
+    }
+}
+
+/// Read the latest block from Kura without initializing it, which could modify the storage
+fn read_latest_block(store_dir: &Path) -> Result<Option<SignedBlock>, String> {
+    let block_store = BlockStore::new(store_dir);
+    let Ok(block_count) = block_store.read_index_count() else {
+        // Block storage isn't created until the peer starts for the first time
+        return Ok(None);
+    };
+    let Some(latest) = block_count.checked_sub(1) else {
+        return Ok(None);
+    };
+
+    block_store
+        .read_block(latest)
+        .map(Some)
+        .map_err(|error| format!("failed to read the latest block: {error}"))
+}
+
+fn check_wasm_runtime() -> Check {
+    const NAME: &str = "wasm runtime";
+
+    match load_module(&create_engine(), "(module)") {
+        Ok(_) => Check::new(
+            NAME,
+            Status::Ok,
+            format!("smart contracts can be compiled with `{}`", Selected::NAME),
+        ),
+        Err(error) => Check::new(
+            NAME,
+            Status::Failure,
+            format!("failed to compile a smart contract: {error}"),
+        ),
+    }
+}
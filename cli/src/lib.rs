@@ -7,8 +7,10 @@
 #[cfg(debug_assertions)]
 use core::sync::atomic::{AtomicBool, Ordering};
 use std::{
+    num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use clap::Parser;
@@ -21,25 +23,34 @@ use iroha_config::{
 use iroha_core::metrics::MetricsReporter;
 use iroha_core::{
     block_sync::{BlockSynchronizer, BlockSynchronizerHandle},
+    consistency::{world_digest, ConsistencyChecker, ConsistencyCheckerHandle},
+    event_sink::{self, EventForwarder},
     gossiper::{TransactionGossiper, TransactionGossiperHandle},
     handler::ThreadHandler,
     kiso::KisoHandle,
-    kura::Kura,
-    query::store::LiveQueryStore,
+    kura::{BlockCount, Kura},
+    partition::PartitionDetector,
+    query::{cache::QueryCache, quota::QueryQuotas, store::LiveQueryStore},
     queue::Queue,
+    replay::{self, ReplayReport},
     smartcontracts::isi::Registrable as _,
     snapshot::{
-        try_read_snapshot, SnapshotMaker, SnapshotMakerHandle, TryReadError as TryReadSnapshotError,
+        export::{Format as StateExportFormat, StateExport},
+        try_read_snapshot, try_write_snapshot, SnapshotMaker, SnapshotMakerHandle,
+        TryReadError as TryReadSnapshotError,
     },
     state::{State, StateReadOnly, World},
-    sumeragi::{GenesisWithPubKey, SumeragiHandle, SumeragiMetrics, SumeragiStartArgs},
-    IrohaNetwork,
+    sumeragi::{
+        BlockMetrics, GenesisWithPubKey, SumeragiHandle, SumeragiMetrics, SumeragiStartArgs,
+    },
+    EventsSender, IrohaNetwork, NetworkMessage,
 };
 use iroha_data_model::{block::SignedBlock, prelude::*};
 use iroha_genesis::GenesisBlock;
 use iroha_logger::{actor::LoggerHandle, InitConfig as LoggerInitConfig};
+use iroha_p2p::{peer::message::PeerMessage, Misbehavior, ReportMisbehavior};
 use iroha_primitives::addr::SocketAddr;
-use iroha_torii::Torii;
+use iroha_torii::{AdminApi, Torii};
 use iroha_version::scale::DecodeVersioned;
 use thiserror::Error;
 use tokio::{
@@ -48,8 +59,12 @@ use tokio::{
     task,
 };
 
+pub mod dev;
+pub mod doctor;
 // FIXME: move from CLI
 pub mod samples;
+#[cfg(unix)]
+pub mod systemd;
 
 /// Iroha is an
 /// [Orchestrator](https://en.wikipedia.org/wiki/Orchestration_%28computing%29)
@@ -59,9 +74,11 @@ pub struct Iroha {
     /// Actor responsible for the configuration
     _kiso: KisoHandle,
     /// Queue of transactions
-    _queue: Arc<Queue>,
+    queue: Arc<Queue>,
     /// Sumeragi consensus
     _sumeragi: SumeragiHandle,
+    /// Peer-to-peer network
+    network: IrohaNetwork,
     /// Kura — block storage
     kura: Arc<Kura>,
     /// Snapshot service. Might be not started depending on the config.
@@ -70,8 +87,10 @@ pub struct Iroha {
     state: Arc<State>,
     /// Shutdown signal
     notify_shutdown: Arc<Notify>,
-    /// Thread handlers
+    /// Thread handlers. Dropped in order, so consensus goes before block storage
     thread_handlers: Vec<ThreadHandler>,
+    /// Deadline for the graceful shutdown
+    shutdown_timeout: Duration,
     /// A boolean value indicating whether or not the peers will receive data from the network.
     /// Used in sumeragi testing.
     #[cfg(debug_assertions)]
@@ -95,6 +114,8 @@ impl Drop for Iroha {
 pub enum StartError {
     #[error("Unable to start peer-to-peer network")]
     StartP2p,
+    #[error("Unable to inherit listen sockets from the service manager")]
+    InheritListeners,
     #[error("Unable to initialize Kura (block storage)")]
     InitKura,
     #[error("Unable to start dev telemetry service")]
@@ -105,6 +126,20 @@ pub enum StartError {
     ListenOsSignal,
     #[error("Unable to start Torii (Iroha HTTP API Gateway)")]
     StartTorii,
+    #[error("Unable to connect to an event sink")]
+    StartEventSink,
+    #[error("Unable to migrate the state snapshot; upgrade through the intermediate releases or remove the snapshot to replay the chain")]
+    MigrateState,
+}
+
+/// Error(s) that might occur while shutting down [`Iroha`]
+#[derive(thiserror::Error, Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub enum ShutdownError {
+    #[error("In-flight work wasn't drained within {0:?}")]
+    Timeout(Duration),
+    #[error("Draining in-flight work panicked")]
+    Drain,
 }
 
 /// Handle for freezing and unfreezing the network
@@ -134,6 +169,7 @@ struct NetworkRelay {
     sumeragi: SumeragiHandle,
     block_sync: BlockSynchronizerHandle,
     gossiper: TransactionGossiperHandle,
+    consistency_checker: ConsistencyCheckerHandle,
     network: IrohaNetwork,
     shutdown_notify: Arc<Notify>,
     #[cfg(debug_assertions)]
@@ -164,7 +200,7 @@ impl NetworkRelay {
         }
     }
 
-    async fn handle_message(&mut self, msg: iroha_core::NetworkMessage) {
+    async fn handle_message(&mut self, PeerMessage(peer_id, msg): PeerMessage<NetworkMessage>) {
         use iroha_core::NetworkMessage::*;
 
         #[cfg(debug_assertions)]
@@ -172,6 +208,16 @@ impl NetworkRelay {
             return;
         }
 
+        if msg.claims_another_sender(&peer_id) {
+            let claimed_sender = msg.claimed_sender();
+            iroha_logger::warn!(%peer_id, ?claimed_sender, "Peer sent message on behalf of another peer");
+            self.network.report_misbehavior(ReportMisbehavior {
+                public_key: peer_id.public_key().clone(),
+                misbehavior: Misbehavior::ProtocolViolation,
+            });
+            return;
+        }
+
         match msg {
             SumeragiBlock(data) => {
                 self.sumeragi.incoming_block_message(*data);
@@ -180,7 +226,8 @@ impl NetworkRelay {
                 self.sumeragi.incoming_control_flow_message(*data);
             }
             BlockSync(data) => self.block_sync.message(*data).await,
-            TransactionGossiper(data) => self.gossiper.gossip(*data).await,
+            TransactionGossiper(data) => self.gossiper.gossip(peer_id, *data).await,
+            StateDigest(data) => self.consistency_checker.gossip(*data).await,
             Health => {}
         }
     }
@@ -252,9 +299,30 @@ impl Iroha {
         genesis: Option<GenesisBlock>,
         logger: LoggerHandle,
     ) -> Result<(impl core::future::Future<Output = ()>, Self), StartError> {
-        let network = IrohaNetwork::start(config.common.key_pair.clone(), config.network.clone())
-            .await
-            .change_context(StartError::StartP2p)?;
+        #[cfg(unix)]
+        let systemd::InheritedListeners {
+            torii: torii_listener,
+            admin: admin_listener,
+            network: network_listener,
+        } = systemd::InheritedListeners::from_env().change_context(StartError::InheritListeners)?;
+        #[cfg(not(unix))]
+        let (torii_listener, admin_listener, network_listener) = (None, None, None);
+
+        let network = match network_listener {
+            Some(listener) => {
+                iroha_logger::info!("Network inherited the listener from the service manager");
+                let listener = tokio::net::TcpListener::from_std(listener)
+                    .change_context(StartError::InheritListeners)?;
+                IrohaNetwork::start_with_listener(
+                    config.common.key_pair.clone(),
+                    config.network.clone(),
+                    listener,
+                )
+            }
+            None => IrohaNetwork::start(config.common.key_pair.clone(), config.network.clone())
+                .await
+                .change_context(StartError::StartP2p)?,
+        };
 
         let (events_sender, _) = broadcast::channel(10000);
         let world = World::with(
@@ -271,7 +339,7 @@ impl Iroha {
             LiveQueryStore::from_config(config.live_query_store, Arc::clone(&notify_shutdown))
                 .start();
 
-        let state = match try_read_snapshot(
+        let mut state = match try_read_snapshot(
             config.snapshot.store_dir.resolve_relative_path(),
             &kura,
             live_query_store_handle.clone(),
@@ -288,6 +356,9 @@ impl Iroha {
                 iroha_logger::info!("Didn't find a state snapshot; creating an empty state");
                 None
             }
+            Err(error @ TryReadSnapshotError::Migration(_)) => {
+                return Err(Report::new(error).change_context(StartError::MigrateState));
+            }
             Err(error) => {
                 iroha_logger::warn!(%error, "Failed to load the state from a snapshot; creating an empty state");
                 None
@@ -299,6 +370,11 @@ impl Iroha {
                 live_query_store_handle.clone(),
             )
         });
+        state.set_record_block_diffs(config.state.record_block_diffs);
+        state.set_archive_events(config.state.archive_events);
+        state.set_profile_instructions(config.state.profile_instructions);
+        state.set_module_cache_capacity(config.state.wasm_module_cache_size);
+        state.set_validation_cache_capacity(config.state.validation_cache_size);
         let state = Arc::new(state);
 
         let queue = Arc::new(Queue::from_config(config.queue, events_sender.clone()));
@@ -314,6 +390,36 @@ impl Iroha {
             queue.clone(),
         );
 
+        // NOTE: started before sumeragi so that the blocks replayed on startup are tracked
+        let query_cache = QueryCache::from_config(
+            &config.live_query_store,
+            &state,
+            &events_sender,
+            metrics_reporter.metrics().clone(),
+            Arc::clone(&notify_shutdown),
+        )
+        .map(QueryCache::start);
+
+        let consistency_checker = ConsistencyChecker::from_config(
+            &config.state,
+            config.common.peer.clone(),
+            config.common.key_pair.clone(),
+            network.clone(),
+            Arc::clone(&state),
+            metrics_reporter.metrics().clone(),
+        )
+        .with_time_source(queue.time_source().clone())
+        .start();
+
+        let partition = PartitionDetector::from_config(
+            &config.network,
+            config.common.peer.clone(),
+            network.clone(),
+            Arc::clone(&state),
+            metrics_reporter.metrics().clone(),
+        )
+        .start();
+
         let start_args = SumeragiStartArgs {
             sumeragi_config: config.sumeragi.clone(),
             common_config: config.common.clone(),
@@ -327,15 +433,23 @@ impl Iroha {
                 public_key: config.genesis.public_key.clone(),
             },
             block_count,
+            consistency_checker: consistency_checker.clone(),
             sumeragi_metrics: SumeragiMetrics {
                 dropped_messages: metrics_reporter.metrics().dropped_messages.clone(),
                 view_changes: metrics_reporter.metrics().view_changes.clone(),
+                blocks: BlockMetrics {
+                    commit_time_ms: metrics_reporter.metrics().commit_time_ms.clone(),
+                    signatures: metrics_reporter.metrics().block_signatures.clone(),
+                    transactions: metrics_reporter.metrics().block_transactions.clone(),
+                    size_bytes: metrics_reporter.metrics().block_size_bytes.clone(),
+                },
             },
         };
         // Starting Sumeragi requires no async context enabled
-        let sumeragi = task::spawn_blocking(move || SumeragiHandle::start(start_args))
-            .await
-            .expect("Failed to join task with Sumeragi start");
+        let (sumeragi, sumeragi_thread_handler) =
+            task::spawn_blocking(move || SumeragiHandle::start(start_args))
+                .await
+                .expect("Failed to join task with Sumeragi start");
 
         let block_sync = BlockSynchronizer::from_config(
             &config.block_sync,
@@ -353,16 +467,29 @@ impl Iroha {
             network.clone(),
             Arc::clone(&queue),
             Arc::clone(&state),
+            config.common.role,
         )
         .start();
 
         #[cfg(debug_assertions)]
         let freeze_status = FreezeStatus::new(config.common.peer.clone());
 
+        let admin = config.admin.clone().map(|admin_config| {
+            AdminApi::new(
+                admin_config,
+                network.clone(),
+                sumeragi.clone(),
+                block_sync.clone(),
+                Arc::clone(&queue),
+                Arc::clone(&state),
+            )
+        });
+
         NetworkRelay {
             sumeragi: sumeragi.clone(),
-            block_sync,
+            block_sync: block_sync.clone(),
             gossiper,
+            consistency_checker,
             network: network.clone(),
             shutdown_notify: Arc::clone(&notify_shutdown),
             #[cfg(debug_assertions)]
@@ -373,22 +500,44 @@ impl Iroha {
         let snapshot_maker = SnapshotMaker::from_config(&config.snapshot, Arc::clone(&state))
             .map(SnapshotMaker::start);
 
+        Self::start_event_sinks(&config, &events_sender, &notify_shutdown).await?;
+
         let kiso = KisoHandle::new(config.clone());
 
         let torii = Torii::new(
             config.common.chain.clone(),
             kiso.clone(),
             config.torii,
+            config.common.role,
+            partition,
             Arc::clone(&queue),
             events_sender,
             Arc::clone(&notify_shutdown),
             live_query_store_handle,
+            query_cache,
+            QueryQuotas::from_config(&config.live_query_store),
             Arc::clone(&kura),
             Arc::clone(&state),
+            admin,
             #[cfg(feature = "telemetry")]
             metrics_reporter,
         );
 
+        let torii = match torii_listener {
+            Some(listener) => torii.with_listener(
+                tokio::net::TcpListener::from_std(listener)
+                    .change_context(StartError::InheritListeners)?,
+            ),
+            None => torii,
+        };
+        let torii = match admin_listener {
+            Some(listener) => torii.with_admin_listener(
+                tokio::net::TcpListener::from_std(listener)
+                    .change_context(StartError::InheritListeners)?,
+            ),
+            None => torii,
+        };
+
         let run_torii = torii
             .start()
             .await
@@ -396,7 +545,14 @@ impl Iroha {
 
         tokio::spawn(run_torii);
 
-        Self::spawn_config_updates_broadcasting(kiso.clone(), logger.clone());
+        // Clients shouldn't be routed to the peer until it serves the latest state
+        #[cfg(unix)]
+        tokio::spawn(async move {
+            block_sync.synced().await;
+            systemd::notify_ready();
+        });
+
+        Self::spawn_config_updates_broadcasting(kiso.clone(), logger.clone(), Arc::clone(&queue));
 
         Self::start_listening_signal(Arc::clone(&notify_shutdown))?;
 
@@ -410,13 +566,15 @@ impl Iroha {
 
         let irohad = Self {
             _kiso: kiso,
-            _queue: queue,
+            queue,
             _sumeragi: sumeragi,
+            network,
             kura,
             _snapshot_maker: snapshot_maker,
             state,
             notify_shutdown,
-            thread_handlers: vec![kura_thread_handler],
+            thread_handlers: vec![sumeragi_thread_handler, kura_thread_handler],
+            shutdown_timeout: config.shutdown.timeout,
             #[cfg(debug_assertions)]
             freeze_status,
         };
@@ -467,6 +625,30 @@ impl Iroha {
         }
     }
 
+    async fn start_event_sinks(
+        config: &Config,
+        events_sender: &EventsSender,
+        notify_shutdown: &Arc<Notify>,
+    ) -> Result<(), StartError> {
+        for sink_config in &config.event_sinks {
+            let sink = event_sink::connect(sink_config)
+                .await
+                .into_report()
+                .map_err(|report| report.change_context(StartError::StartEventSink))
+                .attach_printable_lazy(|| format!("sink: {}", sink_config.name))?;
+            EventForwarder::new(
+                sink,
+                sink_config,
+                events_sender,
+                Arc::clone(notify_shutdown),
+            )
+            .start();
+            iroha_logger::info!(sink = %sink_config.name, "Forwarding events to the sink");
+        }
+
+        Ok(())
+    }
+
     fn start_listening_signal(
         notify_shutdown: Arc<Notify>,
     ) -> Result<task::JoinHandle<()>, StartError> {
@@ -497,12 +679,56 @@ impl Iroha {
         Ok(handle)
     }
 
+    /// Gracefully shut down the peer, draining in-flight work:
+    ///
+    /// 1. stop accepting new transactions;
+    /// 2. notify services listening for shutdown (Torii finishes outstanding requests);
+    /// 3. close p2p connections, saying goodbye to every connected peer;
+    /// 4. stop consensus at a round boundary, aborting the uncommitted round if any;
+    /// 5. flush blocks which are still pending in Kura to the disk.
+    ///
+    /// The whole sequence is bounded by the configured shutdown timeout.
+    ///
+    /// # Errors
+    /// If in-flight work wasn't drained in time. The work is abandoned in this case,
+    /// and it is up to the caller to terminate the process.
+    pub async fn shutdown(mut self) -> Result<(), ShutdownError> {
+        let timeout = self.shutdown_timeout;
+        iroha_logger::info!(?timeout, "Shutting down gracefully");
+        #[cfg(unix)]
+        systemd::notify_stopping();
+
+        self.queue.close();
+        self.notify_shutdown.notify_waiters();
+        self.network.shutdown();
+
+        // Joining threads is blocking, so it is done outside of the async context
+        let thread_handlers = core::mem::take(&mut self.thread_handlers);
+        let drain = task::spawn_blocking(move || drop(thread_handlers));
+
+        match tokio::time::timeout(timeout, drain).await {
+            Ok(Ok(())) => {
+                iroha_logger::info!("Graceful shutdown complete");
+                Ok(())
+            }
+            Ok(Err(error)) => {
+                iroha_logger::error!(%error, "Failed to drain in-flight work");
+                Err(Report::new(ShutdownError::Drain))
+            }
+            Err(_) => {
+                iroha_logger::error!(?timeout, "Graceful shutdown timed out");
+                Err(Report::new(ShutdownError::Timeout(timeout)))
+            }
+        }
+    }
+
     /// Spawns a task which subscribes on updates from configuration actor
     /// and broadcasts them further to interested actors. This way, neither config actor nor other ones know
     /// about each other, achieving loose coupling of code and system.
     fn spawn_config_updates_broadcasting(
         kiso: KisoHandle,
         logger: LoggerHandle,
+        queue: Arc<Queue>,
     ) -> task::JoinHandle<()> {
         tokio::spawn(async move {
             let mut log_level_update = kiso
@@ -510,6 +736,10 @@ impl Iroha {
                 .await
                 // FIXME: don't like neither the message nor inability to throw Result to the outside
                 .expect("Cannot proceed without working subscriptions");
+            let mut authority_filter_update = kiso
+                .subscribe_on_authority_filter()
+                .await
+                .expect("Cannot proceed without working subscriptions");
 
             // See https://github.com/tokio-rs/tokio/issues/5616 and
             // https://github.com/rust-lang/rust-clippy/issues/10636
@@ -522,6 +752,10 @@ impl Iroha {
                             iroha_logger::error!("Failed to reload log level: {error}");
                         };
                     }
+                    Ok(()) = authority_filter_update.changed() => {
+                        let value = authority_filter_update.borrow_and_update().clone();
+                        queue.set_authority_filter(value);
+                    }
                 };
             }
         })
@@ -554,6 +788,193 @@ fn genesis_domain(public_key: PublicKey) -> Domain {
     Domain::new(iroha_genesis::GENESIS_DOMAIN_ID.clone()).build(&genesis_account.id)
 }
 
+/// Error of [`replay_verify`]
+#[derive(Error, Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub enum ReplayVerifyError {
+    #[error("Unable to initialize Kura (block storage)")]
+    InitKura,
+    #[error("Re-executed chain diverged from the stored one")]
+    Diverged,
+}
+
+/// Re-execute every stored block from genesis on top of an empty state
+/// and check that the outcome matches the stored blocks and the state snapshot.
+///
+/// # Errors
+/// - Block storage can't be initialized
+/// - Re-execution diverged from the stored chain, the first divergent block is reported
+pub fn replay_verify(config: &Config) -> Result<ReplayReport, ReplayVerifyError> {
+    let (kura, block_count) =
+        Kura::new(&config.kura).change_context(ReplayVerifyError::InitKura)?;
+    let query_handle =
+        LiveQueryStore::from_config(config.live_query_store, Arc::new(Notify::new())).start();
+
+    let snapshot = match try_read_snapshot(
+        config.snapshot.store_dir.resolve_relative_path(),
+        &kura,
+        query_handle.clone(),
+        block_count,
+    ) {
+        Ok(snapshot) => Some(snapshot),
+        Err(TryReadSnapshotError::NotFound) => None,
+        Err(error) => {
+            iroha_logger::warn!(%error, "Failed to load the state snapshot; it won't be compared");
+            None
+        }
+    };
+    let world = World::with(
+        [genesis_domain(config.genesis.public_key.clone())],
+        [genesis_account(config.genesis.public_key.clone())],
+        [],
+    );
+    let state = State::new(world, Arc::clone(&kura), query_handle);
+    let genesis_account_id = AccountId::new(
+        iroha_genesis::GENESIS_DOMAIN_ID.clone(),
+        config.genesis.public_key.clone(),
+    );
+
+    iroha_logger::info!(
+        blocks = block_count.0,
+        "Re-executing the chain from genesis"
+    );
+    replay::replay_verify(
+        &state,
+        &kura,
+        block_count,
+        &config.common.chain,
+        &genesis_account_id,
+        config
+            .sumeragi
+            .trusted_peers
+            .value()
+            .clone()
+            .into_non_empty_vec(),
+        snapshot.as_ref(),
+    )
+    .map_err(|error| Report::new(error).change_context(ReplayVerifyError::Diverged))
+}
+
+/// Error of [`export_state`] and [`import_state`]
+#[derive(Error, Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub enum StateTransferError {
+    #[error("Unable to initialize Kura (block storage)")]
+    InitKura,
+    #[error("Requested height exceeds the height of the stored chain")]
+    HeightOutOfRange,
+    #[error("Re-executed chain diverged from the stored one")]
+    Diverged,
+    #[error("Nothing to export, the chain is empty")]
+    Empty,
+    #[error("Unable to read or write the exported state")]
+    Io,
+    #[error("Exported state doesn't match the stored chain")]
+    Import,
+    #[error("Unable to write the state snapshot")]
+    WriteSnapshot,
+}
+
+/// Re-execute the stored blocks up to `height`, the latest stored one by default,
+/// and export the resulting world state to `path`.
+///
+/// # Errors
+/// - Block storage can't be initialized or doesn't store `height` blocks
+/// - Re-execution diverged from the stored chain
+/// - The export can't be written
+pub fn export_state(
+    config: &Config,
+    height: Option<NonZeroUsize>,
+    format: StateExportFormat,
+    path: &Path,
+) -> Result<ReplayReport, StateTransferError> {
+    let (kura, block_count) =
+        Kura::new(&config.kura).change_context(StateTransferError::InitKura)?;
+    let height = height.map_or(block_count.0, NonZeroUsize::get);
+    if height > block_count.0 {
+        return Err(Report::new(StateTransferError::HeightOutOfRange))
+            .attach_printable_lazy(|| format!("requested {height}, stored {}", block_count.0));
+    }
+    let query_handle =
+        LiveQueryStore::from_config(config.live_query_store, Arc::new(Notify::new())).start();
+    let world = World::with(
+        [genesis_domain(config.genesis.public_key.clone())],
+        [genesis_account(config.genesis.public_key.clone())],
+        [],
+    );
+    let state = State::new(world, Arc::clone(&kura), query_handle);
+    let genesis_account_id = AccountId::new(
+        iroha_genesis::GENESIS_DOMAIN_ID.clone(),
+        config.genesis.public_key.clone(),
+    );
+
+    iroha_logger::info!(height, "Re-executing the chain up to the exported height");
+    let report = replay::replay_verify(
+        &state,
+        &kura,
+        BlockCount(height),
+        &config.common.chain,
+        &genesis_account_id,
+        config
+            .sumeragi
+            .trusted_peers
+            .value()
+            .clone()
+            .into_non_empty_vec(),
+        None,
+    )
+    .map_err(|error| Report::new(error).change_context(StateTransferError::Diverged))?;
+
+    let export = StateExport::new(&state.view()).ok_or(StateTransferError::Empty)?;
+    std::fs::write(path, export.encode_as(format))
+        .change_context(StateTransferError::Io)
+        .attach_printable_lazy(|| path.display().to_string())?;
+
+    Ok(report)
+}
+
+/// Import the world state exported with [`export_state`] from `path` and store it
+/// as the state snapshot of this peer, which is loaded on the next start.
+///
+/// The peer has to store the blocks up to the exported height already.
+///
+/// # Errors
+/// - Block storage can't be initialized
+/// - The export can't be read or decoded
+/// - The export doesn't match the stored blocks or its own digest
+/// - The snapshot can't be written
+pub fn import_state(
+    config: &Config,
+    format: StateExportFormat,
+    path: &Path,
+) -> Result<ReplayReport, StateTransferError> {
+    let bytes = std::fs::read(path)
+        .change_context(StateTransferError::Io)
+        .attach_printable_lazy(|| path.display().to_string())?;
+    let export = StateExport::decode_as(&bytes, format)
+        .map_err(|error| Report::new(error).change_context(StateTransferError::Import))?;
+    let (kura, _block_count) =
+        Kura::new(&config.kura).change_context(StateTransferError::InitKura)?;
+    let query_handle =
+        LiveQueryStore::from_config(config.live_query_store, Arc::new(Notify::new())).start();
+
+    let state = export
+        .into_state(kura, query_handle)
+        .map_err(|error| Report::new(error).change_context(StateTransferError::Import))?;
+    try_write_snapshot(
+        &state,
+        config.snapshot.store_dir.resolve_relative_path(),
+        config.snapshot.checksum_algorithm,
+    )
+    .map_err(|error| Report::new(error).change_context(StateTransferError::WriteSnapshot))?;
+
+    let state_view = state.view();
+    Ok(ReplayReport {
+        height: state_view.height(),
+        digest: world_digest(state_view.world()),
+    })
+}
+
 /// Error of [`read_config_and_genesis`]
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
@@ -566,6 +987,10 @@ pub enum ConfigError {
     ReadGenesis,
     #[error("The network consists from this one peer only")]
     LonePeer,
+    #[error("Development mode is not allowed by the configuration")]
+    DevModeNotAllowed,
+    #[error("Development mode generates its own genesis, but a genesis file is specified")]
+    DevModeWithGenesisFile,
     #[cfg(feature = "dev-telemetry")]
     #[error("Telemetry output file path is root or empty")]
     TelemetryOutFileIsRootOrEmpty,
@@ -582,9 +1007,12 @@ pub enum ConfigError {
 
 /// Read the configuration and then a genesis block if specified.
 ///
+/// With `--dev`, the genesis block is generated instead, see [`dev`].
+///
 /// # Errors
 /// - If failed to read the config
 /// - If failed to load the genesis block
+/// - If the development mode is requested, but not allowed
 pub fn read_config_and_genesis(
     args: &Args,
 ) -> Result<(Config, LoggerInitConfig, Option<GenesisBlock>), ConfigError> {
@@ -596,13 +1024,15 @@ pub fn read_config_and_genesis(
             .change_context(ConfigError::ReadConfig)?;
     }
 
-    let config = reader
+    let mut config = reader
         .read_and_complete::<UserConfig>()
         .change_context(ConfigError::ReadConfig)?
         .parse()
         .change_context(ConfigError::ParseConfig)?;
 
-    let genesis = if let Some(signed_file) = &config.genesis.file {
+    let genesis = if args.dev {
+        Some(dev::genesis(&mut config, &args.dev_executor)?)
+    } else if let Some(signed_file) = &config.genesis.file {
         let genesis = read_genesis(&signed_file.resolve_relative_path())
             .attach_printable(signed_file.clone().into_attachment().display_path())?;
         Some(genesis)
@@ -610,7 +1040,7 @@ pub fn read_config_and_genesis(
         None
     };
 
-    validate_config(&config)?;
+    validate_config(&config, genesis.is_some())?;
 
     let logger_config = LoggerInitConfig::new(config.logger.clone(), args.terminal_colors);
 
@@ -624,7 +1054,7 @@ fn read_genesis(path: &Path) -> Result<GenesisBlock, ConfigError> {
     Ok(GenesisBlock(genesis))
 }
 
-fn validate_config(config: &Config) -> Result<(), ConfigError> {
+fn validate_config(config: &Config, has_genesis: bool) -> Result<(), ConfigError> {
     let mut emitter = Emitter::new();
 
     // These cause race condition in tests, due to them actually binding TCP listeners
@@ -639,7 +1069,7 @@ fn validate_config(config: &Config) -> Result<(), ConfigError> {
     // maybe validate only if snapshot mode is enabled
     validate_directory_path(&mut emitter, &config.snapshot.store_dir);
 
-    if config.genesis.file.is_none() && !config.sumeragi.contains_other_trusted_peers() {
+    if !has_genesis && !config.sumeragi.contains_other_trusted_peers() {
         emitter.emit(Report::new(ConfigError::LonePeer).attach_printable("\
             Reason: the network consists from this one peer only (no `sumeragi.trusted_peers` provided).\n\
             Since `genesis.file` is not set, there is no way to receive the genesis block.\n\
@@ -766,6 +1196,60 @@ pub struct Args {
         num_args(0..=1),
     )]
     pub terminal_colors: bool,
+    /// Re-execute all stored blocks from genesis, verify the outcome and exit.
+    ///
+    /// Reports the first block which is no longer valid when re-executed,
+    /// e.g. after a change of the execution semantics.
+    #[arg(long)]
+    pub replay_verify: bool,
+    /// Check the configuration and the environment of the peer, print a report and exit.
+    ///
+    /// Covers the key pair, trusted peers and their reachability, disk space for Kura,
+    /// skew of the local clock and availability of the WASM runtime.
+    #[arg(long)]
+    pub doctor: bool,
+    #[command(flatten)]
+    pub state_transfer: StateTransferArgs,
+    /// Start a single peer network from a generated genesis with a well-known faucet account.
+    ///
+    /// Insecure, for local development only. Requires `genesis.allow_insecure_dev_mode`.
+    #[arg(long)]
+    pub dev: bool,
+    /// Path to the executor included into the genesis generated with `--dev`
+    #[arg(
+        long,
+        value_name("PATH"),
+        value_hint(clap::ValueHint::FilePath),
+        default_value("executor.wasm")
+    )]
+    pub dev_executor: PathBuf,
+}
+
+/// Portable export and import of the world state, see [`export_state`] and [`import_state`]
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct StateTransferArgs {
+    /// Export the world state to the file and exit.
+    ///
+    /// The stored chain is re-executed up to `--export-height` to reconstruct the state.
+    #[arg(long, value_name("PATH"), value_hint(clap::ValueHint::FilePath))]
+    pub export_state: Option<PathBuf>,
+    /// Height to export the world state at, the latest stored block by default
+    #[arg(long, value_name("HEIGHT"), requires("export_state"))]
+    pub export_height: Option<NonZeroUsize>,
+    /// Import the world state from the file into the state snapshot of this peer and exit.
+    ///
+    /// The peer has to store the blocks up to the exported height. The imported state
+    /// is verified against the stored block and the digest recorded in the export.
+    #[arg(
+        long,
+        value_name("PATH"),
+        value_hint(clap::ValueHint::FilePath),
+        conflicts_with("export_state")
+    )]
+    pub import_state: Option<PathBuf>,
+    /// Encoding of the exported or imported state: `json` or `scale`
+    #[arg(long, value_name("FORMAT"), default_value_t)]
+    pub state_format: StateExportFormat,
 }
 
 #[cfg(test)]
@@ -862,7 +1346,12 @@ mod tests {
             let (config, _logger, genesis) = read_config_and_genesis(&Args {
                 config: Some(config_path),
                 terminal_colors: false,
+                replay_verify: false,
+                doctor: false,
+                state_transfer: StateTransferArgs::default(),
                 trace_config: false,
+                dev: false,
+                dev_executor: PathBuf::from("executor.wasm"),
             })
             .map_err(|report| eyre::eyre!("{report:?}"))?;
 
@@ -914,7 +1403,12 @@ mod tests {
             let report = read_config_and_genesis(&Args {
                 config: Some(config_path),
                 terminal_colors: false,
+                replay_verify: false,
+                doctor: false,
+                state_transfer: StateTransferArgs::default(),
                 trace_config: false,
+                dev: false,
+                dev_executor: PathBuf::from("executor.wasm"),
             })
             .unwrap_err();
 
@@ -925,6 +1419,82 @@ mod tests {
 
             Ok(())
         }
+
+        #[test]
+        fn dev_mode_is_rejected_unless_allowed() -> eyre::Result<()> {
+            // Given
+
+            let genesis_key_pair = KeyPair::random();
+            let config = config_factory(genesis_key_pair.public_key());
+
+            let dir = tempfile::tempdir()?;
+            std::fs::write(dir.path().join("config.toml"), toml::to_string(&config)?)?;
+            std::fs::write(dir.path().join("executor.wasm"), "")?;
+
+            // When & Then
+
+            let report = read_config_and_genesis(&Args {
+                config: Some(dir.path().join("config.toml")),
+                terminal_colors: false,
+                replay_verify: false,
+                doctor: false,
+                state_transfer: StateTransferArgs::default(),
+                trace_config: false,
+                dev: true,
+                dev_executor: dir.path().join("executor.wasm"),
+            })
+            .unwrap_err();
+
+            assert_contains!(
+                format!("{report:#}"),
+                "Development mode is not allowed by the configuration"
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn dev_mode_generates_genesis_when_allowed() -> eyre::Result<()> {
+            // Given
+
+            let genesis_key_pair = KeyPair::random();
+            let mut config = config_factory(genesis_key_pair.public_key());
+            iroha_config::base::toml::Writer::new(&mut config)
+                .write(["genesis", "allow_insecure_dev_mode"], true);
+
+            let dir = tempfile::tempdir()?;
+            std::fs::write(dir.path().join("config.toml"), toml::to_string(&config)?)?;
+            std::fs::write(dir.path().join("executor.wasm"), "")?;
+
+            // When
+
+            let (config, _logger, genesis) = read_config_and_genesis(&Args {
+                config: Some(dir.path().join("config.toml")),
+                terminal_colors: false,
+                replay_verify: false,
+                doctor: false,
+                state_transfer: StateTransferArgs::default(),
+                trace_config: false,
+                dev: true,
+                dev_executor: dir.path().join("executor.wasm"),
+            })
+            .map_err(|report| eyre::eyre!("{report:?}"))?;
+
+            // Then
+
+            let genesis = genesis.expect("genesis should be generated");
+            assert_ne!(&config.genesis.public_key, genesis_key_pair.public_key());
+            let authority = genesis
+                .0
+                .transactions()
+                .next()
+                .expect("genesis should contain transactions")
+                .value
+                .authority();
+            assert_eq!(authority.signatory(), &config.genesis.public_key);
+
+            Ok(())
+        }
     }
 
     #[test]
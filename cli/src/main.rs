@@ -3,6 +3,7 @@ use std::env;
 
 use clap::Parser;
 use error_stack::{IntoReportCompat, ResultExt};
+use iroha_crypto::ExposedPrivateKey;
 use irohad::{Args, Iroha};
 
 #[derive(thiserror::Error, Debug)]
@@ -13,8 +14,18 @@ enum MainError {
     Config,
     #[error("Could not initialize logger")]
     Logger,
+    #[error("Chain replay verification failed")]
+    ReplayVerify,
+    #[error("Peer diagnostics found problems")]
+    Doctor,
+    #[error("State export failed")]
+    StateExport,
+    #[error("State import failed")]
+    StateImport,
     #[error("Could not start Iroha")]
     IrohaStart,
+    #[error("Could not shut Iroha down gracefully")]
+    IrohaShutdown,
 }
 
 #[tokio::main]
@@ -50,15 +61,84 @@ async fn main() -> error_stack::Result<(), MainError> {
         "Hyperledgerいろは2にようこそ！(translation) Welcome to Hyperledger Iroha!"
     );
 
+    if args.replay_verify {
+        let report = irohad::replay_verify(&config).change_context(MainError::ReplayVerify)?;
+        iroha_logger::info!(
+            height = report.height,
+            digest = %report.digest,
+            "Re-executed chain matches the stored one"
+        );
+        return Ok(());
+    }
+
+    let state_transfer = &args.state_transfer;
+    if let Some(path) = &state_transfer.export_state {
+        let report = irohad::export_state(
+            &config,
+            state_transfer.export_height,
+            state_transfer.state_format,
+            path,
+        )
+        .change_context(MainError::StateExport)?;
+        iroha_logger::info!(
+            height = report.height,
+            digest = %report.digest,
+            path = %path.display(),
+            "World state is exported"
+        );
+        return Ok(());
+    }
+
+    if let Some(path) = &state_transfer.import_state {
+        let report = irohad::import_state(&config, state_transfer.state_format, path)
+            .change_context(MainError::StateImport)?;
+        iroha_logger::info!(
+            height = report.height,
+            digest = %report.digest,
+            "World state is imported into the snapshot, it's loaded on the next start"
+        );
+        return Ok(());
+    }
+
+    if args.doctor {
+        let report = irohad::doctor::diagnose(&config);
+        print!("{report}");
+        if !report.is_healthy() {
+            return Err(error_stack::Report::new(MainError::Doctor));
+        }
+        return Ok(());
+    }
+
+    if args.dev {
+        let faucet_key_pair = irohad::dev::faucet_key_pair();
+        iroha_logger::warn!(
+            genesis_public_key = %config.genesis.public_key,
+            faucet_account = %irohad::dev::faucet_account_id(),
+            faucet_private_key = %ExposedPrivateKey(faucet_key_pair.private_key().clone()),
+            coin = %irohad::dev::coin_id(),
+            "Running in insecure development mode with a generated genesis. Never use it in production!"
+        );
+    }
+
     if genesis.is_some() {
         iroha_logger::debug!("Submitting genesis.");
     }
 
-    Iroha::start_network(config, genesis, logger)
+    let (run, iroha) = Iroha::start_network(config, genesis, logger)
         .await
-        .change_context(MainError::IrohaStart)?
-        .0
-        .await;
+        .change_context(MainError::IrohaStart)?;
+
+    run.await;
+
+    if let Err(report) = iroha
+        .shutdown()
+        .await
+        .change_context(MainError::IrohaShutdown)
+    {
+        // Abandoned work (e.g. a stuck thread) would otherwise block the runtime shutdown forever
+        eprintln!("{report:?}");
+        std::process::exit(1);
+    }
 
     Ok(())
 }
@@ -0,0 +1,186 @@
+//! Integration with the systemd service manager.
+//!
+//! Listen sockets can be [inherited](InheritedListeners) from the service manager (socket activation),
+//! so that the sockets stay open and connections queue up while the peer restarts.
+//! The peer reports readiness once it has caught up with the blocks of the other peers,
+//! so that a load balancer doesn't route clients to a peer serving a stale state.
+//!
+//! Both follow the protocols of `sd_listen_fds(3)` and `sd_notify(3)`.
+use std::{
+    env, io,
+    net::TcpListener,
+    os::{
+        fd::{BorrowedFd, FromRawFd as _, RawFd},
+        unix::net::UnixDatagram,
+    },
+};
+
+/// The first file descriptor passed by the service manager
+const LISTEN_FDS_START: RawFd = 3;
+
+/// Name of the inherited socket to serve Torii on, set with `FileDescriptorName=` of the socket unit
+pub const TORII_FD_NAME: &str = "torii";
+/// Name of the inherited socket to serve the administration API on
+pub const ADMIN_FD_NAME: &str = "admin";
+/// Name of the inherited socket to accept the connections of the other peers on
+pub const NETWORK_FD_NAME: &str = "p2p";
+
+/// Listen sockets inherited from the service manager.
+/// The listeners which weren't inherited are bound to the configured addresses as usual.
+#[derive(Debug, Default)]
+pub struct InheritedListeners {
+    /// Listener of Torii
+    pub torii: Option<TcpListener>,
+    /// Listener of the administration API
+    pub admin: Option<TcpListener>,
+    /// Listener of the peer-to-peer network
+    pub network: Option<TcpListener>,
+}
+
+impl InheritedListeners {
+    /// Take the listen sockets passed to this process, if any.
+    /// The environment describing them is cleared, so that child processes don't inherit it.
+    ///
+    /// # Errors
+    /// If the environment is malformed, or a passed descriptor is not a TCP listener.
+    pub fn from_env() -> io::Result<Self> {
+        let listen_pid = env::var("LISTEN_PID").ok();
+        let listen_fds = env::var("LISTEN_FDS").ok();
+        let listen_fd_names = env::var("LISTEN_FDNAMES").ok();
+        for var in ["LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"] {
+            env::remove_var(var);
+        }
+
+        let (Some(listen_pid), Some(listen_fds)) = (listen_pid, listen_fds) else {
+            return Ok(Self::default());
+        };
+        if parse_var("LISTEN_PID", &listen_pid)? != std::process::id() {
+            // The sockets were passed to another process, e.g. to our parent
+            return Ok(Self::default());
+        }
+        let count: RawFd = parse_var("LISTEN_FDS", &listen_fds)?;
+        let names = listen_fd_names
+            .as_deref()
+            .map(|names| names.split(':').collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let mut listeners = Self::default();
+        for (i, fd) in (LISTEN_FDS_START..LISTEN_FDS_START + count).enumerate() {
+            let listener = take_listener(fd)?;
+            let slot = match names.get(i).copied() {
+                Some(TORII_FD_NAME) => &mut listeners.torii,
+                Some(ADMIN_FD_NAME) => &mut listeners.admin,
+                Some(NETWORK_FD_NAME) => &mut listeners.network,
+                name => {
+                    iroha_logger::warn!(
+                        fd,
+                        ?name,
+                        "Ignoring an inherited socket with an unknown name; expected one of `{TORII_FD_NAME}`, `{ADMIN_FD_NAME}` and `{NETWORK_FD_NAME}`"
+                    );
+                    continue;
+                }
+            };
+            if slot.replace(listener).is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("several sockets are named `{}`", names[i]),
+                ));
+            }
+        }
+
+        Ok(listeners)
+    }
+}
+
+fn parse_var<T: core::str::FromStr>(name: &str, value: &str) -> io::Result<T> {
+    value.parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{name}` is not a number: `{value}`"),
+        )
+    })
+}
+
+fn take_listener(fd: RawFd) -> io::Result<TcpListener> {
+    // SAFETY: the service manager passes the descriptors in the range starting at `LISTEN_FDS_START`,
+    // and this is the only place taking their ownership, since the environment is cleared after.
+    #[allow(unsafe_code)]
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    // The service manager doesn't close the passed descriptors on exec
+    rustix::io::fcntl_setfd(borrowed, rustix::io::FdFlags::CLOEXEC)?;
+    if !rustix::net::sockopt::get_socket_acceptconn(borrowed)? {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("inherited descriptor {fd} is not a listening socket"),
+        ));
+    }
+
+    // SAFETY: see above
+    #[allow(unsafe_code)]
+    let listener = unsafe { TcpListener::from_raw_fd(fd) };
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// Tell the service manager that the peer is ready to serve clients.
+///
+/// Does nothing if the peer isn't run by a service manager expecting notifications.
+pub fn notify_ready() {
+    notify("READY=1");
+}
+
+/// Tell the service manager that the peer is shutting down.
+pub fn notify_stopping() {
+    notify("STOPPING=1");
+}
+
+fn notify(state: &str) {
+    let Some(socket_path) = env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(error) = send_notification(&socket_path, state) {
+        iroha_logger::warn!(%error, state, "Failed to notify the service manager");
+    }
+}
+
+fn send_notification(socket_path: &std::ffi::OsStr, state: &str) -> io::Result<()> {
+    use std::os::unix::ffi::OsStrExt as _;
+
+    let socket = UnixDatagram::unbound()?;
+    match socket_path.as_bytes() {
+        #[cfg(target_os = "linux")]
+        [b'@', name @ ..] => {
+            use std::os::{linux::net::SocketAddrExt as _, unix::net::SocketAddr};
+
+            socket.send_to_addr(state.as_bytes(), &SocketAddr::from_abstract_name(name)?)?;
+        }
+        _ => {
+            socket.send_to(state.as_bytes(), socket_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_is_sent_to_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("notify.sock");
+        let service_manager = UnixDatagram::bind(&socket_path).unwrap();
+
+        send_notification(socket_path.as_os_str(), "READY=1").unwrap();
+
+        let mut buf = [0; 16];
+        let len = service_manager.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+    }
+
+    #[test]
+    fn malformed_environment_is_rejected() {
+        assert!(parse_var::<u32>("LISTEN_PID", "self").is_err());
+        assert_eq!(parse_var::<RawFd>("LISTEN_FDS", "2").unwrap(), 2);
+    }
+}
@@ -0,0 +1,402 @@
+//! Load balancing across the peers of a network.
+//!
+//! [`PeerSet`] polls `/status` of every endpoint it's configured with in the background
+//! and keeps track of how far each peer lags behind the highest block seen across the set
+//! and how often requests to the peer fail recently. Queries are routed to the freshest
+//! healthy peer, so that they don't observe a stale state, while transactions can be sent
+//! to any healthy peer, since they are gossiped to the others anyway.
+//!
+//! A peer is healthy if its latest status was received, it's at most
+//! [`max_lag`](PeerSetBuilder::max_lag) blocks behind and less than half of the recent
+//! requests to it failed.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use eyre::{eyre, Result};
+use iroha_logger::prelude::*;
+use rand::Rng;
+use url::Url;
+
+use crate::{client::Client, crypto::HashOf, data_model::prelude::*};
+
+/// Default interval between the polls of `/status` of the peers
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Default number of blocks a peer can lag behind and still serve queries
+pub const DEFAULT_MAX_LAG: u64 = 2;
+/// Weight of the latest request in the recent error rate of a peer
+const ERROR_RATE_SMOOTHING: f64 = 0.2;
+/// Error rate starting from which a peer isn't used
+const MAX_ERROR_RATE: f64 = 0.5;
+
+/// Builder of a [`PeerSet`], see [`PeerSet::builder`]
+#[derive(Debug)]
+pub struct PeerSetBuilder {
+    client: Client,
+    endpoints: Vec<Url>,
+    poll_interval: Duration,
+    max_lag: u64,
+}
+
+impl PeerSetBuilder {
+    /// Balance the requests to the peer with the given Torii `url` too
+    #[must_use]
+    pub fn endpoint(mut self, mut url: Url) -> Self {
+        // Like the configured Torii URL, the endpoint is joined with the paths of the API
+        if !url.path().ends_with('/') {
+            let path = url.path().to_owned() + "/";
+            url.set_path(&path);
+        }
+        if !self.endpoints.contains(&url) {
+            self.endpoints.push(url);
+        }
+        self
+    }
+
+    /// Poll `/status` of the peers every `poll_interval`. [`DEFAULT_POLL_INTERVAL`] by default.
+    #[must_use]
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Don't use the peers which are more than `max_lag` blocks behind. [`DEFAULT_MAX_LAG`] by default.
+    #[must_use]
+    pub fn max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = max_lag;
+        self
+    }
+
+    /// Poll the status of the peers and start following it in the background
+    pub fn start(self) -> PeerSet {
+        let inner = Arc::new(Inner {
+            endpoints: self
+                .endpoints
+                .into_iter()
+                .map(|url| RwLock::new(Endpoint::new(url)))
+                .collect(),
+            client: self.client,
+            poll_interval: self.poll_interval,
+            max_lag: self.max_lag,
+            stopped: AtomicBool::new(false),
+        });
+        inner.poll();
+
+        thread::spawn({
+            let inner = Arc::clone(&inner);
+            move || inner.follow_status()
+        });
+
+        PeerSet { inner }
+    }
+}
+
+/// Endpoints of the peers of a network weighted by their health, see the [module documentation](self).
+///
+/// The status of the peers is polled until the set is dropped.
+#[derive(Debug)]
+pub struct PeerSet {
+    inner: Arc<Inner>,
+}
+
+impl PeerSet {
+    /// Start building a set of the peers reachable with `client`.
+    /// The Torii URL of `client` is the first endpoint of the set.
+    pub fn builder(client: Client) -> PeerSetBuilder {
+        let endpoints = vec![client.torii_url.clone()];
+        PeerSetBuilder {
+            client,
+            endpoints,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            max_lag: DEFAULT_MAX_LAG,
+        }
+    }
+
+    /// Client sending its requests to the freshest healthy peer, to run queries with
+    ///
+    /// # Errors
+    /// If none of the peers is healthy
+    pub fn query_client(&self) -> Result<Client> {
+        let health = self.health();
+        let endpoint = freshest(&health, self.inner.max_lag)
+            .ok_or_else(|| eyre!("None of the peers is healthy: {health:?}"))?;
+        Ok(self.inner.client_for(&health[endpoint].url))
+    }
+
+    /// Client sending its requests to a healthy peer, picked at random with the peers
+    /// lagging less and failing less preferred, to submit transactions with
+    ///
+    /// # Errors
+    /// If none of the peers is healthy
+    pub fn transaction_client(&self) -> Result<Client> {
+        let health = self.health();
+        let endpoint = weighted(&health, self.inner.max_lag, rand::thread_rng().gen())
+            .ok_or_else(|| eyre!("None of the peers is healthy: {health:?}"))?;
+        Ok(self.inner.client_for(&health[endpoint].url))
+    }
+
+    /// Submit `transaction` to a healthy peer, failing over to the other healthy peers
+    /// if the submission fails
+    ///
+    /// # Errors
+    /// If none of the peers is healthy or the transaction couldn't be submitted to any of them
+    pub fn submit_transaction(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<HashOf<SignedTransaction>> {
+        let mut health = self.health();
+        let mut last_error = None;
+        while let Some(endpoint) = weighted(&health, self.inner.max_lag, rand::thread_rng().gen()) {
+            let url = health.swap_remove(endpoint).url;
+            match self.inner.client_for(&url).submit_transaction(transaction) {
+                Ok(hash) => {
+                    self.report(&url, true);
+                    return Ok(hash);
+                }
+                Err(error) => {
+                    warn!(%url, ?error, "Failed to submit transaction, trying another peer");
+                    self.report(&url, false);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| eyre!("None of the peers is healthy")))
+    }
+
+    /// Account for the outcome of a request sent to the peer with the given Torii `url`
+    /// with a client of this set, so that failing peers are avoided
+    pub fn report(&self, url: &Url, success: bool) {
+        if let Some(endpoint) = self.inner.endpoint(url) {
+            endpoint
+                .write()
+                .expect("Lock isn't poisoned")
+                .record(success);
+        }
+    }
+
+    /// Health of the endpoints as of the latest poll
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let endpoints = self
+            .inner
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.read().expect("Lock isn't poisoned").clone())
+            .collect::<Vec<_>>();
+        let top = endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.height)
+            .max()
+            .unwrap_or_default();
+
+        endpoints
+            .into_iter()
+            .map(|endpoint| EndpointHealth {
+                lag: endpoint.height.map(|height| top.saturating_sub(height)),
+                url: endpoint.url,
+                error_rate: endpoint.error_rate,
+            })
+            .collect()
+    }
+}
+
+impl Drop for PeerSet {
+    fn drop(&mut self) {
+        self.inner.stopped.store(true, Ordering::Release);
+    }
+}
+
+/// Health of an endpoint of a [`PeerSet`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointHealth {
+    /// Torii URL of the peer
+    pub url: Url,
+    /// Number of blocks the peer is behind the highest peer of the set,
+    /// `None` if the latest status of the peer wasn't received
+    pub lag: Option<u64>,
+    /// Share of the recent requests to the peer which failed, from 0 to 1
+    pub error_rate: f64,
+}
+
+impl EndpointHealth {
+    /// Whether the peer can serve requests
+    pub fn is_healthy(&self, max_lag: u64) -> bool {
+        self.lag.is_some_and(|lag| lag <= max_lag) && self.error_rate < MAX_ERROR_RATE
+    }
+
+    /// Preference of the peer among the healthy ones
+    #[allow(clippy::cast_precision_loss)]
+    fn weight(&self) -> f64 {
+        (1.0 - self.error_rate) / (1 + self.lag.unwrap_or_default()) as f64
+    }
+}
+
+/// Index of the healthy endpoint with the least lag, and the least error rate among those
+fn freshest(health: &[EndpointHealth], max_lag: u64) -> Option<usize> {
+    health
+        .iter()
+        .enumerate()
+        .filter(|(_, endpoint)| endpoint.is_healthy(max_lag))
+        .min_by(|(_, a), (_, b)| {
+            a.lag
+                .cmp(&b.lag)
+                .then(a.error_rate.total_cmp(&b.error_rate))
+        })
+        .map(|(i, _)| i)
+}
+
+/// Index of a healthy endpoint picked by `random` from `[0, 1)` in proportion to the weights
+fn weighted(health: &[EndpointHealth], max_lag: u64, random: f64) -> Option<usize> {
+    let healthy = health
+        .iter()
+        .enumerate()
+        .filter(|(_, endpoint)| endpoint.is_healthy(max_lag))
+        .collect::<Vec<_>>();
+    let total = healthy
+        .iter()
+        .map(|(_, endpoint)| endpoint.weight())
+        .sum::<f64>();
+
+    let mut point = random * total;
+    for &(i, endpoint) in &healthy {
+        point -= endpoint.weight();
+        if point < 0.0 {
+            return Some(i);
+        }
+    }
+    // Rounding errors can leave the point past the last weight
+    healthy.last().map(|(i, _)| *i)
+}
+
+#[derive(Debug, Clone)]
+struct Endpoint {
+    url: Url,
+    /// Height of the peer as of the latest poll, `None` if the poll failed
+    height: Option<u64>,
+    error_rate: f64,
+}
+
+impl Endpoint {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            height: None,
+            error_rate: 0.0,
+        }
+    }
+
+    fn record(&mut self, success: bool) {
+        let error = if success { 0.0 } else { 1.0 };
+        self.error_rate += ERROR_RATE_SMOOTHING * (error - self.error_rate);
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    client: Client,
+    poll_interval: Duration,
+    max_lag: u64,
+    endpoints: Vec<RwLock<Endpoint>>,
+    stopped: AtomicBool,
+}
+
+impl Inner {
+    fn client_for(&self, url: &Url) -> Client {
+        let mut client = self.client.clone();
+        client.torii_url = url.clone();
+        client
+    }
+
+    fn endpoint(&self, url: &Url) -> Option<&RwLock<Endpoint>> {
+        self.endpoints
+            .iter()
+            .find(|endpoint| endpoint.read().expect("Lock isn't poisoned").url == *url)
+    }
+
+    fn poll(&self) {
+        for endpoint in &self.endpoints {
+            let url = endpoint.read().expect("Lock isn't poisoned").url.clone();
+            let status = self.client_for(&url).get_status();
+
+            let mut endpoint = endpoint.write().expect("Lock isn't poisoned");
+            endpoint.record(status.is_ok());
+            endpoint.height = match status {
+                Ok(status) => Some(status.blocks),
+                Err(error) => {
+                    debug!(%url, ?error, "Failed to get the status of the peer");
+                    None
+                }
+            };
+        }
+    }
+
+    fn follow_status(&self) {
+        loop {
+            thread::sleep(self.poll_interval);
+            if self.stopped.load(Ordering::Acquire) {
+                break;
+            }
+            self.poll();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(port: u16, lag: Option<u64>, error_rate: f64) -> EndpointHealth {
+        EndpointHealth {
+            url: format!("http://127.0.0.1:{port}/").parse().unwrap(),
+            lag,
+            error_rate,
+        }
+    }
+
+    #[test]
+    fn queries_are_routed_to_the_freshest_peer() {
+        let health = [
+            endpoint(8080, Some(1), 0.0),
+            endpoint(8081, Some(0), 0.3),
+            endpoint(8082, Some(0), 0.1),
+            endpoint(8083, None, 0.0),
+        ];
+        assert_eq!(freshest(&health, DEFAULT_MAX_LAG), Some(2));
+
+        let failing = [endpoint(8080, Some(0), 0.6), endpoint(8081, Some(5), 0.0)];
+        assert_eq!(freshest(&failing, DEFAULT_MAX_LAG), None);
+    }
+
+    #[test]
+    fn transactions_are_sent_to_healthy_peers_by_weight() {
+        let health = [
+            endpoint(8080, Some(0), 0.0),
+            endpoint(8081, Some(3), 0.0),
+            endpoint(8082, Some(1), 0.0),
+        ];
+        // Weights are 1 and 0.5, the lagging peer is excluded
+        assert_eq!(weighted(&health, DEFAULT_MAX_LAG, 0.0), Some(0));
+        assert_eq!(weighted(&health, DEFAULT_MAX_LAG, 0.6), Some(0));
+        assert_eq!(weighted(&health, DEFAULT_MAX_LAG, 0.7), Some(2));
+        assert_eq!(weighted(&health, DEFAULT_MAX_LAG, 0.999_999), Some(2));
+        assert_eq!(weighted(&[], DEFAULT_MAX_LAG, 0.5), None);
+    }
+
+    #[test]
+    fn error_rate_follows_recent_requests() {
+        let mut endpoint = Endpoint::new("http://127.0.0.1:8080/".parse().unwrap());
+        for _ in 0..4 {
+            endpoint.record(false);
+        }
+        assert!(endpoint.error_rate > MAX_ERROR_RATE);
+        for _ in 0..4 {
+            endpoint.record(true);
+        }
+        assert!(endpoint.error_rate < MAX_ERROR_RATE);
+    }
+}
@@ -23,8 +23,8 @@ use rand::Rng;
 use url::Url;
 
 use self::{blocks_api::AsyncBlockStream, events_api::AsyncEventStream};
-pub use crate::query::QueryError;
 use crate::{
+    composer::TransactionComposer,
     config::Config,
     crypto::{HashOf, KeyPair},
     data_model::{
@@ -35,11 +35,17 @@ use crate::{
         },
         isi::Instruction,
         prelude::*,
-        transaction::TransactionBuilder,
+        query::error::QueryExecutionFail,
+        transaction::{error::TransactionRejectionReason, TransactionBuilder},
         ChainId,
     },
     http::{Method as HttpMethod, RequestBuilder, Response, StatusCode},
     http_default::{self, DefaultRequestBuilder, WebSocketError, WebSocketMessage},
+    signer::{self, TransactionSigner},
+};
+pub use crate::{
+    http_default::{ConnectionPool, RequestObserver},
+    query::QueryError,
 };
 
 const APPLICATION_JSON: &str = "application/json";
@@ -52,9 +58,19 @@ pub type QueryResult<T> = core::result::Result<T, QueryError>;
 struct TransactionResponseHandler;
 
 impl TransactionResponseHandler {
-    fn handle(resp: &Response<Vec<u8>>) -> Result<()> {
+    fn handle(resp: &Response<Vec<u8>>) -> Result<SubmissionStatus> {
         if resp.status() == StatusCode::OK {
-            Ok(())
+            SubmissionStatus::decode_all(&mut resp.body().as_slice())
+                .wrap_err("Failed to decode submission status")
+        } else if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            let queue_full = QueueFull::decode_all(&mut resp.body().as_slice())
+                .wrap_err("Failed to decode queue full response")?;
+            Err(queue_full.into())
+        } else if let Some(degraded) = (resp.status() == StatusCode::SERVICE_UNAVAILABLE)
+            .then(|| PeerDegraded::decode_all(&mut resp.body().as_slice()).ok())
+            .flatten()
+        {
+            Err(degraded.into())
         } else {
             Err(
                 ResponseReport::with_msg("Unexpected transaction response", resp)
@@ -142,6 +158,24 @@ pub struct Client {
     /// If `true` add nonce, which makes different hashes for
     /// transactions which occur repeatedly and/or simultaneously
     pub add_transaction_nonce: bool,
+    /// Version of the transactions built by the client,
+    /// see [`Client::negotiate_transaction_version`]
+    pub transaction_version: TransactionVersion,
+    /// Keep-alive connections to the peer shared by the clones of the client
+    pub http_pool: ConnectionPool,
+}
+
+/// Inclusion of a transaction into the blockchain, see [`Client::await_confirmations`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Confirmation {
+    /// Height of the block containing the transaction
+    pub block_height: NonZeroU64,
+    /// Hash of the block containing the transaction
+    pub block_hash: HashOf<SignedBlock>,
+    /// Number of blocks committed on top of the block containing the transaction
+    pub confirmations: u64,
+    /// Reason of the rejection if the transaction was rejected
+    pub error: Option<TransactionRejectionReason>,
 }
 
 /// Representation of `Iroha` client.
@@ -166,6 +200,7 @@ impl Client {
             transaction_add_nonce,
             transaction_ttl,
             transaction_status_timeout,
+            http,
         }: Config,
         mut headers: HashMap<String, String>,
     ) -> Self {
@@ -185,6 +220,8 @@ impl Client {
             account,
             headers,
             add_transaction_nonce: transaction_add_nonce,
+            transaction_version: TransactionVersion::default(),
+            http_pool: ConnectionPool::new(http),
         }
     }
 
@@ -197,7 +234,43 @@ impl Client {
         instructions: Exec,
         metadata: Metadata,
     ) -> SignedTransaction {
-        let tx_builder = TransactionBuilder::new(self.chain.clone(), self.account.clone());
+        self.build_unsigned_transaction(instructions, metadata)
+            .sign(self.key_pair.private_key())
+    }
+
+    /// Builds transaction out of supplied instructions on behalf of `authority`, which granted
+    /// a [`ProxyToken`] to the account of the client. The transaction is signed by the client.
+    pub fn build_proxy_transaction<Exec: Into<Executable>>(
+        &self,
+        authority: AccountId,
+        instructions: Exec,
+        metadata: Metadata,
+    ) -> SignedTransaction {
+        let mut tx_builder = self.build_unsigned_transaction_for(authority, instructions, metadata);
+        tx_builder.set_submitter(self.account.clone());
+        // Transactions of version 1 can't name the submitter
+        tx_builder.set_version(TransactionVersion::V2);
+
+        tx_builder.sign(self.key_pair.private_key())
+    }
+
+    /// Builds transaction out of supplied instructions or wasm without signing it,
+    /// so that it can be signed elsewhere, see [`crate::signer`].
+    pub fn build_unsigned_transaction<Exec: Into<Executable>>(
+        &self,
+        instructions: Exec,
+        metadata: Metadata,
+    ) -> TransactionBuilder {
+        self.build_unsigned_transaction_for(self.account.clone(), instructions, metadata)
+    }
+
+    fn build_unsigned_transaction_for<Exec: Into<Executable>>(
+        &self,
+        authority: AccountId,
+        instructions: Exec,
+        metadata: Metadata,
+    ) -> TransactionBuilder {
+        let tx_builder = TransactionBuilder::new(self.chain.clone(), authority);
 
         let mut tx_builder = match instructions.into() {
             Executable::Instructions(instructions) => tx_builder.with_instructions(instructions),
@@ -211,10 +284,9 @@ impl Client {
             let nonce = rand::thread_rng().gen::<NonZeroU32>();
             tx_builder.set_nonce(nonce);
         };
+        tx_builder.set_version(self.transaction_version);
 
-        tx_builder
-            .with_metadata(metadata)
-            .sign(self.key_pair.private_key())
+        tx_builder.with_metadata(metadata)
     }
 
     /// Signs transaction
@@ -275,25 +347,226 @@ impl Client {
         self.submit_transaction(&self.build_transaction(instructions, metadata))
     }
 
+    /// Sign `transaction` with `signer` and submit it.
+    /// Returns submitted transaction's hash or error string.
+    ///
+    /// # Errors
+    /// - If the signer fails or isn't the authority of the transaction
+    /// - Fails if sending transaction to peer fails or if it response with error
+    pub fn submit_signed_by(
+        &self,
+        transaction: TransactionBuilder,
+        signer: &impl TransactionSigner,
+    ) -> Result<HashOf<SignedTransaction>> {
+        let transaction = signer::sign_transaction(transaction, signer)?;
+        self.submit_transaction(&transaction)
+    }
+
+    /// Start composing a transaction out of instructions given in an arbitrary order,
+    /// see [`TransactionComposer`].
+    pub fn transaction_composer(&self) -> TransactionComposer {
+        TransactionComposer::new(self.account.clone())
+    }
+
     /// Submit a prebuilt transaction.
     /// Returns submitted transaction's hash or error string.
     ///
     /// # Errors
-    /// Fails if sending transaction to peer fails or if it response with error
+    /// Fails if sending transaction to peer fails or if it response with error.
+    /// If the queue of the peer is full, the error can be downcast to [`QueueFull`],
+    /// which tells when to submit the transaction again.
+    /// If the peer is partitioned from the network, the error can be downcast to [`PeerDegraded`]
+    /// and the transaction should be submitted to another peer.
     pub fn submit_transaction(
         &self,
         transaction: &SignedTransaction,
     ) -> Result<HashOf<SignedTransaction>> {
+        self.submit_transaction_with_status(transaction)?;
+        Ok(transaction.hash())
+    }
+
+    /// Submit a prebuilt transaction and return its status.
+    ///
+    /// Submitting a transaction the peer already knows of isn't an error, the peer reports
+    /// whether the transaction is [queued](SubmissionStatus::Queued) or stored in a block instead.
+    ///
+    /// # Errors
+    /// Same as [`Self::submit_transaction`]
+    pub fn submit_transaction_with_status(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<SubmissionStatus> {
         iroha_logger::trace!(tx=?transaction, "Submitting");
         let (req, hash) = self.prepare_transaction_request::<DefaultRequestBuilder>(transaction);
         let response = req
             .build()?
-            .send()
+            .send_with(&self.http_pool)
             .wrap_err_with(|| format!("Failed to send transaction with hash {hash:?}"))?;
+        TransactionResponseHandler::handle(&response)
+    }
+
+    /// Submit a transaction which was signed and SCALE-encoded elsewhere, e.g. by an SDK
+    /// written in another language. The bytes are sent to the peer as they are.
+    ///
+    /// # Errors
+    /// - If the bytes are not a valid versioned transaction; the error contains the offset
+    ///   at which decoding failed and the type which was expected there
+    /// - Fails if sending transaction to peer fails or if it response with error
+    pub fn submit_raw(&self, transaction: &[u8]) -> Result<HashOf<SignedTransaction>> {
+        let hash =
+            SignedTransaction::decode_all_versioned_with_limits(transaction, DecodeLimits::DEFAULT)
+                .wrap_err("Failed to decode transaction")?
+                .hash();
+        iroha_logger::trace!(tx=?hash, "Submitting raw");
+        let response = DefaultRequestBuilder::new(
+            HttpMethod::POST,
+            join_torii_url(&self.torii_url, torii_uri::TRANSACTION),
+        )
+        .headers(self.headers.clone())
+        .body(transaction.to_vec())
+        .build()?
+        .send_with(&self.http_pool)
+        .wrap_err_with(|| format!("Failed to send transaction with hash {hash:?}"))?;
         TransactionResponseHandler::handle(&response)?;
         Ok(hash)
     }
 
+    /// Predict the outcome of the transaction without signing or submitting it.
+    /// The peer executes the transaction on top of its latest state and discards the changes.
+    ///
+    /// # Errors
+    /// Fails if sending the transaction to the peer fails or there is an error in the response
+    pub fn simulate_transaction(
+        &self,
+        transaction: &TransactionBuilder,
+    ) -> Result<TransactionSimulation> {
+        self.simulate(transaction, false)
+    }
+
+    /// Simulate the transaction like [`Self::simulate_transaction`], recording every
+    /// executed instruction together with its events and error into
+    /// [`TransactionSimulation::trace`]. Use [`crate::trace::pretty`] to print the trace.
+    ///
+    /// # Errors
+    /// Fails if sending the transaction to the peer fails or there is an error in the response
+    pub fn trace_transaction(
+        &self,
+        transaction: &TransactionBuilder,
+    ) -> Result<TransactionSimulation> {
+        self.simulate(transaction, true)
+    }
+
+    fn simulate(
+        &self,
+        transaction: &TransactionBuilder,
+        trace: bool,
+    ) -> Result<TransactionSimulation> {
+        let response = DefaultRequestBuilder::new(
+            HttpMethod::POST,
+            join_torii_url(&self.torii_url, torii_uri::TRANSACTION_SIMULATION),
+        )
+        .headers(&self.headers)
+        .param("trace", &trace)
+        .body(transaction.encode())
+        .build()?
+        .send_with(&self.http_pool)
+        .wrap_err("Failed to send transaction for simulation")?;
+
+        if response.status() != StatusCode::OK {
+            return Err(eyre!(
+                "Failed to simulate transaction with HTTP status: {}. {}",
+                response.status(),
+                std::str::from_utf8(response.body()).unwrap_or(""),
+            ));
+        }
+        TransactionSimulation::decode_all(&mut response.body().as_slice())
+            .wrap_err("Failed to decode body")
+    }
+
+    /// Withdraw the transaction with the given `hash` submitted by this client,
+    /// as long as it's still in the queue of the peer
+    ///
+    /// # Errors
+    /// Fails if sending the request fails or the peer couldn't cancel the transaction
+    pub fn cancel_transaction(&self, hash: HashOf<SignedTransaction>) -> Result<()> {
+        let cancellation = CancelTransaction::new(hash).sign(self.key_pair.private_key());
+        let response = DefaultRequestBuilder::new(
+            HttpMethod::POST,
+            join_torii_url(&self.torii_url, torii_uri::CANCEL_TRANSACTION),
+        )
+        .headers(&self.headers)
+        .body(cancellation.encode())
+        .build()?
+        .send_with(&self.http_pool)
+        .wrap_err("Failed to send transaction cancellation")?;
+
+        if response.status() != StatusCode::OK {
+            return Err(eyre!(
+                "Failed to cancel transaction with HTTP status: {}. {}",
+                response.status(),
+                std::str::from_utf8(response.body()).unwrap_or(""),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the transactions of `authority` which the peer holds in its queue
+    /// until the time they are scheduled for.
+    ///
+    /// # Errors
+    /// Fails if sending the request fails or there is an error in the response
+    pub fn scheduled_transactions(&self, authority: &AccountId) -> Result<Vec<SignedTransaction>> {
+        let response = DefaultRequestBuilder::new(
+            HttpMethod::POST,
+            join_torii_url(&self.torii_url, torii_uri::SCHEDULED_TRANSACTIONS),
+        )
+        .headers(&self.headers)
+        .body(authority.encode())
+        .build()?
+        .send_with(&self.http_pool)
+        .wrap_err("Failed to request scheduled transactions")?;
+
+        if response.status() != StatusCode::OK {
+            return Err(eyre!(
+                "Failed to get scheduled transactions with HTTP status: {}. {}",
+                response.status(),
+                std::str::from_utf8(response.body()).unwrap_or(""),
+            ));
+        }
+        Vec::<SignedTransaction>::decode_all(&mut response.body().as_slice())
+            .wrap_err("Failed to decode body")
+    }
+
+    /// Register a webhook of the account of the client, get its delivery status or unregister it.
+    ///
+    /// The peer posts the events matching the filters of the webhook to its URL, signing every
+    /// notification with the secret of the webhook. The same restrictions on the filters apply
+    /// as for [`Self::listen_for_events`].
+    ///
+    /// # Errors
+    /// Fails if sending the request fails or there is an error in the response
+    pub fn webhook(&self, action: WebhookAction) -> Result<WebhookStatus> {
+        let request = WebhookRequest::new(self.account.clone(), action).sign(&self.key_pair);
+        let response = DefaultRequestBuilder::new(
+            HttpMethod::POST,
+            join_torii_url(&self.torii_url, torii_uri::WEBHOOKS),
+        )
+        .headers(&self.headers)
+        .body(request.encode())
+        .build()?
+        .send_with(&self.http_pool)
+        .wrap_err("Failed to send webhook request")?;
+
+        if response.status() != StatusCode::OK {
+            return Err(eyre!(
+                "Webhook request failed with HTTP status: {}. {}",
+                response.status(),
+                std::str::from_utf8(response.body()).unwrap_or(""),
+            ));
+        }
+        WebhookStatus::decode_all(&mut response.body().as_slice()).wrap_err("Failed to decode body")
+    }
+
     /// Submit the prebuilt transaction and wait until it is either rejected or committed.
     /// If rejected, return the rejection reason.
     ///
@@ -304,6 +577,7 @@ impl Client {
         transaction: &SignedTransaction,
     ) -> Result<HashOf<SignedTransaction>> {
         let (init_sender, init_receiver) = tokio::sync::oneshot::channel();
+        let (status_sender, status_receiver) = tokio::sync::oneshot::channel();
         let hash = transaction.hash();
 
         thread::scope(|spawner| {
@@ -313,12 +587,14 @@ impl Client {
                     .blocking_recv()
                     .wrap_err("Failed to receive init message.")?
                 {
-                    self.submit_transaction(transaction)?;
+                    let status = self.submit_transaction_with_status(transaction)?;
+                    let _send_result = status_sender.send(status);
                 }
                 Ok(())
             });
 
-            let confirmation_res = self.listen_for_tx_confirmation(init_sender, hash);
+            let confirmation_res =
+                self.listen_for_tx_confirmation(init_sender, status_receiver, hash);
 
             match submitter_handle.join() {
                 Ok(Ok(())) => confirmation_res,
@@ -331,6 +607,7 @@ impl Client {
     fn listen_for_tx_confirmation(
         &self,
         init_sender: tokio::sync::oneshot::Sender<bool>,
+        status_receiver: tokio::sync::oneshot::Receiver<SubmissionStatus>,
         hash: HashOf<SignedTransaction>,
     ) -> Result<HashOf<SignedTransaction>> {
         let deadline = tokio::time::Instant::now() + self.transaction_status_timeout;
@@ -357,10 +634,23 @@ impl Client {
                 event_iterator_result?
             };
 
-            let result = tokio::time::timeout_at(
-                deadline,
-                Self::listen_for_tx_confirmation_loop(&mut event_iterator, hash),
-            )
+            // No events are emitted for a resubmitted transaction which is already in a block
+            let known_outcome = async {
+                match status_receiver.await {
+                    Ok(SubmissionStatus::Committed(_)) => Ok(hash),
+                    Ok(SubmissionStatus::Rejected(rejected)) => Err(rejected.reason.into()),
+                    Ok(SubmissionStatus::Accepted | SubmissionStatus::Queued) => {
+                        std::future::pending().await
+                    }
+                    Err(_) => Err(eyre!("Transaction wasn't submitted")),
+                }
+            };
+            let result = tokio::time::timeout_at(deadline, async {
+                tokio::select! {
+                    result = Self::listen_for_tx_confirmation_loop(&mut event_iterator, hash) => result,
+                    result = known_outcome => result,
+                }
+            })
             .await
             .map_err(Into::into)
             .and_then(std::convert::identity);
@@ -388,6 +678,9 @@ impl Client {
                                 return Err((Clone::clone(&**reason)).into());
                             }
                             TransactionStatus::Expired => return Err(eyre!("Transaction expired")),
+                            TransactionStatus::Cancelled => {
+                                return Err(eyre!("Transaction cancelled"))
+                            }
                         }
                     }
                     PipelineEventBox::Block(block_event) => {
@@ -406,6 +699,87 @@ impl Client {
         ))
     }
 
+    /// Wait until the block containing the transaction with the given `hash`
+    /// has at least `depth` blocks committed on top of it.
+    ///
+    /// With `depth` of `0` return as soon as the transaction is committed.
+    /// Note that blocks are only created when there are transactions to commit,
+    /// so on an idle network the wait may take indefinitely long.
+    ///
+    /// # Errors
+    /// - Fails if the status of the peer or the transaction can't be queried
+    /// - Forwards from [`Self::listen_for_blocks_async`]
+    /// - Block stream is closed before the transaction gets `depth` confirmations
+    pub fn await_confirmations(
+        &self,
+        hash: HashOf<SignedTransaction>,
+        depth: u64,
+    ) -> Result<Confirmation> {
+        let height = self.confirmation_search_start(hash)?;
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        rt.block_on(async {
+            let mut block_stream = self.listen_for_blocks_async(height).await?;
+            let result = Self::await_confirmations_loop(&mut block_stream, hash, depth).await;
+            block_stream.close().await;
+            result
+        })
+    }
+
+    /// Height of the block to start searching the transaction with the given `hash` from
+    fn confirmation_search_start(&self, hash: HashOf<SignedTransaction>) -> Result<NonZeroU64> {
+        // NOTE: status is requested first so that the transaction committed
+        // after it is either found by the query or streamed
+        let committed_height = self.get_status()?.blocks;
+
+        match self.query_single(transaction::by_hash(hash)) {
+            Ok(output) => {
+                let header = self.query_single(block::header_by_hash(*output.block_hash()))?;
+                Ok(header.height())
+            }
+            Err(QueryError::Validation(ValidationFail::QueryFailed(QueryExecutionFail::Find(
+                _,
+            )))) => Ok(NonZeroU64::new(committed_height).unwrap_or(NonZeroU64::MIN)),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn await_confirmations_loop(
+        block_stream: &mut AsyncBlockStream,
+        hash: HashOf<SignedTransaction>,
+        depth: u64,
+    ) -> Result<Confirmation> {
+        let mut confirmation = None::<Confirmation>;
+
+        while let Some(block) = block_stream.next().await {
+            let block = block?;
+            let height = block.header().height();
+
+            if let Some(confirmation) = &mut confirmation {
+                confirmation.confirmations = height.get() - confirmation.block_height.get();
+            } else if let Some(tx) = block.transactions().find(|tx| tx.as_ref().hash() == hash) {
+                confirmation = Some(Confirmation {
+                    block_height: height,
+                    block_hash: block.hash(),
+                    confirmations: 0,
+                    error: tx.error().clone(),
+                });
+            }
+
+            if let Some(confirmation) = &confirmation {
+                if confirmation.confirmations >= depth {
+                    return Ok(confirmation.clone());
+                }
+            }
+        }
+
+        Err(eyre!(
+            "Connection dropped before the transaction got {depth} confirmations"
+        ))
+    }
+
     /// Lower-level Instructions API entry point.
     ///
     /// Returns a tuple with a provided request builder, a hash of the transaction, and a response handler.
@@ -485,6 +859,10 @@ impl Client {
 
     /// Connect (through `WebSocket`) to listen for `Iroha` `pipeline` and `data` events.
     ///
+    /// Unless the account of the client has the `CanSubscribeToAllEvents` permission,
+    /// the peer only accepts filters scoped to the domain of the account
+    /// (see [`EventFilterBox::is_scoped_to_domain`]) and closes the connection otherwise.
+    ///
     /// # Errors
     /// - Forwards from [`Self::events_handler`]
     /// - Forwards from [`events_api::EventIterator::new`]
@@ -516,8 +894,14 @@ impl Client {
         &self,
         event_filters: impl IntoIterator<Item = impl Into<EventFilterBox>>,
     ) -> Result<events_api::flow::Init> {
-        events_api::flow::Init::new(
+        let request = EventSubscriptionRequest::new(
+            self.account.clone(),
             event_filters.into_iter().map(Into::into).collect(),
+        )
+        .sign(&self.key_pair);
+
+        events_api::flow::Init::new(
+            request,
             self.headers.clone(),
             join_torii_url(&self.torii_url, torii_uri::SUBSCRIPTION),
         )
@@ -557,6 +941,51 @@ impl Client {
         )
     }
 
+    /// Get the transaction versions which the peer accepts in the next block
+    ///
+    /// # Errors
+    /// Fails if sending request or decoding fails
+    pub fn get_transaction_versions(&self) -> Result<Vec<TransactionVersion>> {
+        let resp = DefaultRequestBuilder::new(
+            HttpMethod::GET,
+            join_torii_url(&self.torii_url, torii_uri::TRANSACTION_VERSIONS),
+        )
+        .headers(&self.headers)
+        .build()?
+        .send_with(&self.http_pool)?;
+
+        if resp.status() == StatusCode::NOT_FOUND {
+            // Peers predating transaction versioning only accept version 1
+            return Ok(vec![TransactionVersion::V1]);
+        }
+        if resp.status() != StatusCode::OK {
+            return Err(eyre!(
+                "Failed to get transaction versions with HTTP status: {}. {}",
+                resp.status(),
+                std::str::from_utf8(resp.body()).unwrap_or(""),
+            ));
+        }
+        serde_json::from_slice(resp.body()).wrap_err("Failed to decode body")
+    }
+
+    /// Build transactions of the latest version accepted by the peer,
+    /// so that the client keeps working across the deprecation of old versions
+    /// and with peers which don't know the latest version yet.
+    ///
+    /// # Errors
+    /// Fails if sending request or decoding fails, or if the peer accepts none
+    /// of the versions known to the client
+    pub fn negotiate_transaction_version(&mut self) -> Result<TransactionVersion> {
+        let version = self
+            .get_transaction_versions()?
+            .into_iter()
+            .max()
+            .ok_or_else(|| eyre!("Peer doesn't accept transactions of any known version"))?;
+        self.transaction_version = version;
+
+        Ok(version)
+    }
+
     /// Get value of config on peer
     ///
     /// # Errors
@@ -569,7 +998,7 @@ impl Client {
         .headers(&self.headers)
         .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
         .build()?
-        .send()?;
+        .send_with(&self.http_pool)?;
 
         if resp.status() != StatusCode::OK {
             return Err(eyre!(
@@ -593,7 +1022,7 @@ impl Client {
             .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
             .body(body)
             .build()?
-            .send()?;
+            .send_with(&self.http_pool)?;
 
         if resp.status() != StatusCode::ACCEPTED {
             return Err(eyre!(
@@ -614,7 +1043,7 @@ impl Client {
         let req = self
             .prepare_status_request::<DefaultRequestBuilder>()
             .header(http::header::ACCEPT, "application/x-parity-scale");
-        let resp = req.build()?.send()?;
+        let resp = req.build()?.send_with(&self.http_pool)?;
         let scaled_resp = StatusResponseHandler::handle_raw(&resp).cloned()?;
         DecodeAll::decode_all(&mut scaled_resp.as_slice()).map_err(|err| eyre!("{err}"))
     }
@@ -829,25 +1258,25 @@ pub mod events_api {
             url: Url,
             /// HTTP request headers
             headers: HashMap<String, String>,
-            /// Event filter
-            filters: Vec<EventFilterBox>,
+            /// Signed subscription request
+            request: SignedEventSubscriptionRequest,
         }
 
         impl Init {
-            /// Construct new item with provided filter, headers and url.
+            /// Construct new item with provided subscription request, headers and url.
             ///
             /// # Errors
             /// Fails if [`transform_ws_url`] fails.
             #[inline]
             pub(in super::super) fn new(
-                filters: Vec<EventFilterBox>,
+                request: SignedEventSubscriptionRequest,
                 headers: HashMap<String, String>,
                 url: Url,
             ) -> Result<Self> {
                 Ok(Self {
                     url: transform_ws_url(url)?,
                     headers,
-                    filters,
+                    request,
                 })
             }
         }
@@ -859,10 +1288,10 @@ pub mod events_api {
                 let Self {
                     url,
                     headers,
-                    filters,
+                    request,
                 } = self;
 
-                let msg = EventSubscriptionRequest::new(filters).encode();
+                let msg = request.encode();
                 InitData::new(R::new(HttpMethod::GET, url).headers(headers), msg, Events)
             }
         }
@@ -994,6 +1423,12 @@ pub mod asset {
     pub const fn all_definitions() -> FindAssetsDefinitions {
         FindAssetsDefinitions
     }
+
+    /// Construct a query to find the key-value pairs of the store asset with the keys within `range`.
+    /// Use the pagination of the query to page through large stores.
+    pub fn store_entries(id: AssetId, range: KeyRange) -> FindStoreEntries {
+        FindStoreEntries::new(id, range)
+    }
 }
 
 pub mod block {
@@ -1015,6 +1450,28 @@ pub mod block {
     pub fn header_by_hash(hash: HashOf<SignedBlock>) -> FindBlockHeaderByHash {
         FindBlockHeaderByHash::new(hash)
     }
+
+    /// Construct a query to find changes of the world state made by the block at the given height
+    pub fn state_diff(height: NonZeroU64) -> FindBlockStateDiff {
+        FindBlockStateDiff::new(height)
+    }
+
+    /// Construct a query to find the fees paid out at the end of the block at the given height
+    pub fn rewards(height: NonZeroU64) -> FindBlockRewards {
+        FindBlockRewards::new(height)
+    }
+}
+
+pub mod event {
+    //! Module with queries for archived events
+
+    use super::*;
+
+    /// Construct a query to find the archived events matching `filter`
+    /// emitted by the blocks at the heights within `height_range`
+    pub fn events(filter: impl Into<EventFilterBox>, height_range: HeightRange) -> FindEvents {
+        FindEvents::new(filter.into(), height_range)
+    }
 }
 
 pub mod domain {
@@ -1042,10 +1499,20 @@ pub mod transaction {
         FindTransactionsByAccountId::new(account_id)
     }
 
+    /// Construct a query to retrieve transactions attributed to the app
+    pub fn by_app_id(app_id: AppId) -> FindTransactionsByAppId {
+        FindTransactionsByAppId::new(app_id)
+    }
+
     /// Construct a query to retrieve transaction by hash
     pub fn by_hash(hash: HashOf<SignedTransaction>) -> FindTransactionByHash {
         FindTransactionByHash::new(hash)
     }
+
+    /// Construct a query to check whether the idempotency key is used by a committed transaction
+    pub fn idempotency_record(authority: AccountId, key: Name) -> FindIdempotencyRecord {
+        FindIdempotencyRecord::new(authority, key)
+    }
 }
 
 pub mod trigger {
@@ -1057,10 +1524,25 @@ pub mod trigger {
         FindActiveTriggerIds
     }
 
+    /// Construct a query to get the details of all active triggers
+    pub fn all() -> FindAllActiveTriggers {
+        FindAllActiveTriggers
+    }
+
+    /// Construct a query to get the details of the triggers executed by the accounts of the domain
+    pub fn by_domain(domain_id: DomainId) -> FindTriggersByDomain {
+        FindTriggersByDomain::new(domain_id)
+    }
+
     /// Construct a query to get a trigger by its id
     pub fn by_id(trigger_id: TriggerId) -> FindTriggerById {
         FindTriggerById::new(trigger_id)
     }
+
+    /// Construct a query to get receipts of the triggers executed by the block at the given height
+    pub fn receipts(height: NonZeroU64) -> FindTriggerReceipts {
+        FindTriggerReceipts::new(height)
+    }
 }
 
 pub mod permission {
@@ -1094,6 +1576,16 @@ pub mod role {
     }
 }
 
+pub mod peer {
+    //! Module with queries for peers
+    use super::*;
+
+    /// Construct a query to retrieve the certificate registered for the peer with the given key
+    pub fn certificate(public_key: PublicKey) -> FindPeerCertificate {
+        FindPeerCertificate::new(public_key)
+    }
+}
+
 pub mod parameter {
     //! Module with queries for config parameters
     use super::*;
@@ -1122,7 +1614,7 @@ mod tests {
     use test_samples::gen_account_in;
 
     use super::*;
-    use crate::config::{BasicAuth, Config, WebLogin};
+    use crate::config::{BasicAuth, Config, Http, WebLogin};
 
     const LOGIN: &str = "mad_hatter";
     const PASSWORD: &str = "ilovetea";
@@ -1140,6 +1632,7 @@ mod tests {
             transaction_add_nonce: false,
             transaction_ttl: Duration::from_secs(5),
             transaction_status_timeout: Duration::from_secs(10),
+            http: Http::default(),
         }
     }
 
@@ -1192,6 +1685,30 @@ mod tests {
         assert_eq!(value, &expected_value);
     }
 
+    #[test]
+    fn degraded_peer_rejection_is_decoded() {
+        let degraded = PeerDegraded {
+            partitioned_for_ms: 45_000,
+            reachable_peers: 2,
+            quorum: 3,
+        };
+        let response = Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(degraded.encode())
+            .unwrap();
+
+        let error = TransactionResponseHandler::handle(&response).unwrap_err();
+        assert_eq!(error.downcast_ref::<PeerDegraded>(), Some(&degraded));
+
+        // Other unavailability reasons are reported as they are
+        let response = Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(b"Queue is closed".to_vec())
+            .unwrap();
+        let error = TransactionResponseHandler::handle(&response).unwrap_err();
+        assert!(error.downcast_ref::<PeerDegraded>().is_none());
+    }
+
     #[cfg(test)]
     mod join_torii_url {
         use url::Url;
@@ -0,0 +1,863 @@
+//! Composition of transactions out of instructions which depend on each other.
+//!
+//! [`TransactionComposer`] orders instructions so that entities are registered before
+//! they are used and used before they are unregistered, preserving the original order
+//! of independent instructions. It also estimates which permissions the authority
+//! needs to execute the transaction with the default executor.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet},
+};
+
+use derive_more::Display;
+use iroha_executor_data_model::permission::{
+    account::{
+        CanFreezeAnyAccount, CanRemoveKeyValueInAccount, CanSetKeyValueInAccount,
+        CanUnregisterAccount,
+    },
+    asset::{
+        CanBurnUserAsset, CanMintAssetWithDefinition, CanRegisterAssetWithDefinition,
+        CanRemoveKeyValueInUserAsset, CanSetKeyValueInUserAsset, CanTransferUserAsset,
+        CanUnregisterUserAsset,
+    },
+    asset_definition::{
+        CanRemoveKeyValueInAssetDefinition, CanSetKeyValueInAssetDefinition,
+        CanUnregisterAssetDefinition,
+    },
+    domain::{
+        CanRegisterAccountInDomain, CanRegisterAssetDefinitionInDomain, CanRemoveKeyValueInDomain,
+        CanSetKeyValueInDomain, CanUnregisterDomain,
+    },
+    executor::CanUpgradeExecutor,
+    parameter::CanSetParameters,
+    peer::CanUnregisterAnyPeer,
+    role::CanUnregisterAnyRole,
+    trigger::{
+        CanBurnUserTrigger, CanExecuteUserTrigger, CanMintUserTrigger, CanRemoveKeyValueInTrigger,
+        CanSetKeyValueInTrigger,
+    },
+};
+
+use crate::data_model::{isi::Instruction, prelude::*};
+
+/// Entity instructions of a transaction can depend on
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash)]
+pub enum Entity {
+    /// Domain
+    #[display(fmt = "domain `{_0}`")]
+    Domain(DomainId),
+    /// Account
+    #[display(fmt = "account `{_0}`")]
+    Account(AccountId),
+    /// Asset definition
+    #[display(fmt = "asset definition `{_0}`")]
+    AssetDefinition(AssetDefinitionId),
+    /// Asset
+    #[display(fmt = "asset `{_0}`")]
+    Asset(AssetId),
+    /// Role
+    #[display(fmt = "role `{_0}`")]
+    Role(RoleId),
+    /// Trigger
+    #[display(fmt = "trigger `{_0}`")]
+    Trigger(TriggerId),
+}
+
+/// Error of composing a transaction
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum ComposeError {
+    /// Instructions {first} and {second} both register {entity}
+    DuplicateRegistration {
+        /// Registered entity
+        entity: Entity,
+        /// Index of the first instruction registering the entity
+        first: usize,
+        /// Index of the second instruction registering the entity
+        second: usize,
+    },
+    /// Transaction both registers and unregisters {0}, so the intended order is ambiguous
+    RegisteredAndUnregistered(Entity),
+    /// Instructions {0:?} depend on each other
+    CyclicDependency(Vec<usize>),
+}
+
+/// Transaction composed by [`TransactionComposer`]
+#[derive(Debug, Clone)]
+pub struct ComposedTransaction {
+    /// Instructions in the order they can be executed in
+    pub instructions: Vec<InstructionBox>,
+    /// Permissions the authority needs with the default executor unless it owns the affected
+    /// entities. Entities registered within the transaction are owned by the authority.
+    pub required_permissions: Vec<Permission>,
+}
+
+/// Builder of transactions out of instructions given in an arbitrary order
+#[derive(Debug, Clone)]
+pub struct TransactionComposer {
+    authority: AccountId,
+    instructions: Vec<InstructionBox>,
+}
+
+/// Entities touched by an instruction
+#[derive(Default)]
+struct Effects {
+    registers: Option<Entity>,
+    /// Entity created if it doesn't exist, e.g. asset by minting
+    creates: Option<Entity>,
+    unregisters: Option<Entity>,
+    requires: Vec<Entity>,
+}
+
+impl Entity {
+    /// The entity itself followed by the entities it belongs to
+    fn with_parents(self) -> Vec<Self> {
+        match &self {
+            Self::Account(account) => vec![Self::Domain(account.domain().clone()), self],
+            Self::AssetDefinition(asset_definition) => {
+                vec![Self::Domain(asset_definition.domain().clone()), self]
+            }
+            Self::Asset(asset) => {
+                let mut entities = Self::AssetDefinition(asset.definition().clone()).with_parents();
+                entities.extend(Self::Account(asset.account().clone()).with_parents());
+                entities.push(self);
+                entities
+            }
+            Self::Domain(_) | Self::Role(_) | Self::Trigger(_) => vec![self],
+        }
+    }
+}
+
+impl TransactionComposer {
+    /// Construct [`Self`] for transactions submitted by `authority`
+    pub fn new(authority: AccountId) -> Self {
+        Self {
+            authority,
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Add instruction to the transaction
+    #[must_use]
+    pub fn with_instruction(mut self, instruction: impl Instruction) -> Self {
+        self.instructions.push(instruction.into());
+        self
+    }
+
+    /// Add instructions to the transaction
+    #[must_use]
+    pub fn with_instructions<I: Instruction>(
+        mut self,
+        instructions: impl IntoIterator<Item = I>,
+    ) -> Self {
+        self.instructions
+            .extend(instructions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Order the instructions according to their dependencies and estimate required permissions.
+    ///
+    /// Entities which aren't registered within the transaction are assumed to exist.
+    ///
+    /// # Errors
+    /// - Entity is registered more than once
+    /// - Entity is both registered and unregistered
+    /// - Instructions depend on each other cyclically
+    pub fn compose(self) -> Result<ComposedTransaction, ComposeError> {
+        let effects = self.instructions.iter().map(effects).collect::<Vec<_>>();
+
+        let mut registered_by = HashMap::new();
+        let mut unregistered_by = HashMap::new();
+        for (idx, effects) in effects.iter().enumerate() {
+            if let Some(entity) = &effects.registers {
+                if let Some(first) = registered_by.insert(entity.clone(), idx) {
+                    return Err(ComposeError::DuplicateRegistration {
+                        entity: entity.clone(),
+                        first,
+                        second: idx,
+                    });
+                }
+            }
+            if let Some(entity) = &effects.unregisters {
+                unregistered_by.insert(entity.clone(), idx);
+            }
+        }
+        if let Some(entity) = registered_by
+            .keys()
+            .find(|entity| unregistered_by.contains_key(*entity))
+        {
+            return Err(ComposeError::RegisteredAndUnregistered(entity.clone()));
+        }
+
+        let order = Self::order(&effects, &unregistered_by)?;
+        let registered = registered_by.into_keys().collect::<HashSet<_>>();
+        let required_permissions = self
+            .instructions
+            .iter()
+            .flat_map(|instruction| required_permissions(instruction, &self.authority, &registered))
+            .fold(Vec::new(), |mut permissions, permission| {
+                if !permissions.contains(&permission) {
+                    permissions.push(permission);
+                }
+                permissions
+            });
+
+        let mut instructions = self.instructions.into_iter().map(Some).collect::<Vec<_>>();
+        Ok(ComposedTransaction {
+            instructions: order
+                .into_iter()
+                .filter_map(|idx| instructions[idx].take())
+                .collect(),
+            required_permissions,
+        })
+    }
+
+    /// Topological order of the instructions preferring the original order
+    fn order(
+        effects: &[Effects],
+        unregistered_by: &HashMap<Entity, usize>,
+    ) -> Result<Vec<usize>, ComposeError> {
+        let mut provided_by = HashMap::<&Entity, Vec<usize>>::new();
+        for (idx, effects) in effects.iter().enumerate() {
+            for entity in effects.registers.iter().chain(&effects.creates) {
+                provided_by.entry(entity).or_default().push(idx);
+            }
+        }
+
+        let mut successors = vec![Vec::new(); effects.len()];
+        let mut in_degree = vec![0_usize; effects.len()];
+        let mut add_edge = |from: usize, to: usize| {
+            if from != to {
+                successors[from].push(to);
+                in_degree[to] += 1;
+            }
+        };
+        for (idx, effects) in effects.iter().enumerate() {
+            // Entity can't be used once the entity it belongs to is unregistered
+            let parents = effects
+                .registers
+                .iter()
+                .chain(&effects.creates)
+                .flat_map(|entity| {
+                    let mut parents = entity.clone().with_parents();
+                    parents.pop();
+                    parents
+                });
+            let requires = effects
+                .requires
+                .iter()
+                .flat_map(|entity| entity.clone().with_parents())
+                .chain(parents);
+            for entity in requires {
+                for &providing in provided_by.get(&entity).into_iter().flatten() {
+                    add_edge(providing, idx);
+                }
+                if let Some(&unregistering) = unregistered_by.get(&entity) {
+                    add_edge(idx, unregistering);
+                }
+            }
+        }
+
+        let mut ready = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(idx, _)| Reverse(idx))
+            .collect::<BinaryHeap<_>>();
+        let mut order = Vec::with_capacity(effects.len());
+        while let Some(Reverse(idx)) = ready.pop() {
+            order.push(idx);
+            for &successor in &successors[idx] {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    ready.push(Reverse(successor));
+                }
+            }
+        }
+
+        if order.len() < effects.len() {
+            let cycle = (0..effects.len())
+                .filter(|idx| in_degree[*idx] > 0)
+                .collect();
+            return Err(ComposeError::CyclicDependency(cycle));
+        }
+        Ok(order)
+    }
+}
+
+fn effects(instruction: &InstructionBox) -> Effects {
+    let requires = |requires: Vec<Entity>| Effects {
+        requires,
+        ..Effects::default()
+    };
+    let asset_requires = |asset: &AssetId| {
+        vec![
+            Entity::AssetDefinition(asset.definition().clone()),
+            Entity::Account(asset.account().clone()),
+        ]
+    };
+
+    match instruction {
+        InstructionBox::Register(register) => {
+            let (registers, requires) = match register {
+                RegisterBox::Domain(register) => {
+                    (Entity::Domain(register.object.id().clone()), Vec::new())
+                }
+                RegisterBox::Account(register) => {
+                    let id = register.object.id();
+                    (
+                        Entity::Account(id.clone()),
+                        vec![Entity::Domain(id.domain().clone())],
+                    )
+                }
+                RegisterBox::AssetDefinition(register) => {
+                    let id = register.object.id();
+                    (
+                        Entity::AssetDefinition(id.clone()),
+                        vec![Entity::Domain(id.domain().clone())],
+                    )
+                }
+                RegisterBox::Asset(register) => {
+                    let id = register.object.id();
+                    (Entity::Asset(id.clone()), asset_requires(id))
+                }
+                RegisterBox::Role(register) => {
+                    (Entity::Role(register.object.id().clone()), Vec::new())
+                }
+                RegisterBox::Trigger(register) => {
+                    (Entity::Trigger(register.object.id().clone()), Vec::new())
+                }
+                RegisterBox::Peer(_) => return Effects::default(),
+            };
+            Effects {
+                registers: Some(registers),
+                requires,
+                ..Effects::default()
+            }
+        }
+        InstructionBox::Unregister(unregister) => {
+            let unregisters = match unregister {
+                UnregisterBox::Domain(unregister) => Entity::Domain(unregister.object.clone()),
+                UnregisterBox::Account(unregister) => Entity::Account(unregister.object.clone()),
+                UnregisterBox::AssetDefinition(unregister) => {
+                    Entity::AssetDefinition(unregister.object.clone())
+                }
+                UnregisterBox::Asset(unregister) => Entity::Asset(unregister.object.clone()),
+                UnregisterBox::Role(unregister) => Entity::Role(unregister.object.clone()),
+                UnregisterBox::Trigger(unregister) => Entity::Trigger(unregister.object.clone()),
+                UnregisterBox::Peer(_) => return Effects::default(),
+            };
+            Effects {
+                unregisters: Some(unregisters),
+                ..Effects::default()
+            }
+        }
+        InstructionBox::Mint(MintBox::Asset(mint)) => Effects {
+            creates: Some(Entity::Asset(mint.destination.clone())),
+            requires: asset_requires(&mint.destination),
+            ..Effects::default()
+        },
+        InstructionBox::Mint(MintBox::TriggerRepetitions(mint)) => {
+            requires(vec![Entity::Trigger(mint.destination.clone())])
+        }
+        InstructionBox::Burn(BurnBox::Asset(burn)) => {
+            requires(vec![Entity::Asset(burn.destination.clone())])
+        }
+        InstructionBox::Burn(BurnBox::TriggerRepetitions(burn)) => {
+            requires(vec![Entity::Trigger(burn.destination.clone())])
+        }
+        InstructionBox::Transfer(transfer) => requires(match transfer {
+            TransferBox::Domain(transfer) => vec![
+                Entity::Domain(transfer.object.clone()),
+                Entity::Account(transfer.destination.clone()),
+            ],
+            TransferBox::AssetDefinition(transfer) => vec![
+                Entity::AssetDefinition(transfer.object.clone()),
+                Entity::Account(transfer.destination.clone()),
+            ],
+            TransferBox::Asset(AssetTransferBox::Numeric(transfer)) => vec![
+                Entity::Asset(transfer.source.clone()),
+                Entity::Account(transfer.destination.clone()),
+            ],
+            TransferBox::Asset(AssetTransferBox::Store(transfer)) => vec![
+                Entity::Asset(transfer.source.clone()),
+                Entity::Account(transfer.destination.clone()),
+            ],
+        }),
+        InstructionBox::SetKeyValue(set) => requires(vec![match set {
+            SetKeyValueBox::Domain(set) => Entity::Domain(set.object.clone()),
+            SetKeyValueBox::Account(set) => Entity::Account(set.object.clone()),
+            SetKeyValueBox::AssetDefinition(set) => Entity::AssetDefinition(set.object.clone()),
+            SetKeyValueBox::Asset(set) => Entity::Asset(set.object.clone()),
+            SetKeyValueBox::Trigger(set) => Entity::Trigger(set.object.clone()),
+        }]),
+        InstructionBox::RemoveKeyValue(remove) => requires(vec![match remove {
+            RemoveKeyValueBox::Domain(remove) => Entity::Domain(remove.object.clone()),
+            RemoveKeyValueBox::Account(remove) => Entity::Account(remove.object.clone()),
+            RemoveKeyValueBox::AssetDefinition(remove) => {
+                Entity::AssetDefinition(remove.object.clone())
+            }
+            RemoveKeyValueBox::Asset(remove) => Entity::Asset(remove.object.clone()),
+            RemoveKeyValueBox::Trigger(remove) => Entity::Trigger(remove.object.clone()),
+        }]),
+        InstructionBox::Grant(grant) => requires(match grant {
+            GrantBox::Permission(grant) => vec![Entity::Account(grant.destination.clone())],
+            GrantBox::Role(grant) => vec![
+                Entity::Role(grant.object.clone()),
+                Entity::Account(grant.destination.clone()),
+            ],
+            GrantBox::RolePermission(grant) => vec![Entity::Role(grant.destination.clone())],
+        }),
+        InstructionBox::Revoke(revoke) => requires(match revoke {
+            RevokeBox::Permission(revoke) => vec![Entity::Account(revoke.destination.clone())],
+            RevokeBox::Role(revoke) => vec![
+                Entity::Role(revoke.object.clone()),
+                Entity::Account(revoke.destination.clone()),
+            ],
+            RevokeBox::RolePermission(revoke) => vec![Entity::Role(revoke.destination.clone())],
+        }),
+        InstructionBox::ExecuteTrigger(execute) => {
+            requires(vec![Entity::Trigger(execute.trigger.clone())])
+        }
+        InstructionBox::LockEscrow(lock) => requires(vec![
+            Entity::Asset(lock.source.clone()),
+            Entity::Account(lock.recipient.clone()),
+        ]),
+        InstructionBox::TransferAll(transfer) => requires(vec![
+            Entity::AssetDefinition(transfer.asset_definition.clone()),
+            Entity::Account(transfer.from.clone()),
+            Entity::Account(transfer.to.clone()),
+        ]),
+        InstructionBox::BurnAll(burn) => requires(vec![
+            Entity::AssetDefinition(burn.asset_definition.clone()),
+            Entity::Account(burn.from.clone()),
+        ]),
+        InstructionBox::TransferMultiple(transfer) => requires(
+            std::iter::once(Entity::Account(transfer.from.clone()))
+                .chain(transfer.movements.iter().flat_map(|movement| {
+                    [
+                        Entity::AssetDefinition(movement.asset_definition.clone()),
+                        Entity::Account(movement.to.clone()),
+                    ]
+                }))
+                .collect(),
+        ),
+        InstructionBox::Freeze(Freeze { account })
+        | InstructionBox::Unfreeze(Unfreeze { account }) => {
+            requires(vec![Entity::Account(account.clone())])
+        }
+        InstructionBox::DelegateToTrigger(DelegateToTrigger { trigger, .. })
+        | InstructionBox::RevokeTriggerDelegation(RevokeTriggerDelegation { trigger }) => {
+            requires(vec![Entity::Trigger(trigger.clone())])
+        }
+        InstructionBox::RemoveKeyRange(remove) => {
+            requires(vec![Entity::Asset(remove.asset.clone())])
+        }
+        InstructionBox::RegisterPeerCertificate(register) => requires(vec![Entity::Account(
+            register.certificate.operator().clone(),
+        )]),
+        InstructionBox::SetRecoveryPolicy(set) => requires(
+            std::iter::once(&set.account)
+                .chain(&set.guardians)
+                .cloned()
+                .map(Entity::Account)
+                .collect(),
+        ),
+        InstructionBox::InitiateRecovery(InitiateRecovery { account, .. })
+        | InstructionBox::VetoRecovery(VetoRecovery { account })
+        | InstructionBox::FinalizeRecovery(FinalizeRecovery { account }) => {
+            requires(vec![Entity::Account(account.clone())])
+        }
+        InstructionBox::SetParameter(_)
+        | InstructionBox::Upgrade(_)
+        | InstructionBox::Log(_)
+        | InstructionBox::ClaimEscrow(_)
+        | InstructionBox::RefundEscrow(_)
+        | InstructionBox::RegisterApp(_)
+        | InstructionBox::UnregisterApp(_)
+        | InstructionBox::GrantProxy(_)
+        | InstructionBox::RevokeProxy(_)
+        | InstructionBox::Custom(_) => Effects::default(),
+    }
+}
+
+/// Permissions required by the default executor to execute `instruction`
+/// unless `authority` owns the affected entity
+fn required_permissions(
+    instruction: &InstructionBox,
+    authority: &AccountId,
+    registered: &HashSet<Entity>,
+) -> Vec<Permission> {
+    let owns_domain = |domain: &DomainId| registered.contains(&Entity::Domain(domain.clone()));
+    let owns_asset_definition = |asset_definition: &AssetDefinitionId| {
+        owns_domain(asset_definition.domain())
+            || registered.contains(&Entity::AssetDefinition(asset_definition.clone()))
+    };
+    let owns_account = |account: &AccountId| account == authority || owns_domain(account.domain());
+    let owns_asset = |asset: &AssetId| owns_account(asset.account());
+    let owns_trigger = |trigger: &TriggerId| registered.contains(&Entity::Trigger(trigger.clone()));
+
+    if let InstructionBox::TransferMultiple(transfer) = instruction {
+        if owns_account(&transfer.from) {
+            return Vec::new();
+        }
+        let asset_definitions: BTreeSet<_> = transfer
+            .movements
+            .iter()
+            .map(|movement| &movement.asset_definition)
+            .collect();
+        return asset_definitions
+            .into_iter()
+            .map(|asset_definition| {
+                CanTransferUserAsset {
+                    asset: AssetId::new(asset_definition.clone(), transfer.from.clone()),
+                }
+                .into()
+            })
+            .collect();
+    }
+
+    let permission: Option<Permission> = match instruction {
+        InstructionBox::Register(RegisterBox::Account(register)) => {
+            let domain = register.object.id().domain();
+            (!owns_domain(domain)).then(|| {
+                CanRegisterAccountInDomain {
+                    domain: domain.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Register(RegisterBox::AssetDefinition(register)) => {
+            let domain = register.object.id().domain();
+            (!owns_domain(domain)).then(|| {
+                CanRegisterAssetDefinitionInDomain {
+                    domain: domain.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Register(RegisterBox::Asset(register)) => {
+            let asset_definition = register.object.id().definition();
+            (!owns_asset_definition(asset_definition)).then(|| {
+                CanRegisterAssetWithDefinition {
+                    asset_definition: asset_definition.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Unregister(unregister) => match unregister {
+            UnregisterBox::Peer(_) => Some(CanUnregisterAnyPeer.into()),
+            UnregisterBox::Domain(unregister) => Some(
+                CanUnregisterDomain {
+                    domain: unregister.object.clone(),
+                }
+                .into(),
+            ),
+            UnregisterBox::Account(unregister) => (!owns_account(&unregister.object)).then(|| {
+                CanUnregisterAccount {
+                    account: unregister.object.clone(),
+                }
+                .into()
+            }),
+            UnregisterBox::AssetDefinition(unregister) => {
+                (!owns_asset_definition(&unregister.object)).then(|| {
+                    CanUnregisterAssetDefinition {
+                        asset_definition: unregister.object.clone(),
+                    }
+                    .into()
+                })
+            }
+            UnregisterBox::Asset(unregister) => (!owns_asset(&unregister.object)).then(|| {
+                CanUnregisterUserAsset {
+                    asset: unregister.object.clone(),
+                }
+                .into()
+            }),
+            UnregisterBox::Role(_) => Some(CanUnregisterAnyRole.into()),
+            // Depends on the authority of the trigger which isn't known upfront
+            UnregisterBox::Trigger(_) => None,
+        },
+        InstructionBox::Mint(MintBox::Asset(mint)) => {
+            let asset_definition = mint.destination.definition();
+            (!owns_asset_definition(asset_definition)).then(|| {
+                CanMintAssetWithDefinition {
+                    asset_definition: asset_definition.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Mint(MintBox::TriggerRepetitions(mint)) => {
+            (!owns_trigger(&mint.destination)).then(|| {
+                CanMintUserTrigger {
+                    trigger: mint.destination.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Burn(BurnBox::Asset(burn)) => (!owns_asset(&burn.destination)).then(|| {
+            CanBurnUserAsset {
+                asset: burn.destination.clone(),
+            }
+            .into()
+        }),
+        InstructionBox::Burn(BurnBox::TriggerRepetitions(burn)) => {
+            (!owns_trigger(&burn.destination)).then(|| {
+                CanBurnUserTrigger {
+                    trigger: burn.destination.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::Transfer(TransferBox::Asset(
+            AssetTransferBox::Numeric(Transfer { source, .. })
+            | AssetTransferBox::Store(Transfer { source, .. }),
+        ))
+        | InstructionBox::LockEscrow(LockEscrow { source, .. }) => {
+            (!owns_asset(source)).then(|| {
+                CanTransferUserAsset {
+                    asset: source.clone(),
+                }
+                .into()
+            })
+        }
+        InstructionBox::TransferAll(transfer) => {
+            let asset = AssetId::new(transfer.asset_definition.clone(), transfer.from.clone());
+            (!owns_asset(&asset)).then(|| CanTransferUserAsset { asset }.into())
+        }
+        InstructionBox::BurnAll(burn) => {
+            let asset = AssetId::new(burn.asset_definition.clone(), burn.from.clone());
+            (!owns_asset(&asset)).then(|| CanBurnUserAsset { asset }.into())
+        }
+        InstructionBox::SetKeyValue(set) => match set {
+            SetKeyValueBox::Domain(set) => (!owns_domain(&set.object)).then(|| {
+                CanSetKeyValueInDomain {
+                    domain: set.object.clone(),
+                }
+                .into()
+            }),
+            SetKeyValueBox::Account(set) => (!owns_account(&set.object)).then(|| {
+                CanSetKeyValueInAccount {
+                    account: set.object.clone(),
+                }
+                .into()
+            }),
+            SetKeyValueBox::AssetDefinition(set) => {
+                (!owns_asset_definition(&set.object)).then(|| {
+                    CanSetKeyValueInAssetDefinition {
+                        asset_definition: set.object.clone(),
+                    }
+                    .into()
+                })
+            }
+            SetKeyValueBox::Asset(set) => (!owns_asset(&set.object)).then(|| {
+                CanSetKeyValueInUserAsset {
+                    asset: set.object.clone(),
+                }
+                .into()
+            }),
+            SetKeyValueBox::Trigger(set) => (!owns_trigger(&set.object)).then(|| {
+                CanSetKeyValueInTrigger {
+                    trigger: set.object.clone(),
+                }
+                .into()
+            }),
+        },
+        InstructionBox::RemoveKeyValue(remove) => match remove {
+            RemoveKeyValueBox::Domain(remove) => (!owns_domain(&remove.object)).then(|| {
+                CanRemoveKeyValueInDomain {
+                    domain: remove.object.clone(),
+                }
+                .into()
+            }),
+            RemoveKeyValueBox::Account(remove) => (!owns_account(&remove.object)).then(|| {
+                CanRemoveKeyValueInAccount {
+                    account: remove.object.clone(),
+                }
+                .into()
+            }),
+            RemoveKeyValueBox::AssetDefinition(remove) => (!owns_asset_definition(&remove.object))
+                .then(|| {
+                    CanRemoveKeyValueInAssetDefinition {
+                        asset_definition: remove.object.clone(),
+                    }
+                    .into()
+                }),
+            RemoveKeyValueBox::Asset(remove) => (!owns_asset(&remove.object)).then(|| {
+                CanRemoveKeyValueInUserAsset {
+                    asset: remove.object.clone(),
+                }
+                .into()
+            }),
+            RemoveKeyValueBox::Trigger(remove) => (!owns_trigger(&remove.object)).then(|| {
+                CanRemoveKeyValueInTrigger {
+                    trigger: remove.object.clone(),
+                }
+                .into()
+            }),
+        },
+        InstructionBox::RemoveKeyRange(remove) => (!owns_asset(&remove.asset)).then(|| {
+            CanRemoveKeyValueInUserAsset {
+                asset: remove.asset.clone(),
+            }
+            .into()
+        }),
+        InstructionBox::ExecuteTrigger(execute) => (!owns_trigger(&execute.trigger)).then(|| {
+            CanExecuteUserTrigger {
+                trigger: execute.trigger.clone(),
+            }
+            .into()
+        }),
+        InstructionBox::SetParameter(_) => Some(CanSetParameters.into()),
+        InstructionBox::Upgrade(_) => Some(CanUpgradeExecutor.into()),
+        InstructionBox::Freeze(_) | InstructionBox::Unfreeze(_) => Some(CanFreezeAnyAccount.into()),
+        _ => None,
+    };
+
+    permission.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_samples::{gen_account_in, ALICE_ID};
+
+    use super::*;
+    use crate::data_model::Level;
+
+    fn rose() -> AssetDefinitionId {
+        "rose#garden".parse().unwrap()
+    }
+
+    #[test]
+    fn registration_is_moved_before_usage() {
+        let (bob, _) = gen_account_in("garden");
+        let transfer =
+            Transfer::asset_numeric(AssetId::new(rose(), ALICE_ID.clone()), 1_u32, bob.clone());
+        let mint = Mint::asset_numeric(10_u32, AssetId::new(rose(), ALICE_ID.clone()));
+        let register_definition = Register::asset_definition(AssetDefinition::numeric(rose()));
+        let register_bob = Register::account(Account::new(bob));
+        let register_domain = Register::domain(Domain::new("garden".parse().unwrap()));
+
+        let composed = TransactionComposer::new(ALICE_ID.clone())
+            .with_instruction(transfer.clone())
+            .with_instruction(mint.clone())
+            .with_instruction(register_definition.clone())
+            .with_instruction(register_bob.clone())
+            .with_instruction(register_domain.clone())
+            .compose()
+            .unwrap();
+
+        assert_eq!(
+            composed.instructions,
+            vec![
+                InstructionBox::from(register_domain),
+                register_definition.into(),
+                mint.into(),
+                register_bob.into(),
+                transfer.into(),
+            ]
+        );
+        // Alice owns everything registered in the transaction
+        assert!(composed.required_permissions.is_empty());
+    }
+
+    #[test]
+    fn independent_instructions_keep_order() {
+        let logs = (0..5)
+            .map(|i| Log::new(Level::INFO, i.to_string()))
+            .collect::<Vec<_>>();
+
+        let composed = TransactionComposer::new(ALICE_ID.clone())
+            .with_instructions(logs.clone())
+            .compose()
+            .unwrap();
+
+        assert_eq!(
+            composed.instructions,
+            logs.into_iter().map(Into::into).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn usage_is_moved_before_unregistration() {
+        let asset = AssetId::new(rose(), ALICE_ID.clone());
+        let unregister = Unregister::asset_definition(rose());
+        let burn = Burn::asset_numeric(1_u32, asset);
+
+        let composed = TransactionComposer::new(ALICE_ID.clone())
+            .with_instruction(unregister.clone())
+            .with_instruction(burn.clone())
+            .compose()
+            .unwrap();
+
+        assert_eq!(
+            composed.instructions,
+            vec![InstructionBox::from(burn), unregister.into()]
+        );
+    }
+
+    #[test]
+    fn duplicate_registration_is_rejected() {
+        let register = Register::asset_definition(AssetDefinition::numeric(rose()));
+
+        let error = TransactionComposer::new(ALICE_ID.clone())
+            .with_instructions([register.clone(), register])
+            .compose()
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ComposeError::DuplicateRegistration {
+                first: 0,
+                second: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn registration_and_unregistration_is_rejected() {
+        let error = TransactionComposer::new(ALICE_ID.clone())
+            .with_instruction(Register::asset_definition(AssetDefinition::numeric(rose())))
+            .with_instruction(Unregister::asset_definition(rose()))
+            .compose()
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ComposeError::RegisteredAndUnregistered(Entity::AssetDefinition(_))
+        ));
+    }
+
+    #[test]
+    fn permissions_for_foreign_entities_are_estimated() {
+        let (bob, _) = gen_account_in("garden");
+        let bobs_rose = AssetId::new(rose(), bob.clone());
+
+        let composed = TransactionComposer::new(ALICE_ID.clone())
+            .with_instruction(Mint::asset_numeric(10_u32, bobs_rose.clone()))
+            .with_instruction(Transfer::asset_numeric(
+                bobs_rose.clone(),
+                1_u32,
+                ALICE_ID.clone(),
+            ))
+            .with_instruction(Transfer::asset_numeric(
+                AssetId::new(rose(), ALICE_ID.clone()),
+                1_u32,
+                bob,
+            ))
+            .with_instruction(Mint::asset_numeric(1_u32, bobs_rose.clone()))
+            .compose()
+            .unwrap();
+
+        assert_eq!(
+            composed.required_permissions,
+            vec![
+                CanMintAssetWithDefinition {
+                    asset_definition: rose()
+                }
+                .into(),
+                CanTransferUserAsset { asset: bobs_rose }.into(),
+            ]
+        );
+    }
+}
@@ -1,13 +1,14 @@
 //! Module for client-related configuration and structs
 
 use core::str::FromStr;
-use std::{path::Path, time::Duration};
+use std::{num::NonZeroUsize, path::Path, time::Duration};
 
 use derive_more::Display;
 use error_stack::ResultExt;
 use eyre::Result;
 use iroha_config_base::{read::ConfigReader, toml::TomlSource};
 use iroha_primitives::small::SmallStr;
+use nonzero_ext::nonzero;
 use serde::{Deserialize, Serialize};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use url::Url;
@@ -25,6 +26,12 @@ pub const DEFAULT_TRANSACTION_TIME_TO_LIVE: Duration = Duration::from_secs(100);
 pub const DEFAULT_TRANSACTION_STATUS_TIMEOUT: Duration = Duration::from_secs(15);
 #[allow(missing_docs)]
 pub const DEFAULT_TRANSACTION_NONCE: bool = false;
+#[allow(missing_docs)]
+pub const DEFAULT_HTTP_POOL_SIZE: NonZeroUsize = nonzero!(8_usize);
+#[allow(missing_docs)]
+pub const DEFAULT_HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+#[allow(missing_docs)]
+pub const DEFAULT_HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Valid web auth login string. See [`WebLogin::from_str`]
 #[derive(Debug, Display, Clone, PartialEq, Eq, DeserializeFromStr, SerializeDisplay)]
@@ -55,6 +62,27 @@ pub struct BasicAuth {
     pub password: SmallStr,
 }
 
+/// Settings of the HTTP connections to Torii
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Http {
+    /// Idle connections kept open to a peer between requests
+    pub pool_size: NonZeroUsize,
+    /// Timeout of establishing a connection
+    pub connect_timeout: Duration,
+    /// Timeout of a whole request, including reading the response
+    pub request_timeout: Duration,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self {
+            pool_size: DEFAULT_HTTP_POOL_SIZE,
+            connect_timeout: DEFAULT_HTTP_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_HTTP_REQUEST_TIMEOUT,
+        }
+    }
+}
+
 /// Complete client configuration
 #[derive(Clone, Debug, Serialize)]
 #[allow(missing_docs)]
@@ -67,6 +95,7 @@ pub struct Config {
     pub transaction_ttl: Duration,
     pub transaction_status_timeout: Duration,
     pub transaction_add_nonce: bool,
+    pub http: Http,
 }
 
 /// An error type for [`Config::load`]
@@ -127,6 +156,11 @@ mod tests {
             time_to_live_ms = 100_000
             status_timeout_ms = 100_000
             nonce = false
+
+            [http]
+            pool_size = 4
+            connect_timeout_ms = 1_000
+            request_timeout_ms = 10_000
         }
     }
 
@@ -170,6 +204,34 @@ mod tests {
         assert_eq!(config.torii_api_url.as_str(), "http://127.0.0.1/peer-1/");
     }
 
+    #[test]
+    fn http_defaults_are_applied() {
+        let mut sample = config_sample();
+        sample.remove("http");
+
+        let config = ConfigReader::new()
+            .with_toml_source(TomlSource::inline(sample))
+            .read_and_complete::<user::Root>()
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        assert_eq!(config.http, Http::default());
+    }
+
+    #[test]
+    fn http_request_timeout_shorter_than_connect_is_refused() {
+        let mut sample = config_sample();
+        sample["http"]["request_timeout_ms"] = toml::Value::Integer(500);
+
+        let _ = ConfigReader::new()
+            .with_toml_source(TomlSource::inline(sample))
+            .read_and_complete::<user::Root>()
+            .unwrap()
+            .parse()
+            .expect_err("request can't finish before the connection is established");
+    }
+
     #[test]
     fn invalid_toml_file_is_handled_properly() {
         use std::io::Write;
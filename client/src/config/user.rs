@@ -1,5 +1,7 @@
 //! User configuration view.
 
+use std::num::NonZeroUsize;
+
 use error_stack::{Report, ResultExt};
 use iroha_config_base::{
     attach::ConfigValueAndOrigin,
@@ -26,12 +28,16 @@ pub struct Root {
     pub account: Account,
     #[config(nested)]
     pub transaction: Transaction,
+    #[config(nested)]
+    pub http: Http,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum ParseError {
     #[error("Transaction status timeout should be smaller than its time-to-live")]
     TxTimeoutVsTtl,
+    #[error("HTTP request timeout should be longer than the connect timeout")]
+    HttpRequestTimeoutVsConnect,
     #[error("Failed to construct a key pair from provided public and private keys")]
     KeyPair,
     #[error("Unsupported URL scheme: `{scheme}`")]
@@ -61,6 +67,12 @@ impl Root {
                     status_timeout_ms: tx_timeout,
                     nonce: tx_add_nonce,
                 },
+            http:
+                Http {
+                    pool_size: http_pool_size,
+                    connect_timeout_ms: http_connect_timeout,
+                    request_timeout_ms: http_request_timeout,
+                },
         } = self;
 
         let mut emitter = Emitter::new();
@@ -77,6 +89,14 @@ impl Root {
             )
         }
 
+        if http_request_timeout.value() < http_connect_timeout.value() {
+            emitter.emit(
+                Report::new(ParseError::HttpRequestTimeoutVsConnect)
+                    .attach_printable(http_request_timeout.clone().into_attachment())
+                    .attach_printable(http_connect_timeout.clone().into_attachment()),
+            )
+        }
+
         match torii_url.value().scheme() {
             "http" | "https" => {}
             scheme => emitter.emit(
@@ -118,6 +138,11 @@ impl Root {
             transaction_ttl: tx_ttl.into_value().get(),
             transaction_status_timeout: tx_timeout.into_value().get(),
             transaction_add_nonce: tx_add_nonce,
+            http: super::Http {
+                pool_size: http_pool_size,
+                connect_timeout: http_connect_timeout.into_value().get(),
+                request_timeout: http_request_timeout.into_value().get(),
+            },
         })
     }
 }
@@ -141,6 +166,17 @@ pub struct Transaction {
     pub nonce: bool,
 }
 
+#[derive(Debug, Clone, ReadConfig)]
+#[allow(missing_docs)]
+pub struct Http {
+    #[config(default = "super::DEFAULT_HTTP_POOL_SIZE")]
+    pub pool_size: NonZeroUsize,
+    #[config(default = "super::DEFAULT_HTTP_CONNECT_TIMEOUT.into()")]
+    pub connect_timeout_ms: WithOrigin<DurationMs>,
+    #[config(default = "super::DEFAULT_HTTP_REQUEST_TIMEOUT.into()")]
+    pub request_timeout_ms: WithOrigin<DurationMs>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
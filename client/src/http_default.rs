@@ -1,97 +1,259 @@
 //! Defaults for various items used in communication over http(s).
-use std::{net::TcpStream, str::FromStr};
+use std::{
+    io::Read,
+    net::TcpStream,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
 
-use attohttpc::{
-    body as atto_body, RequestBuilder as AttoHttpRequestBuilder, Response as AttoHttpResponse,
+use eyre::{eyre, Result, WrapErr};
+use http::{
+    header::{HeaderName, HeaderValue},
+    HeaderMap, StatusCode,
 };
-use eyre::{eyre, Error, Result, WrapErr};
-use http::header::HeaderName;
 use tungstenite::{client::IntoClientRequest, stream::MaybeTlsStream, WebSocket};
 pub use tungstenite::{Error as WebSocketError, Message as WebSocketMessage};
 use url::Url;
 
-use crate::http::{Method, RequestBuilder, Response};
+use crate::{
+    config::Http as HttpConfig,
+    http::{Method, RequestBuilder, Response},
+};
 
 type Bytes = Vec<u8>;
-type AttoHttpRequestBuilderWithBytes = AttoHttpRequestBuilder<atto_body::Bytes<Bytes>>;
 
 fn header_name_from_str(str: &str) -> Result<HeaderName> {
     HeaderName::from_str(str).wrap_err_with(|| format!("Failed to parse header name {str}"))
 }
 
-/// Default request builder implemented on top of `attohttpc` crate.
+/// Hooks notified about the requests sent through a [`ConnectionPool`],
+/// e.g. to export metrics of the client.
+pub trait RequestObserver: Send + Sync {
+    /// The request is about to be sent
+    fn started(&self, _method: &Method, _url: &Url) {}
+
+    /// The response was received, `status` is `None` if the request failed
+    fn finished(
+        &self,
+        _method: &Method,
+        _url: &Url,
+        _status: Option<StatusCode>,
+        _elapsed: Duration,
+    ) {
+    }
+}
+
+/// Pool of keep-alive connections to the peers.
+///
+/// Connections are reused by subsequent requests to the same peer instead of being
+/// opened for every request. Clones share the same connections.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    agent: ureq::Agent,
+    request_timeout: Duration,
+    in_flight: Arc<AtomicUsize>,
+    observer: Option<Arc<dyn RequestObserver>>,
+}
+
+impl core::fmt::Debug for ConnectionPool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ConnectionPool")
+            .field("request_timeout", &self.request_timeout)
+            .field("in_flight", &self.in_flight())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ConnectionPool {
+    fn default() -> Self {
+        Self::new(HttpConfig::default())
+    }
+}
+
+impl ConnectionPool {
+    /// Construct a pool with the given settings
+    pub fn new(
+        HttpConfig {
+            pool_size,
+            connect_timeout,
+            request_timeout,
+        }: HttpConfig,
+    ) -> Self {
+        let builder = ureq::AgentBuilder::new()
+            .max_idle_connections_per_host(pool_size.get())
+            .timeout_connect(connect_timeout);
+        #[cfg(any(feature = "tls-native", feature = "tls-native-vendored"))]
+        let builder = match native_tls::TlsConnector::new() {
+            Ok(connector) => builder.tls_connector(Arc::new(connector)),
+            Err(error) => {
+                iroha_logger::warn!(%error, "Failed to initialize native TLS, https requests will fail");
+                builder
+            }
+        };
+
+        Self {
+            agent: builder.build(),
+            request_timeout,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            observer: None,
+        }
+    }
+
+    /// Pool with the default settings shared by all requests sent without a pool,
+    /// see [`DefaultRequest::send`]
+    pub fn shared() -> &'static Self {
+        static SHARED: OnceLock<ConnectionPool> = OnceLock::new();
+        SHARED.get_or_init(Self::default)
+    }
+
+    /// Notify `observer` about every request sent through the pool
+    #[must_use]
+    pub fn with_observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Number of requests sent through the pool which haven't been answered yet
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    fn send(&self, request: DefaultRequest) -> Result<Response<Bytes>> {
+        let DefaultRequest {
+            method,
+            url,
+            headers,
+            body,
+        } = request;
+        let mut call = self
+            .agent
+            .request_url(method.as_str(), &url)
+            .timeout(self.request_timeout);
+        for (name, value) in &headers {
+            call = call.set(
+                name.as_str(),
+                value
+                    .to_str()
+                    .wrap_err_with(|| format!("Header {name} isn't a valid string"))?,
+            );
+        }
+
+        if let Some(observer) = &self.observer {
+            observer.started(&method, &url);
+        }
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        let started_at = Instant::now();
+        let response = match call.send_bytes(&body) {
+            // Error statuses are handled by the callers
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                ClientResponse(response).try_into()
+            }
+            Err(error) => Err(eyre::Error::new(error)),
+        }
+        .wrap_err_with(|| format!("Failed to send http {method} request to {url}"));
+
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        if let Some(observer) = &self.observer {
+            let status = response.as_ref().ok().map(Response::status);
+            observer.finished(&method, &url, status, started_at.elapsed());
+        }
+        response
+    }
+}
+
+/// Default request builder implemented on top of `ureq` crate.
 #[derive(Debug)]
 pub struct DefaultRequestBuilder {
-    inner: Result<AttoHttpRequestBuilder>,
-    body: Option<Vec<u8>>,
+    inner: Result<DefaultRequest>,
 }
 
 impl DefaultRequestBuilder {
-    /// Apply `.and_then()` semantics to the inner `Result` with underlying request builder.
+    /// Apply `.and_then()` semantics to the inner `Result` with underlying request.
     fn and_then<F>(self, fun: F) -> Self
     where
-        F: FnOnce(AttoHttpRequestBuilder) -> Result<AttoHttpRequestBuilder>,
+        F: FnOnce(DefaultRequest) -> Result<DefaultRequest>,
     {
         Self {
             inner: self.inner.and_then(fun),
-            ..self
         }
     }
 
     /// Build request by consuming self.
     pub fn build(self) -> Result<DefaultRequest> {
         self.inner
-            .map(|b| DefaultRequest(b.bytes(self.body.map_or_else(Vec::new, |vec| vec))))
     }
 }
 
 /// Request built by [`DefaultRequestBuilder`].
 #[derive(Debug)]
-pub struct DefaultRequest(AttoHttpRequestBuilderWithBytes);
+pub struct DefaultRequest {
+    method: Method,
+    url: Url,
+    headers: HeaderMap,
+    body: Bytes,
+}
 
 impl DefaultRequest {
-    /// Sends itself and returns byte response
+    /// Sends itself through the [shared](ConnectionPool::shared) pool and returns byte response
     ///
     /// # Errors
     /// Fails if request building and sending fails or response transformation fails
-    pub fn send(mut self) -> Result<Response<Bytes>> {
-        let (method, url) = {
-            let inspect = self.0.inspect();
-            (inspect.method().clone(), inspect.url().clone())
-        };
-
-        let response = self
-            .0
-            .send()
-            .wrap_err_with(|| format!("Failed to send http {method} request to {url}"))?;
+    pub fn send(self) -> Result<Response<Bytes>> {
+        self.send_with(ConnectionPool::shared())
+    }
 
-        ClientResponse(response).try_into()
+    /// Sends itself through the connections of `pool` and returns byte response
+    ///
+    /// # Errors
+    /// Fails if request building and sending fails or response transformation fails
+    pub fn send_with(self, pool: &ConnectionPool) -> Result<Response<Bytes>> {
+        pool.send(self)
     }
 }
 
 impl RequestBuilder for DefaultRequestBuilder {
     fn new(method: Method, url: Url) -> Self {
         Self {
-            inner: Ok(AttoHttpRequestBuilder::new(method, url)),
-            body: None,
+            inner: Ok(DefaultRequest {
+                method,
+                url,
+                headers: HeaderMap::new(),
+                body: Vec::new(),
+            }),
         }
     }
 
     fn header<K: AsRef<str>, V: ToString + ?Sized>(self, key: K, value: &V) -> Self {
-        self.and_then(|builder| {
-            Ok(builder.header(header_name_from_str(key.as_ref())?, value.to_string()))
+        self.and_then(|mut request| {
+            let value = value.to_string();
+            let value = HeaderValue::from_str(&value)
+                .wrap_err_with(|| format!("Failed to parse header value {value}"))?;
+            request
+                .headers
+                .insert(header_name_from_str(key.as_ref())?, value);
+            Ok(request)
         })
     }
 
     fn param<K: AsRef<str>, V: ToString + ?Sized>(self, key: K, value: &V) -> Self {
-        self.and_then(|b| Ok(b.param(key, value.to_string())))
+        self.and_then(|mut request| {
+            request
+                .url
+                .query_pairs_mut()
+                .append_pair(key.as_ref(), &value.to_string());
+            Ok(request)
+        })
     }
 
     fn body(self, data: Vec<u8>) -> Self {
-        Self {
-            body: Some(data),
-            ..self
-        }
+        self.and_then(|mut request| {
+            request.body = data;
+            Ok(request)
+        })
     }
 }
 
@@ -168,10 +330,10 @@ pub type WebSocketStream = WebSocket<MaybeTlsStream<TcpStream>>;
 pub type AsyncWebSocketStream =
     tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
 
-struct ClientResponse(AttoHttpResponse);
+struct ClientResponse(ureq::Response);
 
 impl TryFrom<ClientResponse> for Response<Bytes> {
-    type Error = Error;
+    type Error = eyre::Error;
 
     fn try_from(response: ClientResponse) -> Result<Self> {
         let ClientResponse(response) = response;
@@ -179,16 +341,19 @@ impl TryFrom<ClientResponse> for Response<Bytes> {
         let headers = builder
             .headers_mut()
             .ok_or_else(|| eyre!("Failed to get headers map reference."))?;
-        for (key, value) in response.headers() {
-            headers.insert(key, value.clone());
+        for name in response.headers_names() {
+            let key = header_name_from_str(&name)?;
+            for value in response.all(&name) {
+                headers.append(&key, HeaderValue::from_str(value)?);
+            }
         }
+        let mut bytes = Vec::new();
         response
-            .bytes()
-            .wrap_err("Failed to get response as bytes")
-            .and_then(|bytes| {
-                builder
-                    .body(bytes)
-                    .wrap_err("Failed to construct response bytes body")
-            })
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .wrap_err("Failed to get response as bytes")?;
+        builder
+            .body(bytes)
+            .wrap_err("Failed to construct response bytes body")
     }
 }
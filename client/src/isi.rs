@@ -0,0 +1,234 @@
+//! [`isi!`](crate::isi!) DSL for constructing instructions.
+//!
+//! ```
+//! use iroha::{data_model::prelude::*, isi};
+//!
+//! # fn main() -> Result<(), iroha::data_model::ParseError> {
+//! let alice = "ed0120CE7FA46C9DCE7EA4B125E2E36BDB63EA33073E7590AC92816AE1E861B7048B03@wonderland";
+//! let bob = "ed012004FF5B81046DDCCF19E2E451C45DFB6F53759D4EB30FA2EFA807284D1CC33016@wonderland";
+//! let rose = format!("rose##{alice}");
+//!
+//! let instructions: Vec<InstructionBox> = vec![
+//!     isi!(register domain "garden")?,
+//!     isi!(mint 100_u32 to (rose.as_str()))?,
+//!     isi!(transfer 50_u32 (rose.as_str()) to bob)?,
+//! ];
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Identifiers are given either as IDs or as strings, which are parsed when the instruction
+//! is built, so building an instruction fails if one of the strings isn't a valid identifier.
+//! There are no conditional instructions, so conditions like "if the balance is enough" have
+//! to be checked by the executor or by a trigger.
+
+use crate::data_model::{prelude::*, ParseError};
+
+/// Identifier which is either given as is or parsed from a string
+pub trait IntoId<Id> {
+    /// Convert into the identifier
+    ///
+    /// # Errors
+    /// If the string isn't a valid identifier
+    fn into_id(self) -> Result<Id, ParseError>;
+}
+
+macro_rules! impl_into_id {
+    ($($id:ty),+ $(,)?) => {$(
+        impl IntoId<$id> for $id {
+            fn into_id(self) -> Result<$id, ParseError> {
+                Ok(self)
+            }
+        }
+
+        impl IntoId<$id> for &$id {
+            fn into_id(self) -> Result<$id, ParseError> {
+                Ok(self.clone())
+            }
+        }
+
+        impl IntoId<$id> for &str {
+            fn into_id(self) -> Result<$id, ParseError> {
+                self.parse()
+            }
+        }
+
+        impl IntoId<$id> for String {
+            fn into_id(self) -> Result<$id, ParseError> {
+                self.as_str().into_id()
+            }
+        }
+    )+};
+}
+
+impl_into_id!(DomainId, AccountId, AssetDefinitionId, AssetId);
+
+#[doc(hidden)]
+pub fn id<Id>(id: impl IntoId<Id>) -> Result<Id, ParseError> {
+    id.into_id()
+}
+
+/// Construct an [`InstructionBox`] from a short English-like description.
+///
+/// Supported forms, where every argument is a single token tree (a literal, a variable
+/// or a parenthesized expression):
+///
+/// - `register domain <domain>`
+/// - `register account <account>`
+/// - `register asset <asset definition>` registers a numeric asset definition
+/// - `unregister domain|account|asset <id>`
+/// - `mint <quantity> to <asset>`
+/// - `burn <quantity> from <asset>`
+/// - `transfer <quantity> <asset> to <account>`
+/// - `transfer domain <domain> from <account> to <account>`
+/// - `transfer asset <asset definition> from <account> to <account>`
+///
+/// Evaluates to `Result<InstructionBox, ParseError>`.
+///
+/// # Errors
+/// If an identifier given as a string is invalid
+#[macro_export]
+macro_rules! isi {
+    (register domain $domain:tt) => {
+        $crate::isi::id($domain).map(|domain| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Register::domain($crate::data_model::domain::Domain::new(
+                    domain,
+                )),
+            )
+        })
+    };
+    (register account $account:tt) => {
+        $crate::isi::id($account).map(|account| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Register::account(
+                    $crate::data_model::account::Account::new(account),
+                ),
+            )
+        })
+    };
+    (register asset $definition:tt) => {
+        $crate::isi::id($definition).map(|definition| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Register::asset_definition(
+                    $crate::data_model::asset::AssetDefinition::numeric(definition),
+                ),
+            )
+        })
+    };
+    (unregister domain $domain:tt) => {
+        $crate::isi::id($domain).map(|domain| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Unregister::domain(domain),
+            )
+        })
+    };
+    (unregister account $account:tt) => {
+        $crate::isi::id($account).map(|account| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Unregister::account(account),
+            )
+        })
+    };
+    (unregister asset $definition:tt) => {
+        $crate::isi::id($definition).map(|definition| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Unregister::asset_definition(definition),
+            )
+        })
+    };
+    (mint $quantity:tt to $asset:tt) => {
+        $crate::isi::id($asset).map(|asset| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Mint::asset_numeric($quantity, asset),
+            )
+        })
+    };
+    (burn $quantity:tt from $asset:tt) => {
+        $crate::isi::id($asset).map(|asset| {
+            $crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Burn::asset_numeric($quantity, asset),
+            )
+        })
+    };
+    (transfer domain $domain:tt from $from:tt to $to:tt) => {
+        match (
+            $crate::isi::id($from),
+            $crate::isi::id($domain),
+            $crate::isi::id($to),
+        ) {
+            (Ok(from), Ok(domain), Ok(to)) => Ok($crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Transfer::domain(from, domain, to),
+            )),
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => Err(error),
+        }
+    };
+    (transfer asset $definition:tt from $from:tt to $to:tt) => {
+        match (
+            $crate::isi::id($from),
+            $crate::isi::id($definition),
+            $crate::isi::id($to),
+        ) {
+            (Ok(from), Ok(definition), Ok(to)) => {
+                Ok($crate::data_model::isi::InstructionBox::from(
+                    $crate::data_model::isi::Transfer::asset_definition(from, definition, to),
+                ))
+            }
+            (Err(error), _, _) | (_, Err(error), _) | (_, _, Err(error)) => Err(error),
+        }
+    };
+    (transfer $quantity:tt $asset:tt to $to:tt) => {
+        match ($crate::isi::id($asset), $crate::isi::id($to)) {
+            (Ok(asset), Ok(to)) => Ok($crate::data_model::isi::InstructionBox::from(
+                $crate::data_model::isi::Transfer::asset_numeric(asset, $quantity, to),
+            )),
+            (Err(error), _) | (_, Err(error)) => Err(error),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use test_samples::gen_account_in;
+
+    use super::*;
+
+    #[test]
+    fn instructions_are_lowered_to_constructors() {
+        let (alice, _) = gen_account_in("wonderland");
+        let (bob, _) = gen_account_in("wonderland");
+        let rose: AssetId = format!("rose##{alice}").parse().unwrap();
+
+        assert_eq!(
+            isi!(register domain "garden").unwrap(),
+            InstructionBox::from(Register::domain(Domain::new("garden".parse().unwrap())))
+        );
+        assert_eq!(
+            isi!(register asset "rose#wonderland").unwrap(),
+            InstructionBox::from(Register::asset_definition(AssetDefinition::numeric(
+                "rose#wonderland".parse().unwrap()
+            )))
+        );
+        assert_eq!(
+            isi!(mint 100_u32 to (rose.clone())).unwrap(),
+            InstructionBox::from(Mint::asset_numeric(100_u32, rose.clone()))
+        );
+        assert_eq!(
+            isi!(transfer 50_u32 (format!("rose##{alice}")) to (&bob)).unwrap(),
+            InstructionBox::from(Transfer::asset_numeric(rose, 50_u32, bob.clone()))
+        );
+        assert_eq!(
+            isi!(transfer domain "garden" from (&alice) to (&bob)).unwrap(),
+            InstructionBox::from(Transfer::domain(alice, "garden".parse().unwrap(), bob))
+        );
+    }
+
+    #[test]
+    fn invalid_id_is_reported() {
+        let (alice, _) = gen_account_in("wonderland");
+
+        assert!(isi!(unregister account "alice").is_err());
+        assert!(isi!(transfer domain "garden" from (&alice) to "bob").is_err());
+        assert!(isi!(transfer 1_u32 "rose" to (&alice)).is_err());
+    }
+}
@@ -1,10 +1,16 @@
 //! Crate contains client which talks to Iroha network via http
 
+pub mod balancer;
 pub mod client;
+pub mod composer;
 pub mod config;
 pub mod http;
 mod http_default;
+pub mod isi;
+pub mod mirror;
 pub mod query;
+pub mod signer;
+pub mod trace;
 
 pub mod samples {
     //! Module containing sample configurations for tests and benchmarks.
@@ -16,7 +22,7 @@ pub mod samples {
     use crate::{
         client::{Client, StatusResponseHandler},
         config::{
-            Config, DEFAULT_TRANSACTION_NONCE, DEFAULT_TRANSACTION_STATUS_TIMEOUT,
+            Config, Http, DEFAULT_TRANSACTION_NONCE, DEFAULT_TRANSACTION_STATUS_TIMEOUT,
             DEFAULT_TRANSACTION_TIME_TO_LIVE,
         },
         crypto::KeyPair,
@@ -38,6 +44,7 @@ pub mod samples {
             transaction_ttl: DEFAULT_TRANSACTION_TIME_TO_LIVE,
             transaction_status_timeout: DEFAULT_TRANSACTION_STATUS_TIMEOUT,
             transaction_add_nonce: DEFAULT_TRANSACTION_NONCE,
+            http: Http::default(),
         }
     }
 
@@ -47,7 +54,7 @@ pub mod samples {
     /// Fails if sending request or decoding fails
     pub fn get_status_json(client: &Client) -> Result<Status> {
         let req = client.prepare_status_request::<DefaultRequestBuilder>();
-        let resp = req.build()?.send()?;
+        let resp = req.build()?.send_with(&client.http_pool)?;
         StatusResponseHandler::handle(&resp)
     }
 }
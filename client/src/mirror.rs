@@ -0,0 +1,312 @@
+//! Client-side mirror of a subset of the world state.
+//!
+//! [`StateMirror`] keeps the accounts and assets it's configured with up to date by
+//! listening to their data events and refetching an entity whenever an event about it
+//! arrives, so that repeated reads don't have to query the peer. While the event stream
+//! is down, cached entries are served only until they're older than the configured
+//! staleness bound, after which reads fall back to querying the peer.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use eyre::Result;
+use iroha_logger::prelude::*;
+
+use crate::{client::Client, data_model::prelude::*};
+
+/// Default maximum age of the entries served while the event stream is down
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(5);
+/// Time to wait before resubscribing to the events after the stream ends
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Builder of a [`StateMirror`], see [`StateMirror::builder`]
+#[derive(Debug)]
+pub struct StateMirrorBuilder {
+    client: Client,
+    accounts: Vec<AccountId>,
+    assets: Vec<AssetId>,
+    max_staleness: Duration,
+}
+
+impl StateMirrorBuilder {
+    /// Mirror the account with the given `id`
+    #[must_use]
+    pub fn account(mut self, id: AccountId) -> Self {
+        self.accounts.push(id);
+        self
+    }
+
+    /// Mirror the asset with the given `id`
+    #[must_use]
+    pub fn asset(mut self, id: AssetId) -> Self {
+        self.assets.push(id);
+        self
+    }
+
+    /// Serve cached entries at most `max_staleness` old while the event stream is down.
+    /// [`DEFAULT_MAX_STALENESS`] by default.
+    #[must_use]
+    pub fn max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Fetch the mirrored entities and start following their changes in the background
+    ///
+    /// # Errors
+    /// Fails if the initial fetch of the entities fails
+    pub fn start(self) -> Result<StateMirror> {
+        let inner = Arc::new(Inner {
+            client: self.client,
+            max_staleness: self.max_staleness,
+            accounts: RwLock::new(HashMap::new()),
+            assets: RwLock::new(HashMap::new()),
+            connected: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+        });
+        for id in self.accounts {
+            let account = inner.fetch_account(&id)?;
+            Inner::insert(&inner.accounts, id, account);
+        }
+        for id in self.assets {
+            let asset = inner.fetch_asset(&id)?;
+            Inner::insert(&inner.assets, id, asset);
+        }
+
+        thread::spawn({
+            let inner = Arc::clone(&inner);
+            move || inner.follow_events()
+        });
+
+        Ok(StateMirror { inner })
+    }
+}
+
+/// In-memory view of the configured accounts and assets, see the [module documentation](self).
+///
+/// Events are followed until the mirror is dropped.
+#[derive(Debug)]
+pub struct StateMirror {
+    inner: Arc<Inner>,
+}
+
+impl StateMirror {
+    /// Start building a mirror of the entities visible to `client`
+    pub fn builder(client: Client) -> StateMirrorBuilder {
+        StateMirrorBuilder {
+            client,
+            accounts: Vec::new(),
+            assets: Vec::new(),
+            max_staleness: DEFAULT_MAX_STALENESS,
+        }
+    }
+
+    /// Account with the given `id`, `None` if it doesn't exist.
+    /// Accounts which aren't mirrored are queried from the peer.
+    ///
+    /// # Errors
+    /// Fails if the account has to be queried and the query fails
+    pub fn account(&self, id: &AccountId) -> Result<Option<Account>> {
+        if let Some(account) = self.inner.cached(&self.inner.accounts, id) {
+            return Ok(account);
+        }
+        let account = self.inner.fetch_account(id)?;
+        Inner::update(&self.inner.accounts, id, account.clone());
+        Ok(account)
+    }
+
+    /// Asset with the given `id`, `None` if it doesn't exist.
+    /// Assets which aren't mirrored are queried from the peer.
+    ///
+    /// # Errors
+    /// Fails if the asset has to be queried and the query fails
+    pub fn asset(&self, id: &AssetId) -> Result<Option<Asset>> {
+        if let Some(asset) = self.inner.cached(&self.inner.assets, id) {
+            return Ok(asset);
+        }
+        let asset = self.inner.fetch_asset(id)?;
+        Inner::update(&self.inner.assets, id, asset.clone());
+        Ok(asset)
+    }
+
+    /// Whether the mirror is currently following the events, so that its entries are up to date
+    pub fn is_connected(&self) -> bool {
+        self.inner.connected.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for StateMirror {
+    fn drop(&mut self) {
+        self.inner.stopped.store(true, Ordering::Release);
+    }
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    /// `None` if the entity doesn't exist
+    value: Option<T>,
+    fetched_at: Instant,
+}
+
+type Entries<K, V> = RwLock<HashMap<K, Entry<V>>>;
+
+#[derive(Debug)]
+struct Inner {
+    client: Client,
+    max_staleness: Duration,
+    accounts: Entries<AccountId, Account>,
+    assets: Entries<AssetId, Asset>,
+    /// Whether the event stream is up and the entries were refetched after it was established
+    connected: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl Inner {
+    /// Cached value of a mirrored entity, `None` if it isn't mirrored or is too stale
+    fn cached<K, V>(&self, entries: &Entries<K, V>, id: &K) -> Option<Option<V>>
+    where
+        K: Eq + std::hash::Hash,
+        V: Clone,
+    {
+        let entries = entries.read().expect("Lock isn't poisoned");
+        let entry = entries.get(id)?;
+        let is_fresh = self.connected.load(Ordering::Acquire)
+            || entry.fetched_at.elapsed() <= self.max_staleness;
+        is_fresh.then(|| entry.value.clone())
+    }
+
+    fn fetch_account(&self, id: &AccountId) -> Result<Option<Account>> {
+        Ok(self
+            .client
+            .query(FindAccounts::new())
+            .filter_with(|account| account.id.eq(id.clone()))
+            .execute_single_opt()?)
+    }
+
+    fn fetch_asset(&self, id: &AssetId) -> Result<Option<Asset>> {
+        Ok(self
+            .client
+            .query(FindAssets::new())
+            .filter_with(|asset| asset.id.eq(id.clone()))
+            .execute_single_opt()?)
+    }
+
+    fn refresh_account(&self, id: &AccountId) -> Result<()> {
+        if Self::is_mirrored(&self.accounts, id) {
+            Self::update(&self.accounts, id, self.fetch_account(id)?);
+        }
+        Ok(())
+    }
+
+    fn refresh_asset(&self, id: &AssetId) -> Result<()> {
+        if Self::is_mirrored(&self.assets, id) {
+            Self::update(&self.assets, id, self.fetch_asset(id)?);
+        }
+        Ok(())
+    }
+
+    fn insert<K, V>(entries: &Entries<K, V>, id: K, value: Option<V>)
+    where
+        K: Eq + std::hash::Hash,
+    {
+        let entry = Entry {
+            value,
+            fetched_at: Instant::now(),
+        };
+        entries
+            .write()
+            .expect("Lock isn't poisoned")
+            .insert(id, entry);
+    }
+
+    /// Update the entry of `id` if it's mirrored
+    fn update<K, V>(entries: &Entries<K, V>, id: &K, value: Option<V>)
+    where
+        K: Eq + std::hash::Hash,
+    {
+        if let Some(entry) = entries.write().expect("Lock isn't poisoned").get_mut(id) {
+            *entry = Entry {
+                value,
+                fetched_at: Instant::now(),
+            };
+        }
+    }
+
+    fn is_mirrored<K, V>(entries: &Entries<K, V>, id: &K) -> bool
+    where
+        K: Eq + std::hash::Hash,
+    {
+        entries
+            .read()
+            .expect("Lock isn't poisoned")
+            .contains_key(id)
+    }
+
+    fn follow_events(&self) {
+        while !self.stopped.load(Ordering::Acquire) {
+            if let Err(error) = self.follow_events_once() {
+                warn!(?error, "State mirror lost the event stream");
+            }
+            self.connected.store(false, Ordering::Release);
+            thread::sleep(RECONNECT_INTERVAL);
+        }
+    }
+
+    /// Subscribe to the events of the mirrored entities and apply them until the stream ends
+    fn follow_events_once(&self) -> Result<()> {
+        let account_ids = Self::mirrored_ids(&self.accounts);
+        let asset_ids = Self::mirrored_ids(&self.assets);
+        let filters = account_ids
+            .iter()
+            .cloned()
+            .map(|id| DataEventFilter::from(AccountEventFilter::new().for_account(id)))
+            .chain(
+                asset_ids
+                    .iter()
+                    .cloned()
+                    .map(|id| DataEventFilter::from(AssetEventFilter::new().for_asset(id))),
+            )
+            .collect::<Vec<_>>();
+        let events = self.client.listen_for_events(filters)?;
+
+        // Changes made before the subscription have to be caught up with
+        for id in &account_ids {
+            self.refresh_account(id)?;
+        }
+        for id in &asset_ids {
+            self.refresh_asset(id)?;
+        }
+        self.connected.store(true, Ordering::Release);
+
+        for event in events {
+            if self.stopped.load(Ordering::Acquire) {
+                break;
+            }
+            let EventBox::Data(DataEvent::Domain(DomainEvent::Account(event))) = event? else {
+                continue;
+            };
+            if let AccountEvent::Asset(event) = &event {
+                self.refresh_asset(event.origin())?;
+            }
+            self.refresh_account(event.origin())?;
+        }
+
+        Ok(())
+    }
+
+    fn mirrored_ids<K: Clone, V>(entries: &Entries<K, V>) -> Vec<K> {
+        entries
+            .read()
+            .expect("Lock isn't poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
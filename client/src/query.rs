@@ -24,7 +24,7 @@ use crate::{
     client::{join_torii_url, Client, QueryResult, ResponseReport},
     data_model::query::Query,
     http::{Method as HttpMethod, RequestBuilder},
-    http_default::DefaultRequestBuilder,
+    http_default::{ConnectionPool, DefaultRequestBuilder},
 };
 
 #[derive(Debug)]
@@ -33,6 +33,7 @@ struct ClientQueryRequestHead {
     headers: HashMap<String, String>,
     account_id: AccountId,
     key_pair: KeyPair,
+    http_pool: ConnectionPool,
 }
 
 impl ClientQueryRequestHead {
@@ -49,6 +50,10 @@ impl ClientQueryRequestHead {
         .headers(self.headers.clone())
         .body(query.encode())
     }
+
+    fn send(&self, query: QueryRequest) -> Result<http::Response<Vec<u8>>> {
+        self.assemble(query).build()?.send_with(&self.http_pool)
+    }
 }
 
 /// Decode a raw response from the node's query endpoint
@@ -150,7 +155,7 @@ impl QueryExecutor for Client {
 
         let request = QueryRequest::Singular(query);
 
-        let response = request_head.assemble(request).build()?.send()?;
+        let response = request_head.send(request)?;
         let response = decode_singular_query_response(&response)?;
 
         Ok(response)
@@ -164,7 +169,7 @@ impl QueryExecutor for Client {
 
         let request = QueryRequest::Start(query);
 
-        let response = request_head.assemble(request).build()?.send()?;
+        let response = request_head.send(request)?;
         let response = decode_iterable_query_response(&response)?;
 
         let (batch, cursor) = response.into_parts();
@@ -187,7 +192,7 @@ impl QueryExecutor for Client {
 
         let request = QueryRequest::Continue(cursor);
 
-        let response = request_head.assemble(request).build()?.send()?;
+        let response = request_head.send(request)?;
         let response = decode_iterable_query_response(&response)?;
 
         let (batch, cursor) = response.into_parts();
@@ -211,6 +216,7 @@ impl Client {
             headers: self.headers.clone(),
             account_id: self.account.clone(),
             key_pair: self.key_pair.clone(),
+            http_pool: self.http_pool.clone(),
         }
     }
 
@@ -261,7 +267,7 @@ impl Client {
 
         let request = QueryRequest::Continue(cursor);
 
-        let response = request_head.assemble(request).build()?.send()?;
+        let response = request_head.send(request)?;
         let response = decode_query_response(&response)?;
 
         Ok(response)
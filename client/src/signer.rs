@@ -0,0 +1,238 @@
+//! Signing of transactions outside of the client.
+//!
+//! [`TransactionSigner`] defers signing to whatever holds the key of the authority, e.g. a
+//! hardware wallet or a remote KMS, which only ever sees [`TransactionBuilder::signing_hash`].
+//! [`SignatureCollector`] gathers the signatures of transactions of several authorities as they
+//! arrive, so that the transactions can be submitted together once all of them are signed.
+
+use eyre::Result;
+
+use crate::{
+    crypto::{Hash, KeyPair, PublicKey, Signature},
+    data_model::prelude::*,
+};
+
+/// Signer of transactions on behalf of their authority
+pub trait TransactionSigner {
+    /// Key the signatures of this signer are verified with
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign the `hash` of a transaction payload
+    ///
+    /// # Errors
+    /// If the signer fails to sign, e.g. the device is disconnected or the user declined
+    fn sign(&self, hash: &Hash) -> Result<Signature>;
+}
+
+impl TransactionSigner for KeyPair {
+    fn public_key(&self) -> PublicKey {
+        KeyPair::public_key(self).clone()
+    }
+
+    fn sign(&self, hash: &Hash) -> Result<Signature> {
+        Ok(Signature::new(self.private_key(), hash.as_ref()))
+    }
+}
+
+/// Error of signing a transaction
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum SignError {
+    /// Signer failed to sign the transaction
+    Signer(#[source] eyre::Report),
+    /// Signer isn't the authority `{0}` of the transaction
+    NotAuthority(AccountId),
+    /// Signature isn't made by the authority of the transaction
+    InvalidSignature(#[source] crate::crypto::Error),
+    /// There is no transaction with index {0}
+    UnknownTransaction(usize),
+    /// {0} transactions are not signed yet
+    Incomplete(usize),
+}
+
+/// Sign `transaction` with `signer`, checking that the signature is made by the authority
+/// or, for transactions submitted by a proxy, by the submitter
+///
+/// # Errors
+/// - If the signer isn't the [signer](TransactionBuilder::signer) of the transaction
+/// - If the signer fails or makes an invalid signature
+pub fn sign_transaction(
+    transaction: TransactionBuilder,
+    signer: &impl TransactionSigner,
+) -> Result<SignedTransaction, SignError> {
+    if signer.public_key() != *transaction.signer().signatory() {
+        return Err(SignError::NotAuthority(transaction.signer().clone()));
+    }
+    let signature = signer
+        .sign(&transaction.signing_hash())
+        .map_err(SignError::Signer)?;
+
+    transaction
+        .with_signature(signature)
+        .map_err(SignError::InvalidSignature)
+}
+
+fn verify_signature(
+    transaction: &TransactionBuilder,
+    signature: &Signature,
+) -> Result<(), SignError> {
+    signature
+        .verify(
+            transaction.signer().signatory(),
+            transaction.signing_hash().as_ref(),
+        )
+        .map_err(SignError::InvalidSignature)
+}
+
+/// Transactions waiting for the signatures of their authorities
+#[derive(Debug, Clone)]
+pub struct SignatureCollector {
+    transactions: Vec<(TransactionBuilder, Option<Signature>)>,
+}
+
+impl SignatureCollector {
+    /// Start collecting signatures of `transactions`
+    pub fn new(transactions: impl IntoIterator<Item = TransactionBuilder>) -> Self {
+        Self {
+            transactions: transactions
+                .into_iter()
+                .map(|transaction| (transaction, None))
+                .collect(),
+        }
+    }
+
+    /// Index, [signer](TransactionBuilder::signer) and [signing hash](TransactionBuilder::signing_hash)
+    /// of the transactions which aren't signed yet
+    pub fn pending(&self) -> impl Iterator<Item = (usize, &AccountId, Hash)> + '_ {
+        self.transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, signature))| signature.is_none())
+            .map(|(idx, (transaction, _))| (idx, transaction.signer(), transaction.signing_hash()))
+    }
+
+    /// Whether all transactions are signed
+    pub fn is_complete(&self) -> bool {
+        self.pending().next().is_none()
+    }
+
+    /// Add the `signature` of the transaction with index `idx`, replacing the previous one
+    ///
+    /// # Errors
+    /// - If there is no transaction with index `idx`
+    /// - If the signature isn't made by the authority of the transaction
+    pub fn add_signature(&mut self, idx: usize, signature: Signature) -> Result<(), SignError> {
+        let (transaction, slot) = self
+            .transactions
+            .get_mut(idx)
+            .ok_or(SignError::UnknownTransaction(idx))?;
+        verify_signature(transaction, &signature)?;
+        *slot = Some(signature);
+
+        Ok(())
+    }
+
+    /// Sign all pending transactions whose authority is `signer`, returning their number
+    ///
+    /// # Errors
+    /// If the signer fails or makes an invalid signature
+    pub fn sign_with(&mut self, signer: &impl TransactionSigner) -> Result<usize, SignError> {
+        let public_key = signer.public_key();
+        let own = self
+            .pending()
+            .filter(|(_, authority, _)| *authority.signatory() == public_key)
+            .map(|(idx, _, hash)| (idx, hash))
+            .collect::<Vec<_>>();
+
+        for (idx, hash) in &own {
+            let signature = signer.sign(hash).map_err(SignError::Signer)?;
+            self.add_signature(*idx, signature)?;
+        }
+
+        Ok(own.len())
+    }
+
+    /// Signed transactions in the original order
+    ///
+    /// # Errors
+    /// If some of the transactions aren't signed yet
+    pub fn into_signed(self) -> Result<Vec<SignedTransaction>, SignError> {
+        let pending = self.pending().count();
+        if pending > 0 {
+            return Err(SignError::Incomplete(pending));
+        }
+
+        self.transactions
+            .into_iter()
+            .map(|(transaction, signature)| {
+                transaction
+                    .with_signature(signature.expect("All transactions are signed"))
+                    .map_err(SignError::InvalidSignature)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_samples::gen_account_in;
+
+    use super::*;
+
+    fn transaction(authority: AccountId) -> TransactionBuilder {
+        TransactionBuilder::new(
+            ChainId::from("00000000-0000-0000-0000-000000000000"),
+            authority,
+        )
+        .with_instructions(Vec::<InstructionBox>::new())
+    }
+
+    #[test]
+    fn transaction_is_signed_by_external_signer() {
+        let (authority, key_pair) = gen_account_in("wonderland");
+        let builder = transaction(authority);
+        let expected = builder.clone().sign(key_pair.private_key());
+
+        let signed = sign_transaction(builder, &key_pair).unwrap();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn signer_must_be_authority() {
+        let (authority, _) = gen_account_in("wonderland");
+        let (_, stranger) = gen_account_in("wonderland");
+
+        assert!(matches!(
+            sign_transaction(transaction(authority), &stranger),
+            Err(SignError::NotAuthority(_))
+        ));
+    }
+
+    #[test]
+    fn signatures_are_collected_from_several_authorities() {
+        let (alice, alice_keys) = gen_account_in("wonderland");
+        let (bob, bob_keys) = gen_account_in("wonderland");
+        let mut collector =
+            SignatureCollector::new([transaction(alice.clone()), transaction(bob.clone())]);
+
+        assert_eq!(collector.sign_with(&alice_keys).unwrap(), 1);
+        assert!(!collector.is_complete());
+        assert!(matches!(
+            collector.clone().into_signed(),
+            Err(SignError::Incomplete(1))
+        ));
+
+        let (idx, authority, hash) = collector.pending().next().unwrap();
+        assert_eq!(*authority, bob);
+        let forged = alice_keys.sign(&hash).unwrap();
+        assert!(matches!(
+            collector.add_signature(idx, forged),
+            Err(SignError::InvalidSignature(_))
+        ));
+        let signature = bob_keys.sign(&hash).unwrap();
+        collector.add_signature(idx, signature).unwrap();
+
+        let signed = collector.into_signed().unwrap();
+        assert_eq!(signed[0].authority(), &alice);
+        assert_eq!(signed[1].authority(), &bob);
+    }
+}
@@ -0,0 +1,67 @@
+//! Human-readable rendering of the instruction trace of a simulated transaction.
+//!
+//! Request the trace with [`Client::trace_transaction`](crate::client::Client::trace_transaction)
+//! and print it with [`pretty`]. Every executed instruction is listed in order, followed by
+//! the events it emitted and the error it failed with, if any.
+
+use std::fmt::Write as _;
+
+use crate::data_model::prelude::*;
+
+/// Render `trace` as an indented tree, one line per instruction, event and error
+pub fn pretty(trace: &[InstructionTrace]) -> String {
+    let mut out = String::new();
+    for (index, step) in trace.iter().enumerate() {
+        let outcome = if step.error().is_some() {
+            "failed"
+        } else {
+            "ok"
+        };
+        writeln!(out, "#{index} [{outcome}] {}", step.instruction())
+            .expect("writing to a string doesn't fail");
+        for event in step.events() {
+            writeln!(out, "  event: {event:?}").expect("writing to a string doesn't fail");
+        }
+        if let Some(error) = step.error() {
+            writeln!(out, "  error: {error}").expect("writing to a string doesn't fail");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::data_model::isi::error::{InstructionExecutionError, RepetitionError};
+
+    #[test]
+    fn trace_is_rendered_per_instruction() {
+        let domain_id = DomainId::from_str("wonderland").unwrap();
+        let register: InstructionBox = Register::domain(Domain::new(domain_id.clone())).into();
+        let trace = [
+            InstructionTrace {
+                instruction: register.clone(),
+                events: Vec::new(),
+                error: None,
+            },
+            InstructionTrace {
+                instruction: register.clone(),
+                events: Vec::new(),
+                error: Some(InstructionExecutionError::Repetition(RepetitionError {
+                    instruction: InstructionType::Register,
+                    id: domain_id.into(),
+                })),
+            },
+        ];
+
+        let rendered = pretty(&trace);
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("#0 [ok] {register}"));
+        assert_eq!(lines[1], format!("#1 [failed] {register}"));
+        assert!(lines[2].starts_with("  error: "));
+    }
+}
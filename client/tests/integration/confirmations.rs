@@ -0,0 +1,39 @@
+use std::thread;
+
+use eyre::Result;
+use iroha::data_model::{prelude::*, Level};
+use test_network::*;
+
+fn log(client: &iroha::client::Client, msg: &str) -> SignedTransaction {
+    client.build_transaction([Log::new(Level::INFO, msg.to_owned())], Metadata::default())
+}
+
+#[test]
+fn transaction_confirmations_are_awaited() -> Result<()> {
+    let (_rt, _peer, client) = <PeerBuilder>::new().with_port(11_410).start_with_runtime();
+    wait_for_genesis_committed(&[client.clone()], 0);
+
+    // Transaction which is not committed yet
+    let pending = log(&client, "pending");
+    let awaiter = thread::spawn({
+        let client = client.clone();
+        let hash = pending.hash();
+        move || client.await_confirmations(hash, 1)
+    });
+    client.submit_transaction_blocking(&pending)?;
+    client.submit_transaction_blocking(&log(&client, "first successor"))?;
+
+    let confirmation = awaiter.join().expect("awaiter panicked")?;
+    assert_eq!(confirmation.confirmations, 1);
+    assert!(confirmation.error.is_none());
+
+    // Transaction which is already committed
+    client.submit_transaction_blocking(&log(&client, "second successor"))?;
+    let confirmation = client.await_confirmations(pending.hash(), 2)?;
+    assert_eq!(confirmation.confirmations, 2);
+
+    let height = client.get_status()?.blocks;
+    assert_eq!(confirmation.block_height.get() + 2, height);
+
+    Ok(())
+}
@@ -3,6 +3,8 @@ mod genesis;
 mod multiple_blocks_created;
 mod normal;
 mod offline_peers;
+mod partitioned_peer;
+mod read_replica;
 mod restart_peer;
 mod unregister_peer;
 mod unstable_network;
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use eyre::Result;
+use iroha::{
+    client,
+    data_model::{prelude::*, transaction::error::PeerDegraded},
+};
+use iroha_config::parameters::actual::Root as Config;
+use test_network::*;
+
+#[test]
+fn partitioned_peer_serves_queries_and_rejects_transactions() -> Result<()> {
+    let mut config = Config::test();
+    config.network.partition_timeout = Duration::from_secs(1);
+    let (_rt, mut network, client) = NetworkBuilder::new(4, Some(11_480))
+        .with_config(config)
+        .create_with_runtime();
+    wait_for_genesis_committed(&network.clients(), 0);
+    assert!(!client.get_status()?.degraded);
+
+    // Out of 4 peers, 3 are required to commit a block
+    for peer in network.peers.values_mut().take(2) {
+        peer.stop();
+    }
+
+    client.poll_with_period(Duration::from_secs(1), 30, |client| {
+        Ok(client.get_status()?.degraded)
+    })?;
+
+    let domains = client.query(client::domain::all()).execute_all()?;
+    assert!(!domains.is_empty());
+
+    let error = client
+        .submit(Register::domain(Domain::new("partitioned".parse()?)))
+        .expect_err("Degraded peer must reject transactions");
+    let degraded = error
+        .downcast_ref::<PeerDegraded>()
+        .expect("Rejection must tell that the peer is degraded");
+    assert_eq!(degraded.reachable_peers, 2);
+    assert_eq!(degraded.quorum, 3);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use eyre::Result;
+use iroha::{
+    client::{self, Client},
+    data_model::prelude::*,
+};
+use iroha_config::{parameters::actual::Root as Config, peer::Role};
+use iroha_primitives::unique_vec::UniqueVec;
+use test_network::*;
+
+#[test]
+fn read_replica_follows_chain_and_rejects_transactions() -> Result<()> {
+    let (rt, network, client) = Network::start_test_with_runtime(4, Some(11_460));
+    wait_for_genesis_committed(&network.clients(), 0);
+
+    let mut config = Config::test();
+    config.common.role = Role::ReadReplica;
+    config.sumeragi.trusted_peers.value_mut().others =
+        UniqueVec::from_iter(network.peers().map(|peer| &peer.id).cloned());
+    let replica = rt.block_on(
+        PeerBuilder::new()
+            .with_config(config)
+            .with_into_genesis(WithGenesis::None)
+            .start(),
+    );
+    let replica_client = Client::test(&replica.api_address);
+
+    let domain_id: DomainId = "replicated".parse()?;
+    client.submit_blocking(Register::domain(Domain::new(domain_id.clone())))?;
+
+    // The replica isn't registered, so it only learns about the block through block sync
+    replica_client.poll_with_period(Config::block_sync_gossip_time(), 15, |client| {
+        let domains = client
+            .query(client::domain::all())
+            .filter_with(|domain| domain.id.eq(domain_id.clone()))
+            .execute_all()?;
+        Ok(!domains.is_empty())
+    })?;
+
+    let rejected: DomainId = "rejected".parse()?;
+    assert!(replica_client
+        .submit(Register::domain(Domain::new(rejected)))
+        .is_err());
+
+    Ok(())
+}
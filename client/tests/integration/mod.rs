@@ -1,5 +1,6 @@
 mod asset;
 mod asset_propagation;
+mod confirmations;
 mod events;
 mod extra_functional;
 mod multisig;
@@ -16,5 +17,7 @@ mod transfer_domain;
 mod triggers;
 mod tx_chain_id;
 mod tx_history;
+mod tx_raw;
+mod tx_resubmission;
 mod tx_rollback;
 mod upgrade;
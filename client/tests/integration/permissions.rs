@@ -9,13 +9,16 @@ use iroha::{
         transaction::error::TransactionRejectionReason,
     },
 };
-use iroha_executor_data_model::permission::{
-    asset::{CanSetKeyValueInUserAsset, CanTransferUserAsset},
-    domain::CanSetKeyValueInDomain,
+use iroha_executor_data_model::{
+    permission::{
+        asset::{CanSetKeyValueInUserAsset, CanTransferUserAsset},
+        domain::CanSetKeyValueInDomain,
+    },
+    policy::DomainPolicy,
 };
 use iroha_genesis::GenesisBlock;
 use test_network::{PeerBuilder, *};
-use test_samples::{gen_account_in, ALICE_ID, BOB_ID};
+use test_samples::{gen_account_in, ALICE_ID, BOB_ID, BOB_KEYPAIR};
 
 #[test]
 fn genesis_transactions_are_validated_by_executor() {
@@ -478,3 +481,54 @@ fn associated_permissions_removed_from_role_on_unregister() {
                 .is_ok_and(|permission| permission == set_kv_in_domain)
         }));
 }
+
+#[test]
+fn domain_policy_applies_to_foreign_accounts() -> Result<()> {
+    let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+
+    let (_rt, _peer, iroha) = <PeerBuilder>::new().with_port(11_450).start_with_runtime();
+    wait_for_genesis_committed(&[iroha.clone()], 0);
+
+    // "bob@wonderland" owns "closed" domain which admits only its own accounts
+    let closed_id: DomainId = "closed".parse()?;
+    let (carol_id, _carol_keypair) = gen_account_in("closed");
+    let set_policy = |policy: DomainPolicy| {
+        TransactionBuilder::new(chain_id.clone(), BOB_ID.clone())
+            .with_instructions([SetKeyValue::domain(
+                closed_id.clone(),
+                DomainPolicy::key(),
+                policy,
+            )])
+            .sign(BOB_KEYPAIR.private_key())
+    };
+    let transaction = TransactionBuilder::new(chain_id.clone(), BOB_ID.clone())
+        .with_instructions::<InstructionBox>([
+            Register::domain(Domain::new(closed_id.clone())).into(),
+            Register::account(Account::new(carol_id.clone())).into(),
+        ])
+        .sign(BOB_KEYPAIR.private_key());
+    iroha.submit_transaction_blocking(&transaction)?;
+    iroha.submit_transaction_blocking(&set_policy(DomainPolicy::MembersOnly))?;
+
+    // Transfer is permitted in the source domain, but denied by the destination domain
+    let rose_id = AssetId::new("rose#wonderland".parse()?, ALICE_ID.clone());
+    let transfer = Transfer::asset_numeric(rose_id, 1_u32, carol_id);
+    let err = iroha
+        .submit_blocking(transfer.clone())
+        .expect_err("Transfer into a members-only domain should be denied");
+    let rejection_reason = err
+        .downcast_ref::<TransactionRejectionReason>()
+        .unwrap_or_else(|| panic!("Error {err} is not TransactionRejectionReason"));
+    assert_eq!(
+        rejection_reason,
+        &TransactionRejectionReason::Validation(ValidationFail::DomainDenied(DomainDenial {
+            domain: closed_id,
+            reason: DomainDenialReason::MembersOnly,
+        }))
+    );
+
+    iroha.submit_transaction_blocking(&set_policy(DomainPolicy::Open))?;
+    iroha.submit_blocking(transfer)?;
+
+    Ok(())
+}
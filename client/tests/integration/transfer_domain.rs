@@ -346,9 +346,26 @@ fn domain_owner_transfer() -> Result<()> {
 
     assert_eq!(domain.owned_by(), &alice_id);
 
+    let can_unregister_kingdom = CanUnregisterDomain {
+        domain: kingdom_id.clone(),
+    };
+    let has_permission = |account_id: &AccountId| -> Result<bool> {
+        Ok(test_client
+            .query(client::permission::by_account_id(account_id.clone()))
+            .execute_all()?
+            .iter()
+            .any(|permission| {
+                CanUnregisterDomain::try_from(permission)
+                    .is_ok_and(|permission| permission == can_unregister_kingdom)
+            }))
+    };
+    // Domain admin permissions are granted to the owner
+    assert!(has_permission(&alice_id)?);
+    assert!(!has_permission(&bob_id)?);
+
     test_client
         .submit_blocking(Transfer::domain(
-            alice_id,
+            alice_id.clone(),
             kingdom_id.clone(),
             bob_id.clone(),
         ))
@@ -360,6 +377,10 @@ fn domain_owner_transfer() -> Result<()> {
         .execute_single()?;
     assert_eq!(domain.owned_by(), &bob_id);
 
+    // ...and move to the new owner together with the domain
+    assert!(!has_permission(&alice_id)?);
+    assert!(has_permission(&bob_id)?);
+
     Ok(())
 }
 
@@ -6,6 +6,7 @@ use iroha::{
     client::{self, Client},
     crypto::KeyPair,
     data_model::{
+        parameter::TransactionParameter,
         prelude::*,
         query::error::{FindError, QueryExecutionFail},
         transaction::{Executable, WasmSmartContract},
@@ -14,6 +15,7 @@ use iroha::{
 use iroha_executor_data_model::permission::trigger::CanRegisterUserTrigger;
 use iroha_genesis::GenesisBlock;
 use iroha_logger::info;
+use nonzero_ext::nonzero;
 use test_network::{Peer as TestPeer, *};
 use test_samples::ALICE_ID;
 use tokio::runtime::Runtime;
@@ -620,6 +622,47 @@ fn unregistering_one_of_two_triggers_with_identical_wasm_should_not_cause_origin
     Ok(())
 }
 
+#[test]
+fn trigger_with_too_large_wasm_is_rejected() -> Result<()> {
+    let (_rt, _peer, test_client) = <PeerBuilder>::new().with_port(11_435).start_with_runtime();
+    wait_for_genesis_committed(&vec![test_client.clone()], 0);
+
+    let account_id = ALICE_ID.clone();
+    let trigger_id = TriggerId::from_str("mint_rose_too_large")?;
+
+    let wasm = iroha_wasm_builder::Builder::new("../wasm_samples/mint_rose_trigger")
+        .show_output()
+        .build()?
+        .optimize()?
+        .into_bytes()?;
+    let wasm = WasmSmartContract::from_compiled(wasm);
+
+    test_client.submit_blocking(SetParameter::new(Parameter::Transaction(
+        TransactionParameter::TriggerWasmSize(nonzero!(1024_u64)),
+    )))?;
+
+    let trigger = Trigger::new(
+        trigger_id.clone(),
+        Action::new(
+            wasm,
+            Repeats::Indefinitely,
+            account_id.clone(),
+            ExecuteTriggerEventFilter::new()
+                .for_trigger(trigger_id.clone())
+                .under_authority(account_id),
+        ),
+    );
+    let _err = test_client
+        .submit_blocking(Register::trigger(trigger))
+        .expect_err("Trigger with wasm exceeding the limit should be rejected");
+
+    assert!(test_client
+        .query_single(FindTriggerById { id: trigger_id })
+        .is_err());
+
+    Ok(())
+}
+
 fn get_asset_value(client: &mut Client, asset_id: AssetId) -> Numeric {
     let asset = client
         .query(client::asset::all())
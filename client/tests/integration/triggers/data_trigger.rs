@@ -1,7 +1,14 @@
+use std::{num::NonZeroU64, time::Duration};
+
 use eyre::Result;
-use iroha::{client, data_model::prelude::*};
-use test_network::*;
-use test_samples::{gen_account_in, ALICE_ID};
+use iroha::{
+    client::{self, Client},
+    data_model::{isi::InstructionType, prelude::*, transaction::WasmSmartContract},
+};
+use iroha_genesis::{Faucet, GenesisBlock};
+use test_network::{Peer as TestPeer, *};
+use test_samples::{gen_account_in, ALICE_ID, BOB_ID};
+use tokio::runtime::Runtime;
 
 #[test]
 fn must_execute_both_triggers() -> Result<()> {
@@ -64,3 +71,154 @@ fn must_execute_both_triggers() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn triggers_are_executed_in_order_of_ids_and_receipted() -> Result<()> {
+    let (_rt, _peer, test_client) = <PeerBuilder>::new().with_port(11_440).start_with_runtime();
+    wait_for_genesis_committed(&[test_client.clone()], 0);
+
+    let account_id = ALICE_ID.clone();
+    let asset_id = AssetId::new("rose#wonderland".parse()?, account_id.clone());
+    let filter = DomainEventFilter::new().for_events(DomainEventSet::Created);
+
+    // Registered first, but executed last
+    let failing_trigger_id: TriggerId = "z_burn_too_much".parse()?;
+    test_client.submit_blocking(Register::trigger(Trigger::new(
+        failing_trigger_id.clone(),
+        Action::new(
+            [Burn::asset_numeric(u32::MAX, asset_id.clone())],
+            Repeats::Indefinitely,
+            account_id.clone(),
+            filter.clone(),
+        ),
+    )))?;
+    let trigger_id: TriggerId = "a_mint_rose".parse()?;
+    test_client.submit_blocking(Register::trigger(Trigger::new(
+        trigger_id.clone(),
+        Action::new(
+            [Mint::asset_numeric(1_u32, asset_id)],
+            Repeats::Indefinitely,
+            account_id,
+            filter,
+        ),
+    )))?;
+
+    test_client.submit_blocking(Register::domain(Domain::new("neverland".parse()?)))?;
+    let height = NonZeroU64::new(test_client.get_status()?.blocks).expect("Not zero");
+
+    let receipts = test_client.query_single(client::trigger::receipts(height))?;
+    let executed = receipts
+        .iter()
+        .map(|receipt| (receipt.trigger_id(), receipt.fuel_consumed()))
+        .collect::<Vec<_>>();
+    assert_eq!(executed, [(&trigger_id, 0), (&failing_trigger_id, 0)]);
+    assert_eq!(*receipts[0].outcome(), TriggerCompletedOutcome::Success);
+    assert!(matches!(
+        receipts[1].outcome(),
+        TriggerCompletedOutcome::Failure(_)
+    ));
+
+    // Blocks past the latest one aren't committed yet
+    let next_height = height.checked_add(1).expect("Doesn't overflow");
+    assert!(test_client
+        .query_single(client::trigger::receipts(next_height))
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn delegated_trigger_transfers_on_behalf_of_delegator() -> Result<()> {
+    let (_rt, _peer, test_client) = <PeerBuilder>::new().with_port(11_441).start_with_runtime();
+    wait_for_genesis_committed(&[test_client.clone()], 0);
+
+    let rose: AssetDefinitionId = "rose#wonderland".parse()?;
+    let alice_rose = AssetId::new(rose.clone(), ALICE_ID.clone());
+    let bob_rose = AssetId::new(rose, BOB_ID.clone());
+    let get_bob_roses = || -> Result<Option<AssetValue>> {
+        Ok(test_client
+            .query(client::asset::all())
+            .filter_with(|asset| asset.id.eq(bob_rose.clone()))
+            .execute_all()?
+            .pop()
+            .map(|asset| asset.value().clone()))
+    };
+
+    // Trigger of Bob transferring roses of Alice
+    let trigger_id: TriggerId = "rose_to_bob".parse()?;
+    test_client.submit_blocking(Register::trigger(Trigger::new(
+        trigger_id.clone(),
+        Action::new(
+            [Transfer::asset_numeric(alice_rose, 1_u32, BOB_ID.clone())],
+            Repeats::Indefinitely,
+            BOB_ID.clone(),
+            DomainEventFilter::new().for_events(DomainEventSet::Created),
+        ),
+    )))?;
+    test_client.submit_blocking(DelegateToTrigger::new(
+        trigger_id.clone(),
+        vec![InstructionType::Transfer],
+    ))?;
+
+    test_client.submit_blocking(Register::domain(Domain::new("neverland".parse()?)))?;
+    assert_eq!(get_bob_roses()?, Some(AssetValue::Numeric(numeric!(1))));
+
+    test_client.submit_blocking(RevokeTriggerDelegation::new(trigger_id.clone()))?;
+    // Nothing left to revoke
+    assert!(test_client
+        .submit_blocking(RevokeTriggerDelegation::new(trigger_id))
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn faucet_mints_once_per_cooldown() -> Result<()> {
+    let wasm = iroha_wasm_builder::Builder::new("../wasm_samples/faucet_trigger")
+        .show_output()
+        .build()?
+        .optimize()?
+        .into_bytes()?;
+    let account_id = ALICE_ID.clone();
+    let cabbage_id: AssetDefinitionId = "cabbage#garden_of_live_flowers".parse()?;
+    let faucet = Faucet::new(
+        "faucet".parse()?,
+        WasmSmartContract::from_compiled(wasm),
+        account_id.clone(),
+        cabbage_id.clone(),
+        numeric!(10),
+        Duration::from_secs(3600),
+    );
+
+    let mut peer = TestPeer::new().expect("Failed to create peer");
+    let genesis =
+        GenesisBlock::test_with_instructions(faucet.into_instructions(), vec![peer.id.clone()]);
+    let rt = Runtime::test();
+    let builder = PeerBuilder::new().with_genesis(genesis).with_port(11_420);
+    rt.block_on(builder.start_with_peer(&mut peer));
+    let test_client = Client::test(&peer.api_address);
+    wait_for_genesis_committed(&[test_client.clone()], 0);
+
+    let asset_id = AssetId::new(cabbage_id, account_id.clone());
+    let get_asset_value = |iroha: &client::Client| -> Result<Numeric> {
+        let assets = iroha
+            .query(client::asset::all())
+            .filter_with(|asset| asset.id.eq(asset_id.clone()))
+            .execute_all()?;
+        Ok(match assets.first().map(Asset::value) {
+            Some(AssetValue::Numeric(val)) => *val,
+            Some(_) => panic!("Expected numeric asset value"),
+            None => Numeric::ZERO,
+        })
+    };
+    let request = SetKeyValue::account(account_id, Faucet::REQUEST_KEY.parse()?, true);
+
+    test_client.submit_blocking(request.clone())?;
+    assert_eq!(get_asset_value(&test_client)?, numeric!(10));
+
+    // Requested again within the cooldown period
+    test_client.submit_blocking(request)?;
+    assert_eq!(get_asset_value(&test_client)?, numeric!(10));
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use eyre::Result;
+use iroha::data_model::prelude::*;
+use iroha_version::prelude::*;
+use test_network::*;
+use test_samples::ALICE_ID;
+
+#[test]
+fn client_submits_pre_encoded_transaction() -> Result<()> {
+    let (_rt, _peer, client) = <PeerBuilder>::new().with_port(11_425).start_with_runtime();
+    wait_for_genesis_committed(&[client.clone()], 0);
+
+    let key: Name = "raw".parse()?;
+    let transaction = client.build_transaction(
+        [SetKeyValue::account(ALICE_ID.clone(), key.clone(), true)],
+        Metadata::default(),
+    );
+    let bytes = transaction.encode_versioned();
+
+    let hash = client.submit_raw(&bytes)?;
+    assert_eq!(hash, transaction.hash());
+
+    client.poll(|client| {
+        let value = client.query_single(FindAccountMetadata::new(ALICE_ID.clone(), key.clone()));
+        Ok(value.is_ok())
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn client_reports_offset_of_malformed_transaction() {
+    let (_rt, _peer, client) = <PeerBuilder>::new().with_port(11_430).start_with_runtime();
+    wait_for_genesis_committed(&[client.clone()], 0);
+
+    let transaction = client.build_transaction(
+        [Log::new(Level::INFO, "truncated".to_owned())],
+        Metadata::default(),
+    );
+    let mut bytes = transaction.encode_versioned();
+    bytes.truncate(bytes.len() / 2);
+
+    let error = client.submit_raw(&bytes).unwrap_err();
+    assert!(format!("{error:?}").contains("could not decode"));
+}
@@ -0,0 +1,63 @@
+use eyre::Result;
+use iroha::data_model::prelude::*;
+use test_network::*;
+use test_samples::ALICE_ID;
+
+#[test]
+fn resubmitted_transaction_reports_its_status() -> Result<()> {
+    let (_rt, _peer, client) = <PeerBuilder>::new().with_port(11_455).start_with_runtime();
+    wait_for_genesis_committed(&[client.clone()], 0);
+
+    let transaction = client.build_transaction(
+        [SetKeyValue::account(
+            ALICE_ID.clone(),
+            "resubmitted".parse()?,
+            true,
+        )],
+        Metadata::default(),
+    );
+    assert_eq!(
+        client.submit_transaction_with_status(&transaction)?,
+        SubmissionStatus::Accepted
+    );
+    // Waits for the transaction submitted above rather than failing
+    client.submit_transaction_blocking(&transaction)?;
+
+    let status = client.submit_transaction_with_status(&transaction)?;
+    assert!(
+        matches!(status, SubmissionStatus::Committed(_)),
+        "{status:?}"
+    );
+    // Returns right away, because the transaction is already in a block
+    assert_eq!(
+        client.submit_transaction_blocking(&transaction)?,
+        transaction.hash()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resubmitted_transaction_reports_rejection() -> Result<()> {
+    let (_rt, _peer, client) = <PeerBuilder>::new().with_port(11_460).start_with_runtime();
+    wait_for_genesis_committed(&[client.clone()], 0);
+
+    let transaction = client.build_transaction(
+        [Unregister::domain("unknown".parse()?)],
+        Metadata::default(),
+    );
+    let _err = client
+        .submit_transaction_blocking(&transaction)
+        .unwrap_err();
+
+    let status = client.submit_transaction_with_status(&transaction)?;
+    assert!(
+        matches!(status, SubmissionStatus::Rejected(_)),
+        "{status:?}"
+    );
+    let _err = client
+        .submit_transaction_blocking(&transaction)
+        .unwrap_err();
+
+    Ok(())
+}
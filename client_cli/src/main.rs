@@ -14,6 +14,8 @@ use iroha::{client::Client, config::Config, data_model::prelude::*};
 use iroha_primitives::{addr::SocketAddr, json::JsonString};
 use thiserror::Error;
 
+mod session;
+
 /// Re-usable clap `--metadata <PATH>` (`-m`) argument.
 /// Should be combined with `#[command(flatten)]` attr.
 #[derive(clap::Args, Debug, Clone)]
@@ -82,6 +84,9 @@ struct Args {
     /// More verbose output
     #[arg(short, long)]
     verbose: bool,
+    /// Print every result as JSON on a single line, for automation
+    #[arg(long)]
+    json: bool,
     /// Subcommands of client cli
     #[command(subcommand)]
     subcommand: Subcommand,
@@ -110,6 +115,10 @@ enum Subcommand {
     Blocks(blocks::Args),
     /// The subcommand related to multi-instructions as Json or Json5
     Json(json::Args),
+    /// Start an interactive shell keeping the session between commands
+    Shell(session::ShellArgs),
+    /// Run the commands listed in a file in one session
+    Script(session::ScriptArgs),
 }
 
 /// Context inside which command is executed
@@ -127,11 +136,15 @@ trait RunContext {
     /// - if serialization fails
     /// - if printing fails
     fn print_data(&mut self, data: &dyn Serialize) -> Result<()>;
+
+    /// Remember the transaction submitted by the command
+    fn transaction_submitted(&mut self, _hash: &HashOf<SignedTransaction>) {}
 }
 
 struct PrintJsonContext<W> {
     write: W,
     config: Config,
+    compact: bool,
 }
 
 impl<W: std::io::Write> RunContext for PrintJsonContext<W> {
@@ -140,7 +153,12 @@ impl<W: std::io::Write> RunContext for PrintJsonContext<W> {
     }
 
     fn print_data(&mut self, data: &dyn Serialize) -> Result<()> {
-        writeln!(&mut self.write, "{}", serde_json::to_string_pretty(data)?)?;
+        let json = if self.compact {
+            serde_json::to_string(data)?
+        } else {
+            serde_json::to_string_pretty(data)?
+        };
+        writeln!(&mut self.write, "{json}")?;
         Ok(())
     }
 }
@@ -165,7 +183,7 @@ macro_rules! match_all {
 impl RunArgs for Subcommand {
     fn run(self, context: &mut dyn RunContext) -> Result<()> {
         use Subcommand::*;
-        match_all!((self, context), { Domain, Account, Asset, Peer, Events, Wasm, Blocks, Json })
+        match_all!((self, context), { Domain, Account, Asset, Peer, Events, Wasm, Blocks, Json, Shell, Script })
     }
 }
 
@@ -184,6 +202,7 @@ fn main() -> error_stack::Result<(), MainError> {
         config: config_path,
         subcommand,
         verbose,
+        json,
     } = clap::Parser::parse();
 
     error_stack::Report::set_color_mode(color_mode());
@@ -204,6 +223,7 @@ fn main() -> error_stack::Result<(), MainError> {
     let mut context = PrintJsonContext {
         write: stdout(),
         config,
+        compact: json,
     };
     subcommand
         .run(&mut context)
@@ -242,6 +262,7 @@ fn submit(
     #[cfg(debug_assertions)]
     let err_msg = format!("Failed to submit transaction {tx:?}");
     let hash = iroha.submit_transaction_blocking(&tx).wrap_err(err_msg)?;
+    context.transaction_submitted(&hash);
     context.print_data(&hash)?;
 
     Ok(())
@@ -0,0 +1,332 @@
+//! Interactive shell and scripts running several commands in one session.
+//!
+//! A line of a session is a command as it would be passed to `iroha`, without the global options,
+//! e.g. `domain register --id wonderland`. Arguments are split by whitespace unless quoted with
+//! `'` or `"`, and `$name` or `${name}` is replaced with the value of the variable `name`.
+//! Empty lines and lines starting with `#` are skipped.
+//!
+//! Besides the commands, a session understands:
+//!
+//! - `set <NAME> <VALUE>` to set a variable;
+//! - `account <ACCOUNT_ID> <PRIVATE_KEY>` to sign the following transactions and queries as another account;
+//! - `session` to print the account, the variables and the latest submitted transactions;
+//! - `exit` to end the session.
+//!
+//! The hash of the latest submitted transaction is kept in the `last_tx` variable.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::{stdin, IsTerminal as _, Write as _},
+};
+
+use iroha::crypto::{KeyPair, PrivateKey};
+
+use super::*;
+
+/// How many of the latest submitted transactions a session remembers
+const TRANSACTIONS_TO_REMEMBER: usize = 10;
+
+/// Variable holding the hash of the latest submitted transaction
+const LAST_TX_VARIABLE: &str = "last_tx";
+
+/// Start an interactive shell reading commands from the standard input
+#[derive(clap::Args, Debug, Clone)]
+pub struct ShellArgs {
+    #[command(flatten)]
+    variables: VariablesArgs,
+}
+
+impl RunArgs for ShellArgs {
+    fn run(self, context: &mut dyn RunContext) -> Result<()> {
+        let interactive = stdin().is_terminal();
+        let mut session = Session::new(context, self.variables.into_map());
+
+        let mut line = String::new();
+        loop {
+            if interactive {
+                eprint!("iroha> ");
+                std::io::stderr().flush()?;
+            }
+            line.clear();
+            // The lock of the standard input isn't held, since commands such as `json` read it too
+            if stdin().read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            match session.execute(&line) {
+                Ok(Flow::Continue) => {}
+                Ok(Flow::Exit) => return Ok(()),
+                // Mistakes in an interactive shell are corrected by the next command
+                Err(error) if interactive => eprintln!("Error: {error:?}"),
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// Run the commands listed in a file, stopping at the first failing one
+#[derive(clap::Args, Debug, Clone)]
+pub struct ScriptArgs {
+    /// The file with a command per line
+    #[arg(value_name("PATH"), value_hint(clap::ValueHint::FilePath))]
+    path: PathBuf,
+    #[command(flatten)]
+    variables: VariablesArgs,
+}
+
+impl RunArgs for ScriptArgs {
+    fn run(self, context: &mut dyn RunContext) -> Result<()> {
+        let script = fs::read_to_string(&self.path)
+            .wrap_err_with(|| eyre!("Failed to read the script `{}`", self.path.display()))?;
+        let mut session = Session::new(context, self.variables.into_map());
+
+        for (i, line) in script.lines().enumerate() {
+            let flow = session.execute(line).wrap_err_with(|| {
+                eyre!("Failed to run line {} of `{}`", i + 1, self.path.display())
+            })?;
+            if flow == Flow::Exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Re-usable `--var <NAME=VALUE>` argument
+#[derive(clap::Args, Debug, Clone)]
+struct VariablesArgs {
+    /// Variable available to the commands as `$NAME`
+    #[arg(long = "var", value_name("NAME=VALUE"), value_parser = parse_variable)]
+    variables: Vec<(String, String)>,
+}
+
+impl VariablesArgs {
+    fn into_map(self) -> BTreeMap<String, String> {
+        self.variables.into_iter().collect()
+    }
+}
+
+fn parse_variable(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("expected `NAME=VALUE`, got `{s}`"))
+}
+
+/// Command of a session line
+#[derive(clap::Parser, Debug)]
+#[command(no_binary_name = true, disable_version_flag = true)]
+struct Line {
+    #[command(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Exit,
+}
+
+/// State kept between the commands of a session
+struct Session<'ctx> {
+    outer: &'ctx mut dyn RunContext,
+    config: Config,
+    variables: BTreeMap<String, String>,
+    transactions: VecDeque<HashOf<SignedTransaction>>,
+}
+
+impl<'ctx> Session<'ctx> {
+    fn new(outer: &'ctx mut dyn RunContext, variables: BTreeMap<String, String>) -> Self {
+        Self {
+            config: outer.configuration().clone(),
+            outer,
+            variables,
+            transactions: VecDeque::new(),
+        }
+    }
+
+    fn execute(&mut self, line: &str) -> Result<Flow> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(Flow::Continue);
+        }
+        let words = split(&substitute(line, &self.variables)?)?;
+
+        match words
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            ["exit" | "quit"] => return Ok(Flow::Exit),
+            ["set", name, value] => {
+                self.variables
+                    .insert((*name).to_owned(), (*value).to_owned());
+            }
+            ["account", account, private_key] => {
+                let account: AccountId = account.parse()?;
+                let private_key: PrivateKey = private_key.parse()?;
+                self.config.key_pair = KeyPair::from(private_key);
+                self.config.account = account;
+            }
+            ["session"] => {
+                let state = serde_json::json!({
+                    "account": self.config.account,
+                    "public_key": self.config.key_pair.public_key(),
+                    "variables": self.variables,
+                    "transactions": self.transactions,
+                });
+                self.print_data(&state)?;
+            }
+            _ => {
+                let Line { subcommand } = match <Line as clap::Parser>::try_parse_from(&words) {
+                    Ok(line) => line,
+                    Err(error) if !error.use_stderr() => {
+                        // Help requested
+                        error.print()?;
+                        return Ok(Flow::Continue);
+                    }
+                    Err(error) => return Err(eyre!("{}", error.render())),
+                };
+                if matches!(subcommand, Subcommand::Shell(_)) {
+                    return Err(eyre!("A shell can't be started inside of a session"));
+                }
+                subcommand.run(self)?;
+            }
+        }
+        Ok(Flow::Continue)
+    }
+}
+
+impl RunContext for Session<'_> {
+    fn configuration(&self) -> &Config {
+        &self.config
+    }
+
+    fn print_data(&mut self, data: &dyn Serialize) -> Result<()> {
+        self.outer.print_data(data)
+    }
+
+    fn transaction_submitted(&mut self, hash: &HashOf<SignedTransaction>) {
+        self.outer.transaction_submitted(hash);
+        self.variables
+            .insert(LAST_TX_VARIABLE.to_owned(), hash.to_string());
+        if self.transactions.len() == TRANSACTIONS_TO_REMEMBER {
+            self.transactions.pop_front();
+        }
+        self.transactions.push_back(hash.clone());
+    }
+}
+
+/// Replace `$name` and `${name}` with the values of the variables, `$$` with `$`
+fn substitute(line: &str, variables: &BTreeMap<String, String>) -> Result<String> {
+    let mut substituted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(dollar) = rest.find('$') {
+        substituted.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+
+        let (name, len) = if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| eyre!("Unclosed `${{` in `{line}`"))?;
+            (&braced[..end], end + 2)
+        } else if rest.starts_with('$') {
+            substituted.push('$');
+            rest = &rest[1..];
+            continue;
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], end)
+        };
+        if name.is_empty() {
+            return Err(eyre!("Variable name is missing after `$` in `{line}`"));
+        }
+        let value = variables
+            .get(name)
+            .ok_or_else(|| eyre!("Variable `{name}` is not set"))?;
+        substituted.push_str(value);
+        rest = &rest[len..];
+    }
+    substituted.push_str(rest);
+    Ok(substituted)
+}
+
+/// Split `line` into words by whitespace, keeping the quoted whitespace.
+/// Backslash escapes the next character outside of single quotes.
+fn split(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (Some(open), c) if c == open => quote = None,
+            (None | Some('"'), '\\') => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| eyre!("Nothing to escape at the end of `{line}`"))?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            (_, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(eyre!("Unclosed quote in `{line}`"));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_are_substituted() {
+        let variables = [("domain", "wonderland"), ("last_tx", "ab12")]
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect();
+
+        assert_eq!(
+            substitute("domain register --id $domain-${last_tx} $$5", &variables).unwrap(),
+            "domain register --id wonderland-ab12 $5"
+        );
+        assert!(substitute("echo $unknown", &variables).is_err());
+        assert!(substitute("echo ${domain", &variables).is_err());
+    }
+
+    #[test]
+    fn quoted_whitespace_is_kept() {
+        assert_eq!(
+            split(r#"domain list filter '{"Atom": {"Id": {"Equals": "a b"}}}' "x \"y\"" z\ w"#)
+                .unwrap(),
+            [
+                "domain",
+                "list",
+                "filter",
+                r#"{"Atom": {"Id": {"Equals": "a b"}}}"#,
+                r#"x "y""#,
+                "z w",
+            ]
+        );
+        assert_eq!(split("set empty ''").unwrap(), ["set", "empty", ""]);
+        assert!(split("set name 'value").is_err());
+    }
+
+    #[test]
+    fn session_lines_are_parsed_as_commands() {
+        let Line { subcommand } = <Line as clap::Parser>::try_parse_from(
+            split("domain register --id wonderland").unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(subcommand, Subcommand::Domain(_)));
+    }
+}
@@ -4,16 +4,17 @@
 //!
 //! - Create [`ConfigDTO`] from [`crate::iroha::Configuration`] and serialize it for the client
 //! - Deserialize [`ConfigDTO`] from the client and use [`ConfigDTO::apply_update()`] to update the configuration
-// TODO: Currently logic here is not generalised and handles only `logger.level` parameter. In future, when
-//       other parts of configuration are refactored and there is a solid foundation e.g. as a general
-//       configuration-related crate, this part should be re-written in a clean way.
+// TODO: Currently logic here is not generalised and handles only `logger.level` and `queue.authority_filter`
+//       parameters. In future, when other parts of configuration are refactored and there is a solid foundation
+//       e.g. as a general configuration-related crate, this part should be re-written in a clean way.
 //       Track configuration refactoring here: https://github.com/hyperledger/iroha/issues/2585
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
     logger::Directives,
-    parameters::actual::{Logger as BaseLogger, Root as BaseConfig},
+    parameters::actual::{Logger as BaseLogger, Queue as BaseQueue, Root as BaseConfig},
+    queue::AuthorityFilter,
 };
 
 /// Subset of [`super::iroha`] configuration.
@@ -21,12 +22,16 @@ use crate::{
 pub struct ConfigDTO {
     #[allow(missing_docs)]
     pub logger: Logger,
+    /// Left unchanged on update if missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<Queue>,
 }
 
 impl From<&'_ BaseConfig> for ConfigDTO {
     fn from(value: &'_ BaseConfig) -> Self {
         Self {
             logger: (&value.logger).into(),
+            queue: Some((&value.queue).into()),
         }
     }
 }
@@ -46,11 +51,27 @@ impl From<&'_ BaseLogger> for Logger {
     }
 }
 
+/// Subset of [`super::queue`] configuration.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Queue {
+    #[allow(missing_docs)]
+    pub authority_filter: AuthorityFilter,
+}
+
+impl From<&'_ BaseQueue> for Queue {
+    fn from(value: &'_ BaseQueue) -> Self {
+        Self {
+            authority_filter: value.authority_filter.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use iroha_data_model::Level;
 
     use super::*;
+    use crate::queue::AuthorityRule;
 
     #[test]
     fn snapshot_serialized_form() {
@@ -58,6 +79,12 @@ mod test {
             logger: Logger {
                 level: Level::TRACE.into(),
             },
+            queue: Some(Queue {
+                authority_filter: AuthorityFilter {
+                    allow: vec![AuthorityRule::Domain("wonderland".parse().unwrap())],
+                    deny: Vec::new(),
+                },
+            }),
         };
 
         let actual = serde_json::to_string_pretty(&value).expect("The value is a valid JSON");
@@ -69,6 +96,14 @@ mod test {
                 {
                   "logger": {
                     "level": "trace"
+                  },
+                  "queue": {
+                    "authority_filter": {
+                      "allow": [
+                        "wonderland"
+                      ],
+                      "deny": []
+                    }
                   }
                 }"#]];
         expected.assert_eq(&actual);
@@ -0,0 +1,89 @@
+//! Configuration of the external sinks events are forwarded to
+
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use url::Url;
+
+/// Kind of events forwarded to a sink
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum EventKind {
+    /// Transaction and block status changes
+    Pipeline,
+    /// Changes of the world state
+    Data,
+    /// Time events of the block production
+    Time,
+    /// Requests to execute a trigger
+    ExecuteTrigger,
+    /// Results of trigger executions
+    TriggerCompleted,
+}
+
+/// Encoding of the events delivered to a sink
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum EventFormat {
+    /// JSON, convenient for consumers outside of the Iroha ecosystem
+    #[default]
+    Json,
+    /// SCALE, the encoding used by the Iroha API
+    Scale,
+}
+
+/// Message broker events are published to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum EventSinkTarget {
+    /// Kafka topic
+    Kafka {
+        /// Comma-separated `host:port` list of the bootstrap brokers
+        brokers: String,
+        /// Topic events are produced to
+        topic: String,
+    },
+    /// NATS subject
+    Nats {
+        /// Address of the NATS server, e.g. `nats://localhost:4222`
+        url: Url,
+        /// Subject events are published to
+        subject: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_kind_display_reprs() {
+        assert_eq!(format!("{}", EventKind::Pipeline), "pipeline");
+        assert_eq!(format!("{}", EventKind::ExecuteTrigger), "execute_trigger");
+        assert_eq!(format!("{}", EventFormat::Scale), "scale");
+        assert_eq!(
+            "trigger_completed".parse::<EventKind>().unwrap(),
+            EventKind::TriggerCompleted
+        );
+    }
+}
@@ -5,10 +5,14 @@ use stderrlog::LogLevelNum;
 use tracing::log::SetLoggerError;
 
 pub mod client_api;
+pub mod event_sink;
 pub mod kura;
 pub mod logger;
 pub mod parameters;
+pub mod peer;
+pub mod queue;
 pub mod snapshot;
+pub mod sumeragi;
 
 /// Enables tracing of configuration via [`stderrlog`].
 /// # Errors
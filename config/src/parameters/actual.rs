@@ -2,7 +2,7 @@
 //! structures in a way that is efficient for Iroha internally.
 
 use std::{
-    num::{NonZeroU32, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::PathBuf,
     time::Duration,
 };
@@ -16,8 +16,12 @@ use url::Url;
 pub use user::{DevTelemetry, Logger, Snapshot};
 
 use crate::{
+    event_sink::{EventFormat, EventKind, EventSinkTarget},
     kura::InitMode,
     parameters::{defaults, user},
+    peer::Role,
+    queue::{AdmissionCheck, AuthorityFilter},
+    sumeragi::BlockPacking,
 };
 
 /// Parsed configuration root
@@ -36,8 +40,12 @@ pub struct Root {
     pub logger: Logger,
     pub queue: Queue,
     pub snapshot: Snapshot,
+    pub state: State,
     pub telemetry: Option<Telemetry>,
     pub dev_telemetry: DevTelemetry,
+    pub shutdown: Shutdown,
+    pub admin: Option<Admin>,
+    pub event_sinks: Vec<EventSink>,
 }
 
 /// See [`Root::from_toml_source`]
@@ -67,6 +75,7 @@ pub struct Common {
     pub chain: ChainId,
     pub key_pair: KeyPair,
     pub peer: PeerId,
+    pub role: Role,
 }
 
 /// Network options
@@ -75,6 +84,10 @@ pub struct Common {
 pub struct Network {
     pub address: WithOrigin<SocketAddr>,
     pub idle_timeout: Duration,
+    pub reputation_ban_threshold: NonZeroU32,
+    pub reputation_ban_duration: Duration,
+    pub clock_skew_warn_threshold: Duration,
+    pub partition_timeout: Duration,
 }
 
 /// Parsed genesis configuration
@@ -86,15 +99,20 @@ pub struct Genesis {
     /// If it is none, the peer can only observe the genesis block.
     /// If it is some, the peer is responsible for submitting the genesis block.
     pub file: Option<WithOrigin<PathBuf>>,
+    /// Whether the peer may generate an insecure genesis in development mode
+    pub allow_insecure_dev_mode: bool,
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Queue {
     pub capacity: NonZeroUsize,
     pub capacity_per_user: NonZeroUsize,
     pub transaction_time_to_live: Duration,
     pub future_threshold: Duration,
+    pub clock_skew_tolerance: Duration,
+    pub admission_checks: Vec<AdmissionCheck>,
+    pub authority_filter: AuthorityFilter,
 }
 
 #[allow(missing_docs)]
@@ -102,6 +120,9 @@ pub struct Queue {
 pub struct Kura {
     pub init_mode: InitMode,
     pub store_dir: WithOrigin<PathBuf>,
+    pub explorer_index: bool,
+    pub block_compression_level: u8,
+    pub pipeline_depth: NonZeroUsize,
     pub debug_output_new_blocks: bool,
 }
 
@@ -110,8 +131,11 @@ impl Default for Queue {
         Self {
             transaction_time_to_live: defaults::queue::TRANSACTION_TIME_TO_LIVE,
             future_threshold: defaults::queue::FUTURE_THRESHOLD,
+            clock_skew_tolerance: defaults::queue::CLOCK_SKEW_TOLERANCE,
             capacity: defaults::queue::CAPACITY,
             capacity_per_user: defaults::queue::CAPACITY_PER_USER,
+            admission_checks: defaults::queue::ADMISSION_CHECKS.to_vec(),
+            authority_filter: AuthorityFilter::default(),
         }
     }
 }
@@ -120,6 +144,8 @@ impl Default for Queue {
 #[allow(missing_docs)]
 pub struct Sumeragi {
     pub trusted_peers: WithOrigin<TrustedPeers>,
+    pub block_packing: BlockPacking,
+    pub block_packing_lookahead: NonZeroUsize,
     pub debug_force_soft_fork: bool,
 }
 
@@ -151,6 +177,9 @@ pub struct LiveQueryStore {
     pub idle_time: Duration,
     pub capacity: NonZeroUsize,
     pub capacity_per_user: NonZeroUsize,
+    pub cache_capacity: Option<NonZeroUsize>,
+    pub cost_budget: Option<NonZeroU64>,
+    pub cost_period: Duration,
 }
 
 impl Default for LiveQueryStore {
@@ -159,6 +188,9 @@ impl Default for LiveQueryStore {
             idle_time: defaults::torii::QUERY_IDLE_TIME,
             capacity: defaults::torii::QUERY_STORE_CAPACITY,
             capacity_per_user: defaults::torii::QUERY_STORE_CAPACITY_PER_USER,
+            cache_capacity: None,
+            cost_budget: None,
+            cost_period: defaults::torii::QUERY_COST_PERIOD,
         }
     }
 }
@@ -182,6 +214,64 @@ pub struct TransactionGossiper {
 pub struct Torii {
     pub address: WithOrigin<SocketAddr>,
     pub max_content_len: Bytes<u64>,
+    pub webhooks_per_account: NonZeroUsize,
+    pub webhook_max_retries: u32,
+    pub webhook_retry_delay: Duration,
+}
+
+/// World state options
+#[derive(Debug, Clone, Copy)]
+pub struct State {
+    /// Whether to record per-block state diffs served by `FindBlockStateDiff`
+    pub record_block_diffs: bool,
+    /// Whether to archive per-block events served by `FindEvents`
+    pub archive_events: bool,
+    /// Whether to profile instruction execution of every block
+    pub profile_instructions: bool,
+    /// Maximum number of compiled smart contracts to keep
+    pub wasm_module_cache_size: usize,
+    /// Maximum number of transaction rejections to keep
+    pub validation_cache_size: usize,
+    /// Number of blocks between computations of the world state digest
+    pub digest_period: NonZeroUsize,
+    /// The time between gossiping the world state digests to other peers
+    pub digest_gossip_period: Duration,
+}
+
+/// Graceful shutdown options
+#[derive(Debug, Clone, Copy)]
+pub struct Shutdown {
+    /// Deadline for draining in-flight work (consensus round, Kura flush, p2p goodbyes).
+    /// Whatever is not finished by then is abandoned.
+    pub timeout: Duration,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            timeout: defaults::shutdown::TIMEOUT,
+        }
+    }
+}
+
+/// Complete configuration needed to start the administration API.
+#[derive(Debug, Clone)]
+#[allow(missing_docs)]
+pub struct Admin {
+    pub address: SocketAddr,
+    pub public_key: PublicKey,
+}
+
+/// Complete configuration of a sink events are forwarded to.
+#[derive(Debug, Clone)]
+#[allow(missing_docs)]
+pub struct EventSink {
+    pub name: String,
+    pub target: EventSinkTarget,
+    pub events: Vec<EventKind>,
+    pub format: EventFormat,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
 }
 
 /// Complete configuration needed to start regular telemetry.
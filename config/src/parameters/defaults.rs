@@ -12,16 +12,33 @@ use nonzero_ext::nonzero;
 
 pub mod queue {
     use super::*;
+    use crate::queue::AdmissionCheck;
 
     pub const CAPACITY: NonZeroUsize = nonzero!(2_usize.pow(16));
     pub const CAPACITY_PER_USER: NonZeroUsize = nonzero!(2_usize.pow(16));
     // 24 hours
     pub const TRANSACTION_TIME_TO_LIVE: Duration = Duration::from_secs(24 * 60 * 60);
     pub const FUTURE_THRESHOLD: Duration = Duration::from_secs(1);
+    pub const CLOCK_SKEW_TOLERANCE: Duration = Duration::ZERO;
+    pub const ADMISSION_CHECKS: [AdmissionCheck; 4] = [
+        AdmissionCheck::AuthorityFilter,
+        AdmissionCheck::FutureTimestamp,
+        AdmissionCheck::Expiry,
+        AdmissionCheck::Committed,
+    ];
 }
 
 pub mod kura {
+    use super::*;
+
     pub const STORE_DIR: &str = "./storage";
+    pub const PIPELINE_DEPTH: NonZeroUsize = nonzero!(4_usize);
+}
+
+pub mod sumeragi {
+    use super::*;
+
+    pub const BLOCK_PACKING_LOOKAHEAD: NonZeroUsize = nonzero!(1_usize);
 }
 
 pub mod network {
@@ -34,6 +51,13 @@ pub mod network {
     pub const BLOCK_GOSSIP_SIZE: NonZeroU32 = nonzero!(4u32);
 
     pub const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub const REPUTATION_BAN_THRESHOLD: NonZeroU32 = nonzero!(100u32);
+    pub const REPUTATION_BAN_DURATION: Duration = Duration::from_secs(10 * 60);
+
+    pub const CLOCK_SKEW_WARN_THRESHOLD: Duration = Duration::from_millis(500);
+
+    pub const PARTITION_TIMEOUT: Duration = Duration::from_secs(30);
 }
 
 pub mod snapshot {
@@ -44,6 +68,15 @@ pub mod snapshot {
     pub const CREATE_EVERY: Duration = Duration::from_secs(60);
 }
 
+pub mod state {
+    use super::*;
+
+    pub const DIGEST_PERIOD_BLOCKS: NonZeroUsize = nonzero!(100_usize);
+    pub const DIGEST_GOSSIP_PERIOD: Duration = Duration::from_secs(10);
+    pub const WASM_MODULE_CACHE_SIZE: usize = 64;
+    pub const VALIDATION_CACHE_SIZE: usize = 4096;
+}
+
 pub mod torii {
     use std::{num::NonZeroUsize, time::Duration};
 
@@ -54,6 +87,26 @@ pub mod torii {
     pub const QUERY_IDLE_TIME: Duration = Duration::from_secs(10);
     pub const QUERY_STORE_CAPACITY: NonZeroUsize = nonzero!(128usize);
     pub const QUERY_STORE_CAPACITY_PER_USER: NonZeroUsize = nonzero!(128usize);
+    pub const QUERY_COST_PERIOD: Duration = Duration::from_secs(60);
+    pub const WEBHOOKS_PER_ACCOUNT: NonZeroUsize = nonzero!(8usize);
+    pub const WEBHOOK_MAX_RETRIES: u32 = 5;
+    pub const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+}
+
+pub mod shutdown {
+    use std::time::Duration;
+
+    /// Default deadline for the whole graceful shutdown sequence
+    pub const TIMEOUT: Duration = Duration::from_secs(30);
+}
+
+pub mod event_sink {
+    use std::time::Duration;
+
+    /// Default number of delivery retries before an event is dropped
+    pub const MAX_RETRIES: u32 = 3;
+    /// Default delay before the first delivery retry, doubled on every next one
+    pub const RETRY_DELAY: Duration = Duration::from_millis(500);
 }
 
 pub mod telemetry {
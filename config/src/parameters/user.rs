@@ -13,7 +13,7 @@ use std::{
     borrow::Cow,
     convert::Infallible,
     fmt::Debug,
-    num::{NonZeroU32, NonZeroUsize},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     path::PathBuf,
 };
 
@@ -24,17 +24,21 @@ use iroha_config_base::{
     util::{Bytes, DurationMs, Emitter, EmitterResultExt},
     ReadConfig, WithOrigin,
 };
-use iroha_crypto::{PrivateKey, PublicKey};
+use iroha_crypto::{HashAlgorithm, PrivateKey, PublicKey};
 use iroha_data_model::{peer::PeerId, ChainId};
 use iroha_primitives::{addr::SocketAddr, unique_vec::UniqueVec};
 use serde::Deserialize;
 use url::Url;
 
 use crate::{
+    event_sink::{EventFormat, EventKind, EventSinkTarget},
     kura::InitMode as KuraInitMode,
     logger::{Directives, Format as LoggerFormat},
     parameters::{actual, defaults},
+    peer::Role as PeerRole,
+    queue::{self, AuthorityRule},
     snapshot::Mode as SnapshotMode,
+    sumeragi::BlockPacking,
 };
 
 #[derive(Deserialize, Debug)]
@@ -59,6 +63,8 @@ pub struct Root {
     public_key: WithOrigin<PublicKey>,
     #[config(env = "PRIVATE_KEY")]
     private_key: WithOrigin<PrivateKey>,
+    #[config(env = "ROLE", default)]
+    role: PeerRole,
     #[config(nested)]
     genesis: Genesis,
     #[config(nested)]
@@ -73,11 +79,18 @@ pub struct Root {
     queue: Queue,
     #[config(nested)]
     snapshot: Snapshot,
+    #[config(nested)]
+    state: State,
     telemetry: Option<Telemetry>,
     #[config(nested)]
     dev_telemetry: DevTelemetry,
     #[config(nested)]
     torii: Torii,
+    #[config(nested)]
+    shutdown: Shutdown,
+    admin: Option<Admin>,
+    #[config(default)]
+    event_sinks: Vec<EventSink>,
 }
 
 #[derive(thiserror::Error, Debug, Copy, Clone)]
@@ -111,9 +124,17 @@ impl Root {
         let logger = self.logger;
         let queue = self.queue;
         let snapshot = self.snapshot;
+        let state = self.state.parse();
         let dev_telemetry = self.dev_telemetry;
         let (torii, live_query_store) = self.torii.parse();
         let telemetry = self.telemetry.map(actual::Telemetry::from);
+        let shutdown = self.shutdown.parse();
+        let admin = self.admin.map(actual::Admin::from);
+        let event_sinks = self
+            .event_sinks
+            .into_iter()
+            .map(actual::EventSink::from)
+            .collect();
 
         let peer_id = key_pair.as_ref().map(|key_pair| {
             PeerId::new(
@@ -133,6 +154,7 @@ impl Root {
             chain: self.chain.0,
             key_pair,
             peer: peer_id.unwrap(),
+            role: self.role,
         };
 
         Ok(actual::Root {
@@ -148,8 +170,12 @@ impl Root {
             logger,
             queue: queue.parse(),
             snapshot,
+            state,
             telemetry,
             dev_telemetry,
+            shutdown,
+            admin,
+            event_sinks,
         })
     }
 }
@@ -160,6 +186,10 @@ pub struct Genesis {
     pub public_key: WithOrigin<PublicKey>,
     #[config(env = "GENESIS")]
     pub file: Option<WithOrigin<PathBuf>>,
+    /// Allow bootstrapping the network from a generated genesis with `irohad --dev`.
+    /// Never enable it outside of local development: the genesis it creates is insecure.
+    #[config(env = "GENESIS_ALLOW_INSECURE_DEV_MODE", default)]
+    pub allow_insecure_dev_mode: bool,
 }
 
 impl From<Genesis> for actual::Genesis {
@@ -167,6 +197,7 @@ impl From<Genesis> for actual::Genesis {
         actual::Genesis {
             public_key: genesis.public_key.into_value(),
             file: genesis.file,
+            allow_insecure_dev_mode: genesis.allow_insecure_dev_mode,
         }
     }
 }
@@ -180,6 +211,20 @@ pub struct Kura {
         default = "PathBuf::from(defaults::kura::STORE_DIR)"
     )]
     pub store_dir: WithOrigin<PathBuf>,
+    /// Maintain index files for direct lookups of transactions by hash and by authority
+    #[config(env = "KURA_EXPLORER_INDEX", default)]
+    pub explorer_index: bool,
+    /// Zstd level (1-22) of the stored blocks, `0` stores them uncompressed.
+    /// Blocks stored before changing it are still read.
+    #[config(env = "KURA_BLOCK_COMPRESSION_LEVEL", default)]
+    pub block_compression_level: u8,
+    /// Number of committed blocks which may wait to be written to disk.
+    /// Committing a block waits for the disk once more blocks are pending.
+    #[config(
+        env = "KURA_PIPELINE_DEPTH",
+        default = "defaults::kura::PIPELINE_DEPTH"
+    )]
+    pub pipeline_depth: NonZeroUsize,
     #[config(nested)]
     pub debug: KuraDebug,
 }
@@ -189,6 +234,9 @@ impl Kura {
         let Self {
             init_mode,
             store_dir,
+            explorer_index,
+            block_compression_level,
+            pipeline_depth,
             debug:
                 KuraDebug {
                     output_new_blocks: debug_output_new_blocks,
@@ -198,6 +246,9 @@ impl Kura {
         actual::Kura {
             init_mode,
             store_dir,
+            explorer_index,
+            block_compression_level,
+            pipeline_depth,
             debug_output_new_blocks,
         }
     }
@@ -213,6 +264,10 @@ pub struct KuraDebug {
 pub struct Sumeragi {
     #[config(env = "TRUSTED_PEERS", default)]
     pub trusted_peers: WithOrigin<TrustedPeers>,
+    #[config(env = "SUMERAGI_BLOCK_PACKING", default)]
+    pub block_packing: BlockPacking,
+    #[config(default = "defaults::sumeragi::BLOCK_PACKING_LOOKAHEAD")]
+    pub block_packing_lookahead: NonZeroUsize,
     #[config(nested)]
     pub debug: SumeragiDebug,
 }
@@ -241,6 +296,8 @@ impl Sumeragi {
     fn parse_and_push_self(self, self_id: PeerId) -> actual::Sumeragi {
         let Self {
             trusted_peers,
+            block_packing,
+            block_packing_lookahead,
             debug: SumeragiDebug { force_soft_fork },
         } = self;
 
@@ -249,6 +306,8 @@ impl Sumeragi {
                 myself: self_id,
                 others: x.0,
             }),
+            block_packing,
+            block_packing_lookahead,
             debug_force_soft_fork: force_soft_fork,
         }
     }
@@ -276,6 +335,17 @@ pub struct Network {
     /// Duration of time after which connection with peer is terminated if peer is idle
     #[config(default = "defaults::network::IDLE_TIMEOUT.into()")]
     pub idle_timeout_ms: DurationMs,
+    /// Peer is banned once its reputation score drops below the negated value of this threshold
+    #[config(default = "defaults::network::REPUTATION_BAN_THRESHOLD")]
+    pub reputation_ban_threshold: NonZeroU32,
+    #[config(default = "defaults::network::REPUTATION_BAN_DURATION.into()")]
+    pub reputation_ban_duration_ms: DurationMs,
+    /// Warning is logged once the clock of a peer is found to differ from ours by more than this
+    #[config(default = "defaults::network::CLOCK_SKEW_WARN_THRESHOLD.into()")]
+    pub clock_skew_warn_threshold_ms: DurationMs,
+    /// Peer switches into the degraded mode once a quorum of peers is unreachable for this long
+    #[config(default = "defaults::network::PARTITION_TIMEOUT.into()")]
+    pub partition_timeout_ms: DurationMs,
 }
 
 impl Network {
@@ -293,12 +363,20 @@ impl Network {
             transaction_gossip_size,
             transaction_gossip_period_ms: transaction_gossip_period,
             idle_timeout_ms: idle_timeout,
+            reputation_ban_threshold,
+            reputation_ban_duration_ms: reputation_ban_duration,
+            clock_skew_warn_threshold_ms: clock_skew_warn_threshold,
+            partition_timeout_ms: partition_timeout,
         } = self;
 
         (
             actual::Network {
                 address,
                 idle_timeout: idle_timeout.get(),
+                reputation_ban_threshold,
+                reputation_ban_duration: reputation_ban_duration.get(),
+                clock_skew_warn_threshold: clock_skew_warn_threshold.get(),
+                partition_timeout: partition_timeout.get(),
             },
             actual::BlockSync {
                 gossip_period: block_gossip_period.get(),
@@ -312,7 +390,7 @@ impl Network {
     }
 }
 
-#[derive(Debug, Clone, Copy, ReadConfig)]
+#[derive(Debug, Clone, ReadConfig)]
 pub struct Queue {
     /// The upper limit of the number of transactions waiting in the queue.
     #[config(default = "defaults::queue::CAPACITY")]
@@ -327,6 +405,16 @@ pub struct Queue {
     /// The threshold to determine if a transaction has been tampered to have a future timestamp.
     #[config(default = "defaults::queue::FUTURE_THRESHOLD.into()")]
     pub future_threshold_ms: DurationMs,
+    /// How far the clocks of the peers may drift apart without rejecting each other's transactions.
+    /// Extends both the time to live of transactions and the time they may be dated ahead by,
+    /// but doesn't let scheduled transactions into a block earlier.
+    #[config(default = "defaults::queue::CLOCK_SKEW_TOLERANCE.into()")]
+    pub clock_skew_tolerance_ms: DurationMs,
+    /// Built-in checks a transaction has to pass to be admitted into the queue, in the order they run.
+    #[config(default = "defaults::queue::ADMISSION_CHECKS.to_vec()")]
+    pub admission_checks: Vec<queue::AdmissionCheck>,
+    #[config(nested)]
+    pub authority_filter: AuthorityFilter,
 }
 
 impl Queue {
@@ -336,16 +424,32 @@ impl Queue {
             capacity_per_user,
             transaction_time_to_live_ms: transaction_time_to_live,
             future_threshold_ms: future_threshold,
+            clock_skew_tolerance_ms: clock_skew_tolerance,
+            admission_checks,
+            authority_filter: AuthorityFilter { allow, deny },
         } = self;
         actual::Queue {
             capacity,
             capacity_per_user,
             transaction_time_to_live: transaction_time_to_live.0,
             future_threshold: future_threshold.0,
+            clock_skew_tolerance: clock_skew_tolerance.0,
+            admission_checks,
+            authority_filter: queue::AuthorityFilter { allow, deny },
         }
     }
 }
 
+#[derive(Debug, Clone, ReadConfig)]
+pub struct AuthorityFilter {
+    /// If not empty, only transactions of the listed accounts and domains are admitted into the queue.
+    #[config(default)]
+    pub allow: Vec<AuthorityRule>,
+    /// Transactions of the listed accounts and domains are never admitted into the queue.
+    #[config(default)]
+    pub deny: Vec<AuthorityRule>,
+}
+
 #[derive(Debug, Clone, Default, ReadConfig)]
 pub struct Logger {
     /// Level of logging verbosity
@@ -359,6 +463,83 @@ pub struct Logger {
     pub format: LoggerFormat,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Admin {
+    address: SocketAddr,
+    public_key: PublicKey,
+}
+
+impl From<Admin> for actual::Admin {
+    fn from(
+        Admin {
+            address,
+            public_key,
+        }: Admin,
+    ) -> Self {
+        Self {
+            address,
+            public_key,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventSink {
+    name: String,
+    target: EventSinkTarget,
+    /// Kinds of the forwarded events, all events are forwarded if empty
+    #[serde(default)]
+    events: Vec<EventKind>,
+    #[serde(default)]
+    format: EventFormat,
+    #[serde(default)]
+    max_retries: EventSinkMaxRetries,
+    #[serde(default)]
+    retry_delay_ms: EventSinkRetryDelay,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+struct EventSinkMaxRetries(u32);
+
+impl Default for EventSinkMaxRetries {
+    fn default() -> Self {
+        Self(defaults::event_sink::MAX_RETRIES)
+    }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+struct EventSinkRetryDelay(DurationMs);
+
+impl Default for EventSinkRetryDelay {
+    fn default() -> Self {
+        Self(DurationMs(defaults::event_sink::RETRY_DELAY))
+    }
+}
+
+impl From<EventSink> for actual::EventSink {
+    fn from(
+        EventSink {
+            name,
+            target,
+            events,
+            format,
+            max_retries: EventSinkMaxRetries(max_retries),
+            retry_delay_ms: EventSinkRetryDelay(DurationMs(retry_delay)),
+        }: EventSink,
+    ) -> Self {
+        Self {
+            name,
+            target,
+            events,
+            format,
+            max_retries,
+            retry_delay,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Telemetry {
@@ -408,6 +589,24 @@ impl From<Telemetry> for actual::Telemetry {
     }
 }
 
+#[derive(Debug, Clone, Copy, ReadConfig)]
+pub struct Shutdown {
+    /// Deadline for the whole graceful shutdown sequence.
+    #[config(
+        env = "SHUTDOWN_TIMEOUT_MS",
+        default = "defaults::shutdown::TIMEOUT.into()"
+    )]
+    pub timeout_ms: DurationMs,
+}
+
+impl Shutdown {
+    fn parse(self) -> actual::Shutdown {
+        actual::Shutdown {
+            timeout: self.timeout_ms.get(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, ReadConfig)]
 pub struct DevTelemetry {
     pub out_file: Option<WithOrigin<PathBuf>>,
@@ -424,6 +623,61 @@ pub struct Snapshot {
         env = "SNAPSHOT_STORE_DIR"
     )]
     pub store_dir: WithOrigin<PathBuf>,
+    /// Algorithm of the checksum written next to the snapshot to detect its corruption.
+    /// The checksum never leaves the peer, so any algorithm may be used.
+    #[config(default, env = "SNAPSHOT_CHECKSUM_ALGORITHM")]
+    pub checksum_algorithm: HashAlgorithm,
+}
+
+#[derive(Debug, Clone, Copy, ReadConfig)]
+pub struct State {
+    /// Record created, updated and deleted entities of every block to serve state diff queries.
+    /// Off by default since it roughly doubles the memory consumed by the world state history.
+    #[config(env = "STATE_RECORD_BLOCK_DIFFS", default)]
+    pub record_block_diffs: bool,
+    /// Archive events emitted while applying every block to serve event queries.
+    /// Off by default since the archive grows with the blockchain.
+    #[config(env = "STATE_ARCHIVE_EVENTS", default)]
+    pub archive_events: bool,
+    /// Measure execution time of every instruction kind and state lock waits of every block.
+    /// Off by default to keep timing out of the block execution path.
+    #[config(env = "STATE_PROFILE_INSTRUCTIONS", default)]
+    pub profile_instructions: bool,
+    /// Number of compiled smart contracts kept to skip compilation when they are submitted
+    /// or their triggers are executed again.
+    /// The least recently used ones are evicted first, `0` disables the cache.
+    #[config(
+        env = "STATE_WASM_MODULE_CACHE_SIZE",
+        default = "defaults::state::WASM_MODULE_CACHE_SIZE"
+    )]
+    pub wasm_module_cache_size: usize,
+    /// Number of transaction rejections kept to skip executing the transactions again
+    /// when a block at the same height is built or validated once more, e.g. after a view change.
+    /// The least recently used ones are evicted first, `0` disables the cache.
+    #[config(
+        env = "STATE_VALIDATION_CACHE_SIZE",
+        default = "defaults::state::VALIDATION_CACHE_SIZE"
+    )]
+    pub validation_cache_size: usize,
+    /// Compute a digest of the world state every that many blocks to compare it with other peers.
+    #[config(default = "defaults::state::DIGEST_PERIOD_BLOCKS")]
+    pub digest_period_blocks: NonZeroUsize,
+    #[config(default = "defaults::state::DIGEST_GOSSIP_PERIOD.into()")]
+    pub digest_gossip_period_ms: DurationMs,
+}
+
+impl State {
+    fn parse(self) -> actual::State {
+        actual::State {
+            record_block_diffs: self.record_block_diffs,
+            archive_events: self.archive_events,
+            profile_instructions: self.profile_instructions,
+            wasm_module_cache_size: self.wasm_module_cache_size,
+            validation_cache_size: self.validation_cache_size,
+            digest_period: self.digest_period_blocks,
+            digest_gossip_period: self.digest_gossip_period_ms.get(),
+        }
+    }
 }
 
 #[derive(Debug, ReadConfig)]
@@ -440,6 +694,25 @@ pub struct Torii {
     /// The upper limit of the number of live queries for a single user.
     #[config(default = "defaults::torii::QUERY_STORE_CAPACITY_PER_USER")]
     pub query_store_capacity_per_user: NonZeroUsize,
+    /// The upper limit of the number of cached query results.
+    /// Query results are not cached if not set.
+    pub query_cache_capacity: Option<NonZeroUsize>,
+    /// The cost a single account may spend on queries during `query_cost_period_ms`.
+    /// Each query costs one unit plus one unit per entity it visits.
+    /// Queries are not metered if not set.
+    pub query_cost_budget: Option<NonZeroU64>,
+    /// The time it takes for a spent query budget to be fully replenished.
+    #[config(default = "defaults::torii::QUERY_COST_PERIOD.into()")]
+    pub query_cost_period_ms: DurationMs,
+    /// The upper limit of the number of webhooks registered by a single account.
+    #[config(default = "defaults::torii::WEBHOOKS_PER_ACCOUNT")]
+    pub webhooks_per_account: NonZeroUsize,
+    /// The number of times a failed webhook notification is retried before it is dropped.
+    #[config(default = "defaults::torii::WEBHOOK_MAX_RETRIES")]
+    pub webhook_max_retries: u32,
+    /// The delay before the first retry of a webhook notification, doubled on every next one.
+    #[config(default = "defaults::torii::WEBHOOK_RETRY_DELAY.into()")]
+    pub webhook_retry_delay_ms: DurationMs,
 }
 
 impl Torii {
@@ -447,12 +720,18 @@ impl Torii {
         let torii = actual::Torii {
             address: self.address,
             max_content_len: self.max_content_len,
+            webhooks_per_account: self.webhooks_per_account,
+            webhook_max_retries: self.webhook_max_retries,
+            webhook_retry_delay: self.webhook_retry_delay_ms.get(),
         };
 
         let query = actual::LiveQueryStore {
             idle_time: self.query_idle_time_ms.get(),
             capacity: self.query_store_capacity,
             capacity_per_user: self.query_store_capacity_per_user,
+            cache_capacity: self.query_cache_capacity,
+            cost_budget: self.query_cost_budget,
+            cost_period: self.query_cost_period_ms.get(),
         };
 
         (torii, query)
@@ -0,0 +1,39 @@
+//! Configuration tools related to the peer as a whole.
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// Part the peer plays in the network.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Role {
+    /// Accepts and gossips transactions, and takes part in consensus once registered.
+    #[default]
+    Validator,
+    /// Follows the chain and serves queries and events, but rejects transaction
+    /// submissions and doesn't gossip transactions. Must not be registered as a peer.
+    ReadReplica,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::peer::Role;
+
+    #[test]
+    fn role_display_reprs() {
+        assert_eq!(format!("{}", Role::Validator), "validator");
+        assert_eq!(format!("{}", Role::ReadReplica), "read_replica");
+        assert_eq!("validator".parse::<Role>().unwrap(), Role::Validator);
+        assert_eq!("read_replica".parse::<Role>().unwrap(), Role::ReadReplica);
+    }
+}
@@ -0,0 +1,140 @@
+//! Configuration related to the transactions Queue specifically
+
+use std::str::FromStr;
+
+use iroha_data_model::{account::AccountId, domain::DomainId, ParseError};
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// Built-in check a transaction has to pass to be admitted into the queue
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum AdmissionCheck {
+    /// The authority is admitted by the [`AuthorityFilter`]
+    AuthorityFilter,
+    /// The transaction isn't created too far in the future
+    FutureTimestamp,
+    /// The transaction hasn't outlived its time to live
+    Expiry,
+    /// The transaction isn't committed yet
+    Committed,
+}
+
+/// Authorities matched by an entry of the [`AuthorityFilter`] lists
+#[derive(
+    Debug, Clone, PartialEq, Eq, derive_more::Display, SerializeDisplay, DeserializeFromStr,
+)]
+pub enum AuthorityRule {
+    /// Single account, written as `signatory@domain`
+    Account(AccountId),
+    /// All accounts of the domain, written as `domain`
+    Domain(DomainId),
+}
+
+impl AuthorityRule {
+    /// Check whether `authority` is matched by the rule
+    pub fn matches(&self, authority: &AccountId) -> bool {
+        match self {
+            Self::Account(account) => account == authority,
+            Self::Domain(domain) => authority.domain() == domain,
+        }
+    }
+}
+
+impl FromStr for AuthorityRule {
+    type Err = ParseError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        if rule.contains('@') {
+            rule.parse().map(Self::Account)
+        } else {
+            rule.parse().map(Self::Domain)
+        }
+    }
+}
+
+/// Lists of authorities whose transactions are admitted into or kept out of the queue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorityFilter {
+    /// If not empty, only transactions of the matching authorities are admitted
+    pub allow: Vec<AuthorityRule>,
+    /// Transactions of the matching authorities are never admitted
+    pub deny: Vec<AuthorityRule>,
+}
+
+impl AuthorityFilter {
+    /// Check whether transactions of `authority` are admitted.
+    ///
+    /// The deny list takes precedence over the allow list.
+    pub fn admits(&self, authority: &AccountId) -> bool {
+        let matches = |rules: &[AuthorityRule]| rules.iter().any(|rule| rule.matches(authority));
+
+        !matches(&self.deny) && (self.allow.is_empty() || matches(&self.allow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_crypto::KeyPair;
+
+    use super::*;
+
+    fn account_in(domain: &str) -> AccountId {
+        AccountId::new(domain.parse().unwrap(), KeyPair::random().into_parts().0)
+    }
+
+    #[test]
+    fn admission_check_display_reprs() {
+        assert_eq!(
+            format!("{}", AdmissionCheck::AuthorityFilter),
+            "authority_filter"
+        );
+        assert_eq!(
+            "future_timestamp".parse::<AdmissionCheck>().unwrap(),
+            AdmissionCheck::FutureTimestamp
+        );
+    }
+
+    #[test]
+    fn rule_display_form() {
+        let account = account_in("wonderland");
+        let rules = [
+            AuthorityRule::Account(account.clone()),
+            AuthorityRule::Domain("wonderland".parse().unwrap()),
+        ];
+
+        for rule in rules {
+            assert_eq!(rule.to_string().parse::<AuthorityRule>().unwrap(), rule);
+        }
+        assert_eq!(
+            AuthorityRule::Account(account.clone()).to_string(),
+            account.to_string()
+        );
+    }
+
+    #[test]
+    fn deny_list_takes_precedence() {
+        let alice = account_in("wonderland");
+        let bob = account_in("wonderland");
+        let carol = account_in("looking_glass");
+        let filter = AuthorityFilter {
+            allow: vec![AuthorityRule::Domain("wonderland".parse().unwrap())],
+            deny: vec![AuthorityRule::Account(bob.clone())],
+        };
+
+        assert!(filter.admits(&alice));
+        assert!(!filter.admits(&bob));
+        assert!(!filter.admits(&carol));
+        assert!(AuthorityFilter::default().admits(&carol));
+    }
+}
@@ -0,0 +1,46 @@
+//! Configuration tools related to Sumeragi specifically.
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// Order in which the leader packs pending transactions into a block.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum BlockPacking {
+    /// Transactions are packed in the order of arrival.
+    #[default]
+    Fifo,
+    /// Transactions with higher priority (set in the transaction metadata) are packed first.
+    Priority,
+    /// Transactions of different accounts are packed in turns.
+    FairRoundRobin,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sumeragi::BlockPacking;
+
+    #[test]
+    fn block_packing_display_reprs() {
+        assert_eq!(format!("{}", BlockPacking::Fifo), "fifo");
+        assert_eq!(format!("{}", BlockPacking::Priority), "priority");
+        assert_eq!(
+            format!("{}", BlockPacking::FairRoundRobin),
+            "fair_round_robin"
+        );
+        assert_eq!(
+            "fair_round_robin".parse::<BlockPacking>().unwrap(),
+            BlockPacking::FairRoundRobin
+        );
+    }
+}
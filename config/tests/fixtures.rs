@@ -88,6 +88,7 @@ fn minimal_config_snapshot() {
                         ),
                     ),
                 },
+                role: Validator,
             },
             network: Network {
                 address: WithOrigin {
@@ -98,6 +99,10 @@ fn minimal_config_snapshot() {
                     },
                 },
                 idle_timeout: 60s,
+                reputation_ban_threshold: 100,
+                reputation_ban_duration: 600s,
+                clock_skew_warn_threshold: 500ms,
+                partition_timeout: 30s,
             },
             genesis: Genesis {
                 public_key: PublicKey(
@@ -106,6 +111,7 @@ fn minimal_config_snapshot() {
                     ),
                 ),
                 file: None,
+                allow_insecure_dev_mode: false,
             },
             torii: Torii {
                 address: WithOrigin {
@@ -118,6 +124,9 @@ fn minimal_config_snapshot() {
                 max_content_len: Bytes(
                     16777216,
                 ),
+                webhooks_per_account: 8,
+                webhook_max_retries: 5,
+                webhook_retry_delay: 1s,
             },
             kura: Kura {
                 init_mode: Strict,
@@ -127,6 +136,9 @@ fn minimal_config_snapshot() {
                         id: ParameterId(kura.store_dir),
                     },
                 },
+                explorer_index: false,
+                block_compression_level: 0,
+                pipeline_depth: 4,
                 debug_output_new_blocks: false,
             },
             sumeragi: Sumeragi {
@@ -158,6 +170,8 @@ fn minimal_config_snapshot() {
                         path: "tests/fixtures/base_trusted_peers.toml",
                     },
                 },
+                block_packing: Fifo,
+                block_packing_lookahead: 1,
                 debug_force_soft_fork: false,
             },
             block_sync: BlockSync {
@@ -172,6 +186,9 @@ fn minimal_config_snapshot() {
                 idle_time: 10s,
                 capacity: 128,
                 capacity_per_user: 128,
+                cache_capacity: None,
+                cost_budget: None,
+                cost_period: 60s,
             },
             logger: Logger {
                 level: info,
@@ -182,6 +199,17 @@ fn minimal_config_snapshot() {
                 capacity_per_user: 65536,
                 transaction_time_to_live: 86400s,
                 future_threshold: 1s,
+                clock_skew_tolerance: 0ns,
+                admission_checks: [
+                    AuthorityFilter,
+                    FutureTimestamp,
+                    Expiry,
+                    Committed,
+                ],
+                authority_filter: AuthorityFilter {
+                    allow: [],
+                    deny: [],
+                },
             },
             snapshot: Snapshot {
                 mode: ReadWrite,
@@ -194,11 +222,26 @@ fn minimal_config_snapshot() {
                         id: ParameterId(snapshot.store_dir),
                     },
                 },
+                checksum_algorithm: Blake2b256,
+            },
+            state: State {
+                record_block_diffs: false,
+                archive_events: false,
+                profile_instructions: false,
+                wasm_module_cache_size: 64,
+                validation_cache_size: 4096,
+                digest_period: 100,
+                digest_gossip_period: 10s,
             },
             telemetry: None,
             dev_telemetry: DevTelemetry {
                 out_file: None,
             },
+            shutdown: Shutdown {
+                timeout: 30s,
+            },
+            admin: None,
+            event_sinks: [],
         }"#]].assert_eq(&format!("{config:#?}"));
 }
 
@@ -33,12 +33,16 @@ async fn measure_block_size_for_n_executors(n_executors: u32) {
     let txn_limits = TransactionParameters {
         max_instructions: nonzero!(4096_u64),
         smart_contract_size: nonzero!(1_u64),
+        ..TransactionParameters::default()
     };
     let tx = AcceptedTransaction::accept(tx, &chain_id, txn_limits)
         .expect("Failed to accept Transaction.");
     let dir = tempfile::tempdir().expect("Could not create tempfile.");
     let cfg = Config {
         init_mode: iroha_config::kura::InitMode::Strict,
+        explorer_index: false,
+        block_compression_level: 0,
+        pipeline_depth: nonzero!(4_usize),
         debug_output_new_blocks: false,
         store_dir: WithOrigin::inline(dir.path().to_path_buf()),
     };
@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
 
+use std::num::NonZeroU64;
+
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use iroha_core::{
     block::*,
@@ -22,8 +24,14 @@ static STARTER_KEYPAIR: Lazy<KeyPair> = Lazy::new(KeyPair::random);
 static STARTER_ID: Lazy<AccountId> =
     Lazy::new(|| AccountId::new(STARTER_DOMAIN.clone(), STARTER_KEYPAIR.public_key().clone()));
 
-const TRANSACTION_LIMITS: TransactionParameters =
-    TransactionParameters::new(nonzero!(4096_u64), nonzero!(1_u64));
+const TRANSACTION_LIMITS: TransactionParameters = TransactionParameters::new(
+    nonzero!(4096_u64),
+    nonzero!(1_u64),
+    nonzero!(1_u64),
+    nonzero!(1024_u64),
+    nonzero!(1_u64),
+    NonZeroU64::MAX,
+);
 
 fn build_test_transaction(chain_id: ChainId) -> TransactionBuilder {
     let domain_id: DomainId = "domain".parse().unwrap();
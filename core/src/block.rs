@@ -10,6 +10,7 @@ use iroha_crypto::{HashOf, KeyPair, MerkleTree};
 use iroha_data_model::{
     block::*,
     events::prelude::*,
+    parameter::WasmEngine,
     peer::PeerId,
     transaction::{error::TransactionRejectionReason, prelude::*},
 };
@@ -69,8 +70,48 @@ pub enum BlockValidationError {
     SignatureVerification(#[from] SignatureVerificationError),
     /// Received view change index is too large
     ViewChangeIndexTooLarge,
+    /// Block has more transactions than allowed. Max: {max}, actual: {actual}
+    TooManyTransactions {
+        /// Maximal number of transactions
+        max: u64,
+        /// Actual number of transactions
+        actual: u64,
+    },
+    /// Transactions of the block exceed the size limit. Max: {max_bytes} bytes, actual: {actual_bytes} bytes
+    TransactionsTooLarge {
+        /// Maximal total size of transactions
+        max_bytes: u64,
+        /// Actual total size of transactions
+        actual_bytes: u64,
+    },
     /// Invalid genesis block: {0}
     InvalidGenesis(#[from] InvalidGenesisError),
+    /// Transaction {transaction} is scheduled after the block creation time
+    TransactionNotEligible {
+        /// Hash of the transaction included too early
+        transaction: HashOf<SignedTransaction>,
+    },
+    /// Block is of protocol version {version}, but this peer supports versions up to {supported}. Upgrade the peer to validate this block
+    UnsupportedProtocolVersion {
+        /// Version of the block
+        version: u32,
+        /// Highest version supported by this peer
+        supported: u32,
+    },
+    /// Mismatch between the actual and expected protocol version of the block. Expected: {expected}, actual: {actual}
+    ProtocolVersionMismatch {
+        /// Version scheduled for the height of the block
+        expected: u32,
+        /// Version of the block
+        actual: u32,
+    },
+    /// Network runs smart contracts with the {expected} WASM engine, but this peer runs {actual}. Rebuild the peer with the engine of the network to validate its blocks
+    WasmEngineMismatch {
+        /// Engine recorded in the parameters of the network
+        expected: WasmEngine,
+        /// Engine of this peer
+        actual: WasmEngine,
+    },
 }
 
 /// Error during signature verification
@@ -113,11 +154,12 @@ pub struct BlockBuilder<B>(B);
 
 mod pending {
     use std::{
-        num::NonZeroUsize,
-        time::{Duration, SystemTime},
+        num::{NonZeroU64, NonZeroUsize},
+        time::Duration,
     };
 
-    use iroha_data_model::transaction::CommittedTransaction;
+    use iroha_data_model::{parameter::ProtocolUpgrade, transaction::CommittedTransaction};
+    use iroha_primitives::time::TimeSource;
 
     use super::*;
     use crate::state::StateBlock;
@@ -131,6 +173,8 @@ mod pending {
         /// Collection of transactions which have been accepted.
         /// Transaction will be validated when block is chained.
         transactions: Vec<AcceptedTransaction>,
+        /// Clock the creation time of the block is taken from
+        time_source: TimeSource,
     }
 
     impl BlockBuilder<Pending> {
@@ -141,25 +185,39 @@ mod pending {
         /// if the given list of transaction is empty
         #[inline]
         pub fn new(transactions: Vec<AcceptedTransaction>) -> Self {
-            Self(Pending { transactions })
+            Self(Pending {
+                transactions,
+                time_source: TimeSource::new_system(),
+            })
+        }
+
+        /// Take the creation time of the block from `time_source` instead of the system clock
+        #[must_use]
+        pub fn with_time_source(mut self, time_source: TimeSource) -> Self {
+            self.0.time_source = time_source;
+            self
         }
 
         fn make_header(
+            creation_time: Duration,
             prev_height: usize,
             prev_block_hash: Option<HashOf<SignedBlock>>,
             view_change_index: usize,
             transactions: &[CommittedTransaction],
             consensus_estimation: Duration,
+            protocol_upgrade: ProtocolUpgrade,
         ) -> BlockHeader {
+            let height: NonZeroU64 = NonZeroUsize::new(
+                prev_height
+                    .checked_add(1)
+                    .expect("INTERNAL BUG: Blockchain height exceeds usize::MAX"),
+            )
+            .expect("INTERNAL BUG: block height must not be 0")
+            .try_into()
+            .expect("INTERNAL BUG: Number of blocks exceeds u64::MAX");
+
             BlockHeader {
-                height: NonZeroUsize::new(
-                    prev_height
-                        .checked_add(1)
-                        .expect("INTERNAL BUG: Blockchain height exceeds usize::MAX"),
-                )
-                .expect("INTERNAL BUG: block height must not be 0")
-                .try_into()
-                .expect("INTERNAL BUG: Number of blocks exceeds u64::MAX"),
+                height,
                 prev_block_hash,
                 transactions_hash: transactions
                     .iter()
@@ -167,9 +225,7 @@ mod pending {
                     .collect::<MerkleTree<_>>()
                     .hash()
                     .expect("INTERNAL BUG: Empty block created"),
-                creation_time_ms: SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .expect("INTERNAL BUG: Failed to get the current system time")
+                creation_time_ms: creation_time
                     .as_millis()
                     .try_into()
                     .expect("Time should fit into u64"),
@@ -180,6 +236,7 @@ mod pending {
                     .as_millis()
                     .try_into()
                     .expect("INTERNAL BUG: Time should fit into u64"),
+                protocol_version: protocol_upgrade.version_at(height.get()),
             }
         }
 
@@ -187,27 +244,28 @@ mod pending {
             transactions: Vec<AcceptedTransaction>,
             state_block: &mut StateBlock<'_>,
         ) -> Vec<CommittedTransaction> {
+            // Parameters set by the transactions of this block take effect from the next block
+            let transaction_executor = state_block.transaction_executor();
+
             transactions
                 .into_iter()
-                .map(
-                    |tx| match state_block.transaction_executor().validate(tx, state_block) {
-                        Ok(tx) => CommittedTransaction {
+                .map(|tx| match transaction_executor.validate(tx, state_block) {
+                    Ok(tx) => CommittedTransaction {
+                        value: tx,
+                        error: None,
+                    },
+                    Err((tx, error)) => {
+                        iroha_logger::warn!(
+                            reason = %error,
+                            caused_by = ?error.source(),
+                            "Transaction validation failed",
+                        );
+                        CommittedTransaction {
                             value: tx,
-                            error: None,
-                        },
-                        Err((tx, error)) => {
-                            iroha_logger::warn!(
-                                reason = %error,
-                                caused_by = ?error.source(),
-                                "Transaction validation failed",
-                            );
-                            CommittedTransaction {
-                                value: tx,
-                                error: Some(error),
-                            }
+                            error: Some(error),
                         }
-                    },
-                )
+                    }
+                })
                 .collect()
         }
 
@@ -219,15 +277,23 @@ mod pending {
             view_change_index: usize,
             state: &mut StateBlock<'_>,
         ) -> BlockBuilder<Chained> {
-            let transactions = Self::categorize_transactions(self.0.transactions, state);
+            let Pending {
+                transactions,
+                time_source,
+            } = self.0;
+            // Must be read before the transactions of this block are executed
+            let protocol_upgrade = state.world.parameters().block.protocol_upgrade;
+            let transactions = Self::categorize_transactions(transactions, state);
 
             BlockBuilder(Chained(BlockPayload {
                 header: Self::make_header(
+                    time_source.get_unix_time(),
                     state.height(),
                     state.latest_block_hash(),
                     view_change_index,
                     &transactions,
                     state.world.parameters().sumeragi.consensus_estimation(),
+                    protocol_upgrade,
                 ),
                 transactions,
             }))
@@ -253,7 +319,13 @@ mod chained {
 mod valid {
     use commit::CommittedBlock;
     use indexmap::IndexMap;
-    use iroha_data_model::{account::AccountId, events::pipeline::PipelineEventBox, ChainId};
+    use iroha_data_model::{
+        account::AccountId,
+        events::pipeline::PipelineEventBox,
+        parameter::{BlockParameters, ProtocolUpgrade},
+        ChainId,
+    };
+    use parity_scale_codec::Encode as _;
     use storage::storage::StorageReadOnly;
 
     use super::*;
@@ -316,7 +388,7 @@ mod valid {
                     let signatory_idx = usize::try_from(signature.0)
                         .map_err(|_err| SignatureVerificationError::UnknownSignatory)?;
 
-                    if acc.insert(signatory_idx, signature.1.clone()).is_some() {
+                    if acc.insert(signatory_idx, &signature.1).is_some() {
                         return Err(SignatureVerificationError::DuplicateSignatures {
                             signatory: signatory_idx,
                         });
@@ -398,6 +470,7 @@ mod valid {
         ///
         /// - There is a mismatch between candidate block height and actual blockchain height
         /// - There is a mismatch between candidate block previous block hash and actual previous block hash
+        /// - Block is of a protocol version which isn't supported or isn't scheduled for its height
         /// - Block is not signed by the leader
         /// - Block has duplicate signatures
         /// - Block has unknown signatories
@@ -511,12 +584,20 @@ mod valid {
                 });
             }
 
+            Self::verify_protocol_version(
+                block,
+                state.world().parameters().block.protocol_upgrade,
+            )?;
+
             if block.header().is_genesis() {
                 check_genesis_block(block, genesis_account)?;
             } else {
+                // Genesis block sets the engine of the network
+                Self::verify_wasm_engine(state.world().parameters().wasm_engine())?;
                 Self::verify_leader_signature(block, topology)?;
                 Self::verify_validator_signatures(block, topology)?;
                 Self::verify_no_undefined_signatures(block, topology)?;
+                Self::verify_block_limits(block, state.world().parameters().block)?;
             }
 
             if block.transactions().any(|tx| {
@@ -529,6 +610,74 @@ mod valid {
                 return Err(BlockValidationError::HasCommittedTransactions);
             }
 
+            let block_time = block.header().creation_time();
+            if let Some(tx) = block
+                .transactions()
+                .find(|tx| !tx.as_ref().is_eligible_at(block_time))
+            {
+                return Err(BlockValidationError::TransactionNotEligible {
+                    transaction: tx.as_ref().hash(),
+                });
+            }
+
+            Ok(())
+        }
+
+        fn verify_protocol_version(
+            block: &SignedBlock,
+            protocol_upgrade: ProtocolUpgrade,
+        ) -> Result<(), BlockValidationError> {
+            let header = block.header();
+            if header.protocol_version > PROTOCOL_VERSION {
+                return Err(BlockValidationError::UnsupportedProtocolVersion {
+                    version: header.protocol_version,
+                    supported: PROTOCOL_VERSION,
+                });
+            }
+
+            let expected = protocol_upgrade.version_at(header.height.get());
+            if header.protocol_version != expected {
+                return Err(BlockValidationError::ProtocolVersionMismatch {
+                    expected,
+                    actual: header.protocol_version,
+                });
+            }
+
+            Ok(())
+        }
+
+        fn verify_wasm_engine(expected: WasmEngine) -> Result<(), BlockValidationError> {
+            let actual = crate::smartcontracts::wasm::ENGINE;
+            if expected != actual {
+                return Err(BlockValidationError::WasmEngineMismatch { expected, actual });
+            }
+
+            Ok(())
+        }
+
+        fn verify_block_limits(
+            block: &SignedBlock,
+            limits: BlockParameters,
+        ) -> Result<(), BlockValidationError> {
+            let transactions = block.transactions().len() as u64;
+            if transactions > limits.max_transactions.get() {
+                return Err(BlockValidationError::TooManyTransactions {
+                    max: limits.max_transactions.get(),
+                    actual: transactions,
+                });
+            }
+
+            let size_bytes = block
+                .transactions()
+                .map(|tx| tx.as_ref().encoded_size() as u64)
+                .sum::<u64>();
+            if size_bytes > limits.max_size_bytes.get() {
+                return Err(BlockValidationError::TransactionsTooLarge {
+                    max_bytes: limits.max_size_bytes.get(),
+                    actual_bytes: size_bytes,
+                });
+            }
+
             Ok(())
         }
 
@@ -539,14 +688,14 @@ mod valid {
             state_block: &mut StateBlock<'_>,
         ) -> Result<(), TransactionValidationError> {
             let is_genesis = block.header().is_genesis();
+            // Must match the limits the block was created with, see `categorize_transactions`
+            let transaction_executor = state_block.transaction_executor();
 
             block
                 .transactions()
                 // TODO: Unnecessary clone?
                 .cloned()
                 .try_for_each(|CommittedTransaction { value, error }| {
-                    let transaction_executor = state_block.transaction_executor();
-
                     let tx = if is_genesis {
                         AcceptedTransaction::accept_genesis(
                             value,
@@ -597,8 +746,12 @@ mod valid {
             assert_ne!(Role::Undefined, topology.role(signatory));
             assert_ne!(Role::ProxyTail, topology.role(signatory));
 
+            let BlockSignature(index, signature) = signature;
+            let signature = signature
+                .verify(signatory.public_key(), self.0.payload())
+                .map_err(|_err| SignatureVerificationError::UnknownSignature)?;
             self.0
-                .add_signature(signature, signatory.public_key())
+                .add_signature(index, signature)
                 .map_err(|_err| SignatureVerificationError::UnknownSignature)
         }
 
@@ -739,6 +892,7 @@ mod valid {
                     creation_time_ms: 0,
                     view_change_index: 0,
                     consensus_estimation_ms: 4_000,
+                    protocol_version: GENESIS_PROTOCOL_VERSION,
                 },
                 transactions: Vec::new(),
             };
@@ -761,7 +915,7 @@ mod valid {
         }
     }
 
-    // See also [SignedBlockCandidate::validate_genesis]
+    // See also [SignedBlockV1::validate_genesis]
     fn check_genesis_block(
         block: &SignedBlock,
         genesis_account: &AccountId,
@@ -786,11 +940,100 @@ mod valid {
 
     #[cfg(test)]
     mod tests {
-        use iroha_crypto::SignatureOf;
+        use std::num::NonZeroU64;
+
+        use iroha_crypto::Verified;
+        use iroha_data_model::{isi::Log, transaction::TransactionBuilder, Level};
 
         use super::*;
         use crate::sumeragi::network_topology::test_peers;
 
+        fn block_with_transactions(count: usize) -> SignedBlock {
+            let (alice_id, alice_keypair) = test_samples::gen_account_in("wonderland");
+            let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+            let transactions = (0..count)
+                .map(|i| CommittedTransaction {
+                    value: TransactionBuilder::new(chain_id.clone(), alice_id.clone())
+                        .with_instructions([Log::new(Level::INFO, format!("{i:04}"))])
+                        .sign(alice_keypair.private_key()),
+                    error: None,
+                })
+                .collect();
+
+            ValidBlock::new_dummy_and_modify_payload(KeyPair::random().private_key(), |payload| {
+                payload.transactions = transactions;
+            })
+            .into()
+        }
+
+        fn transactions_size_bytes(block: &SignedBlock) -> u64 {
+            block
+                .transactions()
+                .map(|tx| tx.as_ref().encoded_size() as u64)
+                .sum()
+        }
+
+        #[test]
+        fn block_limits_are_inclusive() {
+            let block = block_with_transactions(3);
+            let limits = BlockParameters::new(
+                nonzero_ext::nonzero!(3_u64),
+                NonZeroU64::new(transactions_size_bytes(&block)).unwrap(),
+            );
+
+            assert_eq!(ValidBlock::verify_block_limits(&block, limits), Ok(()));
+        }
+
+        #[test]
+        fn block_with_too_many_transactions_is_rejected() {
+            let block = block_with_transactions(3);
+            let limits = BlockParameters::new(
+                nonzero_ext::nonzero!(2_u64),
+                NonZeroU64::new(transactions_size_bytes(&block)).unwrap(),
+            );
+
+            assert_eq!(
+                ValidBlock::verify_block_limits(&block, limits),
+                Err(BlockValidationError::TooManyTransactions { max: 2, actual: 3 })
+            );
+        }
+
+        #[test]
+        fn block_with_too_large_transactions_is_rejected() {
+            let block = block_with_transactions(3);
+            let size_bytes = transactions_size_bytes(&block);
+            let limits = BlockParameters::new(
+                nonzero_ext::nonzero!(3_u64),
+                NonZeroU64::new(size_bytes - 1).unwrap(),
+            );
+
+            assert_eq!(
+                ValidBlock::verify_block_limits(&block, limits),
+                Err(BlockValidationError::TransactionsTooLarge {
+                    max_bytes: size_bytes - 1,
+                    actual_bytes: size_bytes,
+                })
+            );
+        }
+
+        #[test]
+        fn blocks_of_another_wasm_engine_are_rejected() {
+            let local = crate::smartcontracts::wasm::ENGINE;
+            let other = match local {
+                WasmEngine::Wasmtime => WasmEngine::Wasmi,
+                WasmEngine::Wasmi => WasmEngine::Wasmtime,
+            };
+
+            assert!(ValidBlock::verify_wasm_engine(local).is_ok());
+            assert_eq!(
+                ValidBlock::verify_wasm_engine(other),
+                Err(BlockValidationError::WasmEngineMismatch {
+                    expected: other,
+                    actual: local,
+                })
+            );
+        }
+
         #[test]
         fn signature_verification_ok() {
             let key_pairs = core::iter::repeat_with(KeyPair::random)
@@ -811,7 +1054,10 @@ mod valid {
                 .skip(1)
                 .filter(|(i, _)| *i != 4) // Skip proxy tail
                 .map(|(i, key_pair)| {
-                    BlockSignature(i as u64, SignatureOf::new(key_pair.private_key(), &payload))
+                    BlockSignature(
+                        i as u64,
+                        Verified::sign(key_pair.private_key(), &payload).into(),
+                    )
                 })
                 .try_for_each(|signature| block.add_signature(signature, &topology))
                 .expect("Failed to add signatures");
@@ -879,7 +1125,10 @@ mod valid {
                 .skip(1)
                 .filter(|(i, _)| *i != 4) // Skip proxy tail
                 .map(|(i, key_pair)| {
-                    BlockSignature(i as u64, SignatureOf::new(key_pair.private_key(), &payload))
+                    BlockSignature(
+                        i as u64,
+                        Verified::sign(key_pair.private_key(), &payload).into(),
+                    )
                 })
                 .try_for_each(|signature| block.add_signature(signature, &topology))
                 .expect("Failed to add signatures");
@@ -1004,6 +1253,7 @@ mod event {
                     block_height: Some(block_height),
                     hash: tx.as_ref().hash(),
                     status,
+                    metadata: tx.as_ref().metadata().clone(),
                 }
             });
 
@@ -1048,16 +1298,21 @@ mod event {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr as _;
+    use std::{num::NonZeroU64, str::FromStr as _, time::Duration};
 
-    use iroha_data_model::prelude::*;
+    use iroha_crypto::Algorithm;
+    use iroha_data_model::{parameter::ProtocolUpgrade, prelude::*};
     use iroha_genesis::GENESIS_DOMAIN_ID;
+    use iroha_primitives::time::TimeSource;
+    use rand::{rngs::StdRng, seq::SliceRandom as _, Rng as _, SeedableRng as _};
     use test_samples::gen_account_in;
 
     use super::*;
     use crate::{
-        kura::Kura, query::store::LiveQueryStore, smartcontracts::isi::Registrable as _,
-        state::State,
+        kura::Kura,
+        query::store::LiveQueryStore,
+        smartcontracts::isi::Registrable as _,
+        state::{State, StateBlock},
     };
 
     #[test]
@@ -1131,6 +1386,40 @@ mod tests {
             .is_some());
     }
 
+    #[tokio::test]
+    async fn block_creation_time_is_taken_from_time_source() {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let account = Account::new(alice_id.clone()).build(&alice_id);
+        let domain_id = DomainId::from_str("wonderland").expect("Valid");
+        let domain = Domain::new(domain_id).build(&alice_id);
+        let world = World::with([domain], [account], []);
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(world, kura, query_handle);
+        let mut state_block = state.block();
+
+        let transaction_limits = state_block.transaction_executor().limits;
+        let tx = TransactionBuilder::new(chain_id.clone(), alice_id)
+            .with_instructions([Log::new(iroha_data_model::Level::INFO, "tick".to_owned())])
+            .sign(alice_keypair.private_key());
+        let tx = AcceptedTransaction::accept(tx, &chain_id, transaction_limits).expect("Valid");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::from_secs(1_000));
+        time_handle.advance(Duration::from_millis(250));
+        let valid_block = BlockBuilder::new(vec![tx])
+            .with_time_source(time_source)
+            .chain(0, &mut state_block)
+            .sign(alice_keypair.private_key())
+            .unpack(|_| {});
+
+        assert_eq!(
+            valid_block.as_ref().header().creation_time(),
+            Duration::from_millis(1_000_250)
+        );
+    }
+
     #[tokio::test]
     async fn tx_order_same_in_validation_and_revalidation() {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
@@ -1267,6 +1556,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn deprecated_transaction_version_is_rejected() {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+
+        // Predefined world state
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let account = Account::new(alice_id.clone()).build(&alice_id);
+        let domain_id = DomainId::from_str("wonderland").expect("Valid");
+        let domain = Domain::new(domain_id).build(&alice_id);
+        let world = World::with([domain], [account], []);
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(world, kura, query_handle);
+        let mut state_block = state.block();
+        state_block
+            .world
+            .parameters
+            .transaction
+            .v1_deprecation_height = NonZeroU64::MIN;
+        let transaction_limits = state_block.transaction_executor().limits;
+
+        let build_tx = |version, domain: &str| {
+            let mut tx = TransactionBuilder::new(chain_id.clone(), alice_id.clone())
+                .with_instructions([Register::domain(Domain::new(domain.parse().unwrap()))]);
+            tx.set_version(version);
+            let tx = tx.sign(alice_keypair.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, transaction_limits).expect("Valid")
+        };
+        let transactions = vec![
+            build_tx(TransactionVersion::V1, "legacy"),
+            build_tx(TransactionVersion::V2, "current"),
+        ];
+        let valid_block = BlockBuilder::new(transactions)
+            .chain(0, &mut state_block)
+            .sign(alice_keypair.private_key())
+            .unpack(|_| {});
+
+        let mut transactions = valid_block.as_ref().transactions();
+        assert!(matches!(
+            transactions.next().unwrap().error,
+            Some(TransactionRejectionReason::LimitCheck(_))
+        ));
+        assert!(transactions.next().unwrap().error.is_none());
+    }
+
     #[tokio::test]
     async fn genesis_public_key_is_checked() {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
@@ -1335,4 +1669,205 @@ mod tests {
             BlockValidationError::InvalidGenesis(InvalidGenesisError::UnexpectedAuthority)
         )
     }
+
+    #[tokio::test]
+    async fn block_protocol_version_is_checked() {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let (genesis_id, _) = gen_account_in("genesis");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(World::with([], [], []), kura, query_handle);
+        let mut state_block = state.block();
+        let leader_key_pair = KeyPair::random();
+        let peer_id = PeerId::new(
+            "127.0.0.1:8080".parse().unwrap(),
+            leader_key_pair.public_key().clone(),
+        );
+        let topology = Topology::new(vec![peer_id]);
+
+        let validate = |protocol_version, state_block: &mut StateBlock<'_>| {
+            let block = ValidBlock::new_dummy_and_modify_payload(
+                leader_key_pair.private_key(),
+                |payload| {
+                    payload.header.height = NonZeroU64::MIN;
+                    payload.header.protocol_version = protocol_version;
+                },
+            );
+            ValidBlock::validate(block.into(), &topology, &chain_id, &genesis_id, state_block)
+                .unpack(|_| {})
+                .unwrap_err()
+                .1
+        };
+
+        assert_eq!(
+            validate(PROTOCOL_VERSION + 1, &mut state_block),
+            BlockValidationError::UnsupportedProtocolVersion {
+                version: PROTOCOL_VERSION + 1,
+                supported: PROTOCOL_VERSION,
+            }
+        );
+        // The upgrade is active starting from the height of the block
+        state_block.world.parameters.block.protocol_upgrade = ProtocolUpgrade::new(
+            GENESIS_PROTOCOL_VERSION,
+            GENESIS_PROTOCOL_VERSION + 1,
+            NonZeroU64::MIN,
+        );
+        assert_eq!(
+            validate(GENESIS_PROTOCOL_VERSION, &mut state_block),
+            BlockValidationError::ProtocolVersionMismatch {
+                expected: GENESIS_PROTOCOL_VERSION + 1,
+                actual: GENESIS_PROTOCOL_VERSION,
+            }
+        );
+    }
+
+    /// Replays a randomized workload on two peers which built the same world
+    /// inserting its entities in different order. Any collection iterated in
+    /// hash or insertion order would make the emitted events diverge.
+    #[tokio::test]
+    async fn events_are_emitted_in_the_same_order_on_every_peer() {
+        const ACCOUNTS: usize = 6;
+        const TRIGGERS: usize = 3;
+        const BLOCKS: usize = 4;
+        const TRANSACTIONS: usize = 8;
+
+        /// Seed of the workload unless another one is given in `IROHA_TEST_SEED`
+        const DEFAULT_SEED: u64 = 3965;
+
+        let seed = std::env::var("IROHA_TEST_SEED").map_or(DEFAULT_SEED, |seed| {
+            seed.parse()
+                .unwrap_or_else(|_| panic!("IROHA_TEST_SEED must be a number, got `{seed}`"))
+        });
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let domain_id = DomainId::from_str("wonderland").expect("Valid");
+        let accounts = (0..ACCOUNTS)
+            .map(|_| {
+                let key_pair =
+                    KeyPair::from_seed(rng.gen::<[u8; 32]>().to_vec(), Algorithm::default());
+                let account_id = AccountId::new(domain_id.clone(), key_pair.public_key().clone());
+                (account_id, key_pair)
+            })
+            .collect::<Vec<_>>();
+        let alice_id = accounts[0].0.clone();
+        let asset_definition_ids = ["rose#wonderland", "tulip#wonderland", "daisy#wonderland"]
+            .map(|id| AssetDefinitionId::from_str(id).expect("Valid"));
+        let new_state = |reversed: bool| {
+            let domain = Domain::new(domain_id.clone()).build(&alice_id);
+            let mut accounts = accounts
+                .iter()
+                .map(|(id, _)| Account::new(id.clone()).build(&alice_id))
+                .collect::<Vec<_>>();
+            let mut asset_definitions = asset_definition_ids
+                .iter()
+                .map(|id| AssetDefinition::numeric(id.clone()).build(&alice_id))
+                .collect::<Vec<_>>();
+            if reversed {
+                accounts.reverse();
+                asset_definitions.reverse();
+            }
+            let world = World::with([domain], accounts, asset_definitions);
+            let query_handle = LiveQueryStore::test().start();
+            State::new(world, Kura::blank_kura_for_testing(), query_handle)
+        };
+        let leader = new_state(false);
+        let follower = new_state(true);
+
+        let (leader_public_key, leader_private_key) =
+            KeyPair::from_seed(rng.gen::<[u8; 32]>().to_vec(), Algorithm::default()).into_parts();
+        let peer_id = PeerId::new("127.0.0.1:8080".parse().unwrap(), leader_public_key);
+        let topology = Topology::new(vec![peer_id.clone()]);
+        let transaction_limits = leader.block().transaction_executor().limits;
+
+        let mut triggers = (0..TRIGGERS)
+            .map(|i| {
+                let key = Name::from_str(&format!("seen_by_{i}")).expect("Valid");
+                let action = Action::new(
+                    [SetKeyValue::account(alice_id.clone(), key, true)],
+                    Repeats::Indefinitely,
+                    alice_id.clone(),
+                    DataEventFilter::Asset(AssetEventFilter::new()),
+                );
+                let id = TriggerId::from_str(&format!("watcher_{i}")).expect("Valid");
+                InstructionBox::from(Register::trigger(Trigger::new(id, action)))
+            })
+            .collect::<Vec<_>>();
+        triggers.shuffle(&mut rng);
+        let mut workload = vec![(alice_id.clone(), triggers)];
+
+        let mut emitted = Vec::new();
+        for _ in 0..BLOCKS {
+            workload.extend((0..TRANSACTIONS).map(|_| {
+                let authority = accounts.choose(&mut rng).unwrap().0.clone();
+                let instructions = (0..rng.gen_range(1..=3))
+                    .map(|_| -> InstructionBox {
+                        let definition_id = asset_definition_ids.choose(&mut rng).unwrap();
+                        let account_id = accounts.choose(&mut rng).unwrap().0.clone();
+                        let asset_id = AssetId::new(definition_id.clone(), account_id);
+                        let quantity = rng.gen_range(1_u32..100);
+                        match rng.gen_range(0..3) {
+                            0 => Mint::asset_numeric(quantity, asset_id).into(),
+                            1 => Burn::asset_numeric(quantity, asset_id).into(),
+                            _ => {
+                                let destination = accounts.choose(&mut rng).unwrap().0.clone();
+                                Transfer::asset_numeric(asset_id, quantity, destination).into()
+                            }
+                        }
+                    })
+                    .collect();
+                (authority, instructions)
+            }));
+            let transactions = workload
+                .drain(..)
+                .map(|(authority, instructions)| {
+                    let (_, key_pair) = accounts
+                        .iter()
+                        .find(|(id, _)| *id == authority)
+                        .expect("Generated above");
+                    let tx = TransactionBuilder::new(chain_id.clone(), authority)
+                        .with_instructions(instructions)
+                        .sign(key_pair.private_key());
+                    AcceptedTransaction::accept(tx, &chain_id, transaction_limits).expect("Valid")
+                })
+                .collect();
+
+            let mut leader_block = leader.block();
+            let block = BlockBuilder::new(transactions)
+                .chain(0, &mut leader_block)
+                .sign(&leader_private_key)
+                .unpack(|_| {})
+                .commit(&topology)
+                .unpack(|_| {})
+                .expect("Signed by the only peer");
+            let leader_events = leader_block.apply_without_execution(&block, vec![peer_id.clone()]);
+            leader_block.commit();
+
+            let mut follower_block = follower.block();
+            let follower_events = follower_block
+                .apply(&block, vec![peer_id.clone()])
+                .expect("Valid")
+                .into_inner();
+            follower_block.commit();
+
+            assert_eq!(
+                leader_events,
+                follower_events,
+                "events of block {} diverged, set IROHA_TEST_SEED={seed} to reproduce",
+                block.as_ref().header().height()
+            );
+            emitted.extend(leader_events);
+        }
+
+        // Make sure the triggers actually fired, otherwise the workload proves little
+        assert!(
+            emitted.iter().any(|event| matches!(
+                event,
+                EventBox::Data(DataEvent::Domain(DomainEvent::Account(
+                    AccountEvent::MetadataInserted(_)
+                )))
+            )),
+            "no trigger fired, set IROHA_TEST_SEED={seed} to reproduce"
+        );
+    }
 }
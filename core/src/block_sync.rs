@@ -2,7 +2,7 @@
 use std::{
     collections::BTreeSet,
     fmt::Debug,
-    num::{NonZeroU32, NonZeroU64},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     sync::Arc,
     time::Duration,
 };
@@ -14,7 +14,7 @@ use iroha_logger::prelude::*;
 use iroha_macro::*;
 use iroha_p2p::Post;
 use parity_scale_codec::{Decode, Encode};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 use crate::{
     kura::Kura,
@@ -27,6 +27,8 @@ use crate::{
 #[derive(Clone)]
 pub struct BlockSynchronizerHandle {
     message_sender: mpsc::Sender<message::Message>,
+    resync_sender: mpsc::Sender<NonZeroUsize>,
+    synced_receiver: watch::Receiver<bool>,
 }
 
 impl BlockSynchronizerHandle {
@@ -39,6 +41,33 @@ impl BlockSynchronizerHandle {
             "BlockSynchronizer must handle messages until there is at least one handle to it",
         )
     }
+
+    /// Force [`BlockSynchronizer`] to forget seen blocks and request
+    /// blocks starting from `height` from every online peer.
+    ///
+    /// NOTE: committed blocks are never rolled back, so of the blocks at or below
+    /// the current height only the latest one can be replaced (soft fork).
+    /// Requests from below the latest block are ignored, the caller is expected to reject them.
+    pub async fn resync_from(&self, height: NonZeroUsize) {
+        self.resync_sender.send(height).await.expect(
+            "BlockSynchronizer must handle messages until there is at least one handle to it",
+        )
+    }
+
+    /// Whether the peer has caught up with the blocks of the other peers.
+    ///
+    /// Once caught up, the peer is considered synced for the rest of its run,
+    /// since consensus keeps it up to date from then on.
+    pub fn is_synced(&self) -> bool {
+        *self.synced_receiver.borrow()
+    }
+
+    /// Wait until the peer has caught up with the blocks of the other peers, see [`Self::is_synced`].
+    pub async fn synced(&self) {
+        let mut synced_receiver = self.synced_receiver.clone();
+        // The sender is only dropped on shutdown, in which case there is nothing to wait for
+        let _shutdown = synced_receiver.wait_for(|synced| *synced).await.is_err();
+    }
 }
 
 /// Structure responsible for block synchronization between peers.
@@ -52,22 +81,38 @@ pub struct BlockSynchronizer {
     state: Arc<State>,
     seen_blocks: BTreeSet<(NonZeroU64, HashOf<SignedBlock>)>,
     latest_height: usize,
+    /// Whether blocks were requested on the previous gossip period
+    awaiting_blocks: bool,
+    /// Whether blocks above the local height were shared since the previous gossip period
+    received_blocks: bool,
+    synced_sender: watch::Sender<bool>,
 }
 
 impl BlockSynchronizer {
     /// Start [`Self`] actor.
     pub fn start(self) -> BlockSynchronizerHandle {
         let (message_sender, message_receiver) = mpsc::channel(1);
-        tokio::task::spawn(self.run(message_receiver));
-        BlockSynchronizerHandle { message_sender }
+        let (resync_sender, resync_receiver) = mpsc::channel(1);
+        let synced_receiver = self.synced_sender.subscribe();
+        tokio::task::spawn(self.run(message_receiver, resync_receiver));
+        BlockSynchronizerHandle {
+            message_sender,
+            resync_sender,
+            synced_receiver,
+        }
     }
 
     /// [`Self`] task.
-    async fn run(mut self, mut message_receiver: mpsc::Receiver<message::Message>) {
+    async fn run(
+        mut self,
+        mut message_receiver: mpsc::Receiver<message::Message>,
+        mut resync_receiver: mpsc::Receiver<NonZeroUsize>,
+    ) {
         let mut gossip_period = tokio::time::interval(self.gossip_period);
         loop {
             tokio::select! {
                 _ = gossip_period.tick() => self.request_block().await,
+                Some(height) = resync_receiver.recv() => self.resync_from(height).await,
                 msg = message_receiver.recv() => {
                     let Some(msg) = msg else {
                         info!("All handler to BlockSynchronizer are dropped. Shutting down...");
@@ -83,6 +128,7 @@ impl BlockSynchronizer {
     /// Sends request for latest blocks to a random peer
     async fn request_block(&mut self) {
         let now_height = self.state.view().height();
+        self.update_synced(now_height);
 
         // This guards against a softfork and adds general redundancy.
         if now_height == self.latest_height {
@@ -93,9 +139,72 @@ impl BlockSynchronizer {
         self.seen_blocks
             .retain(|(height, _hash)| height.get() >= now_height as u64);
 
+        self.awaiting_blocks = false;
         if let Some(random_peer) = self.network.online_peers(Self::random_peer) {
             self.request_latest_blocks_from_peer(random_peer.id().clone())
                 .await;
+            self.awaiting_blocks = true;
+        }
+    }
+
+    /// The peer is synced once it has committed the genesis and either it is the only peer
+    /// of the network, or a request for the blocks after its latest one was left
+    /// without newer blocks for a whole gossip period.
+    /// Peers don't reply to the requests of the peers which are up to date with them.
+    fn update_synced(&mut self, now_height: usize) {
+        let caught_up = now_height > 0
+            && (self.state.view().commit_topology().len() <= 1
+                || (self.awaiting_blocks && !self.received_blocks));
+        self.received_blocks = false;
+
+        if caught_up {
+            self.synced_sender.send_if_modified(|synced| {
+                if *synced {
+                    return false;
+                }
+                info!(
+                    height = now_height,
+                    "Caught up with the blocks of the other peers"
+                );
+                *synced = true;
+                true
+            });
+        }
+    }
+
+    /// Sends request for blocks starting from `height` to all online peers
+    async fn resync_from(&mut self, height: NonZeroUsize) {
+        if height.get() < self.state.view().height() {
+            error!(%height, "Can't resync from height below the latest block");
+            return;
+        }
+
+        let prev_hash = match NonZeroUsize::new(height.get() - 1) {
+            Some(prev_height) => match self.kura.get_block_hash(prev_height) {
+                None => {
+                    error!(%height, "Can't resync from height above the latest block");
+                    return;
+                }
+                hash => hash,
+            },
+            None => None,
+        };
+
+        info!(%height, "Resyncing blocks");
+        self.seen_blocks.clear();
+
+        let peers = self
+            .network
+            .online_peers(|peers| peers.iter().cloned().collect::<Vec<_>>());
+        for peer_id in peers {
+            message::Message::GetBlocksAfter(message::GetBlocksAfter::new(
+                None,
+                prev_hash.clone(),
+                BTreeSet::new(),
+                self.peer_id.clone(),
+            ))
+            .send_to(&self.network, peer_id)
+            .await;
         }
     }
 
@@ -146,6 +255,9 @@ impl BlockSynchronizer {
             state,
             seen_blocks: BTreeSet::new(),
             latest_height: 0,
+            awaiting_blocks: false,
+            received_blocks: false,
+            synced_sender: watch::channel(false).0,
         }
     }
 }
@@ -274,6 +386,11 @@ pub mod message {
                 Message::ShareBlocks(ShareBlocks { blocks, .. }) => {
                     use crate::sumeragi::message::BlockSyncUpdate;
 
+                    let local_height = block_sync.state.view().height() as u64;
+                    block_sync.received_blocks |= blocks
+                        .iter()
+                        .any(|block| block.header().height().get() > local_height);
+
                     for block in blocks.clone() {
                         block_sync
                             .seen_blocks
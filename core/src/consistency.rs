@@ -0,0 +1,560 @@
+//! Consistency checker is actor which compares digests of the world state between peers
+//! to detect state divergence.
+//!
+//! Every computed digest is also published to telemetry as an attestation signed with
+//! the peer key, see [`iroha_telemetry::attestation`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use iroha_config::parameters::actual::State as Config;
+use iroha_crypto::{Hash, KeyPair};
+use iroha_data_model::prelude::PeerId;
+use iroha_p2p::Broadcast;
+use iroha_primitives::time::TimeSource;
+use iroha_telemetry::{
+    attestation::{self, Attestation},
+    metrics::{Metrics, StateDigest},
+};
+use parity_scale_codec::{Decode, Encode};
+use storage::storage::StorageReadOnly;
+use tokio::sync::mpsc;
+
+use crate::{
+    state::{State, StateReadOnly, WorldReadOnly},
+    sumeragi::network_topology::Topology,
+    IrohaNetwork, NetworkMessage,
+};
+
+/// Number of the latest digests kept for comparison
+const DIGESTS_TO_KEEP: usize = 16;
+
+/// Compute the canonical digest of the world state.
+///
+/// Every collection is encoded in the order of its keys, so the digest
+/// only depends on the contents of the world. Triggers and the executor
+/// are not included as they hold compiled WASM.
+pub fn world_digest(world: &impl WorldReadOnly) -> Hash {
+    fn encode_storage<K: Encode, V: Encode>(
+        storage: &impl StorageReadOnly<K, V>,
+        buffer: &mut Vec<u8>,
+    ) {
+        (storage.len() as u64).encode_to(buffer);
+        for (key, value) in storage.iter() {
+            key.encode_to(buffer);
+            value.encode_to(buffer);
+        }
+    }
+
+    let mut buffer = Vec::new();
+    world.parameters().encode_to(&mut buffer);
+    world.trusted_peers_ids().encode_to(&mut buffer);
+    encode_storage(world.domains(), &mut buffer);
+    encode_storage(world.accounts(), &mut buffer);
+    encode_storage(world.asset_definitions(), &mut buffer);
+    encode_storage(world.asset_total_quantities(), &mut buffer);
+    encode_storage(world.assets(), &mut buffer);
+    encode_storage(world.roles(), &mut buffer);
+    encode_storage(world.account_permissions(), &mut buffer);
+    encode_storage(world.account_roles(), &mut buffer);
+    encode_storage(world.escrows(), &mut buffer);
+    encode_storage(world.peer_certificates(), &mut buffer);
+    encode_storage(world.recovery_policies(), &mut buffer);
+    encode_storage(world.apps(), &mut buffer);
+    encode_storage(world.proxies(), &mut buffer);
+    world.executor_data_model().encode_to(&mut buffer);
+
+    Hash::new(buffer)
+}
+
+/// [`ConsistencyChecker`] actor handle.
+#[derive(Clone)]
+pub struct ConsistencyCheckerHandle {
+    digest_period: NonZeroUsize,
+    state: Arc<State>,
+    digest_sender: mpsc::Sender<(u64, Hash)>,
+    gossip_sender: mpsc::Sender<StateDigestGossip>,
+}
+
+impl ConsistencyCheckerHandle {
+    /// Send [`StateDigestGossip`] to actor
+    pub async fn gossip(&self, gossip: StateDigestGossip) {
+        self.gossip_sender.send(gossip).await.expect(
+            "Consistency checker must handle messages until there is at least one handle to it",
+        )
+    }
+
+    /// Notify the actor that the block at `height` has been committed.
+    ///
+    /// Must be called right after the commit, before the next block is applied.
+    /// The digest itself is computed in a separate thread, this call only waits
+    /// until the state at `height` is captured.
+    pub fn block_committed(&self, height: NonZeroU64) {
+        if height.get() % self.digest_period.get() as u64 != 0 {
+            return;
+        }
+
+        let state = Arc::clone(&self.state);
+        let digest_sender = self.digest_sender.clone();
+        let (captured_sender, captured_receiver) = std::sync::mpsc::sync_channel(0);
+        let spawned = std::thread::Builder::new()
+            .name("state digest".to_owned())
+            .spawn(move || {
+                let state_view = state.view();
+                let _ = captured_sender.send(());
+
+                debug_assert_eq!(state_view.height() as u64, height.get());
+                let digest = world_digest(state_view.world());
+                let _ = digest_sender.blocking_send((height.get(), digest));
+            });
+
+        match spawned {
+            Ok(_) => {
+                let _ = captured_receiver.recv();
+            }
+            Err(error) => {
+                iroha_logger::error!(?error, "Failed to spawn state digest thread");
+            }
+        }
+    }
+}
+
+/// Actor which gossips digests of the world state and compares them with digests of other peers
+pub struct ConsistencyChecker {
+    peer_id: PeerId,
+    /// Key pair to sign the attestations of the state with
+    key_pair: KeyPair,
+    /// When the actor was created, to report the uptime of the peer
+    started_at: Instant,
+    /// Clock the attestations are timestamped by
+    time_source: TimeSource,
+    /// Number of blocks between computations of the digest
+    digest_period: NonZeroUsize,
+    /// The time between gossiping the latest digests
+    gossip_period: Duration,
+    network: IrohaNetwork,
+    state: Arc<State>,
+    metrics: Metrics,
+    digests: Digests,
+}
+
+impl ConsistencyChecker {
+    /// Construct [`Self`] from configuration
+    pub fn from_config(
+        Config {
+            digest_period,
+            digest_gossip_period,
+            ..
+        }: &Config,
+        peer_id: PeerId,
+        key_pair: KeyPair,
+        network: IrohaNetwork,
+        state: Arc<State>,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            peer_id,
+            key_pair,
+            started_at: Instant::now(),
+            time_source: TimeSource::new_system(),
+            digest_period: *digest_period,
+            gossip_period: *digest_gossip_period,
+            network,
+            state,
+            metrics,
+            digests: Digests::new(*digest_period),
+        }
+    }
+
+    /// Timestamp the attestations with `time_source` instead of the system clock
+    #[must_use]
+    pub fn with_time_source(mut self, time_source: TimeSource) -> Self {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Start [`Self`] actor.
+    pub fn start(self) -> ConsistencyCheckerHandle {
+        let (digest_sender, digest_receiver) = mpsc::channel(1);
+        let (gossip_sender, gossip_receiver) = mpsc::channel(1);
+        let handle = ConsistencyCheckerHandle {
+            digest_period: self.digest_period,
+            state: Arc::clone(&self.state),
+            digest_sender,
+            gossip_sender,
+        };
+        tokio::task::spawn(self.run(digest_receiver, gossip_receiver));
+        handle
+    }
+
+    async fn run(
+        mut self,
+        mut digest_receiver: mpsc::Receiver<(u64, Hash)>,
+        mut gossip_receiver: mpsc::Receiver<StateDigestGossip>,
+    ) {
+        let mut gossip_period = tokio::time::interval(self.gossip_period);
+        loop {
+            tokio::select! {
+                _ = gossip_period.tick() => self.gossip_digests(),
+                Some((height, digest)) = digest_receiver.recv() => {
+                    self.handle_digest(height, digest);
+                }
+                gossip = gossip_receiver.recv() => {
+                    let Some(gossip) = gossip else {
+                        iroha_logger::info!("All handler to ConsistencyChecker are dropped. Shutting down...");
+                        break;
+                    };
+                    self.handle_gossip(gossip);
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    fn gossip_digests(&self) {
+        let digests = self.digests.latest();
+        if digests.is_empty() {
+            return;
+        }
+
+        self.network.broadcast(Broadcast {
+            data: NetworkMessage::StateDigest(Box::new(StateDigestGossip {
+                peer_id: self.peer_id.clone(),
+                digests,
+            })),
+        });
+    }
+
+    fn handle_digest(&mut self, height: u64, digest: Hash) {
+        iroha_logger::debug!(height, %digest, "Computed world state digest");
+
+        self.metrics.state_digest_height.set(height);
+        *self
+            .metrics
+            .state_digest
+            .write()
+            .expect("INTERNAL BUG: state digest lock is poisoned") = Some(StateDigest {
+            height,
+            hash: digest.into(),
+        });
+
+        self.attest(height, digest);
+
+        self.digests.insert(height, digest);
+        self.check(height);
+    }
+
+    /// Publish a signed attestation of the state at `height` to telemetry.
+    fn attest(&self, height: u64, digest: Hash) {
+        let attestation = Attestation {
+            peer: self.key_pair.public_key().clone(),
+            height,
+            state_digest: digest,
+            version: attestation::version(),
+            uptime_ms: u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            timestamp_ms: u64::try_from(self.time_source.get_unix_time().as_millis())
+                .unwrap_or(u64::MAX),
+        }
+        .sign(&self.key_pair);
+        match serde_json::to_string(&attestation) {
+            Ok(attestation) => iroha_logger::telemetry!(
+                msg = iroha_telemetry::msg::PEER_ATTESTATION,
+                height,
+                attestation = %attestation,
+            ),
+            Err(error) => iroha_logger::error!(%error, "Failed to serialize state attestation"),
+        }
+    }
+
+    fn handle_gossip(&mut self, StateDigestGossip { peer_id, digests }: StateDigestGossip) {
+        // Digests of this peer are compared with the quorum, never taken from the gossip
+        if peer_id == self.peer_id {
+            return;
+        }
+
+        for (height, digest) in digests {
+            if self.digests.insert_peer(peer_id.clone(), height, digest) {
+                self.check(height);
+            }
+        }
+    }
+
+    /// Compare digest of this peer at `height` with the digest of the quorum.
+    fn check(&mut self, height: u64) {
+        let topology = Topology::new(self.state.view().commit_topology().to_vec());
+        let quorum = topology.min_votes_for_commit();
+
+        let Some(Comparison {
+            digest,
+            quorum_digest,
+            diverged_from,
+        }) = self.digests.compare(height, quorum)
+        else {
+            return;
+        };
+        if quorum_digest == digest {
+            iroha_logger::debug!(height, %digest, "World state digest matches the quorum");
+            return;
+        }
+
+        let diverged_from = diverged_from
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        self.metrics.state_divergences.inc();
+        iroha_logger::error!(
+            height,
+            %digest,
+            %quorum_digest,
+            ?diverged_from,
+            "World state of this peer diverged from the quorum"
+        );
+        iroha_logger::telemetry!(
+            msg = iroha_telemetry::msg::STATE_DIVERGENCE,
+            height,
+            digest = %digest,
+            quorum_digest = %quorum_digest,
+        );
+    }
+}
+
+/// Digests of this peer and of the other peers which are kept for comparison
+#[derive(Debug)]
+struct Digests {
+    /// Number of blocks between computations of the digest
+    digest_period: NonZeroUsize,
+    /// Digests computed by this peer
+    own: BTreeMap<u64, Hash>,
+    /// Digests reported by other peers
+    peers: BTreeMap<u64, BTreeMap<PeerId, Hash>>,
+    /// Heights at which the digest was already compared with the quorum's
+    checked: BTreeSet<u64>,
+}
+
+/// Digest of this peer compared with the digest of the quorum at some height
+#[derive(Debug, PartialEq, Eq)]
+struct Comparison {
+    digest: Hash,
+    quorum_digest: Hash,
+    /// Peers which reported [`Self::quorum_digest`]
+    diverged_from: Vec<PeerId>,
+}
+
+impl Digests {
+    fn new(digest_period: NonZeroUsize) -> Self {
+        Self {
+            digest_period,
+            own: BTreeMap::new(),
+            peers: BTreeMap::new(),
+            checked: BTreeSet::new(),
+        }
+    }
+
+    /// The latest digests computed by this peer
+    fn latest(&self) -> Vec<(u64, Hash)> {
+        self.own
+            .iter()
+            .map(|(height, digest)| (*height, *digest))
+            .collect()
+    }
+
+    /// Record the digest computed by this peer, forgetting the oldest ones
+    fn insert(&mut self, height: u64, digest: Hash) {
+        self.own.insert(height, digest);
+        while self.own.len() > DIGESTS_TO_KEEP {
+            self.own.pop_first();
+        }
+        if let Some(oldest) = self.own.keys().next().copied() {
+            self.peers.retain(|height, _| *height >= oldest);
+            self.checked.retain(|height| *height >= oldest);
+        }
+    }
+
+    /// Record the digest reported by `peer_id`, returns whether it was kept for comparison
+    fn insert_peer(&mut self, peer_id: PeerId, height: u64, digest: Hash) -> bool {
+        // Digests older than any of ours can't be compared anymore
+        let oldest = self.own.keys().next().copied();
+        if oldest.is_some_and(|oldest| height < oldest)
+            || height % self.digest_period.get() as u64 != 0
+        {
+            return false;
+        }
+
+        self.peers
+            .entry(height)
+            .or_default()
+            .insert(peer_id, digest);
+        // Bound the memory if this peer lags behind
+        while self.peers.len() > DIGESTS_TO_KEEP {
+            self.peers.pop_first();
+        }
+        self.peers.contains_key(&height)
+    }
+
+    /// Compare the digest of this peer at `height` with the digest of the quorum of `quorum` votes.
+    ///
+    /// Every height is compared only once, [`None`] if it was already compared
+    /// or if there is no quorum yet.
+    fn compare(&mut self, height: u64, quorum: usize) -> Option<Comparison> {
+        if self.checked.contains(&height) {
+            return None;
+        }
+        let digest = *self.own.get(&height)?;
+        let peer_digests = self.peers.get(&height)?;
+
+        let mut votes = BTreeMap::<Hash, usize>::new();
+        *votes.entry(digest).or_default() += 1;
+        for peer_digest in peer_digests.values() {
+            *votes.entry(*peer_digest).or_default() += 1;
+        }
+        let (quorum_digest, _) = votes.into_iter().find(|(_, count)| *count >= quorum)?;
+
+        self.checked.insert(height);
+        let diverged_from = if quorum_digest == digest {
+            Vec::new()
+        } else {
+            peer_digests
+                .iter()
+                .filter(|(_, peer_digest)| **peer_digest == quorum_digest)
+                .map(|(peer_id, _)| peer_id.clone())
+                .collect()
+        };
+        Some(Comparison {
+            digest,
+            quorum_digest,
+            diverged_from,
+        })
+    }
+}
+
+/// Message for gossiping the latest world state digests of a peer.
+#[derive(Decode, Encode, Debug, Clone)]
+pub struct StateDigestGossip {
+    /// Peer which computed the digests.
+    pub peer_id: PeerId,
+    /// Digests by block height.
+    pub digests: Vec<(u64, Hash)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_crypto::KeyPair;
+    use iroha_data_model::prelude::*;
+    use nonzero_ext::nonzero;
+    use test_samples::gen_account_in;
+
+    use super::*;
+    use crate::{
+        kura::Kura, query::store::LiveQueryStore, smartcontracts::isi::Registrable as _,
+        state::World,
+    };
+
+    fn peer(port: u16) -> PeerId {
+        PeerId::new(
+            format!("127.0.0.1:{port}").parse().unwrap(),
+            KeyPair::random().into_parts().0,
+        )
+    }
+
+    /// Digest of the world with a single asset of `quantity` roses
+    fn digest_with_roses(account_id: &AccountId, quantity: u32) -> Hash {
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let rose_id: AssetDefinitionId = "rose#wonderland".parse().unwrap();
+        let world = World::with_assets(
+            [Domain::new(domain_id).build(account_id)],
+            [Account::new(account_id.clone()).build(account_id)],
+            [AssetDefinition::numeric(rose_id.clone()).build(account_id)],
+            [Asset::new(
+                AssetId::new(rose_id, account_id.clone()),
+                Numeric::from(quantity),
+            )],
+        );
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(world, Kura::blank_kura_for_testing(), query_handle);
+
+        let state_view = state.view();
+        world_digest(state_view.world())
+    }
+
+    #[tokio::test]
+    async fn equal_worlds_have_equal_digests() {
+        let (alice_id, _) = gen_account_in("wonderland");
+
+        assert_eq!(
+            digest_with_roses(&alice_id, 13),
+            digest_with_roses(&alice_id, 13)
+        );
+    }
+
+    #[tokio::test]
+    async fn changed_asset_is_reported_as_divergence() {
+        let (alice_id, _) = gen_account_in("wonderland");
+        let digest = digest_with_roses(&alice_id, 13);
+        let changed = digest_with_roses(&alice_id, 14);
+        assert_ne!(digest, changed);
+
+        let mut peers = vec![peer(1338), peer(1339), peer(1340)];
+        peers.sort();
+        // Topology of this peer and 3 others needs 3 votes
+        let quorum = 3;
+        let mut digests = Digests::new(nonzero!(1_usize));
+        digests.insert(1, changed);
+        digests.insert(2, digest);
+        for height in [1, 2] {
+            for peer_id in &peers {
+                assert!(digests.insert_peer(peer_id.clone(), height, digest));
+            }
+        }
+
+        assert_eq!(
+            digests.compare(1, quorum),
+            Some(Comparison {
+                digest: changed,
+                quorum_digest: digest,
+                diverged_from: peers.clone(),
+            })
+        );
+        assert_eq!(
+            digests.compare(2, quorum),
+            Some(Comparison {
+                digest,
+                quorum_digest: digest,
+                diverged_from: Vec::new(),
+            })
+        );
+        // Every height is reported only once
+        assert_eq!(digests.compare(1, quorum), None);
+    }
+
+    #[test]
+    fn digest_claimed_for_another_peer_is_dropped() {
+        let (sender, claimed) = (peer(1338), peer(1339));
+        let gossip = |peer_id: &PeerId| {
+            NetworkMessage::StateDigest(Box::new(StateDigestGossip {
+                peer_id: peer_id.clone(),
+                digests: vec![(1, Hash::prehashed([1; 32]))],
+            }))
+        };
+
+        assert!(gossip(&claimed).claims_another_sender(&sender));
+        assert!(!gossip(&sender).claims_another_sender(&sender));
+    }
+
+    #[test]
+    fn stale_digests_are_not_compared() {
+        let peer_id = peer(1338);
+        let digest = Hash::prehashed([1; 32]);
+        let mut digests = Digests::new(nonzero!(2_usize));
+        for height in (1..=DIGESTS_TO_KEEP as u64 + 1).map(|i| i * 2) {
+            digests.insert(height, digest);
+        }
+
+        assert!(!digests.insert_peer(peer_id.clone(), 2, digest));
+        assert!(!digests.insert_peer(peer_id.clone(), 5, digest));
+        assert!(digests.insert_peer(peer_id, 4, digest));
+    }
+}
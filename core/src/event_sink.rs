@@ -0,0 +1,264 @@
+//! Forwarding of events to external systems.
+//!
+//! Every configured sink gets its own [`EventForwarder`], which subscribes to the event bus,
+//! filters events by kind and delivers them with retries. Events are delivered in the order
+//! they are emitted; an event which can't be delivered after all retries is dropped so that
+//! a broker outage doesn't stall the forwarder forever.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use iroha_config::{
+    event_sink::{EventFormat, EventKind, EventSinkTarget},
+    parameters::actual::EventSink as Config,
+};
+use iroha_data_model::events::EventBox;
+use parity_scale_codec::Encode;
+use tokio::sync::{broadcast, Notify};
+
+use crate::EventsSender;
+
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+/// Destination events are delivered to
+#[async_trait]
+pub trait EventSink: Send {
+    /// Name of the sink used in logs
+    fn name(&self) -> &str;
+
+    /// Deliver a single event.
+    ///
+    /// # Errors
+    /// If the event couldn't be delivered, the delivery is retried then
+    async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()>;
+}
+
+/// Encode `event` in the given `format`
+///
+/// # Errors
+/// If the event can't be serialized to JSON
+pub fn encode(event: &EventBox, format: EventFormat) -> eyre::Result<Vec<u8>> {
+    match format {
+        EventFormat::Json => Ok(serde_json::to_vec(event)?),
+        EventFormat::Scale => Ok(event.encode()),
+    }
+}
+
+/// Check whether `event` is of the given `kind`
+pub fn is_of_kind(event: &EventBox, kind: EventKind) -> bool {
+    matches!(
+        (event, kind),
+        (EventBox::Pipeline(_), EventKind::Pipeline)
+            | (EventBox::Data(_), EventKind::Data)
+            | (EventBox::Time(_), EventKind::Time)
+            | (EventBox::ExecuteTrigger(_), EventKind::ExecuteTrigger)
+            | (EventBox::TriggerCompleted(_), EventKind::TriggerCompleted)
+    )
+}
+
+/// Connect to the broker of the configured sink
+///
+/// # Errors
+/// - If the connection can't be established
+/// - If `irohad` is built without the feature of the broker
+#[cfg_attr(not(feature = "nats"), allow(clippy::unused_async))]
+pub async fn connect(config: &Config) -> eyre::Result<Box<dyn EventSink>> {
+    match &config.target {
+        #[cfg(feature = "kafka")]
+        EventSinkTarget::Kafka { brokers, topic } => Ok(Box::new(kafka::KafkaSink::new(
+            config.name.clone(),
+            brokers,
+            topic.clone(),
+            config.format,
+        )?)),
+        #[cfg(feature = "nats")]
+        EventSinkTarget::Nats { url, subject } => Ok(Box::new(
+            nats::NatsSink::connect(
+                config.name.clone(),
+                url.as_str(),
+                subject.clone(),
+                config.format,
+            )
+            .await?,
+        )),
+        #[allow(unreachable_patterns)]
+        target => Err(eyre::eyre!(
+            "Event sink `{}` requires irohad built with the `{}` feature",
+            config.name,
+            match target {
+                EventSinkTarget::Kafka { .. } => "kafka",
+                EventSinkTarget::Nats { .. } => "nats",
+            }
+        )),
+    }
+}
+
+/// Delivers the events of the bus to a single [`EventSink`]
+pub struct EventForwarder {
+    sink: Box<dyn EventSink>,
+    events: Vec<EventKind>,
+    max_retries: u32,
+    retry_delay: Duration,
+    receiver: broadcast::Receiver<EventBox>,
+    notify_shutdown: Arc<Notify>,
+}
+
+impl EventForwarder {
+    /// Construct [`EventForwarder`] delivering events to `sink` according to `config`.
+    ///
+    /// Only the events emitted after the call are forwarded.
+    pub fn new(
+        sink: Box<dyn EventSink>,
+        config: &Config,
+        events_sender: &EventsSender,
+        notify_shutdown: Arc<Notify>,
+    ) -> Self {
+        Self {
+            sink,
+            events: config.events.clone(),
+            max_retries: config.max_retries,
+            retry_delay: config.retry_delay,
+            receiver: events_sender.subscribe(),
+            notify_shutdown,
+        }
+    }
+
+    /// Start [`EventForwarder`]. Requires a [`tokio::runtime::Runtime`] being run
+    /// as it will create new [`tokio::task`] and detach it.
+    pub fn start(self) -> tokio::task::JoinHandle<()> {
+        tokio::task::spawn(self.run())
+    }
+
+    fn is_forwarded(&self, event: &EventBox) -> bool {
+        self.events.is_empty() || self.events.iter().any(|kind| is_of_kind(event, *kind))
+    }
+
+    async fn run(mut self) {
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(event) if self.is_forwarded(&event) => self.deliver(&event).await,
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        iroha_logger::warn!(sink = self.sink.name(), skipped, "Event sink is lagging behind, events are dropped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                () = self.notify_shutdown.notified() => {
+                    iroha_logger::info!(sink = self.sink.name(), "EventForwarder is being shut down.");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn deliver(&mut self, event: &EventBox) {
+        let mut delay = self.retry_delay;
+        for attempt in 0..=self.max_retries {
+            match self.sink.deliver(event).await {
+                Ok(()) => return,
+                Err(error) if attempt < self.max_retries => {
+                    iroha_logger::warn!(sink = self.sink.name(), attempt, %error, "Failed to deliver event, retrying");
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                }
+                Err(error) => {
+                    iroha_logger::error!(sink = self.sink.name(), %error, "Failed to deliver event, dropping it");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use iroha_data_model::events::{data::prelude::*, time::prelude::*};
+
+    use super::*;
+
+    /// Fails the first `failures` deliveries, then reports the delivered events
+    struct Flaky {
+        failures: u32,
+        attempts: Arc<AtomicU32>,
+        delivered: tokio::sync::mpsc::UnboundedSender<EventBox>,
+    }
+
+    #[async_trait]
+    impl EventSink for Flaky {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                eyre::bail!("broker is unavailable");
+            }
+            self.delivered.send(event.clone())?;
+            Ok(())
+        }
+    }
+
+    fn config(events: Vec<EventKind>) -> Config {
+        Config {
+            name: "flaky".to_owned(),
+            target: EventSinkTarget::Nats {
+                url: "nats://localhost:4222".parse().unwrap(),
+                subject: "iroha".to_owned(),
+            },
+            events,
+            format: EventFormat::Json,
+            max_retries: 2,
+            retry_delay: Duration::from_millis(1),
+        }
+    }
+
+    fn time_event() -> EventBox {
+        EventBox::Time(TimeEvent {
+            prev_interval: None,
+            interval: TimeInterval::new(Duration::from_secs(1), Duration::from_secs(1)),
+        })
+    }
+
+    #[tokio::test]
+    async fn events_are_filtered_and_retried() {
+        let (events_sender, _) = broadcast::channel(16);
+        let (delivered, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let attempts = Arc::new(AtomicU32::new(0));
+        let sink = Flaky {
+            failures: 2,
+            attempts: Arc::clone(&attempts),
+            delivered,
+        };
+        let _forwarder = EventForwarder::new(
+            Box::new(sink),
+            &config(vec![EventKind::Time]),
+            &events_sender,
+            Arc::new(Notify::new()),
+        )
+        .start();
+
+        events_sender
+            .send(EventBox::Data(DataEvent::Domain(DomainEvent::Deleted(
+                "wonderland".parse().unwrap(),
+            ))))
+            .unwrap();
+        events_sender.send(time_event()).unwrap();
+
+        assert_eq!(received.recv().await, Some(time_event()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn events_are_encoded_in_configured_format() {
+        let event = time_event();
+        assert_eq!(encode(&event, EventFormat::Scale).unwrap(), event.encode());
+        let json: EventBox =
+            serde_json::from_slice(&encode(&event, EventFormat::Json).unwrap()).unwrap();
+        assert_eq!(json, event);
+    }
+}
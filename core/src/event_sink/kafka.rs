@@ -0,0 +1,67 @@
+//! [`EventSink`] producing events to a Kafka topic
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use iroha_config::event_sink::EventFormat;
+use iroha_data_model::events::EventBox;
+use rdkafka::{
+    config::ClientConfig,
+    producer::{FutureProducer, FutureRecord},
+    util::Timeout,
+};
+
+use super::{encode, EventSink};
+
+/// How long a message may wait in the producer queue before it's reported as undelivered
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Produces every event as a single message to a Kafka topic
+pub struct KafkaSink {
+    name: String,
+    producer: FutureProducer,
+    topic: String,
+    format: EventFormat,
+}
+
+impl KafkaSink {
+    /// Construct [`KafkaSink`] producing to `topic` of the cluster with the given bootstrap `brokers`
+    ///
+    /// # Errors
+    /// If the producer can't be created from the configuration
+    pub fn new(
+        name: String,
+        brokers: &str,
+        topic: String,
+        format: EventFormat,
+    ) -> eyre::Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("client.id", &name)
+            .create()?;
+
+        Ok(Self {
+            name,
+            producer,
+            topic,
+            format,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()> {
+        let payload = encode(event, self.format)?;
+        let record = FutureRecord::<(), _>::to(&self.topic).payload(&payload);
+        self.producer
+            .send(record, Timeout::After(QUEUE_TIMEOUT))
+            .await
+            .map_err(|(error, _)| error)?;
+        Ok(())
+    }
+}
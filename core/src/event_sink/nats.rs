@@ -0,0 +1,54 @@
+//! [`EventSink`] publishing events to a NATS subject
+
+use async_trait::async_trait;
+use iroha_config::event_sink::EventFormat;
+use iroha_data_model::events::EventBox;
+
+use super::{encode, EventSink};
+
+/// Publishes every event as a single message to a NATS subject
+pub struct NatsSink {
+    name: String,
+    client: async_nats::Client,
+    subject: String,
+    format: EventFormat,
+}
+
+impl NatsSink {
+    /// Connect to the NATS server at `url` to publish to `subject`
+    ///
+    /// # Errors
+    /// If the server can't be reached
+    pub async fn connect(
+        name: String,
+        url: &str,
+        subject: String,
+        format: EventFormat,
+    ) -> eyre::Result<Self> {
+        let client = async_nats::connect(url).await?;
+
+        Ok(Self {
+            name,
+            client,
+            subject,
+            format,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()> {
+        let payload = encode(event, self.format)?;
+        self.client
+            .publish(self.subject.clone(), payload.into())
+            .await?;
+        // Publishing only buffers the message, flushing reports whether it reached the server
+        self.client.flush().await?;
+        Ok(())
+    }
+}
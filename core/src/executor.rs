@@ -140,7 +140,7 @@ impl Executor {
             Self::UserProvided(loaded_executor) => {
                 let runtime =
                     wasm::RuntimeBuilder::<wasm::state::executor::ValidateTransaction>::new()
-                        .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap, see [`wasmtime::Engine`] docs
+                        .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap for both WASM engines
                         .with_config(state_transaction.world.parameters().executor)
                         .build()?;
 
@@ -176,7 +176,7 @@ impl Executor {
             Self::UserProvided(loaded_executor) => {
                 let runtime =
                     wasm::RuntimeBuilder::<wasm::state::executor::ValidateInstruction>::new()
-                        .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap, see [`wasmtime::Engine`] docs
+                        .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap for both WASM engines
                         .with_config(state_transaction.world.parameters().executor)
                         .build()?;
 
@@ -219,7 +219,7 @@ impl Executor {
             Self::UserProvided(loaded_executor) => {
                 let runtime =
                     wasm::RuntimeBuilder::<wasm::state::executor::ValidateQuery<S>>::new()
-                        .with_engine(state_ro.engine().clone()) // Cloning engine is cheap, see [`wasmtime::Engine`] docs
+                        .with_engine(state_ro.engine().clone()) // Cloning engine is cheap for both WASM engines
                         .with_config(state_ro.world().parameters().executor)
                         .build()?;
 
@@ -233,6 +233,18 @@ impl Executor {
         }
     }
 
+    /// Load user-provided `raw_executor` without running its migration,
+    /// e.g. to restore an exported state which was migrated already.
+    ///
+    /// # Errors
+    /// If `raw_executor` can't be loaded
+    pub(crate) fn load(
+        engine: &wasm::engine::Engine,
+        raw_executor: data_model_executor::Executor,
+    ) -> Result<Self, wasm::error::Error> {
+        LoadedExecutor::load(engine, raw_executor).map(Self::UserProvided)
+    }
+
     /// Migrate executor to a new user-provided one.
     ///
     /// Execute `migrate()` entrypoint of the `raw_executor` and set `self` to
@@ -254,7 +266,7 @@ impl Executor {
         let loaded_executor = LoadedExecutor::load(state_transaction.engine, raw_executor)?;
 
         let runtime = wasm::RuntimeBuilder::<wasm::state::executor::Migrate>::new()
-            .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap, see [`wasmtime::Engine`] docs
+            .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap for both WASM engines
             .with_config(state_transaction.world().parameters().executor)
             .build()?;
 
@@ -269,21 +281,26 @@ impl Executor {
     }
 }
 
-/// [`Executor`] with [`Module`](wasmtime::Module) for execution.
+/// [`Executor`] with [`Module`](wasm::engine::Module) for execution.
 ///
-/// Creating a [`wasmtime::Module`] is expensive, so we do it once on [`migrate()`](Executor::migrate)
+/// Creating a [`Module`](wasm::engine::Module) is expensive, so we do it once on [`migrate()`](Executor::migrate)
 /// step and reuse it later on validating steps.
 #[derive(DebugCustom, Clone, Serialize)]
 #[debug(fmt = "LoadedExecutor {{ module: <Module is truncated> }}")]
 pub struct LoadedExecutor {
     #[serde(skip)]
-    module: wasmtime::Module,
+    module: wasm::engine::Module,
     raw_executor: data_model_executor::Executor,
 }
 
 impl LoadedExecutor {
+    /// Executor this one was loaded from
+    pub fn raw_executor(&self) -> &data_model_executor::Executor {
+        &self.raw_executor
+    }
+
     fn load(
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         raw_executor: data_model_executor::Executor,
     ) -> Result<Self, wasm::error::Error> {
         Ok(Self {
@@ -1,29 +1,50 @@
 //! Gossiper is actor which is responsible for transaction gossiping
+//!
+//! Peers periodically announce hashes of the transactions in their queues.
+//! Bodies are only requested by the peers which don't have the transactions yet,
+//! so that large transactions aren't sent to peers which already know them.
+//! Cancellations of queued transactions are broadcast once by every peer which applies them.
+//! Read replicas neither gossip transactions nor process the gossip of other peers.
+//!
+//! The format of [`TransactionGossip`] isn't compatible with the peers which broadcast bodies
+//! of all queued transactions, see the type for details.
 
-use std::{num::NonZeroU32, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use iroha_config::parameters::actual::TransactionGossiper as Config;
-use iroha_data_model::{transaction::SignedTransaction, ChainId};
-use iroha_p2p::Broadcast;
+use iroha_config::{parameters::actual::TransactionGossiper as Config, peer::Role};
+use iroha_crypto::HashOf;
+use iroha_data_model::{
+    peer::PeerId,
+    transaction::{SignedCancelTransaction, SignedTransaction},
+    ChainId,
+};
+use iroha_p2p::{Broadcast, Misbehavior, Post, ReportMisbehavior};
 use parity_scale_codec::{Decode, Encode};
 use tokio::sync::mpsc;
 
 use crate::{
-    queue::Queue, state::State, tx::AcceptedTransaction, IrohaNetwork, NetworkMessage,
-    StateReadOnly, WorldReadOnly,
+    queue::{CancelError, Queue},
+    state::{State, StateView},
+    tx::{AcceptTransactionFail, AcceptedTransaction},
+    IrohaNetwork, NetworkMessage, StateReadOnly, WorldReadOnly,
 };
 
 /// [`Gossiper`] actor handle.
 #[derive(Clone)]
 pub struct TransactionGossiperHandle {
-    message_sender: mpsc::Sender<TransactionGossip>,
+    message_sender: mpsc::Sender<(PeerId, TransactionGossip)>,
 }
 
 impl TransactionGossiperHandle {
-    /// Send [`TransactionGossip`] to actor
-    pub async fn gossip(&self, gossip: TransactionGossip) {
+    /// Send [`TransactionGossip`] received from `peer_id` to actor
+    pub async fn gossip(&self, peer_id: PeerId, gossip: TransactionGossip) {
         self.message_sender
-            .send(gossip)
+            .send((peer_id, gossip))
             .await
             .expect("Gossiper must handle messages until there is at least one handle to it")
     }
@@ -42,6 +63,76 @@ pub struct TransactionGossiper {
     network: IrohaNetwork,
     queue: Arc<Queue>,
     state: Arc<State>,
+    /// Role of this peer, read replicas don't take part in gossiping
+    role: Role,
+    requests: Requests,
+}
+
+/// Transactions requested from other peers with the time of the request.
+///
+/// They aren't requested again for a gossip period, so that a transaction
+/// announced by several peers is only fetched once.
+struct Requests {
+    period: Duration,
+    requested: HashMap<HashOf<SignedTransaction>, Instant>,
+}
+
+impl Requests {
+    fn new(period: Duration) -> Self {
+        Self {
+            period,
+            requested: HashMap::new(),
+        }
+    }
+
+    /// Forget the requests made a period or more before `now`,
+    /// so that transactions which didn't arrive are requested again
+    fn expire(&mut self, now: Instant) {
+        self.requested
+            .retain(|_, requested_at| now.duration_since(*requested_at) < self.period);
+    }
+
+    /// Announced transactions which are neither `known` nor requested already.
+    /// They are considered requested at `now`.
+    fn select(
+        &mut self,
+        hashes: impl IntoIterator<Item = HashOf<SignedTransaction>>,
+        known: impl Fn(&HashOf<SignedTransaction>) -> bool,
+        now: Instant,
+    ) -> Vec<HashOf<SignedTransaction>> {
+        let mut unknown = Vec::new();
+        for hash in hashes {
+            if self.requested.contains_key(&hash) || known(&hash) {
+                continue;
+            }
+            self.requested.insert(hash, now);
+            unknown.push(hash);
+        }
+        unknown
+    }
+
+    /// Transaction has arrived
+    fn complete(&mut self, hash: &HashOf<SignedTransaction>) {
+        self.requested.remove(hash);
+    }
+}
+
+/// Transactions which are known to this peer, i.e. queued, cancelled or committed
+fn is_known(queue: &Queue, state_view: &StateView, hash: &HashOf<SignedTransaction>) -> bool {
+    queue.contains(hash) || queue.is_cancelled(hash) || state_view.has_transaction(*hash)
+}
+
+/// Requested transactions which are still in the queue, at most `limit` of them
+fn queued_transactions(
+    queue: &Queue,
+    hashes: &[HashOf<SignedTransaction>],
+    limit: usize,
+) -> Vec<AcceptedTransaction> {
+    hashes
+        .iter()
+        .take(limit)
+        .filter_map(|hash| queue.get_transaction(hash))
+        .collect()
 }
 
 impl TransactionGossiper {
@@ -62,6 +153,7 @@ impl TransactionGossiper {
         network: IrohaNetwork,
         queue: Arc<Queue>,
         state: Arc<State>,
+        role: Role,
     ) -> Self {
         Self {
             chain_id,
@@ -70,46 +162,165 @@ impl TransactionGossiper {
             network,
             queue,
             state,
+            role,
+            requests: Requests::new(gossip_period),
         }
     }
 
-    async fn run(self, mut message_receiver: mpsc::Receiver<TransactionGossip>) {
+    async fn run(mut self, mut message_receiver: mpsc::Receiver<(PeerId, TransactionGossip)>) {
+        if self.role == Role::ReadReplica {
+            iroha_logger::info!("Read replica doesn't gossip transactions");
+            while message_receiver.recv().await.is_some() {}
+            return;
+        }
+
         let mut gossip_period = tokio::time::interval(self.gossip_period);
         loop {
             tokio::select! {
-                _ = gossip_period.tick() => self.gossip_transactions(),
+                _ = gossip_period.tick() => {
+                    self.gossip_cancellations();
+                    self.gossip_transactions();
+                }
                 transaction_gossip = message_receiver.recv() => {
-                    let Some(transaction_gossip) = transaction_gossip else {
+                    let Some((peer_id, transaction_gossip)) = transaction_gossip else {
                         iroha_logger::info!("All handler to Gossiper are dropped. Shutting down...");
                         break;
                     };
-                    self.handle_transaction_gossip(transaction_gossip);
+                    self.handle_transaction_gossip(&peer_id, transaction_gossip);
                 }
             }
             tokio::task::yield_now().await;
         }
     }
 
-    fn gossip_transactions(&self) {
-        let txs = self
+    fn gossip_size(&self) -> usize {
+        self.gossip_size
+            .get()
+            .try_into()
+            .expect("u32 should always fit in usize")
+    }
+
+    fn gossip_transactions(&mut self) {
+        self.requests.expire(Instant::now());
+
+        let hashes = self
             .queue
-            .n_random_transactions(self.gossip_size.get(), &self.state.view());
+            .n_random_transactions(self.gossip_size.get(), &self.state.view())
+            .iter()
+            .map(|tx| tx.as_ref().hash())
+            .collect::<Vec<_>>();
 
-        if txs.is_empty() {
+        if hashes.is_empty() {
+            return;
+        }
+
+        iroha_logger::trace!(tx_count = hashes.len(), "Announcing transactions");
+        self.network.broadcast(Broadcast {
+            data: NetworkMessage::TransactionGossiper(Box::new(TransactionGossip::Announce(
+                hashes,
+            ))),
+        });
+    }
+
+    fn gossip_cancellations(&self) {
+        let cancellations = self.queue.take_cancellations();
+        if cancellations.is_empty() {
             return;
         }
 
-        iroha_logger::trace!(tx_count = txs.len(), "Gossiping transactions");
+        iroha_logger::trace!(size = cancellations.len(), "Gossiping cancellations");
         self.network.broadcast(Broadcast {
-            data: NetworkMessage::TransactionGossiper(Box::new(TransactionGossip::new(txs))),
+            data: NetworkMessage::TransactionGossiper(Box::new(TransactionGossip::Cancel(
+                cancellations,
+            ))),
         });
     }
 
-    fn handle_transaction_gossip(&self, TransactionGossip { txs }: TransactionGossip) {
-        iroha_logger::trace!(size = txs.len(), "Received new transaction gossip");
+    fn handle_transaction_gossip(&mut self, peer_id: &PeerId, gossip: TransactionGossip) {
+        match gossip {
+            TransactionGossip::Announce(hashes) => self.handle_announce(peer_id, hashes),
+            TransactionGossip::Request(hashes) => self.handle_request(peer_id, &hashes),
+            TransactionGossip::Transactions(txs) => self.handle_transactions(peer_id, txs),
+            TransactionGossip::Cancel(cancellations) => {
+                self.handle_cancellations(peer_id, cancellations);
+            }
+        }
+    }
+
+    /// Cancel the transactions which are still in the queue,
+    /// the applied cancellations are gossiped further during the next gossip period
+    fn handle_cancellations(&self, peer_id: &PeerId, cancellations: Vec<SignedCancelTransaction>) {
+        iroha_logger::trace!(%peer_id, size = cancellations.len(), "Received cancellations");
+
+        for cancellation in cancellations {
+            let hash = *cancellation.payload().hash();
+            match self.queue.cancel(cancellation.clone()) {
+                Ok(()) => {}
+                Err(CancelError::NotFound) => {
+                    iroha_logger::trace!(tx = %hash, "Cancelled transaction isn't in the queue, cancelling it on arrival");
+                    self.queue.cancel_on_arrival(cancellation);
+                }
+                Err(CancelError::InvalidSignature) => {
+                    iroha_logger::warn!(%peer_id, tx = %hash, "Peer gossiped cancellation with invalid signature");
+                    self.network.report_misbehavior(ReportMisbehavior {
+                        public_key: peer_id.public_key().clone(),
+                        misbehavior: Misbehavior::InvalidSignature,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Request the announced transactions which this peer doesn't know yet
+    fn handle_announce(&mut self, peer_id: &PeerId, hashes: Vec<HashOf<SignedTransaction>>) {
+        iroha_logger::trace!(%peer_id, size = hashes.len(), "Received transaction announcement");
+
+        let limit = self.gossip_size();
+        let state_view = self.state.view();
+        let unknown = self.requests.select(
+            hashes.into_iter().take(limit),
+            |hash| is_known(&self.queue, &state_view, hash),
+            Instant::now(),
+        );
+        drop(state_view);
+
+        if unknown.is_empty() {
+            return;
+        }
+
+        iroha_logger::trace!(%peer_id, size = unknown.len(), "Requesting transactions");
+        self.network.post(Post {
+            data: NetworkMessage::TransactionGossiper(Box::new(TransactionGossip::Request(
+                unknown,
+            ))),
+            peer_id: peer_id.clone(),
+        });
+    }
+
+    /// Send the requested transactions which are still in the queue
+    fn handle_request(&self, peer_id: &PeerId, hashes: &[HashOf<SignedTransaction>]) {
+        let txs = queued_transactions(&self.queue, hashes, self.gossip_size());
+
+        if txs.is_empty() {
+            return;
+        }
+
+        iroha_logger::trace!(%peer_id, size = txs.len(), "Sending requested transactions");
+        self.network.post(Post {
+            data: NetworkMessage::TransactionGossiper(Box::new(TransactionGossip::transactions(
+                txs,
+            ))),
+            peer_id: peer_id.clone(),
+        });
+    }
+
+    fn handle_transactions(&mut self, peer_id: &PeerId, txs: Vec<SignedTransaction>) {
+        iroha_logger::trace!(%peer_id, size = txs.len(), "Received transactions");
 
         let state_view = self.state.view();
         for tx in txs {
+            self.requests.complete(&tx.hash());
+
             let transaction_limits = state_view.world().parameters().transaction;
 
             match AcceptedTransaction::accept(tx, &self.chain_id, transaction_limits) {
@@ -127,30 +338,185 @@ impl TransactionGossiper {
                     }) => {
                         iroha_logger::trace!(tx = %tx.as_ref().hash(), "Transaction already in the queue, ignoring...")
                     }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::Cancelled,
+                    }) => {
+                        iroha_logger::trace!(tx = %tx.as_ref().hash(), "Transaction was cancelled, ignoring...")
+                    }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::Closed,
+                    }) => {
+                        iroha_logger::trace!(tx = %tx.as_ref().hash(), "Queue is closed due to shutdown, ignoring...")
+                    }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::AuthorityDenied,
+                    }) => {
+                        iroha_logger::debug!(tx = %tx.as_ref().hash(), authority = %tx.as_ref().authority(), "Transaction authority is denied by this peer, ignoring...")
+                    }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::Rejected(check),
+                    }) => {
+                        iroha_logger::debug!(tx = %tx.as_ref().hash(), check, "Transaction is rejected by an admission check, ignoring...")
+                    }
                     Err(crate::queue::Failure { tx, err }) => {
                         iroha_logger::error!(?err, tx = %tx.as_ref().hash(), "Failed to enqueue transaction.")
                     }
                 },
+                Err(err @ AcceptTransactionFail::SignatureVerification(_)) => {
+                    iroha_logger::warn!(%peer_id, %err, "Peer gossiped transaction with invalid signature");
+                    self.network.report_misbehavior(ReportMisbehavior {
+                        public_key: peer_id.public_key().clone(),
+                        misbehavior: Misbehavior::InvalidSignature,
+                    });
+                }
                 Err(err) => iroha_logger::error!(%err, "Transaction rejected"),
             }
         }
     }
 }
 
-/// Message for gossiping batches of transactions.
+/// Message for gossiping transactions.
+///
+/// **Not compatible with the previous format**, in which the message was a bare batch of
+/// transaction bodies. Peers running different formats fail to decode the gossip of each other
+/// and report it as malformed, so all peers of a network have to be upgraded together.
 #[derive(Decode, Encode, Debug, Clone)]
-pub struct TransactionGossip {
-    /// Batch of transactions.
-    pub txs: Vec<SignedTransaction>,
+pub enum TransactionGossip {
+    /// Hashes of transactions in the queue of the sender.
+    Announce(Vec<HashOf<SignedTransaction>>),
+    /// Request for the bodies of announced transactions the sender doesn't know.
+    Request(Vec<HashOf<SignedTransaction>>),
+    /// Bodies of the requested transactions.
+    Transactions(Vec<SignedTransaction>),
+    /// Cancellations of queued transactions.
+    Cancel(Vec<SignedCancelTransaction>),
 }
 
 impl TransactionGossip {
-    /// Constructor.
-    pub fn new(txs: Vec<AcceptedTransaction>) -> Self {
-        Self {
-            // Converting into non-accepted transaction because it's not possible
-            // to guarantee that the sending peer checked transaction limits
-            txs: txs.into_iter().map(Into::into).collect(),
-        }
+    /// Message with the bodies of `txs`.
+    pub fn transactions(txs: Vec<AcceptedTransaction>) -> Self {
+        // Converting into non-accepted transaction because it's not possible
+        // to guarantee that the sending peer checked transaction limits
+        Self::Transactions(txs.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_config::parameters::actual::Queue as QueueConfig;
+    use iroha_crypto::Hash;
+    use iroha_data_model::{
+        isi::Log, parameter::TransactionParameters, transaction::TransactionBuilder, Level,
+    };
+    use iroha_primitives::time::TimeSource;
+    use nonzero_ext::nonzero;
+    use test_samples::gen_account_in;
+
+    use super::*;
+    use crate::{kura::Kura, query::store::LiveQueryStore, queue::tests::world_with_test_domains};
+
+    const PERIOD: Duration = Duration::from_secs(1);
+
+    fn hash(byte: u8) -> HashOf<SignedTransaction> {
+        HashOf::from_untyped_unchecked(Hash::prehashed([byte; 32]))
+    }
+
+    fn state_and_queue() -> (Arc<State>, Queue, TimeSource) {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Queue::test(
+            QueueConfig {
+                transaction_time_to_live: Duration::from_secs(100),
+                ..QueueConfig::default()
+            },
+            &time_source,
+        );
+
+        (state, queue, time_source)
+    }
+
+    fn accepted_tx(time_source: &TimeSource) -> AcceptedTransaction {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let (account_id, key_pair) = gen_account_in("wonderland");
+        let tx =
+            TransactionBuilder::new_with_time_source(chain_id.clone(), account_id, time_source)
+                .with_instructions([Log::new(Level::INFO, "gossip".to_owned())])
+                .sign(key_pair.private_key());
+        let limits = TransactionParameters {
+            max_instructions: nonzero!(16_u64),
+            ..TransactionParameters::default()
+        };
+
+        AcceptedTransaction::accept(tx, &chain_id, limits).expect("Transaction is valid")
+    }
+
+    #[tokio::test]
+    async fn known_transaction_is_not_requested() {
+        let (state, queue, time_source) = state_and_queue();
+        let tx = accepted_tx(&time_source);
+        let tx_hash = tx.as_ref().hash();
+        queue.push(tx, &state.view()).unwrap();
+
+        let state_view = state.view();
+        let mut requests = Requests::new(PERIOD);
+        let requested = requests.select(
+            [tx_hash],
+            |hash| is_known(&queue, &state_view, hash),
+            Instant::now(),
+        );
+        assert!(requested.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_transaction_is_requested_once_from_all_announcers() {
+        let (state, queue, _time_source) = state_and_queue();
+        let state_view = state.view();
+        let known = |hash: &HashOf<SignedTransaction>| is_known(&queue, &state_view, hash);
+        let mut requests = Requests::new(PERIOD);
+        let now = Instant::now();
+
+        assert_eq!(
+            requests.select([hash(1), hash(1)], known, now),
+            vec![hash(1)]
+        );
+        assert!(requests.select([hash(1)], known, now).is_empty());
+        assert_eq!(
+            requests.select([hash(1), hash(2)], known, now),
+            vec![hash(2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn only_queued_transactions_are_sent_on_request() {
+        let (state, queue, time_source) = state_and_queue();
+        let tx = accepted_tx(&time_source);
+        let tx_hash = tx.as_ref().hash();
+        queue.push(tx, &state.view()).unwrap();
+
+        let sent = queued_transactions(&queue, &[hash(1), tx_hash, hash(2)], 3);
+        assert_eq!(
+            sent.iter().map(|tx| tx.as_ref().hash()).collect::<Vec<_>>(),
+            vec![tx_hash]
+        );
+        assert!(queued_transactions(&queue, &[hash(1), tx_hash], 1).is_empty());
+    }
+
+    #[test]
+    fn requests_expire_after_gossip_period() {
+        let mut requests = Requests::new(PERIOD);
+        let now = Instant::now();
+        assert_eq!(requests.select([hash(1)], |_| false, now), vec![hash(1)]);
+
+        requests.expire(now + PERIOD - Duration::from_millis(1));
+        assert!(requests.select([hash(1)], |_| false, now).is_empty());
+
+        requests.expire(now + PERIOD);
+        assert_eq!(requests.select([hash(1)], |_| false, now), vec![hash(1)]);
     }
 }
@@ -5,13 +5,15 @@
 //! in future.
 //!
 //! Updates mechanism is implemented via subscriptions to [`tokio::sync::watch`] channels. For now,
-//! only `logger.level` field is dynamic, which might be tracked with [`KisoHandle::subscribe_on_log_level()`].
+//! only `logger.level` and `queue.authority_filter` fields are dynamic, which might be tracked with
+//! [`KisoHandle::subscribe_on_log_level()`] and [`KisoHandle::subscribe_on_authority_filter()`].
 
 use eyre::Result;
 use iroha_config::{
-    client_api::{ConfigDTO, Logger as LoggerDTO},
+    client_api::{ConfigDTO, Logger as LoggerDTO, Queue as QueueDTO},
     logger::Directives,
     parameters::actual::Root as Config,
+    queue::AuthorityFilter,
 };
 use tokio::sync::{mpsc, oneshot, watch};
 
@@ -30,10 +32,12 @@ impl KisoHandle {
     pub fn new(state: Config) -> Self {
         let (actor_sender, actor_receiver) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
         let (log_level_update, _) = watch::channel(state.logger.level.clone());
+        let (authority_filter_update, _) = watch::channel(state.queue.authority_filter.clone());
         let mut actor = Actor {
             handle: actor_receiver,
             state,
             log_level_update,
+            authority_filter_update,
         };
         tokio::spawn(async move { actor.run().await });
 
@@ -82,6 +86,20 @@ impl KisoHandle {
         let receiver = rx.await?;
         Ok(receiver)
     }
+
+    /// Subscribe on updates of `queue.authority_filter` parameter.
+    ///
+    /// # Errors
+    /// If communication with actor fails.
+    pub async fn subscribe_on_authority_filter(
+        &self,
+    ) -> Result<watch::Receiver<AuthorityFilter>, Error> {
+        let (tx, rx) = oneshot::channel();
+        let msg = Message::SubscribeOnAuthorityFilter { respond_to: tx };
+        let _ = self.actor.send(msg).await;
+        let receiver = rx.await?;
+        Ok(receiver)
+    }
 }
 
 enum Message {
@@ -95,6 +113,9 @@ enum Message {
     SubscribeOnLogLevel {
         respond_to: oneshot::Sender<watch::Receiver<Directives>>,
     },
+    SubscribeOnAuthorityFilter {
+        respond_to: oneshot::Sender<watch::Receiver<AuthorityFilter>>,
+    },
 }
 
 /// Possible errors might occur while working with [`KisoHandle`]
@@ -112,6 +133,7 @@ struct Actor {
     // new channel here, and new [`Message`] variant. If boilerplate expands, a more general solution will be
     // required. However, as of now a single manually written implementation seems optimal.
     log_level_update: watch::Sender<Directives>,
+    authority_filter_update: watch::Sender<AuthorityFilter>,
 }
 
 impl Actor {
@@ -131,17 +153,30 @@ impl Actor {
                 dto:
                     ConfigDTO {
                         logger: LoggerDTO { level: new_level },
+                        queue,
                     },
                 respond_to,
             } => {
                 let _ = self.log_level_update.send(new_level.clone());
                 self.state.logger.level = new_level;
+                if let Some(QueueDTO {
+                    authority_filter: new_authority_filter,
+                }) = queue
+                {
+                    let _ = self
+                        .authority_filter_update
+                        .send(new_authority_filter.clone());
+                    self.state.queue.authority_filter = new_authority_filter;
+                }
 
                 let _ = respond_to.send(Ok(()));
             }
             Message::SubscribeOnLogLevel { respond_to } => {
                 let _ = respond_to.send(self.log_level_update.subscribe());
             }
+            Message::SubscribeOnAuthorityFilter { respond_to } => {
+                let _ = respond_to.send(self.authority_filter_update.subscribe());
+            }
         }
     }
 }
@@ -152,8 +187,9 @@ mod tests {
 
     use iroha_config::{
         base::{read::ConfigReader, toml::TomlSource},
-        client_api::{ConfigDTO, Logger as LoggerDTO},
+        client_api::{ConfigDTO, Logger as LoggerDTO, Queue as QueueDTO},
         parameters::{actual::Root, user::Root as UserConfig},
+        queue::AuthorityRule,
     };
     use iroha_logger::Level;
 
@@ -192,6 +228,7 @@ mod tests {
             logger: LoggerDTO {
                 level: NEW_LOG_LEVEL.into(),
             },
+            queue: None,
         })
         .await
         .expect("Update should work fine");
@@ -204,4 +241,41 @@ mod tests {
         let value = recv.borrow_and_update().clone();
         assert_eq!(value, NEW_LOG_LEVEL.into());
     }
+
+    #[tokio::test]
+    async fn subscription_on_authority_filter_works() {
+        const WATCH_LAG_MILLIS: u64 = 30;
+
+        let config = test_config();
+        let logger = LoggerDTO::from(&config.logger);
+        let kiso = KisoHandle::new(config);
+
+        let mut recv = kiso
+            .subscribe_on_authority_filter()
+            .await
+            .expect("Subscription should be fine");
+        assert_eq!(*recv.borrow_and_update(), AuthorityFilter::default());
+
+        let new_filter = AuthorityFilter {
+            allow: Vec::new(),
+            deny: vec![AuthorityRule::Domain("wonderland".parse().unwrap())],
+        };
+        kiso.update_with_dto(ConfigDTO {
+            logger,
+            queue: Some(QueueDTO {
+                authority_filter: new_filter.clone(),
+            }),
+        })
+        .await
+        .expect("Update should work fine");
+
+        let () = tokio::time::timeout(Duration::from_millis(WATCH_LAG_MILLIS), recv.changed())
+            .await
+            .expect("Watcher should resolve within timeout")
+            .expect("Watcher should not be closed");
+
+        assert_eq!(*recv.borrow_and_update(), new_filter);
+        let dto = kiso.get_dto().await.expect("Communication should be fine");
+        assert_eq!(dto.queue.expect("Always set").authority_filter, new_filter);
+    }
 }
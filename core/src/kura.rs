@@ -12,17 +12,26 @@ use std::{
 
 use iroha_config::{kura::InitMode, parameters::actual::Kura as Config};
 use iroha_crypto::{Hash, HashOf};
-use iroha_data_model::block::SignedBlock;
+use iroha_data_model::{account::AccountId, block::SignedBlock, transaction::SignedTransaction};
 use iroha_logger::prelude::*;
 use iroha_version::scale::{DecodeVersioned, EncodeVersioned};
 use parity_scale_codec::DecodeAll;
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex};
 
+use self::explorer_index::{ExplorerIndex, TransactionLocation};
 use crate::{block::CommittedBlock, handler::ThreadHandler};
 
+pub mod explorer_index;
+
 const INDEX_FILE_NAME: &str = "blocks.index";
 const DATA_FILE_NAME: &str = "blocks.data";
 const HASHES_FILE_NAME: &str = "blocks.hashes";
+const MIGRATION_DIR_NAME: &str = "migration";
+const MIGRATION_COMPLETE_FILE_NAME: &str = "complete";
+
+/// Prefix of the compressed block records. Versioned blocks start with a non-zero
+/// version byte, so the records without it are read as uncompressed.
+const COMPRESSED_BLOCK_MAGIC: [u8; 4] = [0, b'z', b's', b't'];
 
 const SIZE_OF_BLOCK_HASH: u64 = Hash::LENGTH as u64;
 
@@ -38,6 +47,23 @@ pub struct Kura {
     block_data: Mutex<Vec<(HashOf<SignedBlock>, Option<Arc<SignedBlock>>)>>,
     /// Path to file for plain text blocks.
     block_plain_text_path: Option<PathBuf>,
+    /// Index of the committed transactions, if enabled
+    explorer_index: Option<Mutex<ExplorerIndex>>,
+    /// Maximum number of the stored blocks not yet written to disk, unbounded if [`None`]
+    pipeline_depth: Option<NonZeroUsize>,
+    /// How far the Kura thread got writing the stored blocks
+    persistence: Mutex<Persistence>,
+    /// Notified each time [`Self::persistence`] changes
+    persistence_changed: Condvar,
+}
+
+/// Progress of the Kura thread
+#[derive(Debug, Default)]
+struct Persistence {
+    /// Number of the stored blocks which are written to disk
+    block_count: usize,
+    /// Set once the Kura thread exits, nothing is written to disk afterwards
+    stopped: bool,
 }
 
 impl Kura {
@@ -50,21 +76,33 @@ impl Kura {
     /// path.
     pub fn new(config: &Config) -> Result<(Arc<Self>, BlockCount)> {
         let store_dir = config.store_dir.resolve_relative_path();
-        let mut block_store = BlockStore::new(&store_dir);
+        let mut block_store =
+            BlockStore::new(&store_dir).with_compression_level(config.block_compression_level);
+        block_store.recover_migration()?;
         block_store.create_files_if_they_do_not_exist()?;
 
         let block_plain_text_path = config
             .debug_output_new_blocks
             .then(|| store_dir.join("blocks.json"));
+        let explorer_index = config
+            .explorer_index
+            .then(|| ExplorerIndex::load(&store_dir))
+            .transpose()?
+            .map(Mutex::new);
 
         let kura = Arc::new(Self {
             mode: config.init_mode,
             block_store: Mutex::new(block_store),
             block_data: Mutex::new(Vec::new()),
             block_plain_text_path,
+            explorer_index,
+            pipeline_depth: Some(config.pipeline_depth),
+            persistence: Mutex::default(),
+            persistence_changed: Condvar::new(),
         });
 
         let block_count = kura.init()?;
+        kura.persistence.lock().block_count = block_count.0;
 
         Ok((kura, block_count))
     }
@@ -77,6 +115,10 @@ impl Kura {
             block_store: Mutex::new(BlockStore::new(PathBuf::new())),
             block_data: Mutex::new(Vec::new()),
             block_plain_text_path: None,
+            explorer_index: None,
+            pipeline_depth: None,
+            persistence: Mutex::default(),
+            persistence_changed: Condvar::new(),
         })
     }
 
@@ -124,6 +166,9 @@ impl Kura {
         }?;
 
         let block_count = block_hashes.len();
+        if let Some(explorer_index) = &self.explorer_index {
+            Kura::sync_explorer_index(&block_store, &mut explorer_index.lock(), block_count)?;
+        }
         info!(mode=?self.mode, block_count, "Kura init complete");
 
         // The none value is set in order to indicate that the blocks exist on disk but are not yet loaded.
@@ -188,14 +233,40 @@ impl Kura {
         Ok(block_hashes)
     }
 
+    /// Bring the explorer index in line with the `block_count` blocks in the block store
+    fn sync_explorer_index(
+        block_store: &BlockStore,
+        explorer_index: &mut ExplorerIndex,
+        block_count: usize,
+    ) -> Result<()> {
+        explorer_index.truncate(block_count);
+        if explorer_index.len() < block_count {
+            info!(
+                from_height = explorer_index.len() + 1,
+                "Indexing blocks missing from the explorer index"
+            );
+        }
+        for block_height in explorer_index.len()..block_count {
+            explorer_index.push(&block_store.read_block(block_height as u64)?);
+        }
+        explorer_index.persist()
+    }
+
     #[iroha_logger::log(skip_all)]
     fn kura_receive_blocks_loop(
         kura: &Kura,
         mut shutdown_receiver: tokio::sync::oneshot::Receiver<()>,
     ) {
+        // Blocks stored before the thread is started aren't written yet
         let (mut written_block_count, mut latest_block_hash) = {
             let block_data_guard = kura.block_data.lock();
-            (block_data_guard.len(), block_data_guard.last().map(|d| d.0))
+            let written_block_count = kura.persisted_block_count();
+            (
+                written_block_count,
+                written_block_count
+                    .checked_sub(1)
+                    .map(|idx| block_data_guard[idx].0),
+            )
         };
 
         let mut should_exit = false;
@@ -219,6 +290,7 @@ impl Kura {
             if written_block_count >= block_data_guard.len() {
                 if should_exit {
                     info!("Kura has written remaining blocks to disk and is shutting down.");
+                    kura.update_persistence(|persistence| persistence.stopped = true);
                     return;
                 }
 
@@ -267,6 +339,39 @@ impl Kura {
                     panic!("Kura has encountered a fatal IO error.");
                 }
             }
+            drop(block_store_guard);
+            kura.update_persistence(|persistence| persistence.block_count = written_block_count);
+
+            // Unlike the blocks, the index can be rebuilt, so failing to write it isn't fatal
+            if let Some(explorer_index) = &kura.explorer_index {
+                if let Err(error) = explorer_index.lock().persist() {
+                    error!(?error, "Failed to write explorer index");
+                }
+            }
+        }
+    }
+
+    fn update_persistence(&self, f: impl FnOnce(&mut Persistence)) {
+        f(&mut self.persistence.lock());
+        self.persistence_changed.notify_all();
+    }
+
+    /// Number of the stored blocks which are written to disk.
+    pub fn persisted_block_count(&self) -> usize {
+        self.persistence.lock().block_count
+    }
+
+    /// Block the current thread until the first `block_count` blocks are written to disk.
+    ///
+    /// Returns immediately if this [`Kura`] doesn't write to disk or its thread has stopped.
+    pub fn wait_until_persisted(&self, block_count: usize) {
+        if self.pipeline_depth.is_none() {
+            return;
+        }
+
+        let mut persistence = self.persistence.lock();
+        while persistence.block_count < block_count && !persistence.stopped {
+            self.persistence_changed.wait(&mut persistence);
         }
     }
 
@@ -306,21 +411,11 @@ impl Kura {
             return Some(Arc::clone(block_arc));
         };
 
-        let block_store = self.block_store.lock();
-        let BlockIndex { start, length } = block_store
-            .read_block_index(block_index as u64)
-            .expect("INTERNAL BUG: Failed to read block index from disk.");
-
-        let mut block_buf = vec![
-            0_u8;
-            usize::try_from(length)
-                .expect("INTERNAL BUG: index_len didn't fit in 32-bits")
-        ];
-        block_store
-            .read_block_data(start, &mut block_buf)
-            .expect("INTERNAL BUG: Failed to read block data.");
-        let block = SignedBlock::decode_all_versioned(&block_buf)
-            .expect("INTERNAL BUG: Failed to decode block");
+        let block = self
+            .block_store
+            .lock()
+            .read_block(block_index as u64)
+            .expect("INTERNAL BUG: Failed to read block from disk.");
 
         let block_arc = Arc::new(block);
         data_array_guard[block_index].1 = Some(Arc::clone(&block_arc));
@@ -328,17 +423,59 @@ impl Kura {
     }
 
     /// Put a block in kura's in memory block store.
+    ///
+    /// The block is written to disk by the Kura thread meanwhile the next blocks are processed.
+    /// If more than the pipeline depth of blocks would be waiting for it, blocks until the
+    /// thread catches up.
     pub fn store_block(&self, block: CommittedBlock) {
+        if let Some(pipeline_depth) = self.pipeline_depth {
+            let block_count = self.block_data.lock().len();
+            self.wait_until_persisted((block_count + 1).saturating_sub(pipeline_depth.get()));
+        }
+
         let block = Arc::new(SignedBlock::from(block));
+        if let Some(explorer_index) = &self.explorer_index {
+            explorer_index.lock().push(&block);
+        }
         self.block_data.lock().push((block.hash(), Some(block)));
     }
 
     /// Replace the block in `Kura`'s in memory block store.
     pub fn replace_top_block(&self, block: CommittedBlock) {
         let block = Arc::new(SignedBlock::from(block));
+        if let Some(explorer_index) = &self.explorer_index {
+            let mut explorer_index = explorer_index.lock();
+            explorer_index.pop();
+            explorer_index.push(&block);
+        }
         let mut data = self.block_data.lock();
         data.pop();
         data.push((block.hash(), Some(block)));
+        // The replaced block is still on disk until the Kura thread rewrites it
+        let mut persistence = self.persistence.lock();
+        persistence.block_count = persistence.block_count.min(data.len() - 1);
+    }
+
+    /// Location of the committed transaction with the given `hash`.
+    ///
+    /// Always `None` unless the explorer index is enabled. The index may be ahead of
+    /// the state, so the height of the location has to be checked by the caller.
+    pub fn transaction_location(
+        &self,
+        hash: &HashOf<SignedTransaction>,
+    ) -> Option<TransactionLocation> {
+        self.explorer_index.as_ref()?.lock().transaction(hash)
+    }
+
+    /// Locations of the transactions submitted by `account` in the order of commit,
+    /// `None` if the explorer index isn't enabled. See [`Self::transaction_location`].
+    pub fn account_transactions(&self, account: &AccountId) -> Option<Vec<TransactionLocation>> {
+        Some(
+            self.explorer_index
+                .as_ref()?
+                .lock()
+                .account_transactions(account),
+        )
     }
 }
 
@@ -351,6 +488,8 @@ pub struct BlockCount(pub usize);
 #[derive(Debug)]
 pub struct BlockStore {
     path_to_blockchain: PathBuf,
+    /// Zstd level of the newly written block records, `0` stores them uncompressed
+    compression_level: u8,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -367,6 +506,48 @@ impl BlockStore {
     pub fn new(store_path: impl AsRef<Path>) -> Self {
         Self {
             path_to_blockchain: store_path.as_ref().to_path_buf(),
+            compression_level: 0,
+        }
+    }
+
+    /// Compress the newly written block records with zstd at `level`, `0` disables compression.
+    /// Records are compressed one by one, so compressed and uncompressed records can be mixed.
+    #[must_use]
+    pub fn with_compression_level(mut self, level: u8) -> Self {
+        self.compression_level = level;
+        self
+    }
+
+    /// Encode `block` into a record of the data file,
+    /// compressed if the compression is enabled for this store.
+    ///
+    /// # Errors
+    /// Compression failure.
+    pub fn encode_block(&self, block: &SignedBlock) -> Result<Vec<u8>> {
+        let bytes = block.encode_versioned();
+        if self.compression_level == 0 {
+            return Ok(bytes);
+        }
+
+        let mut record = COMPRESSED_BLOCK_MAGIC.to_vec();
+        record.extend(
+            zstd::encode_all(bytes.as_slice(), i32::from(self.compression_level))
+                .map_err(Error::Compression)?,
+        );
+        Ok(record)
+    }
+
+    /// Decode a record of the data file, either compressed or not.
+    ///
+    /// # Errors
+    /// Malformed record.
+    pub fn decode_block(record: &[u8]) -> Result<SignedBlock> {
+        match record.strip_prefix(&COMPRESSED_BLOCK_MAGIC) {
+            Some(compressed) => {
+                let bytes = zstd::decode_all(compressed).map_err(Error::Compression)?;
+                Ok(SignedBlock::decode_all_versioned(&bytes)?)
+            }
+            None => Ok(SignedBlock::decode_all_versioned(record)?),
         }
     }
 
@@ -538,6 +719,17 @@ impl BlockStore {
         Ok(())
     }
 
+    /// Read and decode the block at the specified `block_height`.
+    ///
+    /// # Errors
+    /// IO Error or malformed block.
+    pub fn read_block(&self, block_height: u64) -> Result<SignedBlock> {
+        let BlockIndex { start, length } = self.read_block_index(block_height)?;
+        let mut block_buf = vec![0_u8; usize::try_from(length)?];
+        self.read_block_data(start, &mut block_buf)?;
+        Self::decode_block(&block_buf)
+    }
+
     /// Write the index of a single block at the specified `block_height`.
     /// If `block_height` is beyond the end of the index file, attempt to
     /// extend the index file.
@@ -714,7 +906,7 @@ impl BlockStore {
     /// Fails if any of the required platform-specific functions
     /// fail.
     pub fn append_block_to_chain(&mut self, block: &SignedBlock) -> Result<()> {
-        let bytes = block.encode_versioned();
+        let bytes = self.encode_block(block)?;
         let new_block_height = self.read_index_count()?;
         let start_location_in_data_file = if new_block_height == 0 {
             0
@@ -733,6 +925,89 @@ impl BlockStore {
 
         Ok(())
     }
+
+    /// Rewrite all the block records with the compression level of this store,
+    /// returning the number of rewritten blocks. The hashes file is left intact.
+    ///
+    /// Records are written into a separate directory first, which is marked complete once all of
+    /// them are written. Only then the data and index files are replaced, so a migration
+    /// interrupted before the mark leaves the store as it was, and one interrupted after it is
+    /// finished by [`Self::recover_migration`]. The store must not be used by a running peer
+    /// during the migration.
+    ///
+    /// # Errors
+    /// IO Error or malformed block.
+    pub fn migrate_compression(&mut self) -> Result<u64> {
+        self.recover_migration()?;
+        let block_count = self.write_migration()?;
+        self.replace_with_migration()?;
+
+        Ok(block_count)
+    }
+
+    /// Finish the migration of the records interrupted after they all were written,
+    /// or discard the one interrupted before. Must be called before the store is used.
+    ///
+    /// # Errors
+    /// IO Error.
+    pub fn recover_migration(&self) -> Result<()> {
+        let migration_dir = self.path_to_blockchain.join(MIGRATION_DIR_NAME);
+        if !migration_dir.exists() {
+            return Ok(());
+        }
+
+        if migration_dir.join(MIGRATION_COMPLETE_FILE_NAME).exists() {
+            iroha_logger::warn!("Finishing the interrupted migration of the block store");
+            return self.replace_with_migration();
+        }
+
+        iroha_logger::warn!("Discarding the interrupted migration of the block store");
+        std::fs::remove_dir_all(&migration_dir).add_err_context(&migration_dir)
+    }
+
+    /// Write all the block records into the migration directory and mark it complete
+    fn write_migration(&self) -> Result<u64> {
+        let migration_dir = self.path_to_blockchain.join(MIGRATION_DIR_NAME);
+        let mut migrated =
+            BlockStore::new(&migration_dir).with_compression_level(self.compression_level);
+        migrated.create_files_if_they_do_not_exist()?;
+
+        let block_count = self.read_index_count()?;
+        let mut start = 0;
+        for block_height in 0..block_count {
+            let bytes = self.encode_block(&self.read_block(block_height)?)?;
+            migrated.write_block_data(start, &bytes)?;
+            migrated.write_block_index(block_height, start, bytes.len() as u64)?;
+            start += bytes.len() as u64;
+        }
+
+        for file_name in [DATA_FILE_NAME, INDEX_FILE_NAME] {
+            let path = migration_dir.join(file_name);
+            std::fs::File::open(&path)
+                .and_then(|file| file.sync_all())
+                .add_err_context(&path)?;
+        }
+        let path = migration_dir.join(MIGRATION_COMPLETE_FILE_NAME);
+        std::fs::File::create(&path)
+            .and_then(|file| file.sync_all())
+            .add_err_context(&path)?;
+
+        Ok(block_count)
+    }
+
+    /// Move the files of the complete migration over the data and index files.
+    /// Files moved before an interruption are skipped, so it can be repeated.
+    fn replace_with_migration(&self) -> Result<()> {
+        let migration_dir = self.path_to_blockchain.join(MIGRATION_DIR_NAME);
+        for file_name in [DATA_FILE_NAME, INDEX_FILE_NAME] {
+            let migrated = migration_dir.join(file_name);
+            if migrated.exists() {
+                let path = self.path_to_blockchain.join(file_name);
+                std::fs::rename(migrated, &path).add_err_context(&path)?;
+            }
+        }
+        std::fs::remove_dir_all(&migration_dir).add_err_context(&migration_dir)
+    }
 }
 
 type Result<T, E = Error> = std::result::Result<T, E>;
@@ -745,6 +1020,8 @@ pub enum Error {
     MkDir(#[source] std::io::Error, PathBuf),
     /// Failed to serialize/deserialize block
     Codec(#[from] parity_scale_codec::Error),
+    /// Failed to decode versioned block
+    VersionedCodec(#[from] iroha_version::error::Error),
     /// Failed to allocate buffer
     Alloc(#[from] std::collections::TryReserveError),
     /// Tried reading block data out of bounds: `start_block_height`, `block_count`
@@ -760,6 +1037,8 @@ pub enum Error {
     IntConversion(#[from] std::num::TryFromIntError),
     /// Blocks count differs hashes file and index file
     HashesFileHeightMismatch,
+    /// Failed to compress/decompress block
+    Compression(#[source] std::io::Error),
 }
 
 trait AddErrContextExt<T> {
@@ -780,10 +1059,11 @@ impl<T> AddErrContextExt<T> for Result<T, std::io::Error> {
 mod tests {
 
     use iroha_crypto::KeyPair;
+    use iroha_data_model::peer::PeerId;
     use tempfile::TempDir;
 
     use super::*;
-    use crate::block::ValidBlock;
+    use crate::{block::ValidBlock, sumeragi::network_topology::Topology};
 
     fn indices<const N: usize>(value: [(u64, u64); N]) -> [BlockIndex; N] {
         let mut ret = [BlockIndex {
@@ -950,16 +1230,356 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compressed_and_uncompressed_blocks_are_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.create_files_if_they_do_not_exist().unwrap();
+
+        let dummy_block: SignedBlock =
+            ValidBlock::new_dummy(KeyPair::random().private_key()).into();
+        block_store.append_block_to_chain(&dummy_block).unwrap();
+        let mut block_store = block_store.with_compression_level(3);
+        block_store.append_block_to_chain(&dummy_block).unwrap();
+
+        let uncompressed = block_store.read_block_index(0).unwrap();
+        let compressed = block_store.read_block_index(1).unwrap();
+        assert_eq!(
+            uncompressed.length,
+            dummy_block.encode_versioned().len() as u64
+        );
+        assert_ne!(compressed.length, uncompressed.length);
+        for block_height in 0..2 {
+            assert_eq!(
+                block_store.read_block(block_height).unwrap().hash(),
+                dummy_block.hash()
+            );
+        }
+    }
+
+    #[test]
+    fn migration_compresses_all_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.create_files_if_they_do_not_exist().unwrap();
+
+        let dummy_block: SignedBlock =
+            ValidBlock::new_dummy(KeyPair::random().private_key()).into();
+        let append_count = 5;
+        for _ in 0..append_count {
+            block_store.append_block_to_chain(&dummy_block).unwrap();
+        }
+
+        let mut block_store = block_store.with_compression_level(3);
+        assert_eq!(block_store.migrate_compression().unwrap(), append_count);
+
+        let compressed = block_store.encode_block(&dummy_block).unwrap();
+        assert!(compressed.starts_with(&COMPRESSED_BLOCK_MAGIC));
+        for block_height in 0..append_count {
+            let BlockIndex { start, length } = block_store.read_block_index(block_height).unwrap();
+            assert_eq!(start, block_height * compressed.len() as u64);
+            assert_eq!(length, compressed.len() as u64);
+            assert_eq!(
+                block_store.read_block(block_height).unwrap().hash(),
+                dummy_block.hash()
+            );
+        }
+        assert_eq!(block_store.read_hashes_count().unwrap(), append_count);
+        assert!(!dir.path().join(MIGRATION_DIR_NAME).exists());
+    }
+
+    #[test]
+    fn migration_interrupted_after_first_rename_is_finished_on_recovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.create_files_if_they_do_not_exist().unwrap();
+
+        let dummy_block: SignedBlock =
+            ValidBlock::new_dummy(KeyPair::random().private_key()).into();
+        let append_count = 5;
+        for _ in 0..append_count {
+            block_store.append_block_to_chain(&dummy_block).unwrap();
+        }
+
+        let block_store = block_store.with_compression_level(3);
+        assert_eq!(block_store.write_migration().unwrap(), append_count);
+        // Interrupted after replacing the data file, but before replacing the index file
+        std::fs::rename(
+            dir.path().join(MIGRATION_DIR_NAME).join(DATA_FILE_NAME),
+            dir.path().join(DATA_FILE_NAME),
+        )
+        .unwrap();
+
+        let block_store = BlockStore::new(dir.path());
+        block_store.recover_migration().unwrap();
+
+        assert!(!dir.path().join(MIGRATION_DIR_NAME).exists());
+        assert_eq!(block_store.read_index_count().unwrap(), append_count);
+        for block_height in 0..append_count {
+            assert_eq!(
+                block_store.read_block(block_height).unwrap().hash(),
+                dummy_block.hash()
+            );
+        }
+    }
+
+    #[test]
+    fn migration_interrupted_before_completion_is_discarded_on_recovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.create_files_if_they_do_not_exist().unwrap();
+
+        let dummy_block: SignedBlock =
+            ValidBlock::new_dummy(KeyPair::random().private_key()).into();
+        block_store.append_block_to_chain(&dummy_block).unwrap();
+
+        // Interrupted while writing the records, so the migration isn't marked complete
+        let migration_dir = dir.path().join(MIGRATION_DIR_NAME);
+        std::fs::create_dir(&migration_dir).unwrap();
+        std::fs::write(migration_dir.join(DATA_FILE_NAME), [0xff; 8]).unwrap();
+
+        block_store.recover_migration().unwrap();
+
+        assert!(!migration_dir.exists());
+        assert_eq!(block_store.read_index_count().unwrap(), 1);
+        assert_eq!(
+            block_store.read_block(0).unwrap().hash(),
+            dummy_block.hash()
+        );
+    }
+
+    fn config(store_dir: &Path, init_mode: InitMode, pipeline_depth: NonZeroUsize) -> Config {
+        Config {
+            init_mode,
+            store_dir: iroha_config::base::WithOrigin::inline(store_dir.to_path_buf()),
+            explorer_index: false,
+            block_compression_level: 0,
+            pipeline_depth,
+            debug_output_new_blocks: false,
+        }
+    }
+
+    fn committed_dummy_block() -> CommittedBlock {
+        let (public_key, private_key) = KeyPair::random().into_parts();
+        let peer_id = PeerId::new("127.0.0.1:8080".parse().unwrap(), public_key);
+        ValidBlock::new_dummy(&private_key)
+            .commit(&Topology::new(vec![peer_id]))
+            .unpack(|_| {})
+            .expect("Block is valid")
+    }
+
+    /// Chain of `count` blocks, each referring to the previous one
+    fn chained_dummy_blocks(count: u64) -> Vec<SignedBlock> {
+        let key_pair = KeyPair::random();
+        let mut prev_block_hash = None;
+        (1..=count)
+            .map(|height| {
+                let block: SignedBlock =
+                    ValidBlock::new_dummy_and_modify_payload(key_pair.private_key(), |payload| {
+                        payload.header.height = height.try_into().unwrap();
+                        payload.header.prev_block_hash = prev_block_hash;
+                    })
+                    .into();
+                prev_block_hash = Some(block.hash());
+                block
+            })
+            .collect()
+    }
+
+    fn block_store_with_blocks(store_dir: &Path, blocks: &[SignedBlock]) -> BlockStore {
+        let mut block_store = BlockStore::new(store_dir);
+        block_store.create_files_if_they_do_not_exist().unwrap();
+        for block in blocks {
+            block_store.append_block_to_chain(block).unwrap();
+        }
+        block_store
+    }
+
     #[tokio::test]
     async fn strict_init_kura() {
         let temp_dir = TempDir::new().unwrap();
-        Kura::new(&Config {
-            init_mode: InitMode::Strict,
-            store_dir: iroha_config::base::WithOrigin::inline(
-                temp_dir.path().to_str().unwrap().into(),
-            ),
-            debug_output_new_blocks: false,
-        })
+        Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Strict,
+            nonzero_ext::nonzero!(4_usize),
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn crash_after_writing_block_data_loses_only_that_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks = chained_dummy_blocks(3);
+        let mut block_store = block_store_with_blocks(temp_dir.path(), &blocks[..2]);
+        // The data of the third block is written, but not its index
+        let BlockIndex { start, length } = block_store.read_block_index(1).unwrap();
+        block_store
+            .write_block_data(start + length, &blocks[2].encode_versioned())
+            .unwrap();
+
+        for init_mode in [InitMode::Strict, InitMode::Fast] {
+            let (kura, block_count) = Kura::new(&config(
+                temp_dir.path(),
+                init_mode,
+                nonzero_ext::nonzero!(4_usize),
+            ))
+            .unwrap();
+            assert_eq!(block_count.0, 2);
+            assert_eq!(kura.persisted_block_count(), 2);
+            assert_eq!(
+                kura.get_block_hash(nonzero_ext::nonzero!(2_usize)),
+                Some(blocks[1].hash())
+            );
+        }
+    }
+
+    #[test]
+    fn crash_before_writing_block_hash_falls_back_to_strict_init() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks = chained_dummy_blocks(3);
+        let mut block_store = block_store_with_blocks(temp_dir.path(), &blocks);
+        // The data and the index of the third block are written, but not its hash
+        block_store
+            .overwrite_block_hashes(&[blocks[0].hash(), blocks[1].hash()])
+            .unwrap();
+
+        let (kura, block_count) = Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Fast,
+            nonzero_ext::nonzero!(4_usize),
+        ))
+        .unwrap();
+        assert_eq!(block_count.0, 3);
+        assert_eq!(
+            kura.get_block_hash(nonzero_ext::nonzero!(3_usize)),
+            Some(blocks[2].hash())
+        );
+        assert_eq!(block_store.read_hashes_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn crash_while_writing_block_data_loses_only_that_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks = chained_dummy_blocks(3);
+        let block_store = block_store_with_blocks(temp_dir.path(), &blocks);
+        // The index count of the third block is written, but only a part of its data
+        let BlockIndex { start, length } = block_store.read_block_index(2).unwrap();
+        let data_path = temp_dir.path().join(DATA_FILE_NAME);
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(data_path)
+            .unwrap()
+            .set_len(start + length / 2)
+            .unwrap();
+
+        let (kura, block_count) = Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Strict,
+            nonzero_ext::nonzero!(4_usize),
+        ))
+        .unwrap();
+        assert_eq!(block_count.0, 2);
+        assert_eq!(kura.get_block_hash(nonzero_ext::nonzero!(3_usize)), None);
+    }
+
+    #[test]
+    fn blocks_are_stored_while_previous_ones_are_written() {
+        let temp_dir = TempDir::new().unwrap();
+        let (kura, _) = Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Strict,
+            nonzero_ext::nonzero!(3_usize),
+        ))
+        .unwrap();
+        let _thread_handle = Kura::start(Arc::clone(&kura));
+
+        // Stall the disk, as if writing the first block took long
+        let block_store = kura.block_store.lock();
+        kura.store_block(committed_dummy_block());
+        let (stored_sender, stored_receiver) = std::sync::mpsc::channel();
+        let store_thread = std::thread::spawn({
+            let kura = Arc::clone(&kura);
+            move || {
+                for _ in 0..2 {
+                    kura.store_block(committed_dummy_block());
+                    stored_sender.send(()).unwrap();
+                }
+            }
+        });
+        for _ in 0..2 {
+            stored_receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .expect("Next blocks should be stored without waiting for the disk");
+        }
+        store_thread.join().unwrap();
+        assert_eq!(kura.persisted_block_count(), 0);
+
+        drop(block_store);
+        kura.wait_until_persisted(3);
+        assert_eq!(kura.block_store.lock().read_index_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn store_block_waits_for_blocks_beyond_pipeline_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let (kura, _) = Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Strict,
+            nonzero_ext::nonzero!(2_usize),
+        ))
+        .unwrap();
+
+        // The Kura thread isn't started yet, so nothing gets written
+        kura.store_block(committed_dummy_block());
+        kura.store_block(committed_dummy_block());
+        let (stored_sender, stored_receiver) = std::sync::mpsc::channel();
+        let store_thread = std::thread::spawn({
+            let kura = Arc::clone(&kura);
+            move || {
+                kura.store_block(committed_dummy_block());
+                stored_sender.send(()).unwrap();
+            }
+        });
+        assert!(stored_receiver
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .is_err());
+        assert_eq!(kura.persisted_block_count(), 0);
+
+        let _thread_handle = Kura::start(Arc::clone(&kura));
+        stored_receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("Block should be stored once the previous ones are written");
+        store_thread.join().unwrap();
+        kura.wait_until_persisted(3);
+        assert_eq!(kura.persisted_block_count(), 3);
+        assert_eq!(kura.block_store.lock().read_index_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn replaced_top_block_is_not_persisted_until_rewritten() {
+        let temp_dir = TempDir::new().unwrap();
+        let (kura, _) = Kura::new(&config(
+            temp_dir.path(),
+            InitMode::Strict,
+            nonzero_ext::nonzero!(1_usize),
+        ))
         .unwrap();
+        let _thread_handle = Kura::start(Arc::clone(&kura));
+
+        kura.store_block(committed_dummy_block());
+        kura.wait_until_persisted(1);
+        let replacement = committed_dummy_block();
+        let replacement_hash = replacement.as_ref().hash();
+        kura.replace_top_block(replacement);
+        kura.wait_until_persisted(1);
+
+        assert_eq!(
+            kura.block_store
+                .lock()
+                .read_block_hashes(0, 1)
+                .unwrap()
+                .first(),
+            Some(&replacement_hash)
+        );
     }
 }
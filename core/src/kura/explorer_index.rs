@@ -0,0 +1,312 @@
+//! Index of the committed transactions maintained by [`Kura`](super::Kura) next to
+//! the block storage if `kura.explorer_index` is enabled.
+//!
+//! Block heights are already mapped to the offsets of the blocks by the block index file.
+//! This index maps transaction hashes to their location in the chain and accounts to
+//! the hashes of the transactions they submitted, so that looking them up doesn't
+//! require scanning every block and no external database is needed.
+//!
+//! The index file is a sequence of entries, one per block in the order of height.
+//! Each entry holds the SCALE-encoded hashes and authorities of the block transactions
+//! and is prefixed with its length as a little-endian `u64`. An incomplete entry left
+//! by an interrupted write is discarded on load and rewritten from the block storage.
+
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use iroha_crypto::HashOf;
+use iroha_data_model::{account::AccountId, block::SignedBlock, transaction::SignedTransaction};
+use iroha_logger::prelude::*;
+use parity_scale_codec::{DecodeAll, Encode};
+
+use super::{AddErrContextExt as _, Result};
+
+const INDEX_FILE_NAME: &str = "explorer.index";
+
+const SIZE_OF_ENTRY_LENGTH: usize = std::mem::size_of::<u64>();
+
+/// Hashes and authorities of the transactions of a block
+type Entry = Vec<(HashOf<SignedTransaction>, AccountId)>;
+
+/// Location of a committed transaction in the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionLocation {
+    /// Height of the block containing the transaction
+    pub height: NonZeroUsize,
+    /// Position of the transaction in the block
+    pub index: usize,
+}
+
+/// Index of the committed transactions, see the [module documentation](self)
+#[derive(Debug, Default)]
+pub struct ExplorerIndex {
+    /// Index file, the index is kept in memory only if not set
+    path: Option<PathBuf>,
+    /// Entries of the indexed blocks
+    blocks: Vec<Entry>,
+    /// Offsets of the persisted entries in the index file followed by the end of the last one
+    offsets: Vec<u64>,
+    transactions: HashMap<HashOf<SignedTransaction>, TransactionLocation>,
+    accounts: HashMap<AccountId, Vec<HashOf<SignedTransaction>>>,
+}
+
+impl ExplorerIndex {
+    /// Load the index from the index file in `store_dir`, starting from scratch if there's none.
+    ///
+    /// # Errors
+    /// IO Error.
+    pub fn load(store_dir: &Path) -> Result<Self> {
+        let path = store_dir.join(INDEX_FILE_NAME);
+        let mut bytes = Vec::new();
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .add_err_context(&path)?
+            .read_to_end(&mut bytes)
+            .add_err_context(&path)?;
+
+        let mut index = Self {
+            path: Some(path),
+            offsets: vec![0],
+            ..Self::default()
+        };
+        let mut start = 0;
+        while start < bytes.len() {
+            let entry_start = start + SIZE_OF_ENTRY_LENGTH;
+            let entry_end = bytes
+                .get(start..entry_start)
+                .map(|length| u64::from_le_bytes(length.try_into().expect("Has the right length")))
+                .and_then(|length| usize::try_from(length).ok())
+                .and_then(|length| entry_start.checked_add(length))
+                .filter(|&entry_end| entry_end <= bytes.len());
+            let Some(entry_end) = entry_end else {
+                warn!("Explorer index ends with an incomplete entry, which is discarded");
+                break;
+            };
+            let Ok(entry) = Entry::decode_all(&mut &bytes[entry_start..entry_end]) else {
+                warn!("Explorer index has a malformed entry. Not reading any entries beyond it.");
+                break;
+            };
+
+            index.push_entry(entry);
+            index.offsets.push(entry_end as u64);
+            start = entry_end;
+        }
+
+        Ok(index)
+    }
+
+    /// Number of indexed blocks
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Whether no blocks are indexed
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Index the transactions of the block following the last indexed one
+    pub fn push(&mut self, block: &SignedBlock) {
+        let entry = block
+            .transactions()
+            .map(|tx| (tx.as_ref().hash(), tx.as_ref().authority().clone()))
+            .collect();
+        self.push_entry(entry);
+    }
+
+    /// Remove the transactions of the last indexed block, e.g. if it's replaced by a soft fork
+    pub fn pop(&mut self) {
+        let Some(entry) = self.blocks.pop() else {
+            return;
+        };
+        for (hash, authority) in entry.iter().rev() {
+            self.transactions.remove(hash);
+            if let Some(hashes) = self.accounts.get_mut(authority) {
+                hashes.pop();
+                if hashes.is_empty() {
+                    self.accounts.remove(authority);
+                }
+            }
+        }
+        // The entry has to be rewritten to the index file
+        self.offsets.truncate(self.blocks.len() + 1);
+    }
+
+    /// Remove the blocks above `block_count`
+    pub fn truncate(&mut self, block_count: usize) {
+        while self.blocks.len() > block_count {
+            self.pop();
+        }
+    }
+
+    /// Location of the committed transaction with the given `hash`
+    pub fn transaction(&self, hash: &HashOf<SignedTransaction>) -> Option<TransactionLocation> {
+        self.transactions.get(hash).copied()
+    }
+
+    /// Locations of the transactions submitted by `account` in the order of commit
+    pub fn account_transactions(&self, account: &AccountId) -> Vec<TransactionLocation> {
+        self.accounts
+            .get(account)
+            .into_iter()
+            .flatten()
+            .filter_map(|hash| self.transaction(hash))
+            .collect()
+    }
+
+    /// Write the entries which aren't in the index file yet
+    ///
+    /// # Errors
+    /// IO Error.
+    pub fn persist(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let persisted = self.offsets.len() - 1;
+        if persisted == self.blocks.len() {
+            return Ok(());
+        }
+
+        let mut end = *self
+            .offsets
+            .last()
+            .expect("Contains at least the start offset");
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .add_err_context(path)?;
+        file.set_len(end).add_err_context(path)?;
+        file.seek(SeekFrom::Start(end)).add_err_context(path)?;
+
+        let mut file = BufWriter::new(file);
+        let mut offsets = Vec::new();
+        for entry in &self.blocks[persisted..] {
+            let bytes = entry.encode();
+            file.write_all(&(bytes.len() as u64).to_le_bytes())
+                .add_err_context(path)?;
+            file.write_all(&bytes).add_err_context(path)?;
+            end += (SIZE_OF_ENTRY_LENGTH + bytes.len()) as u64;
+            offsets.push(end);
+        }
+        file.flush().add_err_context(path)?;
+
+        self.offsets.extend(offsets);
+        Ok(())
+    }
+
+    fn push_entry(&mut self, entry: Entry) {
+        let height = NonZeroUsize::new(self.blocks.len() + 1).expect("Can't be zero");
+        for (index, (hash, authority)) in entry.iter().enumerate() {
+            self.transactions
+                .insert(*hash, TransactionLocation { height, index });
+            self.accounts
+                .entry(authority.clone())
+                .or_default()
+                .push(*hash);
+        }
+        self.blocks.push(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::{prelude::*, transaction::CommittedTransaction, Level};
+    use tempfile::TempDir;
+    use test_samples::gen_account_in;
+
+    use super::*;
+    use crate::block::ValidBlock;
+
+    fn block_with_transactions(count: usize) -> SignedBlock {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let (account_id, key_pair) = gen_account_in("wonderland");
+        let transactions = (0..count)
+            .map(|i| CommittedTransaction {
+                value: TransactionBuilder::new(chain_id.clone(), account_id.clone())
+                    .with_instructions([Log::new(Level::INFO, i.to_string())])
+                    .sign(key_pair.private_key()),
+                error: None,
+            })
+            .collect();
+
+        ValidBlock::new_dummy_and_modify_payload(key_pair.private_key(), |payload| {
+            payload.transactions = transactions;
+        })
+        .into()
+    }
+
+    #[test]
+    fn index_survives_reload_and_soft_fork() {
+        let dir = TempDir::new().unwrap();
+        let blocks = [
+            block_with_transactions(2),
+            block_with_transactions(1),
+            block_with_transactions(3),
+        ];
+        let replacement = block_with_transactions(2);
+
+        let mut index = ExplorerIndex::load(dir.path()).unwrap();
+        for block in &blocks {
+            index.push(block);
+        }
+        index.persist().unwrap();
+        index.pop();
+        index.push(&replacement);
+        index.persist().unwrap();
+
+        let reloaded = ExplorerIndex::load(dir.path()).unwrap();
+        assert_eq!(reloaded.len(), 3);
+        assert_eq!(reloaded.offsets, index.offsets);
+        for (height, block) in [(1, &blocks[0]), (2, &blocks[1]), (3, &replacement)] {
+            let locations = (0..block.transactions().len())
+                .map(|index| TransactionLocation {
+                    height: NonZeroUsize::new(height).unwrap(),
+                    index,
+                })
+                .collect::<Vec<_>>();
+            for (tx, location) in block.transactions().zip(&locations) {
+                assert_eq!(reloaded.transaction(&tx.as_ref().hash()), Some(*location));
+            }
+            let authority = block.transactions().next().unwrap().as_ref().authority();
+            assert_eq!(reloaded.account_transactions(authority), locations);
+        }
+        for tx in blocks[2].transactions() {
+            assert_eq!(reloaded.transaction(&tx.as_ref().hash()), None);
+            assert!(reloaded
+                .account_transactions(tx.as_ref().authority())
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn incomplete_entry_is_discarded() {
+        let dir = TempDir::new().unwrap();
+
+        let mut index = ExplorerIndex::load(dir.path()).unwrap();
+        index.push(&block_with_transactions(1));
+        index.push(&block_with_transactions(1));
+        index.persist().unwrap();
+
+        let path = dir.path().join(INDEX_FILE_NAME);
+        let length = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(length - 1)
+            .unwrap();
+
+        let reloaded = ExplorerIndex::load(dir.path()).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded.offsets, index.offsets[..2]);
+    }
+}
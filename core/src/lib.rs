@@ -2,13 +2,17 @@
 
 pub mod block;
 pub mod block_sync;
+pub mod consistency;
+pub mod event_sink;
 pub mod executor;
 pub mod gossiper;
 pub mod kiso;
 pub mod kura;
 pub mod metrics;
+pub mod partition;
 pub mod query;
 pub mod queue;
+pub mod replay;
 pub mod smartcontracts;
 pub mod snapshot;
 pub mod state;
@@ -17,8 +21,10 @@ pub mod tx;
 
 use core::time::Duration;
 
+use consistency::StateDigestGossip;
 use gossiper::TransactionGossip;
 use iroha_data_model::{events::EventBox, prelude::*};
+use iroha_p2p::{Classify, TrafficClass};
 use iroha_primitives::unique_vec::UniqueVec;
 use parity_scale_codec::{Decode, Encode};
 use tokio::sync::broadcast;
@@ -52,10 +58,57 @@ pub enum NetworkMessage {
     BlockSync(Box<BlockSyncMessage>),
     /// Transaction gossiper message
     TransactionGossiper(Box<TransactionGossip>),
+    /// World state digest gossip message
+    StateDigest(Box<StateDigestGossip>),
     /// Health check message
     Health,
 }
 
+impl NetworkMessage {
+    /// Peer which claims to be the sender of this message, if the message carries one.
+    ///
+    /// It must match the peer the message was actually received from.
+    pub fn claimed_sender(&self) -> Option<&PeerId> {
+        match self {
+            Self::BlockSync(message) => match message.as_ref() {
+                BlockSyncMessage::GetBlocksAfter(message) => Some(&message.peer_id),
+                BlockSyncMessage::ShareBlocks(message) => Some(&message.peer_id),
+            },
+            Self::StateDigest(gossip) => Some(&gossip.peer_id),
+            Self::SumeragiBlock(_)
+            | Self::SumeragiControlFlow(_)
+            | Self::TransactionGossiper(_)
+            | Self::Health => None,
+        }
+    }
+
+    /// Whether the message claims to be sent by a peer other than `sender` it was received from.
+    ///
+    /// Such messages must be dropped.
+    pub fn claims_another_sender(&self, sender: &PeerId) -> bool {
+        self.claimed_sender()
+            .is_some_and(|claimed_sender| claimed_sender.public_key() != sender.public_key())
+    }
+}
+
+impl Classify for NetworkMessage {
+    fn traffic_class(&self) -> TrafficClass {
+        match self {
+            Self::SumeragiBlock(message) => match message.as_ref() {
+                BlockMessage::BlockCreated(_)
+                | BlockMessage::BlockSigned(_)
+                | BlockMessage::BlockCommitted(_) => TrafficClass::Consensus,
+                BlockMessage::BlockSyncUpdate(_) => TrafficClass::Sync,
+            },
+            Self::SumeragiControlFlow(_) => TrafficClass::Consensus,
+            Self::BlockSync(_) => TrafficClass::Sync,
+            Self::TransactionGossiper(_) | Self::StateDigest(_) | Self::Health => {
+                TrafficClass::Regular
+            }
+        }
+    }
+}
+
 pub mod handler {
     //! General purpose thread handler. It is responsible for RAII for
     //! threads started for Kura, Sumeragi and other core routines.
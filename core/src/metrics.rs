@@ -1,6 +1,6 @@
 //! Metrics and status reporting
 
-use std::{num::NonZeroUsize, sync::Arc, time::SystemTime};
+use std::{num::NonZeroUsize, sync::Arc};
 
 use eyre::{Result, WrapErr as _};
 use iroha_telemetry::metrics::Metrics;
@@ -112,11 +112,63 @@ impl MetricsReporter {
             self.metrics.tx_amounts.observe(*amount);
         }
 
+        let latest_block_profile = self.state.block_profiles.lock().drain(..).last();
+        if let Some(profile) = latest_block_profile {
+            self.metrics.block_isi_count.reset();
+            self.metrics.block_isi_time_us.reset();
+            for (kind, times) in &profile.instructions {
+                let kind = kind.to_string();
+                self.metrics
+                    .block_isi_count
+                    .with_label_values(&[&kind])
+                    .set(i64::try_from(times.count).unwrap_or(i64::MAX));
+                self.metrics
+                    .block_isi_time_us
+                    .with_label_values(&[&kind])
+                    .set(i64::try_from(times.total.as_micros()).unwrap_or(i64::MAX));
+            }
+            self.metrics
+                .block_state_lock_wait_us
+                .set(u64::try_from(profile.lock_wait.as_micros()).unwrap_or(u64::MAX));
+        }
+
+        let module_cache = self.state.module_cache.lock().stats();
+        self.metrics.wasm_module_cache_hits.set(module_cache.hits);
+        self.metrics
+            .wasm_module_cache_misses
+            .set(module_cache.misses);
+        self.metrics
+            .wasm_module_cache_size
+            .set(module_cache.size as u64);
+        self.metrics
+            .wasm_compile_time_saved_us
+            .set(u64::try_from(module_cache.compile_time_saved.as_micros()).unwrap_or(u64::MAX));
+
+        let validation_cache = self.state.validation_cache.lock().stats();
+        self.metrics
+            .tx_validation_cache_hits
+            .set(validation_cache.hits);
+        self.metrics
+            .tx_validation_cache_misses
+            .set(validation_cache.misses);
+
+        // Estimating memory usage scans the whole state, so it's only redone after new blocks
+        if *lastest_block_height != start_index {
+            for (collection, usage) in state_view.memory_usage().collections() {
+                self.metrics
+                    .state_entries
+                    .with_label_values(&[collection])
+                    .set(i64::try_from(usage.entries).unwrap_or(i64::MAX));
+                self.metrics
+                    .state_memory_bytes
+                    .with_label_values(&[collection])
+                    .set(i64::try_from(usage.bytes).unwrap_or(i64::MAX));
+            }
+        }
+
         #[allow(clippy::cast_possible_truncation)]
         if let Some(timestamp) = state_view.genesis_timestamp() {
-            let curr_time = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .expect("Failed to get the current system time");
+            let curr_time = self.queue.time_source().get_unix_time();
 
             // this will overflow in 584942417years.
             self.metrics.uptime_since_genesis_ms.set(
@@ -129,6 +181,26 @@ impl MetricsReporter {
 
         self.metrics.connected_peers.set(online_peers_count as u64);
 
+        self.metrics.peer_reputation.reset();
+        self.network.peer_scores(|scores| {
+            for (public_key, score) in scores {
+                self.metrics
+                    .peer_reputation
+                    .with_label_values(&[&public_key.to_string()])
+                    .set(*score);
+            }
+        });
+
+        self.metrics.peer_clock_skew_ms.reset();
+        self.network.clock_skews(|skews| {
+            for (public_key, skew_ms) in skews {
+                self.metrics
+                    .peer_clock_skew_ms
+                    .with_label_values(&[&public_key.to_string()])
+                    .set(*skew_ms);
+            }
+        });
+
         self.metrics
             .domains
             .set(state_view.world().domains().len() as u64);
@@ -146,6 +218,20 @@ impl MetricsReporter {
         }
 
         self.metrics.queue_size.set(self.queue.tx_len() as u64);
+        self.metrics
+            .queue_capacity
+            .set(self.queue.capacity().get() as u64);
+        self.metrics
+            .queue_denied_txs
+            .set(self.queue.denied_tx_count());
+        for check in self.queue.admission_metrics() {
+            for (outcome, count) in [("admitted", check.admitted), ("rejected", check.rejected)] {
+                self.metrics
+                    .queue_admission
+                    .with_label_values(&[check.name, outcome])
+                    .set(i64::try_from(count).unwrap_or(i64::MAX));
+            }
+        }
 
         Ok(())
     }
@@ -0,0 +1,218 @@
+//! Partition detector is actor which watches whether the peer can reach a quorum
+//! of the commit topology.
+//!
+//! Once the quorum is unreachable for longer than the configured timeout, the peer
+//! switches into the degraded mode: its state can no longer be trusted to be the latest,
+//! so Torii still serves queries, but marks their responses as stale and rejects submitted
+//! transactions. The peer leaves the degraded mode as soon as the quorum is reachable again.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use iroha_config::parameters::actual::Network as Config;
+use iroha_data_model::{peer::PeerId, transaction::error::PeerDegraded};
+use iroha_telemetry::metrics::Metrics;
+use parking_lot::RwLock;
+
+use crate::{
+    state::{State, StateReadOnly},
+    sumeragi::network_topology::Topology,
+    IrohaNetwork,
+};
+
+/// The time between checks whether the quorum is reachable
+const CHECK_PERIOD: Duration = Duration::from_secs(1);
+
+/// Whether the peer is in the degraded mode, shared between [`PartitionDetector`] and Torii
+#[derive(Debug, Clone, Default)]
+pub struct PartitionStatus(Arc<RwLock<Option<Partition>>>);
+
+/// Partition of the peer from the quorum of the commit topology
+#[derive(Debug, Clone, Copy)]
+struct Partition {
+    /// When the quorum became unreachable
+    since: Instant,
+    /// Number of peers of the commit topology reachable by this peer, including itself
+    reachable_peers: usize,
+    /// Number of peers required to commit a block
+    quorum: usize,
+}
+
+impl PartitionStatus {
+    /// [`PeerDegraded`] with the details of the partition if the peer is in the degraded mode
+    pub fn degraded(&self) -> Option<PeerDegraded> {
+        (*self.0.read()).map(|partition| PeerDegraded {
+            partitioned_for_ms: partition
+                .since
+                .elapsed()
+                .as_millis()
+                .try_into()
+                .unwrap_or(u64::MAX),
+            reachable_peers: partition.reachable_peers as u64,
+            quorum: partition.quorum as u64,
+        })
+    }
+}
+
+/// Keeps track of for how long the quorum has been unreachable
+#[derive(Debug)]
+struct PartitionTracker {
+    /// Time the quorum has to be unreachable for before the peer is considered partitioned
+    timeout: Duration,
+    /// When the quorum became unreachable, if it is
+    quorum_lost_at: Option<Instant>,
+}
+
+impl PartitionTracker {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            quorum_lost_at: None,
+        }
+    }
+
+    /// Record that `reachable_peers` out of the `quorum` were reachable at `now`
+    /// and return the partition if the quorum has been unreachable for long enough
+    fn observe(
+        &mut self,
+        reachable_peers: usize,
+        quorum: usize,
+        now: Instant,
+    ) -> Option<Partition> {
+        if reachable_peers >= quorum {
+            self.quorum_lost_at = None;
+            return None;
+        }
+
+        let since = *self.quorum_lost_at.get_or_insert(now);
+        (now.saturating_duration_since(since) >= self.timeout).then_some(Partition {
+            since,
+            reachable_peers,
+            quorum,
+        })
+    }
+}
+
+/// Actor which switches the peer into the degraded mode when it is partitioned from the quorum
+pub struct PartitionDetector {
+    peer_id: PeerId,
+    network: IrohaNetwork,
+    state: Arc<State>,
+    metrics: Metrics,
+    tracker: PartitionTracker,
+    status: PartitionStatus,
+}
+
+impl PartitionDetector {
+    /// Construct [`Self`] from configuration
+    pub fn from_config(
+        Config {
+            partition_timeout, ..
+        }: &Config,
+        peer_id: PeerId,
+        network: IrohaNetwork,
+        state: Arc<State>,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            peer_id,
+            network,
+            state,
+            metrics,
+            tracker: PartitionTracker::new(*partition_timeout),
+            status: PartitionStatus::default(),
+        }
+    }
+
+    /// Start [`Self`] actor.
+    pub fn start(self) -> PartitionStatus {
+        let status = self.status.clone();
+        tokio::task::spawn(self.run());
+        status
+    }
+
+    async fn run(mut self) {
+        let mut check_period = tokio::time::interval(CHECK_PERIOD);
+        loop {
+            check_period.tick().await;
+            self.check();
+        }
+    }
+
+    fn check(&mut self) {
+        let commit_topology = self.state.view().commit_topology().to_vec();
+        if commit_topology.is_empty() {
+            // Nothing to be partitioned from before the genesis is committed
+            return;
+        }
+
+        let reachable_peers = self.network.online_peers(|online_peers| {
+            commit_topology
+                .iter()
+                .filter(|peer| **peer == self.peer_id || online_peers.contains(*peer))
+                .count()
+        });
+        let quorum = Topology::new(commit_topology).min_votes_for_commit();
+
+        let partition = self
+            .tracker
+            .observe(reachable_peers, quorum, Instant::now());
+        let mut status = self.status.0.write();
+        match (status.is_some(), partition.is_some()) {
+            (false, true) => iroha_logger::warn!(
+                reachable_peers,
+                quorum,
+                "Quorum of peers is unreachable, switching into the degraded mode"
+            ),
+            (true, false) => {
+                iroha_logger::info!(
+                    "Quorum of peers is reachable again, leaving the degraded mode"
+                );
+            }
+            _ => {}
+        }
+        self.metrics.degraded.set(partition.is_some().into());
+        *status = partition;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn peer_is_partitioned_once_quorum_is_unreachable_for_timeout() {
+        let start = Instant::now();
+        let mut tracker = PartitionTracker::new(TIMEOUT);
+
+        assert!(tracker.observe(1, 3, start).is_none());
+        assert!(tracker
+            .observe(1, 3, start + TIMEOUT - Duration::from_millis(1))
+            .is_none());
+
+        let partition = tracker
+            .observe(2, 3, start + TIMEOUT)
+            .expect("Quorum is unreachable for the timeout");
+        assert_eq!(partition.since, start);
+        assert_eq!(partition.reachable_peers, 2);
+        assert_eq!(partition.quorum, 3);
+    }
+
+    #[test]
+    fn reaching_quorum_resets_partition() {
+        let start = Instant::now();
+        let mut tracker = PartitionTracker::new(TIMEOUT);
+
+        assert!(tracker.observe(1, 3, start).is_none());
+        assert!(tracker.observe(1, 3, start + TIMEOUT).is_some());
+        assert!(tracker.observe(3, 3, start + TIMEOUT * 2).is_none());
+
+        // The timeout starts over after the quorum is lost again
+        assert!(tracker.observe(1, 3, start + TIMEOUT * 3).is_none());
+        assert!(tracker.observe(1, 3, start + TIMEOUT * 4).is_some());
+    }
+}
@@ -0,0 +1,606 @@
+//! This module contains [`QueryCache`], an opt-in cache of query results.
+//!
+//! Results are cached for the latest applied block and only returned to queries
+//! executed on the state at that block. When the next block is applied, results
+//! depending on the entities touched by data events of the block are dropped and
+//! the rest are carried over.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use iroha_config::parameters::actual::LiveQueryStore as Config;
+use iroha_crypto::{Hash, HashOf};
+use iroha_data_model::{
+    block::SignedBlock,
+    events::{
+        pipeline::{BlockEvent, BlockStatus, PipelineEventBox},
+        prelude::*,
+        EventBox,
+    },
+    query::{
+        QueryBox, QueryOutputBatchBox, QueryWithParams, SingularQueryBox, SingularQueryOutputBox,
+    },
+};
+use iroha_telemetry::metrics::Metrics;
+use parity_scale_codec::Encode;
+use tokio::sync::{broadcast, Notify};
+
+use crate::{
+    state::{State, StateReadOnly},
+    EventsSender,
+};
+
+/// Prefixes of the encoded queries, so that singular and iterable queries never share a key
+const SINGULAR: u8 = 0;
+const ITERABLE: u8 = 1;
+
+/// Part of the world state a query output depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Entity {
+    Peers,
+    Domains,
+    Accounts,
+    AssetDefinitions,
+    Assets,
+    AccountPermissions,
+    AccountRoles,
+    Roles,
+    Parameters,
+    ExecutorDataModel,
+}
+
+impl Entity {
+    const ALL: &'static [Self] = &[
+        Self::Peers,
+        Self::Domains,
+        Self::Accounts,
+        Self::AssetDefinitions,
+        Self::Assets,
+        Self::AccountPermissions,
+        Self::AccountRoles,
+        Self::Roles,
+        Self::Parameters,
+        Self::ExecutorDataModel,
+    ];
+
+    /// Entities the output of a singular `query` depends on.
+    ///
+    /// Returns `None` if the output of the query must not be cached,
+    /// e.g. if it depends on blocks or triggers.
+    fn dependencies_of_singular(query: &SingularQueryBox) -> Option<&'static [Self]> {
+        use Entity::*;
+
+        let dependencies: &'static [Self] = match query {
+            SingularQueryBox::FindAssetQuantityById(_) | SingularQueryBox::FindAssetMetadata(_) => {
+                &[Assets]
+            }
+            SingularQueryBox::FindTotalAssetQuantityByAssetDefinitionId(_) => {
+                &[AssetDefinitions, Assets]
+            }
+            SingularQueryBox::FindAssetDefinitionMetadata(_) => &[AssetDefinitions],
+            SingularQueryBox::FindDomainMetadata(_) => &[Domains],
+            SingularQueryBox::FindAccountMetadata(_) => &[Accounts],
+            SingularQueryBox::FindExecutorDataModel(_) => &[ExecutorDataModel],
+            SingularQueryBox::FindParameters(_) => &[Parameters],
+            SingularQueryBox::FindPeerCertificate(_) => &[Peers],
+            SingularQueryBox::FindTriggerById(_)
+            | SingularQueryBox::FindTriggerMetadata(_)
+            | SingularQueryBox::FindTriggerReceipts(_)
+            | SingularQueryBox::FindTransactionByHash(_)
+            | SingularQueryBox::FindBlockHeaderByHash(_)
+            | SingularQueryBox::FindBlockStateDiff(_)
+            | SingularQueryBox::FindBlockRewards(_)
+            | SingularQueryBox::FindIdempotencyRecord(_) => return None,
+        };
+
+        Some(dependencies)
+    }
+
+    /// Entities the output of an iterable `query` depends on.
+    ///
+    /// Returns `None` if the output of the query must not be cached,
+    /// e.g. if it depends on blocks or triggers.
+    fn dependencies_of_iterable(query: &QueryBox) -> Option<&'static [Self]> {
+        use Entity::*;
+
+        let dependencies: &'static [Self] = match query {
+            QueryBox::FindDomains(_) => &[Domains],
+            QueryBox::FindAccounts(_) => &[Accounts],
+            QueryBox::FindAssets(_) | QueryBox::FindStoreEntries(_) => &[Assets],
+            QueryBox::FindAssetsDefinitions(_) => &[AssetDefinitions],
+            QueryBox::FindRoles(_) | QueryBox::FindRoleIds(_) => &[Roles],
+            QueryBox::FindPermissionsByAccountId(_) => &[AccountPermissions],
+            QueryBox::FindRolesByAccountId(_) => &[AccountRoles],
+            QueryBox::FindAccountsWithAsset(_) => &[Accounts, Assets],
+            QueryBox::FindPeers(_) => &[Peers],
+            QueryBox::FindActiveTriggerIds(_)
+            | QueryBox::FindAllActiveTriggers(_)
+            | QueryBox::FindTriggersByDomain(_)
+            | QueryBox::FindTransactionsByAccountId(_)
+            | QueryBox::FindTransactionsByAppId(_)
+            | QueryBox::FindTransactions(_)
+            | QueryBox::FindBlocks(_)
+            | QueryBox::FindBlockHeaders(_)
+            | QueryBox::FindEvents(_) => return None,
+        };
+
+        Some(dependencies)
+    }
+
+    /// Entities touched by `event`, including the ones removed along with the origin of the event.
+    fn touched_by(event: &DataEvent) -> &'static [Self] {
+        use Entity::*;
+
+        const DOMAIN_DELETED: &[Entity] = &[
+            Domains,
+            Accounts,
+            AssetDefinitions,
+            Assets,
+            AccountPermissions,
+            AccountRoles,
+        ];
+        const ACCOUNT_DELETED: &[Entity] = &[Accounts, Assets, AccountPermissions, AccountRoles];
+
+        match event {
+            DataEvent::Peer(_) => &[Peers],
+            DataEvent::Domain(DomainEvent::Deleted(_)) => DOMAIN_DELETED,
+            DataEvent::Domain(DomainEvent::AssetDefinition(AssetDefinitionEvent::Deleted(_))) => {
+                &[AssetDefinitions, Assets]
+            }
+            DataEvent::Domain(DomainEvent::AssetDefinition(_)) => &[AssetDefinitions],
+            DataEvent::Domain(DomainEvent::Account(event)) => match event {
+                AccountEvent::Deleted(_) => ACCOUNT_DELETED,
+                AccountEvent::Asset(_) => &[Assets],
+                AccountEvent::PermissionAdded(_) | AccountEvent::PermissionRemoved(_) => {
+                    &[AccountPermissions]
+                }
+                AccountEvent::RoleGranted(_) | AccountEvent::RoleRevoked(_) => &[AccountRoles],
+                AccountEvent::Created(_)
+                | AccountEvent::MetadataInserted(_)
+                | AccountEvent::MetadataRemoved(_)
+                | AccountEvent::Frozen(_)
+                | AccountEvent::Unfrozen(_) => &[Accounts],
+                // Recovery policies, apps and proxies aren't queried
+                AccountEvent::RecoveryPolicySet(_)
+                | AccountEvent::RecoveryInitiated(_)
+                | AccountEvent::RecoveryVetoed(_)
+                | AccountEvent::AppRegistered(_)
+                | AccountEvent::AppUnregistered(_)
+                | AccountEvent::ProxyGranted(_)
+                | AccountEvent::ProxyRevoked(_)
+                | AccountEvent::ProxyUsed(_) => &[],
+                // Recovery moves everything owned by the account to the new id
+                AccountEvent::Recovered(_) => Self::ALL,
+            },
+            DataEvent::Domain(_) => &[Domains],
+            DataEvent::Trigger(_) => &[],
+            // Escrows aren't queried, and changes of the locked assets come with asset events
+            DataEvent::Escrow(_) => &[],
+            DataEvent::Role(RoleEvent::Deleted(_)) => &[Roles, AccountRoles],
+            DataEvent::Role(_) => &[Roles],
+            DataEvent::Configuration(_) => &[Parameters],
+            // Upgrade of the executor might migrate any part of the state
+            DataEvent::Executor(_) => Self::ALL,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CachedOutput {
+    Singular(SingularQueryOutputBox),
+    Iterable(QueryOutputBatchBox),
+}
+
+#[derive(Debug)]
+struct Entry {
+    output: CachedOutput,
+    dependencies: &'static [Entity],
+    last_access: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// The latest applied block, all entries are valid for the state at this block
+    latest_block: Option<(u64, HashOf<SignedBlock>)>,
+    /// Entities touched by data events since the latest applied block
+    touched: BTreeSet<Entity>,
+    /// Cached outputs by hash of the query
+    entries: BTreeMap<Hash, Entry>,
+    /// Monotonic counter used to find the least recently used entry
+    access_counter: u64,
+}
+
+impl Entries {
+    fn block_applied(&mut self, height: u64, hash: HashOf<SignedBlock>) {
+        let touched = core::mem::take(&mut self.touched);
+        if self
+            .latest_block
+            .is_some_and(|(latest_height, _)| latest_height + 1 == height)
+        {
+            self.entries.retain(|_, entry| {
+                !entry
+                    .dependencies
+                    .iter()
+                    .any(|dependency| touched.contains(dependency))
+            });
+        } else {
+            // The top block was replaced, so the events don't describe all of the changes
+            self.entries.clear();
+        }
+        self.latest_block = Some((height, hash));
+    }
+
+    fn reset(&mut self) {
+        self.latest_block = None;
+        self.touched.clear();
+        self.entries.clear();
+    }
+
+    fn is_latest(&self, block: Option<HashOf<SignedBlock>>) -> bool {
+        block.is_some() && self.latest_block.map(|(_, hash)| hash) == block
+    }
+}
+
+/// Cache of query results which is invalidated by data events.
+pub struct QueryCache {
+    capacity: NonZeroUsize,
+    events: broadcast::Receiver<EventBox>,
+    latest_block: Option<(u64, HashOf<SignedBlock>)>,
+    metrics: Metrics,
+    notify_shutdown: Arc<Notify>,
+}
+
+impl QueryCache {
+    /// Construct [`QueryCache`] from configuration.
+    ///
+    /// Returns `None` if query caching is disabled.
+    pub fn from_config(
+        config: &Config,
+        state: &State,
+        events_sender: &EventsSender,
+        metrics: Metrics,
+        notify_shutdown: Arc<Notify>,
+    ) -> Option<Self> {
+        let capacity = config.cache_capacity?;
+
+        // NOTE: subscribe before reading the state so that no block is missed
+        let events = events_sender.subscribe();
+        let state_view = state.view();
+        let latest_block = state_view
+            .latest_block_hash()
+            .map(|hash| (state_view.height() as u64, hash));
+
+        Some(Self {
+            capacity,
+            events,
+            latest_block,
+            metrics,
+            notify_shutdown,
+        })
+    }
+
+    /// Start [`QueryCache`]. Requires a [`tokio::runtime::Runtime`] being run
+    /// as it will create new [`tokio::task`] and detach it.
+    ///
+    /// Returns a handle to interact with the cache.
+    pub fn start(self) -> QueryCacheHandle {
+        let handle = QueryCacheHandle {
+            capacity: self.capacity,
+            entries: Arc::new(Mutex::new(Entries {
+                latest_block: self.latest_block,
+                ..Entries::default()
+            })),
+            metrics: self.metrics,
+        };
+        tokio::task::spawn(Self::run(handle.clone(), self.events, self.notify_shutdown));
+        handle
+    }
+
+    async fn run(
+        handle: QueryCacheHandle,
+        mut events: broadcast::Receiver<EventBox>,
+        notify_shutdown: Arc<Notify>,
+    ) {
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => handle.handle_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        iroha_logger::warn!(skipped, "Query cache missed events, dropping all cached results");
+                        handle.lock().reset();
+                        handle.report_size(0);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                () = notify_shutdown.notified() => {
+                    iroha_logger::info!("QueryCache is being shut down.");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Handle to interact with [`QueryCache`].
+#[derive(Clone)]
+pub struct QueryCacheHandle {
+    capacity: NonZeroUsize,
+    entries: Arc<Mutex<Entries>>,
+    metrics: Metrics,
+}
+
+impl QueryCacheHandle {
+    /// Get the cached output of a singular `query`, or `execute` it and cache the output.
+    ///
+    /// `latest_block` is the hash of the latest block of the state the query is executed on.
+    ///
+    /// # Errors
+    ///
+    /// Forwards the error of `execute`.
+    pub fn singular<E>(
+        &self,
+        query: SingularQueryBox,
+        latest_block: Option<HashOf<SignedBlock>>,
+        execute: impl FnOnce(SingularQueryBox) -> Result<SingularQueryOutputBox, E>,
+    ) -> Result<SingularQueryOutputBox, E> {
+        let Some(dependencies) = Entity::dependencies_of_singular(&query) else {
+            return execute(query);
+        };
+        let key = Hash::new((SINGULAR, &query).encode());
+
+        if let Some(CachedOutput::Singular(output)) = self.get(&key, latest_block) {
+            return Ok(output);
+        }
+        let output = execute(query)?;
+        self.insert(
+            key,
+            dependencies,
+            latest_block,
+            CachedOutput::Singular(output.clone()),
+        );
+        Ok(output)
+    }
+
+    /// Get the cached sorted and paginated output of an iterable `query`,
+    /// or `execute` it and cache the output.
+    ///
+    /// `latest_block` is the hash of the latest block of the state the query is executed on.
+    ///
+    /// # Errors
+    ///
+    /// Forwards the error of `execute`.
+    pub fn iterable<E>(
+        &self,
+        query: QueryWithParams,
+        latest_block: Option<HashOf<SignedBlock>>,
+        execute: impl FnOnce(QueryWithParams) -> Result<QueryOutputBatchBox, E>,
+    ) -> Result<QueryOutputBatchBox, E> {
+        let Some(dependencies) = Entity::dependencies_of_iterable(&query.query) else {
+            return execute(query);
+        };
+        let key = Hash::new((ITERABLE, &query).encode());
+
+        if let Some(CachedOutput::Iterable(output)) = self.get(&key, latest_block) {
+            return Ok(output);
+        }
+        let output = execute(query)?;
+        self.insert(
+            key,
+            dependencies,
+            latest_block,
+            CachedOutput::Iterable(output.clone()),
+        );
+        Ok(output)
+    }
+
+    fn get(&self, key: &Hash, latest_block: Option<HashOf<SignedBlock>>) -> Option<CachedOutput> {
+        let mut entries = self.lock();
+        if !entries.is_latest(latest_block) {
+            self.metrics.query_cache_misses.inc();
+            return None;
+        }
+
+        entries.access_counter += 1;
+        let access_counter = entries.access_counter;
+        let Some(entry) = entries.entries.get_mut(key) else {
+            self.metrics.query_cache_misses.inc();
+            return None;
+        };
+        entry.last_access = access_counter;
+        self.metrics.query_cache_hits.inc();
+
+        Some(match &entry.output {
+            CachedOutput::Singular(output) => CachedOutput::Singular(output.clone()),
+            CachedOutput::Iterable(output) => CachedOutput::Iterable(output.clone()),
+        })
+    }
+
+    fn insert(
+        &self,
+        key: Hash,
+        dependencies: &'static [Entity],
+        latest_block: Option<HashOf<SignedBlock>>,
+        output: CachedOutput,
+    ) {
+        let mut entries = self.lock();
+        // The state has changed since the query was executed
+        if !entries.is_latest(latest_block) {
+            return;
+        }
+
+        if entries.entries.len() >= self.capacity.get() && !entries.entries.contains_key(&key) {
+            let least_recently_used = entries
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| *key);
+            if let Some(least_recently_used) = least_recently_used {
+                entries.entries.remove(&least_recently_used);
+            }
+        }
+
+        entries.access_counter += 1;
+        let last_access = entries.access_counter;
+        entries.entries.insert(
+            key,
+            Entry {
+                output,
+                dependencies,
+                last_access,
+            },
+        );
+        self.report_size(entries.entries.len());
+    }
+
+    fn handle_event(&self, event: &EventBox) {
+        let mut entries = self.lock();
+        match event {
+            EventBox::Data(event) => {
+                entries.touched.extend(Entity::touched_by(event));
+            }
+            EventBox::Pipeline(PipelineEventBox::Block(BlockEvent {
+                header,
+                hash,
+                status: BlockStatus::Applied,
+            })) => {
+                entries.block_applied(header.height().get(), *hash);
+                self.report_size(entries.entries.len());
+            }
+            _ => {}
+        }
+    }
+
+    fn report_size(&self, size: usize) {
+        self.metrics.query_cache_size.set(size as u64);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .expect("INTERNAL BUG: query cache lock is poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::{
+        block::{BlockHeader, GENESIS_PROTOCOL_VERSION},
+        query::{
+            domain::FindDomains, parameters::QueryParams, predicate::CompoundPredicate,
+            QueryWithFilter,
+        },
+    };
+    use nonzero_ext::nonzero;
+
+    use super::*;
+
+    fn handle(capacity: NonZeroUsize) -> QueryCacheHandle {
+        QueryCacheHandle {
+            capacity,
+            entries: Arc::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    fn apply_block(
+        cache: &QueryCacheHandle,
+        height: u64,
+        events: Vec<DataEvent>,
+    ) -> HashOf<SignedBlock> {
+        let header = BlockHeader {
+            height: height.try_into().expect("height is not zero"),
+            prev_block_hash: None,
+            transactions_hash: HashOf::from_untyped_unchecked(Hash::prehashed([0; 32])),
+            creation_time_ms: 0,
+            view_change_index: 0,
+            consensus_estimation_ms: 0,
+            protocol_version: GENESIS_PROTOCOL_VERSION,
+        };
+        let hash = HashOf::from_untyped_unchecked(Hash::new(height.to_le_bytes()));
+        for event in events {
+            cache.handle_event(&event.into());
+        }
+        cache.handle_event(
+            &BlockEvent {
+                header,
+                hash,
+                status: BlockStatus::Applied,
+            }
+            .into(),
+        );
+        hash
+    }
+
+    fn find_domains() -> QueryWithParams {
+        QueryWithParams::new(
+            QueryBox::FindDomains(QueryWithFilter::new(FindDomains, CompoundPredicate::PASS)),
+            QueryParams::default(),
+        )
+    }
+
+    fn execute(
+        calls: &mut usize,
+    ) -> impl FnOnce(QueryWithParams) -> Result<QueryOutputBatchBox, ()> + '_ {
+        |_| {
+            *calls += 1;
+            Ok(QueryOutputBatchBox::Domain(Vec::new()))
+        }
+    }
+
+    #[test]
+    fn output_is_reused_until_invalidated() {
+        let cache = handle(nonzero!(8_usize));
+        let mut calls = 0;
+
+        let block = apply_block(&cache, 1, Vec::new());
+        cache
+            .iterable(find_domains(), Some(block), execute(&mut calls))
+            .unwrap();
+        cache
+            .iterable(find_domains(), Some(block), execute(&mut calls))
+            .unwrap();
+        assert_eq!(calls, 1);
+
+        // Unrelated changes keep the output
+        let block = apply_block(
+            &cache,
+            2,
+            vec![RoleEvent::Deleted("admin".parse().unwrap()).into()],
+        );
+        cache
+            .iterable(find_domains(), Some(block), execute(&mut calls))
+            .unwrap();
+        assert_eq!(calls, 1);
+
+        // Domain changes drop the output
+        let block = apply_block(
+            &cache,
+            3,
+            vec![DomainEvent::Deleted("wonderland".parse().unwrap()).into()],
+        );
+        cache
+            .iterable(find_domains(), Some(block), execute(&mut calls))
+            .unwrap();
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn output_is_not_cached_for_outdated_state() {
+        let cache = handle(nonzero!(8_usize));
+        let mut calls = 0;
+
+        let outdated = apply_block(&cache, 1, Vec::new());
+        apply_block(&cache, 2, Vec::new());
+        cache
+            .iterable(find_domains(), Some(outdated), execute(&mut calls))
+            .unwrap();
+        cache
+            .iterable(find_domains(), Some(outdated), execute(&mut calls))
+            .unwrap();
+        assert_eq!(calls, 2);
+        assert!(cache.lock().entries.is_empty());
+    }
+}
@@ -115,6 +115,28 @@ impl QueryBatchedErasedIterator {
         }
     }
 
+    /// Creates a new batched iterator over an already collected query output.
+    pub fn from_batch(batch: QueryOutputBatchBox, batch_size: NonZeroU32) -> Self {
+        match batch {
+            QueryOutputBatchBox::Domain(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Account(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Asset(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::AssetDefinition(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Role(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Parameter(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Permission(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Transaction(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Peer(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::RoleId(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::TriggerId(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::Block(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::BlockHeader(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::ArchivedEvent(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::StoreEntry(v) => Self::new(v.into_iter(), batch_size),
+            QueryOutputBatchBox::TriggerDetails(v) => Self::new(v.into_iter(), batch_size),
+        }
+    }
+
     /// Gets the next batch of results.
     ///
     /// Checks if the cursor matches the server's cursor.
@@ -1,5 +1,7 @@
 //! This module contains [`QueryService`](service::QueryService) and helpers.
 
+pub mod cache;
 pub mod cursor;
 pub mod pagination;
+pub mod quota;
 pub mod store;
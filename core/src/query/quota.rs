@@ -0,0 +1,153 @@
+//! This module contains [`QueryQuotas`], an opt-in limit on the cost of queries per account.
+//!
+//! Every query costs [`BASE_COST`] plus one unit per entity it visits, so that a full-world
+//! scan costs as much as the world is large. An account may spend up to the configured budget,
+//! and the spent budget is replenished gradually during the configured period. Queries which
+//! would cost more than the remaining budget are aborted with
+//! [`QueryExecutionFail::QueryCostExceeded`](iroha_data_model::query::error::QueryExecutionFail::QueryCostExceeded).
+
+use std::{
+    num::NonZeroU64,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use iroha_config::parameters::actual::LiveQueryStore as Config;
+use iroha_data_model::account::AccountId;
+
+/// Cost of any query, charged in addition to the entities it visits
+pub const BASE_COST: u64 = 1;
+
+/// Query budgets of the accounts.
+///
+/// Clones share the budgets.
+#[derive(Debug, Clone)]
+pub struct QueryQuotas {
+    budget: NonZeroU64,
+    period: Duration,
+    spent: Arc<DashMap<AccountId, Spent>>,
+}
+
+/// Part of the budget spent by an account, as of `at`
+#[derive(Debug, Clone, Copy)]
+struct Spent {
+    amount: u64,
+    at: Instant,
+}
+
+impl QueryQuotas {
+    /// Construct [`QueryQuotas`] from configuration.
+    ///
+    /// Returns `None` if queries are not metered.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config
+            .cost_budget
+            .map(|budget| Self::new(budget, config.cost_period))
+    }
+
+    /// Construct [`QueryQuotas`] allowing every account to spend `budget` per `period`
+    pub fn new(budget: NonZeroU64, period: Duration) -> Self {
+        Self {
+            budget,
+            period,
+            spent: Arc::default(),
+        }
+    }
+
+    /// The most `account` may spend on its next query
+    pub fn remaining(&self, account: &AccountId) -> u64 {
+        self.remaining_at(account, Instant::now())
+    }
+
+    /// Charge `account` for a query which cost `cost`
+    pub fn charge(&self, account: &AccountId, cost: u64) {
+        self.charge_at(account, cost, Instant::now());
+    }
+
+    fn remaining_at(&self, account: &AccountId, now: Instant) -> u64 {
+        let unreplenished = self
+            .spent
+            .get(account)
+            .map_or(0, |spent| self.unreplenished(*spent, now));
+        if unreplenished == 0 {
+            // Forget the accounts with the whole budget available
+            self.spent.remove(account);
+        }
+        self.budget.get() - unreplenished
+    }
+
+    fn charge_at(&self, account: &AccountId, cost: u64, now: Instant) {
+        let mut spent = self
+            .spent
+            .entry(account.clone())
+            .or_insert(Spent { amount: 0, at: now });
+        let amount = self
+            .unreplenished(*spent, now)
+            .saturating_add(cost)
+            .min(self.budget.get());
+        *spent = Spent { amount, at: now };
+    }
+
+    /// Part of the `spent` amount which hasn't been replenished by `now`
+    fn unreplenished(&self, spent: Spent, now: Instant) -> u64 {
+        let elapsed = now.saturating_duration_since(spent.at).as_nanos();
+        let replenished = u128::from(self.budget.get()) * elapsed / self.period.as_nanos().max(1);
+        u64::try_from(replenished).map_or(0, |replenished| spent.amount.saturating_sub(replenished))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nonzero_ext::nonzero;
+    use test_samples::ALICE_ID;
+
+    use super::*;
+
+    fn quotas() -> QueryQuotas {
+        QueryQuotas::new(nonzero!(100_u64), Duration::from_secs(10))
+    }
+
+    #[test]
+    fn budget_is_spent_and_replenished() {
+        let quotas = quotas();
+        let start = Instant::now();
+
+        assert_eq!(quotas.remaining_at(&ALICE_ID, start), 100);
+        quotas.charge_at(&ALICE_ID, 80, start);
+        assert_eq!(quotas.remaining_at(&ALICE_ID, start), 20);
+        // 10% of the period replenishes 10% of the budget
+        assert_eq!(
+            quotas.remaining_at(&ALICE_ID, start + Duration::from_secs(1)),
+            30
+        );
+        assert_eq!(
+            quotas.remaining_at(&ALICE_ID, start + Duration::from_secs(10)),
+            100
+        );
+    }
+
+    #[test]
+    fn overspending_is_capped_by_budget() {
+        let quotas = quotas();
+        let start = Instant::now();
+
+        quotas.charge_at(&ALICE_ID, 1_000, start);
+        assert_eq!(quotas.remaining_at(&ALICE_ID, start), 0);
+        assert_eq!(
+            quotas.remaining_at(&ALICE_ID, start + Duration::from_secs(10)),
+            100
+        );
+    }
+
+    #[test]
+    fn accounts_have_separate_budgets() {
+        let quotas = quotas();
+        let start = Instant::now();
+        let (bob_id, _) = test_samples::gen_account_in("wonderland");
+
+        quotas.charge_at(&ALICE_ID, 100, start);
+        assert_eq!(quotas.remaining_at(&ALICE_ID, start), 0);
+        assert_eq!(quotas.remaining_at(&bob_id, start), 100);
+    }
+}
@@ -1,12 +1,20 @@
 //! Module with queue actor
 use core::time::Duration;
-use std::{num::NonZeroUsize, ops::Deref, sync::Arc};
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::{ArrayQueue, SegQueue};
 use dashmap::{mapref::entry::Entry, DashMap};
 use eyre::Result;
 use indexmap::IndexSet;
-use iroha_config::parameters::actual::Queue as Config;
+use iroha_config::{parameters::actual::Queue as Config, queue::AuthorityFilter};
 use iroha_crypto::HashOf;
 use iroha_data_model::{
     account::AccountId,
@@ -15,11 +23,15 @@ use iroha_data_model::{
 };
 use iroha_logger::{trace, warn};
 use iroha_primitives::time::TimeSource;
+use parking_lot::{Mutex, RwLock};
 use rand::seq::IteratorRandom;
 use thiserror::Error;
 
+use self::admission::{AdmissionCheck, AdmissionCheckMetrics, AdmissionPipeline};
 use crate::{prelude::*, EventsSender};
 
+pub mod admission;
+
 impl AcceptedTransaction {
     // TODO: We should have another type of transaction like `CheckedTransaction` in the type system?
     /// Check if [`self`] is committed or rejected.
@@ -53,6 +65,64 @@ pub struct Queue {
     /// A point in time that is considered `Future` we cannot use
     /// current time, because of network time synchronisation issues
     future_threshold: Duration,
+    /// How far the clocks of the peers may drift apart, see [`Queue::clock_skew_tolerance`]
+    clock_skew_tolerance: Duration,
+    /// Set when the peer is shutting down, no new transactions are accepted afterwards
+    is_closed: AtomicBool,
+    /// Accounts and domains whose transactions are admitted into the queue
+    authority_filter: RwLock<AuthorityFilter>,
+    /// Checks a transaction has to pass to be admitted into the queue
+    admission: AdmissionPipeline,
+    /// Cancellations which haven't been gossiped to other peers yet
+    cancellations: SegQueue<SignedCancelTransaction>,
+    /// Cancelled transactions which mustn't be accepted again
+    tombstones: Mutex<Tombstones>,
+}
+
+/// Cancelled transactions, remembered until they would have expired so that they aren't
+/// accepted into the queue again, e.g. when gossiped back by a peer which hasn't received
+/// the cancellation yet or resubmitted by someone who saw them.
+///
+/// Holds at most as many transactions as the queue does.
+#[derive(Debug, Default)]
+struct Tombstones(HashMap<HashOf<SignedTransaction>, Tombstone>);
+
+#[derive(Debug)]
+struct Tombstone {
+    /// Time after which the transaction is forgotten
+    expires_at: Duration,
+    /// Cancellation which arrived before the transaction,
+    /// it's verified once the transaction arrives
+    unverified: Option<SignedCancelTransaction>,
+}
+
+impl Tombstones {
+    /// Remember `hash`, making room by forgetting the expired tombstones,
+    /// or else the unverified or the earliest expiring one
+    fn insert(
+        &mut self,
+        hash: HashOf<SignedTransaction>,
+        tombstone: Tombstone,
+        now: Duration,
+        capacity: NonZeroUsize,
+    ) {
+        if self.0.len() >= capacity.get() && !self.0.contains_key(&hash) {
+            self.0.retain(|_, tombstone| tombstone.expires_at >= now);
+            if self.0.len() >= capacity.get() {
+                let evicted = self
+                    .0
+                    .iter()
+                    .min_by_key(|(_, tombstone)| {
+                        (tombstone.unverified.is_none(), tombstone.expires_at)
+                    })
+                    .map(|(hash, _)| *hash);
+                if let Some(evicted) = evicted {
+                    self.0.remove(&evicted);
+                }
+            }
+        }
+        self.0.insert(hash, tombstone);
+    }
 }
 
 /// Queue push error
@@ -71,6 +141,23 @@ pub enum Error {
     MaximumTransactionsPerUser,
     /// The transaction is already in the queue
     IsInQueue,
+    /// Queue is closed because the peer is shutting down
+    Closed,
+    /// Transaction authority is denied by the peer's authority filter
+    AuthorityDenied,
+    /// Transaction is rejected by the `{0}` admission check
+    Rejected(&'static str),
+    /// Transaction was cancelled by its authority
+    Cancelled,
+}
+
+/// Queue cancellation error
+#[derive(Error, Copy, Clone, Debug, displaydoc::Display)]
+pub enum CancelError {
+    /// Transaction isn't in the queue
+    NotFound,
+    /// Cancellation isn't signed by the authority of the transaction
+    InvalidSignature,
 }
 
 /// Failure that can pop up when pushing transaction into the queue
@@ -111,6 +198,9 @@ impl Queue {
             capacity_per_user,
             transaction_time_to_live,
             future_threshold,
+            clock_skew_tolerance,
+            admission_checks,
+            authority_filter,
         }: Config,
         events_sender: EventsSender,
     ) -> Self {
@@ -124,31 +214,122 @@ impl Queue {
             time_source: TimeSource::new_system(),
             tx_time_to_live: transaction_time_to_live,
             future_threshold,
+            clock_skew_tolerance,
+            is_closed: AtomicBool::new(false),
+            authority_filter: RwLock::new(authority_filter),
+            admission: AdmissionPipeline::from_config(&admission_checks),
+            cancellations: SegQueue::new(),
+            tombstones: Mutex::new(Tombstones::default()),
+        }
+    }
+
+    /// Run `check` after the configured admission checks,
+    /// e.g. to enforce a deployment-specific policy.
+    #[must_use]
+    pub fn with_admission_check(mut self, check: impl AdmissionCheck + 'static) -> Self {
+        self.admission = self.admission.with_check(check);
+        self
+    }
+
+    /// Number of transactions admitted and rejected by every admission check
+    pub fn admission_metrics(&self) -> Vec<AdmissionCheckMetrics> {
+        self.admission.metrics()
+    }
+
+    /// Use `time_source` instead of the system clock to expire transactions,
+    /// e.g. to run the queue on virtual time in tests.
+    #[must_use]
+    pub fn with_time_source(mut self, time_source: TimeSource) -> Self {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Clock the queue expires transactions by. The rest of the peer reads the time
+    /// from it too, so that transactions and blocks agree on the current time.
+    pub fn time_source(&self) -> &TimeSource {
+        &self.time_source
+    }
+
+    /// How far the clocks of the peers may drift apart without rejecting each other's transactions.
+    ///
+    /// Transactions are expired and considered to be in the future this much later
+    /// than the local clock says. They never become eligible earlier though, as the block
+    /// they are put into is checked against its own creation time.
+    pub fn clock_skew_tolerance(&self) -> Duration {
+        self.clock_skew_tolerance
+    }
+
+    /// Replace the filter of transaction authorities.
+    ///
+    /// Transactions which are already in the queue are left untouched.
+    pub fn set_authority_filter(&self, authority_filter: AuthorityFilter) {
+        *self.authority_filter.write() = authority_filter;
+    }
+
+    /// Number of transactions which were not admitted because of the authority filter
+    pub fn denied_tx_count(&self) -> u64 {
+        self.admission
+            .rejected_by(admission::AuthorityFilterCheck.name())
+    }
+
+    /// Stop accepting new transactions.
+    ///
+    /// Transactions which are already in the queue are left untouched,
+    /// so that the consensus round in progress can still finish.
+    pub fn close(&self) {
+        if !self.is_closed.swap(true, Ordering::SeqCst) {
+            trace!(len = self.tx_len(), "Queue is closed");
         }
     }
 
+    /// Whether the queue has stopped accepting new transactions
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+
     fn is_pending(&self, tx: &AcceptedTransaction, state_view: &StateView) -> bool {
         !self.is_expired(tx) && !tx.is_in_blockchain(state_view)
     }
 
     /// Checks if the transaction is waiting longer than its TTL or than the TTL from [`Config`].
+    /// Scheduled transactions start waiting once they become eligible.
     pub fn is_expired(&self, tx: &AcceptedTransaction) -> bool {
-        let tx_creation_time = tx.as_ref().creation_time();
+        self.time_source.get_unix_time() > self.expires_at(tx)
+    }
+
+    /// Time after which the transaction is expired, see [`Self::is_expired`].
+    fn expires_at(&self, tx: &AcceptedTransaction) -> Duration {
+        let waiting_since = core::cmp::max(
+            tx.as_ref().creation_time(),
+            tx.as_ref().not_before().unwrap_or_default(),
+        );
 
         let time_limit = tx.as_ref().time_to_live().map_or_else(
             || self.tx_time_to_live,
             |tx_time_to_live| core::cmp::min(self.tx_time_to_live, tx_time_to_live),
         );
 
-        let curr_time = self.time_source.get_unix_time();
-        curr_time.saturating_sub(tx_creation_time) > time_limit
+        waiting_since
+            .saturating_add(time_limit)
+            .saturating_add(self.clock_skew_tolerance)
+    }
+
+    /// Checks if the transaction can already be included in a block, see [`SignedTransaction::not_before`].
+    ///
+    /// Unlike the expiry, this is checked by the local clock without the clock skew tolerance,
+    /// as it's also the clock the creation time of the block is taken from.
+    fn is_eligible(&self, tx: &AcceptedTransaction) -> bool {
+        tx.as_ref().is_eligible_at(self.time_source.get_unix_time())
     }
 
     /// If `true`, this transaction is regarded to have been tampered to have a future timestamp.
     fn is_in_future(&self, tx: &AcceptedTransaction) -> bool {
         let tx_timestamp = tx.as_ref().creation_time();
         let curr_time = self.time_source.get_unix_time();
-        tx_timestamp.saturating_sub(curr_time) > self.future_threshold
+        tx_timestamp.saturating_sub(curr_time)
+            > self
+                .future_threshold
+                .saturating_add(self.clock_skew_tolerance)
     }
 
     /// Returns all pending transactions.
@@ -165,6 +346,38 @@ impl Queue {
         })
     }
 
+    /// Returns the transactions of `authority` which are held in the queue
+    /// until the time they are scheduled for.
+    pub fn scheduled_transactions(&self, authority: &AccountId) -> Vec<AcceptedTransaction> {
+        self.accepted_txs
+            .iter()
+            .filter(|tx| {
+                tx.value().as_ref().authority() == authority && !self.is_eligible(tx.value())
+            })
+            .map(|tx| tx.value().clone())
+            .collect()
+    }
+
+    /// Returns the transaction with the given `hash` if it's in the queue.
+    pub fn get_transaction(&self, hash: &HashOf<SignedTransaction>) -> Option<AcceptedTransaction> {
+        self.accepted_txs.get(hash).map(|tx| tx.value().clone())
+    }
+
+    /// Whether the transaction with the given `hash` is in the queue.
+    pub fn contains(&self, hash: &HashOf<SignedTransaction>) -> bool {
+        self.accepted_txs.contains_key(hash)
+    }
+
+    /// Whether the transaction with the given `hash` was cancelled and mustn't be accepted again.
+    pub fn is_cancelled(&self, hash: &HashOf<SignedTransaction>) -> bool {
+        let now = self.time_source.get_unix_time();
+        self.tombstones
+            .lock()
+            .0
+            .get(hash)
+            .is_some_and(|tombstone| tombstone.unverified.is_none() && tombstone.expires_at >= now)
+    }
+
     /// Returns `n` randomly selected transaction from the queue.
     pub fn n_random_transactions(
         &self,
@@ -181,25 +394,22 @@ impl Queue {
             )
     }
 
-    fn check_tx(&self, tx: &AcceptedTransaction, state_view: &StateView) -> Result<(), Error> {
-        if self.is_in_future(tx) {
-            Err(Error::InFuture)
-        } else if self.is_expired(tx) {
-            Err(Error::Expired)
-        } else if tx.is_in_blockchain(state_view) {
-            Err(Error::InBlockchain)
-        } else {
-            Ok(())
-        }
-    }
-
     /// Push transaction into queue.
     ///
     /// # Errors
     /// See [`enum@Error`]
     pub fn push(&self, tx: AcceptedTransaction, state_view: &StateView) -> Result<(), Failure> {
         trace!(tx=%tx.as_ref().hash(), "Pushing to the queue");
-        if let Err(err) = self.check_tx(&tx, state_view) {
+        if self.is_closed() {
+            return Err(Failure {
+                tx,
+                err: Error::Closed,
+            });
+        }
+        if let Err(err) = self.check_not_cancelled(&tx) {
+            return Err(Failure { tx, err });
+        }
+        if let Err(err) = self.admission.run(&tx, self, state_view) {
             return Err(Failure { tx, err });
         }
 
@@ -231,6 +441,7 @@ impl Queue {
             return Err(Failure { tx, err });
         }
 
+        let metadata = tx.as_ref().metadata().clone();
         // Insert entry first so that the `tx` popped from `queue` will always have a `(hash, tx)` record in `txs`.
         entry.insert(tx);
         self.tx_hashes.push(hash).map_err(|err_hash| {
@@ -250,6 +461,7 @@ impl Queue {
                 hash,
                 block_height: None,
                 status: TransactionStatus::Queued,
+                metadata,
             }
             .into(),
         );
@@ -257,35 +469,144 @@ impl Queue {
         Ok(())
     }
 
-    /// Pop single transaction from the queue. Removes all transactions that fail the `tx_check`.
+    /// Remove the transaction `cancellation` refers to from the queue
+    /// and remember the cancellation so that it's gossiped to other peers.
+    ///
+    /// A transaction which is already being put into a block may still be committed.
+    ///
+    /// # Errors
+    /// See [`CancelError`]
+    pub fn cancel(&self, cancellation: SignedCancelTransaction) -> Result<(), CancelError> {
+        let hash = *cancellation.payload().hash();
+        let Entry::Occupied(entry) = self.accepted_txs.entry(hash) else {
+            return Err(CancelError::NotFound);
+        };
+        if cancellation
+            .verify(entry.get().as_ref().authority())
+            .is_err()
+        {
+            return Err(CancelError::InvalidSignature);
+        }
+        let (_, tx) = entry.remove_entry();
+        self.decrease_per_user_tx_count(tx.as_ref().authority());
+        self.tombstones.lock().insert(
+            hash,
+            Tombstone {
+                expires_at: self.expires_at(&tx),
+                unverified: None,
+            },
+            self.time_source.get_unix_time(),
+            self.capacity,
+        );
+        trace!(tx=%hash, "Transaction is cancelled");
+
+        self.cancellations.push(cancellation);
+        let _ = self.events_sender.send(
+            TransactionEvent {
+                hash,
+                block_height: None,
+                status: TransactionStatus::Cancelled,
+                metadata: tx.as_ref().metadata().clone(),
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    /// Remember `cancellation` of a transaction which isn't in the queue, so that the transaction
+    /// is cancelled as soon as it's pushed, provided the cancellation is signed by its authority.
+    ///
+    /// The cancellation is forgotten after the time to live of transactions.
+    pub fn cancel_on_arrival(&self, cancellation: SignedCancelTransaction) {
+        let hash = *cancellation.payload().hash();
+        let now = self.time_source.get_unix_time();
+        let mut tombstones = self.tombstones.lock();
+        if tombstones.0.contains_key(&hash) {
+            return;
+        }
+        tombstones.insert(
+            hash,
+            Tombstone {
+                expires_at: now
+                    .saturating_add(self.tx_time_to_live)
+                    .saturating_add(self.clock_skew_tolerance),
+                unverified: Some(cancellation),
+            },
+            now,
+            self.capacity,
+        );
+    }
+
+    /// Checks that the transaction wasn't cancelled, applying the cancellation
+    /// which arrived before the transaction, see [`Self::cancel_on_arrival`].
+    fn check_not_cancelled(&self, tx: &AcceptedTransaction) -> Result<(), Error> {
+        let hash = tx.as_ref().hash();
+        let mut tombstones = self.tombstones.lock();
+        let Some(tombstone) = tombstones.0.get_mut(&hash) else {
+            return Ok(());
+        };
+        if tombstone.expires_at < self.time_source.get_unix_time() {
+            tombstones.0.remove(&hash);
+            return Ok(());
+        }
+        let Some(cancellation) = tombstone.unverified.take() else {
+            return Err(Error::Cancelled);
+        };
+        if cancellation.verify(tx.as_ref().authority()).is_err() {
+            tombstones.0.remove(&hash);
+            return Ok(());
+        }
+        tombstone.expires_at = self.expires_at(tx);
+        drop(tombstones);
+
+        trace!(tx=%hash, "Transaction was cancelled before it arrived");
+        // Other peers may not have received the cancellation either
+        self.cancellations.push(cancellation);
+        Err(Error::Cancelled)
+    }
+
+    /// Take the cancellations made since the previous call, see [`Self::cancel`].
+    pub fn take_cancellations(&self) -> Vec<SignedCancelTransaction> {
+        core::iter::from_fn(|| self.cancellations.pop()).collect()
+    }
+
+    /// Pop single transaction from the queue. Removes expired and committed transactions.
+    /// Hashes of the transactions which aren't eligible yet are moved to `scheduled_transactions`.
     fn pop_from_queue(
         self: &Arc<Self>,
         state_view: &StateView,
         expired_transactions: &mut Vec<AcceptedTransaction>,
+        scheduled_transactions: &mut Vec<HashOf<SignedTransaction>>,
     ) -> Option<TransactionGuard> {
         loop {
             let hash = self.tx_hashes.pop()?;
 
             let entry = match self.accepted_txs.entry(hash) {
                 Entry::Occupied(entry) => entry,
-                // FIXME: Reachable under high load. Investigate, see if it's a problem.
+                // Cancelled transactions leave their hashes behind.
+                // FIXME: Also reachable under high load. Investigate, see if it's a problem.
                 // As practice shows this code is not `unreachable!()`.
                 // When transactions are submitted quickly it can be reached.
                 Entry::Vacant(_) => {
-                    warn!("Looks like we're experiencing a high load");
+                    trace!(tx=%hash, "Transaction is no longer in the queue");
                     continue;
                 }
             };
 
             let tx = entry.get();
-            if let Err(e) = self.check_tx(tx, state_view) {
+            let expired = self.is_expired(tx);
+            if expired || tx.is_in_blockchain(state_view) {
                 let (_, tx) = entry.remove_entry();
                 self.decrease_per_user_tx_count(tx.as_ref().authority());
-                if let Error::Expired = e {
+                if expired {
                     expired_transactions.push(tx);
                 }
                 continue;
             }
+            if !self.is_eligible(tx) {
+                scheduled_transactions.push(hash);
+                continue;
+            }
 
             let guard = TransactionGuard {
                 tx: tx.clone(),
@@ -300,6 +621,11 @@ impl Queue {
         self.accepted_txs.len()
     }
 
+    /// Return the maximum number of transactions in the queue.
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
     /// Gets transactions till they fill whole block or till the end of queue.
     ///
     /// BEWARE: Shouldn't be called in parallel with itself.
@@ -328,9 +654,15 @@ impl Queue {
         }
 
         let mut expired_transactions = Vec::new();
+        let mut scheduled_transactions = Vec::new();
 
-        let txs_from_queue =
-            core::iter::from_fn(|| self.pop_from_queue(state_view, &mut expired_transactions));
+        let txs_from_queue = core::iter::from_fn(|| {
+            self.pop_from_queue(
+                state_view,
+                &mut expired_transactions,
+                &mut scheduled_transactions,
+            )
+        });
 
         let transactions_hashes: IndexSet<HashOf<SignedTransaction>> =
             transactions.iter().map(|tx| tx.as_ref().hash()).collect();
@@ -339,12 +671,23 @@ impl Queue {
             .take(max_txs_in_block.get() - transactions.len());
         transactions.extend(txs);
 
+        // Scheduled transactions are held in the queue until they become eligible
+        for hash in scheduled_transactions {
+            if self.tx_hashes.push(hash).is_err() {
+                warn!("Queue is full");
+                if let Some((_, tx)) = self.accepted_txs.remove(&hash) {
+                    self.decrease_per_user_tx_count(tx.as_ref().authority());
+                }
+            }
+        }
+
         expired_transactions
             .into_iter()
             .map(|tx| TransactionEvent {
                 hash: tx.as_ref().hash(),
                 block_height: None,
                 status: TransactionStatus::Expired,
+                metadata: tx.as_ref().metadata().clone(),
             })
             .for_each(|e| {
                 let _ = self.events_sender.send(e.into());
@@ -369,6 +712,7 @@ impl Queue {
                     hash: tx.as_ref().hash(),
                     block_height: None,
                     status: TransactionStatus::Expired,
+                    metadata: tx.as_ref().metadata().clone(),
                 };
                 let _ = self.events_sender.send(event.into());
             }
@@ -417,7 +761,9 @@ impl Queue {
 pub mod tests {
     use std::{str::FromStr, sync::Arc, thread, time::Duration};
 
-    use iroha_data_model::{parameter::TransactionParameters, prelude::*};
+    use iroha_config::queue::AuthorityRule;
+    use iroha_data_model::{isi::Log, parameter::TransactionParameters, prelude::*, Level};
+    use iroha_genesis::GENESIS_DOMAIN_ID;
     use nonzero_ext::nonzero;
     use rand::Rng as _;
     use test_samples::gen_account_in;
@@ -425,10 +771,12 @@ pub mod tests {
 
     use super::*;
     use crate::{
+        block::{BlockBuilder, ValidBlock},
         kura::Kura,
         query::store::LiveQueryStore,
         smartcontracts::isi::Registrable as _,
         state::{State, World},
+        sumeragi::network_topology::Topology,
     };
 
     impl Queue {
@@ -443,6 +791,12 @@ pub mod tests {
                 time_source: time_source.clone(),
                 tx_time_to_live: cfg.transaction_time_to_live,
                 future_threshold: cfg.future_threshold,
+                clock_skew_tolerance: cfg.clock_skew_tolerance,
+                is_closed: AtomicBool::new(false),
+                authority_filter: RwLock::new(cfg.authority_filter),
+                admission: AdmissionPipeline::from_config(&cfg.admission_checks),
+                cancellations: SegQueue::new(),
+                tombstones: Mutex::new(Tombstones::default()),
             }
         }
     }
@@ -469,6 +823,7 @@ pub mod tests {
         let limits = TransactionParameters {
             max_instructions: nonzero!(4096_u64),
             smart_contract_size: nonzero!(1024_u64),
+            ..TransactionParameters::default()
         };
         AcceptedTransaction::accept(tx, &chain_id, limits).expect("Failed to accept Transaction.")
     }
@@ -541,6 +896,174 @@ pub mod tests {
         ));
     }
 
+    #[test]
+    async fn push_tx_after_close() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Queue::test(config_factory(), &time_source);
+        queue
+            .push(accepted_tx_by_someone(&time_source), &state_view)
+            .expect("Failed to push tx into queue");
+
+        queue.close();
+
+        assert!(matches!(
+            queue.push(accepted_tx_by_someone(&time_source), &state_view),
+            Err(Failure {
+                err: Error::Closed,
+                ..
+            })
+        ));
+        assert_eq!(queue.tx_len(), 1);
+    }
+
+    #[test]
+    async fn push_tx_denied_by_authority_filter() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let (bob_id, bob_keypair) = gen_account_in("wonderland");
+
+        let queue = Queue::test(
+            Config {
+                authority_filter: AuthorityFilter {
+                    allow: Vec::new(),
+                    deny: vec![AuthorityRule::Account(alice_id.clone())],
+                },
+                ..config_factory()
+            },
+            &time_source,
+        );
+
+        assert!(matches!(
+            queue.push(
+                accepted_tx_by(alice_id.clone(), &alice_keypair, &time_source),
+                &state_view
+            ),
+            Err(Failure {
+                err: Error::AuthorityDenied,
+                ..
+            })
+        ));
+        queue
+            .push(
+                accepted_tx_by(bob_id.clone(), &bob_keypair, &time_source),
+                &state_view,
+            )
+            .expect("Failed to push tx into queue");
+
+        queue.set_authority_filter(AuthorityFilter {
+            allow: vec![AuthorityRule::Account(alice_id.clone())],
+            deny: Vec::new(),
+        });
+
+        queue
+            .push(
+                accepted_tx_by(alice_id, &alice_keypair, &time_source),
+                &state_view,
+            )
+            .expect("Failed to push tx into queue");
+        assert!(matches!(
+            queue.push(
+                accepted_tx_by(bob_id, &bob_keypair, &time_source),
+                &state_view
+            ),
+            Err(Failure {
+                err: Error::AuthorityDenied,
+                ..
+            })
+        ));
+        assert_eq!(queue.tx_len(), 2);
+        assert_eq!(queue.denied_tx_count(), 2);
+    }
+
+    #[derive(Debug)]
+    struct DenyAccount(AccountId);
+
+    impl AdmissionCheck for DenyAccount {
+        fn name(&self) -> &'static str {
+            "deny_account"
+        }
+
+        fn check(
+            &self,
+            tx: &AcceptedTransaction,
+            _: &Queue,
+            _: &StateView<'_>,
+        ) -> Result<(), Error> {
+            if *tx.as_ref().authority() == self.0 {
+                Err(Error::Rejected(self.name()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    async fn push_tx_rejected_by_custom_admission_check() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let (bob_id, bob_keypair) = gen_account_in("wonderland");
+
+        let queue = Queue::test(
+            Config {
+                admission_checks: vec![
+                    iroha_config::queue::AdmissionCheck::Committed,
+                    iroha_config::queue::AdmissionCheck::Expiry,
+                ],
+                ..config_factory()
+            },
+            &time_source,
+        )
+        .with_admission_check(DenyAccount(alice_id.clone()));
+
+        assert!(matches!(
+            queue.push(
+                accepted_tx_by(alice_id, &alice_keypair, &time_source),
+                &state_view
+            ),
+            Err(Failure {
+                err: Error::Rejected("deny_account"),
+                ..
+            })
+        ));
+        queue
+            .push(
+                accepted_tx_by(bob_id, &bob_keypair, &time_source),
+                &state_view,
+            )
+            .expect("Failed to push tx into queue");
+
+        let metrics = queue.admission_metrics();
+        let counters = metrics
+            .iter()
+            .map(|m| (m.name, m.admitted, m.rejected))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counters,
+            [
+                ("committed", 2, 0),
+                ("expiry", 2, 0),
+                ("deny_account", 1, 1)
+            ]
+        );
+        assert_eq!(queue.denied_tx_count(), 0);
+    }
+
     #[test]
     async fn get_available_txs() {
         let max_txs_in_block = nonzero!(2_usize);
@@ -694,6 +1217,7 @@ pub mod tests {
         let limits = TransactionParameters {
             max_instructions: nonzero!(4096_u64),
             smart_contract_size: nonzero!(1024_u64),
+            ..TransactionParameters::default()
         };
         let tx_hash = tx.hash();
         let tx = AcceptedTransaction::accept(tx, &chain_id, limits)
@@ -709,6 +1233,7 @@ pub mod tests {
                 hash: tx_hash,
                 block_height: None,
                 status: TransactionStatus::Queued,
+                metadata: Metadata::default(),
             }
             .into()
         );
@@ -726,6 +1251,7 @@ pub mod tests {
                 hash: tx_hash,
                 block_height: None,
                 status: TransactionStatus::Expired,
+                metadata: Metadata::default(),
             }
             .into()
         )
@@ -842,6 +1368,298 @@ pub mod tests {
         assert_eq!(queue.accepted_txs.len(), 1);
     }
 
+    #[test]
+    async fn clock_skew_tolerance_extends_future_threshold_and_ttl() {
+        let future_threshold = Duration::from_secs(1);
+        let clock_skew_tolerance = Duration::from_secs(2);
+        let tx_time_to_live = Duration::from_secs(10);
+
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Queue::test(
+            Config {
+                future_threshold,
+                clock_skew_tolerance,
+                transaction_time_to_live: tx_time_to_live,
+                ..Config::default()
+            },
+            &time_source,
+        );
+
+        // Signed by a peer whose clock is ahead of ours, but within the tolerance
+        time_handle.advance(future_threshold * 2);
+        let tx = accepted_tx_by_someone(&time_source);
+        time_handle.rewind(future_threshold * 2);
+        assert!(queue.push(tx.clone(), &state_view).is_ok());
+
+        // Expired only after the time to live plus the tolerance
+        time_handle.advance(future_threshold * 2 + tx_time_to_live + clock_skew_tolerance);
+        assert!(!queue.is_expired(&tx));
+        time_handle.advance(Duration::from_millis(1));
+        assert!(queue.is_expired(&tx));
+
+        // Beyond the tolerance the transaction is still regarded to be in the future
+        time_handle.advance(future_threshold + clock_skew_tolerance * 2);
+        let tx = accepted_tx_by_someone(&time_source);
+        time_handle.rewind(future_threshold + clock_skew_tolerance * 2);
+        assert!(matches!(
+            queue.push(tx, &state_view),
+            Err(Failure {
+                err: Error::InFuture,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    async fn scheduled_tx_is_held_until_eligible() {
+        let max_txs_in_block = nonzero!(2_usize);
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        let not_before = time_source.get_unix_time() + Duration::from_secs(10);
+        let mut tx = TransactionBuilder::new_with_time_source(
+            chain_id.clone(),
+            alice_id.clone(),
+            &time_source,
+        )
+        .with_instructions([Unregister::domain("dummy".parse().unwrap())]);
+        tx.set_not_before(not_before);
+        let tx = tx.sign(alice_keypair.private_key());
+        let tx_hash = tx.hash();
+        let limits = TransactionParameters {
+            max_instructions: nonzero!(4096_u64),
+            smart_contract_size: nonzero!(1024_u64),
+            ..TransactionParameters::default()
+        };
+        let tx = AcceptedTransaction::accept(tx, &chain_id, limits)
+            .expect("Failed to accept Transaction.");
+        queue
+            .push(tx, &state_view)
+            .expect("Failed to push tx into queue");
+
+        assert!(queue
+            .collect_transactions_for_block(&state_view, max_txs_in_block)
+            .is_empty());
+        let scheduled = queue.scheduled_transactions(&alice_id);
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].as_ref().hash(), tx_hash);
+
+        time_handle.advance(Duration::from_secs(10));
+        let available = queue.collect_transactions_for_block(&state_view, max_txs_in_block);
+        assert_eq!(available.len(), 1);
+        assert!(queue.scheduled_transactions(&alice_id).is_empty());
+    }
+
+    #[test]
+    async fn block_is_valid_despite_clock_skew_tolerance() {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let clock_skew_tolerance = Duration::from_secs(2);
+        let genesis_key_pair = KeyPair::random();
+        let genesis_account = AccountId::new(
+            GENESIS_DOMAIN_ID.clone(),
+            genesis_key_pair.public_key().clone(),
+        );
+        let peer_id = PeerId::new(
+            "127.0.0.1:8080".parse().unwrap(),
+            genesis_key_pair.public_key().clone(),
+        );
+        let topology = Topology::new(vec![peer_id]);
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(World::with([], [], []), kura, query_handle);
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::from_secs(1_000));
+        let queue = Arc::new(Queue::test(
+            Config {
+                clock_skew_tolerance,
+                ..config_factory()
+            },
+            &time_source,
+        ));
+
+        // Scheduled within the tolerance
+        let mut tx = TransactionBuilder::new_with_time_source(
+            chain_id.clone(),
+            genesis_account.clone(),
+            &time_source,
+        )
+        .with_instructions([Log::new(Level::INFO, "scheduled".to_owned())]);
+        tx.set_not_before(time_source.get_unix_time() + clock_skew_tolerance / 2);
+        let tx = AcceptedTransaction::accept_genesis(
+            tx.sign(genesis_key_pair.private_key()),
+            &chain_id,
+            &genesis_account,
+        )
+        .expect("Valid");
+        queue
+            .push(tx, &state.view())
+            .expect("Failed to push tx into queue");
+
+        assert!(queue
+            .collect_transactions_for_block(&state.view(), nonzero!(2_usize))
+            .is_empty());
+        time_handle.advance(clock_skew_tolerance / 2);
+        let transactions = queue
+            .collect_transactions_for_block(&state.view(), nonzero!(2_usize))
+            .iter()
+            .map(|tx| (**tx).clone())
+            .collect::<Vec<_>>();
+        assert_eq!(transactions.len(), 1);
+
+        let block = BlockBuilder::new(transactions)
+            .with_time_source(time_source)
+            .chain(0, &mut state.block())
+            .sign(genesis_key_pair.private_key())
+            .unpack(|_| {});
+        let result = ValidBlock::validate(
+            block.into(),
+            &topology,
+            &chain_id,
+            &genesis_account,
+            &mut state.block(),
+        )
+        .unpack(|_| {});
+        assert!(result.is_ok(), "{:?}", result.err().map(|(_, error)| error));
+    }
+
+    #[test]
+    async fn only_authority_can_cancel_tx() {
+        let max_txs_in_block = nonzero!(2_usize);
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let mut queue = Queue::test(config_factory(), &time_source);
+        let (event_sender, mut event_receiver) = tokio::sync::broadcast::channel(2);
+        queue.events_sender = event_sender;
+        let queue = Arc::new(queue);
+
+        let tx = accepted_tx_by(alice_id, &alice_keypair, &time_source);
+        let tx_hash = tx.as_ref().hash();
+        queue
+            .push(tx, &state_view)
+            .expect("Failed to push tx into queue");
+        let _queued_tx_event = event_receiver.recv().await.unwrap();
+
+        let forged = CancelTransaction::new(tx_hash).sign(KeyPair::random().private_key());
+        assert!(matches!(
+            queue.cancel(forged),
+            Err(CancelError::InvalidSignature)
+        ));
+        assert_eq!(queue.tx_len(), 1);
+
+        let cancellation = CancelTransaction::new(tx_hash).sign(alice_keypair.private_key());
+        queue
+            .cancel(cancellation.clone())
+            .expect("Failed to cancel tx");
+        assert_eq!(
+            event_receiver.recv().await.unwrap(),
+            TransactionEvent {
+                hash: tx_hash,
+                block_height: None,
+                status: TransactionStatus::Cancelled,
+                metadata: Metadata::default(),
+            }
+            .into()
+        );
+        assert!(queue
+            .collect_transactions_for_block(&state_view, max_txs_in_block)
+            .is_empty());
+        assert_eq!(queue.take_cancellations(), vec![cancellation.clone()]);
+        assert!(matches!(
+            queue.cancel(cancellation),
+            Err(CancelError::NotFound)
+        ));
+    }
+
+    #[test]
+    async fn cancelled_tx_cannot_be_pushed_again() {
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        let tx = accepted_tx_by(alice_id, &alice_keypair, &time_source);
+        let tx_hash = tx.as_ref().hash();
+        queue
+            .push(tx.clone(), &state_view)
+            .expect("Failed to push tx into queue");
+        queue
+            .cancel(CancelTransaction::new(tx_hash).sign(alice_keypair.private_key()))
+            .expect("Failed to cancel tx");
+        assert!(queue.is_cancelled(&tx_hash));
+
+        assert!(matches!(
+            queue.push(tx.clone(), &state_view),
+            Err(Failure {
+                err: Error::Cancelled,
+                ..
+            })
+        ));
+        assert_eq!(queue.tx_len(), 0);
+
+        // Forgotten once the transaction would have expired anyway
+        time_handle.advance(config_factory().transaction_time_to_live + Duration::from_millis(1));
+        assert!(!queue.is_cancelled(&tx_hash));
+    }
+
+    #[test]
+    async fn cancellation_received_before_tx_is_applied_on_arrival() {
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let state_view = state.view();
+
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        let tx = accepted_tx_by(alice_id.clone(), &alice_keypair, &time_source);
+        let cancellation =
+            CancelTransaction::new(tx.as_ref().hash()).sign(alice_keypair.private_key());
+        queue.cancel_on_arrival(cancellation.clone());
+        // Not verified until the transaction arrives
+        assert!(!queue.is_cancelled(&tx.as_ref().hash()));
+
+        let other_tx = accepted_tx_by(alice_id, &alice_keypair, &time_source);
+        let forged =
+            CancelTransaction::new(other_tx.as_ref().hash()).sign(KeyPair::random().private_key());
+        queue.cancel_on_arrival(forged);
+
+        assert!(matches!(
+            queue.push(tx.clone(), &state_view),
+            Err(Failure {
+                err: Error::Cancelled,
+                ..
+            })
+        ));
+        assert!(queue.is_cancelled(&tx.as_ref().hash()));
+        assert_eq!(queue.take_cancellations(), vec![cancellation]);
+        queue
+            .push(other_tx, &state_view)
+            .expect("Forged cancellation is ignored");
+        assert_eq!(queue.tx_len(), 1);
+    }
+
     #[test]
     async fn queue_throttling() {
         let kura = Kura::blank_kura_for_testing();
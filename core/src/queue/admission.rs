@@ -0,0 +1,218 @@
+//! Checks a transaction has to pass before it's admitted into the [`Queue`].
+//!
+//! The checks run one after another in the configured order and the first rejection wins.
+//! Deployments can add their own checks with [`Queue::with_admission_check`].
+
+use core::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use iroha_config::queue::AdmissionCheck as Config;
+use iroha_logger::trace;
+
+use super::{Error, Queue};
+use crate::prelude::*;
+
+/// Single step of the queue admission pipeline
+pub trait AdmissionCheck: Debug + Send + Sync {
+    /// Name of the check used in logs and metrics
+    fn name(&self) -> &'static str;
+
+    /// Check whether `tx` can be admitted into `queue`.
+    ///
+    /// # Errors
+    /// If the transaction has to be rejected
+    fn check(
+        &self,
+        tx: &AcceptedTransaction,
+        queue: &Queue,
+        state_view: &StateView<'_>,
+    ) -> Result<(), Error>;
+}
+
+/// Rejects transactions whose authority isn't admitted by the peer's authority filter
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorityFilterCheck;
+
+impl AdmissionCheck for AuthorityFilterCheck {
+    fn name(&self) -> &'static str {
+        "authority_filter"
+    }
+
+    fn check(
+        &self,
+        tx: &AcceptedTransaction,
+        queue: &Queue,
+        _: &StateView<'_>,
+    ) -> Result<(), Error> {
+        if queue
+            .authority_filter
+            .read()
+            .admits(tx.as_ref().authority())
+        {
+            Ok(())
+        } else {
+            Err(Error::AuthorityDenied)
+        }
+    }
+}
+
+/// Rejects transactions created too far in the future
+#[derive(Debug, Clone, Copy)]
+pub struct FutureTimestampCheck;
+
+impl AdmissionCheck for FutureTimestampCheck {
+    fn name(&self) -> &'static str {
+        "future_timestamp"
+    }
+
+    fn check(
+        &self,
+        tx: &AcceptedTransaction,
+        queue: &Queue,
+        _: &StateView<'_>,
+    ) -> Result<(), Error> {
+        if queue.is_in_future(tx) {
+            Err(Error::InFuture)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Rejects transactions which have outlived their time to live
+#[derive(Debug, Clone, Copy)]
+pub struct ExpiryCheck;
+
+impl AdmissionCheck for ExpiryCheck {
+    fn name(&self) -> &'static str {
+        "expiry"
+    }
+
+    fn check(
+        &self,
+        tx: &AcceptedTransaction,
+        queue: &Queue,
+        _: &StateView<'_>,
+    ) -> Result<(), Error> {
+        if queue.is_expired(tx) {
+            Err(Error::Expired)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Rejects transactions which are already committed or rejected in a block
+#[derive(Debug, Clone, Copy)]
+pub struct CommittedCheck;
+
+impl AdmissionCheck for CommittedCheck {
+    fn name(&self) -> &'static str {
+        "committed"
+    }
+
+    fn check(
+        &self,
+        tx: &AcceptedTransaction,
+        _: &Queue,
+        state_view: &StateView<'_>,
+    ) -> Result<(), Error> {
+        if tx.is_in_blockchain(state_view) {
+            Err(Error::InBlockchain)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Number of transactions a check has admitted and rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionCheckMetrics {
+    /// See [`AdmissionCheck::name`]
+    pub name: &'static str,
+    /// Number of transactions which passed the check
+    pub admitted: u64,
+    /// Number of transactions which were rejected by the check
+    pub rejected: u64,
+}
+
+#[derive(Debug)]
+struct Stage {
+    check: Box<dyn AdmissionCheck>,
+    admitted: AtomicU64,
+    rejected: AtomicU64,
+}
+
+/// Ordered chain of [`AdmissionCheck`]s
+#[derive(Debug, Default)]
+pub struct AdmissionPipeline {
+    stages: Vec<Stage>,
+}
+
+impl AdmissionPipeline {
+    /// Build the pipeline of built-in checks in the configured order
+    pub fn from_config(checks: &[Config]) -> Self {
+        checks
+            .iter()
+            .fold(Self::default(), |pipeline, check| match check {
+                Config::AuthorityFilter => pipeline.with_check(AuthorityFilterCheck),
+                Config::FutureTimestamp => pipeline.with_check(FutureTimestampCheck),
+                Config::Expiry => pipeline.with_check(ExpiryCheck),
+                Config::Committed => pipeline.with_check(CommittedCheck),
+            })
+    }
+
+    /// Append `check` to the end of the pipeline
+    #[must_use]
+    pub fn with_check(mut self, check: impl AdmissionCheck + 'static) -> Self {
+        self.stages.push(Stage {
+            check: Box::new(check),
+            admitted: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        });
+        self
+    }
+
+    /// Run the checks in order, stopping at the first rejection.
+    ///
+    /// # Errors
+    /// Error of the check which rejected the transaction
+    pub fn run(
+        &self,
+        tx: &AcceptedTransaction,
+        queue: &Queue,
+        state_view: &StateView<'_>,
+    ) -> Result<(), Error> {
+        for stage in &self.stages {
+            if let Err(err) = stage.check.check(tx, queue, state_view) {
+                stage.rejected.fetch_add(1, Ordering::Relaxed);
+                trace!(tx=%tx.as_ref().hash(), check=stage.check.name(), %err, "Transaction isn't admitted");
+                return Err(err);
+            }
+            stage.admitted.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Number of transactions rejected by the checks called `name`
+    pub fn rejected_by(&self, name: &str) -> u64 {
+        self.stages
+            .iter()
+            .filter(|stage| stage.check.name() == name)
+            .map(|stage| stage.rejected.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Counters of every check in the pipeline order
+    pub fn metrics(&self) -> Vec<AdmissionCheckMetrics> {
+        self.stages
+            .iter()
+            .map(|stage| AdmissionCheckMetrics {
+                name: stage.check.name(),
+                admitted: stage.admitted.load(Ordering::Relaxed),
+                rejected: stage.rejected.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+//! Deterministic re-execution of the stored blocks.
+//!
+//! After changes in the execution semantics the chain is re-executed from genesis
+//! on top of an empty world state. Every stored block has to stay valid, i.e. its
+//! transactions must be accepted or rejected exactly as they were originally, and
+//! the resulting world state has to match the state snapshot, if there is one.
+
+use std::num::NonZeroUsize;
+
+use iroha_crypto::Hash;
+use iroha_data_model::{account::AccountId, peer::PeerId, ChainId};
+use tokio::sync::broadcast;
+
+use crate::{
+    block::BlockValidationError,
+    consistency::world_digest,
+    kura::{BlockCount, Kura},
+    state::{State, StateReadOnly},
+    sumeragi::{network_topology::Topology, SumeragiHandle},
+};
+
+/// Divergence of the re-executed chain from the stored one
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum ReplayError {
+    /// Block {0} is missing from the block storage
+    MissingBlock(NonZeroUsize),
+    /// Re-execution diverged at block {height}
+    Diverged {
+        /// Height of the first divergent block
+        height: NonZeroUsize,
+        /// Why the stored block is invalid when re-executed
+        #[source]
+        error: Box<BlockValidationError>,
+    },
+    /// World state at height {height} doesn't match the snapshot: expected digest {expected}, got {actual}
+    SnapshotMismatch {
+        /// Height of the snapshot
+        height: usize,
+        /// Digest of the world state in the snapshot
+        expected: Hash,
+        /// Digest of the re-executed world state
+        actual: Hash,
+    },
+}
+
+/// Outcome of a successful re-execution
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayReport {
+    /// Number of re-executed blocks
+    pub height: usize,
+    /// Digest of the world state after the last block
+    pub digest: Hash,
+}
+
+/// Re-execute all blocks stored in `kura` on top of the empty `state`.
+///
+/// If `snapshot` is given, the world state at the snapshot height is compared with it.
+///
+/// # Errors
+/// At the first block which is either missing or invalid when re-executed,
+/// or if the re-executed world state doesn't match the snapshot
+pub fn replay_verify(
+    state: &State,
+    kura: &Kura,
+    BlockCount(block_count): BlockCount,
+    chain_id: &ChainId,
+    genesis_account: &AccountId,
+    trusted_peers: impl IntoIterator<Item = PeerId>,
+    snapshot: Option<&State>,
+) -> Result<ReplayReport, ReplayError> {
+    // Nobody is interested in the events of the replayed blocks
+    let (events_sender, _) = broadcast::channel(1);
+    let mut topology = Topology::new(trusted_peers);
+    let snapshot = snapshot.map(|snapshot| {
+        let snapshot = snapshot.view();
+        (snapshot.height(), world_digest(snapshot.world()))
+    });
+
+    for height in (1..=block_count).filter_map(NonZeroUsize::new) {
+        let block = kura
+            .get_block_by_height(height)
+            .ok_or(ReplayError::MissingBlock(height))?;
+
+        let mut state_block = state.block();
+        SumeragiHandle::replay_block(
+            chain_id,
+            genesis_account,
+            &block,
+            &mut state_block,
+            &events_sender,
+            &mut topology,
+        )
+        .map_err(|error| ReplayError::Diverged {
+            height,
+            error: Box::new(error),
+        })?;
+        state_block.commit();
+
+        if let Some((snapshot_height, expected)) = snapshot {
+            if snapshot_height == height.get() {
+                let actual = world_digest(state.view().world());
+                if actual != expected {
+                    return Err(ReplayError::SnapshotMismatch {
+                        height: snapshot_height,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+        iroha_logger::debug!(height = height.get(), "Block re-executed");
+    }
+
+    Ok(ReplayReport {
+        height: block_count,
+        digest: world_digest(state.view().world()),
+    })
+}
@@ -114,12 +114,20 @@ pub mod isi {
                 }
             }
 
+            // Nothing is left of the asset
+            let balance = match asset.value {
+                AssetValue::Numeric(_) => AssetValue::Numeric(Numeric::ZERO),
+                AssetValue::Store(_) => AssetValue::Store(Metadata::default()),
+            };
+            let height = state_transaction.executing_height();
             state_transaction
                 .world
                 .emit_events(Some(AccountEvent::Asset(AssetEvent::Removed(
                     AssetChanged {
                         asset: asset.id,
                         amount: asset.value,
+                        balance,
+                        height,
                     },
                 ))));
 
@@ -408,6 +416,58 @@ pub mod isi {
         }
     }
 
+    impl Execute for Freeze {
+        #[metrics(+"freeze_account")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let account_id = self.account;
+
+            let account = state_transaction.world.account_mut(&account_id)?;
+            if account.frozen {
+                return Err(RepetitionError {
+                    instruction: InstructionType::Freeze,
+                    id: account_id.into(),
+                }
+                .into());
+            }
+            account.frozen = true;
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::Frozen(account_id)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for Unfreeze {
+        #[metrics(+"unfreeze_account")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let account_id = self.account;
+
+            let account = state_transaction.world.account_mut(&account_id)?;
+            if !account.frozen {
+                return Err(Error::InvariantViolation(format!(
+                    "Account `{account_id}` isn't frozen"
+                )));
+            }
+            account.frozen = false;
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::Unfrozen(account_id)));
+
+            Ok(())
+        }
+    }
+
     /// Assert that this asset can be registered to an account.
     fn assert_can_register(
         definition_id: &AssetDefinitionId,
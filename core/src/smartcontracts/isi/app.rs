@@ -0,0 +1,70 @@
+//! This module contains implementations of the instructions registering client [`App`]s.
+
+use iroha_data_model::{isi::error::RepetitionError, prelude::*, query::error::FindError};
+use iroha_telemetry::metrics;
+
+use super::prelude::*;
+
+/// ISI module contains all instructions related to apps:
+/// - registering an app bound to a key
+/// - unregistering an app by its owner
+pub mod isi {
+    use super::*;
+
+    impl Execute for RegisterApp {
+        #[metrics(+"register_app")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            state_transaction.world.check_name(&self.app.name)?;
+            if state_transaction.world.apps.get(&self.app).is_some() {
+                return Err(RepetitionError {
+                    instruction: InstructionType::RegisterApp,
+                    id: IdBox::AppId(self.app),
+                }
+                .into());
+            }
+
+            let app = App::new(self.app, authority.clone(), self.public_key);
+            state_transaction
+                .world
+                .apps
+                .insert(app.id().clone(), app.clone());
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::AppRegistered(app)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for UnregisterApp {
+        #[metrics(+"unregister_app")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let app = state_transaction.world.app(&self.app)?;
+            if app.owner() != authority {
+                return Err(Error::InvariantViolation(format!(
+                    "App `{}` can only be unregistered by its owner",
+                    self.app
+                )));
+            }
+
+            let app = state_transaction
+                .world
+                .apps
+                .remove(self.app.clone())
+                .ok_or(FindError::App(self.app))?;
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::AppUnregistered(app)));
+
+            Ok(())
+        }
+    }
+}
@@ -125,6 +125,45 @@ pub mod isi {
         }
     }
 
+    impl Execute for RemoveKeyRange {
+        #[metrics(+"remove_asset_key_range")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = self.asset;
+
+            assert_asset_type(
+                &asset_id.definition,
+                state_transaction,
+                expected_asset_type_store,
+            )?;
+
+            let removed = {
+                let asset = state_transaction.world.asset_mut(&asset_id)?;
+
+                let AssetValue::Store(store) = &mut asset.value else {
+                    return Err(Error::Conversion("Expected store asset type".to_owned()));
+                };
+
+                store.remove_range(&self.range)
+            };
+
+            state_transaction
+                .world
+                .emit_events(removed.into_iter().map(|(key, value)| {
+                    AssetEvent::MetadataRemoved(MetadataChanged {
+                        target: asset_id.clone(),
+                        key,
+                        value,
+                    })
+                }));
+
+            Ok(())
+        }
+    }
+
     impl Execute for Transfer<Asset, Metadata, Account> {
         #[metrics(+"transfer_store")]
         fn execute(
@@ -191,6 +230,7 @@ pub mod isi {
             *quantity = quantity
                 .checked_add(self.object)
                 .ok_or(MathError::Overflow)?;
+            let balance = asset.value.clone();
 
             #[allow(clippy::float_arithmetic)]
             {
@@ -203,11 +243,14 @@ pub mod isi {
                     .increase_asset_total_amount(&asset_id.definition, self.object)?;
             }
 
+            let height = state_transaction.executing_height();
             state_transaction
                 .world
                 .emit_events(Some(AssetEvent::Added(AssetChanged {
                     asset: asset_id,
                     amount: self.object.into(),
+                    balance,
+                    height,
                 })));
 
             Ok(())
@@ -240,6 +283,7 @@ pub mod isi {
             *quantity = quantity
                 .checked_sub(self.object)
                 .ok_or(MathError::NotEnoughQuantity)?;
+            let balance = asset.value.clone();
 
             if asset.value.is_zero_value() {
                 assert!(state_transaction
@@ -260,11 +304,14 @@ pub mod isi {
                     .decrease_asset_total_amount(&asset_id.definition, self.object)?;
             }
 
+            let height = state_transaction.executing_height();
             state_transaction
                 .world
                 .emit_events(Some(AssetEvent::Removed(AssetChanged {
                     asset: asset_id.clone(),
                     amount: self.object.into(),
+                    balance,
+                    height,
                 })));
 
             Ok(())
@@ -288,7 +335,7 @@ pub mod isi {
             )?;
             assert_numeric_spec(&self.object, &asset_definition)?;
 
-            {
+            let source_balance = {
                 let asset = state_transaction
                     .world
                     .assets
@@ -300,6 +347,7 @@ pub mod isi {
                 *quantity = quantity
                     .checked_sub(self.object)
                     .ok_or(MathError::NotEnoughQuantity)?;
+                let balance = asset.value.clone();
                 if asset.value.is_zero_value() {
                     assert!(state_transaction
                         .world
@@ -307,7 +355,8 @@ pub mod isi {
                         .remove(source_id.clone())
                         .is_some());
                 }
-            }
+                balance
+            };
 
             let destination_asset = state_transaction
                 .world
@@ -320,6 +369,7 @@ pub mod isi {
                     .checked_add(self.object)
                     .ok_or(MathError::Overflow)?;
             }
+            let destination_balance = destination_asset.value.clone();
 
             #[allow(clippy::float_arithmetic)]
             {
@@ -329,14 +379,19 @@ pub mod isi {
                     .push(self.object.to_f64());
             }
 
+            let height = state_transaction.executing_height();
             state_transaction.world.emit_events([
                 AssetEvent::Removed(AssetChanged {
                     asset: source_id,
                     amount: self.object.into(),
+                    balance: source_balance,
+                    height,
                 }),
                 AssetEvent::Added(AssetChanged {
                     asset: destination_id,
                     amount: self.object.into(),
+                    balance: destination_balance,
+                    height,
                 }),
             ]);
 
@@ -344,6 +399,88 @@ pub mod isi {
         }
     }
 
+    impl Execute for TransferAll {
+        #[metrics(+"transfer_all")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let source_id = AssetId::new(self.asset_definition, self.from);
+            state_transaction.world.account(&self.to)?;
+
+            match balance(&source_id, state_transaction)? {
+                Some(quantity) => Transfer::asset_numeric(source_id, quantity, self.to)
+                    .execute(authority, state_transaction),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl Execute for TransferMultiple {
+        #[metrics(+"transfer_multiple")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            // A failed movement fails the whole transaction, so none of the movements take effect
+            for AssetMovement {
+                asset_definition,
+                amount,
+                to,
+            } in self.movements
+            {
+                let source_id = AssetId::new(asset_definition, self.from.clone());
+                state_transaction.world.account(&to)?;
+                Transfer::asset_numeric(source_id, amount, to)
+                    .execute(authority, state_transaction)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    impl Execute for BurnAll {
+        #[metrics(+"burn_all")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = AssetId::new(self.asset_definition, self.from);
+
+            match balance(&asset_id, state_transaction)? {
+                Some(quantity) => {
+                    Burn::asset_numeric(quantity, asset_id).execute(authority, state_transaction)
+                }
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Current balance of the numeric asset, `None` if the account holds none of it
+    fn balance(
+        asset_id: &AssetId,
+        state_transaction: &StateTransaction<'_, '_>,
+    ) -> Result<Option<Numeric>, Error> {
+        assert_asset_type(
+            &asset_id.definition,
+            state_transaction,
+            expected_asset_type_numeric,
+        )?;
+        state_transaction.world.account(&asset_id.account)?;
+
+        match state_transaction.world.assets.get(asset_id) {
+            Some(Asset {
+                value: AssetValue::Numeric(quantity),
+                ..
+            }) => Ok(Some(*quantity)),
+            Some(_) => Err(Error::Conversion("Expected numeric asset type".to_owned())),
+            None => Ok(None),
+        }
+    }
+
     /// Assert that asset type is Numeric and that it satisfy asset definition spec
     pub(crate) fn assert_numeric_spec(
         object: &Numeric,
@@ -423,11 +560,13 @@ pub mod isi {
 pub mod query {
     use eyre::Result;
     use iroha_data_model::{
-        asset::{Asset, AssetDefinition, AssetValue},
+        asset::{Asset, AssetDefinition, AssetValue, StoreEntry},
         query::{
             error::QueryExecutionFail as Error,
             predicate::{
-                predicate_atoms::asset::{AssetDefinitionPredicateBox, AssetPredicateBox},
+                predicate_atoms::asset::{
+                    AssetDefinitionPredicateBox, AssetPredicateBox, StoreEntryPredicateBox,
+                },
                 CompoundPredicate,
             },
         },
@@ -466,6 +605,37 @@ pub mod query {
         }
     }
 
+    impl ValidQuery for FindStoreEntries {
+        #[metrics(+"find_store_entries")]
+        fn execute<'state>(
+            self,
+            filter: CompoundPredicate<StoreEntryPredicateBox>,
+            state_ro: &'state impl StateReadOnly,
+        ) -> Result<impl Iterator<Item = StoreEntry> + 'state, Error> {
+            let id = &self.id;
+            let asset = state_ro.world().asset(id).map_err(|asset_err| {
+                if let Err(definition_err) = state_ro.world().asset_definition(&id.definition) {
+                    Error::Find(definition_err)
+                } else {
+                    asset_err
+                }
+            })?;
+            iroha_logger::trace!(%id, range = %self.range);
+            let AssetValue::Store(store) = &asset.value else {
+                return Err(Error::Conversion("expected store, found other".to_owned()));
+            };
+
+            // Keys of the store are ordered, so the range is looked up without a full scan
+            let entries = store
+                .range(&self.range)
+                .map(|(key, value)| StoreEntry::new(key.clone(), value.clone()))
+                .filter(|entry| filter.applies(entry))
+                .collect::<Vec<_>>();
+
+            Ok(entries.into_iter())
+        }
+    }
+
     impl ValidSingularQuery for FindAssetQuantityById {
         #[metrics(+"find_asset_quantity_by_id")]
         fn execute(&self, state_ro: &impl StateReadOnly) -> Result<Numeric, Error> {
@@ -1,12 +1,22 @@
 //! This module contains trait implementations related to block queries
+use core::num::{NonZeroU64, NonZeroUsize};
+
 use eyre::Result;
 use iroha_data_model::{
-    block::BlockHeader,
+    block::{reward::BlockReward, state_diff::BlockStateDiff, BlockHeader},
+    events::{
+        pipeline::{BlockEvent, BlockStatus, TransactionEvent, TransactionStatus},
+        ArchivedEvent, EventFilter,
+    },
     query::{
-        block::FindBlockHeaderByHash,
+        block::{FindBlockHeaderByHash, FindBlockRewards, FindBlockStateDiff},
         error::{FindError, QueryExecutionFail},
+        event::{FindEvents, HeightRange},
         predicate::{
-            predicate_atoms::block::{BlockHeaderPredicateBox, SignedBlockPredicateBox},
+            predicate_atoms::{
+                block::{BlockHeaderPredicateBox, SignedBlockPredicateBox},
+                event::ArchivedEventPredicateBox,
+            },
             CompoundPredicate,
         },
     },
@@ -46,6 +56,85 @@ impl ValidQuery for FindBlockHeaders {
     }
 }
 
+impl ValidQuery for FindEvents {
+    #[metrics(+"find_events")]
+    fn execute<'state>(
+        self,
+        filter: CompoundPredicate<ArchivedEventPredicateBox>,
+        state_ro: &'state impl StateReadOnly,
+    ) -> Result<impl Iterator<Item = Self::Item> + 'state, QueryExecutionFail> {
+        let HeightRange { start, end } = self.height_range;
+        let event_filter = self.filter;
+
+        let end = end.get().min(state_ro.height() as u64);
+        let heights = (start.get()..=end)
+            .map(|height| NonZeroU64::new(height).expect("starts from non-zero height"));
+
+        if let Some(height) = heights
+            .clone()
+            .find(|height| archived_events(*height, state_ro).is_none())
+        {
+            return Err(FindError::EventArchive(height).into());
+        }
+
+        Ok(heights
+            .flat_map(move |height| block_events(height, state_ro))
+            .filter(move |archived| {
+                event_filter.matches(archived.event()) && filter.applies(archived)
+            }))
+    }
+}
+
+/// Events emitted while committing the block at `height` in the order
+/// they are received by the event subscribers.
+///
+/// Pipeline events are reproduced from the block stored in [`Kura`](crate::kura::Kura),
+/// while the rest are taken from the event archive.
+fn block_events(height: NonZeroU64, state_ro: &impl StateReadOnly) -> Vec<ArchivedEvent> {
+    let block = NonZeroUsize::try_from(height)
+        .ok()
+        .and_then(|height| state_ro.kura().get_block_by_height(height))
+        .expect("INTERNAL BUG: Archived block not found in kura");
+
+    let tx_events = block.transactions().map(|tx| {
+        let status = tx.error.as_ref().map_or_else(
+            || TransactionStatus::Approved,
+            |error| TransactionStatus::Rejected(error.clone().into()),
+        );
+
+        TransactionEvent {
+            block_height: Some(height),
+            hash: tx.as_ref().hash(),
+            status,
+            metadata: tx.as_ref().metadata().clone(),
+        }
+    });
+    let block_events = [BlockStatus::Approved, BlockStatus::Committed]
+        .into_iter()
+        .map(|status| BlockEvent {
+            header: block.header().clone(),
+            hash: block.hash(),
+            status,
+        });
+    let archived = archived_events(height, state_ro)
+        .expect("INTERNAL BUG: Checked to be archived")
+        .iter()
+        .cloned();
+
+    tx_events
+        .map(EventBox::from)
+        .chain(block_events.map(EventBox::from))
+        .chain(archived)
+        .map(|event| ArchivedEvent { height, event })
+        .collect()
+}
+
+fn archived_events(height: NonZeroU64, state_ro: &impl StateReadOnly) -> Option<&Vec<EventBox>> {
+    NonZeroUsize::try_from(height)
+        .ok()
+        .and_then(|height| state_ro.event_archive().get(&height))
+}
+
 impl ValidSingularQuery for FindBlockHeaderByHash {
     #[metrics(+"find_block_header")]
     fn execute(&self, state_ro: &impl StateReadOnly) -> Result<BlockHeader, QueryExecutionFail> {
@@ -59,3 +148,38 @@ impl ValidSingularQuery for FindBlockHeaderByHash {
         Ok(block.header().clone())
     }
 }
+
+impl ValidSingularQuery for FindBlockStateDiff {
+    #[metrics(+"find_block_state_diff")]
+    fn execute(&self, state_ro: &impl StateReadOnly) -> Result<BlockStateDiff, QueryExecutionFail> {
+        let height = self.height;
+
+        NonZeroUsize::try_from(height)
+            .ok()
+            .and_then(|height| state_ro.block_diffs().get(&height))
+            .cloned()
+            .ok_or_else(|| QueryExecutionFail::Find(FindError::BlockStateDiff(height)))
+    }
+}
+
+impl ValidSingularQuery for FindBlockRewards {
+    #[metrics(+"find_block_rewards")]
+    fn execute(
+        &self,
+        state_ro: &impl StateReadOnly,
+    ) -> Result<Vec<BlockReward>, QueryExecutionFail> {
+        let height = self.height;
+
+        let height = NonZeroUsize::try_from(height)
+            .ok()
+            .filter(|height| height.get() <= state_ro.height())
+            .ok_or_else(|| QueryExecutionFail::Find(FindError::BlockHeight(height)))?;
+
+        // Blocks which didn't pay out any fees have no rewards stored
+        Ok(state_ro
+            .block_rewards()
+            .get(&height)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
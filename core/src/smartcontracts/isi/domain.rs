@@ -103,6 +103,15 @@ pub mod isi {
 
             state_transaction.world.remove_account_roles(&account_id);
 
+            state_transaction.world.remove_account_apps(&account_id);
+
+            state_transaction.world.remove_account_proxies(&account_id);
+
+            state_transaction
+                .world
+                .recovery_policies
+                .remove(account_id.clone());
+
             let remove_assets: Vec<AssetId> = state_transaction
                 .world
                 .assets_in_account_iter(&account_id)
@@ -139,6 +148,9 @@ pub mod isi {
             let asset_definition = self.object.build(authority);
 
             let asset_definition_id = asset_definition.id().clone();
+            state_transaction
+                .world
+                .check_name(&asset_definition_id.name)?;
             if state_transaction
                 .world
                 .asset_definition(&asset_definition_id)
@@ -0,0 +1,191 @@
+//! This module contains implementations of the instructions locking assets in [`Escrow`]s.
+
+use iroha_data_model::{
+    isi::error::{EscrowError, MathError, RepetitionError},
+    prelude::*,
+    query::error::FindError,
+};
+use iroha_telemetry::metrics;
+
+use super::prelude::*;
+
+/// ISI module contains all instructions related to escrows:
+/// - locking assets
+/// - claiming locked assets by the recipient
+/// - refunding expired escrows to the owner
+pub mod isi {
+    use super::*;
+    use crate::smartcontracts::asset::isi::{
+        assert_asset_type, assert_numeric_spec, expected_asset_type_numeric,
+    };
+
+    impl Execute for LockEscrow {
+        #[metrics(+"lock_escrow")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let source_id = self.source;
+
+            state_transaction.world.check_name(&self.escrow.name)?;
+            if state_transaction.world.escrows.get(&self.escrow).is_some() {
+                return Err(RepetitionError {
+                    instruction: InstructionType::LockEscrow,
+                    id: IdBox::EscrowId(self.escrow),
+                }
+                .into());
+            }
+            state_transaction.world.account(&self.recipient)?;
+
+            let asset_definition = assert_asset_type(
+                &source_id.definition,
+                state_transaction,
+                expected_asset_type_numeric,
+            )?;
+            assert_numeric_spec(&self.amount, &asset_definition)?;
+
+            let balance = {
+                let asset = state_transaction
+                    .world
+                    .assets
+                    .get_mut(&source_id)
+                    .ok_or_else(|| FindError::Asset(source_id.clone()))?;
+                let AssetValue::Numeric(quantity) = &mut asset.value else {
+                    return Err(Error::Conversion("Expected numeric asset type".to_owned()));
+                };
+                *quantity = quantity
+                    .checked_sub(self.amount)
+                    .ok_or(MathError::NotEnoughQuantity)?;
+                let balance = asset.value.clone();
+                if asset.value.is_zero_value() {
+                    assert!(state_transaction
+                        .world
+                        .assets
+                        .remove(source_id.clone())
+                        .is_some());
+                }
+                balance
+            };
+
+            let escrow = Escrow {
+                id: self.escrow,
+                owner: source_id.account.clone(),
+                recipient: self.recipient,
+                asset_definition: source_id.definition.clone(),
+                amount: self.amount,
+                expires_at_ms: state_transaction.now_ms().saturating_add(self.timeout_ms),
+                hash_lock: self.hash_lock,
+            };
+            state_transaction
+                .world
+                .escrows
+                .insert(escrow.id().clone(), escrow.clone());
+
+            let height = state_transaction.executing_height();
+            state_transaction.world.emit_events([
+                DataEvent::from(AssetEvent::Removed(AssetChanged {
+                    asset: source_id,
+                    amount: self.amount.into(),
+                    balance,
+                    height,
+                })),
+                DataEvent::from(EscrowEvent::Locked(escrow)),
+            ]);
+
+            Ok(())
+        }
+    }
+
+    impl Execute for ClaimEscrow {
+        #[metrics(+"claim_escrow")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let escrow = state_transaction.world.escrow(&self.escrow)?;
+
+            if escrow.recipient() != authority {
+                return Err(EscrowError::NotRecipient.into());
+            }
+            if escrow.is_expired(state_transaction.now_ms()) {
+                return Err(EscrowError::Expired.into());
+            }
+            if !escrow.is_unlocked_by(self.preimage.as_deref()) {
+                return Err(EscrowError::InvalidPreimage.into());
+            }
+
+            let escrow = release(&self.escrow, escrow.recipient.clone(), state_transaction)?;
+            state_transaction
+                .world
+                .emit_events(Some(EscrowEvent::Claimed(escrow)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for RefundEscrow {
+        #[metrics(+"refund_escrow")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let escrow = state_transaction.world.escrow(&self.escrow)?;
+
+            if escrow.owner() != authority {
+                return Err(EscrowError::NotOwner.into());
+            }
+            if !escrow.is_expired(state_transaction.now_ms()) {
+                return Err(EscrowError::NotExpired.into());
+            }
+
+            let escrow = release(&self.escrow, escrow.owner.clone(), state_transaction)?;
+            state_transaction
+                .world
+                .emit_events(Some(EscrowEvent::Refunded(escrow)));
+
+            Ok(())
+        }
+    }
+
+    /// Remove the escrow and give the locked funds to the `destination` account
+    fn release(
+        escrow_id: &EscrowId,
+        destination: AccountId,
+        state_transaction: &mut StateTransaction<'_, '_>,
+    ) -> Result<Escrow, Error> {
+        let escrow = state_transaction
+            .world
+            .escrows
+            .remove(escrow_id.clone())
+            .ok_or_else(|| FindError::Escrow(escrow_id.clone()))?;
+        let destination_id = AssetId::new(escrow.asset_definition.clone(), destination);
+
+        let destination_asset = state_transaction
+            .world
+            .asset_or_insert(destination_id.clone(), Numeric::ZERO)?;
+        {
+            let AssetValue::Numeric(quantity) = &mut destination_asset.value else {
+                return Err(Error::Conversion("Expected numeric asset type".to_owned()));
+            };
+            *quantity = quantity
+                .checked_add(escrow.amount)
+                .ok_or(MathError::Overflow)?;
+        }
+        let balance = destination_asset.value.clone();
+
+        let height = state_transaction.executing_height();
+        state_transaction
+            .world
+            .emit_events(Some(AssetEvent::Added(AssetChanged {
+                asset: destination_id,
+                amount: escrow.amount.into(),
+                balance,
+                height,
+            })));
+
+        Ok(escrow)
+    }
+}
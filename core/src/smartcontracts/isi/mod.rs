@@ -2,10 +2,14 @@
 //! Instructions [`InstructionExpr`], generic instruction types and related
 //! implementations.
 pub mod account;
+pub mod app;
 pub mod asset;
 pub mod block;
 pub mod domain;
+pub mod escrow;
+pub mod proxy;
 pub mod query;
+pub mod recovery;
 pub mod triggers;
 pub mod tx;
 pub mod world;
@@ -42,7 +46,18 @@ impl Execute for InstructionBox {
     ) -> Result<(), Error> {
         iroha_logger::debug!(isi=%self, "Executing");
 
-        match self {
+        let profiling = state_transaction
+            .profile
+            .is_some()
+            .then(|| (InstructionType::from(&self), std::time::Instant::now()));
+        let tracing = state_transaction.trace.is_some().then(|| {
+            (
+                self.clone(),
+                state_transaction.world.transaction_events().len(),
+            )
+        });
+
+        let result = match self {
             Self::Register(isi) => isi.execute(authority, state_transaction),
             Self::Unregister(isi) => isi.execute(authority, state_transaction),
             Self::Mint(isi) => isi.execute(authority, state_transaction),
@@ -56,10 +71,51 @@ impl Execute for InstructionBox {
             Self::SetParameter(isi) => isi.execute(authority, state_transaction),
             Self::Upgrade(isi) => isi.execute(authority, state_transaction),
             Self::Log(isi) => isi.execute(authority, state_transaction),
+            Self::LockEscrow(isi) => isi.execute(authority, state_transaction),
+            Self::ClaimEscrow(isi) => isi.execute(authority, state_transaction),
+            Self::RefundEscrow(isi) => isi.execute(authority, state_transaction),
+            Self::TransferAll(isi) => isi.execute(authority, state_transaction),
+            Self::BurnAll(isi) => isi.execute(authority, state_transaction),
+            Self::Freeze(isi) => isi.execute(authority, state_transaction),
+            Self::Unfreeze(isi) => isi.execute(authority, state_transaction),
+            Self::DelegateToTrigger(isi) => isi.execute(authority, state_transaction),
+            Self::RevokeTriggerDelegation(isi) => isi.execute(authority, state_transaction),
+            Self::RemoveKeyRange(isi) => isi.execute(authority, state_transaction),
+            Self::RegisterPeerCertificate(isi) => isi.execute(authority, state_transaction),
+            Self::SetRecoveryPolicy(isi) => isi.execute(authority, state_transaction),
+            Self::InitiateRecovery(isi) => isi.execute(authority, state_transaction),
+            Self::VetoRecovery(isi) => isi.execute(authority, state_transaction),
+            Self::FinalizeRecovery(isi) => isi.execute(authority, state_transaction),
+            Self::TransferMultiple(isi) => isi.execute(authority, state_transaction),
+            Self::RegisterApp(isi) => isi.execute(authority, state_transaction),
+            Self::UnregisterApp(isi) => isi.execute(authority, state_transaction),
+            Self::GrantProxy(isi) => isi.execute(authority, state_transaction),
+            Self::RevokeProxy(isi) => isi.execute(authority, state_transaction),
+            // Only the executor which declared the custom instruction knows its semantics,
+            // so it must not be handed back to the host for execution
             Self::Custom(_) => {
-                panic!("Custom instructions should be handled in custom executor");
+                Err(error::InstructionEvaluationError::Unsupported(InstructionType::Custom).into())
+            }
+        };
+
+        if let (Some((kind, start)), Some(profile)) =
+            (profiling, state_transaction.profile.as_deref_mut())
+        {
+            profile.record(kind, start.elapsed());
+        }
+        if let Some((instruction, events_before)) = tracing {
+            let events = state_transaction.world.transaction_events()[events_before..].to_vec();
+            let error = result.as_ref().err().cloned();
+            if let Some(trace) = state_transaction.trace.as_mut() {
+                trace.push(InstructionTrace {
+                    instruction,
+                    events,
+                    error,
+                });
             }
         }
+
+        result
     }
 }
 
@@ -226,11 +282,24 @@ pub mod prelude {
 
 #[cfg(test)]
 mod tests {
-    use core::str::FromStr as _;
+    use core::{num::NonZeroU64, str::FromStr as _};
     use std::sync::Arc;
 
+    use iroha_crypto::{KeyPair, PrivateKey};
+    use iroha_data_model::{
+        isi::error::{EscrowError, InvalidParameterError, MathError, RecoveryError},
+        parameter::{BlockParameter, NameCharset, NameParameter, ProtocolUpgrade},
+        query::{
+            error::{FindError, QueryExecutionFail},
+            predicate::CompoundPredicate,
+        },
+    };
+    use iroha_primitives::json::JsonString;
+    use nonzero_ext::nonzero;
+    use parity_scale_codec::Encode as _;
     use test_samples::{
-        gen_account_in, ALICE_ID, SAMPLE_GENESIS_ACCOUNT_ID, SAMPLE_GENESIS_ACCOUNT_KEYPAIR,
+        gen_account_in, ALICE_ID, ALICE_KEYPAIR, SAMPLE_GENESIS_ACCOUNT_ID,
+        SAMPLE_GENESIS_ACCOUNT_KEYPAIR,
     };
     use tokio::test;
 
@@ -238,6 +307,7 @@ mod tests {
     use crate::{
         kura::Kura,
         query::store::LiveQueryStore,
+        smartcontracts::{ValidQuery, ValidSingularQuery},
         state::{State, World},
         tx::AcceptTransactionFail,
     };
@@ -281,6 +351,132 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn asset_store_key_range() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        let account_id = ALICE_ID.clone();
+        let asset_id = AssetId::new(
+            AssetDefinitionId::from_str("rose#wonderland")?,
+            account_id.clone(),
+        );
+        for key in ["order_3", "user_2", "order_1", "user_1", "order_2"] {
+            SetKeyValue::asset(asset_id.clone(), key.parse()?, key)
+                .execute(&account_id, &mut state_transaction)?;
+        }
+        let find_keys = |range: KeyRange, state_transaction: &StateTransaction<'_, '_>| {
+            let entries = ValidQuery::execute(
+                FindStoreEntries::new(asset_id.clone(), range),
+                CompoundPredicate::PASS,
+                state_transaction,
+            )?;
+            Ok::<_, QueryExecutionFail>(
+                entries
+                    .map(|entry| entry.key.to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            find_keys(KeyRange::prefix("order_"), &state_transaction)?,
+            ["order_1", "order_2", "order_3"]
+        );
+        assert_eq!(
+            find_keys(
+                KeyRange::all().with_start("order_3".parse()?),
+                &state_transaction
+            )?,
+            ["order_3", "user_1", "user_2"]
+        );
+
+        RemoveKeyRange::new(
+            asset_id.clone(),
+            KeyRange::prefix("order_").with_end("order_3".parse()?),
+        )
+        .execute(&account_id, &mut state_transaction)?;
+        assert_eq!(
+            find_keys(KeyRange::all(), &state_transaction)?,
+            ["order_3", "user_1", "user_2"]
+        );
+        // Nothing is left to remove
+        RemoveKeyRange::new(asset_id.clone(), KeyRange::prefix("order_1"))
+            .execute(&account_id, &mut state_transaction)?;
+        Ok(())
+    }
+
+    #[test]
+    async fn peer_certificate() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        let peer_key_pair = KeyPair::random();
+        let peer_id = PeerId::new(
+            "127.0.0.1:8080".parse()?,
+            peer_key_pair.public_key().clone(),
+        );
+        let issue = |peer: PeerId, private_key: &PrivateKey| {
+            let payload = PeerCertificatePayload {
+                peer,
+                operator: ALICE_ID.clone(),
+                metadata: Metadata::default(),
+            };
+            RegisterPeerCertificate::new(PeerCertificate::new(payload, private_key))
+        };
+        let is_rejected = |result: Result<(), Error>| {
+            matches!(
+                result,
+                Err(Error::InvalidParameter(
+                    InvalidParameterError::PeerCertificate(_)
+                ))
+            )
+        };
+
+        assert!(matches!(
+            issue(peer_id.clone(), peer_key_pair.private_key())
+                .execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Find(FindError::Peer(_)))
+        ));
+        Register::peer(Peer::new(peer_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        // Signed by someone else
+        assert!(is_rejected(
+            issue(peer_id.clone(), KeyPair::random().private_key())
+                .execute(&ALICE_ID, &mut state_transaction)
+        ));
+        // Registered by someone else than the operator
+        assert!(is_rejected(
+            issue(peer_id.clone(), peer_key_pair.private_key())
+                .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)
+        ));
+        // Issued for another address
+        let moved = PeerId::new("127.0.0.1:8081".parse()?, peer_id.public_key().clone());
+        assert!(is_rejected(
+            issue(moved, peer_key_pair.private_key()).execute(&ALICE_ID, &mut state_transaction)
+        ));
+
+        let register = issue(peer_id.clone(), peer_key_pair.private_key());
+        register
+            .clone()
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        let found = ValidSingularQuery::execute(
+            &FindPeerCertificate::new(peer_id.public_key().clone()),
+            &state_transaction,
+        )?;
+        assert_eq!(&found, register.certificate());
+
+        Unregister::peer(peer_id.clone())
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        assert!(state_transaction
+            .world
+            .peer_certificates()
+            .get(peer_id.public_key())
+            .is_none());
+        Ok(())
+    }
+
     #[test]
     async fn account_metadata() -> Result<()> {
         let kura = Kura::blank_kura_for_testing();
@@ -406,6 +602,416 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn trigger_details_are_listed_by_authority_domain() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        let account_id = ALICE_ID.clone();
+        let trigger_id = TriggerId::from_str("test_trigger_id")?;
+        let instructions: Vec<InstructionBox> =
+            vec![Log::new(iroha_data_model::Level::INFO, "hello".to_owned()).into()];
+
+        Register::trigger(Trigger::new(
+            trigger_id.clone(),
+            Action::new(
+                instructions.clone(),
+                Repeats::Exactly(3),
+                account_id.clone(),
+                ExecuteTriggerEventFilter::new()
+                    .for_trigger(trigger_id.clone())
+                    .under_authority(account_id.clone()),
+            ),
+        ))
+        .execute(&account_id, &mut state_transaction)?;
+
+        let details = ValidQuery::execute(
+            FindAllActiveTriggers,
+            CompoundPredicate::PASS,
+            &state_transaction,
+        )?
+        .collect::<Vec<_>>();
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].id(), &trigger_id);
+        assert_eq!(details[0].authority(), &account_id);
+        assert_eq!(details[0].repeats(), &Repeats::Exactly(3));
+        assert_eq!(
+            details[0].executable_hash(),
+            &iroha_crypto::Hash::new(instructions.encode())
+        );
+
+        let by_domain = ValidQuery::execute(
+            FindTriggersByDomain::new("wonderland".parse()?),
+            CompoundPredicate::PASS,
+            &state_transaction,
+        )?
+        .collect::<Vec<_>>();
+        assert_eq!(by_domain, details);
+
+        Register::domain(Domain::new("looking_glass".parse()?))
+            .execute(&account_id, &mut state_transaction)?;
+        assert_eq!(
+            ValidQuery::execute(
+                FindTriggersByDomain::new("looking_glass".parse()?),
+                CompoundPredicate::PASS,
+                &state_transaction,
+            )?
+            .count(),
+            0
+        );
+        assert!(matches!(
+            ValidQuery::execute(
+                FindTriggersByDomain::new("unknown".parse()?),
+                CompoundPredicate::PASS,
+                &state_transaction,
+            )
+            .map(Iterator::count),
+            Err(QueryExecutionFail::Find(FindError::Domain(_)))
+        ));
+
+        Ok(())
+    }
+
+    fn state_with_locked_escrow(
+        kura: &Arc<Kura>,
+        hash_lock: Option<iroha_crypto::Hash>,
+        timeout: core::time::Duration,
+    ) -> Result<(State, AccountId)> {
+        let state = state_with_test_domains(kura)?;
+        let (bob_id, _bob_keypair) = gen_account_in("wonderland");
+        let tulip_id = AssetDefinitionId::from_str("tulip#wonderland")?;
+        let mut state_block = state.block();
+        state_block.world.genesis_creation_time_ms = Some(0);
+        let mut state_transaction = state_block.transaction();
+        Register::account(Account::new(bob_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Register::asset_definition(AssetDefinition::numeric(tulip_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Mint::asset_numeric(100_u32, AssetId::new(tulip_id.clone(), ALICE_ID.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        let mut lock = LockEscrow::new(
+            EscrowId::from_str("swap")?,
+            AssetId::new(tulip_id, ALICE_ID.clone()),
+            numeric!(10),
+            bob_id.clone(),
+            timeout,
+        );
+        lock.hash_lock = hash_lock;
+        lock.execute(&ALICE_ID, &mut state_transaction)?;
+        state_transaction.apply();
+        state_block.commit();
+        Ok((state, bob_id))
+    }
+
+    fn tulips(state_transaction: &StateTransaction<'_, '_>, account_id: &AccountId) -> Numeric {
+        let asset_id = AssetId::new("tulip#wonderland".parse().unwrap(), account_id.clone());
+        state_transaction
+            .world
+            .assets()
+            .get(&asset_id)
+            .map_or(Numeric::ZERO, |asset| match asset.value {
+                AssetValue::Numeric(quantity) => quantity,
+                AssetValue::Store(_) => panic!("expected numeric asset"),
+            })
+    }
+
+    #[test]
+    async fn escrow_is_claimed_by_recipient_revealing_preimage() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let preimage = b"secret".to_vec();
+        let (state, bob_id) = state_with_locked_escrow(
+            &kura,
+            Some(iroha_crypto::Hash::new(&preimage)),
+            core::time::Duration::from_secs(3600),
+        )?;
+        let escrow_id = EscrowId::from_str("swap")?;
+        let mut state_block = state.block();
+        state_block.world.genesis_creation_time_ms = Some(0);
+        let mut state_transaction = state_block.transaction();
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), numeric!(90));
+
+        let cases = [
+            (
+                ALICE_ID.clone(),
+                Some(preimage.clone()),
+                EscrowError::NotRecipient,
+            ),
+            (bob_id.clone(), None, EscrowError::InvalidPreimage),
+            (
+                bob_id.clone(),
+                Some(b"guess".to_vec()),
+                EscrowError::InvalidPreimage,
+            ),
+        ];
+        for (authority, preimage, expected) in cases {
+            let claim = ClaimEscrow {
+                escrow: escrow_id.clone(),
+                preimage,
+            };
+            assert_eq!(
+                claim.execute(&authority, &mut state_transaction),
+                Err(Error::Escrow(expected))
+            );
+        }
+        assert_eq!(
+            RefundEscrow::new(escrow_id.clone()).execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Escrow(EscrowError::NotExpired))
+        );
+
+        ClaimEscrow::new(escrow_id.clone())
+            .with_preimage(preimage)
+            .execute(&bob_id, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &bob_id), numeric!(10));
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), numeric!(90));
+        assert!(state_transaction.world.escrows().get(&escrow_id).is_none());
+        Ok(())
+    }
+
+    #[test]
+    async fn expired_escrow_is_refunded_to_owner() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let (state, bob_id) = state_with_locked_escrow(&kura, None, core::time::Duration::ZERO)?;
+        let escrow_id = EscrowId::from_str("swap")?;
+        let mut state_block = state.block();
+        state_block.world.genesis_creation_time_ms = Some(0);
+        let mut state_transaction = state_block.transaction();
+
+        assert_eq!(
+            ClaimEscrow::new(escrow_id.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::Escrow(EscrowError::Expired))
+        );
+        assert_eq!(
+            RefundEscrow::new(escrow_id.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::Escrow(EscrowError::NotOwner))
+        );
+
+        RefundEscrow::new(escrow_id.clone()).execute(&ALICE_ID, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), numeric!(100));
+        assert_eq!(tulips(&state_transaction, &bob_id), Numeric::ZERO);
+        assert!(matches!(
+            RefundEscrow::new(escrow_id).execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Find(FindError::Escrow(_)))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    async fn account_is_recovered_by_guardian_after_challenge_window() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let (bob_id, _bob_keypair) = gen_account_in("wonderland");
+        let new_key_pair = KeyPair::random();
+        let recovered_id =
+            AccountId::new(ALICE_ID.domain.clone(), new_key_pair.public_key().clone());
+        let tulip_id = AssetDefinitionId::from_str("tulip#wonderland")?;
+        let mut state_block = state.block();
+        state_block.world.genesis_creation_time_ms = Some(0);
+        let mut state_transaction = state_block.transaction();
+        Register::account(Account::new(bob_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Register::asset_definition(AssetDefinition::numeric(tulip_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Mint::asset_numeric(10_u32, AssetId::new(tulip_id, ALICE_ID.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+
+        SetRecoveryPolicy::new(
+            ALICE_ID.clone(),
+            [bob_id.clone()],
+            core::time::Duration::from_secs(3600),
+        )
+        .execute(&ALICE_ID, &mut state_transaction)?;
+        let initiate = InitiateRecovery::new(ALICE_ID.clone(), new_key_pair.public_key().clone());
+        assert_eq!(
+            initiate.clone().execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Recovery(RecoveryError::NotGuardian))
+        );
+        initiate.clone().execute(&bob_id, &mut state_transaction)?;
+        assert_eq!(
+            FinalizeRecovery::new(ALICE_ID.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::Recovery(RecoveryError::ChallengeWindowOpen))
+        );
+        assert_eq!(
+            VetoRecovery::new(ALICE_ID.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::Recovery(RecoveryError::NotOwner))
+        );
+        VetoRecovery::new(ALICE_ID.clone()).execute(&ALICE_ID, &mut state_transaction)?;
+        assert_eq!(
+            FinalizeRecovery::new(ALICE_ID.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::Recovery(RecoveryError::NotInitiated))
+        );
+
+        SetRecoveryPolicy::new(
+            ALICE_ID.clone(),
+            [bob_id.clone()],
+            core::time::Duration::ZERO,
+        )
+        .execute(&ALICE_ID, &mut state_transaction)?;
+        initiate.execute(&bob_id, &mut state_transaction)?;
+        FinalizeRecovery::new(ALICE_ID.clone()).execute(&bob_id, &mut state_transaction)?;
+
+        assert!(state_transaction.world.account(&ALICE_ID).is_err());
+        assert!(state_transaction.world.account(&recovered_id).is_ok());
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), Numeric::ZERO);
+        assert_eq!(tulips(&state_transaction, &recovered_id), numeric!(10));
+        let policy = state_transaction
+            .world
+            .recovery_policies()
+            .get(&recovered_id)
+            .expect("policy should move with the account");
+        assert_eq!(policy.guardians(), &vec![bob_id]);
+        assert!(policy.pending().is_none());
+        Ok(())
+    }
+
+    #[test]
+    async fn transfer_all_and_burn_all_use_live_balance() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let (bob_id, _bob_keypair) = gen_account_in("wonderland");
+        let tulip_id = AssetDefinitionId::from_str("tulip#wonderland")?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        Register::account(Account::new(bob_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Register::asset_definition(AssetDefinition::numeric(tulip_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Mint::asset_numeric(100_u32, AssetId::new(tulip_id.clone(), ALICE_ID.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Mint::asset_numeric(20_u32, AssetId::new(tulip_id.clone(), bob_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+
+        TransferAll::new(tulip_id.clone(), ALICE_ID.clone(), bob_id.clone())
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), Numeric::ZERO);
+        assert_eq!(tulips(&state_transaction, &bob_id), numeric!(120));
+
+        // Nothing is left to transfer
+        TransferAll::new(tulip_id.clone(), ALICE_ID.clone(), bob_id.clone())
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &bob_id), numeric!(120));
+
+        BurnAll::new(tulip_id.clone(), bob_id.clone()).execute(&bob_id, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &bob_id), Numeric::ZERO);
+        assert_eq!(
+            state_transaction.world.asset_total_amount(&tulip_id)?,
+            Numeric::ZERO
+        );
+
+        let rose_id = AssetDefinitionId::from_str("rose#wonderland")?;
+        assert!(matches!(
+            BurnAll::new(rose_id, ALICE_ID.clone()).execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Evaluate(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    async fn transfer_multiple_executes_every_movement() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let (bob_id, _bob_keypair) = gen_account_in("wonderland");
+        let (carol_id, _carol_keypair) = gen_account_in("wonderland");
+        let tulip_id = AssetDefinitionId::from_str("tulip#wonderland")?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        for account_id in [&bob_id, &carol_id] {
+            Register::account(Account::new(account_id.clone()))
+                .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        }
+        Register::asset_definition(AssetDefinition::numeric(tulip_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        Mint::asset_numeric(100_u32, AssetId::new(tulip_id.clone(), ALICE_ID.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+
+        TransferMultiple::new(
+            ALICE_ID.clone(),
+            vec![
+                AssetMovement::new(tulip_id.clone(), numeric!(30), bob_id.clone()),
+                AssetMovement::new(tulip_id.clone(), numeric!(20), carol_id.clone()),
+                AssetMovement::new(tulip_id.clone(), numeric!(10), bob_id.clone()),
+            ],
+        )
+        .execute(&ALICE_ID, &mut state_transaction)?;
+        assert_eq!(tulips(&state_transaction, &ALICE_ID), numeric!(40));
+        assert_eq!(tulips(&state_transaction, &bob_id), numeric!(40));
+        assert_eq!(tulips(&state_transaction, &carol_id), numeric!(20));
+
+        assert_eq!(
+            TransferMultiple::new(
+                ALICE_ID.clone(),
+                vec![
+                    AssetMovement::new(tulip_id.clone(), numeric!(30), bob_id.clone()),
+                    AssetMovement::new(tulip_id, numeric!(30), carol_id),
+                ],
+            )
+            .execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::Math(MathError::NotEnoughQuantity))
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn custom_instruction_is_not_executed_by_host() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+
+        let custom = InstructionBox::from(CustomInstruction::new(JsonString::new("payload")));
+        assert!(matches!(
+            custom
+                .execute(&ALICE_ID, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Evaluate(error::InstructionEvaluationError::Unsupported(
+                InstructionType::Custom
+            ))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    async fn protocol_upgrade_is_only_scheduled_for_future_blocks() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        let schedule = |prior_version, version, activation_height| {
+            SetParameter(Parameter::Block(BlockParameter::ProtocolUpgrade(
+                ProtocolUpgrade::new(prior_version, version, activation_height),
+            )))
+        };
+        let is_rejected = |result: Result<(), Error>| {
+            matches!(
+                result,
+                Err(Error::InvalidParameter(
+                    InvalidParameterError::ProtocolUpgrade(_)
+                ))
+            )
+        };
+
+        // Setting the current value, as the genesis does, is a no-op
+        schedule(1, 1, NonZeroU64::MIN).execute(&ALICE_ID, &mut state_transaction)?;
+        // The block being executed is already created
+        assert!(is_rejected(
+            schedule(1, 2, NonZeroU64::MIN).execute(&ALICE_ID, &mut state_transaction)
+        ));
+        // Not from the current version
+        assert!(is_rejected(
+            schedule(2, 3, nonzero!(10_u64)).execute(&ALICE_ID, &mut state_transaction)
+        ));
+        // Not an upgrade
+        assert!(is_rejected(
+            schedule(1, 1, nonzero!(10_u64)).execute(&ALICE_ID, &mut state_transaction)
+        ));
+
+        schedule(1, 2, nonzero!(10_u64)).execute(&ALICE_ID, &mut state_transaction)?;
+        let protocol_upgrade = state_transaction.world.parameters.block.protocol_upgrade;
+        assert_eq!(protocol_upgrade.version_at(9), 1);
+        assert_eq!(protocol_upgrade.version_at(10), 2);
+        Ok(())
+    }
+
     #[test]
     async fn not_allowed_to_register_genesis_domain_or_account() -> Result<()> {
         let kura = Kura::blank_kura_for_testing();
@@ -429,6 +1035,408 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn registered_names_should_satisfy_name_parameters() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        let is_rejected = |result: Result<(), Error>| {
+            matches!(
+                result,
+                Err(Error::InvalidParameter(InvalidParameterError::Name(_)))
+            )
+        };
+
+        // Non-ASCII names are allowed by default
+        Register::domain(Domain::new(DomainId::from_str("страна_чудес")?))
+            .execute(&ALICE_ID, &mut state_transaction)?;
+
+        SetParameter::new(Parameter::Name(NameParameter::Charset(
+            NameCharset::AsciiAlphanumeric,
+        )))
+        .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        SetParameter::new(Parameter::Name(NameParameter::MaxLen(nonzero!(8_u64))))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+
+        assert!(is_rejected(
+            Register::domain(Domain::new(DomainId::from_str("zauberland")?))
+                .execute(&ALICE_ID, &mut state_transaction)
+        ));
+        assert!(is_rejected(
+            Register::domain(Domain::new(DomainId::from_str("wunder!")?))
+                .execute(&ALICE_ID, &mut state_transaction)
+        ));
+        assert!(is_rejected(
+            Register::asset_definition(AssetDefinition::numeric(AssetDefinitionId::from_str(
+                "tulip~#wonderland"
+            )?))
+            .execute(&ALICE_ID, &mut state_transaction)
+        ));
+        Register::domain(Domain::new(DomainId::from_str("wonder-1")?))
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        // Names registered before the rules were changed stay valid
+        state_transaction
+            .world
+            .domain(&DomainId::from_str("страна_чудес")?)?;
+        Ok(())
+    }
+
+    #[test]
+    async fn transaction_with_used_idempotency_key_should_be_rejected() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        state_block.world.genesis_creation_time_ms = Some(0);
+        let tx_executor = state_block.transaction_executor();
+        let key = Name::from_str("payment_1")?;
+
+        let accepted_tx = |key: &Name| {
+            let mut builder = TransactionBuilder::new(chain_id.clone(), ALICE_ID.clone())
+                .with_instructions::<InstructionBox>([]);
+            builder.set_idempotency_key(key.clone());
+            let tx = builder.sign(ALICE_KEYPAIR.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, tx_executor.limits)
+        };
+
+        let first = tx_executor
+            .validate(accepted_tx(&key)?, &mut state_block)
+            .map_err(|(_, reason)| eyre::eyre!(reason))?;
+        let record = state_block
+            .idempotency_keys
+            .get(&(ALICE_ID.clone(), key.clone()))
+            .copied()
+            .expect("Idempotency key must be recorded");
+        assert_eq!(*record.transaction(), first.hash());
+
+        assert!(matches!(
+            tx_executor.validate(accepted_tx(&key)?, &mut state_block),
+            Err((_, TransactionRejectionReason::DuplicateIdempotencyKey(used))) if used == key
+        ));
+        Ok(())
+    }
+
+    #[test]
+    async fn transaction_of_frozen_account_should_be_rejected() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let tx_executor = state_block.transaction_executor();
+
+        let accepted_tx = || {
+            let tx = TransactionBuilder::new(chain_id.clone(), ALICE_ID.clone())
+                .with_instructions::<InstructionBox>([])
+                .sign(ALICE_KEYPAIR.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, tx_executor.limits)
+        };
+
+        let mut state_transaction = state_block.transaction();
+        Freeze::new(ALICE_ID.clone())
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        assert!(matches!(
+            Freeze::new(ALICE_ID.clone())
+                .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction),
+            Err(Error::Repetition(_))
+        ));
+        state_transaction.apply();
+        assert!(matches!(
+            tx_executor.validate(accepted_tx()?, &mut state_block),
+            Err((_, TransactionRejectionReason::AccountFrozen(account))) if account == *ALICE_ID
+        ));
+
+        let mut state_transaction = state_block.transaction();
+        Unfreeze::new(ALICE_ID.clone())
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        state_transaction.apply();
+        tx_executor
+            .validate(accepted_tx()?, &mut state_block)
+            .map_err(|(_, reason)| eyre::eyre!(reason))?;
+        Ok(())
+    }
+
+    #[test]
+    async fn rejected_transaction_should_not_be_executed_again_on_same_state() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let mut state = state_with_test_domains(&kura)?;
+        state.set_validation_cache_capacity(16);
+        let tx_executor = state.view().transaction_executor();
+
+        let accepted_tx = |instructions: Vec<InstructionBox>| {
+            let tx = TransactionBuilder::new(chain_id.clone(), ALICE_ID.clone())
+                .with_instructions(instructions)
+                .sign(ALICE_KEYPAIR.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, tx_executor.limits)
+        };
+        let rejected_tx = accepted_tx(vec![
+            Unregister::domain(DomainId::from_str("nowhere")?).into()
+        ])?;
+
+        for _ in 0..2 {
+            let mut state_block = state.block();
+            assert!(tx_executor
+                .validate(rejected_tx.clone(), &mut state_block)
+                .is_err());
+        }
+        let stats = state.validation_cache.lock().stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+
+        // Another transaction applied before changes the state it is validated on
+        let mut state_block = state.block();
+        tx_executor
+            .validate(accepted_tx(Vec::new())?, &mut state_block)
+            .map_err(|(_, reason)| eyre::eyre!(reason))?;
+        assert!(tx_executor
+            .validate(rejected_tx.clone(), &mut state_block)
+            .is_err());
+        let stats = state.validation_cache.lock().stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+        drop(state_block);
+
+        // Changes made outside of validation disable the cache for the block
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        Freeze::new(ALICE_ID.clone())
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        state_transaction.apply();
+        assert!(matches!(
+            tx_executor.validate(rejected_tx, &mut state_block),
+            Err((_, TransactionRejectionReason::AccountFrozen(_)))
+        ));
+        let stats = state.validation_cache.lock().stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+        Ok(())
+    }
+
+    #[test]
+    async fn transaction_attributed_to_app_should_be_signed_with_its_key() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let tx_executor = state_block.transaction_executor();
+        let app_id = AppId::from_str("wallet")?;
+        let (bob_id, bob_keypair) = gen_account_in("wonderland");
+
+        let accepted_tx = |authority: &AccountId, keypair: &KeyPair, app: &str| {
+            let mut metadata = Metadata::default();
+            metadata.insert(APP_ID_METADATA_KEY.parse().unwrap(), app);
+            let tx = TransactionBuilder::new(chain_id.clone(), authority.clone())
+                .with_instructions::<InstructionBox>([])
+                .with_metadata(metadata)
+                .sign(keypair.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, tx_executor.limits)
+        };
+
+        assert!(matches!(
+            tx_executor.validate(accepted_tx(&ALICE_ID, &ALICE_KEYPAIR, "wallet")?, &mut state_block),
+            Err((_, TransactionRejectionReason::UnknownApp(app))) if app == app_id
+        ));
+        assert!(matches!(
+            tx_executor.validate(
+                accepted_tx(&ALICE_ID, &ALICE_KEYPAIR, "no app")?,
+                &mut state_block
+            ),
+            Err((_, TransactionRejectionReason::InvalidAppId(_)))
+        ));
+
+        let mut state_transaction = state_block.transaction();
+        Register::account(Account::new(bob_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        RegisterApp::new(app_id.clone(), ALICE_KEYPAIR.public_key().clone())
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        assert!(matches!(
+            RegisterApp::new(app_id.clone(), bob_keypair.public_key().clone())
+                .execute(&bob_id, &mut state_transaction),
+            Err(Error::Repetition(_))
+        ));
+        state_transaction.apply();
+
+        let attributed = tx_executor
+            .validate(
+                accepted_tx(&ALICE_ID, &ALICE_KEYPAIR, "wallet")?,
+                &mut state_block,
+            )
+            .map_err(|(_, reason)| eyre::eyre!(reason))?;
+        assert_eq!(attributed.app_id(), Some(Ok(app_id.clone())));
+        assert!(matches!(
+            tx_executor.validate(accepted_tx(&bob_id, &bob_keypair, "wallet")?, &mut state_block),
+            Err((_, TransactionRejectionReason::AppKeyMismatch(app))) if app == app_id
+        ));
+
+        let mut state_transaction = state_block.transaction();
+        assert!(matches!(
+            UnregisterApp::new(app_id.clone()).execute(&bob_id, &mut state_transaction),
+            Err(Error::InvariantViolation(_))
+        ));
+        UnregisterApp::new(app_id.clone()).execute(&ALICE_ID, &mut state_transaction)?;
+        assert!(state_transaction.world.app(&app_id).is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn proxy_submits_covered_instructions_on_behalf_of_authority() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let mut state_block = state.block();
+        let tx_executor = state_block.transaction_executor();
+        let (custodian_id, custodian_keypair) = gen_account_in("wonderland");
+
+        let proxy_tx = |instruction: InstructionBox| {
+            let mut builder = TransactionBuilder::new(chain_id.clone(), ALICE_ID.clone())
+                .with_instructions([instruction]);
+            builder.set_submitter(custodian_id.clone());
+            let tx = builder.sign(custodian_keypair.private_key());
+            AcceptedTransaction::accept(tx, &chain_id, tx_executor.limits)
+        };
+        let log =
+            || InstructionBox::from(Log::new(iroha_data_model::Level::INFO, "hello".to_owned()));
+        let set_key_value = || {
+            InstructionBox::from(SetKeyValue::account(
+                ALICE_ID.clone(),
+                "key".parse().unwrap(),
+                "value",
+            ))
+        };
+
+        let mut state_transaction = state_block.transaction();
+        Register::account(Account::new(custodian_id.clone()))
+            .execute(&SAMPLE_GENESIS_ACCOUNT_ID, &mut state_transaction)?;
+        state_transaction.apply();
+        assert!(matches!(
+            tx_executor.validate(proxy_tx(log())?, &mut state_block),
+            Err((_, TransactionRejectionReason::ProxyNotAuthorized(_)))
+        ));
+
+        let mut state_transaction = state_block.transaction();
+        assert!(matches!(
+            GrantProxy::new(ALICE_ID.clone(), vec![InstructionType::Log], None)
+                .execute(&ALICE_ID, &mut state_transaction),
+            Err(Error::InvariantViolation(_))
+        ));
+        GrantProxy::new(custodian_id.clone(), vec![InstructionType::Log], None)
+            .execute(&ALICE_ID, &mut state_transaction)?;
+        state_transaction.apply();
+
+        let proxied = tx_executor
+            .validate(proxy_tx(log())?, &mut state_block)
+            .map_err(|(_, reason)| eyre::eyre!(reason))?;
+        assert_eq!(proxied.authority(), &*ALICE_ID);
+        assert_eq!(proxied.signer(), &custodian_id);
+        assert!(matches!(
+            tx_executor.validate(proxy_tx(set_key_value())?, &mut state_block),
+            Err((_, TransactionRejectionReason::ProxyNotAuthorized(_)))
+        ));
+
+        let mut state_transaction = state_block.transaction();
+        assert!(matches!(
+            RevokeProxy::new(custodian_id.clone()).execute(&custodian_id, &mut state_transaction),
+            Err(Error::InvariantViolation(_))
+        ));
+        RevokeProxy::new(custodian_id.clone()).execute(&ALICE_ID, &mut state_transaction)?;
+        state_transaction.apply();
+        assert!(matches!(
+            tx_executor.validate(proxy_tx(log())?, &mut state_block),
+            Err((_, TransactionRejectionReason::ProxyNotAuthorized(_)))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    async fn simulated_transaction_reports_events_without_changing_state() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let domain_id = DomainId::from_str("looking_glass")?;
+        let tx_executor = state.block().transaction_executor();
+
+        let tx = TransactionBuilder::new(chain_id, ALICE_ID.clone())
+            .with_instructions([Register::domain(Domain::new(domain_id.clone()))])
+            .sign(ALICE_KEYPAIR.private_key());
+        let simulation = tx_executor.simulate(tx, &state, false);
+
+        assert_eq!(*simulation.rejection(), None);
+        assert!(simulation.events().iter().any(|event| matches!(
+            event,
+            EventBox::Data(DataEvent::Domain(DomainEvent::Created(domain)))
+                if *domain.id() == domain_id
+        )));
+        assert!(state.view().world().domain(&domain_id).is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn traced_simulation_records_executed_instructions() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let register = Register::domain(Domain::new(DomainId::from_str("looking_glass")?));
+        let tx_executor = state.block().transaction_executor();
+
+        let tx = TransactionBuilder::new(chain_id, ALICE_ID.clone())
+            .with_instructions([register.clone(), register.clone()])
+            .sign(ALICE_KEYPAIR.private_key());
+        assert_eq!(
+            *tx_executor.simulate(tx.clone(), &state, false).trace(),
+            None
+        );
+        let simulation = tx_executor.simulate(tx, &state, true);
+
+        let trace = simulation.trace().as_ref().expect("trace was requested");
+        assert_eq!(trace.len(), 2);
+        assert_eq!(*trace[0].instruction(), InstructionBox::from(register));
+        assert!(matches!(
+            trace[0].events().as_slice(),
+            [EventBox::Data(DataEvent::Domain(DomainEvent::Created(_)))]
+        ));
+        assert_eq!(*trace[0].error(), None);
+        assert!(trace[1].events().is_empty());
+        assert!(matches!(trace[1].error(), Some(Error::Repetition(_))));
+        Ok(())
+    }
+
+    #[test]
+    async fn asset_quantity_events_report_resulting_balance() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let asset_definition_id = AssetDefinitionId::from_str("tulip#wonderland")?;
+        let asset_id = AssetId::new(asset_definition_id.clone(), ALICE_ID.clone());
+        let tx_executor = state.block().transaction_executor();
+
+        let tx = TransactionBuilder::new(chain_id, ALICE_ID.clone())
+            .with_instructions::<InstructionBox>([
+                Register::asset_definition(AssetDefinition::numeric(asset_definition_id)).into(),
+                Mint::asset_numeric(10_u32, asset_id.clone()).into(),
+                Burn::asset_numeric(3_u32, asset_id).into(),
+            ])
+            .sign(ALICE_KEYPAIR.private_key());
+        let simulation = tx_executor.simulate(tx, &state, false);
+
+        assert_eq!(*simulation.rejection(), None);
+        let changes = simulation
+            .events()
+            .iter()
+            .filter_map(|event| match event {
+                EventBox::Data(DataEvent::Domain(DomainEvent::Account(AccountEvent::Asset(
+                    AssetEvent::Added(changed) | AssetEvent::Removed(changed),
+                )))) => Some((changed.balance.clone(), changed.height.get())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (AssetValue::from(10_u32), 1_u64),
+                (AssetValue::from(7_u32), 1_u64)
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     async fn transaction_signed_by_genesis_account_should_be_rejected() -> Result<()> {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
@@ -446,4 +1454,29 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[test]
+    async fn transaction_with_too_large_metadata_should_be_rejected() -> Result<()> {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let state_block = state.block();
+        let tx_limits = state_block.transaction_executor().limits;
+
+        let mut metadata = Metadata::default();
+        metadata.insert(
+            Name::from_str("memo")?,
+            "x".repeat(tx_limits.max_metadata_bytes.get().try_into()?)
+                .as_str(),
+        );
+        let tx = TransactionBuilder::new(chain_id.clone(), ALICE_ID.clone())
+            .with_instructions::<InstructionBox>([])
+            .with_metadata(metadata)
+            .sign(ALICE_KEYPAIR.private_key());
+        assert!(matches!(
+            AcceptedTransaction::accept(tx, &chain_id, tx_limits),
+            Err(AcceptTransactionFail::TransactionLimit(_))
+        ));
+        Ok(())
+    }
 }
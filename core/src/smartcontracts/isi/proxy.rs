@@ -0,0 +1,70 @@
+//! This module contains implementations of the instructions granting [`ProxyToken`]s.
+
+use iroha_data_model::prelude::*;
+use iroha_telemetry::metrics;
+
+use super::prelude::*;
+
+/// ISI module contains all instructions related to proxies:
+/// - granting a proxy token to an account
+/// - revoking a proxy token by the granting account
+pub mod isi {
+    use super::*;
+
+    impl Execute for GrantProxy {
+        #[metrics(+"grant_proxy")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            state_transaction.world.account(&self.proxy)?;
+            if self.proxy == *authority {
+                return Err(Error::InvariantViolation(String::from(
+                    "Account can't be a proxy of itself",
+                )));
+            }
+
+            let token = ProxyToken {
+                account: authority.clone(),
+                proxy: self.proxy,
+                instructions: self.instructions,
+                expires_at_ms: self.expires_at_ms,
+            };
+            state_transaction
+                .world
+                .proxies
+                .insert((token.account.clone(), token.proxy.clone()), token.clone());
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::ProxyGranted(token)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for RevokeProxy {
+        #[metrics(+"revoke_proxy")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let token = state_transaction
+                .world
+                .proxies
+                .remove((authority.clone(), self.proxy.clone()))
+                .ok_or_else(|| {
+                    Error::InvariantViolation(format!(
+                        "Account `{}` isn't a proxy of the authority",
+                        self.proxy
+                    ))
+                })?;
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::ProxyRevoked(token)));
+
+            Ok(())
+        }
+    }
+}
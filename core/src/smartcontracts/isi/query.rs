@@ -1,21 +1,24 @@
 //! Query functionality. The common error type is also defined here,
 //! alongside functions for converting them into HTTP responses.
-use std::cmp::Ordering;
+use std::{cell::Cell, cmp::Ordering, num::NonZeroU32};
 
 use eyre::Result;
 use iroha_data_model::{
     prelude::*,
     query::{
-        error::QueryExecutionFail as Error, parameters::QueryParams, QueryBox, QueryOutputBatchBox,
-        QueryRequest, QueryRequestWithAuthority, QueryResponse, SingularQueryBox,
-        SingularQueryOutputBox,
+        error::QueryExecutionFail as Error,
+        parameters::QueryParams,
+        predicate::{CompoundPredicate, EvaluatePredicate as _, HasPredicateBox},
+        QueryBox, QueryOutputBatchBox, QueryRequest, QueryRequestWithAuthority, QueryResponse,
+        QueryWithFilterFor, QueryWithParams, SingularQueryBox, SingularQueryOutputBox,
     },
 };
 
 use crate::{
     prelude::ValidSingularQuery,
     query::{
-        cursor::QueryBatchedErasedIterator, pagination::Paginate as _, store::LiveQueryStoreHandle,
+        cache::QueryCacheHandle, cursor::QueryBatchedErasedIterator, pagination::Paginate as _,
+        quota::BASE_COST, store::LiveQueryStoreHandle,
     },
     smartcontracts::{wasm, ValidQuery},
     state::{StateReadOnly, WorldReadOnly},
@@ -110,6 +113,24 @@ impl SortableQueryOutput for iroha_data_model::block::BlockHeader {
     }
 }
 
+impl SortableQueryOutput for iroha_data_model::events::ArchivedEvent {
+    fn get_metadata_sorting_key(&self, _key: &Name) -> Option<JsonString> {
+        None
+    }
+}
+
+impl SortableQueryOutput for iroha_data_model::asset::StoreEntry {
+    fn get_metadata_sorting_key(&self, _key: &Name) -> Option<JsonString> {
+        None
+    }
+}
+
+impl SortableQueryOutput for iroha_data_model::trigger::TriggerDetails {
+    fn get_metadata_sorting_key(&self, _key: &Name) -> Option<JsonString> {
+        None
+    }
+}
+
 /// Applies sorting and pagination to the query output and wraps it into a type-erasing batching iterator.
 ///
 /// # Errors
@@ -117,27 +138,48 @@ impl SortableQueryOutput for iroha_data_model::block::BlockHeader {
 /// Returns an error if the fetch size is too big
 pub fn apply_query_postprocessing<I>(
     iter: I,
-    &QueryParams {
-        pagination,
-        ref sorting,
-        fetch_size,
-    }: &QueryParams,
+    params: &QueryParams,
 ) -> Result<QueryBatchedErasedIterator, Error>
 where
     I: Iterator,
     I::Item: SortableQueryOutput + Send + Sync + 'static,
     QueryOutputBatchBox: From<Vec<I::Item>>,
 {
-    // validate the fetch (aka batch) size
-    let fetch_size = fetch_size
+    let fetch_size = validate_fetch_size(params)?;
+    let output = sort_and_paginate(iter, params);
+
+    Ok(QueryBatchedErasedIterator::new(
+        output.into_iter(),
+        fetch_size,
+    ))
+}
+
+/// Validate the fetch (aka batch) size
+fn validate_fetch_size(params: &QueryParams) -> Result<NonZeroU32, Error> {
+    let fetch_size = params
+        .fetch_size
         .fetch_size
         .unwrap_or(iroha_data_model::query::parameters::DEFAULT_FETCH_SIZE);
     if fetch_size > iroha_data_model::query::parameters::MAX_FETCH_SIZE {
         return Err(Error::FetchSizeTooBig);
     }
 
-    // sort & paginate, erase the iterator with QueryBatchedErasedIterator
-    let output = match &sorting.sort_by_metadata_key {
+    Ok(fetch_size)
+}
+
+fn sort_and_paginate<I>(
+    iter: I,
+    &QueryParams {
+        pagination,
+        ref sorting,
+        ..
+    }: &QueryParams,
+) -> Vec<I::Item>
+where
+    I: Iterator,
+    I::Item: SortableQueryOutput,
+{
+    match &sorting.sort_by_metadata_key {
         Some(key) => {
             // if sorting was requested, we need to retrieve all the results first
             let mut pairs: Vec<(Option<JsonString>, I::Item)> = iter
@@ -155,27 +197,78 @@ where
                 },
             );
 
-            QueryBatchedErasedIterator::new(
-                pairs.into_iter().map(|(_, val)| val).paginate(pagination),
-                fetch_size,
-            )
+            pairs
+                .into_iter()
+                .map(|(_, val)| val)
+                .paginate(pagination)
+                .collect()
         }
         // no sorting required, can just paginate the results without constructing the full output vec
         None => {
-            // FP: this collect is very deliberate
-            #[allow(clippy::needless_collect)]
-            let output = iter
-                .paginate(pagination)
+            iter.paginate(pagination)
                 // it should theoretically be possible to not collect the results into a vec and build the response lazily
                 // but:
                 // - the iterator is bound to the 'state lifetime and this lifetime should somehow be erased
                 // - for small queries this might not be efficient
                 // TODO: investigate this
-                .collect::<Vec<_>>();
+                .collect()
+        }
+    }
+}
 
-            QueryBatchedErasedIterator::new(output.into_iter(), fetch_size)
+/// Counts the entities visited by a query, stopping the query once it exceeds the limit
+#[derive(Debug)]
+struct CostMeter {
+    visited: Cell<u64>,
+    limit: Option<u64>,
+}
+
+impl CostMeter {
+    fn new(limit: Option<u64>) -> Self {
+        Self {
+            visited: Cell::new(0),
+            limit,
         }
-    };
+    }
+
+    /// Count the next entity, returns `false` if it exceeds the limit
+    fn visit(&self) -> bool {
+        let visited = self.visited.get() + 1;
+        self.visited.set(visited);
+        self.limit.map_or(true, |limit| visited <= limit)
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        match self.limit {
+            Some(limit) if self.visited.get() > limit => Err(Error::QueryCostExceeded),
+            _ => Ok(()),
+        }
+    }
+
+    fn cost(&self) -> u64 {
+        BASE_COST.saturating_add(self.visited.get())
+    }
+}
+
+/// Execute an iterable query, metering every entity it visits
+fn meter_iterable<Q>(
+    query: QueryWithFilterFor<Q>,
+    params: &QueryParams,
+    state: &impl StateReadOnly,
+    meter: &CostMeter,
+) -> Result<Vec<Q::Item>, Error>
+where
+    Q: ValidQuery,
+    Q::Item: HasPredicateBox + SortableQueryOutput,
+{
+    let predicate = query.predicate;
+    // The predicate is applied here rather than by the query,
+    // so that the entities it rejects are metered too
+    let visited = ValidQuery::execute(query.query, CompoundPredicate::PASS, state)?
+        .take_while(|_| meter.visit())
+        .filter(|item| predicate.applies(item));
+    let output = sort_and_paginate(visited, params);
+    meter.check()?;
 
     Ok(output)
 }
@@ -227,129 +320,187 @@ impl ValidQueryRequest {
     /// # Errors
     ///
     /// Returns an error if the query execution fails.
-    #[allow(clippy::too_many_lines)] // not much we can do, we _need_ to list all the box types here
     pub fn execute(
         self,
         live_query_store: &LiveQueryStoreHandle,
         state: &impl StateReadOnly,
         authority: &AccountId,
     ) -> Result<QueryResponse, Error> {
-        match self.0 {
+        self.execute_with_cache(None, live_query_store, state, authority)
+    }
+
+    /// Execute a validated query request, reusing the outputs of equal queries from `cache`.
+    ///
+    /// The cache must only be used if `state` is a view of the committed state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    pub fn execute_with_cache(
+        self,
+        cache: Option<&QueryCacheHandle>,
+        live_query_store: &LiveQueryStoreHandle,
+        state: &impl StateReadOnly,
+        authority: &AccountId,
+    ) -> Result<QueryResponse, Error> {
+        self.execute_metered(cache, live_query_store, state, authority, None)
+            .map(|(response, _cost)| response)
+    }
+
+    /// Execute a validated query request, as [`Self::execute_with_cache`] does, metering its cost.
+    ///
+    /// Returns the response along with the cost of the query, see [`quota`](crate::query::quota).
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`Error::QueryCostExceeded`] if the cost of the query exceeds `budget`
+    /// - Returns an error if the query execution fails.
+    pub fn execute_metered(
+        self,
+        cache: Option<&QueryCacheHandle>,
+        live_query_store: &LiveQueryStoreHandle,
+        state: &impl StateReadOnly,
+        authority: &AccountId,
+        budget: Option<u64>,
+    ) -> Result<(QueryResponse, u64), Error> {
+        let limit = budget
+            .map(|budget| {
+                budget
+                    .checked_sub(BASE_COST)
+                    .ok_or(Error::QueryCostExceeded)
+            })
+            .transpose()?;
+        let meter = CostMeter::new(limit);
+
+        let response = match self.0 {
             QueryRequest::Singular(singular_query) => {
-                let output = match singular_query {
-                    SingularQueryBox::FindAssetQuantityById(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindExecutorDataModel(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindParameters(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindTotalAssetQuantityByAssetDefinitionId(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindTriggerById(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindDomainMetadata(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindAccountMetadata(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindAssetMetadata(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindAssetDefinitionMetadata(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindTriggerMetadata(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindTransactionByHash(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindBlockHeaderByHash(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
+                let output = match cache {
+                    Some(cache) => {
+                        cache.singular(singular_query, state.latest_block_hash(), |q| {
+                            Self::execute_singular(q, state)
+                        })?
                     }
+                    None => Self::execute_singular(singular_query, state)?,
                 };
 
-                Ok(QueryResponse::Singular(output))
+                QueryResponse::Singular(output)
             }
             QueryRequest::Start(iter_query) => {
-                let output = match iter_query.query {
-                    // dispatch on a concrete query type, erasing the type with `QueryBatchedErasedIterator` in the end
-                    QueryBox::FindDomains(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAccounts(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAssets(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAssetsDefinitions(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRoles(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRoleIds(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindPermissionsByAccountId(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRolesByAccountId(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindTransactionsByAccountId(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAccountsWithAsset(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindPeers(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindActiveTriggerIds(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindTransactions(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindBlocks(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindBlockHeaders(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        &iter_query.params,
-                    )?,
+                let fetch_size = validate_fetch_size(&iter_query.params)?;
+                let output = match cache {
+                    // Outputs served from the cache cost only the base cost
+                    Some(cache) => cache.iterable(iter_query, state.latest_block_hash(), |q| {
+                        Self::execute_iterable(q, state, &meter)
+                    })?,
+                    None => Self::execute_iterable(iter_query, state, &meter)?,
                 };
 
-                Ok(QueryResponse::Iterable(
-                    live_query_store.handle_iter_start(output, authority)?,
-                ))
+                QueryResponse::Iterable(live_query_store.handle_iter_start(
+                    QueryBatchedErasedIterator::from_batch(output, fetch_size),
+                    authority,
+                )?)
             }
-            QueryRequest::Continue(cursor) => Ok(QueryResponse::Iterable(
-                live_query_store.handle_iter_continue(cursor)?,
-            )),
-        }
+            // The entities were visited and paid for when the query was started
+            QueryRequest::Continue(cursor) => {
+                QueryResponse::Iterable(live_query_store.handle_iter_continue(cursor)?)
+            }
+        };
+
+        Ok((response, meter.cost()))
+    }
+
+    fn execute_singular(
+        singular_query: SingularQueryBox,
+        state: &impl StateReadOnly,
+    ) -> Result<SingularQueryOutputBox, Error> {
+        let output = match singular_query {
+            SingularQueryBox::FindAssetQuantityById(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindExecutorDataModel(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindParameters(q) => SingularQueryOutputBox::from(q.execute(state)?),
+            SingularQueryBox::FindTotalAssetQuantityByAssetDefinitionId(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindTriggerById(q) => SingularQueryOutputBox::from(q.execute(state)?),
+            SingularQueryBox::FindDomainMetadata(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindAccountMetadata(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindAssetMetadata(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindAssetDefinitionMetadata(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindTriggerMetadata(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindTriggerReceipts(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindTransactionByHash(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindBlockHeaderByHash(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindBlockStateDiff(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindBlockRewards(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindIdempotencyRecord(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+            SingularQueryBox::FindPeerCertificate(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
+            }
+        };
+
+        Ok(output)
+    }
+
+    fn execute_iterable(
+        QueryWithParams { query, params }: QueryWithParams,
+        state: &impl StateReadOnly,
+        meter: &CostMeter,
+    ) -> Result<QueryOutputBatchBox, Error> {
+        let output: QueryOutputBatchBox = match query {
+            QueryBox::FindDomains(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindAccounts(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindAssets(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindAssetsDefinitions(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindStoreEntries(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindRoles(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindRoleIds(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindPermissionsByAccountId(q) => {
+                meter_iterable(q, &params, state, meter)?.into()
+            }
+            QueryBox::FindRolesByAccountId(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindTransactionsByAccountId(q) => {
+                meter_iterable(q, &params, state, meter)?.into()
+            }
+            QueryBox::FindTransactionsByAppId(q) => {
+                meter_iterable(q, &params, state, meter)?.into()
+            }
+            QueryBox::FindAccountsWithAsset(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindPeers(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindActiveTriggerIds(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindAllActiveTriggers(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindTriggersByDomain(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindTransactions(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindBlocks(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindBlockHeaders(q) => meter_iterable(q, &params, state, meter)?.into(),
+            QueryBox::FindEvents(q) => meter_iterable(q, &params, state, meter)?.into(),
+        };
+
+        Ok(output)
     }
 }
 
@@ -359,8 +510,9 @@ mod tests {
 
     use iroha_crypto::{Hash, HashOf, KeyPair};
     use iroha_data_model::{
+        events::pipeline::TransactionEventFilter,
         parameter::TransactionParameters,
-        query::{error::FindError, predicate::CompoundPredicate},
+        query::{error::FindError, predicate::CompoundPredicate, QueryWithFilter},
     };
     use iroha_primitives::json::JsonString;
     use nonzero_ext::nonzero;
@@ -427,16 +579,19 @@ mod tests {
 
         let kura = Kura::blank_kura_for_testing();
         let query_handle = LiveQueryStore::test().start();
-        let state = State::new(world_with_test_domains(), kura.clone(), query_handle);
+        let mut state = State::new(world_with_test_domains(), kura.clone(), query_handle);
+        state.set_archive_events(true);
         {
             let mut state_block = state.block();
             let limits = TransactionParameters {
                 max_instructions: nonzero!(1000_u64),
                 smart_contract_size: nonzero!(1024_u64),
+                ..TransactionParameters::default()
             };
             let huge_limits = TransactionParameters {
                 max_instructions: nonzero!(1000_u64),
                 smart_contract_size: nonzero!(1024_u64),
+                ..TransactionParameters::default()
             };
 
             state_block.world.parameters.transaction = limits;
@@ -547,6 +702,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn find_archived_events() -> Result<()> {
+        let state = state_with_test_blocks_and_transactions(3, 1, 1)?;
+        let query = FindEvents::new(
+            TransactionEventFilter::default().into(),
+            HeightRange {
+                start: nonzero!(2_u64),
+                end: nonzero!(10_u64),
+            },
+        );
+        let events =
+            ValidQuery::execute(query, CompoundPredicate::PASS, &state.view())?.collect::<Vec<_>>();
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event.height().get())
+                .collect::<Vec<_>>(),
+            [2, 2, 3, 3]
+        );
+        assert!(events.chunks(2).all(|events| matches!(
+            [events[0].event(), events[1].event()],
+            [
+                EventBox::Pipeline(PipelineEventBox::Transaction(approved)),
+                EventBox::Pipeline(PipelineEventBox::Transaction(rejected)),
+            ] if *approved.status() == TransactionStatus::Approved
+                && matches!(rejected.status(), TransactionStatus::Rejected(_))
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn find_events_of_not_archived_block() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(world_with_test_domains(), kura.clone(), query_handle);
+        {
+            let mut state_block = state.block();
+            let (peer_public_key, peer_private_key) = KeyPair::random().into_parts();
+            let peer_id = PeerId::new("127.0.0.1:8080".parse().unwrap(), peer_public_key);
+            let topology = Topology::new(vec![peer_id]);
+            let block = BlockBuilder::new(Vec::new())
+                .chain(0, &mut state_block)
+                .sign(&peer_private_key)
+                .unpack(|_| {})
+                .commit(&topology)
+                .unpack(|_| {})
+                .expect("Block is valid");
+
+            let _events = state_block
+                .apply(&block, topology.as_ref().to_owned())
+                .unwrap();
+            kura.store_block(block);
+            state_block.commit();
+        }
+
+        let query = FindEvents::new(
+            TransactionEventFilter::default().into(),
+            HeightRange {
+                start: nonzero!(1_u64),
+                end: nonzero!(1_u64),
+            },
+        );
+        let Err(error) = ValidQuery::execute(query, CompoundPredicate::PASS, &state.view()) else {
+            panic!("Events must not be archived");
+        };
+        assert!(matches!(
+            error,
+            Error::Find(FindError::EventArchive(height)) if height.get() == 1
+        ));
+    }
+
     #[test]
     async fn find_block_header_by_hash() -> Result<()> {
         let state = state_with_test_blocks_and_transactions(1, 1, 1)?;
@@ -672,4 +900,43 @@ mod tests {
         assert_eq!(JsonString::from(vec![1_u32, 2_u32, 3_u32,]), bytes,);
         Ok(())
     }
+
+    #[test]
+    async fn query_cost_is_metered() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let domain = Domain::new(DomainId::from_str("wonderland")?).build(&ALICE_ID);
+        let accounts = core::iter::once(ALICE_ID.clone())
+            .chain((0..4).map(|_| gen_account_in("wonderland").0))
+            .map(|id| Account::new(id).build(&ALICE_ID));
+        let world = World::with([domain], accounts, []);
+        let state = State::new(world, kura, query_handle.clone());
+        let state_view = state.view();
+        let execute = |budget| {
+            // Filtered out accounts are visited and metered too
+            let query = QueryWithFilter::new(FindAccounts, CompoundPredicate::FAIL);
+            let request = QueryRequest::Start(QueryWithParams::new(
+                QueryBox::FindAccounts(query),
+                QueryParams::default(),
+            ));
+            ValidQueryRequest(request).execute_metered(
+                None,
+                &query_handle,
+                &state_view,
+                &ALICE_ID,
+                budget,
+            )
+        };
+
+        let (_, cost) = execute(None)?;
+        assert_eq!(cost, BASE_COST + 5);
+        let (_, cost) = execute(Some(BASE_COST + 5))?;
+        assert_eq!(cost, BASE_COST + 5);
+        assert_eq!(
+            execute(Some(BASE_COST + 4)).unwrap_err(),
+            Error::QueryCostExceeded
+        );
+        assert_eq!(execute(Some(0)).unwrap_err(), Error::QueryCostExceeded);
+        Ok(())
+    }
 }
@@ -0,0 +1,353 @@
+//! This module contains implementations of the instructions recovering [`Account`]s
+//! according to their [`RecoveryPolicy`].
+
+use iroha_data_model::{
+    isi::error::{RecoveryError, RepetitionError},
+    prelude::*,
+    query::error::FindError,
+};
+use iroha_telemetry::metrics;
+
+use super::prelude::*;
+
+/// ISI module contains all instructions related to account recovery:
+/// - designating the guardians of an account
+/// - initiating the recovery by a guardian
+/// - vetoing the recovery by the account itself
+/// - finalizing the recovery once the challenge window has passed
+pub mod isi {
+    use super::*;
+    use crate::{role::RoleIdWithOwner, smartcontracts::triggers::set::Delegation};
+
+    impl Execute for SetRecoveryPolicy {
+        #[metrics(+"set_recovery_policy")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let account_id = self.account;
+
+            state_transaction.world.account(&account_id)?;
+            let mut guardians = self.guardians;
+            guardians.sort();
+            guardians.dedup();
+            for guardian in &guardians {
+                state_transaction.world.account(guardian)?;
+            }
+
+            let policy = RecoveryPolicy {
+                account: account_id.clone(),
+                guardians,
+                delay_ms: self.delay_ms,
+                pending: None,
+            };
+            if policy.guardians.is_empty() {
+                state_transaction.world.recovery_policies.remove(account_id);
+            } else {
+                state_transaction
+                    .world
+                    .recovery_policies
+                    .insert(account_id, policy.clone());
+            }
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::RecoveryPolicySet(policy)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for InitiateRecovery {
+        #[metrics(+"initiate_recovery")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let new_account_id = AccountId::new(self.account.domain.clone(), self.new_signatory);
+            if state_transaction.world.account(&new_account_id).is_ok() {
+                return Err(RepetitionError {
+                    instruction: InstructionType::InitiateRecovery,
+                    id: new_account_id.into(),
+                }
+                .into());
+            }
+
+            let now_ms = state_transaction.now_ms();
+            let policy = state_transaction
+                .world
+                .recovery_policies
+                .get_mut(&self.account)
+                .ok_or(RecoveryError::NoGuardians)?;
+            if !policy.is_guardian(authority) {
+                return Err(RecoveryError::NotGuardian.into());
+            }
+            if policy.pending.is_some() {
+                return Err(RecoveryError::AlreadyInitiated.into());
+            }
+            policy.pending = Some(PendingRecovery {
+                guardian: authority.clone(),
+                new_signatory: new_account_id.signatory,
+                finalizable_at_ms: now_ms.saturating_add(policy.delay_ms),
+            });
+            let policy = policy.clone();
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::RecoveryInitiated(policy)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for VetoRecovery {
+        #[metrics(+"veto_recovery")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let account_id = self.account;
+
+            if account_id != *authority {
+                return Err(RecoveryError::NotOwner.into());
+            }
+            state_transaction
+                .world
+                .recovery_policies
+                .get_mut(&account_id)
+                .and_then(|policy| policy.pending.take())
+                .ok_or(RecoveryError::NotInitiated)?;
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::RecoveryVetoed(account_id)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for FinalizeRecovery {
+        #[metrics(+"finalize_recovery")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let account_id = self.account;
+
+            let policy = state_transaction
+                .world
+                .recovery_policies
+                .get(&account_id)
+                .ok_or(RecoveryError::NotInitiated)?;
+            let pending = policy.pending.as_ref().ok_or(RecoveryError::NotInitiated)?;
+            if !policy.is_guardian(authority) {
+                return Err(RecoveryError::NotGuardian.into());
+            }
+            if !pending.is_finalizable(state_transaction.now_ms()) {
+                return Err(RecoveryError::ChallengeWindowOpen.into());
+            }
+
+            let new_account_id =
+                AccountId::new(account_id.domain.clone(), pending.new_signatory.clone());
+            if state_transaction.world.account(&new_account_id).is_ok() {
+                return Err(RepetitionError {
+                    instruction: InstructionType::FinalizeRecovery,
+                    id: new_account_id.into(),
+                }
+                .into());
+            }
+
+            move_account(&account_id, &new_account_id, state_transaction)?;
+
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::Recovered(AccountRecovered {
+                    account: account_id,
+                    new_account: new_account_id,
+                })));
+
+            Ok(())
+        }
+    }
+
+    /// Move the account together with everything it owns from `old` to `new` id.
+    ///
+    /// Permissions referring to the old id and peer certificates naming it as the operator
+    /// are left intact, as they can't be rewritten without the executor or the peer.
+    fn move_account(
+        old: &AccountId,
+        new: &AccountId,
+        state_transaction: &mut StateTransaction<'_, '_>,
+    ) -> Result<(), Error> {
+        let world = &mut state_transaction.world;
+
+        let mut account = world
+            .accounts
+            .remove(old.clone())
+            .ok_or_else(|| FindError::Account(old.clone()))?;
+        account.id = new.clone();
+        world.accounts.insert(new.clone(), account);
+
+        if let Some(mut policy) = world.recovery_policies.remove(old.clone()) {
+            policy.account = new.clone();
+            policy.pending = None;
+            world.recovery_policies.insert(new.clone(), policy);
+        }
+        let guarded: Vec<AccountId> = world
+            .recovery_policies
+            .iter()
+            .filter(|(_, policy)| policy.is_guardian(old))
+            .map(|(account_id, _)| account_id.clone())
+            .collect();
+        for account_id in guarded {
+            let policy = world
+                .recovery_policies
+                .get_mut(&account_id)
+                .expect("INTERNAL BUG: recovery policy disappeared");
+            for guardian in &mut policy.guardians {
+                if guardian == old {
+                    guardian.clone_from(new);
+                }
+            }
+            if let Some(pending) = &mut policy.pending {
+                if pending.guardian == *old {
+                    pending.guardian.clone_from(new);
+                }
+            }
+        }
+
+        let asset_ids: Vec<AssetId> = world
+            .assets_in_account_iter(old)
+            .map(|asset| asset.id().clone())
+            .collect();
+        for asset_id in asset_ids {
+            let mut asset = world
+                .assets
+                .remove(asset_id.clone())
+                .expect("INTERNAL BUG: asset disappeared");
+            asset.id = AssetId::new(asset_id.definition, new.clone());
+            world.assets.insert(asset.id.clone(), asset);
+        }
+
+        if let Some(permissions) = world.account_permissions.remove(old.clone()) {
+            world.account_permissions.insert(new.clone(), permissions);
+        }
+        let roles: Vec<RoleId> = world.account_roles_iter(old).cloned().collect();
+        world.remove_account_roles(old);
+        for role in roles {
+            world
+                .account_roles
+                .insert(RoleIdWithOwner::new(new.clone(), role), ());
+        }
+
+        let domains: Vec<DomainId> = world
+            .domains
+            .iter()
+            .filter(|(_, domain)| domain.owned_by == *old)
+            .map(|(domain_id, _)| domain_id.clone())
+            .collect();
+        for domain_id in domains {
+            world.domain_mut(&domain_id)?.owned_by = new.clone();
+        }
+        let asset_definitions: Vec<AssetDefinitionId> = world
+            .asset_definitions
+            .iter()
+            .filter(|(_, asset_definition)| asset_definition.owned_by == *old)
+            .map(|(asset_definition_id, _)| asset_definition_id.clone())
+            .collect();
+        for asset_definition_id in asset_definitions {
+            world.asset_definition_mut(&asset_definition_id)?.owned_by = new.clone();
+        }
+
+        let escrows: Vec<EscrowId> = world
+            .escrows
+            .iter()
+            .filter(|(_, escrow)| escrow.owner == *old || escrow.recipient == *old)
+            .map(|(escrow_id, _)| escrow_id.clone())
+            .collect();
+        for escrow_id in escrows {
+            let escrow = world
+                .escrows
+                .get_mut(&escrow_id)
+                .expect("INTERNAL BUG: escrow disappeared");
+            if escrow.owner == *old {
+                escrow.owner = new.clone();
+            }
+            if escrow.recipient == *old {
+                escrow.recipient = new.clone();
+            }
+        }
+
+        let apps: Vec<AppId> = world
+            .apps
+            .iter()
+            .filter(|(_, app)| app.owner == *old)
+            .map(|(app_id, _)| app_id.clone())
+            .collect();
+        for app_id in apps {
+            world
+                .apps
+                .get_mut(&app_id)
+                .expect("INTERNAL BUG: app disappeared")
+                .owner = new.clone();
+        }
+
+        let proxies: Vec<(AccountId, AccountId)> = world
+            .proxies
+            .iter()
+            .filter(|((account, proxy), _)| account == old || proxy == old)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in proxies {
+            let mut token = world
+                .proxies
+                .remove(key)
+                .expect("INTERNAL BUG: proxy token disappeared");
+            if token.account == *old {
+                token.account = new.clone();
+            }
+            if token.proxy == *old {
+                token.proxy = new.clone();
+            }
+            world
+                .proxies
+                .insert((token.account.clone(), token.proxy.clone()), token);
+        }
+
+        let triggers: Vec<TriggerId> = world
+            .triggers
+            .inspect_by_action(
+                |action| action.authority() == old,
+                |trigger_id, _| trigger_id.clone(),
+            )
+            .collect();
+        for trigger_id in triggers {
+            world
+                .triggers
+                .inspect_by_id_mut(&trigger_id, |action| action.set_authority(new.clone()))
+                .expect("INTERNAL BUG: trigger disappeared");
+        }
+        let delegations: Vec<(TriggerId, Delegation)> = world
+            .triggers
+            .delegations()
+            .iter()
+            .filter(|(_, delegation)| delegation.delegator == *old)
+            .map(|(trigger_id, delegation)| (trigger_id.clone(), delegation.clone()))
+            .collect();
+        for (trigger_id, delegation) in delegations {
+            world.triggers.delegate(
+                trigger_id,
+                Delegation {
+                    delegator: new.clone(),
+                    ..delegation
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
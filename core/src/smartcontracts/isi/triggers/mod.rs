@@ -30,6 +30,8 @@ pub mod isi {
         ) -> Result<(), Error> {
             let new_trigger = self.object;
 
+            state_transaction.world.check_name(&new_trigger.id.name)?;
+
             if !new_trigger.action.filter.mintable() {
                 match &new_trigger.action.repeats {
                     Repeats::Exactly(action) if *action == 1 => (),
@@ -39,6 +41,28 @@ pub mod isi {
                 }
             }
 
+            if let Executable::Wasm(wasm) = &new_trigger.action.executable {
+                let trigger_wasm_size_limit = state_transaction
+                    .world()
+                    .parameters()
+                    .transaction
+                    .trigger_wasm_size
+                    .get()
+                    .try_into()
+                    .expect("INTERNAL BUG: trigger wasm size exceeds usize::MAX");
+
+                if wasm.size_bytes() > trigger_wasm_size_limit {
+                    return Err(Error::InvalidParameter(InvalidParameterError::Wasm(
+                        format!(
+                            "WASM binary size is too large: max {}, got {} \
+                            (configured by \"TransactionParameter::TriggerWasmSize\")",
+                            trigger_wasm_size_limit,
+                            wasm.size_bytes()
+                        ),
+                    )));
+                }
+            }
+
             let last_block_estimation = state_transaction.latest_block().map(|block| {
                 block.header().creation_time() + block.header().consensus_estimation()
             });
@@ -317,26 +341,149 @@ pub mod isi {
             Ok(())
         }
     }
+
+    impl Execute for DelegateToTrigger {
+        #[metrics(+"delegate_to_trigger")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let trigger = self.trigger;
+
+            let triggers = &mut state_transaction.world.triggers;
+            let trigger_authority = triggers
+                .inspect_by_id(&trigger, |action| action.authority().clone())
+                .ok_or_else(|| FindError::Trigger(trigger.clone()))?;
+            if trigger_authority == *authority {
+                return Err(Error::InvariantViolation(String::from(
+                    "Trigger already executes with the authority of its account",
+                )));
+            }
+            if let Some(delegation) = triggers.delegations().get(&trigger) {
+                if delegation.delegator != *authority {
+                    return Err(Error::InvariantViolation(format!(
+                        "Trigger is already delegated authority by `{}`",
+                        delegation.delegator
+                    )));
+                }
+            }
+
+            triggers.delegate(
+                trigger.clone(),
+                super::set::Delegation {
+                    delegator: authority.clone(),
+                    instructions: self.instructions,
+                },
+            );
+
+            state_transaction
+                .world
+                .emit_events(Some(TriggerEvent::Delegated(TriggerDelegationChanged {
+                    trigger,
+                    delegator: authority.clone(),
+                })));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for RevokeTriggerDelegation {
+        #[metrics(+"revoke_trigger_delegation")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let trigger = self.trigger;
+
+            let triggers = &mut state_transaction.world.triggers;
+            if triggers.inspect_by_id(&trigger, |_| ()).is_none() {
+                return Err(FindError::Trigger(trigger).into());
+            }
+            match triggers.delegations().get(&trigger) {
+                Some(delegation) if delegation.delegator == *authority => {}
+                Some(_) => {
+                    return Err(Error::InvariantViolation(String::from(
+                        "Only the delegator can revoke the delegation",
+                    )))
+                }
+                None => {
+                    return Err(Error::InvariantViolation(String::from(
+                        "Trigger isn't delegated any authority",
+                    )))
+                }
+            }
+            triggers.revoke_delegation(trigger.clone());
+
+            state_transaction
+                .world
+                .emit_events(Some(TriggerEvent::DelegationRevoked(
+                    TriggerDelegationChanged {
+                        trigger,
+                        delegator: authority.clone(),
+                    },
+                )));
+
+            Ok(())
+        }
+    }
 }
 
 pub mod query {
     //! Queries associated to triggers.
+    use core::num::NonZeroUsize;
+
+    use iroha_crypto::Hash;
     use iroha_data_model::{
         query::{
             error::QueryExecutionFail as Error,
-            predicate::{predicate_atoms::trigger::TriggerIdPredicateBox, CompoundPredicate},
+            predicate::{
+                predicate_atoms::trigger::{TriggerDetailsPredicateBox, TriggerIdPredicateBox},
+                CompoundPredicate,
+            },
         },
-        trigger::{Trigger, TriggerId},
+        trigger::{Trigger, TriggerDetails, TriggerId},
     };
     use iroha_primitives::json::JsonString;
+    use parity_scale_codec::Encode as _;
 
     use super::*;
     use crate::{
         prelude::*,
-        smartcontracts::{triggers::set::SetReadOnly, ValidQuery},
+        smartcontracts::{
+            triggers::{
+                set::{ExecutableRef, SetReadOnly},
+                specialized::{LoadedAction, LoadedActionTrait},
+            },
+            ValidQuery,
+        },
         state::StateReadOnly,
     };
 
+    /// Details of the trigger, which don't include its executable and metadata
+    fn trigger_details(id: &TriggerId, action: &dyn LoadedActionTrait) -> TriggerDetails {
+        let LoadedAction {
+            executable,
+            repeats,
+            authority,
+            filter,
+            ..
+        } = action.clone_and_box();
+        let executable_hash = match executable {
+            ExecutableRef::Wasm(blob_hash) => blob_hash.into(),
+            ExecutableRef::Instructions(instructions) => Hash::new(instructions.encode()),
+        };
+
+        TriggerDetails {
+            id: id.clone(),
+            authority,
+            filter,
+            repeats,
+            executable_hash,
+        }
+    }
+
     impl ValidQuery for FindActiveTriggerIds {
         #[metrics(+"find_active_triggers")]
         fn execute<'state>(
@@ -353,6 +500,43 @@ pub mod query {
         }
     }
 
+    impl ValidQuery for FindAllActiveTriggers {
+        #[metrics(+"find_all_active_triggers")]
+        fn execute<'state>(
+            self,
+            filter: CompoundPredicate<TriggerDetailsPredicateBox>,
+            state_ro: &'state impl StateReadOnly,
+        ) -> Result<impl Iterator<Item = TriggerDetails> + 'state, Error> {
+            Ok(state_ro
+                .world()
+                .triggers()
+                .inspect_by_action(|_| true, trigger_details)
+                .filter(move |details| filter.applies(details)))
+        }
+    }
+
+    impl ValidQuery for FindTriggersByDomain {
+        #[metrics(+"find_triggers_by_domain")]
+        fn execute<'state>(
+            self,
+            filter: CompoundPredicate<TriggerDetailsPredicateBox>,
+            state_ro: &'state impl StateReadOnly,
+        ) -> Result<impl Iterator<Item = TriggerDetails> + 'state, Error> {
+            let domain = self.domain;
+            iroha_logger::trace!(%domain);
+            state_ro.world().domain(&domain)?;
+
+            Ok(state_ro
+                .world()
+                .triggers()
+                .inspect_by_action(
+                    move |action| *action.authority().domain() == domain,
+                    trigger_details,
+                )
+                .filter(move |details| filter.applies(details)))
+        }
+    }
+
     impl ValidSingularQuery for FindTriggerById {
         #[metrics(+"find_trigger_by_id")]
         fn execute(&self, state_ro: &impl StateReadOnly) -> Result<Trigger, Error> {
@@ -397,4 +581,24 @@ pub mod query {
                 .map(Into::into)
         }
     }
+
+    impl ValidSingularQuery for FindTriggerReceipts {
+        #[metrics(+"find_trigger_receipts")]
+        fn execute(&self, state_ro: &impl StateReadOnly) -> Result<Vec<TriggerReceipt>, Error> {
+            let height = self.height;
+            iroha_logger::trace!(%height);
+
+            let height = NonZeroUsize::try_from(height)
+                .ok()
+                .filter(|height| height.get() <= state_ro.height())
+                .ok_or_else(|| Error::Find(FindError::BlockHeight(height)))?;
+
+            // Blocks which didn't execute any trigger have no receipts stored
+            Ok(state_ro
+                .trigger_receipts()
+                .get(&height)
+                .cloned()
+                .unwrap_or_default())
+        }
+    }
 }
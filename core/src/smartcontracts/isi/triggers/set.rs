@@ -9,7 +9,7 @@
 //! trigger hooks.
 
 use core::cmp::min;
-use std::{fmt, marker::PhantomData, num::NonZeroU64};
+use std::{fmt, num::NonZeroU64};
 
 use iroha_crypto::HashOf;
 use iroha_data_model::{
@@ -19,6 +19,7 @@ use iroha_data_model::{
     query::error::FindError,
     transaction::WasmSmartContract,
 };
+use parity_scale_codec::{Decode, Encode};
 use serde::{
     de::{DeserializeSeed, MapAccess, Visitor},
     Deserialize, Serialize,
@@ -39,7 +40,7 @@ use crate::{
         },
         wasm,
     },
-    state::deserialize::WasmSeed,
+    state::{deserialize::WasmSeed, memory::CollectionUsage},
 };
 
 /// Error type for [`Set`] operations.
@@ -80,11 +81,14 @@ pub struct Set {
     /// [`WasmSmartContract`]s map by wasm blob hash.
     /// This map serves multiple purposes:
     /// 1. Querying original wasm blob of trigger
-    /// 2. Getting compiled by wasmtime module for execution
+    /// 2. Getting wasm blob to compile for execution
     /// 3. Deduplicating triggers with the same wasm blob
     contracts: WasmSmartContractMap,
+    /// Authority delegated to triggers by accounts other than the trigger authority
+    delegations: Storage<TriggerId, Delegation>,
     /// List of actions that should be triggered by events provided by `handle_*` methods.
-    /// Vector is used to save the exact triggers order.
+    /// Vector is used to save the exact triggers order: events in the order they were handled
+    /// and, since trigger maps are ordered by id, triggers of the same event by ascending id.
     // NOTE: Cell is used because matched_ids changed as whole (not granularly)
     matched_ids: Cell<Vec<(EventBox, TriggerId)>>,
 }
@@ -103,6 +107,8 @@ pub struct SetBlock<'set> {
     ids: StorageBlock<'set, TriggerId, TriggeringEventType>,
     /// Original [`WasmSmartContract`]s by [`TriggerId`] for querying purposes.
     contracts: WasmSmartContractMapBlock<'set>,
+    /// Authority delegated to triggers by accounts other than the trigger authority
+    delegations: StorageBlock<'set, TriggerId, Delegation>,
     /// List of actions that should be triggered by events provided by `handle_*` methods.
     /// Vector is used to save the exact triggers order.
     matched_ids: CellBlock<'set, Vec<(EventBox, TriggerId)>>,
@@ -124,6 +130,8 @@ pub struct SetTransaction<'block, 'set> {
     ids: StorageTransaction<'block, 'set, TriggerId, TriggeringEventType>,
     /// Original [`WasmSmartContract`]s by [`TriggerId`] for querying purposes.
     contracts: WasmSmartContractMapTransaction<'block, 'set>,
+    /// Authority delegated to triggers by accounts other than the trigger authority
+    delegations: StorageTransaction<'block, 'set, TriggerId, Delegation>,
     /// List of actions that should be triggered by events provided by `handle_*` methods.
     /// Vector is used to save the exact triggers order.
     matched_ids: CellTransaction<'block, 'set, Vec<(EventBox, TriggerId)>>,
@@ -143,23 +151,43 @@ pub struct SetView<'set> {
     ids: StorageView<'set, TriggerId, TriggeringEventType>,
     /// Original [`WasmSmartContract`]s by [`TriggerId`] for querying purposes.
     contracts: WasmSmartContractMapView<'set>,
+    /// Authority delegated to triggers by accounts other than the trigger authority
+    delegations: StorageView<'set, TriggerId, Delegation>,
     /// List of actions that should be triggered by events provided by `handle_*` methods.
     /// Vector is used to save the exact triggers order.
     matched_ids: CellView<'set, Vec<(EventBox, TriggerId)>>,
 }
 
-/// Entry in wasm smart-contracts map
-#[derive(Debug, Clone, Serialize)]
+/// Entry in wasm smart-contracts map.
+///
+/// Only the original blob is kept, modules are compiled on demand through the
+/// [`ModuleCache`](crate::smartcontracts::wasm::cache::ModuleCache) of the state,
+/// so that modules of rarely executed triggers don't stay in memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WasmSmartContractEntry {
     /// Original wasm binary blob
     original_contract: WasmSmartContract,
-    /// Compiled with [`wasmtime`] smart-contract
-    #[serde(skip)]
-    compiled_contract: wasmtime::Module,
     /// Number of times this contract is used
     count: NonZeroU64,
 }
 
+/// Authority of an account delegated to a trigger
+#[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize)]
+pub struct Delegation {
+    /// Account which delegated its authority
+    pub delegator: AccountId,
+    /// Kinds of instructions the trigger executes on behalf of the delegator
+    pub instructions: Vec<InstructionType>,
+}
+
+impl Delegation {
+    /// Check if `instruction` is executed on behalf of the delegator
+    pub fn covers(&self, instruction: &InstructionBox) -> bool {
+        self.instructions
+            .contains(&InstructionType::from(instruction))
+    }
+}
+
 impl<'de> DeserializeSeed<'de> for WasmSeed<'_, Set> {
     type Value = Set;
 
@@ -167,11 +195,9 @@ impl<'de> DeserializeSeed<'de> for WasmSeed<'_, Set> {
     where
         D: serde::Deserializer<'de>,
     {
-        struct SetVisitor<'e> {
-            loader: WasmSeed<'e, Set>,
-        }
+        struct SetVisitor;
 
-        impl<'de> Visitor<'de> for SetVisitor<'_> {
+        impl<'de> Visitor<'de> for SetVisitor {
             type Value = Set;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -188,6 +214,7 @@ impl<'de> DeserializeSeed<'de> for WasmSeed<'_, Set> {
                 let mut by_call_triggers = None;
                 let mut ids = None;
                 let mut contracts = None;
+                let mut delegations = None;
                 let mut matched_ids = None;
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -207,11 +234,10 @@ impl<'de> DeserializeSeed<'de> for WasmSeed<'_, Set> {
                             ids = Some(map.next_value()?);
                         }
                         "contracts" => {
-                            contracts =
-                                Some(map.next_value_seed(storage::serde::StorageSeeded {
-                                    kseed: PhantomData,
-                                    vseed: self.loader.cast::<WasmSmartContractEntry>(),
-                                })?);
+                            contracts = Some(map.next_value()?);
+                        }
+                        "delegations" => {
+                            delegations = Some(map.next_value()?);
                         }
                         "matched_ids" => {
                             matched_ids = Some(map.next_value()?);
@@ -232,70 +258,18 @@ impl<'de> DeserializeSeed<'de> for WasmSeed<'_, Set> {
                     ids: ids.ok_or_else(|| serde::de::Error::missing_field("ids"))?,
                     contracts: contracts
                         .ok_or_else(|| serde::de::Error::missing_field("contracts"))?,
+                    // Snapshots taken before delegations were introduced don't have them
+                    delegations: delegations.unwrap_or_default(),
                     matched_ids: matched_ids
                         .ok_or_else(|| serde::de::Error::missing_field("matched_ids"))?,
                 })
             }
         }
 
-        deserializer.deserialize_map(SetVisitor { loader: self })
+        deserializer.deserialize_map(SetVisitor)
     }
 }
 
-impl<'de> DeserializeSeed<'de> for WasmSeed<'_, WasmSmartContractEntry> {
-    type Value = WasmSmartContractEntry;
-
-    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct WasmSmartContractEntryVisitor<'e> {
-            loader: WasmSeed<'e, WasmSmartContractEntry>,
-        }
-
-        impl<'de> Visitor<'de> for WasmSmartContractEntryVisitor<'_> {
-            type Value = WasmSmartContractEntry;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("struct WasmSmartContractEntry")
-            }
-
-            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
-            where
-                M: MapAccess<'de>,
-            {
-                let mut original_contract = None;
-                let mut count = None;
-
-                while let Some(key) = map.next_key::<String>()? {
-                    match key.as_str() {
-                        "original_contract" => {
-                            original_contract = Some(map.next_value()?);
-                        }
-                        "count" => {
-                            count = Some(map.next_value()?);
-                        }
-                        _ => { /* Ignore unknown fields */ }
-                    }
-                }
-
-                let original_contract = original_contract
-                    .ok_or_else(|| serde::de::Error::missing_field("original_contract"))?;
-                let count = count.ok_or_else(|| serde::de::Error::missing_field("count"))?;
-                let compiled_contract = wasm::load_module(self.loader.engine, &original_contract)
-                    .map_err(serde::de::Error::custom)?;
-
-                Ok(WasmSmartContractEntry {
-                    original_contract,
-                    compiled_contract,
-                    count,
-                })
-            }
-        }
-
-        deserializer.deserialize_map(WasmSmartContractEntryVisitor { loader: self })
-    }
-}
 /// Trait to perform read-only operations on [`WorldBlock`], [`WorldTransaction`] and [`WorldView`]
 #[allow(missing_docs)]
 pub trait SetReadOnly {
@@ -310,6 +284,7 @@ pub trait SetReadOnly {
     fn ids(&self) -> &impl StorageReadOnly<TriggerId, TriggeringEventType>;
     fn contracts(&self)
         -> &impl StorageReadOnly<HashOf<WasmSmartContract>, WasmSmartContractEntry>;
+    fn delegations(&self) -> &impl StorageReadOnly<TriggerId, Delegation>;
     fn matched_ids(&self) -> &[(EventBox, TriggerId)];
 
     /// Get original [`WasmSmartContract`] for [`TriggerId`].
@@ -325,14 +300,39 @@ pub trait SetReadOnly {
             .map(|entry| &entry.original_contract)
     }
 
-    /// Get compiled [`wasmtime::Module`] for [`TriggerId`].
-    /// Returns `None` if there's no [`Trigger`]
-    /// with specified `id` that has WASM executable
-    #[inline]
-    fn get_compiled_contract(&self, hash: &HashOf<WasmSmartContract>) -> Option<&wasmtime::Module> {
-        self.contracts()
-            .get(hash)
-            .map(|entry| &entry.compiled_contract)
+    /// Estimate the memory used by the triggers, including the blobs of their smart contracts
+    fn memory_usage(&self) -> CollectionUsage {
+        fn action_size<F: Encode>(_id: &TriggerId, action: &LoadedAction<F>) -> usize {
+            let executable = match &action.executable {
+                ExecutableRef::Wasm(_) => 0,
+                ExecutableRef::Instructions(instructions) => instructions.encoded_size(),
+            };
+            executable
+                + action.authority.encoded_size()
+                + action.filter.encoded_size()
+                + action.metadata.encoded_size()
+        }
+
+        let actions = [
+            CollectionUsage::of_with(self.data_triggers().iter(), action_size),
+            CollectionUsage::of_with(self.pipeline_triggers().iter(), action_size),
+            CollectionUsage::of_with(self.time_triggers().iter(), action_size),
+            CollectionUsage::of_with(self.by_call_triggers().iter(), action_size),
+        ];
+        let contracts = CollectionUsage::of_with(self.contracts().iter(), |_, entry| {
+            entry.original_contract.as_ref().len()
+        });
+        let delegations = CollectionUsage::of_with(self.delegations().iter(), |_, delegation| {
+            delegation.delegator.encoded_size()
+                + delegation.instructions.len() * core::mem::size_of::<InstructionType>()
+        });
+
+        CollectionUsage {
+            entries: actions.iter().map(|usage| usage.entries).sum(),
+            bytes: actions.iter().map(|usage| usage.bytes).sum::<usize>()
+                + contracts.bytes
+                + delegations.bytes,
+        }
     }
 
     /// Convert [`LoadedAction`] to original [`Action`] by retrieving original
@@ -505,6 +505,9 @@ macro_rules! impl_set_ro {
             fn contracts(&self) -> &impl StorageReadOnly<HashOf<WasmSmartContract>, WasmSmartContractEntry> {
                 &self.contracts
             }
+            fn delegations(&self) -> &impl StorageReadOnly<TriggerId, Delegation> {
+                &self.delegations
+            }
             fn matched_ids(&self) -> &[(EventBox, TriggerId)] {
                 &self.matched_ids
             }
@@ -526,6 +529,7 @@ impl Set {
             by_call_triggers: self.by_call_triggers.block(),
             ids: self.ids.block(),
             contracts: self.contracts.block(),
+            delegations: self.delegations.block(),
             matched_ids: self.matched_ids.block(),
         }
     }
@@ -539,6 +543,7 @@ impl Set {
             by_call_triggers: self.by_call_triggers.block_and_revert(),
             ids: self.ids.block_and_revert(),
             contracts: self.contracts.block_and_revert(),
+            delegations: self.delegations.block_and_revert(),
             matched_ids: self.matched_ids.block_and_revert(),
         }
     }
@@ -552,6 +557,7 @@ impl Set {
             by_call_triggers: self.by_call_triggers.view(),
             ids: self.ids.view(),
             contracts: self.contracts.view(),
+            delegations: self.delegations.view(),
             matched_ids: self.matched_ids.view(),
         }
     }
@@ -567,6 +573,7 @@ impl<'set> SetBlock<'set> {
             by_call_triggers: self.by_call_triggers.transaction(),
             ids: self.ids.transaction(),
             contracts: self.contracts.transaction(),
+            delegations: self.delegations.transaction(),
             matched_ids: self.matched_ids.transaction(),
         }
     }
@@ -575,6 +582,7 @@ impl<'set> SetBlock<'set> {
     pub fn commit(self) {
         // NOTE: commit in reverse order
         self.matched_ids.commit();
+        self.delegations.commit();
         self.contracts.commit();
         self.ids.commit();
         self.by_call_triggers.commit();
@@ -623,6 +631,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     pub fn apply(self) {
         // NOTE: apply in reverse order
         self.matched_ids.apply();
+        self.delegations.apply();
         self.contracts.apply();
         self.ids.apply();
         self.by_call_triggers.apply();
@@ -641,7 +650,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     #[inline]
     pub fn add_data_trigger(
         &mut self,
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         trigger: SpecializedTrigger<DataEventFilter>,
     ) -> Result<bool> {
         self.add_to(engine, trigger, TriggeringEventType::Data, |me| {
@@ -659,7 +668,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     #[inline]
     pub fn add_pipeline_trigger(
         &mut self,
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         trigger: SpecializedTrigger<PipelineEventFilterBox>,
     ) -> Result<bool> {
         self.add_to(engine, trigger, TriggeringEventType::Pipeline, |me| {
@@ -677,7 +686,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     #[inline]
     pub fn add_time_trigger(
         &mut self,
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         trigger: SpecializedTrigger<TimeEventFilter>,
     ) -> Result<bool> {
         self.add_to(engine, trigger, TriggeringEventType::Time, |me| {
@@ -695,7 +704,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     #[inline]
     pub fn add_by_call_trigger(
         &mut self,
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         trigger: SpecializedTrigger<ExecuteTriggerEventFilter>,
     ) -> Result<bool> {
         self.add_to(engine, trigger, TriggeringEventType::ExecuteTrigger, |me| {
@@ -712,7 +721,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
     /// Return [`Err`] if failed to preload wasm trigger
     fn add_to<F: TriggeringEventFilter + storage::Value>(
         &mut self,
-        engine: &wasmtime::Engine,
+        engine: &wasm::engine::Engine,
         trigger: SpecializedTrigger<F>,
         event_type: TriggeringEventType,
         map: impl FnOnce(&mut Self) -> &mut StorageTransaction<'block, 'set, TriggerId, LoadedAction<F>>,
@@ -743,14 +752,14 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
                     *count = count.checked_add(1).expect(
                         "There is no way someone could register 2^64 amount of same triggers",
                     );
-                    // Cloning module is cheap, under Arc inside
                 } else {
-                    let module = wasm::load_module(engine, &bytes)?;
+                    // Only check that the blob compiles, the module is compiled
+                    // again through the module cache once the trigger is executed
+                    wasm::load_module(engine, &bytes)?;
                     self.contracts.insert(
                         hash,
                         WasmSmartContractEntry {
                             original_contract: bytes,
-                            compiled_contract: module,
                             count: NonZeroU64::MIN,
                         },
                     );
@@ -818,6 +827,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
         let Some(event_type) = self.ids.remove(id.clone()) else {
             return false;
         };
+        self.delegations.remove(id.clone());
 
         let removed = match event_type {
             TriggeringEventType::Data => {
@@ -842,6 +852,19 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
         true
     }
 
+    /// Delegate authority of an account to the trigger identified by `id`,
+    /// replacing the previous delegation if any.
+    pub fn delegate(&mut self, id: TriggerId, delegation: Delegation) {
+        self.delegations.insert(id, delegation);
+    }
+
+    /// Revoke authority delegated to the trigger identified by `id`.
+    ///
+    /// Return the revoked delegation if there was one.
+    pub fn revoke_delegation(&mut self, id: TriggerId) -> Option<Delegation> {
+        self.delegations.remove(id)
+    }
+
     /// Modify repetitions of the hook identified by [`Id`].
     ///
     /// # Errors
@@ -927,18 +950,20 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
             by_call_triggers,
             ids,
             contracts,
+            delegations,
             ..
         } = self;
-        Self::remove_zeros(ids, contracts, data_triggers);
-        Self::remove_zeros(ids, contracts, pipeline_triggers);
-        Self::remove_zeros(ids, contracts, time_triggers);
-        Self::remove_zeros(ids, contracts, by_call_triggers);
+        Self::remove_zeros(ids, contracts, delegations, data_triggers);
+        Self::remove_zeros(ids, contracts, delegations, pipeline_triggers);
+        Self::remove_zeros(ids, contracts, delegations, time_triggers);
+        Self::remove_zeros(ids, contracts, delegations, by_call_triggers);
     }
 
     /// Remove actions with zero execution count from `triggers`
     fn remove_zeros<F: storage::Value + EventFilter>(
         ids: &mut StorageTransaction<'block, 'set, TriggerId, TriggeringEventType>,
         contracts: &mut WasmSmartContractMapTransaction<'block, 'set>,
+        delegations: &mut StorageTransaction<'block, 'set, TriggerId, Delegation>,
         triggers: &mut StorageTransaction<'block, 'set, TriggerId, LoadedAction<F>>,
     ) {
         let to_remove: Vec<TriggerId> = triggers
@@ -952,6 +977,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
             .collect();
 
         for id in to_remove {
+            delegations.remove(id.clone());
             ids.remove(id.clone())
                 .and_then(|_| Self::remove_from(contracts, triggers, id).then_some(()))
                 .expect("`Set`'s `ids`, `contracts` and typed trigger collections are inconsistent. This is a bug")
@@ -1007,7 +1033,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
 
 /// Same as [`Executable`](iroha_data_model::transaction::Executable), but instead of
 /// [`Wasm`](iroha_data_model::transaction::Executable::Wasm) contains hash of the WASM blob
-/// Which can be used to obtain compiled by `wasmtime` module
+/// Which can be used to obtain compiled module
 #[derive(Clone, Serialize, Deserialize)]
 pub enum ExecutableRef {
     /// Loaded WASM
@@ -149,6 +149,9 @@ pub trait LoadedActionTrait {
     /// Get action technical account
     fn authority(&self) -> &AccountId;
 
+    /// Set action technical account
+    fn set_authority(&mut self, authority: AccountId);
+
     /// Get action metadata
     fn metadata(&self) -> &Metadata;
 
@@ -182,6 +185,10 @@ impl<F: EventFilter + Into<EventFilterBox> + Clone> LoadedActionTrait for Loaded
         &self.authority
     }
 
+    fn set_authority(&mut self, authority: AccountId) {
+        self.authority = authority;
+    }
+
     fn metadata(&self) -> &Metadata {
         &self.metadata
     }
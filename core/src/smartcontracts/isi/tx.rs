@@ -93,15 +93,57 @@ impl ValidQuery for FindTransactionsByAccountId {
         state_ro: &'state impl StateReadOnly,
     ) -> Result<impl Iterator<Item = Self::Item> + 'state, QueryExecutionFail> {
         let account_id = self.account.clone();
+        let kura = state_ro.kura();
+        let height = state_ro.height();
+
+        // Only one of these is set depending on whether the explorer index is enabled
+        let indexed = kura.account_transactions(&account_id).map(|locations| {
+            locations
+                .into_iter()
+                .take_while(move |location| location.height.get() <= height)
+                .map(move |location| {
+                    let block = kura
+                        .get_block_by_height(location.height)
+                        .expect("INTERNAL BUG: Failed to load block");
+                    BlockTransactionRef(block, location.index)
+                })
+        });
+        let scanned = indexed.is_none().then(|| {
+            state_ro
+                .all_blocks()
+                .flat_map(BlockTransactionIter::new)
+                .filter(move |tx| *tx.authority() == account_id)
+        });
+
+        Ok(indexed
+            .into_iter()
+            .flatten()
+            .chain(scanned.into_iter().flatten())
+            .map(|tx| TransactionQueryOutput {
+                block_hash: tx.block_hash(),
+                transaction: tx.value(),
+            })
+            .filter(move |tx| filter.applies(tx)))
+    }
+}
+
+impl ValidQuery for FindTransactionsByAppId {
+    #[metrics(+"find_transactions_by_app_id")]
+    fn execute<'state>(
+        self,
+        filter: CompoundPredicate<TransactionQueryOutputPredicateBox>,
+        state_ro: &'state impl StateReadOnly,
+    ) -> Result<impl Iterator<Item = Self::Item> + 'state, QueryExecutionFail> {
+        let app_id = self.app;
 
         Ok(state_ro
             .all_blocks()
             .flat_map(BlockTransactionIter::new)
-            .filter(move |tx| *tx.authority() == account_id)
             .map(|tx| TransactionQueryOutput {
                 block_hash: tx.block_hash(),
                 transaction: tx.value(),
             })
+            .filter(move |tx| tx.transaction.app_id().as_ref() == Some(&app_id))
             .filter(move |tx| filter.applies(tx)))
     }
 }
@@ -115,18 +157,22 @@ impl ValidSingularQuery for FindTransactionByHash {
         let tx_hash = self.hash;
 
         iroha_logger::trace!(%tx_hash);
-        if !state_ro.has_transaction(tx_hash) {
+        let Some(&height) = state_ro.transactions().get(&tx_hash) else {
             return Err(FindError::Transaction(tx_hash).into());
         };
         let block = state_ro
-            .block_with_tx(&tx_hash)
+            .kura()
+            .get_block_by_height(height)
             .ok_or_else(|| FindError::Transaction(tx_hash))?;
 
         let block_hash = block.hash();
 
         let mut transactions = block.transactions();
-        transactions
-            .find(|transaction| transaction.value.hash() == tx_hash)
+        let transaction = match state_ro.kura().transaction_location(&tx_hash) {
+            Some(location) if location.height == height => transactions.nth(location.index),
+            _ => transactions.find(|transaction| transaction.value.hash() == tx_hash),
+        };
+        transaction
             .cloned()
             .map(|transaction| TransactionQueryOutput {
                 block_hash,
@@ -135,3 +181,18 @@ impl ValidSingularQuery for FindTransactionByHash {
             .ok_or_else(|| FindError::Transaction(tx_hash).into())
     }
 }
+
+impl ValidSingularQuery for FindIdempotencyRecord {
+    #[metrics(+"find_idempotency_record")]
+    fn execute(
+        &self,
+        state_ro: &impl StateReadOnly,
+    ) -> Result<IdempotencyRecord, QueryExecutionFail> {
+        state_ro
+            .idempotency_keys()
+            .get(&(self.authority.clone(), self.key.clone()))
+            .filter(|record| record.expires_at_ms > state_ro.now_ms())
+            .copied()
+            .ok_or_else(|| FindError::IdempotencyKey(self.key.clone()).into())
+    }
+}
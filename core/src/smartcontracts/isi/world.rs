@@ -20,7 +20,7 @@ pub mod isi {
     use eyre::Result;
     use iroha_data_model::{
         isi::error::{InstructionExecutionError, InvalidParameterError, RepetitionError},
-        parameter::{CustomParameter, Parameter},
+        parameter::{BlockParameter, CustomParameter, Parameter, ProtocolUpgrade},
         prelude::*,
         query::error::FindError,
         Level,
@@ -68,6 +68,7 @@ pub mod isi {
             };
 
             world.trusted_peers_ids.remove(index);
+            world.peer_certificates.remove(peer_id.public_key.clone());
 
             world.emit_events(Some(PeerEvent::Removed(peer_id)));
 
@@ -75,6 +76,50 @@ pub mod isi {
         }
     }
 
+    impl Execute for RegisterPeerCertificate {
+        #[metrics(+"register_peer_certificate")]
+        fn execute(
+            self,
+            authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let certificate = self.certificate;
+            let peer_id = certificate.peer().clone();
+
+            let world = &mut state_transaction.world;
+            let Some(registered) = world.trusted_peers_ids.iter().find(|id| **id == peer_id) else {
+                return Err(FindError::Peer(peer_id).into());
+            };
+            if registered.address != peer_id.address {
+                return Err(InvalidParameterError::PeerCertificate(format!(
+                    "peer is registered with address {}, not {}",
+                    registered.address, peer_id.address
+                ))
+                .into());
+            }
+            certificate.verify().map_err(|_| {
+                InvalidParameterError::PeerCertificate(
+                    "not signed with the key of the peer".to_owned(),
+                )
+            })?;
+            if certificate.operator() != authority {
+                return Err(InvalidParameterError::PeerCertificate(format!(
+                    "has to be registered by the operator {}",
+                    certificate.operator()
+                ))
+                .into());
+            }
+            world.account(certificate.operator())?;
+
+            world
+                .peer_certificates
+                .insert(peer_id.public_key.clone(), certificate);
+            world.emit_events(Some(PeerEvent::CertificateRegistered(peer_id)));
+
+            Ok(())
+        }
+    }
+
     impl Execute for Register<Domain> {
         #[metrics("register_domain")]
         fn execute(
@@ -92,6 +137,7 @@ pub mod isi {
             }
 
             let world = &mut state_transaction.world;
+            world.check_name(&domain_id.name)?;
             if world.domains.get(&domain_id).is_some() {
                 return Err(RepetitionError {
                     instruction: InstructionType::Register,
@@ -147,6 +193,15 @@ pub mod isi {
 
                 state_transaction.world.remove_account_roles(&account);
 
+                state_transaction.world.remove_account_apps(&account);
+
+                state_transaction.world.remove_account_proxies(&account);
+
+                state_transaction
+                    .world
+                    .recovery_policies
+                    .remove(account.clone());
+
                 let remove_assets: Vec<AssetId> = state_transaction
                     .world
                     .assets_in_account_iter(&account)
@@ -201,6 +256,7 @@ pub mod isi {
         ) -> Result<(), Error> {
             let role = self.object.build(authority);
 
+            state_transaction.world.check_name(&role.id.name)?;
             if state_transaction.world.roles.get(role.id()).is_some() {
                 return Err(RepetitionError {
                     instruction: InstructionType::Register,
@@ -326,6 +382,10 @@ pub mod isi {
             _authority: &AccountId,
             state_transaction: &mut StateTransaction<'_, '_>,
         ) -> Result<(), Error> {
+            if let Parameter::Block(BlockParameter::ProtocolUpgrade(next)) = self.0 {
+                check_protocol_upgrade(next, state_transaction)?;
+            }
+
             macro_rules! set_parameter {
                 ($($container:ident($param:ident.$field:ident) => $single:ident::$variant:ident),* $(,)?) => {
                     match self.0 { $(
@@ -346,6 +406,42 @@ pub mod isi {
                                 }))
                             );
                         })*
+                        Parameter::Fee(next) => {
+                            use iroha_data_model::parameter::FeeParameter;
+
+                            let fee = &mut state_transaction.world.parameters.fee;
+                            let prev = match &next {
+                                FeeParameter::Collector(collector) => FeeParameter::Collector(
+                                    core::mem::replace(&mut fee.collector, collector.clone()),
+                                ),
+                                FeeParameter::Distribution(distribution) => {
+                                    FeeParameter::Distribution(core::mem::replace(
+                                        &mut fee.distribution,
+                                        *distribution,
+                                    ))
+                                }
+                            };
+
+                            state_transaction
+                                .world
+                                .emit_events(Some(ConfigurationEvent::Changed(ParameterChanged {
+                                    old_value: Parameter::Fee(prev),
+                                    new_value: Parameter::Fee(next),
+                                })));
+                        }
+                        Parameter::WasmEngine(next) => {
+                            let prev = core::mem::replace(
+                                &mut state_transaction.world.parameters.wasm_engine,
+                                next,
+                            );
+
+                            state_transaction
+                                .world
+                                .emit_events(Some(ConfigurationEvent::Changed(ParameterChanged {
+                                    old_value: Parameter::WasmEngine(prev),
+                                    new_value: Parameter::WasmEngine(next),
+                                })));
+                        }
                         Parameter::Custom(next) => {
                             let prev = state_transaction
                                 .world
@@ -380,21 +476,65 @@ pub mod isi {
                 Sumeragi(sumeragi.commit_time_ms) => SumeragiParameter::CommitTimeMs,
 
                 Block(block.max_transactions) => BlockParameter::MaxTransactions,
+                Block(block.max_size_bytes) => BlockParameter::MaxSizeBytes,
+                Block(block.protocol_upgrade) => BlockParameter::ProtocolUpgrade,
 
                 Transaction(transaction.max_instructions) => TransactionParameter::MaxInstructions,
                 Transaction(transaction.smart_contract_size) => TransactionParameter::SmartContractSize,
+                Transaction(transaction.idempotency_window_ms) => TransactionParameter::IdempotencyWindowMs,
+                Transaction(transaction.max_metadata_bytes) => TransactionParameter::MaxMetadataBytes,
+                Transaction(transaction.trigger_wasm_size) => TransactionParameter::TriggerWasmSize,
+                Transaction(transaction.v1_deprecation_height) => TransactionParameter::V1DeprecationHeight,
 
                 SmartContract(smart_contract.fuel) => SmartContractParameter::Fuel,
                 SmartContract(smart_contract.memory) => SmartContractParameter::Memory,
 
                 Executor(executor.fuel) => SmartContractParameter::Fuel,
                 Executor(executor.memory) => SmartContractParameter::Memory,
+
+                Name(name.max_len) => NameParameter::MaxLen,
+                Name(name.charset) => NameParameter::Charset,
             );
 
             Ok(())
         }
     }
 
+    /// Check that the `next` protocol upgrade can be scheduled by the block being executed
+    fn check_protocol_upgrade(
+        next: ProtocolUpgrade,
+        state_transaction: &StateTransaction<'_, '_>,
+    ) -> Result<(), InvalidParameterError> {
+        let current = state_transaction.world.parameters.block.protocol_upgrade;
+        if next == current {
+            return Ok(());
+        }
+
+        // The block being executed is already created with the current version
+        let height = state_transaction.executing_height().get();
+        let active = current.version_at(height);
+        if next.prior_version != active {
+            return Err(InvalidParameterError::ProtocolUpgrade(format!(
+                "upgrade is from version {}, but the chain is at version {active}",
+                next.prior_version
+            )));
+        }
+        if next.version <= active {
+            return Err(InvalidParameterError::ProtocolUpgrade(format!(
+                "version {} is not newer than the current version {active}",
+                next.version
+            )));
+        }
+        if next.activation_height.get() <= height {
+            return Err(InvalidParameterError::ProtocolUpgrade(format!(
+                "activation height {} is not above the current height {height}",
+                next.activation_height
+            )));
+        }
+
+        Ok(())
+    }
+
     impl Execute for Upgrade {
         #[metrics(+"upgrade_executor")]
         fn execute(
@@ -457,7 +597,7 @@ pub mod query {
         peer::Peer,
         prelude::*,
         query::{
-            error::QueryExecutionFail as Error,
+            error::{FindError, QueryExecutionFail as Error},
             predicate::{
                 predicate_atoms::{
                     peer::PeerPredicateBox,
@@ -523,6 +663,18 @@ pub mod query {
         }
     }
 
+    impl ValidSingularQuery for FindPeerCertificate {
+        #[metrics(+"find_peer_certificate")]
+        fn execute(&self, state_ro: &impl StateReadOnly) -> Result<PeerCertificate, Error> {
+            state_ro
+                .world()
+                .peer_certificates()
+                .get(&self.public_key)
+                .cloned()
+                .ok_or_else(|| Error::Find(FindError::PeerCertificate(self.public_key.clone())))
+        }
+    }
+
     impl ValidSingularQuery for FindExecutorDataModel {
         #[metrics(+"find_executor_data_model")]
         fn execute(&self, state_ro: &impl StateReadOnly) -> Result<ExecutorDataModel, Error> {
@@ -2,7 +2,7 @@
 //! `WebAssembly` VM Smartcontracts can be written in Rust, compiled
 //! to wasm format and submitted in a transaction
 
-use std::{borrow::Borrow, num::NonZeroU64};
+use std::{borrow::Borrow, num::NonZeroU64, sync::Arc};
 
 use error::*;
 use import::traits::{ExecuteOperations as _, GetExecutorPayloads as _, SetDataModel as _};
@@ -19,18 +19,26 @@ use iroha_data_model::{
 use iroha_logger::debug;
 // NOTE: Using error_span so that span info is logged on every event
 use iroha_logger::{error_span as wasm_log_span, prelude::tracing::Span};
-use iroha_wasm_codec::{self as codec, WasmUsize};
-use wasmtime::{
-    Caller, Config as WasmtimeConfig, Engine, Linker, Module, Store, StoreLimits,
-    StoreLimitsBuilder, TypedFunc,
+pub use iroha_wasm_codec::engine;
+use iroha_wasm_codec::{
+    self as codec,
+    engine::{
+        Backend as _, Caller, Engine, Linker, Module, Selected, Store, StoreLimits,
+        StoreLimitsBuilder, TypedFunc,
+    },
+    WasmUsize,
 };
+use parking_lot::Mutex;
 
+use self::cache::ModuleCache;
 use crate::{
     query::store::LiveQueryStoreHandle,
-    smartcontracts::{query::ValidQueryRequest, Execute},
+    smartcontracts::{query::ValidQueryRequest, triggers::set::SetReadOnly as _, Execute},
     state::{StateReadOnly, StateTransaction, WorldReadOnly},
 };
 
+pub mod cache;
+
 /// Name of the exported memory
 const WASM_MEMORY: &str = "memory";
 const WASM_MODULE: &str = "iroha";
@@ -112,21 +120,21 @@ mod import {
 pub mod error {
     //! Error types for [`wasm`](super) and their impls
 
-    use wasmtime::{Error as WasmtimeError, Trap};
+    use super::engine::{self, Backend as _, Error as EngineError, TrapKind};
 
     /// `WebAssembly` execution error type
     #[derive(Debug, thiserror::Error, displaydoc::Display)]
     #[ignore_extra_doc_attributes]
     pub enum Error {
         /// Runtime initialization failure
-        Initialization(#[source] WasmtimeError),
+        Initialization(#[source] EngineError),
         /// Runtime finalization failure.
         ///
         /// Currently only [`crate::query::store::Error`] might fail in this case.
         /// [`From`] is not implemented to force users to explicitly wrap this error.
         Finalization(#[source] crate::query::store::Error),
         /// Failed to load module
-        ModuleLoading(#[source] WasmtimeError),
+        ModuleLoading(#[source] EngineError),
         /// Module could not be instantiated
         Instantiation(#[from] InstantiationError),
         /// Export error
@@ -134,7 +142,7 @@ pub mod error {
         /// Call to the function exported from module failed
         ExportFnCall(#[from] ExportFnCallError),
         /// Failed to decode object from bytes with length prefix
-        Decode(#[source] WasmtimeError),
+        Decode(#[source] EngineError),
     }
 
     /// Instantiation error
@@ -143,8 +151,8 @@ pub mod error {
     pub enum InstantiationError {
         /// Linker failed to instantiate module
         ///
-        /// [`wasmtime::Linker::instantiate`] failed
-        Linker(#[from] WasmtimeError),
+        /// [`Backend::instantiate`](engine::Backend::instantiate) failed
+        Linker(#[from] EngineError),
         /// Export which should always be present is missing
         MandatoryExport(#[from] ExportError),
     }
@@ -213,26 +221,19 @@ pub mod error {
     #[derive(Debug, thiserror::Error, displaydoc::Display)]
     pub enum ExportFnCallError {
         /// Failed to execute operation on host
-        HostExecution(#[source] wasmtime::Error),
+        HostExecution(#[source] EngineError),
         /// Execution limits exceeded
-        ExecutionLimitsExceeded(#[source] wasmtime::Error),
+        ExecutionLimitsExceeded(#[source] EngineError),
         /// Other kind of trap
-        Other(#[source] wasmtime::Error),
-    }
-
-    impl From<wasmtime::Error> for ExportFnCallError {
-        fn from(err: wasmtime::Error) -> Self {
-            match err.downcast_ref() {
-                Some(&trap) => match trap {
-                    Trap::StackOverflow
-                    | Trap::MemoryOutOfBounds
-                    | Trap::TableOutOfBounds
-                    | Trap::IndirectCallToNull
-                    | Trap::OutOfFuel
-                    | Trap::Interrupt => Self::ExecutionLimitsExceeded(err),
-                    _ => Self::Other(err),
-                },
-                None => Self::HostExecution(err),
+        Other(#[source] EngineError),
+    }
+
+    impl From<EngineError> for ExportFnCallError {
+        fn from(err: EngineError) -> Self {
+            match engine::Selected::trap_kind(&err) {
+                TrapKind::Host => Self::HostExecution(err),
+                TrapKind::ExecutionLimits => Self::ExecutionLimitsExceeded(err),
+                TrapKind::Other => Self::Other(err),
             }
         }
     }
@@ -241,41 +242,37 @@ pub mod error {
 /// [`Result`] type for this module
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+/// [`WasmEngine`](iroha_data_model::parameter::WasmEngine) this peer executes the smart contracts with
+#[cfg(feature = "wasmi")]
+pub const ENGINE: iroha_data_model::parameter::WasmEngine =
+    iroha_data_model::parameter::WasmEngine::Wasmi;
+/// [`WasmEngine`](iroha_data_model::parameter::WasmEngine) this peer executes the smart contracts with
+#[cfg(not(feature = "wasmi"))]
+pub const ENGINE: iroha_data_model::parameter::WasmEngine =
+    iroha_data_model::parameter::WasmEngine::Wasmtime;
+
 /// Create [`Module`] from bytes.
 ///
 /// # Errors
 ///
-/// See [`Module::new`]
+/// See [`Backend::load_module`](engine::Backend::load_module)
 // TODO: Probably we can do some checks here such as searching for entrypoint function
-pub fn load_module(engine: &Engine, bytes: impl AsRef<[u8]>) -> Result<wasmtime::Module> {
-    Module::new(engine, bytes).map_err(Error::ModuleLoading)
+pub fn load_module(engine: &Engine, bytes: impl AsRef<[u8]>) -> Result<Module> {
+    Selected::load_module(engine, bytes.as_ref()).map_err(Error::ModuleLoading)
 }
 
-/// Create [`Engine`] with a predefined configuration.
+/// Create [`Engine`] of the [selected](engine::Selected) engine with a predefined configuration.
 ///
 /// # Panics
 ///
 /// Panics if something is wrong with the configuration.
 /// Configuration is hardcoded and tested, so this function should never panic.
 pub fn create_engine() -> Engine {
-    create_config()
-        .and_then(|config| Engine::new(&config).map_err(Error::Initialization))
+    Selected::create_engine()
+        .map_err(Error::Initialization)
         .expect("Failed to create WASM engine with a predefined configuration. This is a bug")
 }
 
-fn create_config() -> Result<WasmtimeConfig> {
-    let mut config = WasmtimeConfig::new();
-    config
-        .consume_fuel(true)
-        .cache_config_load_default()
-        .map_err(Error::Initialization)?;
-    #[cfg(feature = "profiling")]
-    {
-        config.profiler(wasmtime::ProfilingStrategy::PerfMap);
-    }
-    Ok(config)
-}
-
 /// Remove all executed queries from the query storage.
 fn forget_all_executed_queries(
     query_handle: &LiveQueryStoreHandle,
@@ -441,6 +438,22 @@ pub mod state {
                 self.0
             }
         }
+
+        /// Trait to account the fuel consumed by WASM.
+        ///
+        /// Only the mutable chain state keeps the account, see [`StateTransaction::fuel_consumed`].
+        pub trait FuelAccount {
+            /// Record the `fuel` consumed by WASM.
+            fn add_fuel_consumed(&mut self, _fuel: u64) {}
+        }
+
+        impl<S: StateReadOnly> FuelAccount for WithConst<'_, S> {}
+
+        impl FuelAccount for WithMut<'_, '_, '_> {
+            fn add_fuel_consumed(&mut self, fuel: u64) {
+                self.0.fuel_consumed = self.0.fuel_consumed.saturating_add(fuel);
+            }
+        }
     }
 
     pub mod specific {
@@ -594,10 +607,11 @@ pub struct Runtime<S> {
     engine: Engine,
     linker: Linker<S>,
     config: Config,
+    module_cache: Option<Arc<Mutex<ModuleCache>>>,
 }
 
 impl<S> Runtime<S> {
-    fn get_memory(caller: &mut impl GetExport) -> Result<wasmtime::Memory, ExportError> {
+    fn get_memory(caller: &mut impl GetExport) -> Result<engine::Memory, ExportError> {
         caller
             .get_export(WASM_MEMORY)
             .ok_or_else(|| ExportError::not_found(WASM_MEMORY))?
@@ -619,11 +633,11 @@ impl<S> Runtime<S> {
             })
     }
 
-    fn get_typed_func<P: wasmtime::WasmParams, R: wasmtime::WasmResults>(
-        instance: &wasmtime::Instance,
+    fn get_typed_func<P: engine::WasmParams, R: engine::WasmResults>(
+        instance: &engine::Instance,
         mut store: &mut Store<S>,
         func_name: &'static str,
-    ) -> Result<wasmtime::TypedFunc<P, R>, ExportError> {
+    ) -> Result<TypedFunc<P, R>, ExportError> {
         instance
             .get_func(&mut store, func_name)
             .ok_or_else(|| ExportError::not_found(func_name))?
@@ -635,19 +649,20 @@ impl<S> Runtime<S> {
         &self,
         store: &mut Store<S>,
         bytes: impl AsRef<[u8]>,
-    ) -> Result<wasmtime::Instance> {
-        let module = load_module(&self.engine, bytes)?;
+    ) -> Result<engine::Instance> {
+        let module = match &self.module_cache {
+            Some(module_cache) => module_cache.lock().get_or_load(&self.engine, bytes)?,
+            None => load_module(&self.engine, bytes)?,
+        };
         self.instantiate_module(&module, store).map_err(Into::into)
     }
 
     fn instantiate_module(
         &self,
-        module: &wasmtime::Module,
-        mut store: &mut wasmtime::Store<S>,
-    ) -> Result<wasmtime::Instance, InstantiationError> {
-        let instance = self
-            .linker
-            .instantiate(&mut store, module)
+        module: &Module,
+        store: &mut Store<S>,
+    ) -> Result<engine::Instance, InstantiationError> {
+        let instance = Selected::instantiate(&self.linker, store, module)
             .map_err(InstantiationError::Linker)?;
 
         Self::check_mandatory_exports(&instance, store)?;
@@ -656,8 +671,8 @@ impl<S> Runtime<S> {
     }
 
     fn check_mandatory_exports(
-        instance: &wasmtime::Instance,
-        mut store: &mut wasmtime::Store<S>,
+        instance: &engine::Instance,
+        mut store: &mut Store<S>,
     ) -> Result<(), InstantiationError> {
         let _ = Self::get_memory(&mut (instance, &mut store))?;
         let _ = Self::get_typed_func::<WasmUsize, WasmUsize>(
@@ -698,7 +713,7 @@ impl<S> Runtime<S> {
 struct LogError(u8);
 
 /// It's required by `#[codec::wrap]` to parse well
-type WasmtimeError = wasmtime::Error;
+type EngineError = engine::Error;
 
 impl<W, S> Runtime<state::CommonState<W, S>> {
     /// Log the given string at the given log level
@@ -710,13 +725,13 @@ impl<W, S> Runtime<state::CommonState<W, S>> {
     pub fn log(
         (log_level, msg): (u8, String),
         state: &state::CommonState<W, S>,
-    ) -> Result<(), WasmtimeError> {
+    ) -> Result<(), EngineError> {
         const TARGET: &str = "WASM";
 
         let _span = state.log_span.enter();
         match LogLevel::from_repr(log_level)
             .ok_or(LogError(log_level))
-            .map_err(wasmtime::Error::from)?
+            .map_err(Selected::host_error)?
         {
             LogLevel::TRACE => {
                 iroha_logger::trace!(target: TARGET, msg);
@@ -737,6 +752,13 @@ impl<W, S> Runtime<state::CommonState<W, S>> {
         Ok(())
     }
 
+    fn fuel_consumed(&self, store: &Store<state::CommonState<W, S>>) -> u64 {
+        let fuel_left = store
+            .get_fuel()
+            .expect("Wasm Runtime config is malformed, this is a bug");
+        self.config.fuel.get().saturating_sub(fuel_left)
+    }
+
     fn create_store(&self, state: state::CommonState<W, S>) -> Store<state::CommonState<W, S>> {
         let mut store = Store::new(&self.engine, state);
 
@@ -749,10 +771,13 @@ impl<W, S> Runtime<state::CommonState<W, S>> {
     }
 }
 
-impl<W: state::chain_state::ConstState, S> Runtime<state::CommonState<W, S>> {
+impl<W, S> Runtime<state::CommonState<W, S>>
+where
+    W: state::chain_state::ConstState + state::chain_state::FuelAccount,
+{
     fn execute_executor_validate_internal(
         &self,
-        module: &wasmtime::Module,
+        module: &Module,
         state: state::CommonState<W, S>,
         validate_fn_name: &'static str,
     ) -> Result<executor::Result> {
@@ -774,8 +799,10 @@ impl<W: state::chain_state::ConstState, S> Runtime<state::CommonState<W, S>> {
         let validation_res =
             codec::decode_with_length_prefix_from_memory(&memory, &dealloc_fn, &mut store, offset)
                 .map_err(Error::Decode)?;
+        let fuel_consumed = self.fuel_consumed(&store);
 
         let mut state = store.into_data();
+        state.state.add_fuel_consumed(fuel_consumed);
         let executed_queries = state.take_executed_queries();
         forget_all_executed_queries(
             state.state.state().borrow().query_handle(),
@@ -925,7 +952,9 @@ impl<'wrld, 'block: 'wrld, 'state: 'block> Runtime<state::SmartContract<'wrld, '
         main_fn
             .call(&mut store, ())
             .map_err(ExportFnCallError::from)?;
+        let fuel_consumed = self.fuel_consumed(&store);
         let mut state = store.into_data();
+        state::chain_state::FuelAccount::add_fuel_consumed(&mut state.state, fuel_consumed);
         let executed_queries = state.take_executed_queries();
         forget_all_executed_queries(state.state.0.query_handle, executed_queries);
 
@@ -977,7 +1006,7 @@ impl<'wrld, 'block: 'wrld, 'state: 'block> Runtime<state::Trigger<'wrld, 'block,
         state_transaction: &'wrld mut StateTransaction<'block, 'state>,
         id: &TriggerId,
         authority: AccountId,
-        module: &wasmtime::Module,
+        module: &Module,
         event: EventBox,
     ) -> Result<()> {
         let span = wasm_log_span!("Trigger execution", %id, %authority);
@@ -995,14 +1024,18 @@ impl<'wrld, 'block: 'wrld, 'state: 'block> Runtime<state::Trigger<'wrld, 'block,
         let main_fn = Self::get_typed_func(&instance, &mut store, import::TRIGGER_MAIN)?;
 
         // NOTE: This function takes ownership of the pointer
-        main_fn
+        let result = main_fn
             .call(&mut store, ())
-            .map_err(ExportFnCallError::from)?;
+            .map_err(ExportFnCallError::from);
+        // Fuel is accounted even if the trigger fails, so that its receipt is accurate
+        let fuel_consumed = self.fuel_consumed(&store);
 
         let mut state = store.into_data();
+        state::chain_state::FuelAccount::add_fuel_consumed(&mut state.state, fuel_consumed);
         let executed_queries = state.take_executed_queries();
         forget_all_executed_queries(state.state.0.query_handle, executed_queries);
 
+        result?;
         Ok(())
     }
 
@@ -1032,7 +1065,27 @@ impl<'wrld, 'block, 'state> import::traits::ExecuteOperations<state::Trigger<'wr
         instruction: InstructionBox,
         state: &mut state::Trigger<'wrld, 'block, 'state>,
     ) -> Result<(), ValidationFail> {
-        Self::default_execute_instruction(instruction, state)
+        let delegator = state
+            .state
+            .0
+            .world
+            .triggers
+            .delegations()
+            .get(&state.specific_state.id)
+            .filter(|delegation| delegation.covers(&instruction))
+            .map(|delegation| delegation.delegator.clone());
+        let Some(delegator) = delegator else {
+            return Self::default_execute_instruction(instruction, state);
+        };
+
+        debug!(%instruction, %delegator, "Executing on behalf of delegator");
+        state
+            .state
+            .0
+            .world
+            .executor
+            .clone() // Cloning executor is a cheap operation
+            .validate_instruction(state.state.0, &delegator, instruction)
     }
 }
 
@@ -1111,7 +1164,7 @@ impl<'wrld, 'block, 'state> Runtime<state::executor::ValidateTransaction<'wrld,
         &self,
         state_transaction: &'wrld mut StateTransaction<'block, 'state>,
         authority: &AccountId,
-        module: &wasmtime::Module,
+        module: &Module,
         transaction: SignedTransaction,
     ) -> Result<executor::Result> {
         let span = wasm_log_span!("Running `validate_transaction()`");
@@ -1191,7 +1244,7 @@ impl<'wrld, 'block, 'state> Runtime<state::executor::ValidateInstruction<'wrld,
         &self,
         state_transaction: &'wrld mut StateTransaction<'block, 'state>,
         authority: &AccountId,
-        module: &wasmtime::Module,
+        module: &Module,
         instruction: InstructionBox,
     ) -> Result<executor::Result> {
         let span = wasm_log_span!("Running `validate_instruction()`");
@@ -1271,7 +1324,7 @@ impl<'wrld, S: StateReadOnly> Runtime<state::executor::ValidateQuery<'wrld, S>>
         &self,
         state_ro: &'wrld S,
         authority: &AccountId,
-        module: &wasmtime::Module,
+        module: &Module,
         query: AnyQueryBox,
     ) -> Result<executor::Result> {
         let span = wasm_log_span!("Running `validate_query()`");
@@ -1366,7 +1419,7 @@ impl<'wrld, 'block, 'state> Runtime<state::executor::Migrate<'wrld, 'block, 'sta
         &self,
         state_transaction: &'wrld mut StateTransaction<'block, 'state>,
         authority: &AccountId,
-        module: &wasmtime::Module,
+        module: &Module,
     ) -> Result<(), error::Error> {
         let span = wasm_log_span!("Running migration");
         let state = state::executor::Migrate::new(
@@ -1460,6 +1513,7 @@ pub struct RuntimeBuilder<S> {
     engine: Option<Engine>,
     config: Option<Config>,
     linker: Option<Linker<S>>,
+    module_cache: Option<Arc<Mutex<ModuleCache>>>,
 }
 
 impl<S> RuntimeBuilder<S> {
@@ -1469,6 +1523,7 @@ impl<S> RuntimeBuilder<S> {
             engine: None,
             config: None,
             linker: None,
+            module_cache: None,
         }
     }
 
@@ -1488,6 +1543,15 @@ impl<S> RuntimeBuilder<S> {
         self
     }
 
+    /// Sets the [`ModuleCache`] to look up compiled smart contracts in.
+    /// The cache must only hold modules compiled by the [`Engine`] of the [`Runtime`].
+    #[must_use]
+    #[inline]
+    pub fn with_module_cache(mut self, module_cache: Arc<Mutex<ModuleCache>>) -> Self {
+        self.module_cache = Some(module_cache);
+        self
+    }
+
     /// Finalizes the builder and creates a [`Runtime`].
     ///
     /// This is private and is used by `build()` methods from more specialized builders.
@@ -1501,6 +1565,7 @@ impl<S> RuntimeBuilder<S> {
             engine,
             linker,
             config: self.config.unwrap_or_default(),
+            module_cache: self.module_cache,
         })
     }
 }
@@ -1514,13 +1579,13 @@ macro_rules! create_imports {
         $linker.func_wrap(
                 WASM_MODULE,
                 export::LOG,
-                |caller: ::wasmtime::Caller<$ty>, offset, len| Runtime::log(caller, offset, len),
+                |caller: Caller<$ty>, offset, len| Runtime::log(caller, offset, len),
             )
             .and_then(|l| {
                 l.func_wrap(
                     WASM_MODULE,
                     export::DBG,
-                    |caller: ::wasmtime::Caller<$ty>, offset, len| Runtime::dbg(caller, offset, len),
+                    |caller: Caller<$ty>, offset, len| Runtime::dbg(caller, offset, len),
                 )
             })
             $(.and_then(|l| {
@@ -1530,7 +1595,8 @@ macro_rules! create_imports {
                     $fn,
                 )
             }))*
-            .map_err(Error::Initialization)
+            // `wasmi` fails with a dedicated linker error
+            .map_err(|error| Error::Initialization(error.into()))
     };
 }
 
@@ -1545,9 +1611,9 @@ impl<'wrld, 'block, 'state> RuntimeBuilder<state::SmartContract<'wrld, 'block, '
             let mut linker = Linker::new(engine);
 
             create_imports!(linker, state::SmartContract<'wrld, 'block, 'state>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::SmartContract<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::SmartContract<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_SMART_CONTRACT_PAYLOAD => |caller: ::wasmtime::Caller<state::SmartContract<'wrld, 'block, 'state>>| Runtime::get_smart_contract_payload(caller),
+                export::EXECUTE_ISI => |caller: Caller<state::SmartContract<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::SmartContract<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_SMART_CONTRACT_PAYLOAD => |caller: Caller<state::SmartContract<'wrld, 'block, 'state>>| Runtime::get_smart_contract_payload(caller),
             )?;
             Ok(linker)
         })
@@ -1565,9 +1631,9 @@ impl<'wrld, 'block, 'state> RuntimeBuilder<state::Trigger<'wrld, 'block, 'state>
             let mut linker = Linker::new(engine);
 
             create_imports!(linker, state::Trigger<'wrld, 'block, 'state>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::Trigger<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::Trigger<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_TRIGGER_PAYLOAD => |caller: ::wasmtime::Caller<state::Trigger<'wrld, 'block, 'state>>| Runtime::get_trigger_payload(caller),
+                export::EXECUTE_ISI => |caller: Caller<state::Trigger<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::Trigger<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_TRIGGER_PAYLOAD => |caller: Caller<state::Trigger<'wrld, 'block, 'state>>| Runtime::get_trigger_payload(caller),
             )?;
             Ok(linker)
         })
@@ -1589,13 +1655,13 @@ impl<'wrld, 'block, 'state>
             let mut linker = Linker::new(engine);
 
             create_imports!(linker, state::executor::ValidateTransaction<'wrld, 'block, 'state>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_MIGRATE_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
-                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
-                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
-                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
-                export::SET_DATA_MODEL => |caller: ::wasmtime::Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
+                export::EXECUTE_ISI => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_MIGRATE_PAYLOAD => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
+                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
+                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
+                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
+                export::SET_DATA_MODEL => |caller: Caller<state::executor::ValidateTransaction<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
             )?;
             Ok(linker)
         })
@@ -1617,13 +1683,13 @@ impl<'wrld, 'block, 'state>
             let mut linker = Linker::new(engine);
 
             create_imports!(linker, state::executor::ValidateInstruction<'wrld, 'block, 'state>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_MIGRATE_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
-                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
-                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
-                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
-                export::SET_DATA_MODEL => |caller: ::wasmtime::Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
+                export::EXECUTE_ISI => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_MIGRATE_PAYLOAD => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
+                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
+                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
+                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
+                export::SET_DATA_MODEL => |caller: Caller<state::executor::ValidateInstruction<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
             )?;
             Ok(linker)
         })
@@ -1642,13 +1708,13 @@ impl<'wrld, S: StateReadOnly> RuntimeBuilder<state::executor::ValidateQuery<'wrl
 
             // NOTE: doesn't need closure here because `ValidateQuery` is covariant over 'wrld so 'static can be used and substituted with appropriate lifetime
             create_imports!(linker, state::executor::ValidateQuery<'_, S>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_MIGRATE_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_migrate_payload(caller),
-                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_transaction_payload(caller),
-                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_instruction_payload(caller),
-                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_query_payload(caller),
-                export::SET_DATA_MODEL => |caller: ::wasmtime::Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::set_data_model(caller, offset, len),
+                export::EXECUTE_ISI => |caller: Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_MIGRATE_PAYLOAD => |caller: Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_migrate_payload(caller),
+                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_transaction_payload(caller),
+                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_instruction_payload(caller),
+                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: Caller<state::executor::ValidateQuery<'_, S>>| Runtime::get_validate_query_payload(caller),
+                export::SET_DATA_MODEL => |caller: Caller<state::executor::ValidateQuery<'_, S>>, offset, len| Runtime::set_data_model(caller, offset, len),
             )?;
             Ok(linker)
         })
@@ -1667,33 +1733,33 @@ impl<'wrld, 'block, 'state> RuntimeBuilder<state::executor::Migrate<'wrld, 'bloc
             let mut linker = Linker::new(engine);
 
             create_imports!(linker, state::executor::Migrate<'wrld, 'block, 'state>,
-                export::EXECUTE_ISI => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
-                export::EXECUTE_QUERY => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
-                export::GET_MIGRATE_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
-                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
-                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
-                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
-                export::SET_DATA_MODEL => |caller: ::wasmtime::Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
+                export::EXECUTE_ISI => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::execute_instruction(caller, offset, len),
+                export::EXECUTE_QUERY => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::execute_query(caller, offset, len),
+                export::GET_MIGRATE_PAYLOAD => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_migrate_payload(caller),
+                export::GET_VALIDATE_TRANSACTION_PAYLOAD => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_transaction_payload(caller),
+                export::GET_VALIDATE_INSTRUCTION_PAYLOAD => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_instruction_payload(caller),
+                export::GET_VALIDATE_QUERY_PAYLOAD => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>| Runtime::get_validate_query_payload(caller),
+                export::SET_DATA_MODEL => |caller: Caller<state::executor::Migrate<'wrld, 'block, 'state>>, offset, len| Runtime::set_data_model(caller, offset, len),
             )?;
             Ok(linker)
         })
     }
 }
 
-/// Helper trait to make a function generic over `get_export()` fn from `wasmtime` crate
+/// Helper trait to make a function generic over `get_export()` fn of the WASM engine
 trait GetExport {
-    fn get_export(&mut self, name: &str) -> Option<wasmtime::Extern>;
+    fn get_export(&mut self, name: &str) -> Option<engine::Extern>;
 }
 
 impl<T> GetExport for Caller<'_, T> {
-    fn get_export(&mut self, name: &str) -> Option<wasmtime::Extern> {
+    fn get_export(&mut self, name: &str) -> Option<engine::Extern> {
         Self::get_export(self, name)
     }
 }
 
-impl<C: wasmtime::AsContextMut> GetExport for (&wasmtime::Instance, C) {
-    fn get_export(&mut self, name: &str) -> Option<wasmtime::Extern> {
-        wasmtime::Instance::get_export(self.0, &mut self.1, name)
+impl<C: engine::AsContextMut> GetExport for (&engine::Instance, C) {
+    fn get_export(&mut self, name: &str) -> Option<engine::Extern> {
+        engine::Instance::get_export(self.0, &mut self.1, name)
     }
 }
 
@@ -0,0 +1,160 @@
+//! This module contains [`ModuleCache`], a cache of compiled wasm modules.
+//!
+//! Compiling a module takes much longer than instantiating it, so smart contracts
+//! submitted over and over again are compiled once and then looked up by the hash
+//! of their blob. Modules of registered triggers go through this cache as well, so
+//! only the modules of recently executed triggers are kept in memory and the modules
+//! of the cold ones are evicted.
+
+use std::time::{Duration, Instant};
+
+use indexmap::IndexMap;
+use iroha_crypto::Hash;
+
+use super::{
+    engine::{Engine, Module},
+    load_module, Result,
+};
+
+/// Least recently used cache of compiled modules keyed by the hash of the wasm blob
+#[derive(Debug, Default)]
+pub struct ModuleCache {
+    /// Maximum number of cached modules, caching is disabled if `0`
+    capacity: usize,
+    /// Modules ordered from the least to the most recently used
+    modules: IndexMap<Hash, CachedModule>,
+    stats: ModuleCacheStats,
+}
+
+#[derive(Debug)]
+struct CachedModule {
+    module: Module,
+    /// Time it took to compile the module
+    compile_time: Duration,
+}
+
+/// Counters of the [`ModuleCache`] usage since the peer start
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// Number of modules found in the cache
+    pub hits: u64,
+    /// Number of modules which had to be compiled
+    pub misses: u64,
+    /// Compilation time saved by the cache hits
+    pub compile_time_saved: Duration,
+    /// Number of modules in the cache
+    pub size: usize,
+}
+
+impl ModuleCache {
+    /// Create cache holding at most `capacity` modules
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Change the maximum number of cached modules, evicting the least recently used ones
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// Get the module compiled from `bytes`, compiling and caching it if it isn't cached yet.
+    ///
+    /// # Errors
+    ///
+    /// See [`load_module`]
+    pub fn get_or_load(&mut self, engine: &Engine, bytes: impl AsRef<[u8]>) -> Result<Module> {
+        let hash = Hash::new(bytes.as_ref());
+
+        if let Some(index) = self.modules.get_index_of(&hash) {
+            let last = self.modules.len() - 1;
+            self.modules.move_index(index, last);
+            let (_, cached) = self.modules.get_index(last).expect("Index is in bounds");
+            self.stats.hits += 1;
+            self.stats.compile_time_saved += cached.compile_time;
+            return Ok(cached.module.clone()); // Cloning module is cheap, under Arc inside
+        }
+
+        let started_at = Instant::now();
+        let module = load_module(engine, bytes)?;
+        let compile_time = started_at.elapsed();
+        self.stats.misses += 1;
+
+        if self.capacity > 0 {
+            self.modules.insert(
+                hash,
+                CachedModule {
+                    module: module.clone(),
+                    compile_time,
+                },
+            );
+            self.evict();
+        }
+
+        Ok(module)
+    }
+
+    /// Usage counters of the cache
+    pub fn stats(&self) -> ModuleCacheStats {
+        ModuleCacheStats {
+            size: self.modules.len(),
+            ..self.stats
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.modules.len() > self.capacity {
+            self.modules.shift_remove_index(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smartcontracts::wasm::create_engine;
+
+    fn module(name: &str) -> Vec<u8> {
+        format!(r#"(module (func (export "{name}")))"#).into_bytes()
+    }
+
+    #[test]
+    fn least_recently_used_module_is_evicted() -> Result<()> {
+        let engine = create_engine();
+        let mut cache = ModuleCache::new(2);
+
+        cache.get_or_load(&engine, module("a"))?;
+        cache.get_or_load(&engine, module("b"))?;
+        // `a` becomes the most recently used one
+        cache.get_or_load(&engine, module("a"))?;
+        cache.get_or_load(&engine, module("c"))?;
+        // `b` has been evicted
+        cache.get_or_load(&engine, module("b"))?;
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 4);
+        assert_eq!(stats.size, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() -> Result<()> {
+        let engine = create_engine();
+        let mut cache = ModuleCache::new(0);
+
+        cache.get_or_load(&engine, module("a"))?;
+        cache.get_or_load(&engine, module("a"))?;
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.size, 0);
+
+        Ok(())
+    }
+}
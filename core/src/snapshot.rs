@@ -1,6 +1,6 @@
 //! This module contains [`State`] snapshot actor service.
 use std::{
-    io::Read,
+    io::{Read, Write},
     num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::Arc,
@@ -8,12 +8,13 @@ use std::{
 };
 
 use iroha_config::{parameters::actual::Snapshot as Config, snapshot::Mode};
-use iroha_crypto::HashOf;
+use iroha_crypto::{Hash, HashAlgorithm, HashOf, Hasher};
 use iroha_data_model::block::SignedBlock;
 use iroha_logger::prelude::*;
 use serde::{de::DeserializeSeed, Serialize};
 use tokio::sync::mpsc;
 
+use self::migration::{MigrationError, MigrationRegistry};
 use crate::{
     kura::{BlockCount, Kura},
     query::store::LiveQueryStoreHandle,
@@ -24,6 +25,17 @@ use crate::{
 const SNAPSHOT_FILE_NAME: &str = "snapshot.data";
 /// Name of the temporary [`State`] snapshot file.
 const SNAPSHOT_TMP_FILE_NAME: &str = "snapshot.tmp";
+/// Name of the file with the checksum of the [`State`] snapshot file.
+const SNAPSHOT_CHECKSUM_FILE_NAME: &str = "snapshot.checksum";
+/// Name of the temporary file with the checksum of the [`State`] snapshot file.
+const SNAPSHOT_CHECKSUM_TMP_FILE_NAME: &str = "snapshot.checksum.tmp";
+/// Name of the file with the layout version of the [`State`] snapshot file.
+const SNAPSHOT_VERSION_FILE_NAME: &str = "snapshot.version";
+/// Name of the temporary file with the layout version of the [`State`] snapshot file.
+const SNAPSHOT_VERSION_TMP_FILE_NAME: &str = "snapshot.version.tmp";
+
+pub mod export;
+pub mod migration;
 
 // /// Errors produced by [`SnapshotMaker`] actor.
 // pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -42,6 +54,8 @@ pub struct SnapshotMaker {
     create_every: Duration,
     /// Path to the directory where snapshots are stored
     store_dir: PathBuf,
+    /// Algorithm of the snapshot checksum
+    checksum_algorithm: HashAlgorithm,
     /// Hash of the latest block stored in the state
     latest_block_hash: Option<HashOf<SignedBlock>>,
 }
@@ -82,6 +96,7 @@ impl SnapshotMaker {
     /// Invoke snapshot creation task
     async fn create_snapshot(&mut self) {
         let store_dir = self.store_dir.clone();
+        let checksum_algorithm = self.checksum_algorithm;
         let latest_block_hash;
         let at_height;
         {
@@ -94,7 +109,7 @@ impl SnapshotMaker {
             let state = self.state.clone();
             let handle = tokio::task::spawn_blocking(move || -> Result<(), TryWriteError> {
                 // TODO: enhance error by attaching `store_dir` parameter origin
-                try_write_snapshot(&state, store_dir)
+                try_write_snapshot(&state, store_dir, checksum_algorithm)
             });
 
             match handle.await {
@@ -122,6 +137,7 @@ impl SnapshotMaker {
                 state,
                 create_every: config.create_every_ms.get(),
                 store_dir: config.store_dir.resolve_relative_path(),
+                checksum_algorithm: config.checksum_algorithm,
                 latest_block_hash,
             })
         } else {
@@ -132,8 +148,13 @@ impl SnapshotMaker {
 
 /// Try to deserialize [`State`] from a snapshot file.
 ///
+/// The snapshot is checked against its checksum, if there is one, and brought up to date
+/// with the [migrations](migration::MIGRATIONS) shipped with this release.
+///
 /// # Errors
 /// - IO errors
+/// - Checksum mismatch
+/// - Missing or failed migrations
 /// - Deserialization errors
 pub fn try_read_snapshot(
     store_dir: impl AsRef<Path>,
@@ -155,12 +176,20 @@ pub fn try_read_snapshot(
     };
     file.read_to_end(&mut bytes)
         .map_err(|err| TryReadError::IO(err, path.clone()))?;
-    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    verify_checksum(store_dir.as_ref(), &bytes)?;
+    let version = read_version(store_dir.as_ref())?;
     let seed = KuraSeed {
         kura: Arc::clone(kura),
         query_handle,
     };
-    let state = seed.deserialize(&mut deserializer)?;
+    let state = if version == migration::VERSION {
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        seed.deserialize(&mut deserializer)?
+    } else {
+        let mut snapshot: serde_json::Value = serde_json::from_slice(&bytes)?;
+        MigrationRegistry::default().migrate(&mut snapshot, version, migration::VERSION)?;
+        seed.deserialize(snapshot)?
+    };
     let state_view = state.view();
     let snapshot_height = state_view.height();
     if snapshot_height > block_count {
@@ -193,13 +222,79 @@ pub fn try_read_snapshot(
     Ok(state)
 }
 
-/// Serialize and write snapshot to file,
+/// Read the layout version stored next to the snapshot.
+/// Snapshots without a version were written before the layout was versioned and have version 1.
+fn read_version(store_dir: &Path) -> Result<u32, TryReadError> {
+    let path = store_dir.join(SNAPSHOT_VERSION_FILE_NAME);
+    match std::fs::read_to_string(&path) {
+        Ok(version) => version
+            .trim()
+            .parse()
+            .map_err(|_| TryReadError::MalformedVersion(path)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(1),
+        Err(err) => Err(TryReadError::IO(err, path)),
+    }
+}
+
+/// Check the snapshot `bytes` against the checksum stored next to them.
+/// Snapshots without a checksum, e.g. written by older versions, aren't checked.
+fn verify_checksum(store_dir: &Path, bytes: &[u8]) -> Result<(), TryReadError> {
+    let path = store_dir.join(SNAPSHOT_CHECKSUM_FILE_NAME);
+    let checksum = match std::fs::read_to_string(&path) {
+        Ok(checksum) => checksum,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(TryReadError::IO(err, path)),
+    };
+    let (algorithm, expected): (HashAlgorithm, Hash) = checksum
+        .trim()
+        .split_once(':')
+        .and_then(|(algorithm, hash)| Some((algorithm.parse().ok()?, hash.parse().ok()?)))
+        .ok_or(TryReadError::MalformedChecksum(path))?;
+    let actual = Hash::new_with(algorithm, bytes);
+    if actual != expected {
+        return Err(TryReadError::MismatchedChecksum {
+            algorithm,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Writer computing the checksum of everything written through it
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Serialize and write snapshot to file together with its checksum,
 /// overwriting any previously stored data.
 ///
 /// # Errors
 /// - IO errors
 /// - Serialization errors
-fn try_write_snapshot(state: &State, store_dir: impl AsRef<Path>) -> Result<(), TryWriteError> {
+pub fn try_write_snapshot(
+    state: &State,
+    store_dir: impl AsRef<Path>,
+    checksum_algorithm: HashAlgorithm,
+) -> Result<(), TryWriteError> {
+    {
+        // Snapshot ahead of the blocks on disk is rejected on restart
+        let state_view = state.view();
+        state_view.kura().wait_until_persisted(state_view.height());
+    }
     std::fs::create_dir_all(store_dir.as_ref())
         .map_err(|err| TryWriteError::IO(err, store_dir.as_ref().to_path_buf()))?;
     let path_to_file = store_dir.as_ref().join(SNAPSHOT_FILE_NAME);
@@ -210,10 +305,37 @@ fn try_write_snapshot(state: &State, store_dir: impl AsRef<Path>) -> Result<(),
         .truncate(true)
         .open(&path_to_tmp_file)
         .map_err(|err| TryWriteError::IO(err, path_to_tmp_file.clone()))?;
-    let mut serializer = serde_json::Serializer::new(file);
+    let mut serializer = serde_json::Serializer::new(ChecksumWriter {
+        inner: file,
+        hasher: checksum_algorithm.hasher(),
+    });
     state.serialize(&mut serializer)?;
+    let checksum = serializer.into_inner().hasher.finalize();
+
+    let path_to_checksum_file = store_dir.as_ref().join(SNAPSHOT_CHECKSUM_FILE_NAME);
+    let path_to_checksum_tmp_file = store_dir.as_ref().join(SNAPSHOT_CHECKSUM_TMP_FILE_NAME);
+    std::fs::write(
+        &path_to_checksum_tmp_file,
+        format!("{checksum_algorithm}:{checksum}"),
+    )
+    .map_err(|err| TryWriteError::IO(err, path_to_checksum_tmp_file.clone()))?;
+    let path_to_version_file = store_dir.as_ref().join(SNAPSHOT_VERSION_FILE_NAME);
+    let path_to_version_tmp_file = store_dir.as_ref().join(SNAPSHOT_VERSION_TMP_FILE_NAME);
+    std::fs::write(&path_to_version_tmp_file, migration::VERSION.to_string())
+        .map_err(|err| TryWriteError::IO(err, path_to_version_tmp_file.clone()))?;
+    // Snapshot without a checksum is better than one with the checksum of the previous snapshot
+    match std::fs::remove_file(&path_to_checksum_file) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(TryWriteError::IO(err, path_to_checksum_file));
+        }
+        _ => {}
+    }
     std::fs::rename(path_to_tmp_file, &path_to_file)
         .map_err(|err| TryWriteError::IO(err, path_to_file.clone()))?;
+    std::fs::rename(path_to_checksum_tmp_file, &path_to_checksum_file)
+        .map_err(|err| TryWriteError::IO(err, path_to_checksum_file.clone()))?;
+    std::fs::rename(path_to_version_tmp_file, &path_to_version_file)
+        .map_err(|err| TryWriteError::IO(err, path_to_version_file.clone()))?;
     Ok(())
 }
 
@@ -226,6 +348,21 @@ pub enum TryReadError {
     IO(#[source] std::io::Error, PathBuf),
     /// Error (de)serializing state snapshot
     Serialization(#[from] serde_json::Error),
+    /// Snapshot checksum file {0:?} is malformed
+    MalformedChecksum(PathBuf),
+    /// Snapshot version file {0:?} is malformed
+    MalformedVersion(PathBuf),
+    /// Unable to migrate the snapshot to the current layout
+    Migration(#[from] MigrationError),
+    /// Snapshot is corrupted. Its `algorithm` checksum is `actual` while `expected` was stored
+    MismatchedChecksum {
+        /// Algorithm of the checksum
+        algorithm: HashAlgorithm,
+        /// Checksum stored next to the snapshot
+        expected: Hash,
+        /// Checksum of the read snapshot
+        actual: Hash,
+    },
     /// Snapshot is in a non-consistent state. Snapshot has greater height (`snapshot_height`) than kura block store (`kura_height`)
     MismatchedHeight {
         /// The amount of block hashes stored by snapshot
@@ -246,7 +383,7 @@ pub enum TryReadError {
 
 /// Error variants for snapshot writing
 #[derive(thiserror::Error, Debug, displaydoc::Display)]
-enum TryWriteError {
+pub enum TryWriteError {
     /// Failed reading/writing {1:?} from disk
     IO(#[source] std::io::Error, PathBuf),
     /// Error (de)serializing World State View snapshot
@@ -283,7 +420,7 @@ mod tests {
         let snapshot_store_dir = tmp_root.path().join("path/to/snapshot/dir");
         let state = state_factory();
 
-        try_write_snapshot(&state, &snapshot_store_dir).unwrap();
+        try_write_snapshot(&state, &snapshot_store_dir, HashAlgorithm::default()).unwrap();
 
         assert!(Path::exists(snapshot_store_dir.as_path()))
     }
@@ -294,7 +431,7 @@ mod tests {
         let store_dir = tmp_root.path().join("snapshot");
         let state = state_factory();
 
-        try_write_snapshot(&state, &store_dir).unwrap();
+        try_write_snapshot(&state, &store_dir, HashAlgorithm::default()).unwrap();
         let _wsv = try_read_snapshot(
             &store_dir,
             &Kura::blank_kura_for_testing(),
@@ -304,6 +441,36 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    async fn corrupted_snapshot_is_detected() {
+        let tmp_root = tempdir().unwrap();
+        let store_dir = tmp_root.path().join("snapshot");
+        let state = state_factory();
+
+        try_write_snapshot(&state, &store_dir, HashAlgorithm::Sha256).unwrap();
+        let path = store_dir.join(SNAPSHOT_FILE_NAME);
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.push(b' ');
+        std::fs::write(&path, bytes).unwrap();
+
+        let Err(error) = try_read_snapshot(
+            &store_dir,
+            &Kura::blank_kura_for_testing(),
+            LiveQueryStore::test().start(),
+            BlockCount(state.view().height()),
+        ) else {
+            panic!("should not be ok")
+        };
+
+        assert!(matches!(
+            error,
+            TryReadError::MismatchedChecksum {
+                algorithm: HashAlgorithm::Sha256,
+                ..
+            }
+        ));
+    }
+
     #[test]
     async fn cannot_find_snapshot_on_read_is_not_found() {
         let tmp_root = tempdir().unwrap();
@@ -343,6 +510,31 @@ mod tests {
         assert_eq!(format!("{error}"), "Error (de)serializing state snapshot");
     }
 
+    #[test]
+    async fn refuses_snapshot_of_newer_version() {
+        let tmp_root = tempdir().unwrap();
+        let store_dir = tmp_root.path().join("snapshot");
+        let state = state_factory();
+
+        try_write_snapshot(&state, &store_dir, HashAlgorithm::default()).unwrap();
+        std::fs::write(
+            store_dir.join(SNAPSHOT_VERSION_FILE_NAME),
+            (migration::VERSION + 1).to_string(),
+        )
+        .unwrap();
+        let result = try_read_snapshot(
+            &store_dir,
+            &Kura::blank_kura_for_testing(),
+            LiveQueryStore::test().start(),
+            BlockCount(state.view().height()),
+        );
+
+        assert!(matches!(
+            result,
+            Err(TryReadError::Migration(MigrationError::Newer { .. }))
+        ));
+    }
+
     #[test]
     async fn can_read_multiple_blocks() {
         let tmp_root = tempdir().unwrap();
@@ -389,7 +581,7 @@ mod tests {
         }
         kura.store_block(committed_block);
 
-        try_write_snapshot(&state, &store_dir).unwrap();
+        try_write_snapshot(&state, &store_dir, HashAlgorithm::default()).unwrap();
 
         let state = try_read_snapshot(
             &store_dir,
@@ -461,7 +653,7 @@ mod tests {
             .unwrap();
         kura.store_block(committed_block);
 
-        try_write_snapshot(&state, &store_dir).unwrap();
+        try_write_snapshot(&state, &store_dir, HashAlgorithm::default()).unwrap();
 
         let state = try_read_snapshot(
             &store_dir,
@@ -0,0 +1,420 @@
+//! Portable export of the world state.
+//!
+//! Unlike the [`State`] snapshot, which is an internal format tied to the version of the peer,
+//! an export holds the world state at a single height as plain data model entities encoded
+//! with JSON or SCALE. It's meant for forensic analysis of the state outside of the peer and
+//! for migrating the state into a fresh peer, e.g. one with another storage backend.
+//!
+//! Only the world state is exported. The importing peer has to store the blocks up to the
+//! exported height, the block hashes and committed transactions are restored from them.
+
+use std::{fmt, num::NonZeroUsize, str::FromStr, sync::Arc};
+
+use iroha_crypto::{Hash, HashOf};
+use iroha_data_model::{
+    block::SignedBlock,
+    executor::{Executor as RawExecutor, ExecutorDataModel},
+    parameter::Parameters,
+    permission::Permissions,
+    prelude::*,
+};
+use iroha_primitives::numeric::Numeric;
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+use serde::{Deserialize, Serialize};
+use storage::{cell::Cell, storage::StorageReadOnly};
+
+use crate::{
+    consistency::world_digest,
+    executor::Executor,
+    kura::Kura,
+    query::store::LiveQueryStoreHandle,
+    role::RoleIdWithOwner,
+    smartcontracts::triggers::set::{Delegation, SetReadOnly as _},
+    state::{State, StateReadOnly, World, WorldReadOnly},
+};
+
+/// Encoding of a [`StateExport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// JSON, for inspection with general purpose tools
+    #[default]
+    Json,
+    /// SCALE, compact and lossless
+    Scale,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Scale => write!(f, "scale"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "json" => Ok(Self::Json),
+            "scale" => Ok(Self::Scale),
+            _ => Err(UnknownFormat(format.to_owned())),
+        }
+    }
+}
+
+/// Unknown export format `{0}`, expected `json` or `scale`
+#[derive(Debug, Clone, thiserror::Error, displaydoc::Display)]
+pub struct UnknownFormat(String);
+
+/// World state at a single height
+#[derive(Debug, Clone, Encode, Decode, Serialize, Deserialize)]
+pub struct StateExport {
+    /// Height of the latest block applied to the exported state
+    pub height: u64,
+    /// Hash of the block at [`Self::height`]
+    pub block_hash: HashOf<SignedBlock>,
+    /// [`world_digest`] of the exported state
+    pub digest: Hash,
+    /// Topology which committed the block at [`Self::height`]
+    pub commit_topology: Vec<PeerId>,
+    /// Topology which committed the block preceding [`Self::height`]
+    pub prev_commit_topology: Vec<PeerId>,
+    /// On-chain parameters
+    pub parameters: Parameters,
+    /// Trusted peers
+    pub trusted_peers: Vec<PeerId>,
+    /// Registered domains
+    pub domains: Vec<Domain>,
+    /// Registered accounts
+    pub accounts: Vec<Account>,
+    /// Registered asset definitions
+    pub asset_definitions: Vec<AssetDefinition>,
+    /// Total quantities of the asset definitions
+    pub asset_total_quantities: Vec<(AssetDefinitionId, Numeric)>,
+    /// Registered assets
+    pub assets: Vec<Asset>,
+    /// Registered roles
+    pub roles: Vec<Role>,
+    /// Permissions granted to accounts
+    pub account_permissions: Vec<(AccountId, Permissions)>,
+    /// Roles granted to accounts
+    pub account_roles: Vec<RoleIdWithOwner>,
+    /// Assets locked in escrows
+    pub escrows: Vec<Escrow>,
+    /// Certificates of the trusted peers
+    pub peer_certificates: Vec<PeerCertificate>,
+    /// Guardians and pending recoveries of the accounts
+    pub recovery_policies: Vec<RecoveryPolicy>,
+    /// Client applications to which transactions are attributed
+    pub apps: Vec<App>,
+    /// Proxy tokens granted by the accounts
+    pub proxies: Vec<ProxyToken>,
+    /// Registered triggers
+    pub triggers: Vec<Trigger>,
+    /// Authority delegated to triggers
+    pub delegations: Vec<(TriggerId, Delegation)>,
+    /// User-provided executor, `None` for the initial one
+    pub executor: Option<RawExecutor>,
+    /// Executor-defined data model
+    pub executor_data_model: ExecutorDataModel,
+}
+
+/// Error of [`StateExport::decode_as`] and [`StateExport::into_state`]
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum ImportError {
+    /// Export isn't valid JSON
+    Json(#[from] serde_json::Error),
+    /// Export isn't valid SCALE
+    Scale(#[from] parity_scale_codec::Error),
+    /// Block {0} of the exported state isn't stored by this peer
+    MissingBlock(u64),
+    /// Block {height} stored by this peer has hash {actual}, while the state was exported at {expected}
+    MismatchedBlockHash {
+        /// Height of the exported state
+        height: u64,
+        /// Hash of the block in the export
+        expected: HashOf<SignedBlock>,
+        /// Hash of the block stored by this peer
+        actual: HashOf<SignedBlock>,
+    },
+    /// Imported world state has digest {actual}, while {expected} was exported
+    MismatchedDigest {
+        /// Digest in the export
+        expected: Hash,
+        /// Digest of the imported world state
+        actual: Hash,
+    },
+    /// Failed to load the exported executor
+    Executor(#[source] crate::smartcontracts::wasm::error::Error),
+    /// Failed to load trigger `{0}`
+    Trigger(TriggerId),
+}
+
+impl StateExport {
+    /// Export the world state of `state`.
+    ///
+    /// Returns `None` if no block is applied to the state yet.
+    pub fn new(state: &impl StateReadOnly) -> Option<Self> {
+        fn collect<K: Clone, V: Clone>(storage: &impl StorageReadOnly<K, V>) -> Vec<(K, V)> {
+            storage
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        }
+        fn values<K, V: Clone>(storage: &impl StorageReadOnly<K, V>) -> Vec<V> {
+            storage.iter().map(|(_, value)| value.clone()).collect()
+        }
+
+        let block_hash = state.latest_block_hash()?;
+        let world = state.world();
+        let triggers = world
+            .triggers()
+            .ids_iter()
+            .filter_map(|id| {
+                #[allow(clippy::redundant_closure_for_method_calls)]
+                let action = world
+                    .triggers()
+                    .inspect_by_id(id, |action| action.clone_and_box())?;
+                let action = world.triggers().get_original_action(action);
+                Some(Trigger::new(id.clone(), action.into()))
+            })
+            .collect();
+        let executor = match world.executor() {
+            Executor::Initial => None,
+            Executor::UserProvided(executor) => Some(executor.raw_executor().clone()),
+        };
+
+        Some(Self {
+            height: state.height() as u64,
+            block_hash,
+            digest: world_digest(world),
+            commit_topology: state.commit_topology().to_vec(),
+            prev_commit_topology: state.prev_commit_topology().to_vec(),
+            parameters: world.parameters().clone(),
+            trusted_peers: world.trusted_peers_ids().iter().cloned().collect(),
+            domains: values(world.domains()),
+            accounts: values(world.accounts()),
+            asset_definitions: values(world.asset_definitions()),
+            asset_total_quantities: collect(world.asset_total_quantities()),
+            assets: values(world.assets()),
+            roles: values(world.roles()),
+            account_permissions: collect(world.account_permissions()),
+            account_roles: world
+                .account_roles()
+                .iter()
+                .map(|(role, ())| role.clone())
+                .collect(),
+            escrows: values(world.escrows()),
+            peer_certificates: values(world.peer_certificates()),
+            recovery_policies: values(world.recovery_policies()),
+            apps: values(world.apps()),
+            proxies: values(world.proxies()),
+            triggers,
+            delegations: collect(world.triggers().delegations()),
+            executor,
+            executor_data_model: world.executor_data_model().clone(),
+        })
+    }
+
+    /// Encode the export in the given `format`
+    ///
+    /// # Panics
+    /// Never, all of the exported entities are serializable to JSON
+    pub fn encode_as(&self, format: Format) -> Vec<u8> {
+        match format {
+            Format::Json => serde_json::to_vec_pretty(self)
+                .expect("INTERNAL BUG: exported state isn't serializable to JSON"),
+            Format::Scale => self.encode(),
+        }
+    }
+
+    /// Decode the export encoded in the given `format`
+    ///
+    /// # Errors
+    /// If `bytes` aren't a valid export
+    pub fn decode_as(bytes: &[u8], format: Format) -> Result<Self, ImportError> {
+        match format {
+            Format::Json => Ok(serde_json::from_slice(bytes)?),
+            Format::Scale => Ok(Self::decode_all(&mut &bytes[..])?),
+        }
+    }
+
+    /// Reconstruct the [`State`] on top of the blocks stored in `kura`.
+    ///
+    /// # Errors
+    /// - If `kura` doesn't store the block the state was exported at
+    /// - If the reconstructed world state doesn't match the exported digest
+    /// - If the executor or a trigger can't be loaded
+    pub fn into_state(
+        self,
+        kura: Arc<Kura>,
+        query_handle: LiveQueryStoreHandle,
+    ) -> Result<State, ImportError> {
+        let block_hashes = (1..=self.height)
+            .map(|height| {
+                usize::try_from(height)
+                    .ok()
+                    .and_then(NonZeroUsize::new)
+                    .and_then(|height| kura.get_block_hash(height))
+                    .ok_or(ImportError::MissingBlock(height))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let actual = *block_hashes
+            .last()
+            .ok_or(ImportError::MissingBlock(self.height))?;
+        if actual != self.block_hash {
+            return Err(ImportError::MismatchedBlockHash {
+                height: self.height,
+                expected: self.block_hash,
+                actual,
+            });
+        }
+
+        let mut transactions = Vec::new();
+        for height in (1..=block_hashes.len()).filter_map(NonZeroUsize::new) {
+            let block = kura
+                .get_block_by_height(height)
+                .ok_or(ImportError::MissingBlock(height.get() as u64))?;
+            transactions.extend(block.transactions().map(|tx| (tx.value.hash(), height)));
+        }
+
+        let world = World {
+            parameters: Cell::new(self.parameters),
+            trusted_peers_ids: Cell::new(self.trusted_peers.into_iter().collect()),
+            domains: self
+                .domains
+                .into_iter()
+                .map(|domain| (domain.id().clone(), domain))
+                .collect(),
+            accounts: self
+                .accounts
+                .into_iter()
+                .map(|account| (account.id().clone(), account))
+                .collect(),
+            asset_definitions: self
+                .asset_definitions
+                .into_iter()
+                .map(|definition| (definition.id().clone(), definition))
+                .collect(),
+            asset_total_quantities: self.asset_total_quantities.into_iter().collect(),
+            assets: self
+                .assets
+                .into_iter()
+                .map(|asset| (asset.id().clone(), asset))
+                .collect(),
+            roles: self
+                .roles
+                .into_iter()
+                .map(|role| (role.id().clone(), role))
+                .collect(),
+            account_permissions: self.account_permissions.into_iter().collect(),
+            account_roles: self
+                .account_roles
+                .into_iter()
+                .map(|role| (role, ()))
+                .collect(),
+            escrows: self
+                .escrows
+                .into_iter()
+                .map(|escrow| (escrow.id().clone(), escrow))
+                .collect(),
+            peer_certificates: self
+                .peer_certificates
+                .into_iter()
+                .map(|certificate| (certificate.peer().public_key.clone(), certificate))
+                .collect(),
+            recovery_policies: self
+                .recovery_policies
+                .into_iter()
+                .map(|policy| (policy.account().clone(), policy))
+                .collect(),
+            apps: self
+                .apps
+                .into_iter()
+                .map(|app| (app.id().clone(), app))
+                .collect(),
+            proxies: self
+                .proxies
+                .into_iter()
+                .map(|token| ((token.account().clone(), token.proxy().clone()), token))
+                .collect(),
+            executor_data_model: Cell::new(self.executor_data_model),
+            ..World::new()
+        };
+        let mut state = State::new(world, kura, query_handle);
+        state.block_hashes = Cell::new(block_hashes);
+        state.transactions = transactions.into_iter().collect();
+        state.commit_topology = Cell::new(self.commit_topology);
+        state.prev_commit_topology = Cell::new(self.prev_commit_topology);
+        if let Some(executor) = self.executor {
+            state.world.executor =
+                Cell::new(Executor::load(&state.engine, executor).map_err(ImportError::Executor)?);
+        }
+        restore_triggers(&mut state, self.triggers, self.delegations)?;
+
+        let actual = world_digest(state.view().world());
+        if actual != self.digest {
+            return Err(ImportError::MismatchedDigest {
+                expected: self.digest,
+                actual,
+            });
+        }
+
+        Ok(state)
+    }
+}
+
+fn restore_triggers(
+    state: &mut State,
+    triggers: Vec<Trigger>,
+    delegations: Vec<(TriggerId, Delegation)>,
+) -> Result<(), ImportError> {
+    let engine = state.engine.clone(); // Cloning engine is cheap
+    let mut set_block = state.world.triggers.block();
+    let mut set = set_block.transaction();
+    for trigger in triggers {
+        let id = trigger.id().clone();
+        let added = match &trigger.action.filter {
+            EventFilterBox::Data(_) => trigger
+                .try_into()
+                .map(|trigger| set.add_data_trigger(&engine, trigger)),
+            EventFilterBox::Pipeline(_) => trigger
+                .try_into()
+                .map(|trigger| set.add_pipeline_trigger(&engine, trigger)),
+            EventFilterBox::Time(_) => trigger
+                .try_into()
+                .map(|trigger| set.add_time_trigger(&engine, trigger)),
+            EventFilterBox::ExecuteTrigger(_) => trigger
+                .try_into()
+                .map(|trigger| set.add_by_call_trigger(&engine, trigger)),
+            EventFilterBox::TriggerCompleted(_) => {
+                Err("Triggers can't listen to trigger completion")
+            }
+        };
+        if !matches!(added, Ok(Ok(true))) {
+            return Err(ImportError::Trigger(id));
+        }
+    }
+    for (id, delegation) in delegations {
+        set.delegate(id, delegation);
+    }
+    set.apply();
+    set_block.commit();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_display_repr() {
+        for format in [Format::Json, Format::Scale] {
+            assert_eq!(format.to_string().parse::<Format>().unwrap(), format);
+        }
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Migrations of the [`State`](crate::state::State) snapshot between layouts.
+//!
+//! Every snapshot is written together with the [`VERSION`] of its layout. When a release
+//! changes the layout it bumps [`VERSION`] and appends a [`Migration`] from the previous
+//! version to [`MIGRATIONS`]. On startup a snapshot of an older version is brought up to date
+//! by applying the migrations in order, so the peer doesn't have to replay the whole chain.
+//! If any migration on the way is missing the snapshot is refused.
+
+use serde_json::Value;
+
+/// Version of the snapshot layout written by this release
+pub const VERSION: u32 = 1;
+
+/// Migrations shipped with this release, ordered by [`Migration::from`]
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// Result of a single [`Migration`] step
+pub type MigrationResult = Result<(), String>;
+
+/// Migration of the snapshot from version [`Self::from`] to the next one
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Version of the layout the migration applies to
+    pub from: u32,
+    /// Short human-readable summary of the layout change
+    pub description: &'static str,
+    /// Rewrite the serialized snapshot in place
+    pub migrate: fn(&mut Value) -> MigrationResult,
+}
+
+/// Ordered set of [`Migration`]s
+#[derive(Debug, Clone)]
+pub struct MigrationRegistry {
+    migrations: Vec<Migration>,
+}
+
+impl Default for MigrationRegistry {
+    fn default() -> Self {
+        Self {
+            migrations: MIGRATIONS.to_vec(),
+        }
+    }
+}
+
+impl MigrationRegistry {
+    /// Registry without any migrations
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Register `migration`, replacing the one registered for the same version
+    #[must_use]
+    pub fn with_migration(mut self, migration: Migration) -> Self {
+        self.migrations
+            .retain(|registered| registered.from != migration.from);
+        self.migrations.push(migration);
+        self.migrations.sort_by_key(|migration| migration.from);
+        self
+    }
+
+    /// Migrations to apply to bring a snapshot from version `from` to version `to`
+    ///
+    /// # Errors
+    /// - If the snapshot is newer than `to`
+    /// - If a migration for any of the intermediate versions isn't registered
+    pub fn plan(&self, from: u32, to: u32) -> Result<Vec<&Migration>, MigrationError> {
+        if from > to {
+            return Err(MigrationError::Newer {
+                version: from,
+                supported: to,
+            });
+        }
+        (from..to)
+            .map(|version| {
+                self.migrations
+                    .iter()
+                    .find(|migration| migration.from == version)
+                    .ok_or(MigrationError::Missing { version })
+            })
+            .collect()
+    }
+
+    /// Apply migrations to the serialized `snapshot` of version `from` to bring it to version `to`
+    ///
+    /// # Errors
+    /// - If no migration path from `from` to `to` exists, see [`Self::plan`]
+    /// - If any of the migrations fails
+    pub fn migrate(&self, snapshot: &mut Value, from: u32, to: u32) -> Result<(), MigrationError> {
+        let plan = self.plan(from, to)?;
+        let total = plan.len();
+        for (step, migration) in plan.into_iter().enumerate() {
+            iroha_logger::info!(
+                step = step + 1,
+                total,
+                from = migration.from,
+                description = migration.description,
+                "Migrating the state snapshot"
+            );
+            (migration.migrate)(snapshot).map_err(|reason| MigrationError::Failed {
+                version: migration.from,
+                reason,
+            })?;
+        }
+        if total > 0 {
+            iroha_logger::info!(from, to, "Migrated the state snapshot");
+        }
+        Ok(())
+    }
+}
+
+/// Error of migrating the snapshot
+#[derive(Debug, Clone, thiserror::Error, displaydoc::Display)]
+pub enum MigrationError {
+    /// Snapshot has layout version {version}, which is newer than the version {supported} supported by this peer
+    Newer {
+        /// Version of the snapshot
+        version: u32,
+        /// Version written by this peer
+        supported: u32,
+    },
+    /// No migration is registered for snapshot layout version {version}
+    Missing {
+        /// Version without a migration
+        version: u32,
+    },
+    /// Migration from snapshot layout version {version} failed: {reason}
+    Failed {
+        /// Version the failed migration applies to
+        version: u32,
+        /// Reason reported by the migration
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rename_world(snapshot: &mut Value) -> MigrationResult {
+        let world = snapshot
+            .as_object_mut()
+            .and_then(|snapshot| snapshot.remove("wsv"))
+            .ok_or("snapshot has no `wsv`")?;
+        snapshot["world"] = world;
+        Ok(())
+    }
+
+    fn add_field(snapshot: &mut Value) -> MigrationResult {
+        snapshot["world"]["added"] = json!(true);
+        Ok(())
+    }
+
+    fn registry() -> MigrationRegistry {
+        MigrationRegistry::empty()
+            .with_migration(Migration {
+                from: 2,
+                description: "add field",
+                migrate: add_field,
+            })
+            .with_migration(Migration {
+                from: 1,
+                description: "rename world",
+                migrate: rename_world,
+            })
+    }
+
+    #[test]
+    fn migrations_are_applied_in_order() {
+        let mut snapshot = json!({ "wsv": {} });
+
+        registry().migrate(&mut snapshot, 1, 3).unwrap();
+
+        assert_eq!(snapshot, json!({ "world": { "added": true } }));
+    }
+
+    #[test]
+    fn up_to_date_snapshot_is_left_intact() {
+        let mut snapshot = json!({ "wsv": {} });
+
+        registry().migrate(&mut snapshot, 3, 3).unwrap();
+
+        assert_eq!(snapshot, json!({ "wsv": {} }));
+    }
+
+    #[test]
+    fn missing_migration_is_refused() {
+        let registry = registry().with_migration(Migration {
+            from: 4,
+            description: "unreachable",
+            migrate: add_field,
+        });
+
+        assert!(matches!(
+            registry.plan(1, 5),
+            Err(MigrationError::Missing { version: 3 })
+        ));
+        assert!(matches!(
+            registry.plan(6, 5),
+            Err(MigrationError::Newer { version: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn failed_migration_is_reported() {
+        let mut snapshot = json!({});
+
+        assert!(matches!(
+            registry().migrate(&mut snapshot, 1, 3),
+            Err(MigrationError::Failed { version: 1, .. })
+        ));
+    }
+}
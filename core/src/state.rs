@@ -1,22 +1,26 @@
 //! This module provides the [`State`] — an in-memory representation of the current blockchain state.
 use std::{
-    collections::BTreeSet, marker::PhantomData, num::NonZeroUsize, sync::Arc, time::Duration,
+    collections::BTreeSet,
+    marker::PhantomData,
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use eyre::Result;
 use iroha_crypto::HashOf;
 use iroha_data_model::{
     account::AccountId,
-    block::SignedBlock,
+    block::{reward::BlockReward, state_diff::BlockStateDiff, SignedBlock},
     events::{
         pipeline::BlockEvent,
         time::TimeEvent,
-        trigger_completed::{TriggerCompletedEvent, TriggerCompletedOutcome},
+        trigger_completed::{TriggerCompletedEvent, TriggerCompletedOutcome, TriggerReceipt},
         EventBox,
     },
     executor::ExecutorDataModel,
-    isi::error::{InstructionExecutionError as Error, MathError},
-    parameter::Parameters,
+    isi::error::{InstructionExecutionError as Error, InvalidParameterError, MathError},
+    parameter::{FeeDistribution, FeeParameters, Parameters},
     permission::Permissions,
     prelude::*,
     query::error::{FindError, QueryExecutionFail},
@@ -24,6 +28,7 @@ use iroha_data_model::{
 };
 use iroha_logger::prelude::*;
 use iroha_primitives::{must_use::MustUse, numeric::Numeric, small::SmallVec};
+use parity_scale_codec::Encode;
 use parking_lot::Mutex;
 use range_bounds::*;
 use serde::{
@@ -38,6 +43,10 @@ use storage::{
     },
 };
 
+use self::{
+    memory::{CollectionUsage, MemoryUsage},
+    profile::BlockProfile,
+};
 use crate::{
     block::CommittedBlock,
     executor::Executor,
@@ -53,14 +62,23 @@ use crate::{
             },
             specialized::LoadedActionTrait,
         },
-        wasm, Execute,
+        wasm::{self, cache::ModuleCache},
+        Execute,
+    },
+    sumeragi::network_topology::Topology,
+    tx::{
+        cache::{ValidationCache, ValidationState},
+        TransactionExecutor,
     },
-    tx::TransactionExecutor,
     PeersIds,
 };
 
 /// The global entity consisting of `domains`, `triggers` and etc.
 /// For example registration of domain, will have this as an ISI target.
+///
+/// Every collection is ordered by key, so that iterating it (and hence the order
+/// of emitted events and executed triggers) is the same on every peer.
+/// Don't introduce hash-ordered collections here.
 #[derive(Default, Serialize)]
 pub struct World {
     /// Iroha on-chain parameters.
@@ -83,6 +101,16 @@ pub struct World {
     pub(crate) account_permissions: Storage<AccountId, Permissions>,
     /// Roles of an account.
     pub(crate) account_roles: Storage<RoleIdWithOwner, ()>,
+    /// Assets locked in escrows.
+    pub(crate) escrows: Storage<EscrowId, Escrow>,
+    /// Certificates of the trusted peers.
+    pub(crate) peer_certificates: Storage<PublicKey, PeerCertificate>,
+    /// Guardians and pending recoveries of the accounts.
+    pub(crate) recovery_policies: Storage<AccountId, RecoveryPolicy>,
+    /// Client applications to which transactions are attributed.
+    pub(crate) apps: Storage<AppId, App>,
+    /// Proxy tokens granted by the accounts, by the granting account and the proxy.
+    pub(crate) proxies: Storage<(AccountId, AccountId), ProxyToken>,
     /// Triggers
     pub(crate) triggers: TriggerSet,
     /// Runtime Executor
@@ -113,6 +141,16 @@ pub struct WorldBlock<'world> {
     pub(crate) account_permissions: StorageBlock<'world, AccountId, Permissions>,
     /// Roles of an account.
     pub(crate) account_roles: StorageBlock<'world, RoleIdWithOwner, ()>,
+    /// Assets locked in escrows.
+    pub(crate) escrows: StorageBlock<'world, EscrowId, Escrow>,
+    /// Certificates of the trusted peers.
+    pub(crate) peer_certificates: StorageBlock<'world, PublicKey, PeerCertificate>,
+    /// Guardians and pending recoveries of the accounts.
+    pub(crate) recovery_policies: StorageBlock<'world, AccountId, RecoveryPolicy>,
+    /// Client applications to which transactions are attributed.
+    pub(crate) apps: StorageBlock<'world, AppId, App>,
+    /// Proxy tokens granted by the accounts, by the granting account and the proxy.
+    pub(crate) proxies: StorageBlock<'world, (AccountId, AccountId), ProxyToken>,
     /// Triggers
     pub(crate) triggers: TriggerSetBlock<'world>,
     /// Runtime Executor
@@ -149,6 +187,16 @@ pub struct WorldTransaction<'block, 'world> {
     pub(crate) account_permissions: StorageTransaction<'block, 'world, AccountId, Permissions>,
     /// Roles of an account.
     pub(crate) account_roles: StorageTransaction<'block, 'world, RoleIdWithOwner, ()>,
+    /// Assets locked in escrows.
+    pub(crate) escrows: StorageTransaction<'block, 'world, EscrowId, Escrow>,
+    /// Certificates of the trusted peers.
+    pub(crate) peer_certificates: StorageTransaction<'block, 'world, PublicKey, PeerCertificate>,
+    /// Guardians and pending recoveries of the accounts.
+    pub(crate) recovery_policies: StorageTransaction<'block, 'world, AccountId, RecoveryPolicy>,
+    /// Client applications to which transactions are attributed.
+    pub(crate) apps: StorageTransaction<'block, 'world, AppId, App>,
+    /// Proxy tokens granted by the accounts, by the granting account and the proxy.
+    pub(crate) proxies: StorageTransaction<'block, 'world, (AccountId, AccountId), ProxyToken>,
     /// Triggers
     pub(crate) triggers: TriggerSetTransaction<'block, 'world>,
     /// Runtime Executor
@@ -191,6 +239,16 @@ pub struct WorldView<'world> {
     pub(crate) account_permissions: StorageView<'world, AccountId, Permissions>,
     /// Roles of an account.
     pub(crate) account_roles: StorageView<'world, RoleIdWithOwner, ()>,
+    /// Assets locked in escrows.
+    pub(crate) escrows: StorageView<'world, EscrowId, Escrow>,
+    /// Certificates of the trusted peers.
+    pub(crate) peer_certificates: StorageView<'world, PublicKey, PeerCertificate>,
+    /// Guardians and pending recoveries of the accounts.
+    pub(crate) recovery_policies: StorageView<'world, AccountId, RecoveryPolicy>,
+    /// Client applications to which transactions are attributed.
+    pub(crate) apps: StorageView<'world, AppId, App>,
+    /// Proxy tokens granted by the accounts, by the granting account and the proxy.
+    pub(crate) proxies: StorageView<'world, (AccountId, AccountId), ProxyToken>,
     /// Triggers
     pub(crate) triggers: TriggerSetView<'world>,
     /// Runtime Executor
@@ -211,13 +269,29 @@ pub struct State {
     pub block_hashes: Cell<Vec<HashOf<SignedBlock>>>,
     /// Hashes of transactions mapped onto block height where they stored
     pub transactions: Storage<HashOf<SignedTransaction>, NonZeroUsize>,
+    /// Idempotency keys of the recently committed transactions mapped onto their usage records
+    pub idempotency_keys: Storage<(AccountId, Name), IdempotencyRecord>,
     /// Topology used to commit latest block
     pub commit_topology: Cell<Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: Cell<Vec<PeerId>>,
+    /// State changes made by blocks mapped onto block height, recorded only if enabled
+    pub block_diffs: Storage<NonZeroUsize, BlockStateDiff>,
+    /// Events emitted while applying blocks mapped onto block height, archived only if enabled
+    pub event_archive: Storage<NonZeroUsize, Vec<EventBox>>,
+    /// Receipts of the triggers executed while applying blocks mapped onto block height
+    pub trigger_receipts: Storage<NonZeroUsize, Vec<TriggerReceipt>>,
+    /// Fees paid out at the end of blocks mapped onto block height
+    pub block_rewards: Storage<NonZeroUsize, Vec<BlockReward>>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
     #[serde(skip)]
-    pub engine: wasmtime::Engine,
+    pub engine: wasm::engine::Engine,
+    /// Smart contracts compiled by [`Self::engine`]
+    #[serde(skip)]
+    pub module_cache: Arc<Mutex<ModuleCache>>,
+    /// Rejections of the transactions validated on top of the latest block
+    #[serde(skip)]
+    pub validation_cache: Mutex<ValidationCache>,
 
     /// Reference to Kura subsystem.
     #[serde(skip)]
@@ -232,6 +306,18 @@ pub struct State {
     /// Lock to prevent getting inconsistent view of the state
     #[serde(skip)]
     view_lock: parking_lot::RwLock<()>,
+    /// Whether to record [`Self::block_diffs`]
+    #[serde(skip)]
+    record_block_diffs: bool,
+    /// Whether to record [`Self::event_archive`]
+    #[serde(skip)]
+    archive_events: bool,
+    /// Instruction execution profiles of the committed blocks not yet exported via telemetry
+    #[serde(skip)]
+    pub block_profiles: Arc<Mutex<Vec<BlockProfile>>>,
+    /// Whether to record [`Self::block_profiles`]
+    #[serde(skip)]
+    profile_instructions: bool,
 }
 
 /// Struct for block's aggregated changes
@@ -242,12 +328,31 @@ pub struct StateBlock<'state> {
     pub block_hashes: CellBlock<'state, Vec<HashOf<SignedBlock>>>,
     /// Hashes of transactions mapped onto block height where they stored
     pub transactions: StorageBlock<'state, HashOf<SignedTransaction>, NonZeroUsize>,
+    /// Idempotency keys of the recently committed transactions mapped onto their usage records
+    pub idempotency_keys: StorageBlock<'state, (AccountId, Name), IdempotencyRecord>,
     /// Topology used to commit latest block
     pub commit_topology: CellBlock<'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellBlock<'state, Vec<PeerId>>,
+    /// State changes made by blocks mapped onto block height
+    pub block_diffs: StorageBlock<'state, NonZeroUsize, BlockStateDiff>,
+    /// Events emitted while applying blocks mapped onto block height
+    pub event_archive: StorageBlock<'state, NonZeroUsize, Vec<EventBox>>,
+    /// Receipts of the triggers executed while applying blocks mapped onto block height
+    pub trigger_receipts: StorageBlock<'state, NonZeroUsize, Vec<TriggerReceipt>>,
+    /// Fees paid out at the end of blocks mapped onto block height
+    pub block_rewards: StorageBlock<'state, NonZeroUsize, Vec<BlockReward>>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
-    pub engine: &'state wasmtime::Engine,
+    pub engine: &'state wasm::engine::Engine,
+    /// Smart contracts compiled by [`Self::engine`]
+    pub module_cache: &'state Arc<Mutex<ModuleCache>>,
+    /// Rejections of the transactions validated on top of the latest block
+    validation_cache: &'state Mutex<ValidationCache>,
+    /// State the next transaction is validated on, `None` until a transaction is applied
+    validation_state: Option<ValidationState>,
+    /// Set when a [`StateTransaction`] is applied outside of transaction validation,
+    /// the cached rejections can't be used after such changes
+    changed: bool,
 
     /// Reference to Kura subsystem.
     kura: &'state Kura,
@@ -258,6 +363,18 @@ pub struct StateBlock<'state> {
     pub new_tx_amounts: &'state Mutex<Vec<f64>>,
     /// Lock to prevent getting inconsistent view of the state
     view_lock: &'state parking_lot::RwLock<()>,
+    /// Committed world, used to capture entities before they are changed by the block
+    committed_world: &'state World,
+    /// Diff of the latest block if it is being replaced
+    replaced_block_diff: Option<BlockStateDiff>,
+    /// Whether to record the state diff of the block
+    record_block_diffs: bool,
+    /// Whether to archive the events emitted while applying the block
+    archive_events: bool,
+    /// Profiles of the committed blocks not yet exported via telemetry
+    block_profiles: &'state Mutex<Vec<BlockProfile>>,
+    /// Instruction execution profile of the block, recorded only if profiling is enabled
+    profile: Option<BlockProfile>,
 }
 
 /// Struct for single transaction's aggregated changes
@@ -268,12 +385,24 @@ pub struct StateTransaction<'block, 'state> {
     pub block_hashes: CellTransaction<'block, 'state, Vec<HashOf<SignedBlock>>>,
     /// Hashes of transactions mapped onto block height where they stored
     pub transactions: StorageTransaction<'block, 'state, HashOf<SignedTransaction>, NonZeroUsize>,
+    /// Idempotency keys of the recently committed transactions mapped onto their usage records
+    pub idempotency_keys: StorageTransaction<'block, 'state, (AccountId, Name), IdempotencyRecord>,
     /// Topology used to commit latest block
     pub commit_topology: CellTransaction<'block, 'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellTransaction<'block, 'state, Vec<PeerId>>,
+    /// State changes made by blocks mapped onto block height
+    pub block_diffs: StorageTransaction<'block, 'state, NonZeroUsize, BlockStateDiff>,
+    /// Events emitted while applying blocks mapped onto block height
+    pub event_archive: StorageTransaction<'block, 'state, NonZeroUsize, Vec<EventBox>>,
+    /// Receipts of the triggers executed while applying blocks mapped onto block height
+    pub trigger_receipts: StorageTransaction<'block, 'state, NonZeroUsize, Vec<TriggerReceipt>>,
+    /// Fees paid out at the end of blocks mapped onto block height
+    pub block_rewards: StorageTransaction<'block, 'state, NonZeroUsize, Vec<BlockReward>>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
-    pub engine: &'state wasmtime::Engine,
+    pub engine: &'state wasm::engine::Engine,
+    /// Smart contracts compiled by [`Self::engine`]
+    pub module_cache: &'state Arc<Mutex<ModuleCache>>,
 
     /// Reference to Kura subsystem.
     kura: &'state Kura,
@@ -282,6 +411,14 @@ pub struct StateTransaction<'block, 'state> {
     /// Temporary metrics buffer of amounts of any asset that has been transacted.
     /// TODO: this should be done through events
     pub new_tx_amounts: &'state Mutex<Vec<f64>>,
+    /// Fuel consumed by WASM executed within the transaction
+    pub fuel_consumed: u64,
+    /// Instruction execution profile of the block, if profiling is enabled
+    pub profile: Option<&'block mut BlockProfile>,
+    /// Instructions executed within the transaction, if tracing is enabled
+    pub trace: Option<Vec<InstructionTrace>>,
+    /// Set once the transaction is applied to the block
+    block_changed: &'block mut bool,
 }
 
 /// Consistent point in time view of the [`State`]
@@ -292,12 +429,22 @@ pub struct StateView<'state> {
     pub block_hashes: CellView<'state, Vec<HashOf<SignedBlock>>>,
     /// Hashes of transactions mapped onto block height where they stored
     pub transactions: StorageView<'state, HashOf<SignedTransaction>, NonZeroUsize>,
+    /// Idempotency keys of the recently committed transactions mapped onto their usage records
+    pub idempotency_keys: StorageView<'state, (AccountId, Name), IdempotencyRecord>,
     /// Topology used to commit latest block
     pub commit_topology: CellView<'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellView<'state, Vec<PeerId>>,
+    /// State changes made by blocks mapped onto block height
+    pub block_diffs: StorageView<'state, NonZeroUsize, BlockStateDiff>,
+    /// Events emitted while applying blocks mapped onto block height
+    pub event_archive: StorageView<'state, NonZeroUsize, Vec<EventBox>>,
+    /// Receipts of the triggers executed while applying blocks mapped onto block height
+    pub trigger_receipts: StorageView<'state, NonZeroUsize, Vec<TriggerReceipt>>,
+    /// Fees paid out at the end of blocks mapped onto block height
+    pub block_rewards: StorageView<'state, NonZeroUsize, Vec<BlockReward>>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
-    pub engine: &'state wasmtime::Engine,
+    pub engine: &'state wasm::engine::Engine,
 
     /// Reference to Kura subsystem.
     kura: &'state Kura,
@@ -372,6 +519,11 @@ impl World {
             roles: self.roles.block(),
             account_permissions: self.account_permissions.block(),
             account_roles: self.account_roles.block(),
+            escrows: self.escrows.block(),
+            peer_certificates: self.peer_certificates.block(),
+            recovery_policies: self.recovery_policies.block(),
+            apps: self.apps.block(),
+            proxies: self.proxies.block(),
             triggers: self.triggers.block(),
             executor: self.executor.block(),
             executor_data_model: self.executor_data_model.block(),
@@ -394,6 +546,11 @@ impl World {
             roles: self.roles.block_and_revert(),
             account_permissions: self.account_permissions.block_and_revert(),
             account_roles: self.account_roles.block_and_revert(),
+            escrows: self.escrows.block_and_revert(),
+            peer_certificates: self.peer_certificates.block_and_revert(),
+            recovery_policies: self.recovery_policies.block_and_revert(),
+            apps: self.apps.block_and_revert(),
+            proxies: self.proxies.block_and_revert(),
             triggers: self.triggers.block_and_revert(),
             executor: self.executor.block_and_revert(),
             executor_data_model: self.executor_data_model.block_and_revert(),
@@ -416,6 +573,11 @@ impl World {
             roles: self.roles.view(),
             account_permissions: self.account_permissions.view(),
             account_roles: self.account_roles.view(),
+            escrows: self.escrows.view(),
+            peer_certificates: self.peer_certificates.view(),
+            recovery_policies: self.recovery_policies.view(),
+            apps: self.apps.view(),
+            proxies: self.proxies.view(),
             triggers: self.triggers.view(),
             executor: self.executor.view(),
             executor_data_model: self.executor_data_model.view(),
@@ -438,6 +600,11 @@ pub trait WorldReadOnly {
     fn roles(&self) -> &impl StorageReadOnly<RoleId, Role>;
     fn account_permissions(&self) -> &impl StorageReadOnly<AccountId, Permissions>;
     fn account_roles(&self) -> &impl StorageReadOnly<RoleIdWithOwner, ()>;
+    fn escrows(&self) -> &impl StorageReadOnly<EscrowId, Escrow>;
+    fn peer_certificates(&self) -> &impl StorageReadOnly<PublicKey, PeerCertificate>;
+    fn recovery_policies(&self) -> &impl StorageReadOnly<AccountId, RecoveryPolicy>;
+    fn apps(&self) -> &impl StorageReadOnly<AppId, App>;
+    fn proxies(&self) -> &impl StorageReadOnly<(AccountId, AccountId), ProxyToken>;
     fn triggers(&self) -> &impl TriggerSetReadOnly;
     fn executor(&self) -> &Executor;
     fn executor_data_model(&self) -> &ExecutorDataModel;
@@ -672,6 +839,30 @@ pub trait WorldReadOnly {
             .copied()
     }
 
+    // Escrow-related methods
+
+    /// Get `Escrow` immutable view.
+    ///
+    /// # Errors
+    /// - Escrow entry not found
+    fn escrow(&self, id: &EscrowId) -> Result<&Escrow, FindError> {
+        self.escrows()
+            .get(id)
+            .ok_or_else(|| FindError::Escrow(id.clone()))
+    }
+
+    // App-related methods
+
+    /// Get `App` immutable view.
+    ///
+    /// # Errors
+    /// - App entry not found
+    fn app(&self, id: &AppId) -> Result<&App, FindError> {
+        self.apps()
+            .get(id)
+            .ok_or_else(|| FindError::App(id.clone()))
+    }
+
     /// Get an immutable iterator over the [`PeerId`]s.
     fn peers(&self) -> impl ExactSizeIterator<Item = &PeerId> {
         self.trusted_peers_ids().iter()
@@ -682,6 +873,17 @@ pub trait WorldReadOnly {
     fn peers_ids(&self) -> &PeersIds {
         self.trusted_peers_ids()
     }
+
+    /// Check that the `name` of a newly registered entity satisfies the name parameters.
+    ///
+    /// # Errors
+    /// - If the name is too long or contains characters not allowed by the parameters
+    fn check_name(&self, name: &Name) -> Result<(), InvalidParameterError> {
+        self.parameters()
+            .name
+            .validate(name)
+            .map_err(|error| InvalidParameterError::Name(format!("`{name}`: {error}")))
+    }
 }
 
 macro_rules! impl_world_ro {
@@ -717,6 +919,21 @@ macro_rules! impl_world_ro {
             fn account_roles(&self) -> &impl StorageReadOnly<RoleIdWithOwner, ()> {
                 &self.account_roles
             }
+            fn escrows(&self) -> &impl StorageReadOnly<EscrowId, Escrow> {
+                &self.escrows
+            }
+            fn peer_certificates(&self) -> &impl StorageReadOnly<PublicKey, PeerCertificate> {
+                &self.peer_certificates
+            }
+            fn recovery_policies(&self) -> &impl StorageReadOnly<AccountId, RecoveryPolicy> {
+                &self.recovery_policies
+            }
+            fn apps(&self) -> &impl StorageReadOnly<AppId, App> {
+                &self.apps
+            }
+            fn proxies(&self) -> &impl StorageReadOnly<(AccountId, AccountId), ProxyToken> {
+                &self.proxies
+            }
             fn triggers(&self) -> &impl TriggerSetReadOnly {
                 &self.triggers
             }
@@ -754,6 +971,11 @@ impl<'world> WorldBlock<'world> {
             roles: self.roles.transaction(),
             account_permissions: self.account_permissions.transaction(),
             account_roles: self.account_roles.transaction(),
+            escrows: self.escrows.transaction(),
+            peer_certificates: self.peer_certificates.transaction(),
+            recovery_policies: self.recovery_policies.transaction(),
+            apps: self.apps.transaction(),
+            proxies: self.proxies.transaction(),
             triggers: self.triggers.transaction(),
             executor: self.executor.transaction(),
             executor_data_model: self.executor_data_model.transaction(),
@@ -778,6 +1000,11 @@ impl<'world> WorldBlock<'world> {
             roles,
             account_permissions,
             account_roles,
+            escrows,
+            peer_certificates,
+            recovery_policies,
+            apps,
+            proxies,
             triggers,
             executor,
             executor_data_model,
@@ -788,6 +1015,11 @@ impl<'world> WorldBlock<'world> {
         executor_data_model.commit();
         executor.commit();
         triggers.commit();
+        proxies.commit();
+        apps.commit();
+        recovery_policies.commit();
+        peer_certificates.commit();
+        escrows.commit();
         account_roles.commit();
         account_permissions.commit();
         roles.commit();
@@ -816,6 +1048,11 @@ impl WorldTransaction<'_, '_> {
             roles,
             account_permissions,
             account_roles,
+            escrows,
+            peer_certificates,
+            recovery_policies,
+            apps,
+            proxies,
             triggers,
             executor,
             executor_data_model,
@@ -825,6 +1062,11 @@ impl WorldTransaction<'_, '_> {
         executor_data_model.apply();
         executor.apply();
         triggers.apply();
+        proxies.apply();
+        apps.apply();
+        recovery_policies.apply();
+        peer_certificates.apply();
+        escrows.apply();
         account_roles.apply();
         account_permissions.apply();
         roles.apply();
@@ -902,6 +1144,34 @@ impl WorldTransaction<'_, '_> {
         }
     }
 
+    /// Remove all [`App`]s owned by the [`Account`]
+    pub fn remove_account_apps(&mut self, account: &AccountId) {
+        let apps_to_remove = self
+            .apps
+            .iter()
+            .filter(|(_, app)| app.owner == *account)
+            .map(|(app_id, _)| app_id.clone())
+            .collect::<Vec<_>>();
+
+        for app in apps_to_remove {
+            self.apps.remove(app);
+        }
+    }
+
+    /// Remove all [`ProxyToken`]s granted by or to the [`Account`]
+    pub fn remove_account_proxies(&mut self, account: &AccountId) {
+        let proxies_to_remove = self
+            .proxies
+            .iter()
+            .filter(|((granter, proxy), _)| granter == account || proxy == account)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for key in proxies_to_remove {
+            self.proxies.remove(key);
+        }
+    }
+
     /// Get mutable reference to [`Asset`]
     ///
     /// # Errors
@@ -1092,6 +1362,16 @@ impl WorldTransaction<'_, '_> {
         }
         events_buffer.extend(data_events.into_iter().map(Into::into));
     }
+
+    /// Events emitted by the current transaction so far
+    pub fn transaction_events(&self) -> &[EventBox] {
+        let TransactionEventBuffer {
+            events_buffer,
+            events_created_in_transaction,
+        } = &self.events_buffer;
+
+        &events_buffer[events_buffer.len() - events_created_in_transaction..]
+    }
 }
 
 impl TransactionEventBuffer<'_> {
@@ -1126,46 +1406,125 @@ impl State {
         Self {
             world,
             transactions: Storage::new(),
+            idempotency_keys: Storage::new(),
             commit_topology: Cell::new(Vec::new()),
             prev_commit_topology: Cell::new(Vec::new()),
             block_hashes: Cell::new(Vec::new()),
+            block_diffs: Storage::new(),
+            event_archive: Storage::new(),
+            trigger_receipts: Storage::new(),
+            block_rewards: Storage::new(),
             new_tx_amounts: Arc::new(Mutex::new(Vec::new())),
             engine: wasm::create_engine(),
+            module_cache: Arc::new(Mutex::new(ModuleCache::default())),
+            validation_cache: Mutex::new(ValidationCache::default()),
             kura,
             query_handle,
             view_lock: parking_lot::RwLock::new(()),
+            record_block_diffs: false,
+            archive_events: false,
+            block_profiles: Arc::new(Mutex::new(Vec::new())),
+            profile_instructions: false,
         }
     }
 
+    /// Enable or disable recording of per-block state diffs.
+    /// Recording is disabled by default because of the memory it takes.
+    pub fn set_record_block_diffs(&mut self, record_block_diffs: bool) {
+        self.record_block_diffs = record_block_diffs;
+    }
+
+    /// Enable or disable archiving of the events emitted while applying blocks.
+    /// Archiving is disabled by default because of the memory it takes.
+    pub fn set_archive_events(&mut self, archive_events: bool) {
+        self.archive_events = archive_events;
+    }
+
+    /// Enable or disable profiling of instruction execution in every committed block.
+    /// Profiling is disabled by default to keep timing out of block execution.
+    pub fn set_profile_instructions(&mut self, profile_instructions: bool) {
+        self.profile_instructions = profile_instructions;
+    }
+
+    /// Set the maximum number of smart contracts kept compiled.
+    /// Caching is disabled by default.
+    pub fn set_module_cache_capacity(&mut self, capacity: usize) {
+        self.module_cache.lock().set_capacity(capacity);
+    }
+
+    /// Set the maximum number of transaction rejections kept to skip validating
+    /// the transactions again on the same state. Caching is disabled by default.
+    pub fn set_validation_cache_capacity(&mut self, capacity: usize) {
+        self.validation_cache.lock().set_capacity(capacity);
+    }
+
     /// Create structure to execute a block
     pub fn block(&self) -> StateBlock<'_> {
         StateBlock {
             world: self.world.block(),
             block_hashes: self.block_hashes.block(),
             transactions: self.transactions.block(),
+            idempotency_keys: self.idempotency_keys.block(),
             commit_topology: self.commit_topology.block(),
             prev_commit_topology: self.prev_commit_topology.block(),
+            block_diffs: self.block_diffs.block(),
+            event_archive: self.event_archive.block(),
+            trigger_receipts: self.trigger_receipts.block(),
+            block_rewards: self.block_rewards.block(),
             engine: &self.engine,
+            module_cache: &self.module_cache,
+            validation_cache: &self.validation_cache,
+            validation_state: None,
+            changed: false,
             kura: &self.kura,
             query_handle: &self.query_handle,
             new_tx_amounts: &self.new_tx_amounts,
             view_lock: &self.view_lock,
+            committed_world: &self.world,
+            replaced_block_diff: None,
+            record_block_diffs: self.record_block_diffs,
+            archive_events: self.archive_events,
+            block_profiles: &self.block_profiles,
+            profile: self.profile_instructions.then(BlockProfile::default),
         }
     }
 
     /// Create structure to execute a block while reverting changes made in the latest block
     pub fn block_and_revert(&self) -> StateBlock<'_> {
+        let replaced_block_diff = self
+            .record_block_diffs
+            .then(|| {
+                let height = NonZeroUsize::new(self.block_hashes.view().len())?;
+                self.block_diffs.view().get(&height).cloned()
+            })
+            .flatten();
+
         StateBlock {
             world: self.world.block_and_revert(),
             block_hashes: self.block_hashes.block_and_revert(),
             transactions: self.transactions.block_and_revert(),
+            idempotency_keys: self.idempotency_keys.block_and_revert(),
             commit_topology: self.commit_topology.block_and_revert(),
             prev_commit_topology: self.prev_commit_topology.block_and_revert(),
+            block_diffs: self.block_diffs.block_and_revert(),
+            event_archive: self.event_archive.block_and_revert(),
+            trigger_receipts: self.trigger_receipts.block_and_revert(),
+            block_rewards: self.block_rewards.block_and_revert(),
             engine: &self.engine,
+            module_cache: &self.module_cache,
+            validation_cache: &self.validation_cache,
+            validation_state: None,
+            changed: false,
             kura: &self.kura,
             query_handle: &self.query_handle,
             new_tx_amounts: &self.new_tx_amounts,
             view_lock: &self.view_lock,
+            committed_world: &self.world,
+            replaced_block_diff,
+            record_block_diffs: self.record_block_diffs,
+            archive_events: self.archive_events,
+            block_profiles: &self.block_profiles,
+            profile: self.profile_instructions.then(BlockProfile::default),
         }
     }
 
@@ -1176,8 +1535,13 @@ impl State {
             world: self.world.view(),
             block_hashes: self.block_hashes.view(),
             transactions: self.transactions.view(),
+            idempotency_keys: self.idempotency_keys.view(),
             commit_topology: self.commit_topology.view(),
             prev_commit_topology: self.prev_commit_topology.view(),
+            block_diffs: self.block_diffs.view(),
+            event_archive: self.event_archive.view(),
+            trigger_receipts: self.trigger_receipts.view(),
+            block_rewards: self.block_rewards.view(),
             engine: &self.engine,
             kura: &self.kura,
             query_handle: &self.query_handle,
@@ -1192,9 +1556,14 @@ pub trait StateReadOnly {
     fn world(&self) -> &impl WorldReadOnly;
     fn block_hashes(&self) -> &[HashOf<SignedBlock>];
     fn transactions(&self) -> &impl StorageReadOnly<HashOf<SignedTransaction>, NonZeroUsize>;
+    fn idempotency_keys(&self) -> &impl StorageReadOnly<(AccountId, Name), IdempotencyRecord>;
     fn commit_topology(&self) -> &[PeerId];
     fn prev_commit_topology(&self) -> &[PeerId];
-    fn engine(&self) -> &wasmtime::Engine;
+    fn block_diffs(&self) -> &impl StorageReadOnly<NonZeroUsize, BlockStateDiff>;
+    fn event_archive(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<EventBox>>;
+    fn trigger_receipts(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<TriggerReceipt>>;
+    fn block_rewards(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<BlockReward>>;
+    fn engine(&self) -> &wasm::engine::Engine;
     fn kura(&self) -> &Kura;
     fn query_handle(&self) -> &LiveQueryStoreHandle;
     fn new_tx_amounts(&self) -> &Mutex<Vec<f64>>;
@@ -1207,6 +1576,24 @@ pub trait StateReadOnly {
         NonZeroUsize::new(self.height()).and_then(|height| self.kura().get_block_by_height(height))
     }
 
+    /// Current time (in ms) as estimated from the latest block, which is the same on every peer
+    ///
+    /// # Panics
+    /// If there are no blocks and the creation time of the genesis block isn't set
+    fn now_ms(&self) -> u64 {
+        self.latest_block().map_or_else(
+            || {
+                self.world()
+                    .genesis_creation_time_ms()
+                    .expect("INTERNAL BUG: genesis creation time not set")
+            },
+            |block| {
+                let now = block.header().creation_time() + block.header().consensus_estimation();
+                u64::try_from(now.as_millis()).unwrap_or(u64::MAX)
+            },
+        )
+    }
+
     /// Return the hash of the latest block
     fn latest_block_hash(&self) -> Option<HashOf<SignedBlock>> {
         self.block_hashes().iter().nth_back(0).copied()
@@ -1292,10 +1679,68 @@ pub trait StateReadOnly {
         self.transactions().get(&hash).is_some()
     }
 
+    /// Find the committed or rejected [`SignedTransaction`] together with the height of its block
+    fn committed_transaction(
+        &self,
+        hash: &HashOf<SignedTransaction>,
+    ) -> Option<(NonZeroUsize, CommittedTransaction)> {
+        let height = *self.transactions().get(hash)?;
+        let block = self.kura().get_block_by_height(height)?;
+        let transaction = block
+            .transactions()
+            .find(|transaction| transaction.as_ref().hash() == *hash)?
+            .clone();
+        Some((height, transaction))
+    }
+
     /// Get transaction executor
     fn transaction_executor(&self) -> TransactionExecutor {
         TransactionExecutor::new(self.world().parameters().transaction)
     }
+
+    /// Estimate the memory used by the collections of the state.
+    ///
+    /// Goes through every entry of the state, so it's as expensive as a full scan.
+    fn memory_usage(&self) -> MemoryUsage {
+        let world = self.world();
+        let account_permissions = CollectionUsage::of(world.account_permissions().iter());
+        let account_roles = CollectionUsage::of(world.account_roles().iter());
+        let block_hashes = self.block_hashes();
+
+        MemoryUsage {
+            domains: CollectionUsage::of(world.domains().iter()),
+            accounts: CollectionUsage::of(world.accounts().iter()),
+            asset_definitions: CollectionUsage::of(world.asset_definitions().iter()),
+            assets: CollectionUsage::of(world.assets().iter()),
+            roles: CollectionUsage::of(world.roles().iter()),
+            account_permissions: CollectionUsage {
+                entries: account_permissions.entries + account_roles.entries,
+                bytes: account_permissions.bytes + account_roles.bytes,
+            },
+            escrows: CollectionUsage::of(world.escrows().iter()),
+            triggers: world.triggers().memory_usage(),
+            asset_total_quantities: CollectionUsage::of(world.asset_total_quantities().iter()),
+            block_hashes: CollectionUsage {
+                entries: block_hashes.len(),
+                bytes: core::mem::size_of_val(block_hashes),
+            },
+            transactions: CollectionUsage::of_with(self.transactions().iter(), |_, _| 0),
+            idempotency_keys: CollectionUsage::of(self.idempotency_keys().iter()),
+            block_diffs: CollectionUsage::of_with(self.block_diffs().iter(), |_, diff| {
+                diff.encoded_size()
+            }),
+            event_archive: CollectionUsage::of_with(self.event_archive().iter(), |_, events| {
+                events.encoded_size()
+            }),
+            trigger_receipts: CollectionUsage::of_with(
+                self.trigger_receipts().iter(),
+                |_, receipts| receipts.encoded_size(),
+            ),
+            block_rewards: CollectionUsage::of_with(self.block_rewards().iter(), |_, rewards| {
+                rewards.encoded_size()
+            }),
+        }
+    }
 }
 
 macro_rules! impl_state_ro {
@@ -1310,13 +1755,28 @@ macro_rules! impl_state_ro {
             fn transactions(&self) -> &impl StorageReadOnly<HashOf<SignedTransaction>, NonZeroUsize> {
                 &self.transactions
             }
+            fn idempotency_keys(&self) -> &impl StorageReadOnly<(AccountId, Name), IdempotencyRecord> {
+                &self.idempotency_keys
+            }
             fn commit_topology(&self) -> &[PeerId] {
                 &self.commit_topology
             }
             fn prev_commit_topology(&self) -> &[PeerId] {
                 &self.prev_commit_topology
             }
-            fn engine(&self) -> &wasmtime::Engine {
+            fn block_diffs(&self) -> &impl StorageReadOnly<NonZeroUsize, BlockStateDiff> {
+                &self.block_diffs
+            }
+            fn event_archive(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<EventBox>> {
+                &self.event_archive
+            }
+            fn trigger_receipts(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<TriggerReceipt>> {
+                &self.trigger_receipts
+            }
+            fn block_rewards(&self) -> &impl StorageReadOnly<NonZeroUsize, Vec<BlockReward>> {
+                &self.block_rewards
+            }
+            fn engine(&self) -> &wasm::engine::Engine {
                 &self.engine
             }
             fn kura(&self) -> &Kura {
@@ -1337,18 +1797,75 @@ impl_state_ro! {
 }
 
 impl<'state> StateBlock<'state> {
+    /// State the next transaction is validated on, `None` if it isn't known
+    /// because the block was changed outside of transaction validation.
+    pub(crate) fn validation_state(&self) -> Option<ValidationState> {
+        if self.changed {
+            return None;
+        }
+
+        let initial = ValidationState::new(self.height(), self.latest_block_hash());
+        match self.validation_state {
+            None => Some(initial),
+            // The block was applied after the transactions, which no longer make up its state
+            Some(state) => state.is_same_block(&initial).then_some(state),
+        }
+    }
+
+    /// Reason the transaction with `hash` was rejected for on `state`,
+    /// `None` unless it was validated on exactly this state before.
+    pub(crate) fn cached_rejection(
+        &self,
+        hash: HashOf<SignedTransaction>,
+        state: Option<ValidationState>,
+    ) -> Option<TransactionRejectionReason> {
+        self.validation_cache.lock().get(hash, state?)
+    }
+
+    /// Remember that the transaction with `hash` is rejected on `state`.
+    pub(crate) fn cache_rejection(
+        &self,
+        hash: HashOf<SignedTransaction>,
+        state: Option<ValidationState>,
+        reason: TransactionRejectionReason,
+    ) {
+        if let Some(state) = state {
+            self.validation_cache.lock().insert(hash, state, reason);
+        }
+    }
+
+    /// Record that the transaction with `hash` validated on `state` is applied to the block.
+    pub(crate) fn transaction_validated(
+        &mut self,
+        hash: HashOf<SignedTransaction>,
+        state: Option<ValidationState>,
+    ) {
+        self.validation_state = state.map(|state| state.applied(hash));
+        self.changed = state.is_none();
+    }
+
     /// Create struct to store changes during transaction or trigger execution
     pub fn transaction(&mut self) -> StateTransaction<'_, 'state> {
         StateTransaction {
             world: self.world.trasaction(),
             block_hashes: self.block_hashes.transaction(),
             transactions: self.transactions.transaction(),
+            idempotency_keys: self.idempotency_keys.transaction(),
             commit_topology: self.commit_topology.transaction(),
             prev_commit_topology: self.prev_commit_topology.transaction(),
+            block_diffs: self.block_diffs.transaction(),
+            event_archive: self.event_archive.transaction(),
+            trigger_receipts: self.trigger_receipts.transaction(),
+            block_rewards: self.block_rewards.transaction(),
             engine: self.engine,
+            module_cache: self.module_cache,
             kura: self.kura,
             query_handle: self.query_handle,
             new_tx_amounts: self.new_tx_amounts,
+            fuel_consumed: 0,
+            profile: self.profile.as_mut(),
+            trace: None,
+            block_changed: &mut self.changed,
         }
     }
 
@@ -1359,17 +1876,42 @@ impl<'state> StateBlock<'state> {
             world,
             block_hashes,
             transactions,
+            idempotency_keys,
             commit_topology: committed_topology,
             prev_commit_topology: prev_committed_topology,
+            block_diffs,
+            event_archive,
+            trigger_receipts,
+            block_rewards,
             view_lock,
             engine: _,
+            module_cache: _,
+            validation_cache: _,
+            validation_state: _,
+            changed: _,
             kura: _,
             query_handle: _,
             new_tx_amounts: _,
+            committed_world: _,
+            replaced_block_diff: _,
+            record_block_diffs: _,
+            archive_events: _,
+            block_profiles,
+            profile,
         } = self;
+        let lock_wait_start = profile.is_some().then(Instant::now);
         let _view_lock = view_lock.write();
+        if let (Some(mut profile), Some(start)) = (profile, lock_wait_start) {
+            profile.lock_wait = start.elapsed();
+            block_profiles.lock().push(profile);
+        }
+        block_rewards.commit();
+        trigger_receipts.commit();
+        event_archive.commit();
+        block_diffs.commit();
         prev_committed_topology.commit();
         committed_topology.commit();
+        idempotency_keys.commit();
         transactions.commit();
         block_hashes.commit();
         world.commit();
@@ -1453,10 +1995,11 @@ impl<'state> StateBlock<'state> {
             .for_each(|tx_hash| {
                 self.transactions.insert(tx_hash, block_height);
             });
+        self.prune_idempotency_keys(block.as_ref().header().creation_time_ms);
 
         self.world.triggers.handle_time_event(time_event);
 
-        let res = self.process_triggers();
+        let res = self.process_triggers(block_height);
 
         if let Err(errors) = res {
             warn!(
@@ -1465,6 +2008,19 @@ impl<'state> StateBlock<'state> {
             );
         }
 
+        self.distribute_fees(block, block_height);
+        self.prune_empty_assets();
+
+        if self.record_block_diffs {
+            let diff = block_diff::collect(
+                &self.world.events_buffer,
+                &self.committed_world.view(),
+                self.replaced_block_diff.as_ref(),
+                &self.world,
+            );
+            self.block_diffs.insert(block_height, diff);
+        }
+
         self.block_hashes.push(block_hash);
 
         *self.prev_commit_topology = core::mem::take(&mut self.commit_topology);
@@ -1478,7 +2034,153 @@ impl<'state> StateBlock<'state> {
             }
             .into(),
         );
-        core::mem::take(&mut self.world.events_buffer)
+        let events = core::mem::take(&mut self.world.events_buffer);
+        if self.archive_events {
+            self.event_archive.insert(block_height, events.clone());
+        }
+        events
+    }
+
+    /// Forget idempotency keys which can be used again at `now_ms`
+    fn prune_idempotency_keys(&mut self, now_ms: u64) {
+        let expired = self
+            .idempotency_keys
+            .iter()
+            .filter(|(_, record)| record.expires_at_ms <= now_ms)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for key in expired {
+            self.idempotency_keys.remove(key);
+        }
+    }
+
+    /// Pay out the fees collected in `block` according to [`FeeParameters`] and record the payouts.
+    ///
+    /// Payouts are executed as transfers and burns on behalf of the collector, emitting the usual
+    /// events. Fees without a recipient, e.g. if none of the peers registered a certificate naming
+    /// its operator, are left to the collector.
+    fn distribute_fees(&mut self, block: &CommittedBlock, block_height: NonZeroUsize) {
+        let FeeParameters {
+            collector,
+            distribution,
+        } = self.world.parameters.fee.clone();
+        let Some(collector) = collector else {
+            return;
+        };
+        let fees = self
+            .world
+            .assets_in_account_iter(&collector)
+            .filter_map(|asset| match asset.value {
+                AssetValue::Numeric(amount) if !amount.is_zero() => {
+                    Some((asset.id().clone(), amount))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if fees.is_empty() {
+            return;
+        }
+
+        let recipients = self.fee_recipients(block, distribution);
+        let mut rewards = Vec::new();
+        for (asset_id, amount) in fees {
+            for (recipient, share) in reward::split(amount, &recipients) {
+                let mut transaction = self.transaction();
+                let result = match &recipient {
+                    None => Burn::asset_numeric(share, asset_id.clone())
+                        .execute(&collector, &mut transaction),
+                    Some(recipient) => {
+                        Transfer::asset_numeric(asset_id.clone(), share, recipient.clone())
+                            .execute(&collector, &mut transaction)
+                    }
+                };
+                match result {
+                    Ok(()) => {
+                        transaction.apply();
+                        rewards.push(BlockReward::new(
+                            asset_id.definition.clone(),
+                            recipient,
+                            share,
+                        ));
+                    }
+                    Err(error) => warn!(%asset_id, ?recipient, %error, "Failed to pay out fees"),
+                }
+            }
+        }
+
+        if !rewards.is_empty() {
+            let mut transaction = self.transaction();
+            transaction.block_rewards.insert(block_height, rewards);
+            transaction.apply();
+        }
+    }
+
+    /// Recipients of the fees collected in `block`, [`None`] standing for burning the fees
+    fn fee_recipients(
+        &self,
+        block: &CommittedBlock,
+        distribution: FeeDistribution,
+    ) -> Vec<Option<AccountId>> {
+        let operator = |peer: &PeerId| {
+            self.world
+                .peer_certificates
+                .get(peer.public_key())
+                .map(|certificate| Some(certificate.operator().clone()))
+        };
+
+        match distribution {
+            FeeDistribution::Burn => vec![None],
+            FeeDistribution::Leader => self
+                .block_topology(block)
+                .first()
+                .and_then(operator)
+                .into_iter()
+                .collect(),
+            FeeDistribution::SigningPeers => {
+                let topology = self.block_topology(block);
+                block
+                    .as_ref()
+                    .signatures()
+                    .filter_map(|signature| {
+                        usize::try_from(signature.0)
+                            .ok()
+                            .and_then(|index| topology.get(index))
+                    })
+                    .filter_map(operator)
+                    .collect()
+            }
+        }
+    }
+
+    /// Peers in the order of their roles in the round which committed `block`
+    fn block_topology(&self, block: &CommittedBlock) -> Vec<PeerId> {
+        // The block is committed with the topology of the previous block rotated by view changes
+        let peers = self.commit_topology.to_vec();
+        if peers.is_empty() {
+            return peers;
+        }
+        let mut topology = Topology::new(peers);
+        topology.nth_rotation(block.as_ref().header().view_change_index as usize);
+        topology.into_iter().collect()
+    }
+
+    /// Unregister the assets changed by the block which are left empty,
+    /// if their domains opted into [`hygiene::PRUNE_EMPTY_ASSETS`].
+    ///
+    /// Assets are unregistered on behalf of their owners, emitting the usual events,
+    /// so the pass is deterministic and every removal is visible to the subscribers.
+    fn prune_empty_assets(&mut self) {
+        let empty = hygiene::empty_assets(&self.world, &self.world.events_buffer);
+
+        for asset_id in empty {
+            let owner = asset_id.account.clone();
+            let mut transaction = self.transaction();
+            match Unregister::asset(asset_id.clone()).execute(&owner, &mut transaction) {
+                Ok(()) => transaction.apply(),
+                Err(error) => warn!(%asset_id, %error, "Failed to prune an empty asset"),
+            }
+        }
     }
 
     /// Create time event using previous and current blocks
@@ -1500,11 +2202,15 @@ impl<'state> StateBlock<'state> {
         }
     }
 
-    /// Process every trigger in `matched_ids`
-    fn process_triggers(&mut self) -> Result<(), Vec<eyre::Report>> {
+    /// Process every trigger in `matched_ids` and record their receipts for the block at `block_height`
+    ///
+    /// Triggers are executed in the order the events matching them were emitted.
+    /// Triggers matched by the same event are executed in ascending order of their ids.
+    fn process_triggers(&mut self, block_height: NonZeroUsize) -> Result<(), Vec<eyre::Report>> {
         // Cloning and clearing `self.matched_ids` so that `handle_` call won't deadlock
         let matched_ids = self.world.triggers.extract_matched_ids();
         let mut succeed = Vec::<TriggerId>::with_capacity(matched_ids.len());
+        let mut receipts = Vec::with_capacity(matched_ids.len());
         let mut errors = Vec::new();
         for (event, id) in matched_ids {
             // Eliding the closure triggers a lifetime mismatch
@@ -1520,30 +2226,40 @@ impl<'state> StateBlock<'state> {
                     }
                 }
                 // Execute every trigger in it's own transaction
-                let event = {
+                let (outcome, fuel_consumed) = {
                     let mut transaction = self.transaction();
-                    match transaction.process_trigger(&id, &action, event) {
+                    let result = transaction.process_trigger(&id, &action, event);
+                    let fuel_consumed = transaction.fuel_consumed;
+                    let outcome = match result {
                         Ok(()) => {
                             transaction.apply();
                             succeed.push(id.clone());
-                            TriggerCompletedEvent::new(id, TriggerCompletedOutcome::Success)
+                            TriggerCompletedOutcome::Success
                         }
                         Err(error) => {
-                            let event = TriggerCompletedEvent::new(
-                                id,
-                                TriggerCompletedOutcome::Failure(error.to_string()),
-                            );
+                            let outcome = TriggerCompletedOutcome::Failure(error.to_string());
                             errors.push(error);
-                            event
+                            outcome
                         }
-                    }
+                    };
+                    (outcome, fuel_consumed)
                 };
-                self.world.events_buffer.push(event.into());
+                receipts.push(TriggerReceipt::new(
+                    id.clone(),
+                    fuel_consumed,
+                    outcome.clone(),
+                ));
+                self.world
+                    .events_buffer
+                    .push(TriggerCompletedEvent::new(id, outcome).into());
             }
         }
 
         let mut transaction = self.transaction();
         transaction.world.triggers.decrease_repeats(&succeed);
+        if !receipts.is_empty() {
+            transaction.trigger_receipts.insert(block_height, receipts);
+        }
         transaction.apply();
 
         errors.is_empty().then_some(()).ok_or(errors)
@@ -1551,6 +2267,11 @@ impl<'state> StateBlock<'state> {
 }
 
 impl StateTransaction<'_, '_> {
+    /// Height of the block being executed, which isn't yet a part of [`Self::height`]
+    pub fn executing_height(&self) -> NonZeroU64 {
+        NonZeroU64::MIN.saturating_add(self.height() as u64)
+    }
+
     /// Apply transaction making it's changes visible
     pub fn apply(self) {
         // NOTE: intentionally destruct self not to forget apply some fields
@@ -1558,15 +2279,31 @@ impl StateTransaction<'_, '_> {
             world,
             block_hashes,
             transactions,
+            idempotency_keys,
             commit_topology: committed_topology,
             prev_commit_topology: prev_committed_topology,
+            block_diffs,
+            event_archive,
+            trigger_receipts,
+            block_rewards,
             engine: _,
+            module_cache: _,
             kura: _,
             query_handle: _,
             new_tx_amounts: _,
+            fuel_consumed: _,
+            profile: _,
+            trace: _,
+            block_changed,
         } = self;
+        *block_changed = true;
+        block_rewards.apply();
+        trigger_receipts.apply();
+        event_archive.apply();
+        block_diffs.apply();
         prev_committed_topology.apply();
         committed_topology.apply();
+        idempotency_keys.apply();
         transactions.apply();
         block_hashes.apply();
         world.apply();
@@ -1581,6 +2318,7 @@ impl StateTransaction<'_, '_> {
                 let mut wasm_runtime = wasm::RuntimeBuilder::<wasm::state::SmartContract>::new()
                     .with_config(self.world().parameters().smart_contract)
                     .with_engine(self.engine.clone()) // Cloning engine is cheap
+                    .with_module_cache(Arc::clone(self.module_cache))
                     .build()?;
                 wasm_runtime
                     .execute(self, authority, bytes)
@@ -1611,15 +2349,30 @@ impl StateTransaction<'_, '_> {
 
         match action.executable() {
             Instructions(instructions) => {
-                self.process_instructions(instructions.iter().cloned(), authority)
+                let Some(delegation) = self.world.triggers.delegations().get(id).cloned() else {
+                    return self.process_instructions(instructions.iter().cloned(), authority);
+                };
+                // Instructions executed on behalf of the delegator are validated by the executor
+                // so that the trigger can't do more than the delegator is permitted to
+                instructions.iter().cloned().try_for_each(|instruction| {
+                    if delegation.covers(&instruction) {
+                        self.world
+                            .executor
+                            .clone() // Cloning executor is a cheap operation
+                            .validate_instruction(self, &delegation.delegator, instruction)?;
+                    } else {
+                        instruction.execute(authority, self)?;
+                    }
+                    Ok::<_, eyre::Report>(())
+                })
             }
             Wasm(blob_hash) => {
-                let module = self
+                let blob = self
                     .world
                     .triggers
-                    .get_compiled_contract(blob_hash)
-                    .expect("INTERNAL BUG: contract is not present")
-                    .clone();
+                    .get_original_contract(blob_hash)
+                    .expect("INTERNAL BUG: contract is not present");
+                let module = self.module_cache.lock().get_or_load(self.engine, blob)?;
                 let mut wasm_runtime = wasm::RuntimeBuilder::<wasm::state::Trigger>::new()
                     .with_config(self.world().parameters().smart_contract)
                     .with_engine(self.engine.clone()) // Cloning engine is cheap
@@ -1632,6 +2385,410 @@ impl StateTransaction<'_, '_> {
     }
 }
 
+/// Profiling of instruction execution, see [`State::set_profile_instructions`]
+pub mod profile {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use iroha_data_model::isi::InstructionType;
+
+    /// Time spent executing instructions of a single kind
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct InstructionTimes {
+        /// Number of executed instructions
+        pub count: u64,
+        /// Total execution time of the instructions
+        pub total: Duration,
+    }
+
+    /// Instruction execution profile of a single block
+    #[derive(Debug, Default, Clone)]
+    pub struct BlockProfile {
+        /// Execution times by instruction kind, including instructions of rejected transactions
+        pub instructions: BTreeMap<InstructionType, InstructionTimes>,
+        /// Time spent waiting for the state lock to commit the block
+        pub lock_wait: Duration,
+    }
+
+    impl BlockProfile {
+        /// Record execution of an instruction of the given kind
+        pub fn record(&mut self, kind: InstructionType, elapsed: Duration) {
+            let times = self.instructions.entry(kind).or_default();
+            times.count += 1;
+            times.total += elapsed;
+        }
+    }
+}
+
+/// Accounting of the memory used by the state, see [`StateReadOnly::memory_usage`]
+pub mod memory {
+    use core::mem::size_of;
+
+    use parity_scale_codec::Encode;
+
+    /// Approximate memory used by a collection of the state.
+    ///
+    /// Size of an entry is estimated as the inline size of its key and value plus the size of
+    /// the data they own, which is approximated by the size of its SCALE encoding.
+    /// Overhead of the collection itself isn't accounted for.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct CollectionUsage {
+        /// Number of entries
+        pub entries: usize,
+        /// Estimated number of bytes used by the entries
+        pub bytes: usize,
+    }
+
+    impl CollectionUsage {
+        /// Usage of a collection consisting of `entries`
+        pub fn of<'entry, K, V>(entries: impl IntoIterator<Item = (&'entry K, &'entry V)>) -> Self
+        where
+            K: Encode + 'entry,
+            V: Encode + 'entry,
+        {
+            Self::of_with(entries, |key, value| {
+                key.encoded_size() + value.encoded_size()
+            })
+        }
+
+        /// Usage of a collection consisting of `entries`, where `owned_size`
+        /// estimates the size of the data owned by an entry
+        pub fn of_with<'entry, K: 'entry, V: 'entry>(
+            entries: impl IntoIterator<Item = (&'entry K, &'entry V)>,
+            owned_size: impl Fn(&K, &V) -> usize,
+        ) -> Self {
+            entries
+                .into_iter()
+                .fold(Self::default(), |usage, (key, value)| Self {
+                    entries: usage.entries + 1,
+                    bytes: usage.bytes + size_of::<K>() + size_of::<V>() + owned_size(key, value),
+                })
+        }
+    }
+
+    /// Approximate memory used by the collections of the state
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct MemoryUsage {
+        /// Registered domains
+        pub domains: CollectionUsage,
+        /// Registered accounts
+        pub accounts: CollectionUsage,
+        /// Registered asset definitions
+        pub asset_definitions: CollectionUsage,
+        /// Registered assets
+        pub assets: CollectionUsage,
+        /// Registered roles
+        pub roles: CollectionUsage,
+        /// Permissions and roles granted to accounts
+        pub account_permissions: CollectionUsage,
+        /// Assets locked in escrows
+        pub escrows: CollectionUsage,
+        /// Registered triggers, including the blobs of their smart contracts
+        pub triggers: CollectionUsage,
+        /// Total quantities of asset definitions
+        pub asset_total_quantities: CollectionUsage,
+        /// Hashes of the committed blocks
+        pub block_hashes: CollectionUsage,
+        /// Index of the committed transactions by hash
+        pub transactions: CollectionUsage,
+        /// Idempotency keys of the recently committed transactions
+        pub idempotency_keys: CollectionUsage,
+        /// Recorded state changes of the committed blocks
+        pub block_diffs: CollectionUsage,
+        /// Archived events of the committed blocks
+        pub event_archive: CollectionUsage,
+        /// Receipts of the executed triggers
+        pub trigger_receipts: CollectionUsage,
+        /// Fees paid out at the end of the committed blocks
+        pub block_rewards: CollectionUsage,
+    }
+
+    impl MemoryUsage {
+        /// Usage of every collection together with its name
+        pub fn collections(&self) -> [(&'static str, CollectionUsage); 16] {
+            [
+                ("domains", self.domains),
+                ("accounts", self.accounts),
+                ("asset_definitions", self.asset_definitions),
+                ("assets", self.assets),
+                ("roles", self.roles),
+                ("account_permissions", self.account_permissions),
+                ("escrows", self.escrows),
+                ("triggers", self.triggers),
+                ("asset_total_quantities", self.asset_total_quantities),
+                ("block_hashes", self.block_hashes),
+                ("transactions", self.transactions),
+                ("idempotency_keys", self.idempotency_keys),
+                ("block_diffs", self.block_diffs),
+                ("event_archive", self.event_archive),
+                ("trigger_receipts", self.trigger_receipts),
+                ("block_rewards", self.block_rewards),
+            ]
+        }
+
+        /// Estimated number of bytes used by all collections
+        pub fn total_bytes(&self) -> usize {
+            self.collections()
+                .iter()
+                .map(|(_, collection)| collection.bytes)
+                .sum()
+        }
+    }
+}
+
+/// Splitting of the collected fees among their recipients
+mod reward {
+    use iroha_primitives::numeric::Numeric;
+
+    /// Equal shares of `amount` for each of the `recipients`.
+    ///
+    /// Shares are of the same scale as `amount`, so the remainder which can't be split is left
+    /// out. Recipients appearing several times get a share for each appearance.
+    pub(super) fn split<R: Clone>(amount: Numeric, recipients: &[R]) -> Vec<(R, Numeric)> {
+        let Ok(count) = u128::try_from(recipients.len()) else {
+            return Vec::new();
+        };
+        let Some(mantissa) = amount.mantissa().checked_div(count) else {
+            return Vec::new();
+        };
+        if mantissa == 0 {
+            return Vec::new();
+        }
+
+        let share = Numeric::new(mantissa, amount.scale());
+        recipients
+            .iter()
+            .map(|recipient| (recipient.clone(), share))
+            .collect()
+    }
+}
+
+/// Deterministic removal of the leftovers no longer carrying any state
+pub mod hygiene {
+    use std::collections::BTreeSet;
+
+    use iroha_data_model::{events::EventBox, prelude::*};
+    use storage::storage::StorageReadOnly;
+
+    use super::WorldReadOnly;
+
+    /// Key of the [`Domain`] metadata opting the domain into the removal of empty assets.
+    ///
+    /// If set to `true`, numeric assets of the asset definitions of the domain with a zero balance
+    /// and store assets without any entries are unregistered at the end of the block which
+    /// changed them. Assets which were already empty when the domain opted in are left
+    /// until they are changed again.
+    pub const PRUNE_EMPTY_ASSETS: &str = "prune_empty_assets";
+
+    /// Whether the domain opted into the removal of empty assets
+    pub fn prunes_empty_assets(domain: &Domain) -> bool {
+        domain
+            .metadata()
+            .get(PRUNE_EMPTY_ASSETS)
+            .and_then(|value| value.try_into_any::<bool>().ok())
+            .unwrap_or(false)
+    }
+
+    /// Ids of the assets changed by `events` which are empty and to be removed
+    /// according to the policies of their domains.
+    ///
+    /// Only the changed assets are checked, so that the cost doesn't grow with the number of assets.
+    pub(super) fn empty_assets(world: &impl WorldReadOnly, events: &[EventBox]) -> Vec<AssetId> {
+        let changed = events
+            .iter()
+            .filter_map(|event| match event {
+                EventBox::Data(DataEvent::Domain(DomainEvent::Account(AccountEvent::Asset(
+                    event,
+                )))) => Some(event.origin()),
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+
+        changed
+            .into_iter()
+            .filter(|asset_id| {
+                world
+                    .domain(&asset_id.definition.domain)
+                    .is_ok_and(prunes_empty_assets)
+            })
+            .filter(|asset_id| {
+                world
+                    .assets()
+                    .get(asset_id)
+                    .is_some_and(|asset| is_empty(&asset.value))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn is_empty(value: &AssetValue) -> bool {
+        match value {
+            AssetValue::Numeric(quantity) => quantity.is_zero(),
+            AssetValue::Store(store) => store.iter().len() == 0,
+        }
+    }
+}
+
+/// Recording of [`BlockStateDiff`]s
+mod block_diff {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use iroha_data_model::{
+        block::state_diff::{BlockStateDiff, StateChange, StateChangeBox},
+        events::EventBox,
+        prelude::*,
+        role::RoleId,
+    };
+    use storage::storage::StorageReadOnly;
+
+    use super::WorldReadOnly;
+
+    /// Id of an entity whose changes are recorded
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    enum EntityId {
+        Domain(DomainId),
+        Account(AccountId),
+        AssetDefinition(AssetDefinitionId),
+        Asset(AssetId),
+        Role(RoleId),
+        Peer(PeerId),
+        Escrow(EscrowId),
+        Parameters,
+    }
+
+    impl EntityId {
+        /// Entity changed by the event if changes of such entities are recorded
+        fn changed_by(event: &EventBox) -> Option<Self> {
+            let EventBox::Data(event) = event else {
+                return None;
+            };
+
+            match event {
+                DataEvent::Domain(DomainEvent::Account(AccountEvent::Asset(event))) => {
+                    Some(Self::Asset(event.origin().clone()))
+                }
+                // Permissions and roles of accounts are stored apart from them
+                DataEvent::Domain(DomainEvent::Account(
+                    AccountEvent::PermissionAdded(_)
+                    | AccountEvent::PermissionRemoved(_)
+                    | AccountEvent::RoleGranted(_)
+                    | AccountEvent::RoleRevoked(_),
+                )) => None,
+                DataEvent::Domain(DomainEvent::Account(event)) => {
+                    Some(Self::Account(event.origin().clone()))
+                }
+                // Total quantity is stored apart from the asset definition
+                DataEvent::Domain(DomainEvent::AssetDefinition(
+                    AssetDefinitionEvent::TotalQuantityChanged(_),
+                )) => None,
+                DataEvent::Domain(DomainEvent::AssetDefinition(event)) => {
+                    Some(Self::AssetDefinition(event.origin().clone()))
+                }
+                DataEvent::Domain(event) => Some(Self::Domain(event.origin().clone())),
+                DataEvent::Role(event) => Some(Self::Role(event.origin().clone())),
+                DataEvent::Peer(event) => Some(Self::Peer(event.origin().clone())),
+                DataEvent::Escrow(event) => Some(Self::Escrow(event.origin().clone())),
+                DataEvent::Configuration(_) => Some(Self::Parameters),
+                DataEvent::Trigger(_) | DataEvent::Executor(_) => None,
+            }
+        }
+
+        /// Id of the entity changed by the recorded change
+        fn of(change: &StateChangeBox) -> Self {
+            fn value<T>(change: &StateChange<T>) -> &T {
+                change
+                    .before
+                    .as_ref()
+                    .or(change.after.as_ref())
+                    .expect("INTERNAL BUG: recorded change without values")
+            }
+
+            match change {
+                StateChangeBox::Domain(change) => Self::Domain(value(change).id().clone()),
+                StateChangeBox::Account(change) => Self::Account(value(change).id().clone()),
+                StateChangeBox::AssetDefinition(change) => {
+                    Self::AssetDefinition(value(change).id().clone())
+                }
+                StateChangeBox::Asset(change) => Self::Asset(value(change).id().clone()),
+                StateChangeBox::Role(change) => Self::Role(value(change).id().clone()),
+                StateChangeBox::Peer(change) => Self::Peer(value(change).id().clone()),
+                StateChangeBox::Escrow(change) => Self::Escrow(value(change).id().clone()),
+                StateChangeBox::Parameters(_) => Self::Parameters,
+            }
+        }
+
+        /// Value of the entity before and after the block.
+        /// `None` if the entity was created and deleted by the same block.
+        fn change(
+            &self,
+            world_before: &impl WorldReadOnly,
+            replaced: Option<&StateChangeBox>,
+            world_after: &impl WorldReadOnly,
+        ) -> Option<StateChangeBox> {
+            macro_rules! change {
+                ($variant:ident, |$world:ident| $lookup:expr) => {{
+                    let before = match replaced {
+                        Some(StateChangeBox::$variant(change)) => change.before.clone(),
+                        _ => {
+                            let $world = world_before;
+                            $lookup
+                        }
+                    };
+                    let after = {
+                        let $world = world_after;
+                        $lookup
+                    };
+                    (before.is_some() || after.is_some())
+                        .then(|| StateChange { before, after }.into())
+                }};
+            }
+
+            match self {
+                Self::Domain(id) => change!(Domain, |world| world.domains().get(id).cloned()),
+                Self::Account(id) => change!(Account, |world| world.accounts().get(id).cloned()),
+                Self::AssetDefinition(id) => change!(AssetDefinition, |world| world
+                    .asset_definitions()
+                    .get(id)
+                    .cloned()),
+                Self::Asset(id) => change!(Asset, |world| world.assets().get(id).cloned()),
+                Self::Role(id) => change!(Role, |world| world.roles().get(id).cloned()),
+                Self::Peer(id) => change!(Peer, |world| world
+                    .peers_ids()
+                    .contains(id)
+                    .then(|| Peer::new(id.clone()))),
+                Self::Escrow(id) => change!(Escrow, |world| world.escrows().get(id).cloned()),
+                Self::Parameters => change!(Parameters, |world| Some(world.parameters().clone())),
+            }
+        }
+    }
+
+    /// Collect changes of the entities touched by `events` in the order of the first touch.
+    ///
+    /// Values before the block are taken from `world_before`, unless the block replaces
+    /// the latest block in which case the values overwritten by it are taken from its diff.
+    pub(super) fn collect(
+        events: &[EventBox],
+        world_before: &impl WorldReadOnly,
+        replaced: Option<&BlockStateDiff>,
+        world_after: &impl WorldReadOnly,
+    ) -> BlockStateDiff {
+        let replaced = replaced
+            .into_iter()
+            .flat_map(|diff| &diff.changes)
+            .map(|change| (EntityId::of(change), change))
+            .collect::<BTreeMap<_, _>>();
+        let mut seen = BTreeSet::new();
+
+        let changes = events
+            .iter()
+            .filter_map(EntityId::changed_by)
+            .filter(|id| seen.insert(id.clone()))
+            .filter_map(|id| id.change(world_before, replaced.get(&id).copied(), world_after))
+            .collect();
+
+        BlockStateDiff { changes }
+    }
+}
+
 /// Bounds for `range` queries
 mod range_bounds {
     use core::ops::{Bound, RangeBounds};
@@ -1868,7 +3025,7 @@ pub(crate) mod deserialize {
     // Loader for [`Set`]
     #[derive(Clone, Copy)]
     pub struct WasmSeed<'e, T> {
-        pub engine: &'e wasmtime::Engine,
+        pub engine: &'e wasm::engine::Engine,
         _marker: PhantomData<T>,
     }
 
@@ -1962,6 +3119,11 @@ pub(crate) mod deserialize {
                     let mut roles = None;
                     let mut account_permissions = None;
                     let mut account_roles = None;
+                    let mut escrows = None;
+                    let mut peer_certificates = None;
+                    let mut recovery_policies = None;
+                    let mut apps = None;
+                    let mut proxies = None;
                     let mut triggers = None;
                     let mut executor = None;
                     let mut executor_data_model = None;
@@ -1998,6 +3160,21 @@ pub(crate) mod deserialize {
                             "account_roles" => {
                                 account_roles = Some(map.next_value()?);
                             }
+                            "escrows" => {
+                                escrows = Some(map.next_value()?);
+                            }
+                            "peer_certificates" => {
+                                peer_certificates = Some(map.next_value()?);
+                            }
+                            "recovery_policies" => {
+                                recovery_policies = Some(map.next_value()?);
+                            }
+                            "apps" => {
+                                apps = Some(map.next_value()?);
+                            }
+                            "proxies" => {
+                                proxies = Some(map.next_value()?);
+                            }
                             "triggers" => {
                                 triggers =
                                     Some(map.next_value_seed(self.loader.cast::<TriggerSet>())?);
@@ -2036,6 +3213,16 @@ pub(crate) mod deserialize {
                         })?,
                         account_roles: account_roles
                             .ok_or_else(|| serde::de::Error::missing_field("account_roles"))?,
+                        escrows: escrows
+                            .ok_or_else(|| serde::de::Error::missing_field("escrows"))?,
+                        // Snapshots taken before peer certificates were introduced don't have them
+                        peer_certificates: peer_certificates.unwrap_or_default(),
+                        // Snapshots taken before account recovery was introduced don't have policies
+                        recovery_policies: recovery_policies.unwrap_or_default(),
+                        // Snapshots taken before apps were introduced don't have them
+                        apps: apps.unwrap_or_default(),
+                        // Snapshots taken before proxies were introduced don't have them
+                        proxies: proxies.unwrap_or_default(),
                         triggers: triggers
                             .ok_or_else(|| serde::de::Error::missing_field("triggers"))?,
                         executor: executor
@@ -2056,6 +3243,11 @@ pub(crate) mod deserialize {
                     "roles",
                     "account_permissions",
                     "account_roles",
+                    "escrows",
+                    "peer_certificates",
+                    "recovery_policies",
+                    "apps",
+                    "proxies",
                     "triggers",
                     "executor",
                     "executor_data_model",
@@ -2098,8 +3290,13 @@ pub(crate) mod deserialize {
                     let mut world = None;
                     let mut block_hashes = None;
                     let mut transactions = None;
+                    let mut idempotency_keys = None;
                     let mut commit_topology = None;
                     let mut prev_commit_topology = None;
+                    let mut block_diffs = None;
+                    let mut event_archive = None;
+                    let mut trigger_receipts = None;
+                    let mut block_rewards = None;
 
                     let engine = wasm::create_engine();
 
@@ -2119,12 +3316,27 @@ pub(crate) mod deserialize {
                             "transactions" => {
                                 transactions = Some(map.next_value()?);
                             }
+                            "idempotency_keys" => {
+                                idempotency_keys = Some(map.next_value()?);
+                            }
                             "commit_topology" => {
                                 commit_topology = Some(map.next_value()?);
                             }
                             "prev_commit_topology" => {
                                 prev_commit_topology = Some(map.next_value()?);
                             }
+                            "block_diffs" => {
+                                block_diffs = Some(map.next_value()?);
+                            }
+                            "event_archive" => {
+                                event_archive = Some(map.next_value()?);
+                            }
+                            "trigger_receipts" => {
+                                trigger_receipts = Some(map.next_value()?);
+                            }
+                            "block_rewards" => {
+                                block_rewards = Some(map.next_value()?);
+                            }
                             _ => { /* Skip unknown fields */ }
                         }
                     }
@@ -2135,16 +3347,32 @@ pub(crate) mod deserialize {
                             .ok_or_else(|| serde::de::Error::missing_field("block_hashes"))?,
                         transactions: transactions
                             .ok_or_else(|| serde::de::Error::missing_field("transactions"))?,
+                        // Snapshots made before idempotency keys were introduced don't have them
+                        idempotency_keys: idempotency_keys.unwrap_or_else(Storage::new),
                         commit_topology: commit_topology
                             .ok_or_else(|| serde::de::Error::missing_field("commit_topology"))?,
                         prev_commit_topology: prev_commit_topology.ok_or_else(|| {
                             serde::de::Error::missing_field("prev_commit_topology")
                         })?,
+                        // Snapshots made before diffs were introduced don't have them
+                        block_diffs: block_diffs.unwrap_or_else(Storage::new),
+                        // Snapshots made before the archive was introduced don't have it
+                        event_archive: event_archive.unwrap_or_else(Storage::new),
+                        // Snapshots made before the receipts were introduced don't have them
+                        trigger_receipts: trigger_receipts.unwrap_or_else(Storage::new),
+                        // Snapshots made before the rewards were introduced don't have them
+                        block_rewards: block_rewards.unwrap_or_else(Storage::new),
                         kura: self.loader.kura,
                         query_handle: self.loader.query_handle,
                         engine,
+                        module_cache: Arc::new(Mutex::new(ModuleCache::default())),
+                        validation_cache: Mutex::new(ValidationCache::default()),
                         new_tx_amounts: Arc::new(Mutex::new(Vec::new())),
                         view_lock: parking_lot::RwLock::new(()),
+                        record_block_diffs: false,
+                        archive_events: false,
+                        block_profiles: Arc::new(Mutex::new(Vec::new())),
+                        profile_instructions: false,
                     })
                 }
             }
@@ -2155,8 +3383,13 @@ pub(crate) mod deserialize {
                     "world",
                     "block_hashes",
                     "transactions",
+                    "idempotency_keys",
                     "commit_topology",
                     "prev_commit_topology",
+                    "block_diffs",
+                    "event_archive",
+                    "trigger_receipts",
+                    "block_rewards",
                 ],
                 StateVisitor { loader: self },
             )
@@ -2168,14 +3401,14 @@ pub(crate) mod deserialize {
 mod tests {
     use core::num::NonZeroU64;
 
-    use iroha_data_model::block::BlockPayload;
+    use iroha_data_model::{
+        block::{state_diff::StateChangeBox, BlockPayload},
+        parameter::FeeParameter,
+    };
     use test_samples::gen_account_in;
 
     use super::*;
-    use crate::{
-        block::ValidBlock, query::store::LiveQueryStore, role::RoleIdWithOwner,
-        sumeragi::network_topology::Topology,
-    };
+    use crate::{block::ValidBlock, query::store::LiveQueryStore, role::RoleIdWithOwner};
 
     /// Used to inject faulty payload for testing
     fn new_dummy_block_with_payload(f: impl FnOnce(&mut BlockPayload)) -> CommittedBlock {
@@ -2243,6 +3476,320 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn block_diff_is_recorded_and_replaced() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let mut state = State::new(World::default(), kura, query_handle);
+        state.set_record_block_diffs(true);
+
+        let (authority, _authority_keypair) = gen_account_in("wonderland");
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let height = nonzero_ext::nonzero!(1_usize);
+        let apply_block = |mut state_block: StateBlock<'_>| {
+            let mut transaction = state_block.transaction();
+            Register::domain(Domain::new(domain_id.clone()))
+                .execute(&authority, &mut transaction)
+                .unwrap();
+            transaction.apply();
+
+            let block = new_dummy_block_with_payload(|payload| {
+                payload.header.height = NonZeroU64::new(1).unwrap();
+            });
+            let _events = state_block.apply_without_execution(&block, Vec::new());
+            state_block.commit();
+        };
+
+        apply_block(state.block());
+        // Domain registered by the replaced block must not be reported as existing before
+        apply_block(state.block_and_revert());
+
+        let state_view = state.view();
+        let diff = state_view.block_diffs.get(&height).unwrap();
+        let [StateChangeBox::Domain(change)] = diff.changes.as_slice() else {
+            panic!("Unexpected diff: {diff:?}");
+        };
+        assert!(change.is_created());
+        assert_eq!(change.after.as_ref().unwrap().id(), &domain_id);
+    }
+
+    #[tokio::test]
+    async fn instruction_profile_is_recorded_on_commit() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let mut state = State::new(World::default(), kura, query_handle);
+        state.set_profile_instructions(true);
+
+        let (authority, _authority_keypair) = gen_account_in("wonderland");
+        let mut state_block = state.block();
+        let mut transaction = state_block.transaction();
+        InstructionBox::from(Register::domain(Domain::new("wonderland".parse().unwrap())))
+            .execute(&authority, &mut transaction)
+            .unwrap();
+        transaction.apply();
+        let block = new_dummy_block_with_payload(|payload| {
+            payload.header.height = NonZeroU64::new(1).unwrap();
+        });
+        let _events = state_block.apply_without_execution(&block, Vec::new());
+        state_block.commit();
+
+        let profiles = state.block_profiles.lock();
+        let [profile] = profiles.as_slice() else {
+            panic!("Expected a single profile: {profiles:?}");
+        };
+        assert_eq!(
+            profile.instructions[&iroha_data_model::isi::InstructionType::Register].count,
+            1
+        );
+    }
+
+    #[test]
+    fn fees_are_split_equally() {
+        let amount = Numeric::new(1_001, 2);
+
+        assert_eq!(
+            reward::split(amount, &["alice", "bob"]),
+            vec![
+                ("alice", Numeric::new(500, 2)),
+                ("bob", Numeric::new(500, 2))
+            ]
+        );
+        assert_eq!(reward::split(amount, &["alice"]), vec![("alice", amount)]);
+        assert!(reward::split(Numeric::new(1, 0), &["alice", "bob"]).is_empty());
+        assert!(reward::split::<&str>(amount, &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn fees_are_paid_to_operators_of_signing_peers() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(World::default(), kura, query_handle);
+
+        let (collector_id, _collector_keypair) = gen_account_in("wonderland");
+        let (operator_id, _operator_keypair) = gen_account_in("wonderland");
+        let (peer_public_key, peer_private_key) = iroha_crypto::KeyPair::random().into_parts();
+        let peer_id = PeerId::new("127.0.0.1:8080".parse().unwrap(), peer_public_key);
+        let rose_id: AssetDefinitionId = "rose#wonderland".parse().unwrap();
+        let collected_id = AssetId::new(rose_id.clone(), collector_id.clone());
+        let instructions: [InstructionBox; 7] = [
+            Register::domain(Domain::new("wonderland".parse().unwrap())).into(),
+            Register::account(Account::new(collector_id.clone())).into(),
+            Register::account(Account::new(operator_id.clone())).into(),
+            Register::asset_definition(AssetDefinition::numeric(rose_id.clone())).into(),
+            Mint::asset_numeric(10_u32, collected_id.clone()).into(),
+            SetParameter::new(Parameter::Fee(FeeParameter::Collector(Some(
+                collector_id.clone(),
+            ))))
+            .into(),
+            SetParameter::new(Parameter::Fee(FeeParameter::Distribution(
+                FeeDistribution::SigningPeers,
+            )))
+            .into(),
+        ];
+
+        let mut state_block = state.block();
+        let mut transaction = state_block.transaction();
+        for instruction in instructions {
+            instruction
+                .execute(&collector_id, &mut transaction)
+                .unwrap();
+        }
+        let certificate = PeerCertificate::new(
+            PeerCertificatePayload {
+                peer: peer_id.clone(),
+                operator: operator_id.clone(),
+                metadata: Metadata::default(),
+            },
+            &peer_private_key,
+        );
+        transaction
+            .world
+            .peer_certificates
+            .insert(peer_id.public_key().clone(), certificate);
+        transaction.apply();
+        *state_block.commit_topology = vec![peer_id.clone()];
+
+        let block = ValidBlock::new_dummy_and_modify_payload(&peer_private_key, |payload| {
+            payload.header.height = NonZeroU64::new(1).unwrap();
+        })
+        .commit(&Topology::new(vec![peer_id]))
+        .unpack(|_| {})
+        .unwrap();
+        let _events = state_block.apply_without_execution(&block, Vec::new());
+        state_block.commit();
+
+        let state_view = state.view();
+        let world = state_view.world();
+        // Assets are removed once all of them are transferred
+        assert!(world.asset(&collected_id).is_err());
+        assert_eq!(
+            world
+                .asset(&AssetId::new(rose_id.clone(), operator_id.clone()))
+                .unwrap()
+                .value,
+            AssetValue::Numeric(Numeric::from(10_u32))
+        );
+        assert_eq!(
+            state_view.block_rewards().get(&NonZeroUsize::MIN),
+            Some(&vec![BlockReward::new(
+                rose_id,
+                Some(operator_id),
+                Numeric::from(10_u32)
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_assets_are_pruned_by_domain_policy() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(World::default(), kura, query_handle);
+
+        let (alice_id, _alice_keypair) = gen_account_in("wonderland");
+        let mut policy = Metadata::default();
+        policy.insert(hygiene::PRUNE_EMPTY_ASSETS.parse().unwrap(), true);
+        let rose_id: AssetDefinitionId = "rose#wonderland".parse().unwrap();
+        let tulip_id: AssetDefinitionId = "tulip#wonderland".parse().unwrap();
+        let cat_id: AssetDefinitionId = "cat#looking_glass".parse().unwrap();
+        let instructions: [InstructionBox; 9] = [
+            Register::domain(Domain::new("wonderland".parse().unwrap()).with_metadata(policy))
+                .into(),
+            Register::domain(Domain::new("looking_glass".parse().unwrap())).into(),
+            Register::account(Account::new(alice_id.clone())).into(),
+            Register::asset_definition(AssetDefinition::numeric(rose_id.clone())).into(),
+            Register::asset_definition(AssetDefinition::numeric(tulip_id.clone())).into(),
+            Register::asset_definition(AssetDefinition::numeric(cat_id.clone())).into(),
+            Register::asset(Asset::new(
+                AssetId::new(rose_id.clone(), alice_id.clone()),
+                Numeric::ZERO,
+            ))
+            .into(),
+            Register::asset(Asset::new(
+                AssetId::new(tulip_id.clone(), alice_id.clone()),
+                Numeric::from(5_u32),
+            ))
+            .into(),
+            Register::asset(Asset::new(
+                AssetId::new(cat_id.clone(), alice_id.clone()),
+                Numeric::ZERO,
+            ))
+            .into(),
+        ];
+
+        let mut state_block = state.block();
+        let mut transaction = state_block.transaction();
+        for instruction in instructions {
+            instruction.execute(&alice_id, &mut transaction).unwrap();
+        }
+        transaction.apply();
+        let block = new_dummy_block_with_payload(|payload| {
+            payload.header.height = NonZeroU64::new(1).unwrap();
+        });
+        let events = state_block.apply_without_execution(&block, Vec::new());
+        state_block.commit();
+
+        let state_view = state.view();
+        let world = state_view.world();
+        assert!(world
+            .asset(&AssetId::new(rose_id.clone(), alice_id.clone()))
+            .is_err());
+        assert!(world
+            .asset(&AssetId::new(tulip_id, alice_id.clone()))
+            .is_ok());
+        assert!(world.asset(&AssetId::new(cat_id, alice_id.clone())).is_ok());
+        assert!(events.iter().any(|event| matches!(
+            event,
+            EventBox::Data(DataEvent::Domain(DomainEvent::Account(AccountEvent::Asset(
+                AssetEvent::Removed(changed)
+            )))) if changed.asset.definition == rose_id
+        )));
+    }
+
+    #[tokio::test]
+    async fn empty_assets_are_pruned_once_changed() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(World::default(), kura, query_handle);
+
+        let (alice_id, _alice_keypair) = gen_account_in("wonderland");
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let rose_id = AssetId::new("rose#wonderland".parse().unwrap(), alice_id.clone());
+        let tulip_id = AssetId::new("tulip#wonderland".parse().unwrap(), alice_id.clone());
+        let apply_block = |height: u64, instructions: Vec<InstructionBox>| {
+            let mut state_block = state.block();
+            let mut transaction = state_block.transaction();
+            for instruction in instructions {
+                instruction.execute(&alice_id, &mut transaction).unwrap();
+            }
+            transaction.apply();
+            let block = new_dummy_block_with_payload(|payload| {
+                payload.header.height = NonZeroU64::new(height).unwrap();
+            });
+            let _events = state_block.apply_without_execution(&block, Vec::new());
+            state_block.commit();
+        };
+
+        // The asset is left empty before the domain opts in
+        apply_block(
+            1,
+            vec![
+                Register::domain(Domain::new(domain_id.clone())).into(),
+                Register::account(Account::new(alice_id.clone())).into(),
+                Register::asset_definition(AssetDefinition::numeric(rose_id.definition.clone()))
+                    .into(),
+                Register::asset_definition(AssetDefinition::numeric(tulip_id.definition.clone()))
+                    .into(),
+                Register::asset(Asset::new(rose_id.clone(), Numeric::ZERO)).into(),
+            ],
+        );
+        apply_block(
+            2,
+            vec![
+                SetKeyValue::domain(
+                    domain_id,
+                    hygiene::PRUNE_EMPTY_ASSETS.parse().unwrap(),
+                    true,
+                )
+                .into(),
+                Mint::asset_numeric(5_u32, tulip_id.clone()).into(),
+            ],
+        );
+        assert!(state.view().world().asset(&rose_id).is_ok());
+        assert!(state.view().world().asset(&tulip_id).is_ok());
+
+        apply_block(
+            3,
+            vec![
+                Mint::asset_numeric(5_u32, rose_id.clone()).into(),
+                Burn::asset_numeric(5_u32, rose_id.clone()).into(),
+            ],
+        );
+        assert!(state.view().world().asset(&rose_id).is_err());
+        assert!(state.view().world().asset(&tulip_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn memory_usage_is_accounted_per_collection() {
+        let (alice_id, _alice_keypair) = gen_account_in("wonderland");
+        let (bob_id, _bob_keypair) = gen_account_in("wonderland");
+        let domain = Domain::new("wonderland".parse().unwrap()).build(&alice_id);
+        let accounts = [
+            Account::new(alice_id.clone()).build(&alice_id),
+            Account::new(bob_id).build(&alice_id),
+        ];
+        let world = World::with([domain], accounts, []);
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(world, kura, query_handle);
+
+        let usage = state.view().memory_usage();
+        assert_eq!(usage.domains.entries, 1);
+        assert_eq!(usage.accounts.entries, 2);
+        assert!(usage.accounts.bytes > 2 * core::mem::size_of::<Account>());
+        assert_eq!(usage.assets, memory::CollectionUsage::default());
+        assert!(usage.total_bytes() >= usage.domains.bytes + usage.accounts.bytes);
+    }
+
     #[test]
     fn role_account_range() {
         let (account_id, _account_keypair) = gen_account_in("wonderland");
@@ -2,11 +2,18 @@
 use std::{collections::BTreeSet, ops::Deref, sync::mpsc};
 
 use iroha_crypto::{HashOf, KeyPair};
-use iroha_data_model::{block::*, events::pipeline::PipelineEventBox, peer::PeerId};
-use iroha_p2p::UpdateTopology;
+use iroha_data_model::{
+    block::*, events::pipeline::PipelineEventBox, parameter::BlockParameters, peer::PeerId,
+};
+use iroha_p2p::{UpdateCertificates, UpdateTopology};
+use parity_scale_codec::Encode as _;
 use tracing::{span, Level};
 
-use super::{view_change::ProofBuilder, *};
+use super::{
+    packing::{self, BlockPackingStrategy},
+    view_change::ProofBuilder,
+    *,
+};
 use crate::{block::*, queue::TransactionGuard, sumeragi::tracing::instrument};
 
 /// `Sumeragi` is the implementation of the consensus.
@@ -39,8 +46,16 @@ pub struct Sumeragi {
     /// sumeragi is more dependent on the code that is internal to the
     /// subsystem.
     pub transaction_cache: Vec<TransactionGuard>,
+    /// Order in which the leader packs transactions from [`Self::transaction_cache`] into a block
+    pub block_packing: Box<dyn BlockPackingStrategy>,
+    /// Number of blocks worth of transactions kept in [`Self::transaction_cache`]
+    pub block_packing_lookahead: NonZeroUsize,
     /// Metrics for reporting number of view changes in current round
     pub view_changes_metric: iroha_telemetry::metrics::ViewChangesGauge,
+    /// Metrics reported on every block commit
+    pub block_metrics: BlockMetrics,
+    /// Handle to the world state consistency checker
+    pub consistency_checker: ConsistencyCheckerHandle,
 
     /// Was there a commit in previous round?
     pub was_commit: bool,
@@ -48,6 +63,10 @@ pub struct Sumeragi {
     // NOTE: Round is only restarted on a block commit, so that in the case of
     // a view change a new block is immediately created by the leader
     pub round_start_time: Instant,
+    /// Time since the Unix epoch (in ms) at which the current round started
+    pub round_start_ms: u64,
+    /// Sender of the consensus round state snapshots
+    pub round_status: tokio::sync::watch::Sender<RoundStatus>,
 }
 
 #[allow(clippy::missing_fields_in_debug)]
@@ -65,6 +84,44 @@ impl Sumeragi {
         self.topology.role(&self.peer_id)
     }
 
+    fn start_round(&mut self) {
+        self.round_start_time = Instant::now();
+        self.round_start_ms = self
+            .queue
+            .time_source()
+            .get_unix_time()
+            .as_millis()
+            .try_into()
+            .expect("Time should fit into u64");
+    }
+
+    /// Publish the current consensus round state for the readers of [`SumeragiHandle::round_status`].
+    fn update_round_status(
+        &self,
+        height: usize,
+        voting_block: Option<&VotingBlock>,
+        voting_signatures: &BTreeSet<BlockSignature>,
+        view_change_proof_chain: &ProofChain,
+    ) {
+        let status = RoundStatus {
+            height,
+            view_change_index: self.topology.view_change_index(),
+            role: self.role(),
+            leader: self.topology.leader().clone(),
+            round_start_ms: self.round_start_ms,
+            voting_block: voting_block.map(|block| block.as_ref().as_ref().hash()),
+            voting_signatures: voting_signatures.len(),
+            view_change_proofs: view_change_proof_chain.signature_counts(),
+        };
+        self.round_status.send_if_modified(|current| {
+            let modified = *current != status;
+            if modified {
+                *current = status;
+            }
+            modified
+        });
+    }
+
     /// Send a sumeragi packet over the network to the specified `peer`.
     /// # Errors
     /// Fails if network sending fails
@@ -114,6 +171,17 @@ impl Sumeragi {
         self.network.update_topology(UpdateTopology(peers));
     }
 
+    /// Pass the registered peer certificates to the network, which presents and checks them.
+    fn update_certificates(&self, world: &impl WorldReadOnly) {
+        let certificates = world
+            .peer_certificates()
+            .iter()
+            .map(|(_, certificate)| certificate.clone())
+            .collect();
+        self.network
+            .update_certificates(UpdateCertificates(certificates));
+    }
+
     fn send_event(&self, event: impl Into<EventBox>) {
         let _ = self.events_sender.send(event.into());
     }
@@ -266,7 +334,7 @@ impl Sumeragi {
 
                     // NOTE: By this time genesis block is executed and list of trusted peers is updated
                     self.topology = Topology::new(state_block.world.trusted_peers_ids.clone());
-                    self.commit_block(block, state_block);
+                    self.commit_block(block, state_block, None);
                     return Ok(());
                 }
                 Err(mpsc::TryRecvError::Disconnected) => return Err(EarlyReturn::Disconnected),
@@ -316,23 +384,32 @@ impl Sumeragi {
             .expect("Genesis invalid");
 
         self.broadcast_packet(msg);
-        self.commit_block(genesis, state_block);
+        self.commit_block(genesis, state_block, None);
     }
 
-    fn commit_block(&mut self, block: CommittedBlock, state_block: StateBlock<'_>) {
-        self.update_state::<NewBlockStrategy>(block, state_block);
+    /// Commit `block`, `received_at` is when it was created or received for voting
+    /// and is [`None`] for blocks committed without voting
+    fn commit_block(
+        &mut self,
+        block: CommittedBlock,
+        state_block: StateBlock<'_>,
+        received_at: Option<Instant>,
+    ) {
+        self.update_state::<NewBlockStrategy>(block, state_block, received_at);
     }
 
     fn replace_top_block(&mut self, block: CommittedBlock, state_block: StateBlock<'_>) {
-        self.update_state::<ReplaceTopBlockStrategy>(block, state_block);
+        self.update_state::<ReplaceTopBlockStrategy>(block, state_block, None);
     }
 
     fn update_state<Strategy: ApplyBlockStrategy>(
         &mut self,
         block: CommittedBlock,
         mut state_block: StateBlock<'_>,
+        received_at: Option<Instant>,
     ) {
         let prev_role = self.role();
+        let leader = self.topology.leader().clone();
 
         self.topology
             .block_committed(block.as_ref(), state_block.world.peers().cloned());
@@ -341,14 +418,20 @@ impl Sumeragi {
             state_block.apply_without_execution(&block, self.topology.as_ref().to_owned());
 
         self.cache_transaction(&state_block);
+        self.update_certificates(&state_block.world);
         self.connect_peers(&self.topology);
 
         let block_hash = block.as_ref().hash();
         let block_height = block.as_ref().header().height();
+        let commit_time = commit_time(received_at, Instant::now());
+        self.report_block_committed(block.as_ref(), &leader, commit_time);
+        // Kura writes the block meanwhile the next one is processed,
+        // this waits only if it lags behind by more than the pipeline depth
         Strategy::kura_store_block(&self.kura, block);
 
         // Commit new block making it's effect visible for the rest of application
         state_block.commit();
+        self.consistency_checker.block_committed(block_height);
         info!(
             peer_id=%self.peer_id,
             %prev_role,
@@ -369,10 +452,44 @@ impl Sumeragi {
         // so it should be done AFTER public facing state update
         state_events.into_iter().for_each(|e| self.send_event(e));
 
-        self.round_start_time = Instant::now();
+        self.start_round();
         self.was_commit = true;
     }
 
+    /// Report statistics of the finished round to Prometheus and telemetry.
+    fn report_block_committed(
+        &self,
+        block: &SignedBlock,
+        leader: &PeerId,
+        commit_time: Option<Duration>,
+    ) {
+        let signatures = block.signatures().len() as u64;
+        let transactions = block.transactions().len() as u64;
+        let size_bytes = block.encoded_size() as u64;
+
+        if let Some(commit_time) = commit_time {
+            self.block_metrics
+                .commit_time_ms
+                .observe(commit_time.as_secs_f64() * 1000.0);
+        }
+        self.block_metrics.signatures.set(signatures);
+        self.block_metrics.transactions.set(transactions);
+        self.block_metrics.size_bytes.set(size_bytes);
+
+        iroha_logger::telemetry!(
+            msg = iroha_telemetry::msg::BLOCK_IMPORT,
+            height = block.header().height().get(),
+            best = %block.hash(),
+            %leader,
+            view_changes = block.header().view_change_index,
+            commit_time_ms = commit_time
+                .map(|commit_time| u64::try_from(commit_time.as_millis()).unwrap_or(u64::MAX)),
+            signatures,
+            transactions,
+            size_bytes,
+        );
+    }
+
     fn cache_transaction(&mut self, state_block: &StateBlock<'_>) {
         self.transaction_cache.retain(|tx| {
             !state_block.has_transaction(tx.as_ref().hash()) && !self.queue.is_expired(tx)
@@ -387,6 +504,7 @@ impl Sumeragi {
         BlockCreated { block }: BlockCreated,
         existing_voting_block: &mut Option<VotingBlock>,
     ) -> Option<VotingBlock<'state>> {
+        let received_at = Instant::now();
         if state.view().height() == 1 && block.header().height.get() == 1 {
             // Consider our peer has genesis,
             // and some other peer has genesis and broadcast it to our peer,
@@ -406,7 +524,7 @@ impl Sumeragi {
             false,
         )
         .unpack(|e| self.send_event(e))
-        .map(|(block, state_block)| VotingBlock::new(block, state_block))
+        .map(|(block, state_block)| VotingBlock::new(block, state_block, received_at))
         .map_err(|(block, error)| {
             warn!(
                 peer_id=%self.peer_id,
@@ -462,7 +580,7 @@ impl Sumeragi {
                 ) {
                     Ok(BlockSyncOk::CommitBlock(block, state_block, topology)) => {
                         self.topology = topology;
-                        self.commit_block(block, state_block);
+                        self.commit_block(block, state_block, None);
                     }
                     Ok(BlockSyncOk::ReplaceTopBlock(block, state_block, topology)) => {
                         let latest_block = state_block
@@ -714,9 +832,11 @@ impl Sumeragi {
                                     .commit(&self.topology)
                                     .unpack(|e| self.send_event(e))
                                 {
-                                    Ok(committed_block) => {
-                                        self.commit_block(committed_block, voted_block.state_block)
-                                    }
+                                    Ok(committed_block) => self.commit_block(
+                                        committed_block,
+                                        voted_block.state_block,
+                                        Some(voted_block.received_at),
+                                    ),
                                     Err((mut block, error)) => {
                                         error!(
                                             peer_id=%self.peer_id,
@@ -812,7 +932,11 @@ impl Sumeragi {
                 self.broadcast_packet(msg);
             }
 
-            self.commit_block(committed_block, voting_block.state_block);
+            self.commit_block(
+                committed_block,
+                voting_block.state_block,
+                Some(voting_block.received_at),
+            );
 
             return None;
         }
@@ -828,11 +952,8 @@ impl Sumeragi {
     ) {
         assert_eq!(self.role(), Role::Leader);
 
-        let max_transactions: NonZeroUsize = state
-            .world
-            .view()
-            .parameters
-            .block
+        let block_parameters = state.world.view().parameters.block;
+        let max_transactions: NonZeroUsize = block_parameters
             .max_transactions
             .try_into()
             .expect("INTERNAL BUG: transactions in block exceed usize::MAX");
@@ -842,15 +963,20 @@ impl Sumeragi {
         let tx_cache_non_empty = !self.transaction_cache.is_empty();
 
         if tx_cache_full || (deadline_reached && tx_cache_non_empty) {
-            let transactions = self
-                .transaction_cache
-                .iter()
-                .map(|tx| tx.deref().clone())
-                .collect::<Vec<_>>();
+            let transactions = select_block_transactions(
+                &mut self.transaction_cache,
+                &*self.block_packing,
+                block_parameters,
+                &self.peer_id,
+            );
+            if transactions.is_empty() {
+                return;
+            }
 
             let mut state_block = state.block();
             let create_block_start_time = Instant::now();
             let new_block = BlockBuilder::new(transactions)
+                .with_time_source(self.queue.time_source().clone())
                 .chain(self.topology.view_change_index(), &mut state_block)
                 .sign(self.key_pair.private_key())
                 .unpack(|e| self.send_event(e));
@@ -878,7 +1004,11 @@ impl Sumeragi {
                 );
 
                 let msg = BlockCreated::from(&new_block);
-                *voting_block = Some(VotingBlock::new(new_block, state_block));
+                *voting_block = Some(VotingBlock::new(
+                    new_block,
+                    state_block,
+                    create_block_start_time,
+                ));
                 self.broadcast_packet(msg);
             } else {
                 let committed_block = new_block
@@ -888,12 +1018,18 @@ impl Sumeragi {
 
                 let msg = BlockCommitted::from(&committed_block);
                 self.broadcast_packet(msg);
-                self.commit_block(committed_block, state_block);
+                self.commit_block(committed_block, state_block, Some(create_block_start_time));
             }
         }
     }
 }
 
+/// Time from when a block was created or received for voting till `committed_at`,
+/// [`None`] if it was committed without voting, e.g. received via block sync
+fn commit_time(received_at: Option<Instant>, committed_at: Instant) -> Option<Duration> {
+    received_at.map(|received_at| committed_at.saturating_duration_since(received_at))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn reset_state(
     peer_id: &PeerId,
@@ -963,6 +1099,7 @@ pub(crate) fn run(
     state: Arc<State>,
 ) {
     // Connect peers with initial topology
+    sumeragi.update_certificates(&state.view().world);
     sumeragi.connect_peers(&sumeragi.topology);
 
     let genesis_account = AccountId::new(
@@ -1008,7 +1145,7 @@ pub(crate) fn run(
     let mut last_view_change_time = Instant::now();
 
     sumeragi.was_commit = false;
-    sumeragi.round_start_time = Instant::now();
+    sumeragi.start_round();
     while !should_terminate(&mut shutdown_receiver) {
         if should_sleep {
             let span = span!(Level::TRACE, "main_thread_sleep");
@@ -1031,16 +1168,17 @@ pub(crate) fn run(
                 !expired
             });
 
+        let max_transactions: NonZeroUsize = state
+            .world
+            .view()
+            .parameters
+            .block
+            .max_transactions
+            .try_into()
+            .expect("INTERNAL BUG: transactions in block exceed usize::MAX");
         sumeragi.queue.get_transactions_for_block(
             &state_view,
-            state
-                .world
-                .view()
-                .parameters
-                .block
-                .max_transactions
-                .try_into()
-                .expect("INTERNAL BUG: transactions in block exceed usize::MAX"),
+            max_transactions.saturating_mul(sumeragi.block_packing_lookahead),
             &mut sumeragi.transaction_cache,
         );
 
@@ -1133,7 +1271,7 @@ pub(crate) fn run(
                     .unwrap_or_else(|err| error!("{err}"));
             }
 
-            let msg = ControlFlowMessage::new(view_change_proof_chain.clone());
+            let msg = ControlFlowMessage::new((&view_change_proof_chain).into());
             sumeragi.broadcast_control_flow_packet(msg);
 
             // NOTE: View change must be periodically suggested until it is accepted.
@@ -1159,6 +1297,23 @@ pub(crate) fn run(
         if sumeragi.role() == Role::Leader && voting_block.is_none() {
             sumeragi.try_create_block(&state, &mut voting_block);
         }
+
+        sumeragi.update_round_status(
+            state.view().height(),
+            voting_block.as_ref(),
+            &voting_signatures,
+            &view_change_proof_chain,
+        );
+    }
+
+    // NOTE: Uncommitted state of the voting block is simply discarded,
+    // the block will be received via block sync after restart
+    if let Some(VotingBlock { block, .. }) = voting_block {
+        info!(
+            peer_id=%sumeragi.peer_id,
+            block=%block.as_ref().hash(),
+            "Aborting consensus round in progress"
+        );
     }
 }
 
@@ -1360,6 +1515,41 @@ fn categorize_block_sync(
     }
 }
 
+/// Select transactions of the next block out of the `transaction_cache` of the leader.
+///
+/// Transactions which don't fit into the rest of the block are left in the cache for the next
+/// block. Transactions larger than a whole block can never be committed, so they are removed
+/// from the cache, which also removes them from the queue.
+fn select_block_transactions(
+    transaction_cache: &mut Vec<TransactionGuard>,
+    strategy: &dyn BlockPackingStrategy,
+    limits: BlockParameters,
+    peer_id: &PeerId,
+) -> Vec<AcceptedTransaction> {
+    let max_size_bytes = limits.max_size_bytes.get();
+    transaction_cache.retain(|tx| {
+        let fits = tx.as_ref().encoded_size() as u64 <= max_size_bytes;
+        if !fits {
+            warn!(
+                %peer_id,
+                tx=%tx.as_ref().hash(),
+                max_size_bytes,
+                "Transaction doesn't fit into a block and is dropped"
+            );
+        }
+        fits
+    });
+
+    let candidates = transaction_cache
+        .iter()
+        .map(|tx| tx.as_ref())
+        .collect::<Vec<_>>();
+    packing::pack(strategy, &candidates, limits)
+        .into_iter()
+        .map(|idx| transaction_cache[idx].deref().clone())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use iroha_data_model::{isi::InstructionBox, transaction::TransactionBuilder};
@@ -1752,6 +1942,7 @@ mod tests {
         let mut voting_block = Some(VotingBlock::new(
             ValidBlock::new_dummy(&leader_private_key),
             state.block(),
+            Instant::now(),
         ));
 
         let block_sync_type = categorize_block_sync(&block, &state.view());
@@ -1767,4 +1958,97 @@ mod tests {
         assert!(matches!(result, Err((_, BlockSyncError::BlockNotValid(_)))));
         assert!(voting_block.is_some());
     }
+
+    #[test]
+    async fn leader_leaves_transactions_not_fitting_into_block_queued() {
+        use iroha_config::parameters::actual::Queue as QueueConfig;
+        use iroha_data_model::{isi::Log, parameter::TransactionParameters, Level};
+        use iroha_primitives::time::TimeSource;
+
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::test().start();
+        let state = State::new(
+            crate::queue::tests::world_with_test_domains(),
+            kura,
+            query_handle,
+        );
+        let (_time_handle, time_source) = TimeSource::new_mock(Duration::default());
+        let queue = Arc::new(Queue::test(QueueConfig::default(), &time_source));
+
+        let tx = |message: &str, log_count: u32| {
+            let tx = TransactionBuilder::new_with_time_source(
+                chain_id.clone(),
+                alice_id.clone(),
+                &time_source,
+            )
+            .with_instructions((0..log_count).map(|_| Log::new(Level::INFO, message.to_owned())))
+            .sign(alice_keypair.private_key());
+            let limits = TransactionParameters {
+                max_instructions: nonzero!(4096_u64),
+                ..TransactionParameters::default()
+            };
+            AcceptedTransaction::accept(tx, &chain_id, limits).expect("Valid")
+        };
+        let small = ["a", "b", "c"].map(|message| tx(message, 1));
+        let oversized = tx("d", 100);
+        let small_size_bytes = small[0].as_ref().encoded_size() as u64;
+        for tx in small.iter().chain([&oversized]) {
+            queue
+                .push(tx.clone(), &state.view())
+                .expect("Failed to push tx into queue");
+        }
+
+        let mut transaction_cache = Vec::new();
+        queue.get_transactions_for_block(&state.view(), nonzero!(10_usize), &mut transaction_cache);
+        assert_eq!(transaction_cache.len(), 4);
+
+        // Room for two small transactions only
+        let limits = BlockParameters::new(
+            nonzero!(10_u64),
+            std::num::NonZeroU64::new(2 * small_size_bytes).unwrap(),
+        );
+        let transactions = select_block_transactions(
+            &mut transaction_cache,
+            &packing::Fifo,
+            limits,
+            &PeerId::new(
+                "127.0.0.1:1337".parse().unwrap(),
+                KeyPair::random().into_parts().0,
+            ),
+        );
+
+        fn hashes<'tx>(
+            txs: impl IntoIterator<Item = &'tx AcceptedTransaction>,
+        ) -> Vec<HashOf<SignedTransaction>> {
+            txs.into_iter().map(|tx| tx.as_ref().hash()).collect()
+        }
+        assert_eq!(hashes(&transactions), hashes(&small[..2]));
+        // The third small transaction is left for the next block,
+        // while the oversized one can never be committed and is dropped
+        assert_eq!(
+            hashes(transaction_cache.iter().map(Deref::deref)),
+            hashes(&small)
+        );
+        assert_eq!(queue.tx_len(), 3);
+    }
+
+    #[test]
+    async fn commit_time_is_measured_from_block_reception() {
+        let round_start = Instant::now();
+        // The peer has been idle for a while before the block arrived
+        let received_at = round_start + Duration::from_secs(5);
+        let committed_at = received_at + Duration::from_millis(200);
+
+        assert_eq!(
+            commit_time(Some(received_at), committed_at),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            commit_time(Some(committed_at), received_at),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(commit_time(None, committed_at), None);
+    }
 }
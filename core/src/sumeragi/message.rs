@@ -1,6 +1,7 @@
 //! Contains message structures for p2p communication during consensus.
 use iroha_crypto::HashOf;
 use iroha_data_model::block::{BlockSignature, SignedBlock};
+use iroha_data_model_derive::ValidatedDecode;
 use iroha_macro::*;
 use parity_scale_codec::{Decode, Encode};
 
@@ -26,12 +27,12 @@ pub enum BlockMessage {
 pub struct ControlFlowMessage {
     /// Proof of view change. As part of this message handling, all
     /// peers which agree with view change should sign it.
-    pub view_change_proofs: view_change::ProofChain,
+    pub view_change_proofs: view_change::SignedProofChain,
 }
 
 impl ControlFlowMessage {
     /// Helper function to construct a `ControlFlowMessage`
-    pub fn new(view_change_proofs: view_change::ProofChain) -> ControlFlowMessage {
+    pub fn new(view_change_proofs: view_change::SignedProofChain) -> ControlFlowMessage {
         ControlFlowMessage { view_change_proofs }
     }
 }
@@ -76,7 +77,8 @@ impl From<&ValidBlock> for BlockSigned {
 }
 
 /// `BlockCommitted` message structure.
-#[derive(Debug, Clone, Encode)]
+#[derive(Debug, Clone, Encode, ValidatedDecode)]
+#[validated_decode(decode_only)]
 pub struct BlockCommitted {
     /// Hash of the block being signed.
     pub hash: HashOf<SignedBlock>,
@@ -111,26 +113,14 @@ impl From<&SignedBlock> for BlockSyncUpdate {
 
 mod candidate {
     use indexmap::IndexSet;
-    use parity_scale_codec::Input;
 
     use super::*;
 
-    #[derive(Decode)]
-    struct BlockCommittedCandidate {
-        /// Hash of the block being signed.
-        pub hash: HashOf<SignedBlock>,
-        /// Set of signatures.
-        pub signatures: Vec<BlockSignature>,
-    }
-
-    impl BlockCommittedCandidate {
-        fn validate(self) -> Result<BlockCommitted, &'static str> {
+    impl BlockCommitted {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
             self.validate_signatures()?;
 
-            Ok(BlockCommitted {
-                hash: self.hash,
-                signatures: self.signatures,
-            })
+            Ok(self)
         }
 
         fn validate_signatures(&self) -> Result<(), &'static str> {
@@ -152,12 +142,4 @@ mod candidate {
             Ok(())
         }
     }
-
-    impl Decode for BlockCommitted {
-        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            BlockCommittedCandidate::decode(input)?
-                .validate()
-                .map_err(Into::into)
-        }
-    }
 }
@@ -9,14 +9,21 @@ use std::{
 };
 
 use eyre::Result;
-use iroha_config::parameters::actual::{Common as CommonConfig, Sumeragi as SumeragiConfig};
+use iroha_config::{
+    parameters::actual::{Common as CommonConfig, Sumeragi as SumeragiConfig},
+    peer::Role as PeerRole,
+};
+use iroha_crypto::HashOf;
 use iroha_data_model::{account::AccountId, block::SignedBlock, prelude::*};
 use iroha_genesis::GenesisBlock;
 use iroha_logger::prelude::*;
 use network_topology::{Role, Topology};
+use serde::Serialize;
+use tokio::sync::watch;
 
 use crate::{
-    block::ValidBlock,
+    block::{BlockValidationError, ValidBlock},
+    consistency::ConsistencyCheckerHandle,
     handler::ThreadHandler,
     kura::BlockCount,
     state::{State, StateBlock},
@@ -25,24 +32,32 @@ use crate::{
 pub mod main_loop;
 pub mod message;
 pub mod network_topology;
+pub mod packing;
 pub mod view_change;
 
 use self::{message::*, view_change::ProofChain};
 use crate::{kura::Kura, prelude::*, queue::Queue, EventsSender, IrohaNetwork, NetworkMessage};
 
 /// Handle to `Sumeragi` actor
+///
+/// NOTE: at least one handle should outlive the [`ThreadHandler`] returned from
+/// [`SumeragiHandle::start`] to prevent sumeragi thread from panicking
 #[derive(Clone)]
 pub struct SumeragiHandle {
     peer_id: PeerId,
     /// Counter for amount of dropped messages by sumeragi
     dropped_messages_metric: iroha_telemetry::metrics::DroppedMessagesCounter,
-    _thread_handle: Arc<ThreadHandler>,
-    // Should be dropped after `_thread_handle` to prevent sumeargi thread from panicking
     control_message_sender: mpsc::SyncSender<ControlFlowMessage>,
     message_sender: mpsc::SyncSender<BlockMessage>,
+    round_status: watch::Receiver<RoundStatus>,
 }
 
 impl SumeragiHandle {
+    /// Get the latest snapshot of the consensus round state.
+    pub fn round_status(&self) -> RoundStatus {
+        self.round_status.borrow().clone()
+    }
+
     /// Deposit a sumeragi control flow network message.
     pub fn incoming_control_flow_message(&self, msg: ControlFlowMessage) {
         if let Err(error) = self.control_message_sender.try_send(msg) {
@@ -71,14 +86,18 @@ impl SumeragiHandle {
         }
     }
 
-    fn replay_block(
+    /// Re-execute the stored `block` on top of `state_block`.
+    ///
+    /// # Errors
+    /// If the block is no longer valid when re-executed
+    pub(crate) fn replay_block(
         chain_id: &ChainId,
         genesis_account: &AccountId,
         block: &SignedBlock,
         state_block: &mut StateBlock<'_>,
         events_sender: &EventsSender,
         topology: &mut Topology,
-    ) {
+    ) -> Result<(), BlockValidationError> {
         // NOTE: topology need to be updated up to block's view_change_index
         topology.nth_rotation(block.header().view_change_index as usize);
 
@@ -96,12 +115,12 @@ impl SumeragiHandle {
         .unpack(|e| {
             let _ = events_sender.send(e.into());
         })
-        .expect("INTERNAL BUG: Invalid block stored in Kura")
+        .map_err(|(_, error)| error)?
         .commit(topology)
         .unpack(|e| {
             let _ = events_sender.send(e.into());
         })
-        .expect("INTERNAL BUG: Invalid block stored in Kura");
+        .map_err(|(_, error)| error)?;
 
         if block.as_ref().header().is_genesis() {
             *topology = Topology::new(state_block.world.trusted_peers_ids.clone());
@@ -115,10 +134,15 @@ impl SumeragiHandle {
             .for_each(|e| {
                 let _ = events_sender.send(e);
             });
+
+        Ok(())
     }
 
     /// Start [`Sumeragi`] actor and return handle to it.
     ///
+    /// Dropping the returned [`ThreadHandler`] stops the consensus at the
+    /// boundary of the current main loop iteration, so that no block is left half-applied.
+    ///
     /// # Panics
     /// May panic if something is of during initialization which is bug.
     #[allow(clippy::too_many_lines)]
@@ -133,13 +157,15 @@ impl SumeragiHandle {
             network,
             genesis_network,
             block_count: BlockCount(block_count),
+            consistency_checker,
             sumeragi_metrics:
                 SumeragiMetrics {
                     view_changes,
                     dropped_messages,
+                    blocks: block_metrics,
                 },
         }: SumeragiStartArgs,
-    ) -> SumeragiHandle {
+    ) -> (SumeragiHandle, ThreadHandler) {
         let (control_message_sender, control_message_receiver) = mpsc::sync_channel(100);
         let (message_sender, message_receiver) = mpsc::sync_channel(100);
 
@@ -182,7 +208,8 @@ impl SumeragiHandle {
                 &mut state_block,
                 &events_sender,
                 &mut topology,
-            );
+            )
+            .expect("INTERNAL BUG: Invalid block stored in Kura");
 
             state_block.commit();
         }
@@ -195,6 +222,25 @@ impl SumeragiHandle {
         let debug_force_soft_fork = false;
 
         let peer_id = common_config.peer;
+        if common_config.role == PeerRole::ReadReplica
+            && state.view().height() > 0
+            && topology.role(&peer_id) != Role::Undefined
+        {
+            warn!(
+                %peer_id,
+                "Read replica is registered as a peer and takes part in consensus, unregister it"
+            );
+        }
+        let (round_status_sender, round_status) = watch::channel(RoundStatus {
+            height: state.view().height(),
+            view_change_index: topology.view_change_index(),
+            role: topology.role(&peer_id),
+            leader: topology.leader().clone(),
+            round_start_ms: 0,
+            voting_block: None,
+            voting_signatures: 0,
+            view_change_proofs: Vec::new(),
+        });
         let sumeragi = main_loop::Sumeragi {
             chain_id: common_config.chain,
             key_pair: common_config.key_pair,
@@ -208,9 +254,15 @@ impl SumeragiHandle {
             debug_force_soft_fork,
             topology,
             transaction_cache: Vec::new(),
+            block_packing: sumeragi_config.block_packing.into(),
+            block_packing_lookahead: sumeragi_config.block_packing_lookahead,
             view_changes_metric: view_changes,
+            block_metrics,
+            consistency_checker,
             was_commit: false,
             round_start_time: Instant::now(),
+            round_start_ms: 0,
+            round_status: round_status_sender,
         };
 
         // Oneshot channel to allow forcefully stopping the thread.
@@ -233,13 +285,15 @@ impl SumeragiHandle {
         };
 
         let thread_handle = ThreadHandler::new(Box::new(shutdown), thread_handle);
-        SumeragiHandle {
+        let handle = SumeragiHandle {
             peer_id,
             dropped_messages_metric: dropped_messages,
             control_message_sender,
             message_sender,
-            _thread_handle: Arc::new(thread_handle),
-        }
+            round_status,
+        };
+
+        (handle, thread_handle)
     }
 }
 
@@ -252,12 +306,34 @@ pub const PEERS_CONNECT_INTERVAL: Duration = Duration::from_secs(1);
 /// The interval of telemetry updates.
 pub const TELEMETRY_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Snapshot of the consensus round state as seen by this peer. Meant for debugging.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RoundStatus {
+    /// Height of the latest committed block
+    pub height: usize,
+    /// Number of view changes in the current round
+    pub view_change_index: usize,
+    /// Role of this peer in the current round
+    pub role: Role,
+    /// Leader of the current round
+    pub leader: PeerId,
+    /// Time since the Unix epoch (in ms) at which the current round started
+    pub round_start_ms: u64,
+    /// Hash of the block which is currently voted on
+    pub voting_block: Option<HashOf<SignedBlock>>,
+    /// Number of signatures collected for the voting block (only proxy tail collects them)
+    pub voting_signatures: usize,
+    /// Number of signatures collected for each view change proof of the current round,
+    /// indexed by the view change index
+    pub view_change_proofs: Vec<usize>,
+}
+
 /// Structure represents a block that is currently in discussion.
 pub struct VotingBlock<'state> {
     /// Valid Block
     block: ValidBlock,
-    /// At what time has this peer voted for this block
-    pub voted_at: Instant,
+    /// When this peer created the block or received it for voting
+    pub received_at: Instant,
     /// [`WorldState`] after applying transactions to it but before it was committed
     pub state_block: StateBlock<'state>,
 }
@@ -269,11 +345,11 @@ impl AsRef<ValidBlock> for VotingBlock<'_> {
 }
 
 impl VotingBlock<'_> {
-    /// Construct new `VotingBlock` with current time.
-    fn new(block: ValidBlock, state_block: StateBlock<'_>) -> VotingBlock {
+    /// Construct new `VotingBlock` created or received at `received_at`.
+    fn new(block: ValidBlock, state_block: StateBlock<'_>, received_at: Instant) -> VotingBlock {
         VotingBlock {
             block,
-            voted_at: Instant::now(),
+            received_at,
             state_block,
         }
     }
@@ -291,6 +367,7 @@ pub struct SumeragiStartArgs {
     pub network: IrohaNetwork,
     pub genesis_network: GenesisWithPubKey,
     pub block_count: BlockCount,
+    pub consistency_checker: ConsistencyCheckerHandle,
     pub sumeragi_metrics: SumeragiMetrics,
 }
 
@@ -300,6 +377,21 @@ pub struct SumeragiMetrics {
     pub view_changes: iroha_telemetry::metrics::ViewChangesGauge,
     /// Amount of dropped messages by sumeragi
     pub dropped_messages: iroha_telemetry::metrics::DroppedMessagesCounter,
+    /// Metrics reported on every block commit
+    pub blocks: BlockMetrics,
+}
+
+/// Metrics reported by sumeragi on every block commit
+#[derive(Clone)]
+pub struct BlockMetrics {
+    /// Time from creating or receiving a block for voting till its commit in milliseconds
+    pub commit_time_ms: iroha_telemetry::metrics::CommitTimeHistogram,
+    /// Number of signatures of the latest committed block
+    pub signatures: iroha_telemetry::metrics::BlockGauge,
+    /// Number of transactions in the latest committed block
+    pub transactions: iroha_telemetry::metrics::BlockGauge,
+    /// Size of the latest committed block in bytes
+    pub size_bytes: iroha_telemetry::metrics::BlockGauge,
 }
 
 /// Optional genesis paired with genesis public key for verification
@@ -268,7 +268,7 @@ impl<'topology> ConsensusTopology<'topology> {
 }
 
 /// Possible Peer's roles in consensus.
-#[derive(Debug, Display, Clone, Copy, PartialOrd, Ord, Eq, PartialEq, Hash)]
+#[derive(Debug, Display, Clone, Copy, PartialOrd, Ord, Eq, PartialEq, Hash, serde::Serialize)]
 pub enum Role {
     /// Leader.
     Leader,
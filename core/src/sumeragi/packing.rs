@@ -0,0 +1,282 @@
+//! Strategies deciding which of the pending transactions the leader packs into a block.
+use std::cmp::Reverse;
+
+use indexmap::IndexMap;
+pub use iroha_config::sumeragi::BlockPacking;
+use iroha_data_model::{
+    account::AccountId, parameter::BlockParameters, transaction::SignedTransaction,
+};
+use parity_scale_codec::Encode as _;
+
+/// Key of the transaction metadata which holds the priority used by [`Priority`] strategy.
+///
+/// Transactions without it have the lowest priority (`0`).
+pub const PRIORITY_KEY: &str = "priority";
+
+/// Ordering of the pending transactions used by the leader during block creation
+pub trait BlockPackingStrategy: Send {
+    /// Return indices of `candidates` in the order they should be packed into a block.
+    ///
+    /// `candidates` are given in the order of arrival.
+    fn order(&self, candidates: &[&SignedTransaction]) -> Vec<usize>;
+}
+
+/// Pack transactions in the order of arrival
+#[derive(Debug, Clone, Copy)]
+pub struct Fifo;
+
+impl BlockPackingStrategy for Fifo {
+    fn order(&self, candidates: &[&SignedTransaction]) -> Vec<usize> {
+        (0..candidates.len()).collect()
+    }
+}
+
+/// Pack transactions with higher priority first, see [`PRIORITY_KEY`].
+/// Transactions of equal priority are packed in the order of arrival.
+#[derive(Debug, Clone, Copy)]
+pub struct Priority;
+
+impl Priority {
+    fn priority_of(tx: &SignedTransaction) -> u64 {
+        tx.metadata()
+            .get(PRIORITY_KEY)
+            .and_then(|priority| priority.try_into_any().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl BlockPackingStrategy for Priority {
+    fn order(&self, candidates: &[&SignedTransaction]) -> Vec<usize> {
+        let mut order = (0..candidates.len()).collect::<Vec<_>>();
+        // NOTE: sort is stable, so the order of arrival is kept for equal priorities
+        order.sort_by_cached_key(|idx| Reverse(Self::priority_of(candidates[*idx])));
+        order
+    }
+}
+
+/// Pack one transaction of every account in turns, so that accounts
+/// submitting many transactions can't starve the others.
+/// Transactions of the same account are packed in the order of arrival.
+#[derive(Debug, Clone, Copy)]
+pub struct FairRoundRobin;
+
+impl BlockPackingStrategy for FairRoundRobin {
+    fn order(&self, candidates: &[&SignedTransaction]) -> Vec<usize> {
+        let mut per_account = IndexMap::<&AccountId, Vec<usize>>::new();
+        for (idx, tx) in candidates.iter().enumerate() {
+            per_account.entry(tx.authority()).or_default().push(idx);
+        }
+
+        let mut order = Vec::with_capacity(candidates.len());
+        for turn in 0.. {
+            let before = order.len();
+            order.extend(
+                per_account
+                    .values()
+                    .filter_map(|txs| txs.get(turn).copied()),
+            );
+            if order.len() == before {
+                break;
+            }
+        }
+        order
+    }
+}
+
+impl From<BlockPacking> for Box<dyn BlockPackingStrategy> {
+    fn from(value: BlockPacking) -> Self {
+        match value {
+            BlockPacking::Fifo => Box::new(Fifo),
+            BlockPacking::Priority => Box::new(Priority),
+            BlockPacking::FairRoundRobin => Box::new(FairRoundRobin),
+        }
+    }
+}
+
+/// Select transactions for the next block out of `candidates`.
+///
+/// Transactions are taken in the order given by `strategy` while they fit into `limits`.
+/// Return indices of the selected `candidates` in the order they should appear in the block.
+pub fn pack(
+    strategy: &dyn BlockPackingStrategy,
+    candidates: &[&SignedTransaction],
+    limits: BlockParameters,
+) -> Vec<usize> {
+    let max_transactions = limits
+        .max_transactions
+        .get()
+        .try_into()
+        .unwrap_or(usize::MAX);
+
+    let mut size_bytes = 0;
+    strategy
+        .order(candidates)
+        .into_iter()
+        // Transactions which don't fit are left for the next block
+        .filter(|idx| {
+            let tx_size_bytes = candidates[*idx].encoded_size() as u64;
+            if size_bytes + tx_size_bytes > limits.max_size_bytes.get() {
+                return false;
+            }
+            size_bytes += tx_size_bytes;
+            true
+        })
+        .take(max_transactions)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::{isi::Log, metadata::Metadata, ChainId, Level};
+    use nonzero_ext::nonzero;
+    use test_samples::gen_account_in;
+
+    use super::*;
+
+    const BLOCK_SIZE: usize = 8;
+    /// Number of blocks worth of transactions the leader chooses from
+    const LOOKAHEAD: usize = 4;
+    const ROUNDS: usize = 10;
+    /// Transactions submitted by the busy account every round
+    const BUSY_ACCOUNT_LOAD: usize = 6;
+    const IDLE_ACCOUNTS: usize = 3;
+
+    struct Pending {
+        tx: SignedTransaction,
+        submitted_at: usize,
+    }
+
+    #[derive(Default)]
+    struct Report {
+        committed: usize,
+        /// Sum of the number of rounds transactions of idle accounts waited for the commit
+        idle_latency: usize,
+        idle_committed: usize,
+        /// Rounds high priority transactions waited for the commit
+        high_priority_latency: Vec<usize>,
+    }
+
+    fn tx(
+        authority: &AccountId,
+        key_pair: &iroha_crypto::KeyPair,
+        priority: u64,
+    ) -> SignedTransaction {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let mut metadata = Metadata::default();
+        if priority > 0 {
+            metadata.insert(PRIORITY_KEY.parse().unwrap(), priority);
+        }
+        iroha_data_model::transaction::TransactionBuilder::new(chain_id, authority.clone())
+            .with_instructions([Log::new(Level::INFO, "packing".to_owned())])
+            .with_metadata(metadata)
+            .sign(key_pair.private_key())
+    }
+
+    /// Simulate the leader creating blocks while the load is slightly above the capacity:
+    /// one account submits many transactions per round and a few other accounts submit one.
+    /// Every third round the last of the busy account transactions has high priority.
+    fn simulate(strategy: &dyn BlockPackingStrategy) -> Report {
+        let (busy_account, busy_key_pair) = gen_account_in("wonderland");
+        let idle_accounts = (0..IDLE_ACCOUNTS)
+            .map(|_| gen_account_in("wonderland"))
+            .collect::<Vec<_>>();
+        let limits = BlockParameters::new(nonzero!(BLOCK_SIZE as u64), nonzero!(u64::MAX));
+
+        let mut report = Report::default();
+        let mut pending = Vec::<Pending>::new();
+        for round in 0..ROUNDS {
+            for i in 0..BUSY_ACCOUNT_LOAD {
+                let priority = u64::from(i == BUSY_ACCOUNT_LOAD - 1 && round % 3 == 0) * 10;
+                pending.push(Pending {
+                    tx: tx(&busy_account, &busy_key_pair, priority),
+                    submitted_at: round,
+                });
+            }
+            for (account, key_pair) in &idle_accounts {
+                pending.push(Pending {
+                    tx: tx(account, key_pair, 0),
+                    submitted_at: round,
+                });
+            }
+
+            let pool = pending.len().min(BLOCK_SIZE * LOOKAHEAD);
+            let candidates = pending[..pool]
+                .iter()
+                .map(|pending| &pending.tx)
+                .collect::<Vec<_>>();
+            let mut selected = pack(strategy, &candidates, limits);
+            assert!(selected.len() <= BLOCK_SIZE);
+            report.committed += selected.len();
+
+            selected.sort_unstable();
+            for idx in selected.into_iter().rev() {
+                let Pending { tx, submitted_at } = pending.remove(idx);
+                let latency = round - submitted_at;
+                if tx.authority() != &busy_account {
+                    report.idle_latency += latency;
+                    report.idle_committed += 1;
+                } else if Priority::priority_of(&tx) > 0 {
+                    report.high_priority_latency.push(latency);
+                }
+            }
+        }
+
+        report
+    }
+
+    #[test]
+    fn throughput_is_the_same_for_all_strategies() {
+        for strategy in [
+            BlockPacking::Fifo,
+            BlockPacking::Priority,
+            BlockPacking::FairRoundRobin,
+        ] {
+            let report = simulate(&*Box::<dyn BlockPackingStrategy>::from(strategy));
+            assert_eq!(report.committed, ROUNDS * BLOCK_SIZE, "{strategy}");
+        }
+    }
+
+    #[test]
+    fn fair_round_robin_reduces_latency_of_idle_accounts() {
+        let fifo = simulate(&Fifo);
+        let fair = simulate(&FairRoundRobin);
+
+        // Every round each idle account gets into the block
+        assert_eq!(fair.idle_committed, ROUNDS * IDLE_ACCOUNTS);
+        assert_eq!(fair.idle_latency, 0);
+        assert!(fifo.idle_committed < fair.idle_committed);
+        assert!(fifo.idle_latency > 0);
+    }
+
+    #[test]
+    fn priority_transactions_are_packed_first() {
+        let fifo = simulate(&Fifo);
+        let priority = simulate(&Priority);
+
+        assert!(!priority.high_priority_latency.is_empty());
+        assert!(priority
+            .high_priority_latency
+            .iter()
+            .all(|latency| *latency == 0));
+        assert!(fifo
+            .high_priority_latency
+            .iter()
+            .any(|latency| *latency > 0));
+    }
+
+    #[test]
+    fn block_size_limit_is_respected() {
+        let (account, key_pair) = gen_account_in("wonderland");
+        let txs = (0..4)
+            .map(|_| tx(&account, &key_pair, 0))
+            .collect::<Vec<_>>();
+        let candidates = txs.iter().collect::<Vec<_>>();
+        let tx_size = txs[0].encoded_size() as u64;
+        let limits = BlockParameters::new(
+            nonzero!(10_u64),
+            (2 * tx_size + tx_size / 2).try_into().unwrap(),
+        );
+
+        assert_eq!(pack(&Fifo, &candidates, limits), vec![0, 1]);
+    }
+}
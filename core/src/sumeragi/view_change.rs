@@ -2,15 +2,20 @@
 //! Where view change is a process of changing topology due to some faulty network behavior.
 
 use eyre::Result;
-use indexmap::IndexSet;
-use iroha_crypto::{HashOf, PublicKey, SignatureOf};
+use iroha_crypto::{HashOf, PublicKey, SignatureOf, Unverified, Verified};
 use iroha_data_model::block::SignedBlock;
+use iroha_data_model_derive::ValidatedDecode;
 use parity_scale_codec::{Decode, Encode};
 use thiserror::Error;
 
 use super::network_topology::Topology;
 
-type ViewChangeProofSignature = (PublicKey, SignatureOf<ViewChangeProofPayload>);
+type ViewChangeProofSignature = (PublicKey, Unverified<SignatureOf<ViewChangeProofPayload>>);
+
+/// Maximal number of proofs in a [`SignedProofChain`] received from another peer
+pub const MAX_PROOF_CHAIN_LENGTH: usize = 1024;
+/// Maximal number of signatures of a single proof received from another peer
+pub const MAX_PROOF_SIGNATURES: usize = 512;
 
 /// Error emerge during insertion of `Proof` into `ProofChain`
 #[derive(Error, displaydoc::Display, Debug, Clone, Copy)]
@@ -18,8 +23,10 @@ type ViewChangeProofSignature = (PublicKey, SignatureOf<ViewChangeProofPayload>)
 pub enum Error {
     /// Block hash of proof doesn't match hash of proof chain
     BlockHashMismatch,
-    /// View change index is not present in proof chain
+    /// Proof is for a view change after the one which is being proven
     ViewChangeNotFound,
+    /// Proof isn't validly signed by any of the peers in topology
+    NoValidSignatures,
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
@@ -31,12 +38,23 @@ struct ViewChangeProofPayload {
 }
 
 /// The proof of a view change. It needs to be signed by f+1 peers for proof to be valid and view change to happen.
-#[derive(Debug, Clone, Encode)]
+///
+/// Signatures of the proof are yet to be verified, see [`VerifiedViewChangeProof`].
+#[derive(Debug, Clone, Encode, ValidatedDecode)]
+#[validated_decode(decode_only)]
 pub struct SignedViewChangeProof {
+    #[validated_decode(decode_with = candidate::decode_signatures)]
     signatures: Vec<ViewChangeProofSignature>,
     payload: ViewChangeProofPayload,
 }
 
+/// [`SignedViewChangeProof`] of which only the valid signatures of the peers in topology are kept.
+#[derive(Debug, Clone)]
+struct VerifiedViewChangeProof {
+    signatures: Vec<(PublicKey, Verified<SignatureOf<ViewChangeProofPayload>>)>,
+    payload: ViewChangeProofPayload,
+}
+
 /// Builder for proofs
 #[repr(transparent)]
 pub struct ProofBuilder(SignedViewChangeProof);
@@ -61,30 +79,78 @@ impl ProofBuilder {
 
     /// Sign this message with the peer's private key.
     pub fn sign(mut self, key_pair: &iroha_crypto::KeyPair) -> SignedViewChangeProof {
-        let signature = SignatureOf::new(key_pair.private_key(), &self.0.payload);
-        self.0.signatures = vec![(key_pair.public_key().clone(), signature)];
+        let signature = Verified::sign(key_pair.private_key(), &self.0.payload);
+        self.0.signatures = vec![(key_pair.public_key().clone(), signature.into())];
         self.0
     }
 }
 
 impl SignedViewChangeProof {
+    /// Proof with the same payload and only those signatures of `self` which are valid.
+    fn into_verified(self, topology: &Topology) -> Option<VerifiedViewChangeProof> {
+        let mut proof = VerifiedViewChangeProof {
+            signatures: Vec::new(),
+            payload: self.payload,
+        };
+        proof.merge_signatures(self.signatures, topology);
+
+        (!proof.signatures.is_empty()).then_some(proof)
+    }
+}
+
+impl From<&VerifiedViewChangeProof> for SignedViewChangeProof {
+    fn from(proof: &VerifiedViewChangeProof) -> Self {
+        Self {
+            signatures: proof
+                .signatures
+                .iter()
+                .map(|(public_key, signature)| (public_key.clone(), signature.clone().into()))
+                .collect(),
+            payload: proof.payload.clone(),
+        }
+    }
+}
+
+impl VerifiedViewChangeProof {
     /// Verify the signatures of `other` and add them to this proof.
-    fn merge_signatures(&mut self, other: Vec<ViewChangeProofSignature>, topology: &Topology) {
-        let signatures = core::mem::take(&mut self.signatures)
-            .into_iter()
-            .collect::<IndexSet<_>>();
-
-        self.signatures = other
-            .into_iter()
-            .fold(signatures, |mut acc, (public_key, signature)| {
-                if topology.position(&public_key).is_some() {
-                    acc.insert((public_key, signature));
-                }
+    ///
+    /// Signatures are verified lazily, only once the proof becomes relevant
+    /// for the current view change, so that received proofs are cheap to decode.
+    /// Signatures of peers outside of `topology` are dropped and only the first
+    /// signature of every peer is kept.
+    ///
+    /// Returns the number of valid signatures in `other`, including the ones
+    /// of the peers which have already signed this proof.
+    fn merge_signatures(
+        &mut self,
+        other: Vec<ViewChangeProofSignature>,
+        topology: &Topology,
+    ) -> usize {
+        let mut valid_count = 0;
 
-                acc
-            })
-            .into_iter()
-            .collect();
+        for (public_key, signature) in other {
+            if topology.position(&public_key).is_none() {
+                continue;
+            }
+            let known = self
+                .signatures
+                .iter()
+                .find(|(signatory, _)| *signatory == public_key);
+            if known.is_some_and(|(_, known)| **known == *signature.as_unverified()) {
+                valid_count += 1;
+                continue;
+            }
+            let Ok(signature) = signature.verify(&public_key, &self.payload) else {
+                continue;
+            };
+
+            valid_count += 1;
+            if known.is_none() {
+                self.signatures.push((public_key, signature));
+            }
+        }
+
+        valid_count
     }
 
     /// Verify if the proof is valid, given the peers in `topology`.
@@ -101,8 +167,38 @@ impl SignedViewChangeProof {
 }
 
 /// Structure representing sequence of view change proofs.
-#[derive(Debug, Clone, Encode, Default)]
-pub struct ProofChain(Vec<SignedViewChangeProof>);
+///
+/// The chain is proven up to the first proof which isn't signed by more than
+/// [`Topology::max_faults`] peers and only the proof of the next view change
+/// is updated. Proofs of the view changes which are already proven are ignored.
+/// The chain only holds verified signatures, it's exchanged with other peers as [`SignedProofChain`].
+#[derive(Debug, Clone, Default)]
+pub struct ProofChain(Vec<VerifiedViewChangeProof>);
+
+/// [`ProofChain`] as it is sent to and received from other peers
+#[derive(Debug, Clone, Encode)]
+pub struct SignedProofChain(Vec<SignedViewChangeProof>);
+
+impl From<&ProofChain> for SignedProofChain {
+    fn from(chain: &ProofChain) -> Self {
+        Self(chain.0.iter().map(Into::into).collect())
+    }
+}
+
+/// Number of proofs at the start of the chain which are for `latest_block` and in order
+fn valid_prefix_len<'proof>(
+    payloads: impl IntoIterator<Item = &'proof ViewChangeProofPayload>,
+    latest_block: HashOf<SignedBlock>,
+) -> usize {
+    payloads
+        .into_iter()
+        .enumerate()
+        .take_while(|(i, payload)| {
+            let view_change_index = payload.view_change_index as usize;
+            payload.latest_block == latest_block && view_change_index == *i
+        })
+        .count()
+}
 
 impl ProofChain {
     /// Verify the view change proof chain.
@@ -124,17 +220,14 @@ impl ProofChain {
             .count()
     }
 
+    /// Number of signatures of every proof in the chain.
+    pub fn signature_counts(&self) -> Vec<usize> {
+        self.0.iter().map(|proof| proof.signatures.len()).collect()
+    }
+
     /// Remove invalid proofs from the chain.
     pub fn prune(&mut self, latest_block: HashOf<SignedBlock>) {
-        let valid_count = self
-            .0
-            .iter()
-            .enumerate()
-            .take_while(|(i, proof)| {
-                let view_change_index = proof.payload.view_change_index as usize;
-                proof.payload.latest_block == latest_block && view_change_index == *i
-            })
-            .count();
+        let valid_count = valid_prefix_len(self.0.iter().map(|proof| &proof.payload), latest_block);
         self.0.truncate(valid_count);
     }
 
@@ -142,7 +235,8 @@ impl ProofChain {
     ///
     /// # Errors
     /// - If proof latest block hash doesn't match peer latest block hash
-    /// - If proof view change number differs from view change number
+    /// - If proof is for a view change after the next unproven one
+    /// - If proof isn't validly signed by any of the peers in topology
     pub fn insert_proof(
         &mut self,
         new_proof: SignedViewChangeProof,
@@ -153,60 +247,73 @@ impl ProofChain {
             return Err(Error::BlockHashMismatch);
         }
         let next_unfinished_view_change = self.verify_with_state(topology, latest_block);
-        if new_proof.payload.view_change_index as usize != next_unfinished_view_change {
-            return Err(Error::ViewChangeNotFound); // We only care about the current view change that may or may not happen.
+        let view_change_index = new_proof.payload.view_change_index as usize;
+        if view_change_index < next_unfinished_view_change {
+            return Ok(());
         }
-
-        let is_proof_chain_incomplete = next_unfinished_view_change < self.0.len();
-        if is_proof_chain_incomplete {
-            self.0[next_unfinished_view_change].merge_signatures(new_proof.signatures, topology);
-        } else {
-            self.0.push(new_proof);
+        if view_change_index > next_unfinished_view_change {
+            return Err(Error::ViewChangeNotFound);
         }
-        Ok(())
+
+        self.insert_next_proof(new_proof, next_unfinished_view_change, topology)
     }
 
     /// Add latest proof from other chain into current.
     ///
+    /// Only the proofs of `other` up to the first one for another block
+    /// or out of order are considered.
+    ///
     /// # Errors
-    /// - If there is mismatch between `other` proof chain latest block hash and peer's latest block hash
-    /// - If `other` proof chain doesn't have proof for current view chain
+    /// - If the first proof of `other` is for a block other than peer's latest block
+    /// - If `other` doesn't start with the first view change
+    /// - If proof of `other` for the next view change isn't validly signed by any of the peers in topology
     pub fn merge(
         &mut self,
-        mut other: Self,
+        mut other: SignedProofChain,
         topology: &Topology,
         latest_block: HashOf<SignedBlock>,
     ) -> Result<(), Error> {
-        other.prune(latest_block);
-
-        if other.0.is_empty() {
+        if other
+            .0
+            .first()
+            .is_some_and(|proof| proof.payload.latest_block != latest_block)
+        {
             return Err(Error::BlockHashMismatch);
         }
+        let valid_count =
+            valid_prefix_len(other.0.iter().map(|proof| &proof.payload), latest_block);
+        other.0.truncate(valid_count);
+        if other.0.is_empty() {
+            return Err(Error::ViewChangeNotFound);
+        }
 
         let next_unfinished_view_change = self.verify_with_state(topology, latest_block);
-        let is_proof_chain_incomplete = next_unfinished_view_change < self.0.len();
-        let other_contain_additional_proofs = next_unfinished_view_change < other.0.len();
-
-        match (is_proof_chain_incomplete, other_contain_additional_proofs) {
-            // Case 1: proof chain is incomplete and other have corresponding proof.
-            (true, true) => {
-                let new_proof = other.0.swap_remove(next_unfinished_view_change);
-                self.0[next_unfinished_view_change]
-                    .merge_signatures(new_proof.signatures, topology);
-            }
-            // Case 2: proof chain is complete, but other have additional proof.
-            (false, true) => {
-                let new_proof = other.0.swap_remove(next_unfinished_view_change);
-                self.0.push(new_proof);
-            }
-            // Case 3: proof chain is incomplete, but other doesn't contain corresponding proof.
-            // Usually this mean that sender peer is behind receiver peer.
-            (true, false) => {
-                return Err(Error::ViewChangeNotFound);
+        if other.0.len() <= next_unfinished_view_change {
+            // Sender peer is behind, its proofs are already proven
+            return Ok(());
+        }
+
+        let new_proof = other.0.swap_remove(next_unfinished_view_change);
+        self.insert_next_proof(new_proof, next_unfinished_view_change, topology)
+    }
+
+    /// Insert the proof of the next unproven view change, either adding
+    /// its signatures to the existing proof or appending it to the chain.
+    fn insert_next_proof(
+        &mut self,
+        new_proof: SignedViewChangeProof,
+        next_unfinished_view_change: usize,
+        topology: &Topology,
+    ) -> Result<(), Error> {
+        if let Some(proof) = self.0.get_mut(next_unfinished_view_change) {
+            if proof.merge_signatures(new_proof.signatures, topology) == 0 {
+                return Err(Error::NoValidSignatures);
             }
-            // Case 4: proof chain is complete, but other doesn't have any new peer.
-            // This considered normal course of action.
-            (false, false) => {}
+        } else {
+            let new_proof = new_proof
+                .into_verified(topology)
+                .ok_or(Error::NoValidSignatures)?;
+            self.0.push(new_proof);
         }
 
         Ok(())
@@ -215,24 +322,35 @@ impl ProofChain {
 
 mod candidate {
     use indexmap::IndexSet;
-    use parity_scale_codec::Input;
+    use parity_scale_codec::{Compact, Input};
 
     use super::*;
 
-    #[derive(Decode)]
-    struct SignedProofCandidate {
-        signatures: Vec<ViewChangeProofSignature>,
-        payload: ViewChangeProofPayload,
+    /// Decode vector rejecting the ones longer than `max_len` before decoding the elements
+    fn decode_bounded_vec<T: Decode, I: Input>(
+        input: &mut I,
+        max_len: usize,
+        error: &'static str,
+    ) -> Result<Vec<T>, parity_scale_codec::Error> {
+        let len = Compact::<u32>::decode(input)?.0 as usize;
+        if len > max_len {
+            return Err(error.into());
+        }
+
+        (0..len).map(|_| T::decode(input)).collect()
+    }
+
+    pub(super) fn decode_signatures<I: Input>(
+        input: &mut I,
+    ) -> Result<Vec<ViewChangeProofSignature>, parity_scale_codec::Error> {
+        decode_bounded_vec(input, MAX_PROOF_SIGNATURES, "Too many signatures in proof")
     }
 
-    impl SignedProofCandidate {
-        fn validate(self) -> Result<SignedViewChangeProof, &'static str> {
+    impl SignedViewChangeProof {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
             self.validate_signatures()?;
 
-            Ok(SignedViewChangeProof {
-                signatures: self.signatures,
-                payload: self.payload,
-            })
+            Ok(self)
         }
 
         fn validate_signatures(&self) -> Result<(), &'static str> {
@@ -251,34 +369,192 @@ mod candidate {
                     Ok(acc)
                 })?;
 
-            self.signatures
-                .iter()
-                .try_for_each(|(public_key, payload)| {
-                    payload
-                        .verify(public_key, &self.payload)
-                        .map_err(|_| "Invalid signature")
-                })?;
-
             Ok(())
         }
     }
 
-    impl Decode for SignedViewChangeProof {
-        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            SignedProofCandidate::decode(input)?
-                .validate()
-                .map_err(Into::into)
-        }
-    }
-    impl Decode for ProofChain {
+    impl Decode for SignedProofChain {
         fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            let proofs = Vec::<SignedViewChangeProof>::decode(input)?;
+            let proofs = decode_bounded_vec::<SignedViewChangeProof, _>(
+                input,
+                MAX_PROOF_CHAIN_LENGTH,
+                "Too many proofs in proof chain",
+            )?;
 
             if proofs.is_empty() {
                 return Err("Empty proof chain".into());
             }
 
-            Ok(ProofChain(proofs))
+            Ok(SignedProofChain(proofs))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_crypto::{Algorithm, Hash, KeyPair};
+    use iroha_data_model::peer::PeerId;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::sumeragi::network_topology::test_peers;
+
+    fn latest_block() -> HashOf<SignedBlock> {
+        HashOf::from_untyped_unchecked(Hash::prehashed([0; 32]))
+    }
+
+    #[test]
+    fn too_long_proof_chain_is_not_decoded() {
+        let key_pair = KeyPair::random();
+        let chain = |len| {
+            SignedProofChain(
+                (0..len)
+                    .map(|i| ProofBuilder::new(latest_block(), i).sign(&key_pair))
+                    .collect(),
+            )
+            .encode()
+        };
+
+        assert!(SignedProofChain::decode(&mut chain(MAX_PROOF_CHAIN_LENGTH).as_slice()).is_ok());
+        assert!(
+            SignedProofChain::decode(&mut chain(MAX_PROOF_CHAIN_LENGTH + 1).as_slice()).is_err()
+        );
+    }
+
+    #[test]
+    fn forged_signatures_are_not_counted() {
+        let key_pairs = core::iter::repeat_with(KeyPair::random)
+            .take(4)
+            .collect::<Vec<_>>();
+        let mut key_pair_iter = key_pairs.iter();
+        let topology = Topology::new(test_peers![0, 1, 2, 3: key_pair_iter]);
+        let payload = ProofBuilder::new(latest_block(), 0).0.payload;
+        let sign = |signatory: &KeyPair, key_pair: &KeyPair| {
+            (
+                signatory.public_key().clone(),
+                Unverified::new(SignatureOf::new(key_pair.private_key(), &payload)),
+            )
+        };
+
+        let forged = SignedProofChain(vec![SignedViewChangeProof {
+            signatures: vec![
+                sign(&key_pairs[0], &key_pairs[0]),
+                sign(&key_pairs[1], &key_pairs[0]),
+            ],
+            payload: payload.clone(),
+        }]);
+        let mut chain = ProofChain::default();
+        chain.merge(forged, &topology, latest_block()).unwrap();
+        assert_eq!(chain.verify_with_state(&topology, latest_block()), 0);
+
+        let genuine = SignedProofChain(vec![SignedViewChangeProof {
+            signatures: vec![sign(&key_pairs[1], &key_pairs[1])],
+            payload,
+        }]);
+        chain.merge(genuine, &topology, latest_block()).unwrap();
+        assert_eq!(chain.verify_with_state(&topology, latest_block()), 1);
+    }
+
+    /// Conformance vectors, see the description in the file for the format
+    const TEST_VECTORS: &str = include_str!("../../test_vectors/view_change_proofs.json");
+
+    #[derive(Deserialize)]
+    struct TestVectors {
+        topology: usize,
+        cases: Vec<TestCase>,
+    }
+
+    #[derive(Deserialize)]
+    struct TestCase {
+        name: String,
+        chain: Vec<TestProof>,
+        insert_proof: Option<TestProof>,
+        merge: Option<Vec<TestProof>>,
+        result: String,
+        verified: usize,
+        signatures: Vec<usize>,
+    }
+
+    #[derive(Deserialize)]
+    struct TestProof {
+        block: u8,
+        view_change_index: usize,
+        signatures: Vec<(usize, usize)>,
+    }
+
+    impl TestProof {
+        fn build(&self, key_pairs: &[KeyPair]) -> SignedViewChangeProof {
+            let latest_block = HashOf::from_untyped_unchecked(Hash::prehashed([self.block; 32]));
+            let payload = ProofBuilder::new(latest_block, self.view_change_index)
+                .0
+                .payload;
+            let signatures = self
+                .signatures
+                .iter()
+                .map(|&(signatory, signer)| {
+                    (
+                        key_pairs[signatory].public_key().clone(),
+                        Unverified::new(SignatureOf::new(
+                            key_pairs[signer].private_key(),
+                            &payload,
+                        )),
+                    )
+                })
+                .collect();
+
+            SignedViewChangeProof {
+                signatures,
+                payload,
+            }
+        }
+    }
+
+    #[test]
+    fn proof_chain_conforms_to_test_vectors() {
+        let vectors: TestVectors = serde_json::from_str(TEST_VECTORS).unwrap();
+        let key_pairs = (0..=u8::MAX)
+            .map(|seed| KeyPair::from_seed(vec![seed], Algorithm::Ed25519))
+            .collect::<Vec<_>>();
+        let topology = Topology::new(key_pairs[..vectors.topology].iter().enumerate().map(
+            |(i, key_pair)| {
+                let port = u16::try_from(i).unwrap();
+                PeerId::new(([0, 0, 0, 0], port).into(), key_pair.public_key().clone())
+            },
+        ));
+
+        for case in vectors.cases {
+            let mut chain = ProofChain(
+                case.chain
+                    .iter()
+                    .map(|proof| {
+                        proof
+                            .build(&key_pairs)
+                            .into_verified(&topology)
+                            .expect("Proofs held by the peer are valid")
+                    })
+                    .collect(),
+            );
+            let result = match (case.insert_proof, case.merge) {
+                (Some(proof), None) => {
+                    chain.insert_proof(proof.build(&key_pairs), &topology, latest_block())
+                }
+                (None, Some(other)) => chain.merge(
+                    SignedProofChain(other.iter().map(|proof| proof.build(&key_pairs)).collect()),
+                    &topology,
+                    latest_block(),
+                ),
+                _ => panic!("{}: expected either `insert_proof` or `merge`", case.name),
+            };
+
+            let result = result.map_or_else(|error| format!("{error:?}"), |()| "Ok".to_owned());
+            assert_eq!(result, case.result, "{}", case.name);
+            assert_eq!(
+                chain.verify_with_state(&topology, latest_block()),
+                case.verified,
+                "{}",
+                case.name
+            );
+            assert_eq!(chain.signature_counts(), case.signatures, "{}", case.name);
         }
     }
 }
@@ -8,23 +8,28 @@
 //! This is also where the actual execution of instructions, as well
 //! as various forms of validation are performed.
 
+use std::sync::Arc;
+
 use eyre::Result;
-use iroha_crypto::SignatureOf;
+use iroha_crypto::Signature;
 pub use iroha_data_model::prelude::*;
 use iroha_data_model::{
-    isi::error::Mismatch,
+    isi::{error::Mismatch, InstructionType},
     query::error::FindError,
-    transaction::{error::TransactionLimitError, TransactionPayload},
+    transaction::error::TransactionLimitError,
 };
 use iroha_logger::{debug, error};
 use iroha_macro::FromVariant;
+use parity_scale_codec::Encode as _;
 use storage::storage::StorageReadOnly;
 
 use crate::{
-    smartcontracts::wasm,
-    state::{StateBlock, StateTransaction},
+    smartcontracts::{triggers::set::SetReadOnly, wasm},
+    state::{State, StateBlock, StateReadOnly, StateTransaction},
 };
 
+pub mod cache;
+
 /// `AcceptedTransaction` — a transaction accepted by Iroha peer.
 #[derive(Debug, Clone, PartialEq, Eq)]
 // FIX: Inner field should be private to maintain invariants
@@ -34,7 +39,7 @@ pub struct AcceptedTransaction(pub(crate) SignedTransaction);
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignatureVerificationFail {
     /// Signature which verification has failed
-    pub signature: SignatureOf<TransactionPayload>,
+    pub signature: Signature,
     /// Error which happened during verification
     pub reason: String,
 }
@@ -106,10 +111,30 @@ impl AcceptedTransaction {
             }));
         }
 
-        if *iroha_genesis::GENESIS_DOMAIN_ID == *tx.authority().domain() {
+        if *iroha_genesis::GENESIS_DOMAIN_ID == *tx.authority().domain()
+            || *iroha_genesis::GENESIS_DOMAIN_ID == *tx.signer().domain()
+        {
             return Err(AcceptTransactionFail::UnexpectedGenesisAccountSignature);
         }
 
+        let metadata_size_limit = limits
+            .max_metadata_bytes
+            .get()
+            .try_into()
+            .expect("INTERNAL BUG: max metadata size exceeds usize::MAX");
+        // The memo is client data just like the metadata
+        let metadata_size = tx.metadata().encoded_size() + tx.memo().map_or(0, str::len);
+        if metadata_size > metadata_size_limit {
+            return Err(AcceptTransactionFail::TransactionLimit(
+                TransactionLimitError {
+                    reason: format!(
+                        "Transaction metadata is too large: max {}, got {metadata_size}",
+                        limits.max_metadata_bytes
+                    ),
+                },
+            ));
+        }
+
         match &tx.instructions() {
             Executable::Instructions(instructions) => {
                 let instruction_limit = limits
@@ -158,6 +183,29 @@ impl AcceptedTransaction {
 
         Ok(Self(tx))
     }
+
+    /// Check that transactions of this version can still be included in the block at `height`
+    ///
+    /// # Errors
+    /// If the version is deprecated at `height`
+    pub fn check_version(
+        &self,
+        limits: TransactionParameters,
+        height: u64,
+    ) -> Result<(), TransactionLimitError> {
+        let version = self.0.transaction_version();
+        if limits.accepts_version(version, height) {
+            return Ok(());
+        }
+
+        Err(TransactionLimitError {
+            reason: format!(
+                "Transactions of version {version} are not accepted starting from block {} \
+                (configured by \"TransactionParameter::V1DeprecationHeight\")",
+                limits.v1_deprecation_height
+            ),
+        })
+    }
 }
 
 impl From<AcceptedTransaction> for SignedTransaction {
@@ -207,13 +255,65 @@ impl TransactionExecutor {
         tx: AcceptedTransaction,
         state_block: &mut StateBlock<'_>,
     ) -> Result<SignedTransaction, (SignedTransaction, TransactionRejectionReason)> {
-        let mut state_transaction = state_block.transaction();
-        if let Err(rejection_reason) = self.validate_internal(tx.clone(), &mut state_transaction) {
+        let tx_hash = tx.as_ref().hash();
+        let validation_state = state_block.validation_state();
+        if let Some(rejection_reason) = state_block.cached_rejection(tx_hash, validation_state) {
+            debug!(tx=%tx_hash, "Transaction was already rejected on this state");
             return Err((tx.0, rejection_reason));
         }
-        state_transaction.apply();
 
-        Ok(tx.0)
+        let result = {
+            let mut state_transaction = state_block.transaction();
+            let result = self.validate_internal(tx.clone(), &mut state_transaction);
+            if result.is_ok() {
+                state_transaction.apply();
+            }
+            result
+        };
+
+        match result {
+            Ok(()) => {
+                state_block.transaction_validated(tx_hash, validation_state);
+                Ok(tx.0)
+            }
+            Err(rejection_reason) => {
+                state_block.cache_rejection(tx_hash, validation_state, rejection_reason.clone());
+                Err((tx.0, rejection_reason))
+            }
+        }
+    }
+
+    /// Execute the transaction on top of the latest committed state and discard the changes.
+    ///
+    /// Signatures and limits are not checked, so that the outcome can be
+    /// predicted before the transaction is signed. If `trace` is set, every
+    /// executed instruction is recorded together with its events and error.
+    pub fn simulate(
+        &self,
+        tx: SignedTransaction,
+        state: &State,
+        trace: bool,
+    ) -> TransactionSimulation {
+        let mut state_block = state.block();
+        let mut state_transaction = state_block.transaction();
+        state_transaction.trace = trace.then(Vec::new);
+        let rejection = self
+            .validate_internal(AcceptedTransaction(tx), &mut state_transaction)
+            .err();
+
+        TransactionSimulation {
+            fuel: state_transaction.fuel_consumed,
+            events: state_transaction.world.transaction_events().to_vec(),
+            triggers: state_transaction
+                .world
+                .triggers
+                .matched_ids()
+                .iter()
+                .map(|(_, id)| id.clone())
+                .collect(),
+            rejection,
+            trace: state_transaction.trace.take(),
+        }
     }
 
     fn validate_internal(
@@ -223,23 +323,149 @@ impl TransactionExecutor {
     ) -> Result<(), TransactionRejectionReason> {
         let authority = tx.as_ref().authority();
 
-        if state_transaction.world.accounts.get(authority).is_none() {
+        let Some(account) = state_transaction.world.accounts.get(authority) else {
             return Err(TransactionRejectionReason::AccountDoesNotExist(
                 FindError::Account(authority.clone()),
             ));
+        };
+        if account.is_frozen() {
+            return Err(TransactionRejectionReason::AccountFrozen(authority.clone()));
+        }
+        if let Some(submitter) = tx.as_ref().submitter() {
+            let token = Self::check_proxy(tx.as_ref(), submitter, state_transaction)?;
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::ProxyUsed(token)));
+        }
+        Self::check_app(tx.as_ref(), state_transaction)?;
+
+        let height = state_transaction.height() as u64 + 1;
+        tx.check_version(self.limits, height)
+            .map_err(TransactionRejectionReason::LimitCheck)?;
+
+        let tx_hash = tx.as_ref().hash();
+        let idempotency_key = tx
+            .as_ref()
+            .idempotency_key()
+            .map(|key| (authority.clone(), key.clone()));
+        if let Some(idempotency_key) = &idempotency_key {
+            Self::check_idempotency_key(idempotency_key, state_transaction)?;
         }
 
-        debug!(tx=%tx.as_ref().hash(), "Validating transaction");
+        debug!(tx=%tx_hash, "Validating transaction");
         Self::validate_with_runtime_executor(tx.clone(), state_transaction)?;
 
         if let (authority, Executable::Wasm(bytes)) = tx.into() {
             self.validate_wasm(authority, state_transaction, bytes)?
         }
 
+        if let Some(idempotency_key) = idempotency_key {
+            let record = IdempotencyRecord {
+                transaction: tx_hash,
+                expires_at_ms: state_transaction
+                    .now_ms()
+                    .saturating_add(self.limits.idempotency_window_ms.get()),
+            };
+            state_transaction
+                .idempotency_keys
+                .insert(idempotency_key, record);
+        }
+
         debug!("Validation successful");
         Ok(())
     }
 
+    /// Check that the app named in the metadata is registered with the key
+    /// the transaction is signed with
+    fn check_app(
+        tx: &SignedTransaction,
+        state_transaction: &StateTransaction<'_, '_>,
+    ) -> Result<(), TransactionRejectionReason> {
+        let Some(app_id) = tx.app_id() else {
+            return Ok(());
+        };
+        let app_id = app_id.map_err(TransactionRejectionReason::InvalidAppId)?;
+        let Some(app) = state_transaction.world.apps.get(&app_id) else {
+            return Err(TransactionRejectionReason::UnknownApp(app_id));
+        };
+        if app.public_key() != tx.signer().signatory() {
+            return Err(TransactionRejectionReason::AppKeyMismatch(app_id));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the authority granted the submitter a valid proxy token
+    /// covering every instruction of the transaction
+    fn check_proxy(
+        tx: &SignedTransaction,
+        submitter: &AccountId,
+        state_transaction: &StateTransaction<'_, '_>,
+    ) -> Result<ProxyToken, TransactionRejectionReason> {
+        let authority = tx.authority();
+        let Some(token) = state_transaction
+            .world
+            .proxies
+            .get(&(authority.clone(), submitter.clone()))
+        else {
+            return Err(TransactionRejectionReason::ProxyNotAuthorized(format!(
+                "`{authority}` didn't grant a proxy token to `{submitter}`"
+            )));
+        };
+        if !token.is_valid_at(state_transaction.now_ms()) {
+            return Err(TransactionRejectionReason::ProxyNotAuthorized(
+                String::from("Proxy token has expired"),
+            ));
+        }
+        if state_transaction
+            .world
+            .accounts
+            .get(submitter)
+            .map_or(true, Account::is_frozen)
+        {
+            return Err(TransactionRejectionReason::ProxyNotAuthorized(format!(
+                "Submitter `{submitter}` doesn't exist or is frozen"
+            )));
+        }
+        let Executable::Instructions(instructions) = tx.instructions() else {
+            return Err(TransactionRejectionReason::ProxyNotAuthorized(
+                String::from("Smart contracts can't be submitted by a proxy"),
+            ));
+        };
+        if let Some(instruction) = instructions
+            .iter()
+            .map(InstructionType::from)
+            .find(|instruction| !token.covers(*instruction))
+        {
+            return Err(TransactionRejectionReason::ProxyNotAuthorized(format!(
+                "Proxy token doesn't cover `{instruction}` instructions"
+            )));
+        }
+
+        Ok(token.clone())
+    }
+
+    /// Check that the idempotency key isn't used by a recently committed transaction
+    fn check_idempotency_key(
+        idempotency_key: &(AccountId, Name),
+        state_transaction: &StateTransaction<'_, '_>,
+    ) -> Result<(), TransactionRejectionReason> {
+        let now_ms = state_transaction.now_ms();
+        let is_used = state_transaction
+            .idempotency_keys
+            .get(idempotency_key)
+            .is_some_and(|record| record.expires_at_ms > now_ms);
+
+        if is_used {
+            let (_, key) = idempotency_key;
+            return Err(TransactionRejectionReason::DuplicateIdempotencyKey(
+                key.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
     fn validate_wasm(
         &self,
         authority: AccountId,
@@ -249,6 +475,8 @@ impl TransactionExecutor {
         debug!("Validating wasm");
 
         wasm::RuntimeBuilder::<wasm::state::SmartContract>::new()
+            .with_engine(state_transaction.engine.clone()) // Cloning engine is cheap
+            .with_module_cache(Arc::clone(state_transaction.module_cache))
             .build()
             .and_then(|mut wasm_runtime| {
                 wasm_runtime.validate(
@@ -0,0 +1,264 @@
+//! This module contains [`ValidationCache`], a cache of transaction rejections.
+//!
+//! A peer validates the same transactions again whenever a block at the same height is
+//! built or validated more than once, e.g. when it becomes the leader after a view change
+//! and creates a block from the transactions of the block it has already validated.
+//! The outcome of a transaction depends only on the state it is executed on, so rejections
+//! are cached together with that state: the latest block and the transactions applied
+//! before in the block being built. The executor may read any part of the state, so
+//! any change of it invalidates the entry.
+//!
+//! Accepted transactions aren't cached, since they have to be executed anyway to apply
+//! their changes to the block.
+
+use indexmap::IndexMap;
+use iroha_crypto::{Hash, HashOf};
+use iroha_data_model::{
+    block::SignedBlock,
+    transaction::{error::TransactionRejectionReason, SignedTransaction},
+};
+use parity_scale_codec::Encode;
+
+/// State a transaction is validated on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidationState {
+    /// Height of the latest block
+    height: usize,
+    /// Hash of the latest block, differs at the same height after a soft fork
+    latest_block: Option<HashOf<SignedBlock>>,
+    /// Digest of the transactions applied in the block so far, `None` if there are none
+    applied: Option<Hash>,
+}
+
+impl ValidationState {
+    /// State at the beginning of the block built on top of `latest_block` at `height`
+    pub fn new(height: usize, latest_block: Option<HashOf<SignedBlock>>) -> Self {
+        Self {
+            height,
+            latest_block,
+            applied: None,
+        }
+    }
+
+    /// Whether both states are of blocks built on top of the same latest block
+    pub fn is_same_block(&self, other: &Self) -> bool {
+        self.height == other.height && self.latest_block == other.latest_block
+    }
+
+    /// State after the transaction with `hash` is applied on top of this one
+    #[must_use]
+    pub fn applied(self, hash: HashOf<SignedTransaction>) -> Self {
+        Self {
+            applied: Some(Hash::new((self.applied, hash).encode())),
+            ..self
+        }
+    }
+}
+
+/// Least recently used cache of transaction rejections keyed by
+/// the hash of the transaction and the state it was rejected on
+#[derive(Debug, Default)]
+pub struct ValidationCache {
+    /// Maximum number of cached rejections, caching is disabled if `0`
+    capacity: usize,
+    /// Rejections ordered from the least to the most recently used
+    rejections: IndexMap<(HashOf<SignedTransaction>, ValidationState), TransactionRejectionReason>,
+    stats: ValidationCacheStats,
+}
+
+/// Counters of the [`ValidationCache`] usage since the peer start
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationCacheStats {
+    /// Number of transactions which weren't executed again
+    pub hits: u64,
+    /// Number of transactions which had to be executed
+    pub misses: u64,
+    /// Number of rejections in the cache
+    pub size: usize,
+}
+
+impl ValidationCache {
+    /// Create cache holding at most `capacity` rejections
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Change the maximum number of cached rejections, evicting the least recently used ones
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// Get the reason the transaction with `hash` was rejected for on `state`,
+    /// `None` if it wasn't validated on this state yet.
+    pub fn get(
+        &mut self,
+        hash: HashOf<SignedTransaction>,
+        state: ValidationState,
+    ) -> Option<TransactionRejectionReason> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let Some(index) = self.rejections.get_index_of(&(hash, state)) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        let last = self.rejections.len() - 1;
+        self.rejections.move_index(index, last);
+        self.stats.hits += 1;
+        self.rejections
+            .get_index(last)
+            .map(|(_, reason)| reason.clone())
+    }
+
+    /// Remember that the transaction with `hash` is rejected on `state` for `reason`.
+    ///
+    /// Rejections made on top of the blocks below `state` can't be looked up anymore,
+    /// so they are dropped.
+    pub fn insert(
+        &mut self,
+        hash: HashOf<SignedTransaction>,
+        state: ValidationState,
+        reason: TransactionRejectionReason,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.rejections
+            .retain(|(_, cached_state), _| cached_state.height >= state.height);
+        self.rejections.insert((hash, state), reason);
+        self.evict();
+    }
+
+    /// Usage counters of the cache
+    pub fn stats(&self) -> ValidationCacheStats {
+        ValidationCacheStats {
+            size: self.rejections.len(),
+            ..self.stats
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.rejections.len() > self.capacity {
+            self.rejections.shift_remove_index(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::account::AccountId;
+
+    use super::*;
+
+    fn tx_hash(byte: u8) -> HashOf<SignedTransaction> {
+        HashOf::from_untyped_unchecked(Hash::prehashed([byte; Hash::LENGTH]))
+    }
+
+    fn block_hash(byte: u8) -> Option<HashOf<SignedBlock>> {
+        Some(HashOf::from_untyped_unchecked(Hash::prehashed(
+            [byte; Hash::LENGTH],
+        )))
+    }
+
+    fn reason() -> TransactionRejectionReason {
+        let authority: AccountId =
+            "ed0120CE7FA46C9DCE7EA4B125E2E36BDB63EA33073E7590AC92816AE1E861B7048B03@wonderland"
+                .parse()
+                .unwrap();
+        TransactionRejectionReason::AccountFrozen(authority)
+    }
+
+    #[test]
+    fn rejection_is_reused_on_the_same_state() {
+        let mut cache = ValidationCache::new(8);
+        let state = ValidationState::new(3, block_hash(3)).applied(tx_hash(1));
+        cache.insert(tx_hash(2), state, reason());
+
+        assert_eq!(cache.get(tx_hash(2), state), Some(reason()));
+        assert_eq!(
+            cache.get(
+                tx_hash(2),
+                ValidationState::new(3, block_hash(3)).applied(tx_hash(1))
+            ),
+            Some(reason())
+        );
+        assert_eq!(cache.stats().hits, 2);
+    }
+
+    #[test]
+    fn rejection_is_not_reused_on_changed_state() {
+        let mut cache = ValidationCache::new(8);
+        let state = ValidationState::new(3, block_hash(3));
+        cache.insert(tx_hash(2), state, reason());
+
+        // Another transaction is applied before in the block
+        assert_eq!(cache.get(tx_hash(2), state.applied(tx_hash(1))), None);
+        // The latest block is replaced by a soft fork
+        assert_eq!(
+            cache.get(tx_hash(2), ValidationState::new(3, block_hash(4))),
+            None
+        );
+        // The next block is committed
+        assert_eq!(
+            cache.get(tx_hash(2), ValidationState::new(4, block_hash(4))),
+            None
+        );
+        assert_eq!(cache.stats().misses, 3);
+    }
+
+    #[test]
+    fn applied_transactions_are_ordered() {
+        let state = ValidationState::new(3, block_hash(3));
+        assert_ne!(
+            state.applied(tx_hash(1)).applied(tx_hash(2)),
+            state.applied(tx_hash(2)).applied(tx_hash(1))
+        );
+    }
+
+    #[test]
+    fn rejections_below_the_latest_block_are_dropped() {
+        let mut cache = ValidationCache::new(8);
+        cache.insert(tx_hash(1), ValidationState::new(3, block_hash(3)), reason());
+        cache.insert(tx_hash(2), ValidationState::new(4, block_hash(4)), reason());
+
+        assert_eq!(cache.stats().size, 1);
+        assert_eq!(
+            cache.get(tx_hash(1), ValidationState::new(3, block_hash(3))),
+            None
+        );
+    }
+
+    #[test]
+    fn least_recently_used_rejection_is_evicted() {
+        let mut cache = ValidationCache::new(2);
+        let state = ValidationState::new(3, block_hash(3));
+        cache.insert(tx_hash(1), state, reason());
+        cache.insert(tx_hash(2), state, reason());
+        cache.get(tx_hash(1), state);
+        cache.insert(
+            tx_hash(3),
+            state,
+            TransactionRejectionReason::InvalidAppId("rejected".to_owned()),
+        );
+
+        assert!(cache.get(tx_hash(1), state).is_some());
+        assert!(cache.get(tx_hash(2), state).is_none());
+        assert!(cache.get(tx_hash(3), state).is_some());
+    }
+
+    #[test]
+    fn nothing_is_cached_if_disabled() {
+        let mut cache = ValidationCache::default();
+        let state = ValidationState::new(3, block_hash(3));
+        cache.insert(tx_hash(1), state, reason());
+
+        assert_eq!(cache.get(tx_hash(1), state), None);
+        assert_eq!(cache.stats(), ValidationCacheStats::default());
+    }
+}
@@ -15,7 +15,6 @@ use iroha_crypto::{ExposedPrivateKey, KeyPair};
 use iroha_data_model::{asset::AssetDefinitionId, isi::InstructionBox, ChainId};
 use iroha_executor_data_model::permission::{
     asset::{CanBurnAssetWithDefinition, CanMintAssetWithDefinition},
-    domain::CanUnregisterDomain,
     executor::CanUpgradeExecutor,
     peer::CanUnregisterAnyPeer,
     role::CanUnregisterAnyRole,
@@ -114,12 +113,6 @@ impl TestGenesis for GenesisBlock {
             Grant::account_permission(CanUnregisterAnyPeer, ALICE_ID.clone());
         let grant_unregister_any_role_permission =
             Grant::account_permission(CanUnregisterAnyRole, ALICE_ID.clone());
-        let grant_unregister_wonderland_domain = Grant::account_permission(
-            CanUnregisterDomain {
-                domain: "wonderland".parse().unwrap(),
-            },
-            ALICE_ID.clone(),
-        );
         let grant_upgrade_executor_permission =
             Grant::account_permission(CanUpgradeExecutor, ALICE_ID.clone());
         for isi in [
@@ -127,7 +120,6 @@ impl TestGenesis for GenesisBlock {
             grant_burn_rose_permission,
             grant_unregister_any_peer_permission,
             grant_unregister_any_role_permission,
-            grant_unregister_wonderland_domain,
             grant_upgrade_executor_permission,
         ] {
             genesis.append_instruction(isi);
@@ -4,19 +4,146 @@ use core::{hash, marker::PhantomData, num::NonZeroU8, str::FromStr};
 
 #[cfg(not(feature = "ffi_import"))]
 use blake2::{
-    digest::{Update, VariableOutput},
-    Blake2bVar,
+    digest::{FixedOutput, Update, VariableOutput},
+    Blake2bVar, Blake2s256,
 };
 use derive_more::{DebugCustom, Deref, DerefMut, Display};
 use iroha_schema::{IntoSchema, TypeId};
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
-use serde_with::DeserializeFromStr;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+use crate::{error::NoSuchAlgorithm, hex_decode, ParseError};
+
+/// String representation of [`HashAlgorithm::Blake2b256`]
+pub const BLAKE2B_256: &str = "blake2b_256";
+/// String representation of [`HashAlgorithm::Blake2s256`]
+pub const BLAKE2S_256: &str = "blake2s_256";
+/// String representation of [`HashAlgorithm::Sha256`]
+pub const SHA_256: &str = "sha256";
+
+/// Algorithm computing a [`Hash`].
+///
+/// Hashes which are part of the protocol (of blocks, transactions, etc.) are always computed
+/// with the default [`HashAlgorithm::Blake2b256`], see [`Hash::new`]. The other algorithms are
+/// only meant for data which doesn't leave the peer, such as checksums of the stored data.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+pub enum HashAlgorithm {
+    /// BLAKE2b with 256-bit output
+    #[default]
+    Blake2b256,
+    /// BLAKE2s with 256-bit output, faster than BLAKE2b on platforms without 64-bit arithmetic
+    Blake2s256,
+    /// SHA-256, hardware accelerated on many platforms
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Maps the algorithm to its static string representation
+    pub const fn as_static_str(self) -> &'static str {
+        match self {
+            Self::Blake2b256 => BLAKE2B_256,
+            Self::Blake2s256 => BLAKE2S_256,
+            Self::Sha256 => SHA_256,
+        }
+    }
 
-use crate::{hex_decode, ParseError};
+    /// Start computing a hash incrementally
+    #[cfg(not(feature = "ffi_import"))]
+    pub fn hasher(self) -> Hasher {
+        match self {
+            Self::Blake2b256 => Hasher::Blake2b(
+                Blake2bVar::new(Hash::LENGTH).expect("Failed to initialize variable size hash"),
+            ),
+            Self::Blake2s256 => Hasher::Blake2s(Blake2s256::default()),
+            Self::Sha256 => Hasher::Sha256(sha2::Sha256::default()),
+        }
+    }
+}
+
+impl core::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_static_str())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = NoSuchAlgorithm;
+
+    fn from_str(algorithm: &str) -> Result<Self, Self::Err> {
+        match algorithm {
+            BLAKE2B_256 => Ok(Self::Blake2b256),
+            BLAKE2S_256 => Ok(Self::Blake2s256),
+            SHA_256 => Ok(Self::Sha256),
+            _ => Err(NoSuchAlgorithm),
+        }
+    }
+}
+
+/// Incremental computation of a [`Hash`], see [`HashAlgorithm::hasher`]
+#[cfg(not(feature = "ffi_import"))]
+#[derive(Clone)]
+pub enum Hasher {
+    /// [`HashAlgorithm::Blake2b256`]
+    Blake2b(Blake2bVar),
+    /// [`HashAlgorithm::Blake2s256`]
+    Blake2s(Blake2s256),
+    /// [`HashAlgorithm::Sha256`]
+    Sha256(sha2::Sha256),
+}
 
-/// Hash of Iroha entities. Currently supports only blake2b-32.
-/// The least significant bit of hash is set to 1.
+#[cfg(not(feature = "ffi_import"))]
+impl core::fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let algorithm = match self {
+            Self::Blake2b(_) => HashAlgorithm::Blake2b256,
+            Self::Blake2s(_) => HashAlgorithm::Blake2s256,
+            Self::Sha256(_) => HashAlgorithm::Sha256,
+        };
+        f.debug_tuple("Hasher").field(&algorithm).finish()
+    }
+}
+
+#[cfg(not(feature = "ffi_import"))]
+impl Hasher {
+    /// Feed more `bytes` into the hash
+    pub fn update(&mut self, bytes: impl AsRef<[u8]>) {
+        let bytes = bytes.as_ref();
+        match self {
+            Self::Blake2b(hasher) => hasher.update(bytes),
+            Self::Blake2s(hasher) => hasher.update(bytes),
+            Self::Sha256(hasher) => hasher.update(bytes),
+        }
+    }
+
+    /// Hash of all the bytes fed so far
+    pub fn finalize(self) -> Hash {
+        let mut hash = [0; Hash::LENGTH];
+        match self {
+            Self::Blake2b(hasher) => hasher
+                .finalize_variable(&mut hash)
+                .expect("Output has the configured size"),
+            Self::Blake2s(hasher) => hash.copy_from_slice(&hasher.finalize_fixed()),
+            Self::Sha256(hasher) => hash.copy_from_slice(&hasher.finalize_fixed()),
+        }
+        Hash::prehashed(hash)
+    }
+}
+
+/// Hash of Iroha entities, computed with blake2b-32 unless [another algorithm](HashAlgorithm) is
+/// explicitly chosen. The least significant bit of hash is set to 1.
 #[derive(
     DebugCustom,
     Display,
@@ -44,7 +171,7 @@ impl Hash {
     /// Length of hash
     pub const LENGTH: usize = 32;
 
-    /// Wrap the given bytes; they must be prehashed with `Blake2bVar`
+    /// Wrap the given bytes; they must be prehashed with one of [`HashAlgorithm`]s
     pub fn prehashed(mut hash: [u8; Self::LENGTH]) -> Self {
         hash[Self::LENGTH - 1] |= 1;
         // SAFETY:
@@ -63,7 +190,7 @@ impl Hash {
 }
 
 impl Hash {
-    /// Hash the given bytes.
+    /// Hash the given bytes with the default [`HashAlgorithm`].
     #[must_use]
     pub fn new(bytes: impl AsRef<[u8]>) -> Self {
         let vec_hash = Blake2bVar::new(Self::LENGTH)
@@ -76,6 +203,14 @@ impl Hash {
 
         Hash::prehashed(hash)
     }
+
+    /// Hash the given bytes with `algorithm`.
+    #[must_use]
+    pub fn new_with(algorithm: HashAlgorithm, bytes: impl AsRef<[u8]>) -> Self {
+        let mut hasher = algorithm.hasher();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
 }
 
 impl From<Hash> for [u8; Hash::LENGTH] {
@@ -326,4 +461,39 @@ mod tests {
                 [..]
         );
     }
+
+    #[test]
+    fn default_algorithm_is_used_by_default() {
+        let bytes = b"i am data";
+        assert_eq!(
+            Hash::new_with(HashAlgorithm::default(), bytes),
+            Hash::new(bytes)
+        );
+        assert_ne!(
+            Hash::new_with(HashAlgorithm::Blake2s256, bytes),
+            Hash::new(bytes)
+        );
+        assert_ne!(
+            Hash::new_with(HashAlgorithm::Sha256, bytes),
+            Hash::new_with(HashAlgorithm::Blake2s256, bytes)
+        );
+    }
+
+    #[test]
+    fn hasher_is_incremental() {
+        for algorithm in [
+            HashAlgorithm::Blake2b256,
+            HashAlgorithm::Blake2s256,
+            HashAlgorithm::Sha256,
+        ] {
+            let mut hasher = algorithm.hasher();
+            hasher.update(b"i am ");
+            hasher.update(b"data");
+            assert_eq!(hasher.finalize(), Hash::new_with(algorithm, b"i am data"));
+            assert_eq!(
+                algorithm.to_string().parse::<HashAlgorithm>().unwrap(),
+                algorithm
+            );
+        }
+    }
 }
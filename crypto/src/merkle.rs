@@ -8,7 +8,7 @@ use iroha_schema::{IntoSchema, TypeId};
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
-use crate::{Hash, HashOf};
+use crate::{Hash, HashAlgorithm, HashOf};
 
 /// [Merkle Tree](https://en.wikipedia.org/wiki/Merkle_tree) used to validate `T`
 #[derive(Debug, TypeId, Decode, Encode, Deserialize, Serialize)]
@@ -151,6 +151,32 @@ impl<T> MerkleTree<T> {
         self.get(0).and_then(|node| node.map(HashOf::transmute))
     }
 
+    /// Get the hash of [`MerkleTree`] computed with `algorithm` instead of the default one.
+    ///
+    /// Nodes of the tree are always hashed with the default algorithm since the tree hash
+    /// is a part of the protocol, so the root is recomputed from the leaves.
+    pub fn hash_with(&self, algorithm: HashAlgorithm) -> Option<HashOf<Self>> {
+        let first_leaf = 2_usize.pow(self.height()).saturating_sub(1);
+        let mut layer = self
+            .0
+            .iter()
+            .skip(first_leaf)
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [l_hash, r_hash] => Self::pair_hash(algorithm, l_hash, r_hash),
+                    [l_hash] => *l_hash,
+                    _ => unreachable!("Chunks have 1 or 2 elements"),
+                })
+                .collect();
+        }
+        layer.first().copied().map(HashOf::transmute)
+    }
+
     /// Get the `idx`-th leaf hash.
     pub fn get_leaf_hash(&self, idx: usize) -> Option<HashOf<T>> {
         if let Some(node) = self.get_leaf(idx) {
@@ -215,13 +241,17 @@ impl<T> MerkleTree<T> {
             (None, Some(_)) => unreachable!(),
             (None, None) => return None,
         };
+        Some(Self::pair_hash(HashAlgorithm::default(), l_hash, r_hash))
+    }
+
+    fn pair_hash(algorithm: HashAlgorithm, l_hash: &HashOf<T>, r_hash: &HashOf<T>) -> HashOf<T> {
         let sum: Vec<_> = l_hash
             .as_ref()
             .iter()
             .zip(r_hash.as_ref().iter())
             .map(|(l, r)| l.wrapping_add(*r))
             .collect();
-        Some(HashOf::from_untyped_unchecked(Hash::new(sum)))
+        HashOf::from_untyped_unchecked(Hash::new_with(algorithm, sum))
     }
 }
 
@@ -312,4 +342,16 @@ mod tests {
             assert_eq!(testee_leaf, tester_leaf);
         }
     }
+
+    #[test]
+    fn hash_with_algorithm() {
+        for n_leaves in 0..=9 {
+            let tree = test_hashes(n_leaves).into_iter().collect::<MerkleTree<_>>();
+
+            assert_eq!(tree.hash_with(HashAlgorithm::default()), tree.hash());
+            if n_leaves > 1 {
+                assert_ne!(tree.hash_with(HashAlgorithm::Sha256), tree.hash());
+            }
+        }
+    }
 }
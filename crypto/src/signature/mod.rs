@@ -198,6 +198,14 @@ impl<T: IntoSchema> IntoSchema for SignatureOf<T> {
 }
 
 impl<T> SignatureOf<T> {
+    /// Treat `signature` made elsewhere (e.g. by a hardware wallet) as a signature of `T`.
+    ///
+    /// **The signature isn't verified.** Wrap it into [`Unverified`] and verify it with
+    /// [`Unverified::verify`] before relying on it.
+    pub fn from_signature(signature: Signature) -> Self {
+        Self(signature, PhantomData)
+    }
+
     /// Create [`SignatureOf`] from the given hash with [`KeyPair::private_key`].
     ///
     /// # Errors
@@ -212,7 +220,7 @@ impl<T> SignatureOf<T> {
     /// # Errors
     ///
     /// Fails if the given hash didn't pass verification
-    pub fn verify_hash(&self, public_key: &PublicKey, hash: HashOf<T>) -> Result<(), Error> {
+    pub(crate) fn verify_hash(&self, public_key: &PublicKey, hash: HashOf<T>) -> Result<(), Error> {
         self.0.verify(public_key, hash.as_ref())
     }
 }
@@ -228,16 +236,99 @@ impl<T: parity_scale_codec::Encode> SignatureOf<T> {
     pub fn new(private_key: &PrivateKey, value: &T) -> Self {
         Self::from_hash(private_key, HashOf::new(value))
     }
+}
+
+ffi::ffi_item! {
+    /// Signature which wasn't verified yet, e.g. because it was received from another peer.
+    ///
+    /// Decoded values carry their signatures as [`Unverified`]. The signature can't be used
+    /// until it's turned into [`Verified`] with [`Unverified::verify`], so that skipping the
+    /// verification doesn't compile.
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature="ffi_import", derive(TypeId))]
+    #[cfg_attr(not(feature="ffi_import"), derive(Hash, Decode, Encode, Serialize, Deserialize, IntoSchema))]
+    #[cfg_attr(not(feature="ffi_import"), serde(transparent))]
+    #[cfg_attr(not(feature="ffi_import"), schema(transparent))]
+    // Transmute guard
+    #[repr(transparent)]
+    pub struct Unverified<S>(S);
+
+    // SAFETY: `Unverified` has no trap representation in `S`
+    ffi_type(unsafe {robust})
+}
+
+/// Signature which is known to be made by the signatory over the signed value,
+/// either because it was [verified](Unverified::verify) or because it was made locally.
+#[derive(Debug, Clone, PartialEq, Eq, Deref)]
+pub struct Verified<S>(S);
+
+impl<S> Unverified<S> {
+    /// Wrap a signature whose validity isn't known
+    #[inline]
+    pub const fn new(signature: S) -> Self {
+        Self(signature)
+    }
 
-    /// Verifies signature for this item
+    /// Signature as it was received, e.g. to be displayed or forwarded.
+    ///
+    /// Nothing is known about its validity.
+    #[inline]
+    pub const fn as_unverified(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S> From<Verified<S>> for Unverified<S> {
+    fn from(signature: Verified<S>) -> Self {
+        Self(signature.0)
+    }
+}
+
+impl<T> Unverified<SignatureOf<T>> {
+    /// Verify the signature of the `hash` made by `public_key`.
+    ///
+    /// # Errors
+    /// Fails if the given hash didn't pass verification
+    pub fn verify_hash(
+        &self,
+        public_key: &PublicKey,
+        hash: HashOf<T>,
+    ) -> Result<Verified<SignatureOf<T>>, Error> {
+        self.0.verify_hash(public_key, hash)?;
+        Ok(Verified(self.0.clone()))
+    }
+}
+
+impl<T: parity_scale_codec::Encode> Unverified<SignatureOf<T>> {
+    /// Verify the signature of `value` made by `public_key`.
     ///
     /// # Errors
     /// Fails if verification fails
-    pub fn verify(&self, public_key: &PublicKey, value: &T) -> Result<(), Error> {
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        value: &T,
+    ) -> Result<Verified<SignatureOf<T>>, Error> {
         self.verify_hash(public_key, HashOf::new(value))
     }
 }
 
+impl<S> Verified<S> {
+    /// Unwrap the verified signature
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<T: parity_scale_codec::Encode> Verified<SignatureOf<T>> {
+    /// Sign `value` with `private_key`, which makes the signature valid by construction
+    #[inline]
+    pub fn sign(private_key: &PrivateKey, value: &T) -> Self {
+        Self(SignatureOf::new(private_key, value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -245,6 +336,44 @@ mod tests {
     use super::*;
     use crate::{Algorithm, KeyPair};
 
+    #[test]
+    #[cfg(feature = "rand")]
+    fn unverified_signature_is_verified_against_signatory() {
+        let key_pair = KeyPair::random();
+        let impostor = KeyPair::random();
+        let message = 1_u32;
+        let signature = SignatureOf::new(key_pair.private_key(), &message);
+
+        assert!(Unverified::new(signature.clone())
+            .verify(impostor.public_key(), &message)
+            .is_err());
+        assert!(Unverified::new(signature.clone())
+            .verify(key_pair.public_key(), &2)
+            .is_err());
+        let verified = Unverified::new(signature.clone())
+            .verify(key_pair.public_key(), &message)
+            .unwrap();
+        assert_eq!(verified.into_inner(), signature);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn unverified_signature_is_encoded_as_the_signature() {
+        let key_pair = KeyPair::random();
+        let signature = SignatureOf::new(key_pair.private_key(), &1_u32);
+        let unverified = Unverified::new(signature.clone());
+
+        assert_eq!(unverified.encode(), signature.encode());
+        assert_eq!(
+            serde_json::to_value(&unverified).unwrap(),
+            serde_json::to_value(&signature).unwrap()
+        );
+        assert_eq!(
+            Unverified::<SignatureOf<u32>>::decode(&mut signature.encode().as_slice()).unwrap(),
+            unverified
+        );
+    }
+
     #[test]
     #[cfg(feature = "rand")]
     fn create_signature_ed25519() {
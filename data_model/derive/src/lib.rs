@@ -5,6 +5,7 @@ mod has_origin;
 mod id;
 mod model;
 mod partially_tagged;
+mod validated_decode;
 
 use iroha_macro_utils::Emitter;
 use manyhow::{emit, manyhow, Result};
@@ -567,3 +568,58 @@ pub fn event_set_derive(input: TokenStream) -> TokenStream {
 
     emitter.finish_token_stream_with(result)
 }
+
+/// Derive `parity_scale_codec::Decode` and `serde::Deserialize` which reject invalid values.
+///
+/// The type is first decoded field by field and then passed to `fn validate(self) -> Result<Self, E>`,
+/// which the type must provide. The value is only returned if the validation succeeds, otherwise
+/// the error is reported as a decoding error, so `E` has to implement both
+/// `Into<parity_scale_codec::Error>` and `Display`.
+///
+/// Deserialization goes through a private `<Type>Candidate` struct with the same fields,
+/// which carries over the `serde` attributes of the type and its fields.
+///
+/// Supported attributes:
+/// - `#[validated_decode(decode_only)]` on the type: don't derive `Deserialize`
+/// - `#[validated_decode(decode_with = path)]` on a field: decode the field with
+///   `path(input) -> Result<FieldType, parity_scale_codec::Error>` instead of its `Decode` impl
+///
+/// Only structs with named fields and without generics are supported.
+///
+/// # Example
+///
+/// ```
+/// use iroha_data_model_derive::ValidatedDecode;
+/// use parity_scale_codec::{DecodeAll, Encode};
+/// use serde::Serialize;
+///
+/// #[derive(Debug, Encode, Serialize, ValidatedDecode)]
+/// struct Range {
+///     start: u32,
+///     end: u32,
+/// }
+///
+/// impl Range {
+///     fn validate(self) -> Result<Self, &'static str> {
+///         if self.start > self.end {
+///             return Err("Range must not end before it starts");
+///         }
+///
+///         Ok(self)
+///     }
+/// }
+///
+/// let valid = Range { start: 1, end: 2 }.encode();
+/// assert!(Range::decode_all(&mut valid.as_slice()).is_ok());
+///
+/// let invalid = Range { start: 2, end: 1 }.encode();
+/// assert!(Range::decode_all(&mut invalid.as_slice()).is_err());
+/// assert!(serde_json::from_str::<Range>(r#"{"start": 2, "end": 1}"#).is_err());
+/// ```
+#[manyhow]
+#[proc_macro_derive(ValidatedDecode, attributes(validated_decode, serde))]
+pub fn validated_decode_derive(input: TokenStream) -> Result<TokenStream> {
+    let input = syn::parse2(input)?;
+
+    validated_decode::impl_validated_decode(&input)
+}
@@ -0,0 +1,164 @@
+// darling-generated code triggers this lint
+#![allow(clippy::option_if_let_else)]
+
+use darling::{util::Flag, FromDeriveInput, FromField};
+use manyhow::{bail, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, Attribute, Generics, Ident, Path, Type};
+
+#[derive(FromDeriveInput)]
+#[darling(
+    attributes(validated_decode),
+    forward_attrs(serde),
+    supports(struct_named)
+)]
+struct ValidatedDecodeInput {
+    ident: Ident,
+    generics: Generics,
+    data: darling::ast::Data<darling::util::Ignored, ValidatedDecodeField>,
+    attrs: Vec<Attribute>,
+    decode_only: Flag,
+}
+
+#[derive(FromField)]
+#[darling(attributes(validated_decode), forward_attrs(serde, codec))]
+struct ValidatedDecodeField {
+    ident: Option<Ident>,
+    ty: Type,
+    attrs: Vec<Attribute>,
+    decode_with: Option<Path>,
+}
+
+impl ValidatedDecodeInput {
+    fn fields(&self) -> &[ValidatedDecodeField] {
+        match &self.data {
+            darling::ast::Data::Struct(fields) => &fields.fields,
+            darling::ast::Data::Enum(_) => {
+                unreachable!(
+                    "Only structs are supported. Enforced by `darling(supports(struct_named))`"
+                )
+            }
+        }
+    }
+}
+
+impl ValidatedDecodeField {
+    fn ident(&self) -> &Ident {
+        self.ident.as_ref().expect(
+            "BUG: Only named fields are supported. Enforced by `darling(supports(struct_named))`",
+        )
+    }
+
+    fn serde_attrs(&self) -> impl Iterator<Item = &Attribute> {
+        self.attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+    }
+
+    fn codec_attr(&self) -> Option<&Attribute> {
+        self.attrs.iter().find(|attr| attr.path().is_ident("codec"))
+    }
+
+    /// Expression decoding the field from `input`, spanned to the field
+    /// so that type errors and the error context point to it
+    fn decode_expr(&self, struct_ident: &Ident) -> TokenStream {
+        let ty = &self.ty;
+        let context = format!("Could not decode `{struct_ident}::{}`", self.ident());
+        let decode = self.decode_with.as_ref().map_or_else(
+            || quote_spanned!(ty.span()=> <#ty as ::parity_scale_codec::Decode>::decode(input)),
+            |decode_with| quote_spanned!(decode_with.span()=> #decode_with(input)),
+        );
+
+        quote_spanned! {ty.span()=>
+            #decode.map_err(|error| error.chain(#context))?
+        }
+    }
+}
+
+pub fn impl_validated_decode(input: &syn::DeriveInput) -> Result<TokenStream> {
+    let input = ValidatedDecodeInput::from_derive_input(input)?;
+    if !input.generics.params.is_empty() {
+        bail!(input.generics, "Generic types are not supported");
+    }
+    for field in input.fields() {
+        if let Some(attr) = field.codec_attr() {
+            bail!(
+                attr,
+                "`codec` attributes are ignored when decoding, use `#[validated_decode(decode_with = ...)]` instead"
+            );
+        }
+    }
+
+    let decode = impl_decode(&input);
+    let deserialize = (!input.decode_only.is_present()).then(|| impl_deserialize(&input));
+
+    Ok(quote! {
+        const _: () = {
+            #decode
+            #deserialize
+        };
+    })
+}
+
+fn impl_decode(input: &ValidatedDecodeInput) -> TokenStream {
+    let ident = &input.ident;
+    let field_idents = input.fields().iter().map(ValidatedDecodeField::ident);
+    let field_decodes = input.fields().iter().map(|field| field.decode_expr(ident));
+
+    quote! {
+        impl ::parity_scale_codec::Decode for #ident {
+            fn decode<I: ::parity_scale_codec::Input>(
+                input: &mut I,
+            ) -> ::core::result::Result<Self, ::parity_scale_codec::Error> {
+                let candidate = Self {
+                    #(#field_idents: #field_decodes,)*
+                };
+
+                Self::validate(candidate).map_err(::core::convert::Into::into)
+            }
+        }
+    }
+}
+
+fn impl_deserialize(input: &ValidatedDecodeInput) -> TokenStream {
+    let ident = &input.ident;
+    let candidate_ident = format_ident!("{}Candidate", ident);
+    let container_attrs = &input.attrs;
+    let field_idents = input
+        .fields()
+        .iter()
+        .map(ValidatedDecodeField::ident)
+        .collect::<Vec<_>>();
+    let fields = input.fields().iter().map(|field| {
+        let field_ident = field.ident();
+        let ty = &field.ty;
+        let attrs = field.serde_attrs();
+
+        quote_spanned! {field.ty.span()=>
+            #(#attrs)*
+            #field_ident: #ty
+        }
+    });
+
+    quote! {
+        #[derive(::serde::Deserialize)]
+        #(#container_attrs)*
+        struct #candidate_ident {
+            #(#fields,)*
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #ident {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let #candidate_ident { #(#field_idents),* } =
+                    <#candidate_ident as ::serde::Deserialize>::deserialize(deserializer)?;
+                let candidate = Self { #(#field_idents),* };
+
+                Self::validate(candidate).map_err(<D::Error as ::serde::de::Error>::custom)
+            }
+        }
+    }
+}
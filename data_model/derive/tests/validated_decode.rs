@@ -0,0 +1,105 @@
+//! Basic tests for traits derived by [`ValidatedDecode`] macro
+
+use iroha_data_model_derive::ValidatedDecode;
+use parity_scale_codec::{Compact, Decode, DecodeAll, Encode, Input};
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Eq, Encode, Serialize, ValidatedDecode)]
+#[serde(rename_all = "camelCase")]
+struct Range {
+    range_start: u32,
+    #[serde(default = "unbounded")]
+    range_end: u32,
+}
+
+fn unbounded() -> u32 {
+    u32::MAX
+}
+
+impl Range {
+    fn validate(self) -> Result<Self, &'static str> {
+        if self.range_start > self.range_end {
+            return Err("Range must not end before it starts");
+        }
+
+        Ok(self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Encode, ValidatedDecode)]
+#[validated_decode(decode_only)]
+struct Short {
+    #[validated_decode(decode_with = decode_short_vec)]
+    items: Vec<u8>,
+}
+
+fn decode_short_vec<I: Input>(input: &mut I) -> Result<Vec<u8>, parity_scale_codec::Error> {
+    let len = Compact::<u32>::decode(input)?.0;
+    if len > 2 {
+        return Err("Too many items".into());
+    }
+
+    (0..len).map(|_| u8::decode(input)).collect()
+}
+
+impl Short {
+    fn validate(self) -> Result<Self, &'static str> {
+        if self.items.is_empty() {
+            return Err("No items");
+        }
+
+        Ok(self)
+    }
+}
+
+#[test]
+fn valid_value_is_decoded() {
+    let range = Range {
+        range_start: 1,
+        range_end: 2,
+    };
+
+    let encoded = range.encode();
+    assert_eq!(Range::decode_all(&mut encoded.as_slice()).unwrap(), range);
+
+    let json = serde_json::to_string(&range).unwrap();
+    assert_eq!(serde_json::from_str::<Range>(&json).unwrap(), range);
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+    let range = Range {
+        range_start: 2,
+        range_end: 1,
+    };
+
+    let encoded = range.encode();
+    assert!(Range::decode_all(&mut encoded.as_slice()).is_err());
+
+    let json = serde_json::to_string(&range).unwrap();
+    let error = serde_json::from_str::<Range>(&json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Range must not end before it starts"));
+}
+
+#[test]
+fn serde_attributes_are_respected() {
+    let range = serde_json::from_str::<Range>(r#"{"rangeStart": 1}"#).unwrap();
+    assert_eq!(range.range_end, u32::MAX);
+}
+
+#[test]
+fn fields_are_decoded_with_custom_functions() {
+    let valid = Short { items: vec![1, 2] }.encode();
+    assert!(Short::decode_all(&mut valid.as_slice()).is_ok());
+
+    let too_long = Short {
+        items: vec![1, 2, 3],
+    }
+    .encode();
+    assert!(Short::decode_all(&mut too_long.as_slice()).is_err());
+
+    let invalid = Short { items: vec![] }.encode();
+    assert!(Short::decode_all(&mut invalid.as_slice()).is_err());
+}
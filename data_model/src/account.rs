@@ -75,6 +75,8 @@ mod model {
         pub id: AccountId,
         /// Metadata of this account as a key-value store.
         pub metadata: Metadata,
+        /// Frozen account can't author transactions, see [`Freeze`](crate::isi::Freeze).
+        pub frozen: bool,
     }
 
     /// Builder which should be submitted in a transaction to create a new [`Account`]
@@ -115,6 +117,12 @@ impl Account {
     pub fn signatory(&self) -> &PublicKey {
         &self.id.signatory
     }
+
+    /// Return `true` if transactions authored by the `Account` are rejected.
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
 }
 
 impl NewAccount {
@@ -141,6 +149,7 @@ impl NewAccount {
         Account {
             id: self.id,
             metadata: self.metadata,
+            frozen: false,
         }
     }
 }
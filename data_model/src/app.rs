@@ -0,0 +1,105 @@
+//! Structures, traits and impls related to client `App`s.
+//!
+//! A client application registers an id bound to its key. Transactions name the application
+//! which submitted them in the [`APP_ID_METADATA_KEY`] entry of their metadata, and the
+//! attribution is accepted only if the transaction is signed with the key of the application.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+use iroha_crypto::PublicKey;
+use iroha_data_model_derive::model;
+
+pub use self::model::*;
+use crate::{account::AccountId, metadata::Metadata, Identifiable, Name};
+
+/// Key of the transaction metadata entry naming the [`App`] which submitted the transaction.
+pub const APP_ID_METADATA_KEY: &str = "app_id";
+
+#[model]
+mod model {
+    use derive_more::{Constructor, Display, FromStr};
+    use getset::Getters;
+    use iroha_data_model_derive::IdEqOrdHash;
+    use iroha_schema::IntoSchema;
+    use parity_scale_codec::{Decode, Encode};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// Identification of a client application.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash,
+        Constructor,
+        FromStr,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[getset(get = "pub")]
+    #[serde(transparent)]
+    #[repr(transparent)]
+    #[ffi_type(opaque)]
+    pub struct AppId {
+        /// App name, should be unique.
+        pub name: Name,
+    }
+
+    /// Client application to which transactions are attributed.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        IdEqOrdHash,
+        Constructor,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{id}")]
+    #[ffi_type]
+    pub struct App {
+        /// Unique name of the app.
+        pub id: AppId,
+        /// Account which registered the app and can unregister it.
+        #[getset(get = "pub")]
+        pub owner: AccountId,
+        /// Key with which the transactions attributed to the app are signed.
+        #[getset(get = "pub")]
+        pub public_key: PublicKey,
+    }
+}
+
+impl AppId {
+    /// Read the app named in the [`APP_ID_METADATA_KEY`] entry of the transaction `metadata`.
+    ///
+    /// Returns [`None`] if the transaction isn't attributed to any app.
+    ///
+    /// # Errors
+    /// Fails if the entry isn't a valid app id
+    pub fn from_metadata(metadata: &Metadata) -> Option<Result<Self, String>> {
+        metadata.get(APP_ID_METADATA_KEY).map(|value| {
+            value
+                .try_into_any::<Self>()
+                .map_err(|_| format!("`{APP_ID_METADATA_KEY}` is not a valid app id: {value}"))
+        })
+    }
+}
+
+/// The prelude re-exports most commonly used traits, structs and macros from this module.
+pub mod prelude {
+    pub use super::{App, AppId, APP_ID_METADATA_KEY};
+}
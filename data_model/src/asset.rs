@@ -8,7 +8,10 @@ use std::collections::btree_map;
 
 use derive_more::{Constructor, DebugCustom, Display};
 use iroha_data_model_derive::{model, IdEqOrdHash};
-use iroha_primitives::numeric::{Numeric, NumericSpec, NumericSpecParseError};
+use iroha_primitives::{
+    json::JsonString,
+    numeric::{Numeric, NumericSpec, NumericSpecParseError},
+};
 use iroha_schema::IntoSchema;
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
@@ -153,6 +156,33 @@ mod model {
         pub value: AssetValue,
     }
 
+    /// Key-value pair stored in a [`Store`](AssetValue::Store) asset.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Constructor,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{key}: {value}")]
+    #[getset(get = "pub")]
+    #[ffi_type]
+    pub struct StoreEntry {
+        /// Key of the pair.
+        pub key: Name,
+        /// Value of the pair.
+        pub value: JsonString,
+    }
+
     /// Builder which can be submitted in a transaction to create a new [`AssetDefinition`]
     #[derive(
         Debug, Display, Clone, IdEqOrdHash, Decode, Encode, Deserialize, Serialize, IntoSchema,
@@ -482,7 +512,7 @@ impl Registered for AssetDefinition {
 pub mod prelude {
     pub use super::{
         Asset, AssetDefinition, AssetDefinitionId, AssetId, AssetType, AssetValue, Mintable,
-        NewAssetDefinition,
+        NewAssetDefinition, StoreEntry,
     };
 }
 
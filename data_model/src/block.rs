@@ -9,8 +9,8 @@ use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use core::{fmt::Display, time::Duration};
 
 use derive_more::Display;
-use iroha_crypto::{HashOf, MerkleTree, PrivateKey, SignatureOf};
-use iroha_data_model_derive::model;
+use iroha_crypto::{HashOf, MerkleTree, PrivateKey, SignatureOf, Unverified, Verified};
+use iroha_data_model_derive::{model, ValidatedDecode};
 use iroha_macro::FromVariant;
 use iroha_schema::IntoSchema;
 use iroha_version::{declare_versioned, version_with_scale};
@@ -21,6 +21,16 @@ use serde::{Deserialize, Serialize};
 pub use self::model::*;
 use crate::transaction::prelude::*;
 
+/// Protocol version of the genesis block
+pub const GENESIS_PROTOCOL_VERSION: u32 = 1;
+
+/// Highest protocol version this peer can validate and execute blocks of.
+///
+/// The version of the chain only changes at the height scheduled with
+/// [`BlockParameter::ProtocolUpgrade`](crate::parameter::BlockParameter::ProtocolUpgrade),
+/// so peers should be upgraded to a release supporting the new version before that height.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 #[model]
 mod model {
     use core::num::NonZeroU64;
@@ -66,10 +76,13 @@ mod model {
         #[getset(skip)]
         pub creation_time_ms: u64,
         /// Value of view change index. Used to resolve soft forks.
-        #[getset(skip)]
+        #[getset(get_copy = "pub")]
         pub view_change_index: u32,
         /// Estimation of consensus duration (in milliseconds).
         pub consensus_estimation_ms: u64,
+        /// Version of the protocol the block was created with.
+        #[getset(get_copy = "pub")]
+        pub protocol_version: u32,
     }
 
     #[derive(
@@ -112,14 +125,25 @@ mod model {
     pub struct BlockSignature(
         /// Index of the peer in the topology
         pub u64,
-        /// Payload
-        pub SignatureOf<BlockPayload>,
+        /// Payload. Received blocks carry signatures which are yet to be verified
+        /// against the topology.
+        pub Unverified<SignatureOf<BlockPayload>>,
     );
 
     /// Signed block
     #[version_with_scale(version = 1, versioned_alias = "SignedBlock")]
     #[derive(
-        Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Serialize, IntoSchema,
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Encode,
+        Serialize,
+        ValidatedDecode,
+        IntoSchema,
     )]
     #[display(fmt = "{}", "self.hash()")]
     #[ffi_type]
@@ -159,7 +183,7 @@ impl BlockPayload {
     /// Create new signed block, using `key_pair` to sign `payload`
     #[cfg(feature = "transparent_api")]
     pub fn sign(self, private_key: &iroha_crypto::PrivateKey) -> SignedBlock {
-        let signatures = vec![BlockSignature(0, SignatureOf::new(private_key, &self))];
+        let signatures = vec![BlockSignature(0, Verified::sign(private_key, &self).into())];
 
         SignedBlockV1 {
             signatures,
@@ -215,27 +239,42 @@ impl SignedBlock {
         block.hash()
     }
 
-    /// Add signature to the block
+    /// Verify that `signature` of the block payload was made by `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the signature doesn't correspond to the block payload or the key
+    pub fn verify_signature(
+        &self,
+        signature: &BlockSignature,
+        public_key: &iroha_crypto::PublicKey,
+    ) -> Result<Verified<SignatureOf<BlockPayload>>, iroha_crypto::Error> {
+        let SignedBlock::V1(block) = self;
+        signature.1.verify(public_key, &block.payload)
+    }
+
+    /// Add signature of the peer at index `signatory` in the topology to the block.
+    /// The signature has to be verified against the key of the peer beforehand.
     ///
     /// # Errors
     ///
-    /// if signature is invalid
+    /// if the block is already signed by `signatory`
     #[cfg(feature = "transparent_api")]
     pub fn add_signature(
         &mut self,
-        signature: BlockSignature,
-        public_key: &iroha_crypto::PublicKey,
+        signatory: u64,
+        signature: Verified<SignatureOf<BlockPayload>>,
     ) -> Result<(), iroha_crypto::Error> {
-        if self.signatures().any(|s| signature.0 == s.0) {
+        if self.signatures().any(|s| signatory == s.0) {
             return Err(iroha_crypto::Error::Signing(
                 "Duplicate signature".to_owned(),
             ));
         }
 
-        signature.1.verify(public_key, self.payload())?;
-
         let SignedBlock::V1(block) = self;
-        block.signatures.push(signature);
+        block
+            .signatures
+            .push(BlockSignature(signatory, signature.into()));
 
         Ok(())
     }
@@ -257,7 +296,7 @@ impl SignedBlock {
 
         block.signatures.push(BlockSignature(
             signatory as u64,
-            SignatureOf::new(private_key, &block.payload),
+            Verified::sign(private_key, &block.payload).into(),
         ));
     }
 
@@ -282,6 +321,7 @@ impl SignedBlock {
             creation_time_ms,
             view_change_index: 0,
             consensus_estimation_ms: 0,
+            protocol_version: GENESIS_PROTOCOL_VERSION,
         };
         let transactions = genesis_transactions
             .into_iter()
@@ -296,7 +336,7 @@ impl SignedBlock {
             transactions,
         };
 
-        let signature = BlockSignature(0, SignatureOf::new(genesis_private_key, &payload));
+        let signature = BlockSignature(0, Verified::sign(genesis_private_key, &payload).into());
         SignedBlockV1 {
             signatures: vec![signature],
             payload,
@@ -311,29 +351,18 @@ mod candidate {
     #[cfg(feature = "std")]
     use std::collections::BTreeSet;
 
-    use parity_scale_codec::Input;
-
     use super::*;
     use crate::isi::InstructionBox;
 
-    #[derive(Decode, Deserialize)]
-    struct SignedBlockCandidate {
-        signatures: Vec<BlockSignature>,
-        payload: BlockPayload,
-    }
-
-    impl SignedBlockCandidate {
-        fn validate(self) -> Result<SignedBlockV1, &'static str> {
+    impl SignedBlockV1 {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
             self.validate_signatures()?;
             self.validate_header()?;
             if self.payload.header.height.get() == 1 {
                 self.validate_genesis()?;
             }
 
-            Ok(SignedBlockV1 {
-                signatures: self.signatures,
-                payload: self.payload,
-            })
+            Ok(self)
         }
 
         fn validate_genesis(&self) -> Result<(), &'static str> {
@@ -408,26 +437,6 @@ mod candidate {
             Ok(())
         }
     }
-
-    impl Decode for SignedBlockV1 {
-        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            SignedBlockCandidate::decode(input)?
-                .validate()
-                .map_err(Into::into)
-        }
-    }
-    impl<'de> Deserialize<'de> for SignedBlockV1 {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            use serde::de::Error as _;
-
-            SignedBlockCandidate::deserialize(deserializer)?
-                .validate()
-                .map_err(D::Error::custom)
-        }
-    }
 }
 
 impl Display for SignedBlock {
@@ -479,6 +488,154 @@ pub mod stream {
     }
 }
 
+pub mod state_diff {
+    //! Changes of the world state made by a block, recorded for explorers.
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    pub use self::model::*;
+    use super::*;
+    use crate::{
+        account::Account,
+        asset::{Asset, AssetDefinition},
+        domain::Domain,
+        escrow::Escrow,
+        parameter::Parameters,
+        peer::Peer,
+        role::Role,
+    };
+
+    #[model]
+    mod model {
+        use super::*;
+
+        /// Value of an entity before and after the block was applied.
+        ///
+        /// `before` is `None` if the entity was created by the block,
+        /// `after` is `None` if the entity was deleted by the block.
+        #[derive(
+            Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema,
+        )]
+        #[ffi_type]
+        pub struct StateChange<T> {
+            /// Value before the block was applied
+            pub before: Option<T>,
+            /// Value after the block was applied
+            pub after: Option<T>,
+        }
+
+        /// Change of a single entity of the world state
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            FromVariant,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[ffi_type]
+        pub enum StateChangeBox {
+            /// Domain change
+            Domain(StateChange<Domain>),
+            /// Account change
+            Account(StateChange<Account>),
+            /// Asset definition change
+            AssetDefinition(StateChange<AssetDefinition>),
+            /// Asset change
+            Asset(StateChange<Asset>),
+            /// Role change
+            Role(StateChange<Role>),
+            /// Peer change
+            Peer(StateChange<Peer>),
+            /// Escrow change
+            Escrow(StateChange<Escrow>),
+            /// Change of chain-wide parameters
+            Parameters(StateChange<Parameters>),
+        }
+
+        /// All entities of the world state created, updated or deleted by a block
+        #[derive(
+            Debug, Default, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema,
+        )]
+        #[ffi_type]
+        pub struct BlockStateDiff {
+            /// Changes in the order in which entities were first touched by the block
+            pub changes: Vec<StateChangeBox>,
+        }
+    }
+
+    impl<T> StateChange<T> {
+        /// Whether the entity was created by the block
+        pub fn is_created(&self) -> bool {
+            self.before.is_none() && self.after.is_some()
+        }
+
+        /// Whether the entity was deleted by the block
+        pub fn is_deleted(&self) -> bool {
+            self.before.is_some() && self.after.is_none()
+        }
+    }
+
+    /// Exports common structs and enums from this module.
+    pub mod prelude {
+        pub use super::{BlockStateDiff, StateChange, StateChangeBox};
+    }
+}
+
+pub mod reward {
+    //! Payouts of the fees collected in a block, see [`FeeParameters`](crate::parameter::FeeParameters).
+
+    use derive_more::Constructor;
+    use getset::Getters;
+    use iroha_primitives::numeric::Numeric;
+
+    pub use self::model::*;
+    use super::*;
+    use crate::{account::AccountId, asset::AssetDefinitionId};
+
+    #[model]
+    mod model {
+        use super::*;
+
+        /// Fees paid out at the end of a block
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Getters,
+            Constructor,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[getset(get = "pub")]
+        #[ffi_type]
+        pub struct BlockReward {
+            /// Definition of the asset the fees were collected in
+            asset_definition: AssetDefinitionId,
+            /// Operator account the fees were paid to, [`None`] if the fees were burned
+            recipient: Option<AccountId>,
+            /// Amount paid out
+            amount: Numeric,
+        }
+    }
+
+    /// Exports common structs and enums from this module.
+    pub mod prelude {
+        pub use super::BlockReward;
+    }
+}
+
 pub mod error {
     //! Module containing errors that can occur during instruction evaluation
 
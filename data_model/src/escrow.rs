@@ -0,0 +1,116 @@
+//! Structures, traits and impls related to `Escrow`s.
+//!
+//! Escrow holds an amount of a numeric asset locked by its owner until either
+//! the recipient claims it or the owner takes it back once the escrow has expired.
+//! Optionally, the recipient has to reveal the preimage of a hash in order to claim the funds,
+//! which allows to build hash time-locked contracts (HTLC) for cross-chain swaps.
+
+use iroha_crypto::Hash;
+use iroha_data_model_derive::model;
+use iroha_primitives::numeric::Numeric;
+
+pub use self::model::*;
+use crate::{account::AccountId, asset::AssetDefinitionId, Identifiable, Name};
+
+#[model]
+mod model {
+    use derive_more::{Constructor, Display, FromStr};
+    use getset::{CopyGetters, Getters};
+    use iroha_data_model_derive::IdEqOrdHash;
+    use iroha_schema::IntoSchema;
+    use parity_scale_codec::{Decode, Encode};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// Identification of an escrow.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash,
+        Constructor,
+        FromStr,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[getset(get = "pub")]
+    #[serde(transparent)]
+    #[repr(transparent)]
+    #[ffi_type(opaque)]
+    pub struct EscrowId {
+        /// Escrow name, should be unique.
+        pub name: Name,
+    }
+
+    /// Amount of an asset locked until it is either claimed by the recipient or refunded to the owner.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        IdEqOrdHash,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{id}")]
+    #[ffi_type]
+    pub struct Escrow {
+        /// Unique name of the escrow.
+        pub id: EscrowId,
+        /// Account which locked the funds and gets them back on refund.
+        #[getset(get = "pub")]
+        pub owner: AccountId,
+        /// Account which is allowed to claim the funds.
+        #[getset(get = "pub")]
+        pub recipient: AccountId,
+        /// Definition of the locked asset.
+        #[getset(get = "pub")]
+        pub asset_definition: AssetDefinitionId,
+        /// Locked amount.
+        #[getset(get_copy = "pub")]
+        pub amount: Numeric,
+        /// Time since the Unix epoch (in ms) starting from which the funds
+        /// can no longer be claimed and can be refunded instead.
+        #[getset(get_copy = "pub")]
+        pub expires_at_ms: u64,
+        /// Hash of the preimage the recipient has to reveal to claim the funds.
+        #[getset(get = "pub")]
+        pub hash_lock: Option<Hash>,
+    }
+}
+
+impl Escrow {
+    /// Check whether the escrow has expired at the given time.
+    #[inline]
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms >= self.expires_at_ms
+    }
+
+    /// Check whether `preimage` opens the hash lock of the escrow.
+    /// Always `true` for escrows without the hash lock.
+    pub fn is_unlocked_by(&self, preimage: Option<&[u8]>) -> bool {
+        match (&self.hash_lock, preimage) {
+            (None, _) => true,
+            (Some(hash_lock), Some(preimage)) => Hash::new(preimage) == *hash_lock,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// The prelude re-exports most commonly used traits, structs and macros from this module.
+pub mod prelude {
+    pub use super::{Escrow, EscrowId};
+}
@@ -1,6 +1,8 @@
 //! This module contains data events
 #![allow(missing_docs)]
 
+use core::num::NonZeroU64;
+
 use getset::Getters;
 use iroha_data_model_derive::{model, EventSet, HasOrigin};
 use iroha_primitives::{json::JsonString, numeric::Numeric};
@@ -92,6 +94,8 @@ mod model {
         Configuration(config::ConfigurationEvent),
         /// Executor event
         Executor(executor::ExecutorEvent),
+        /// Escrow event
+        Escrow(escrow::EscrowEvent),
     }
 }
 
@@ -146,6 +150,9 @@ mod asset {
         use super::*;
 
         /// Depending on the wrapping event, [`Self`] represents the added or removed asset quantity.
+        ///
+        /// The resulting balance lets consumers check that they haven't missed any events
+        /// without tracking the balances themselves.
         #[derive(
             Debug,
             Clone,
@@ -165,6 +172,10 @@ mod asset {
         pub struct AssetChanged {
             pub asset: AssetId,
             pub amount: AssetValue,
+            /// Value of the asset after the change
+            pub balance: AssetValue,
+            /// Height of the block whose execution changed the asset
+            pub height: NonZeroU64,
         }
 
         /// [`Self`] represents updated total asset quantity.
@@ -225,6 +236,7 @@ mod peer {
         pub enum PeerEvent {
             Added(PeerId),
             Removed(PeerId),
+            CertificateRegistered(PeerId),
         }
     }
 }
@@ -282,6 +294,27 @@ mod role {
     }
 }
 
+mod escrow {
+    //! This module contains `EscrowEvent` and its impls
+
+    use super::*;
+
+    data_event! {
+        #[has_origin(origin = Escrow)]
+        pub enum EscrowEvent {
+            /// Funds were locked in the escrow.
+            #[has_origin(escrow => escrow.id())]
+            Locked(Escrow),
+            /// Funds were claimed by the recipient of the escrow.
+            #[has_origin(escrow => escrow.id())]
+            Claimed(Escrow),
+            /// Funds were returned to the owner of the expired escrow.
+            #[has_origin(escrow => escrow.id())]
+            Refunded(Escrow),
+        }
+    }
+}
+
 mod account {
     //! This module contains `AccountEvent` and its impls
 
@@ -312,6 +345,25 @@ mod account {
             MetadataInserted(AccountMetadataChanged),
             #[has_origin(metadata_changed => &metadata_changed.target)]
             MetadataRemoved(AccountMetadataChanged),
+            Frozen(AccountId),
+            Unfrozen(AccountId),
+            #[has_origin(policy => &policy.account)]
+            RecoveryPolicySet(RecoveryPolicy),
+            #[has_origin(policy => &policy.account)]
+            RecoveryInitiated(RecoveryPolicy),
+            RecoveryVetoed(AccountId),
+            #[has_origin(recovered => &recovered.account)]
+            Recovered(AccountRecovered),
+            #[has_origin(app => app.owner())]
+            AppRegistered(App),
+            #[has_origin(app => app.owner())]
+            AppUnregistered(App),
+            #[has_origin(token => token.account())]
+            ProxyGranted(ProxyToken),
+            #[has_origin(token => token.account())]
+            ProxyRevoked(ProxyToken),
+            #[has_origin(token => token.account())]
+            ProxyUsed(ProxyToken),
         }
     }
 
@@ -364,6 +416,28 @@ mod account {
             pub account: AccountId,
             pub role: RoleId,
         }
+
+        /// Event indicating that the account was moved to the signatory of its pending recovery
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Getters,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[getset(get = "pub")]
+        #[ffi_type]
+        pub struct AccountRecovered {
+            pub account: AccountId,
+            pub new_account: AccountId,
+        }
     }
 
     impl AccountPermissionChanged {
@@ -452,6 +526,10 @@ mod trigger {
             MetadataInserted(TriggerMetadataChanged),
             #[has_origin(metadata_changed => &metadata_changed.target)]
             MetadataRemoved(TriggerMetadataChanged),
+            #[has_origin(delegation_changed => &delegation_changed.trigger)]
+            Delegated(TriggerDelegationChanged),
+            #[has_origin(delegation_changed => &delegation_changed.trigger)]
+            DelegationRevoked(TriggerDelegationChanged),
         }
     }
 
@@ -480,6 +558,29 @@ mod trigger {
             pub trigger: TriggerId,
             pub by: u32,
         }
+
+        /// Depending on the wrapping event, [`Self`] represents the account which delegated
+        /// its authority to the trigger or revoked the delegation.
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Getters,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[getset(get = "pub")]
+        #[ffi_type]
+        pub struct TriggerDelegationChanged {
+            pub trigger: TriggerId,
+            pub delegator: AccountId,
+        }
     }
 }
 
@@ -634,6 +735,7 @@ impl DataEvent {
             Self::Domain(event) => Some(event.origin()),
             Self::Configuration(_)
             | Self::Executor(_)
+            | Self::Escrow(_)
             | Self::Peer(_)
             | Self::Role(_)
             | Self::Trigger(_) => None,
@@ -660,7 +762,10 @@ impl<Id> MetadataChanged<Id> {
 
 pub mod prelude {
     pub use super::{
-        account::{AccountEvent, AccountEventSet, AccountPermissionChanged, AccountRoleChanged},
+        account::{
+            AccountEvent, AccountEventSet, AccountPermissionChanged, AccountRecovered,
+            AccountRoleChanged,
+        },
         asset::{
             AssetChanged, AssetDefinitionEvent, AssetDefinitionEventSet,
             AssetDefinitionOwnerChanged, AssetDefinitionTotalQuantityChanged, AssetEvent,
@@ -668,10 +773,14 @@ pub mod prelude {
         },
         config::{ConfigurationEvent, ConfigurationEventSet, ParameterChanged},
         domain::{DomainEvent, DomainEventSet, DomainOwnerChanged},
+        escrow::{EscrowEvent, EscrowEventSet},
         executor::{ExecutorEvent, ExecutorEventSet, ExecutorUpgrade},
         peer::{PeerEvent, PeerEventSet},
         role::{RoleEvent, RoleEventSet, RolePermissionChanged},
-        trigger::{TriggerEvent, TriggerEventSet, TriggerNumberOfExecutionsChanged},
+        trigger::{
+            TriggerDelegationChanged, TriggerEvent, TriggerEventSet,
+            TriggerNumberOfExecutionsChanged,
+        },
         DataEvent, HasOrigin, MetadataChanged,
     };
 }
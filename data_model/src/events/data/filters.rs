@@ -51,6 +51,8 @@ mod model {
         Configuration(ConfigurationEventFilter),
         /// Matches [`ExecutorEvent`]s
         Executor(ExecutorEventFilter),
+        /// Matches [`EscrowEvent`]s
+        Escrow(EscrowEventFilter),
     }
 
     /// An event filter for [`PeerEvent`]s
@@ -95,6 +97,8 @@ mod model {
         pub(super) id_matcher: Option<super::DomainId>,
         /// Matches only event from this set
         pub(super) event_set: DomainEventSet,
+        /// If specified matches only changes of the metadata under this key
+        pub(super) metadata_key_matcher: Option<Name>,
     }
 
     /// An event filter for [`AccountEvent`]s
@@ -117,6 +121,8 @@ mod model {
         pub(super) id_matcher: Option<super::AccountId>,
         /// Matches only event from this set
         pub(super) event_set: AccountEventSet,
+        /// If specified matches only changes of the metadata under this key
+        pub(super) metadata_key_matcher: Option<Name>,
     }
 
     /// An event filter for [`AssetEvent`]s
@@ -139,6 +145,8 @@ mod model {
         pub(super) id_matcher: Option<super::AssetId>,
         /// Matches only event from this set
         pub(super) event_set: AssetEventSet,
+        /// If specified matches only changes of the metadata under this key
+        pub(super) metadata_key_matcher: Option<Name>,
     }
 
     /// An event filter for [`AssetDefinitionEvent`]s
@@ -161,6 +169,8 @@ mod model {
         pub(super) id_matcher: Option<super::AssetDefinitionId>,
         /// Matches only event from this set
         pub(super) event_set: AssetDefinitionEventSet,
+        /// If specified matches only changes of the metadata under this key
+        pub(super) metadata_key_matcher: Option<Name>,
     }
 
     /// An event filter for [`TriggerEvent`]s
@@ -183,6 +193,8 @@ mod model {
         pub(super) id_matcher: Option<super::TriggerId>,
         /// Matches only event from this set
         pub(super) event_set: TriggerEventSet,
+        /// If specified matches only changes of the metadata under this key
+        pub(super) metadata_key_matcher: Option<Name>,
     }
 
     /// An event filter for [`RoleEvent`]s
@@ -247,6 +259,28 @@ mod model {
         /// Matches only event from this set
         pub(super) event_set: ExecutorEventSet,
     }
+
+    /// An event filter for [`EscrowEvent`]s
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub struct EscrowEventFilter {
+        /// If specified matches only events originating from this escrow
+        pub(super) id_matcher: Option<super::EscrowId>,
+        /// Matches only event from this set
+        pub(super) event_set: EscrowEventSet,
+    }
 }
 
 impl PeerEventFilter {
@@ -304,6 +338,7 @@ impl DomainEventFilter {
         Self {
             id_matcher: None,
             event_set: DomainEventSet::all(),
+            metadata_key_matcher: None,
         }
     }
 
@@ -320,6 +355,13 @@ impl DomainEventFilter {
         self.event_set = event_set;
         self
     }
+
+    /// Modifies a [`DomainEventFilter`] to accept only [`DomainEvent`]s inserting or removing metadata under `key`.
+    #[must_use]
+    pub fn for_metadata_key(mut self, key: Name) -> Self {
+        self.metadata_key_matcher = Some(key);
+        self
+    }
 }
 
 impl Default for DomainEventFilter {
@@ -343,6 +385,12 @@ impl EventFilter for DomainEventFilter {
             return false;
         }
 
+        if let Some(key_matcher) = &self.metadata_key_matcher {
+            if event.metadata_key() != Some(key_matcher) {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -353,6 +401,7 @@ impl AccountEventFilter {
         Self {
             id_matcher: None,
             event_set: AccountEventSet::all(),
+            metadata_key_matcher: None,
         }
     }
 
@@ -369,6 +418,13 @@ impl AccountEventFilter {
         self.event_set = event_set;
         self
     }
+
+    /// Modifies a [`AccountEventFilter`] to accept only [`AccountEvent`]s inserting or removing metadata under `key`.
+    #[must_use]
+    pub fn for_metadata_key(mut self, key: Name) -> Self {
+        self.metadata_key_matcher = Some(key);
+        self
+    }
 }
 
 impl Default for AccountEventFilter {
@@ -392,6 +448,12 @@ impl super::EventFilter for AccountEventFilter {
             return false;
         }
 
+        if let Some(key_matcher) = &self.metadata_key_matcher {
+            if event.metadata_key() != Some(key_matcher) {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -402,6 +464,7 @@ impl AssetEventFilter {
         Self {
             id_matcher: None,
             event_set: AssetEventSet::all(),
+            metadata_key_matcher: None,
         }
     }
 
@@ -418,6 +481,13 @@ impl AssetEventFilter {
         self.event_set = event_set;
         self
     }
+
+    /// Modifies a [`AssetEventFilter`] to accept only [`AssetEvent`]s inserting or removing metadata under `key`.
+    #[must_use]
+    pub fn for_metadata_key(mut self, key: Name) -> Self {
+        self.metadata_key_matcher = Some(key);
+        self
+    }
 }
 
 impl Default for AssetEventFilter {
@@ -441,6 +511,12 @@ impl super::EventFilter for AssetEventFilter {
             return false;
         }
 
+        if let Some(key_matcher) = &self.metadata_key_matcher {
+            if event.metadata_key() != Some(key_matcher) {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -451,6 +527,7 @@ impl AssetDefinitionEventFilter {
         Self {
             id_matcher: None,
             event_set: AssetDefinitionEventSet::all(),
+            metadata_key_matcher: None,
         }
     }
 
@@ -467,6 +544,13 @@ impl AssetDefinitionEventFilter {
         self.event_set = event_set;
         self
     }
+
+    /// Modifies a [`AssetDefinitionEventFilter`] to accept only [`AssetDefinitionEvent`]s inserting or removing metadata under `key`.
+    #[must_use]
+    pub fn for_metadata_key(mut self, key: Name) -> Self {
+        self.metadata_key_matcher = Some(key);
+        self
+    }
 }
 
 impl Default for AssetDefinitionEventFilter {
@@ -490,6 +574,12 @@ impl super::EventFilter for AssetDefinitionEventFilter {
             return false;
         }
 
+        if let Some(key_matcher) = &self.metadata_key_matcher {
+            if event.metadata_key() != Some(key_matcher) {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -500,6 +590,7 @@ impl TriggerEventFilter {
         Self {
             id_matcher: None,
             event_set: TriggerEventSet::all(),
+            metadata_key_matcher: None,
         }
     }
 
@@ -516,6 +607,13 @@ impl TriggerEventFilter {
         self.event_set = event_set;
         self
     }
+
+    /// Modifies a [`TriggerEventFilter`] to accept only [`TriggerEvent`]s inserting or removing metadata under `key`.
+    #[must_use]
+    pub fn for_metadata_key(mut self, key: Name) -> Self {
+        self.metadata_key_matcher = Some(key);
+        self
+    }
 }
 
 impl Default for TriggerEventFilter {
@@ -539,6 +637,12 @@ impl super::EventFilter for TriggerEventFilter {
             return false;
         }
 
+        if let Some(key_matcher) = &self.metadata_key_matcher {
+            if event.metadata_key() != Some(key_matcher) {
+                return false;
+            }
+        }
+
         true
     }
 }
@@ -662,6 +766,144 @@ impl super::EventFilter for ExecutorEventFilter {
     }
 }
 
+impl EscrowEventFilter {
+    /// Creates a new [`EscrowEventFilter`] accepting all [`EscrowEvent`]s.
+    pub const fn new() -> Self {
+        Self {
+            id_matcher: None,
+            event_set: EscrowEventSet::all(),
+        }
+    }
+
+    /// Modifies a [`EscrowEventFilter`] to accept only [`EscrowEvent`]s originating from ids matching `id_matcher`.
+    #[must_use]
+    pub fn for_escrow(mut self, id_matcher: EscrowId) -> Self {
+        self.id_matcher = Some(id_matcher);
+        self
+    }
+
+    /// Modifies a [`EscrowEventFilter`] to accept only [`EscrowEvent`]s of types matching `event_set`.
+    #[must_use]
+    pub const fn for_events(mut self, event_set: EscrowEventSet) -> Self {
+        self.event_set = event_set;
+        self
+    }
+}
+
+impl Default for EscrowEventFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "transparent_api")]
+impl super::EventFilter for EscrowEventFilter {
+    type Event = super::EscrowEvent;
+
+    fn matches(&self, event: &Self::Event) -> bool {
+        if let Some(id_matcher) = &self.id_matcher {
+            if id_matcher != event.origin() {
+                return false;
+            }
+        }
+
+        if !self.event_set.matches(event) {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Events which can change metadata of an entity
+#[cfg(feature = "transparent_api")]
+trait MetadataKey {
+    /// Key of the inserted or removed metadata, if the event (or the one nested in it) changes metadata
+    fn metadata_key(&self) -> Option<&Name>;
+}
+
+#[cfg(feature = "transparent_api")]
+impl MetadataKey for AssetEvent {
+    fn metadata_key(&self) -> Option<&Name> {
+        match self {
+            Self::MetadataInserted(changed) | Self::MetadataRemoved(changed) => Some(&changed.key),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "transparent_api")]
+impl MetadataKey for AssetDefinitionEvent {
+    fn metadata_key(&self) -> Option<&Name> {
+        match self {
+            Self::MetadataInserted(changed) | Self::MetadataRemoved(changed) => Some(&changed.key),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "transparent_api")]
+impl MetadataKey for AccountEvent {
+    fn metadata_key(&self) -> Option<&Name> {
+        match self {
+            Self::MetadataInserted(changed) | Self::MetadataRemoved(changed) => Some(&changed.key),
+            Self::Asset(event) => event.metadata_key(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "transparent_api")]
+impl MetadataKey for DomainEvent {
+    fn metadata_key(&self) -> Option<&Name> {
+        match self {
+            Self::MetadataInserted(changed) | Self::MetadataRemoved(changed) => Some(&changed.key),
+            Self::Account(event) => event.metadata_key(),
+            Self::AssetDefinition(event) => event.metadata_key(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "transparent_api")]
+impl MetadataKey for TriggerEvent {
+    fn metadata_key(&self) -> Option<&Name> {
+        match self {
+            Self::MetadataInserted(changed) | Self::MetadataRemoved(changed) => Some(&changed.key),
+            _ => None,
+        }
+    }
+}
+
+impl DataEventFilter {
+    /// Whether the filter only matches events originating from entities of `domain`
+    pub fn is_scoped_to_domain(&self, domain: &DomainId) -> bool {
+        match self {
+            Self::Domain(filter) => filter.id_matcher.as_ref() == Some(domain),
+            Self::Account(filter) => filter
+                .id_matcher
+                .as_ref()
+                .is_some_and(|account| account.domain() == domain),
+            // Asset events are emitted as events of the account owning the asset
+            Self::Asset(filter) => filter
+                .id_matcher
+                .as_ref()
+                .is_some_and(|asset| asset.account.domain() == domain),
+            Self::AssetDefinition(filter) => filter
+                .id_matcher
+                .as_ref()
+                .is_some_and(|asset_definition| &asset_definition.domain == domain),
+            Self::Any
+            | Self::Peer(_)
+            | Self::Trigger(_)
+            | Self::Role(_)
+            | Self::Configuration(_)
+            | Self::Executor(_)
+            | Self::Escrow(_) => false,
+        }
+    }
+}
+
 #[cfg(feature = "transparent_api")]
 impl EventFilter for DataEventFilter {
     type Event = DataEvent;
@@ -688,6 +930,7 @@ impl EventFilter for DataEventFilter {
             (DataEvent::Role(event), Role(filter)) => filter.matches(event),
             (DataEvent::Configuration(event), Configuration(filter)) => filter.matches(event),
             (DataEvent::Executor(event), Executor(filter)) => filter.matches(event),
+            (DataEvent::Escrow(event), Escrow(filter)) => filter.matches(event),
 
             (
                 DataEvent::Peer(_)
@@ -695,7 +938,8 @@ impl EventFilter for DataEventFilter {
                 | DataEvent::Trigger(_)
                 | DataEvent::Role(_)
                 | DataEvent::Configuration(_)
-                | DataEvent::Executor(_),
+                | DataEvent::Executor(_)
+                | DataEvent::Escrow(_),
                 Any,
             ) => true,
             (
@@ -704,7 +948,8 @@ impl EventFilter for DataEventFilter {
                 | DataEvent::Trigger(_)
                 | DataEvent::Role(_)
                 | DataEvent::Configuration(_)
-                | DataEvent::Executor(_),
+                | DataEvent::Executor(_)
+                | DataEvent::Escrow(_),
                 _,
             ) => false,
         }
@@ -714,8 +959,8 @@ impl EventFilter for DataEventFilter {
 pub mod prelude {
     pub use super::{
         AccountEventFilter, AssetDefinitionEventFilter, AssetEventFilter, ConfigurationEventFilter,
-        DataEventFilter, DomainEventFilter, ExecutorEventFilter, PeerEventFilter, RoleEventFilter,
-        TriggerEventFilter,
+        DataEventFilter, DomainEventFilter, EscrowEventFilter, ExecutorEventFilter,
+        PeerEventFilter, RoleEventFilter, TriggerEventFilter,
     };
 }
 #[cfg(test)]
@@ -772,4 +1017,79 @@ mod tests {
         assert!(!asset_filter.matches(&account_created));
         assert!(asset_filter.matches(&asset_created));
     }
+
+    #[test]
+    #[cfg(feature = "transparent_api")]
+    fn metadata_key_scope() {
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let account_id = AccountId::new(domain_id.clone(), KeyPair::random().into_parts().0);
+        let kyc_status: Name = "kyc_status".parse().unwrap();
+
+        let metadata_changed = |key: &Name| MetadataChanged {
+            target: account_id.clone(),
+            key: key.clone(),
+            value: JsonString::new("verified"),
+        };
+        let kyc_inserted = DomainEvent::Account(AccountEvent::MetadataInserted(metadata_changed(
+            &kyc_status,
+        )))
+        .into();
+        let kyc_removed =
+            DomainEvent::Account(AccountEvent::MetadataRemoved(metadata_changed(&kyc_status)))
+                .into();
+        let other_inserted = DomainEvent::Account(AccountEvent::MetadataInserted(
+            metadata_changed(&"nickname".parse().unwrap()),
+        ))
+        .into();
+        let account_created = DomainEvent::Account(AccountEvent::Created(
+            Account::new(account_id.clone()).into_account(),
+        ))
+        .into();
+
+        // any account in the domain
+        let filter = DataEventFilter::Domain(
+            DomainEventFilter::new()
+                .for_domain(domain_id)
+                .for_events(DomainEventSet::AnyAccount)
+                .for_metadata_key(kyc_status),
+        );
+
+        assert!(filter.matches(&kyc_inserted));
+        assert!(filter.matches(&kyc_removed));
+        assert!(!filter.matches(&other_inserted));
+        assert!(!filter.matches(&account_created));
+    }
+
+    #[test]
+    fn domain_scope() {
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let other_domain_id: DomainId = "looking_glass".parse().unwrap();
+        let account_id = AccountId::new(domain_id.clone(), KeyPair::random().into_parts().0);
+        let asset_id: AssetId = format!("rose#{other_domain_id}#{account_id}")
+            .parse()
+            .unwrap();
+
+        let scoped = [
+            DataEventFilter::Domain(DomainEventFilter::new().for_domain(domain_id.clone())),
+            DataEventFilter::Account(AccountEventFilter::new().for_account(account_id)),
+            // Asset of an account of the domain, even though its definition is foreign
+            DataEventFilter::Asset(AssetEventFilter::new().for_asset(asset_id.clone())),
+        ];
+        let unscoped = [
+            DataEventFilter::Any,
+            DataEventFilter::Domain(DomainEventFilter::new()),
+            DataEventFilter::Domain(DomainEventFilter::new().for_domain(other_domain_id)),
+            DataEventFilter::AssetDefinition(
+                AssetDefinitionEventFilter::new().for_asset_definition(asset_id.definition),
+            ),
+            DataEventFilter::Peer(PeerEventFilter::new()),
+        ];
+
+        for filter in scoped {
+            assert!(filter.is_scoped_to_domain(&domain_id), "{filter:?}");
+        }
+        for filter in unscoped {
+            assert!(!filter.is_scoped_to_domain(&domain_id), "{filter:?}");
+        }
+    }
 }
@@ -95,6 +95,13 @@ impl ExecuteTriggerEventFilter {
         self.authority = Some(authority);
         self
     }
+
+    /// Whether the filter only matches executions under the authority of an account of `domain`
+    pub fn is_scoped_to_domain(&self, domain: &DomainId) -> bool {
+        self.authority
+            .as_ref()
+            .is_some_and(|authority| authority.domain() == domain)
+    }
 }
 
 #[cfg(feature = "transparent_api")]
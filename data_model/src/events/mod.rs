@@ -2,6 +2,7 @@
 
 #[cfg(not(feature = "std"))]
 use alloc::{format, string::String, vec::Vec};
+use core::num::NonZeroU64;
 
 use iroha_data_model_derive::model;
 use iroha_macro::FromVariant;
@@ -20,6 +21,8 @@ pub mod trigger_completed;
 
 #[model]
 mod model {
+    use getset::{CopyGetters, Getters};
+
     use super::*;
 
     #[allow(missing_docs)]
@@ -51,6 +54,32 @@ mod model {
         TriggerCompleted(trigger_completed::TriggerCompletedEvent),
     }
 
+    /// Event archived by the peer along with the height of the block which emitted it.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct ArchivedEvent {
+        /// Height of the block which emitted the event.
+        #[getset(get_copy = "pub")]
+        pub height: NonZeroU64,
+        /// The event itself.
+        #[getset(get = "pub")]
+        pub event: EventBox,
+    }
+
     /// Event type which could invoke trigger execution.
     #[derive(
         Debug, Clone, Copy, PartialEq, Eq, Decode, Encode, IntoSchema, Serialize, Deserialize,
@@ -98,6 +127,27 @@ mod model {
     }
 }
 
+impl EventFilterBox {
+    /// Whether the filter only matches events which don't disclose anything
+    /// about entities outside of `domain`.
+    ///
+    /// Block and time events carry no domain data, so their filters are always scoped.
+    /// Transaction events may reveal why someone else's transaction was rejected,
+    /// so only the events of a known transaction are allowed.
+    pub fn is_scoped_to_domain(&self, domain: &crate::domain::DomainId) -> bool {
+        match self {
+            Self::Pipeline(pipeline::PipelineEventFilterBox::Transaction(filter)) => {
+                filter.hash.is_some()
+            }
+            Self::Pipeline(pipeline::PipelineEventFilterBox::Block(_)) | Self::Time(_) => true,
+            Self::Data(filter) => filter.is_scoped_to_domain(domain),
+            Self::ExecuteTrigger(filter) => filter.is_scoped_to_domain(domain),
+            // Triggers don't belong to any domain
+            Self::TriggerCompleted(_) => false,
+        }
+    }
+}
+
 impl From<TransactionEvent> for EventBox {
     fn from(source: TransactionEvent) -> Self {
         Self::Pipeline(source.into())
@@ -255,11 +305,13 @@ pub mod stream {
     //! Structures related to event streaming over HTTP
 
     use derive_more::Constructor;
+    use iroha_crypto::{SignatureOf, Unverified, Verified};
     use iroha_data_model_derive::model;
     use iroha_version::prelude::*;
 
     pub use self::model::*;
     use super::*;
+    use crate::account::AccountId;
 
     #[model]
     mod model {
@@ -271,11 +323,48 @@ pub mod stream {
         #[repr(transparent)]
         pub struct EventMessage(pub EventBox);
 
-        /// Message sent by the stream consumer.
         /// Request sent by the client to subscribe to events.
         #[derive(Debug, Clone, Constructor, Decode, Encode, Deserialize, Serialize, IntoSchema)]
-        #[repr(transparent)]
-        pub struct EventSubscriptionRequest(pub Vec<EventFilterBox>);
+        pub struct EventSubscriptionRequest {
+            /// Account subscribing to the events.
+            /// Unless permitted otherwise, it only receives events of its own domain.
+            pub authority: AccountId,
+            /// Events matching any of the filters are sent to the subscriber
+            pub filters: Vec<EventFilterBox>,
+        }
+
+        /// Message sent by the stream consumer.
+        /// [`EventSubscriptionRequest`] signed by its authority.
+        #[derive(Debug, Clone, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+        pub struct SignedEventSubscriptionRequest {
+            /// Signature of the authority
+            pub signature: Unverified<SignatureOf<EventSubscriptionRequest>>,
+            /// Signed request
+            pub payload: EventSubscriptionRequest,
+        }
+    }
+
+    impl EventSubscriptionRequest {
+        /// Sign the request with the key pair of its authority
+        #[must_use]
+        pub fn sign(self, key_pair: &iroha_crypto::KeyPair) -> SignedEventSubscriptionRequest {
+            SignedEventSubscriptionRequest {
+                signature: Verified::sign(key_pair.private_key(), &self).into(),
+                payload: self,
+            }
+        }
+    }
+
+    impl SignedEventSubscriptionRequest {
+        /// Verify that the request is signed by its authority
+        ///
+        /// # Errors
+        /// If the signature doesn't match the authority
+        pub fn verify(&self) -> Result<(), iroha_crypto::Error> {
+            self.signature
+                .verify(self.payload.authority.signatory(), &self.payload)
+                .map(drop)
+        }
     }
 
     impl From<EventMessage> for EventBox {
@@ -285,14 +374,126 @@ pub mod stream {
     }
 }
 
+#[cfg(feature = "http")]
+pub mod webhook {
+    //! Structures related to the webhook notifications about events
+
+    use derive_more::Constructor;
+    use iroha_crypto::{SignatureOf, Unverified, Verified};
+    use iroha_data_model_derive::model;
+
+    pub use self::model::*;
+    use super::*;
+    use crate::account::AccountId;
+
+    /// Identifier of a webhook, unique within the lifetime of the peer
+    pub type WebhookId = u64;
+
+    #[model]
+    mod model {
+        use super::*;
+
+        /// Webhook posting the events matching any of the filters to the URL
+        #[derive(Debug, Clone, Constructor, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+        pub struct WebhookRegistration {
+            /// Events matching any of the filters are posted to the webhook
+            pub filters: Vec<EventFilterBox>,
+            /// HTTP(S) URL the events are posted to
+            pub url: String,
+            /// Key of the HMAC-SHA256 signature attached to every notification
+            pub secret: String,
+        }
+
+        /// Action on a webhook of the authority
+        #[derive(Debug, Clone, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+        pub enum WebhookAction {
+            /// Register a new webhook
+            Register(WebhookRegistration),
+            /// Get the delivery status of the webhook
+            Status(WebhookId),
+            /// Stop posting events to the webhook
+            Unregister(WebhookId),
+        }
+
+        /// Request sent by the client to manage its webhooks.
+        #[derive(Debug, Clone, Constructor, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+        pub struct WebhookRequest {
+            /// Account owning the webhook.
+            /// Unless permitted otherwise, it is only notified of the events of its own domain.
+            pub authority: AccountId,
+            /// Action on the webhook
+            pub action: WebhookAction,
+        }
+
+        /// [`WebhookRequest`] signed by its authority.
+        #[derive(Debug, Clone, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+        pub struct SignedWebhookRequest {
+            /// Signature of the authority
+            pub signature: Unverified<SignatureOf<WebhookRequest>>,
+            /// Signed request
+            pub payload: WebhookRequest,
+        }
+
+        /// Delivery status of a webhook, sent in response to a [`WebhookRequest`]
+        #[derive(
+            Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema,
+        )]
+        pub struct WebhookStatus {
+            /// Identifier of the webhook
+            pub id: WebhookId,
+            /// URL the events are posted to
+            pub url: String,
+            /// Number of events delivered to the webhook
+            pub delivered: u64,
+            /// Number of events dropped after all delivery attempts failed
+            pub dropped: u64,
+            /// Number of events waiting to be delivered
+            pub pending: u64,
+            /// Error of the latest failed delivery attempt
+            pub last_error: Option<String>,
+        }
+    }
+
+    impl WebhookRequest {
+        /// Sign the request with the key pair of its authority
+        #[must_use]
+        pub fn sign(self, key_pair: &iroha_crypto::KeyPair) -> SignedWebhookRequest {
+            SignedWebhookRequest {
+                signature: Verified::sign(key_pair.private_key(), &self).into(),
+                payload: self,
+            }
+        }
+    }
+
+    impl SignedWebhookRequest {
+        /// Verify that the request is signed by its authority
+        ///
+        /// # Errors
+        /// If the signature doesn't match the authority
+        pub fn verify(&self) -> Result<(), iroha_crypto::Error> {
+            self.signature
+                .verify(self.payload.authority.signatory(), &self.payload)
+                .map(drop)
+        }
+    }
+}
+
 /// Exports common structs and enums from this module.
 pub mod prelude {
     #[cfg(feature = "http")]
-    pub use super::stream::{EventMessage, EventSubscriptionRequest};
+    pub use super::stream::{
+        EventMessage, EventSubscriptionRequest, SignedEventSubscriptionRequest,
+    };
+    #[cfg(feature = "http")]
+    pub use super::webhook::{
+        SignedWebhookRequest, WebhookAction, WebhookId, WebhookRegistration, WebhookRequest,
+        WebhookStatus,
+    };
     #[cfg(feature = "transparent_api")]
     pub use super::EventFilter;
     pub use super::{
         data::prelude::*, execute_trigger::prelude::*, pipeline::prelude::*, time::prelude::*,
-        trigger_completed::prelude::*, EventBox, EventFilterBox, TriggeringEventType,
+        trigger_completed::prelude::*, ArchivedEvent, EventBox, EventFilterBox,
+        TriggeringEventType,
     };
 }
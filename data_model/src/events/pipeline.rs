@@ -13,7 +13,9 @@ use serde::{Deserialize, Serialize};
 
 pub use self::model::*;
 use crate::{
+    app::AppId,
     block::{BlockHeader, SignedBlock},
+    metadata::Metadata,
     transaction::SignedTransaction,
 };
 
@@ -88,6 +90,9 @@ mod model {
         pub block_height: Option<NonZeroU64>,
         #[getset(get = "pub")]
         pub status: TransactionStatus,
+        /// Client metadata of the transaction, not interpreted by the execution
+        #[getset(get = "pub")]
+        pub metadata: Metadata,
     }
 
     /// Report of block's status in the pipeline
@@ -141,6 +146,8 @@ mod model {
         Approved,
         /// Transaction was stored in the block as invalid
         Rejected(Box<crate::transaction::error::TransactionRejectionReason>),
+        /// Transaction was withdrawn from the queue by its authority
+        Cancelled,
     }
 
     #[derive(
@@ -209,6 +216,24 @@ mod model {
         pub block_height: Option<Option<NonZeroU64>>,
         #[getset(get = "pub")]
         pub status: Option<TransactionStatus>,
+        #[getset(get = "pub")]
+        pub app_id: Option<AppId>,
+    }
+}
+
+impl TransactionEvent {
+    /// App named in the metadata of the transaction.
+    ///
+    /// The app is verified when the transaction is validated, so for queued
+    /// transactions this is only the claim of the client. [`None`] for transactions
+    /// rejected before the app was verified.
+    pub fn app_id(&self) -> Option<AppId> {
+        if let TransactionStatus::Rejected(reason) = &self.status {
+            if reason.precedes_app_verification() {
+                return None;
+            }
+        }
+        AppId::from_metadata(&self.metadata).and_then(Result::ok)
     }
 }
 
@@ -245,6 +270,7 @@ impl TransactionEventFilter {
             hash: None,
             block_height: None,
             status: None,
+            app_id: None,
         }
     }
 
@@ -269,6 +295,13 @@ impl TransactionEventFilter {
         self
     }
 
+    /// Match only transactions attributed to the given app
+    #[must_use]
+    pub fn for_app(mut self, app_id: AppId) -> Self {
+        self.app_id = Some(app_id);
+        self
+    }
+
     /// Block height
     // TODO: Derive with getset
     pub fn block_height(&self) -> Option<Option<NonZeroU64>> {
@@ -323,6 +356,9 @@ impl super::EventFilter for PipelineEventFilterBox {
                     transaction_filter.status.as_ref(),
                     &transaction_event.status,
                 ),
+                transaction_filter.app_id.as_ref().map_or(true, |app_id| {
+                    transaction_event.app_id().as_ref() == Some(app_id)
+                }),
             ]
             .into_iter()
             .all(core::convert::identity),
@@ -362,6 +398,7 @@ mod tests {
                 creation_time_ms: 0,
                 view_change_index: 0,
                 consensus_estimation_ms: 0,
+                protocol_version: crate::block::GENESIS_PROTOCOL_VERSION,
             }
         }
     }
@@ -372,12 +409,14 @@ mod tests {
             TransactionEvent {
                 hash: HashOf::from_untyped_unchecked(Hash::prehashed([0_u8; Hash::LENGTH])),
                 block_height: None,
+                metadata: Metadata::default(),
                 status: TransactionStatus::Queued,
             }
             .into(),
             TransactionEvent {
                 hash: HashOf::from_untyped_unchecked(Hash::prehashed([0_u8; Hash::LENGTH])),
                 block_height: Some(nonzero!(3_u64)),
+                metadata: Metadata::default(),
                 status: TransactionStatus::Rejected(Box::new(Validation(
                     ValidationFail::TooComplex,
                 ))),
@@ -386,6 +425,7 @@ mod tests {
             TransactionEvent {
                 hash: HashOf::from_untyped_unchecked(Hash::prehashed([2_u8; Hash::LENGTH])),
                 block_height: None,
+                metadata: Metadata::default(),
                 status: TransactionStatus::Approved,
             }
             .into(),
@@ -415,12 +455,14 @@ mod tests {
                 TransactionEvent {
                     hash: HashOf::from_untyped_unchecked(Hash::prehashed([0_u8; Hash::LENGTH])),
                     block_height: None,
+                    metadata: Metadata::default(),
                     status: TransactionStatus::Queued,
                 }
                 .into(),
                 TransactionEvent {
                     hash: HashOf::from_untyped_unchecked(Hash::prehashed([0_u8; Hash::LENGTH])),
                     block_height: Some(nonzero!(3_u64)),
+                    metadata: Metadata::default(),
                     status: TransactionStatus::Rejected(Box::new(Validation(
                         ValidationFail::TooComplex,
                     ))),
@@ -462,9 +504,34 @@ mod tests {
             vec![TransactionEvent {
                 hash: HashOf::from_untyped_unchecked(Hash::prehashed([2_u8; Hash::LENGTH])),
                 block_height: None,
+                metadata: Metadata::default(),
                 status: TransactionStatus::Approved,
             }
             .into()],
         );
     }
+
+    #[test]
+    fn events_are_filtered_by_app() {
+        let event = |app_id: Option<&str>| -> PipelineEventBox {
+            let mut metadata = Metadata::default();
+            if let Some(app_id) = app_id {
+                metadata.insert(crate::app::APP_ID_METADATA_KEY.parse().unwrap(), app_id);
+            }
+            TransactionEvent {
+                hash: HashOf::from_untyped_unchecked(Hash::prehashed([0_u8; Hash::LENGTH])),
+                block_height: None,
+                metadata,
+                status: TransactionStatus::Approved,
+            }
+            .into()
+        };
+        let filter: PipelineEventFilterBox = TransactionEventFilter::default()
+            .for_app("wallet".parse().unwrap())
+            .into();
+
+        assert!(filter.matches(&event(Some("wallet"))));
+        assert!(!filter.matches(&event(Some("exchange"))));
+        assert!(!filter.matches(&event(None)));
+    }
 }
@@ -4,7 +4,7 @@
 use alloc::{format, string::String, vec::Vec};
 
 use derive_more::Constructor;
-use getset::Getters;
+use getset::{CopyGetters, Getters};
 use iroha_data_model_derive::model;
 use iroha_macro::FromVariant;
 use iroha_schema::IntoSchema;
@@ -74,6 +74,36 @@ mod model {
         Failure(String),
     }
 
+    /// Record of a trigger executed while applying a block
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Constructor,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct TriggerReceipt {
+        /// Id of the executed trigger
+        #[getset(get = "pub")]
+        trigger_id: TriggerId,
+        /// Fuel consumed by the trigger, always `0` for triggers made of instructions
+        #[getset(get_copy = "pub")]
+        fuel_consumed: u64,
+        /// Outcome of the execution
+        #[getset(get = "pub")]
+        outcome: TriggerCompletedOutcome,
+    }
+
     /// Filter [`TriggerCompletedEvent`] by
     /// 1. if `triger_id` is some filter based on trigger id
     /// 2. if `outcome_type` is some filter based on execution outcome (success/failure)
@@ -161,7 +191,7 @@ impl super::EventFilter for TriggerCompletedEventFilter {
 pub mod prelude {
     pub use super::{
         TriggerCompletedEvent, TriggerCompletedEventFilter, TriggerCompletedOutcome,
-        TriggerCompletedOutcomeType,
+        TriggerCompletedOutcomeType, TriggerReceipt,
     };
 }
 
@@ -117,6 +117,46 @@ mod model {
         Upgrade(Upgrade),
         #[debug(fmt = "{_0:?}")]
         Log(Log),
+        #[debug(fmt = "{_0:?}")]
+        LockEscrow(LockEscrow),
+        #[debug(fmt = "{_0:?}")]
+        ClaimEscrow(ClaimEscrow),
+        #[debug(fmt = "{_0:?}")]
+        RefundEscrow(RefundEscrow),
+        #[debug(fmt = "{_0:?}")]
+        TransferAll(TransferAll),
+        #[debug(fmt = "{_0:?}")]
+        BurnAll(BurnAll),
+        #[debug(fmt = "{_0:?}")]
+        Freeze(Freeze),
+        #[debug(fmt = "{_0:?}")]
+        Unfreeze(Unfreeze),
+        #[debug(fmt = "{_0:?}")]
+        DelegateToTrigger(DelegateToTrigger),
+        #[debug(fmt = "{_0:?}")]
+        RevokeTriggerDelegation(RevokeTriggerDelegation),
+        #[debug(fmt = "{_0:?}")]
+        RemoveKeyRange(RemoveKeyRange),
+        #[debug(fmt = "{_0:?}")]
+        RegisterPeerCertificate(RegisterPeerCertificate),
+        #[debug(fmt = "{_0:?}")]
+        SetRecoveryPolicy(SetRecoveryPolicy),
+        #[debug(fmt = "{_0:?}")]
+        InitiateRecovery(InitiateRecovery),
+        #[debug(fmt = "{_0:?}")]
+        VetoRecovery(VetoRecovery),
+        #[debug(fmt = "{_0:?}")]
+        FinalizeRecovery(FinalizeRecovery),
+        #[debug(fmt = "{_0:?}")]
+        TransferMultiple(TransferMultiple),
+        #[debug(fmt = "{_0:?}")]
+        RegisterApp(RegisterApp),
+        #[debug(fmt = "{_0:?}")]
+        UnregisterApp(UnregisterApp),
+        #[debug(fmt = "{_0:?}")]
+        GrantProxy(GrantProxy),
+        #[debug(fmt = "{_0:?}")]
+        RevokeProxy(RevokeProxy),
 
         #[debug(fmt = "{_0:?}")]
         Custom(CustomInstruction),
@@ -178,6 +218,26 @@ impl_instruction! {
     Upgrade,
     ExecuteTrigger,
     Log,
+    LockEscrow,
+    ClaimEscrow,
+    RefundEscrow,
+    TransferAll,
+    BurnAll,
+    Freeze,
+    Unfreeze,
+    DelegateToTrigger,
+    RevokeTriggerDelegation,
+    RemoveKeyRange,
+    RegisterPeerCertificate,
+    SetRecoveryPolicy,
+    InitiateRecovery,
+    VetoRecovery,
+    FinalizeRecovery,
+    TransferMultiple,
+    RegisterApp,
+    UnregisterApp,
+    GrantProxy,
+    RevokeProxy,
 }
 
 impl Instruction for InstructionBox {}
@@ -189,6 +249,9 @@ impl BuiltInInstruction for InstructionBox {
 }
 
 mod transparent {
+    use core::time::Duration;
+
+    use iroha_crypto::Hash;
     use iroha_primitives::json::JsonString;
 
     use super::*;
@@ -252,6 +315,9 @@ mod transparent {
 
     iroha_data_model_derive::model_single! {
         /// Generic instruction for setting a chain-wide config parameter.
+        ///
+        /// The new value applies from the next block on, so all transactions
+        /// of a block are validated with the same parameters.
         #[derive(Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Constructor)]
         #[derive(parity_scale_codec::Decode, parity_scale_codec::Encode)]
         #[derive(serde::Deserialize, serde::Serialize)]
@@ -977,12 +1043,384 @@ mod transparent {
         }
     }
 
+    isi! {
+        /// Instruction to lock an amount of an asset in an [`Escrow`] until it is either
+        /// claimed by the recipient or refunded to the owner after the timeout.
+        #[derive(Display)]
+        #[display(fmt = "LOCK `{amount}` FROM `{source}` IN ESCROW `{escrow}` FOR `{recipient}`")]
+        pub struct LockEscrow {
+            /// Id of the escrow to create.
+            pub escrow: EscrowId,
+            /// Asset to take the locked amount from.
+            pub source: AssetId,
+            /// Amount to lock.
+            pub amount: Numeric,
+            /// Account which is allowed to claim the funds.
+            pub recipient: AccountId,
+            /// Time (in ms) after which the funds can no longer be claimed and can be refunded.
+            pub timeout_ms: u64,
+            /// Hash of the preimage the recipient has to reveal to claim the funds.
+            pub hash_lock: Option<Hash>,
+        }
+    }
+
+    impl LockEscrow {
+        /// Constructs a new [`LockEscrow`] of the `amount` taken from the `source` asset,
+        /// claimable by the `recipient` until the `timeout` elapses.
+        pub fn new(
+            escrow: EscrowId,
+            source: AssetId,
+            amount: Numeric,
+            recipient: AccountId,
+            timeout: Duration,
+        ) -> Self {
+            Self {
+                escrow,
+                source,
+                amount,
+                recipient,
+                timeout_ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+                hash_lock: None,
+            }
+        }
+
+        /// Require the recipient to reveal the preimage of `hash_lock` to claim the funds
+        #[must_use]
+        pub fn with_hash_lock(mut self, hash_lock: Hash) -> Self {
+            self.hash_lock = Some(hash_lock);
+            self
+        }
+    }
+
+    isi! {
+        /// Instruction to transfer the funds locked in an [`Escrow`] to its recipient.
+        #[derive(Display)]
+        #[display(fmt = "CLAIM ESCROW `{escrow}`")]
+        pub struct ClaimEscrow {
+            /// Id of the escrow to claim.
+            pub escrow: EscrowId,
+            /// Preimage of the hash lock of the escrow, if it has one.
+            pub preimage: Option<Vec<u8>>,
+        }
+    }
+
+    impl ClaimEscrow {
+        /// Constructs a new [`ClaimEscrow`] for an escrow without the hash lock.
+        pub fn new(escrow: EscrowId) -> Self {
+            Self {
+                escrow,
+                preimage: None,
+            }
+        }
+
+        /// Reveal the preimage of the hash lock of the escrow
+        #[must_use]
+        pub fn with_preimage(mut self, preimage: impl Into<Vec<u8>>) -> Self {
+            self.preimage = Some(preimage.into());
+            self
+        }
+    }
+
+    isi! {
+        /// Instruction to return the funds locked in an expired [`Escrow`] to its owner.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REFUND ESCROW `{escrow}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct RefundEscrow {
+            /// Id of the escrow to refund.
+            pub escrow: EscrowId,
+        }
+    }
+
+    isi! {
+        /// Instruction to transfer the whole balance of an asset from one account to another.
+        ///
+        /// The amount is taken from the balance at the time of execution,
+        /// so concurrent changes to the balance can't leave a remainder.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "TRANSFER ALL `{asset_definition}` FROM `{from}` TO `{to}`")]
+        pub struct TransferAll {
+            /// Definition of the numeric asset to transfer.
+            pub asset_definition: AssetDefinitionId,
+            /// Account to take the whole balance from.
+            pub from: AccountId,
+            /// Account to give the balance to.
+            pub to: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to transfer several numeric assets from one account at once,
+        /// e.g. to pay the salaries of many accounts in one go.
+        ///
+        /// Either all movements are executed or none, and permissions are checked
+        /// once for the whole batch rather than for every movement.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "TRANSFER {} MOVEMENTS FROM `{from}`", "movements.len()")]
+        pub struct TransferMultiple {
+            /// Account to take the assets from.
+            pub from: AccountId,
+            /// Movements executed in order.
+            pub movements: Vec<AssetMovement>,
+        }
+    }
+
+    isi! {
+        /// Single movement of a [`TransferMultiple`].
+        #[derive(Constructor, Display)]
+        #[display(fmt = "`{amount}` OF `{asset_definition}` TO `{to}`")]
+        pub struct AssetMovement {
+            /// Definition of the numeric asset to transfer.
+            pub asset_definition: AssetDefinitionId,
+            /// Amount of the asset to transfer.
+            pub amount: Numeric,
+            /// Account to give the asset to.
+            pub to: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to burn the whole balance of an asset held by an account.
+        ///
+        /// The amount is taken from the balance at the time of execution,
+        /// so concurrent changes to the balance can't leave a remainder.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "BURN ALL `{asset_definition}` FROM `{from}`")]
+        pub struct BurnAll {
+            /// Definition of the numeric asset to burn.
+            pub asset_definition: AssetDefinitionId,
+            /// Account to burn the whole balance from.
+            pub from: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to freeze an account.
+        ///
+        /// Transactions authored by a frozen account are rejected until it is unfrozen.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "FREEZE `{account}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct Freeze {
+            /// Account to freeze.
+            pub account: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to unfreeze a previously frozen account.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "UNFREEZE `{account}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct Unfreeze {
+            /// Account to unfreeze.
+            pub account: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to let a trigger execute some kinds of instructions on behalf of the authority.
+        ///
+        /// The delegated instructions of the trigger are validated as if they were submitted
+        /// by the authority. Delegating again replaces the kinds of delegated instructions.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "DELEGATE `{instructions:?}` TO `{trigger}`")]
+        pub struct DelegateToTrigger {
+            /// Trigger acting on behalf of the authority.
+            pub trigger: TriggerId,
+            /// Kinds of instructions the trigger is allowed to execute on behalf of the authority.
+            pub instructions: Vec<InstructionType>,
+        }
+    }
+
+    isi! {
+        /// Instruction to revoke a delegation previously given by the authority to a trigger.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REVOKE DELEGATION TO `{trigger}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct RevokeTriggerDelegation {
+            /// Trigger which no longer acts on behalf of the authority.
+            pub trigger: TriggerId,
+        }
+    }
+
+    isi! {
+        /// Instruction to remove every key-value pair with the key within the range
+        /// from a [`Store`](AssetValue::Store) asset.
+        ///
+        /// Succeeds even if there are no such pairs.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REMOVE `{range}` KEYS FROM `{asset}`")]
+        pub struct RemoveKeyRange {
+            /// Store asset to remove the pairs from.
+            pub asset: AssetId,
+            /// Range of the keys of the pairs to remove.
+            pub range: KeyRange,
+        }
+    }
+
+    isi! {
+        /// Instruction to register the [`PeerCertificate`] of a registered peer,
+        /// replacing the previously registered one.
+        ///
+        /// Has to be submitted by the operator named in the certificate.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REGISTER CERTIFICATE `{certificate}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct RegisterPeerCertificate {
+            /// Certificate signed by the peer.
+            pub certificate: PeerCertificate,
+        }
+    }
+
+    isi! {
+        /// Instruction to designate the guardians which can recover an account,
+        /// replacing the previous [`RecoveryPolicy`] and cancelling the pending recovery.
+        ///
+        /// Recovery is disabled if no guardians are given.
+        #[derive(Display)]
+        #[display(fmt = "SET RECOVERY POLICY OF `{account}`")]
+        pub struct SetRecoveryPolicy {
+            /// Account to protect.
+            pub account: AccountId,
+            /// Accounts which can initiate and finalize the recovery.
+            pub guardians: Vec<AccountId>,
+            /// Time (in ms) during which the owner can veto an initiated recovery.
+            pub delay_ms: u64,
+        }
+    }
+
+    impl SetRecoveryPolicy {
+        /// Constructs a new [`SetRecoveryPolicy`] letting the `guardians` recover the `account`
+        /// unless the owner vetoes the recovery within the `delay`.
+        pub fn new(
+            account: AccountId,
+            guardians: impl IntoIterator<Item = AccountId>,
+            delay: Duration,
+        ) -> Self {
+            Self {
+                account,
+                guardians: guardians.into_iter().collect(),
+                delay_ms: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+            }
+        }
+    }
+
+    isi! {
+        /// Instruction to start moving an account to a new signatory.
+        ///
+        /// Has to be submitted by one of the guardians of the account.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "INITIATE RECOVERY OF `{account}` TO `{new_signatory}`")]
+        pub struct InitiateRecovery {
+            /// Account to recover.
+            pub account: AccountId,
+            /// Signatory the account is moved to.
+            pub new_signatory: PublicKey,
+        }
+    }
+
+    isi! {
+        /// Instruction to cancel the pending recovery of an account.
+        ///
+        /// Has to be submitted by the account itself before the recovery is finalized.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "VETO RECOVERY OF `{account}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct VetoRecovery {
+            /// Account whose recovery is cancelled.
+            pub account: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to move an account to the signatory of its pending recovery
+        /// once the challenge window has passed.
+        ///
+        /// Has to be submitted by one of the guardians of the account. Assets, permissions,
+        /// roles and other entities of the account are moved to the new account id.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "FINALIZE RECOVERY OF `{account}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct FinalizeRecovery {
+            /// Account to recover.
+            pub account: AccountId,
+        }
+    }
+
+    isi! {
+        /// Instruction to register a client application, to which transactions
+        /// signed with its key can be attributed.
+        ///
+        /// The authority of the transaction becomes the owner of the app.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REGISTER APP `{app}` WITH KEY `{public_key}`")]
+        pub struct RegisterApp {
+            /// Id of the app to register.
+            pub app: AppId,
+            /// Key with which the transactions attributed to the app are signed.
+            pub public_key: PublicKey,
+        }
+    }
+
+    isi! {
+        /// Instruction to unregister a client application.
+        ///
+        /// Transactions already attributed to the app keep their attribution.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "UNREGISTER APP `{app}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct UnregisterApp {
+            /// App to unregister.
+            pub app: AppId,
+        }
+    }
+
+    isi! {
+        /// Instruction to let the proxy submit transactions with some kinds of instructions
+        /// on behalf of the authority, signing them with its own key.
+        ///
+        /// The instructions are validated as if they were signed by the authority.
+        /// Granting again replaces the [`ProxyToken`] previously granted to the proxy.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "GRANT PROXY `{proxy}` FOR `{instructions:?}`")]
+        pub struct GrantProxy {
+            /// Account submitting transactions on behalf of the authority.
+            pub proxy: AccountId,
+            /// Kinds of instructions the proxy can submit on behalf of the authority.
+            pub instructions: Vec<InstructionType>,
+            /// Time since the Unix epoch (in ms) starting from which the token is no longer valid.
+            pub expires_at_ms: Option<u64>,
+        }
+    }
+
+    isi! {
+        /// Instruction to revoke a [`ProxyToken`] previously granted by the authority.
+        #[derive(Constructor, Display)]
+        #[display(fmt = "REVOKE PROXY `{proxy}`")]
+        #[serde(transparent)]
+        #[repr(transparent)]
+        pub struct RevokeProxy {
+            /// Account which no longer submits transactions on behalf of the authority.
+            pub proxy: AccountId,
+        }
+    }
+
     isi! {
         /// Blockchain specific instruction (defined in the executor).
         /// Can be used to extend instruction set or add expression system.
         ///
         /// Note: If using custom instructions remember to set (during the executor migration)
-        /// [`ExecutorDataModel::instructions`]
+        /// [`ExecutorDataModel::instructions`]. The executor has to interpret them itself:
+        /// the host refuses to execute custom instructions.
         ///
         /// # Examples
         ///
@@ -1296,6 +1734,10 @@ pub mod error {
                 #[skip_try_from]
                 String,
             ),
+            /// Escrow conditions aren't met
+            Escrow(#[cfg_attr(feature = "std", source)] EscrowError),
+            /// Account recovery conditions aren't met
+            Recovery(#[cfg_attr(feature = "std", source)] RecoveryError),
         }
 
         /// Evaluation error. This error indicates instruction is not a valid Iroha DSL
@@ -1469,6 +1911,78 @@ pub mod error {
             Wasm(String),
             /// Attempt to register a time-trigger with `start` point in the past
             TimeTriggerInThePast,
+            /// Invalid peer certificate: {0}
+            PeerCertificate(String),
+            /// Invalid protocol upgrade: {0}
+            ProtocolUpgrade(String),
+            /// Invalid name: {0}
+            Name(String),
+        }
+
+        /// Escrow error, which occurs when the funds are released not according to the escrow conditions
+        #[derive(
+            Debug,
+            displaydoc::Display,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Deserialize,
+            Serialize,
+            Decode,
+            Encode,
+            IntoSchema,
+        )]
+        #[cfg_attr(feature = "std", derive(thiserror::Error))]
+        #[ffi_type]
+        #[repr(u8)]
+        pub enum EscrowError {
+            /// Escrow can only be claimed by its recipient
+            NotRecipient,
+            /// Escrow can only be refunded by its owner
+            NotOwner,
+            /// Escrow has expired and can no longer be claimed
+            Expired,
+            /// Escrow hasn't expired yet and can't be refunded
+            NotExpired,
+            /// Preimage doesn't match the hash lock of the escrow
+            InvalidPreimage,
+        }
+
+        /// Recovery error, which occurs when an account is recovered not according to its recovery policy
+        #[derive(
+            Debug,
+            displaydoc::Display,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Deserialize,
+            Serialize,
+            Decode,
+            Encode,
+            IntoSchema,
+        )]
+        #[cfg_attr(feature = "std", derive(thiserror::Error))]
+        #[ffi_type]
+        #[repr(u8)]
+        pub enum RecoveryError {
+            /// Account doesn't have any guardians
+            NoGuardians,
+            /// Recovery can only be initiated and finalized by a guardian of the account
+            NotGuardian,
+            /// Recovery can only be vetoed by the account being recovered
+            NotOwner,
+            /// Account is already being recovered
+            AlreadyInitiated,
+            /// Account isn't being recovered
+            NotInitiated,
+            /// Challenge window of the recovery hasn't passed yet
+            ChallengeWindowOpen,
         }
 
         /// Repetition of of `{instruction}` for id `{id}`
@@ -1506,9 +2020,13 @@ pub mod error {
 /// The prelude re-exports most commonly used traits, structs and macros from this crate.
 pub mod prelude {
     pub use super::{
-        AssetTransferBox, Burn, BurnBox, CustomInstruction, ExecuteTrigger, Grant, GrantBox,
-        InstructionBox, Log, Mint, MintBox, Register, RegisterBox, RemoveKeyValue,
-        RemoveKeyValueBox, Revoke, RevokeBox, SetKeyValue, SetKeyValueBox, SetParameter, Transfer,
-        TransferBox, Unregister, UnregisterBox, Upgrade,
+        AssetMovement, AssetTransferBox, Burn, BurnAll, BurnBox, ClaimEscrow, CustomInstruction,
+        DelegateToTrigger, ExecuteTrigger, FinalizeRecovery, Freeze, Grant, GrantBox, GrantProxy,
+        InitiateRecovery, InstructionBox, LockEscrow, Log, Mint, MintBox, RefundEscrow, Register,
+        RegisterApp, RegisterBox, RegisterPeerCertificate, RemoveKeyRange, RemoveKeyValue,
+        RemoveKeyValueBox, Revoke, RevokeBox, RevokeProxy, RevokeTriggerDelegation, SetKeyValue,
+        SetKeyValueBox, SetParameter, SetRecoveryPolicy, Transfer, TransferAll, TransferBox,
+        TransferMultiple, Unfreeze, Unregister, UnregisterApp, UnregisterBox, Upgrade,
+        VetoRecovery,
     };
 }
@@ -24,9 +24,11 @@ pub use self::model::*;
 use crate::name::Name;
 
 pub mod account;
+pub mod app;
 pub mod asset;
 pub mod block;
 pub mod domain;
+pub mod escrow;
 pub mod events;
 pub mod executor;
 pub mod ipfs;
@@ -36,7 +38,9 @@ pub mod name;
 pub mod parameter;
 pub mod peer;
 pub mod permission;
+pub mod proxy;
 pub mod query;
+pub mod recovery;
 pub mod role;
 pub mod smart_contract;
 pub mod transaction;
@@ -111,6 +115,26 @@ mod seal {
         Upgrade,
         ExecuteTrigger,
         Log,
+        LockEscrow,
+        ClaimEscrow,
+        RefundEscrow,
+        TransferAll,
+        BurnAll,
+        Freeze,
+        Unfreeze,
+        DelegateToTrigger,
+        RevokeTriggerDelegation,
+        RemoveKeyRange,
+        RegisterPeerCertificate,
+        SetRecoveryPolicy,
+        InitiateRecovery,
+        VetoRecovery,
+        FinalizeRecovery,
+        TransferMultiple,
+        RegisterApp,
+        UnregisterApp,
+        GrantProxy,
+        RevokeProxy,
 
         // Boxed queries
         SingularQueryBox,
@@ -122,6 +146,7 @@ mod seal {
         FindAssetQuantityById,
         FindTotalAssetQuantityByAssetDefinitionId,
         FindAssetMetadata,
+        FindStoreEntries,
         FindAssetDefinitionMetadata,
         FindDomains,
         FindDomainMetadata,
@@ -129,14 +154,22 @@ mod seal {
         FindBlocks,
         FindBlockHeaders,
         FindBlockHeaderByHash,
+        FindBlockStateDiff,
+        FindBlockRewards,
+        FindEvents,
         FindTransactions,
         FindTransactionsByAccountId,
+        FindTransactionsByAppId,
         FindTransactionByHash,
+        FindIdempotencyRecord,
         FindPermissionsByAccountId,
         FindExecutorDataModel,
         FindActiveTriggerIds,
+        FindAllActiveTriggers,
+        FindTriggersByDomain,
         FindTriggerById,
         FindTriggerMetadata,
+        FindTriggerReceipts,
         FindRoles,
         FindRoleIds,
         FindRolesByAccountId,
@@ -194,6 +227,8 @@ impl<EXPECTED: core::fmt::Debug, GOT: core::fmt::Debug> std::error::Error
 #[model]
 #[allow(clippy::redundant_pub_crate)]
 mod model {
+    use getset::Getters;
+
     use super::*;
 
     /// Unique id of blockchain
@@ -266,6 +301,10 @@ mod model {
         Permission(permission::Permission),
         /// [`CustomParameter`](`parameter::CustomParameter`) variant.
         CustomParameterId(parameter::CustomParameterId),
+        /// [`EscrowId`](`escrow::EscrowId`) variant.
+        EscrowId(escrow::EscrowId),
+        /// [`AppId`](`app::AppId`) variant.
+        AppId(app::AppId),
     }
 
     /// Operation validation failed.
@@ -327,6 +366,59 @@ mod model {
             #[skip_try_from]
             String,
         ),
+        /// Operation is not permitted by the policy of {0}
+        DomainDenied(DomainDenial),
+    }
+
+    /// Denial of an operation by the policy of one of the domains it touches
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "domain `{domain}`: {reason}")]
+    #[getset(get = "pub")]
+    #[ffi_type]
+    pub struct DomainDenial {
+        /// Domain whose policy denied the operation
+        pub domain: domain::DomainId,
+        /// Why the operation was denied
+        pub reason: DomainDenialReason,
+    }
+
+    /// Reason a domain policy denied an operation
+    #[derive(
+        Debug,
+        displaydoc::Display,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    #[repr(u8)]
+    pub enum DomainDenialReason {
+        /// only accounts of the domain are admitted
+        MembersOnly,
+        /// policy of the domain is malformed
+        MalformedPolicy,
     }
 
     /// Log level for reading from environment and (de)serializing
@@ -485,10 +577,11 @@ pub mod prelude {
     };
 
     pub use super::{
-        account::prelude::*, asset::prelude::*, domain::prelude::*, events::prelude::*,
-        executor::prelude::*, isi::prelude::*, metadata::prelude::*, name::prelude::*,
-        parameter::prelude::*, peer::prelude::*, permission::prelude::*, query::prelude::*,
-        role::prelude::*, transaction::prelude::*, trigger::prelude::*, ChainId, EnumTryAsError,
-        HasMetadata, IdBox, Identifiable, ValidationFail,
+        account::prelude::*, app::prelude::*, asset::prelude::*, domain::prelude::*,
+        escrow::prelude::*, events::prelude::*, executor::prelude::*, isi::prelude::*,
+        metadata::prelude::*, name::prelude::*, parameter::prelude::*, peer::prelude::*,
+        permission::prelude::*, proxy::prelude::*, query::prelude::*, recovery::prelude::*,
+        role::prelude::*, transaction::prelude::*, trigger::prelude::*, ChainId, DomainDenial,
+        DomainDenialReason, EnumTryAsError, HasMetadata, IdBox, Identifiable, ValidationFail,
     };
 }
@@ -2,7 +2,7 @@
 
 #[cfg(not(feature = "std"))]
 use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
-use core::borrow::Borrow;
+use core::{borrow::Borrow, fmt, ops::Bound};
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 
@@ -47,6 +47,31 @@ mod model {
     #[display(fmt = "Metadata")]
     #[allow(clippy::multiple_inherent_impl)]
     pub struct Metadata(pub(super) BTreeMap<Name, JsonString>);
+
+    /// Range of [`Metadata`] keys in their lexicographic order.
+    #[derive(
+        Debug,
+        Clone,
+        Default,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Deserialize,
+        Serialize,
+        Decode,
+        Encode,
+        IntoSchema,
+    )]
+    #[ffi_type(opaque)]
+    pub struct KeyRange {
+        /// Prefix every key in the range starts with.
+        pub prefix: String,
+        /// First key of the range, unbounded if not set.
+        pub start: Option<Name>,
+        /// Key right after the last key of the range, unbounded if not set.
+        pub end: Option<Name>,
+    }
 }
 
 impl Metadata {
@@ -74,6 +99,92 @@ impl Metadata {
     pub fn insert(&mut self, key: Name, value: impl Into<JsonString>) -> Option<JsonString> {
         self.0.insert(key, value.into())
     }
+
+    /// Iterate over key/value pairs with the keys within `range` in the order of the keys.
+    pub fn range<'a>(
+        &'a self,
+        range: &'a KeyRange,
+    ) -> impl Iterator<Item = (&'a Name, &'a JsonString)> + 'a {
+        let start = match &range.start {
+            Some(start) if start.as_ref() > range.prefix.as_str() => {
+                Bound::Included(start.as_ref())
+            }
+            _ => Bound::Included(range.prefix.as_str()),
+        };
+
+        self.0
+            .range::<str, _>((start, Bound::Unbounded))
+            .take_while(|(key, _)| {
+                range.precedes_end(key) && key.as_ref().starts_with(&range.prefix)
+            })
+    }
+}
+
+impl KeyRange {
+    /// Range of all keys
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Range of the keys starting with `prefix`
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Range of the keys from `start` inclusive to `end` exclusive
+    pub fn between(start: Name, end: Name) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
+            ..Self::default()
+        }
+    }
+
+    /// Constrain the range to start from `start` inclusive
+    #[must_use]
+    pub fn with_start(mut self, start: Name) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Constrain the range to end before `end`
+    #[must_use]
+    pub fn with_end(mut self, end: Name) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Check if `key` is within the range
+    pub fn contains(&self, key: &Name) -> bool {
+        key.as_ref().starts_with(&self.prefix)
+            && self.start.as_ref().map_or(true, |start| key >= start)
+            && self.precedes_end(key)
+    }
+
+    fn precedes_end(&self, key: &Name) -> bool {
+        self.end.as_ref().map_or(true, |end| key < end)
+    }
+}
+
+impl fmt::Display for KeyRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}*", self.prefix)?;
+        if self.start.is_none() && self.end.is_none() {
+            return Ok(());
+        }
+        f.write_str(" [")?;
+        if let Some(start) = &self.start {
+            write!(f, "{start}")?;
+        }
+        f.write_str("..")?;
+        if let Some(end) = &self.end {
+            write!(f, "{end}")?;
+        }
+        f.write_str(")")
+    }
 }
 
 #[cfg(feature = "transparent_api")]
@@ -88,9 +199,81 @@ impl Metadata {
     {
         self.0.remove(key)
     }
+
+    /// Remove the keys within `range`, returning the removed key/value pairs in the order of the keys.
+    pub fn remove_range(&mut self, range: &KeyRange) -> Vec<(Name, JsonString)> {
+        let keys = self
+            .range(range)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        keys.into_iter()
+            .filter_map(|key| self.0.remove(&key).map(|value| (key, value)))
+            .collect()
+    }
 }
 
 pub mod prelude {
     //! Prelude: re-export most commonly used traits, structs and macros from this module.
-    pub use super::Metadata;
+    pub use super::{KeyRange, Metadata};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(keys: &[&str]) -> Metadata {
+        let mut metadata = Metadata::default();
+        for key in keys {
+            metadata.insert(key.parse().unwrap(), *key);
+        }
+        metadata
+    }
+
+    fn keys_in(metadata: &Metadata, range: &KeyRange) -> Vec<String> {
+        metadata
+            .range(range)
+            .map(|(key, _)| key.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn range_is_ordered_and_bounded() {
+        let metadata = metadata(&["user_c", "item_a", "user_a", "user_b", "userz", "zeta"]);
+
+        assert_eq!(
+            keys_in(&metadata, &KeyRange::prefix("user_")),
+            ["user_a", "user_b", "user_c"]
+        );
+        assert_eq!(
+            keys_in(
+                &metadata,
+                &KeyRange::prefix("user").with_start("user_b".parse().unwrap())
+            ),
+            ["user_b", "user_c", "userz"]
+        );
+        assert_eq!(
+            keys_in(
+                &metadata,
+                &KeyRange::between("item_b".parse().unwrap(), "user_c".parse().unwrap())
+            ),
+            ["user_a", "user_b"]
+        );
+        assert_eq!(keys_in(&metadata, &KeyRange::all()).len(), 6);
+        assert!(keys_in(&metadata, &KeyRange::prefix("nothing")).is_empty());
+    }
+
+    #[test]
+    fn range_matches_contains() {
+        let metadata = metadata(&["a", "ab", "abc", "b", "ba"]);
+        let range = KeyRange::prefix("a").with_end("abc".parse().unwrap());
+
+        let expected = metadata
+            .iter()
+            .filter(|(key, _)| range.contains(key))
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(keys_in(&metadata, &range), expected);
+        assert_eq!(expected, ["a", "ab"]);
+    }
 }
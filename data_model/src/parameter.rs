@@ -10,7 +10,10 @@ use iroha_primitives::json::JsonString;
 use nonzero_ext::nonzero;
 
 pub use self::model::*;
-use crate::{name::Name, Identifiable};
+use crate::{
+    account::AccountId, block::GENESIS_PROTOCOL_VERSION, name::Name,
+    transaction::TransactionVersion, Identifiable, ParseError,
+};
 
 /// Collection of [`CustomParameter`]s
 pub(crate) type CustomParameters = btree_map::BTreeMap<CustomParameterId, CustomParameter>;
@@ -115,7 +118,7 @@ mod model {
         Serialize,
         IntoSchema,
     )]
-    #[display(fmt = "{max_transactions}_BL")]
+    #[display(fmt = "{max_transactions},{max_size_bytes},{protocol_upgrade}_BL")]
     #[getset(get_copy = "pub")]
     pub struct BlockParameters {
         /// Maximal number of transactions in a block.
@@ -123,6 +126,44 @@ mod model {
         /// A block is created if this limit is reached or [`SumeragiParameters::block_time_ms`] has expired,
         /// whichever comes first. Regardless of the limits, an empty block is never created.
         pub max_transactions: NonZeroU64,
+        /// Maximal total size (in bytes) of the encoded transactions in a block.
+        ///
+        /// Transactions which don't fit are left for the next block.
+        pub max_size_bytes: NonZeroU64,
+        /// Upgrade of the protocol version of the blocks, see [`ProtocolUpgrade`]
+        pub protocol_upgrade: ProtocolUpgrade,
+    }
+
+    /// Scheduled change of the protocol version recorded in the block headers.
+    ///
+    /// Blocks below [`Self::activation_height`] are of [`Self::prior_version`], and blocks
+    /// starting from it are of [`Self::version`]. An upgrade can only be scheduled for a future
+    /// block and must start from the version the chain is currently at.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{prior_version}->{version}@{activation_height}")]
+    #[getset(get_copy = "pub")]
+    pub struct ProtocolUpgrade {
+        /// Protocol version of the blocks below [`Self::activation_height`]
+        pub prior_version: u32,
+        /// Protocol version of the blocks starting from [`Self::activation_height`]
+        pub version: u32,
+        /// Height of the first block of [`Self::version`]
+        pub activation_height: NonZeroU64,
     }
 
     /// Single block parameter
@@ -133,6 +174,8 @@ mod model {
     )]
     pub enum BlockParameter {
         MaxTransactions(NonZeroU64),
+        MaxSizeBytes(NonZeroU64),
+        ProtocolUpgrade(ProtocolUpgrade),
     }
 
     /// Limits that a transaction must obey to be accepted.
@@ -150,13 +193,24 @@ mod model {
         Serialize,
         IntoSchema,
     )]
-    #[display(fmt = "{max_instructions},{smart_contract_size}_TL")]
+    #[display(
+        fmt = "{max_instructions},{smart_contract_size},{idempotency_window_ms},{max_metadata_bytes},{trigger_wasm_size},{v1_deprecation_height}_TL"
+    )]
     #[getset(get_copy = "pub")]
     pub struct TransactionParameters {
         /// Maximum number of instructions per transaction
         pub max_instructions: NonZeroU64,
         /// Maximum size of wasm binary in bytes
         pub smart_contract_size: NonZeroU64,
+        /// Time (in ms) during which the idempotency key of a committed transaction
+        /// can't be used by other transactions of the same authority
+        pub idempotency_window_ms: NonZeroU64,
+        /// Maximum size of the encoded transaction metadata in bytes
+        pub max_metadata_bytes: NonZeroU64,
+        /// Maximum size of wasm binary of a registered trigger in bytes
+        pub trigger_wasm_size: NonZeroU64,
+        /// Height of the first block which can't contain transactions of version 1
+        pub v1_deprecation_height: NonZeroU64,
     }
 
     /// Single transaction parameter
@@ -168,6 +222,10 @@ mod model {
     pub enum TransactionParameter {
         MaxInstructions(NonZeroU64),
         SmartContractSize(NonZeroU64),
+        IdempotencyWindowMs(NonZeroU64),
+        MaxMetadataBytes(NonZeroU64),
+        TriggerWasmSize(NonZeroU64),
+        V1DeprecationHeight(NonZeroU64),
     }
 
     /// Limits that a smart contract must obey at runtime to considered valid.
@@ -205,6 +263,195 @@ mod model {
         Memory(NonZeroU64),
     }
 
+    /// Distribution of the transaction fees among the peers.
+    ///
+    /// Fees are whatever the executor transfers to the [`Self::collector`] account while executing
+    /// a block. At the end of the block every numeric asset held by the collector is paid out
+    /// according to [`Self::distribution`].
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub struct FeeParameters {
+        /// Account collecting the fees, fees aren't distributed if [`None`]
+        #[getset(get = "pub")]
+        pub collector: Option<AccountId>,
+        /// Policy of paying out the collected fees
+        #[getset(get_copy = "pub")]
+        pub distribution: FeeDistribution,
+    }
+
+    /// Policy of paying out the fees collected in a block
+    #[derive(
+        Debug,
+        Display,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum FeeDistribution {
+        /// Burn the fees
+        #[default]
+        Burn,
+        /// Pay the fees to the operator of the leader which created the block
+        Leader,
+        /// Split the fees equally among the operators of the peers which signed the block.
+        ///
+        /// The remainder which can't be split is left to the collector for the next block.
+        SigningPeers,
+    }
+
+    /// Single fee parameter
+    ///
+    /// Check [`FeeParameters`] for more details
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum FeeParameter {
+        Collector(Option<AccountId>),
+        Distribution(FeeDistribution),
+    }
+
+    /// Rules the names of newly registered entities (domains, asset definitions, roles,
+    /// triggers and escrows) have to satisfy.
+    ///
+    /// These rules are checked in addition to the structural rules of [`Name`], which apply to
+    /// every name regardless of the parameters. Names registered before the rules were changed
+    /// stay valid.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{max_len},{charset}_NL")]
+    #[getset(get_copy = "pub")]
+    pub struct NameParameters {
+        /// Maximum length of a name in characters
+        pub max_len: NonZeroU64,
+        /// Characters allowed in names
+        pub charset: NameCharset,
+    }
+
+    /// Set of characters allowed in names
+    #[derive(
+        Debug,
+        Display,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum NameCharset {
+        /// Any Unicode character allowed by [`Name`]
+        #[default]
+        Unicode,
+        /// Printable ASCII characters
+        Ascii,
+        /// ASCII letters, digits, `_`, `-` and `.`
+        AsciiAlphanumeric,
+    }
+
+    /// Single name parameter
+    ///
+    /// Check [`NameParameters`] for more details
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum NameParameter {
+        MaxLen(NonZeroU64),
+        Charset(NameCharset),
+    }
+
+    /// WASM engine the smart contracts and the executor are run with.
+    ///
+    /// Engines charge different amounts of fuel for the same code and the fuel consumed is
+    /// a part of the chain state, so every peer has to run the engine recorded in the parameters.
+    /// Peers running another engine refuse to validate the blocks of the network.
+    #[derive(
+        Debug,
+        Display,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum WasmEngine {
+        /// Modules are compiled to native code with `wasmtime`
+        #[default]
+        Wasmtime,
+        /// Modules are interpreted with `wasmi`
+        Wasmi,
+    }
+
     /// Blockchain specific parameter defined in the executor
     #[derive(
         Debug, Display, Clone, IdEqOrdHash, Decode, Encode, Deserialize, Serialize, IntoSchema,
@@ -254,6 +501,15 @@ mod model {
         /// Smart contract parameters
         #[getset(get_copy = "pub")]
         pub smart_contract: SmartContractParameters,
+        /// Fee parameters
+        #[getset(get = "pub")]
+        pub fee: FeeParameters,
+        /// Name parameters
+        #[getset(get_copy = "pub")]
+        pub name: NameParameters,
+        /// WASM engine of the network
+        #[getset(get_copy = "pub")]
+        pub wasm_engine: WasmEngine,
         /// Collection of blockchain specific parameters
         #[getset(get = "pub")]
         pub custom: CustomParameters,
@@ -284,6 +540,9 @@ mod model {
         SmartContract(SmartContractParameter),
         Executor(SmartContractParameter),
         Custom(CustomParameter),
+        Fee(FeeParameter),
+        Name(NameParameter),
+        WasmEngine(WasmEngine),
     }
 }
 
@@ -294,6 +553,9 @@ impl core::fmt::Display for Parameter {
             Self::Block(v) => core::fmt::Display::fmt(&v, f),
             Self::Transaction(v) => core::fmt::Display::fmt(&v, f),
             Self::SmartContract(v) | Self::Executor(v) => core::fmt::Display::fmt(&v, f),
+            Self::Fee(v) => core::fmt::Display::fmt(&v, f),
+            Self::Name(v) => core::fmt::Display::fmt(&v, f),
+            Self::WasmEngine(v) => write!(f, "WasmEngine({v})"),
             Self::Custom(v) => write!(f, "{}({})", v.id, v.payload),
         }
     }
@@ -343,8 +605,16 @@ impl Default for BlockParameters {
     fn default() -> Self {
         /// Default value for [`Parameters::MaxTransactionsInBlock`]
         pub const DEFAULT_TRANSACTIONS_IN_BLOCK: NonZeroU64 = nonzero!(2_u64.pow(9));
+        /// Default value for [`BlockParameters::max_size_bytes`]
+        pub const DEFAULT_BLOCK_SIZE: NonZeroU64 = nonzero!(16 * 2_u64.pow(20));
 
-        Self::new(DEFAULT_TRANSACTIONS_IN_BLOCK)
+        Self::new(DEFAULT_TRANSACTIONS_IN_BLOCK, DEFAULT_BLOCK_SIZE)
+    }
+}
+
+impl Default for ProtocolUpgrade {
+    fn default() -> Self {
+        Self::NONE
     }
 }
 
@@ -352,8 +622,20 @@ impl Default for TransactionParameters {
     fn default() -> Self {
         const DEFAULT_INSTRUCTION_NUMBER: NonZeroU64 = nonzero!(2_u64.pow(12));
         const DEFAULT_SMART_CONTRACT_SIZE: NonZeroU64 = nonzero!(4 * 2_u64.pow(20));
-
-        Self::new(DEFAULT_INSTRUCTION_NUMBER, DEFAULT_SMART_CONTRACT_SIZE)
+        const DEFAULT_IDEMPOTENCY_WINDOW: NonZeroU64 = nonzero!(24 * 60 * 60 * 1000_u64);
+        const DEFAULT_METADATA_SIZE: NonZeroU64 = nonzero!(2_u64.pow(16));
+        const DEFAULT_TRIGGER_WASM_SIZE: NonZeroU64 = nonzero!(4 * 2_u64.pow(20));
+        // No deprecation is scheduled
+        const DEFAULT_V1_DEPRECATION_HEIGHT: NonZeroU64 = NonZeroU64::MAX;
+
+        Self::new(
+            DEFAULT_INSTRUCTION_NUMBER,
+            DEFAULT_SMART_CONTRACT_SIZE,
+            DEFAULT_IDEMPOTENCY_WINDOW,
+            DEFAULT_METADATA_SIZE,
+            DEFAULT_TRIGGER_WASM_SIZE,
+            DEFAULT_V1_DEPRECATION_HEIGHT,
+        )
     }
 }
 
@@ -383,6 +665,9 @@ impl Parameters {
                     .parameters()
                     .map(Parameter::SmartContract),
             )
+            .chain(self.fee.parameters().map(Parameter::Fee))
+            .chain(self.name.parameters().map(Parameter::Name))
+            .chain(core::iter::once(Parameter::WasmEngine(self.wasm_engine)))
             .chain(self.custom.values().cloned().map(Parameter::Custom))
     }
 }
@@ -414,22 +699,77 @@ impl SumeragiParameters {
 
 impl BlockParameters {
     /// Construct [`Self`]
-    pub const fn new(max_transactions: NonZeroU64) -> Self {
-        Self { max_transactions }
+    pub const fn new(max_transactions: NonZeroU64, max_size_bytes: NonZeroU64) -> Self {
+        Self {
+            max_transactions,
+            max_size_bytes,
+            protocol_upgrade: ProtocolUpgrade::NONE,
+        }
     }
 
     /// Convert [`Self`] into iterator of individual parameters
     pub fn parameters(&self) -> impl Iterator<Item = BlockParameter> {
-        [BlockParameter::MaxTransactions(self.max_transactions)].into_iter()
+        [
+            BlockParameter::MaxTransactions(self.max_transactions),
+            BlockParameter::MaxSizeBytes(self.max_size_bytes),
+            BlockParameter::ProtocolUpgrade(self.protocol_upgrade),
+        ]
+        .into_iter()
+    }
+}
+
+impl ProtocolUpgrade {
+    /// No upgrade is scheduled, every block is of [`GENESIS_PROTOCOL_VERSION`]
+    pub const NONE: Self = Self {
+        prior_version: GENESIS_PROTOCOL_VERSION,
+        version: GENESIS_PROTOCOL_VERSION,
+        activation_height: NonZeroU64::MIN,
+    };
+
+    /// Construct [`Self`]
+    pub const fn new(prior_version: u32, version: u32, activation_height: NonZeroU64) -> Self {
+        Self {
+            prior_version,
+            version,
+            activation_height,
+        }
+    }
+
+    /// Protocol version of the block at `height`
+    pub const fn version_at(&self, height: u64) -> u32 {
+        if height < self.activation_height.get() {
+            self.prior_version
+        } else {
+            self.version
+        }
     }
 }
 
 impl TransactionParameters {
     /// Construct [`Self`]
-    pub const fn new(max_instructions: NonZeroU64, smart_contract_size: NonZeroU64) -> Self {
+    pub const fn new(
+        max_instructions: NonZeroU64,
+        smart_contract_size: NonZeroU64,
+        idempotency_window_ms: NonZeroU64,
+        max_metadata_bytes: NonZeroU64,
+        trigger_wasm_size: NonZeroU64,
+        v1_deprecation_height: NonZeroU64,
+    ) -> Self {
         Self {
             max_instructions,
             smart_contract_size,
+            idempotency_window_ms,
+            max_metadata_bytes,
+            trigger_wasm_size,
+            v1_deprecation_height,
+        }
+    }
+
+    /// Whether transactions of `version` can be included in the block at `height`
+    pub fn accepts_version(&self, version: TransactionVersion, height: u64) -> bool {
+        match version {
+            TransactionVersion::V1 => height < self.v1_deprecation_height.get(),
+            TransactionVersion::V2 => true,
         }
     }
 
@@ -438,6 +778,10 @@ impl TransactionParameters {
         [
             TransactionParameter::MaxInstructions(self.max_instructions),
             TransactionParameter::SmartContractSize(self.smart_contract_size),
+            TransactionParameter::IdempotencyWindowMs(self.idempotency_window_ms),
+            TransactionParameter::MaxMetadataBytes(self.max_metadata_bytes),
+            TransactionParameter::TriggerWasmSize(self.trigger_wasm_size),
+            TransactionParameter::V1DeprecationHeight(self.v1_deprecation_height),
         ]
         .into_iter()
     }
@@ -454,6 +798,90 @@ impl SmartContractParameters {
     }
 }
 
+impl FeeParameters {
+    /// Convert [`Self`] into iterator of individual parameters
+    pub fn parameters(&self) -> impl Iterator<Item = FeeParameter> {
+        [
+            FeeParameter::Collector(self.collector.clone()),
+            FeeParameter::Distribution(self.distribution),
+        ]
+        .into_iter()
+    }
+}
+
+impl core::fmt::Display for FeeParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Collector(Some(collector)) => write!(f, "Collector({collector})"),
+            Self::Collector(None) => write!(f, "Collector(None)"),
+            Self::Distribution(distribution) => write!(f, "Distribution({distribution})"),
+        }
+    }
+}
+
+impl Default for NameParameters {
+    fn default() -> Self {
+        const DEFAULT_MAX_LEN: NonZeroU64 = nonzero!(256_u64);
+
+        Self {
+            max_len: DEFAULT_MAX_LEN,
+            charset: NameCharset::default(),
+        }
+    }
+}
+
+impl NameParameters {
+    /// Check whether `name` satisfies the rules
+    ///
+    /// # Errors
+    /// - If the name is longer than [`Self::max_len`]
+    /// - If the name contains characters outside of [`Self::charset`]
+    pub fn validate(&self, name: &Name) -> Result<(), ParseError> {
+        let name = name.as_ref();
+
+        if name.chars().count() as u64 > self.max_len.get() {
+            return Err(ParseError {
+                reason: "`Name` exceeds the length limit configured by `NameParameter::MaxLen`",
+            });
+        }
+        if !name.chars().all(|ch| self.charset.contains(ch)) {
+            return Err(ParseError {
+                reason: "`Name` contains characters not allowed by `NameParameter::Charset`",
+            });
+        }
+        Ok(())
+    }
+
+    /// Convert [`Self`] into iterator of individual parameters
+    pub fn parameters(&self) -> impl Iterator<Item = NameParameter> {
+        [
+            NameParameter::MaxLen(self.max_len),
+            NameParameter::Charset(self.charset),
+        ]
+        .into_iter()
+    }
+}
+
+impl NameCharset {
+    /// Check whether `ch` belongs to the charset
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            Self::Unicode => true,
+            Self::Ascii => ch.is_ascii_graphic(),
+            Self::AsciiAlphanumeric => ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'),
+        }
+    }
+}
+
+impl core::fmt::Display for NameParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MaxLen(max_len) => write!(f, "MaxLen({max_len})"),
+            Self::Charset(charset) => write!(f, "Charset({charset})"),
+        }
+    }
+}
+
 impl CustomParameterId {
     /// Getter for name
     pub fn name(&self) -> &Name {
@@ -489,22 +917,34 @@ mod candidate {
     enum TransactionParameterCandidate {
         MaxInstructions(NonZeroU64),
         SmartContractSize(NonZeroU64),
+        IdempotencyWindowMs(NonZeroU64),
+        MaxMetadataBytes(NonZeroU64),
+        TriggerWasmSize(NonZeroU64),
+        V1DeprecationHeight(NonZeroU64),
     }
 
     #[derive(Decode, Deserialize)]
     struct TransactionParametersCandidate {
         max_instructions: NonZeroU64,
         smart_contract_size: NonZeroU64,
+        idempotency_window_ms: NonZeroU64,
+        max_metadata_bytes: NonZeroU64,
+        trigger_wasm_size: NonZeroU64,
+        v1_deprecation_height: NonZeroU64,
     }
 
     #[derive(Decode, Deserialize)]
     enum BlockParameterCandidate {
         MaxTransactions(NonZeroU64),
+        MaxSizeBytes(NonZeroU64),
+        ProtocolUpgrade(ProtocolUpgrade),
     }
 
     #[derive(Decode, Deserialize)]
     struct BlockParametersCandidate {
         max_transactions: NonZeroU64,
+        max_size_bytes: NonZeroU64,
+        protocol_upgrade: ProtocolUpgrade,
     }
 
     #[derive(Decode, Deserialize)]
@@ -528,6 +968,15 @@ mod candidate {
 
                     BlockParameter::MaxTransactions(max_transactions)
                 }
+                Self::MaxSizeBytes(max_size_bytes) => {
+                    let _ = NonZeroUsize::try_from(max_size_bytes)
+                        .map_err(|_| "BlockParameter::MaxSizeBytes exceeds usize::MAX")?;
+
+                    BlockParameter::MaxSizeBytes(max_size_bytes)
+                }
+                Self::ProtocolUpgrade(protocol_upgrade) => {
+                    BlockParameter::ProtocolUpgrade(protocol_upgrade)
+                }
             })
         }
     }
@@ -537,8 +986,13 @@ mod candidate {
             let _ = NonZeroUsize::try_from(self.max_transactions)
                 .map_err(|_| "BlockParameters::max_transactions exceeds usize::MAX")?;
 
+            let _ = NonZeroUsize::try_from(self.max_size_bytes)
+                .map_err(|_| "BlockParameters::max_size_bytes exceeds usize::MAX")?;
+
             Ok(BlockParameters {
                 max_transactions: self.max_transactions,
+                max_size_bytes: self.max_size_bytes,
+                protocol_upgrade: self.protocol_upgrade,
             })
         }
     }
@@ -557,6 +1011,22 @@ mod candidate {
                     })?;
                     TransactionParameter::SmartContractSize(smart_contract_size)
                 }
+                Self::IdempotencyWindowMs(idempotency_window_ms) => {
+                    TransactionParameter::IdempotencyWindowMs(idempotency_window_ms)
+                }
+                Self::MaxMetadataBytes(max_metadata_bytes) => {
+                    let _ = NonZeroUsize::try_from(max_metadata_bytes)
+                        .map_err(|_| "TransactionParameter::MaxMetadataBytes exceeds usize::MAX")?;
+                    TransactionParameter::MaxMetadataBytes(max_metadata_bytes)
+                }
+                Self::TriggerWasmSize(trigger_wasm_size) => {
+                    let _ = NonZeroUsize::try_from(trigger_wasm_size)
+                        .map_err(|_| "TransactionParameter::TriggerWasmSize exceeds usize::MAX")?;
+                    TransactionParameter::TriggerWasmSize(trigger_wasm_size)
+                }
+                Self::V1DeprecationHeight(v1_deprecation_height) => {
+                    TransactionParameter::V1DeprecationHeight(v1_deprecation_height)
+                }
             })
         }
     }
@@ -569,9 +1039,19 @@ mod candidate {
             let _ = NonZeroUsize::try_from(self.smart_contract_size)
                 .map_err(|_| "TransactionParameters::smart_contract_size exceeds usize::MAX")?;
 
+            let _ = NonZeroUsize::try_from(self.max_metadata_bytes)
+                .map_err(|_| "TransactionParameters::max_metadata_bytes exceeds usize::MAX")?;
+
+            let _ = NonZeroUsize::try_from(self.trigger_wasm_size)
+                .map_err(|_| "TransactionParameters::trigger_wasm_size exceeds usize::MAX")?;
+
             Ok(TransactionParameters {
                 max_instructions: self.max_instructions,
                 smart_contract_size: self.smart_contract_size,
+                idempotency_window_ms: self.idempotency_window_ms,
+                max_metadata_bytes: self.max_metadata_bytes,
+                trigger_wasm_size: self.trigger_wasm_size,
+                v1_deprecation_height: self.v1_deprecation_height,
             })
         }
     }
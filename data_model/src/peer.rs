@@ -9,11 +9,12 @@ use core::{
 };
 
 use derive_more::Display;
+use iroha_crypto::{PrivateKey, SignatureOf, Unverified, Verified};
 use iroha_data_model_derive::model;
 use iroha_primitives::addr::SocketAddr;
 
 pub use self::model::*;
-use crate::{Identifiable, PublicKey, Registered};
+use crate::{account::AccountId, metadata::Metadata, Identifiable, PublicKey, Registered};
 
 #[model]
 mod model {
@@ -55,6 +56,61 @@ mod model {
         /// Peer Identification.
         pub id: PeerId,
     }
+
+    /// Statement of the peer operator about the peer, signed by the peer.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+        Getters,
+    )]
+    #[getset(get = "pub")]
+    #[ffi_type]
+    pub struct PeerCertificatePayload {
+        /// Peer the certificate is issued for. Its key is the key the peer uses in the network.
+        pub peer: PeerId,
+        /// Account of the operator running the peer.
+        pub operator: AccountId,
+        /// Contact and ownership information published by the operator.
+        pub metadata: Metadata,
+    }
+
+    /// Certificate binding the network identity of a peer to its operator.
+    ///
+    /// It's signed with the key of the peer, and can only be registered by the operator,
+    /// so both of them vouch for it.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+        Getters,
+    )]
+    #[display(fmt = "{} operated by {}", "payload.peer", "payload.operator")]
+    #[getset(get = "pub")]
+    #[ffi_type]
+    pub struct PeerCertificate {
+        /// Signed statement.
+        pub payload: PeerCertificatePayload,
+        /// Signature of the peer over the payload.
+        pub signature: Unverified<SignatureOf<PeerCertificatePayload>>,
+    }
 }
 
 impl PeerId {
@@ -76,6 +132,36 @@ impl Peer {
     }
 }
 
+impl PeerCertificate {
+    /// Issue a certificate of the peer signed with its `private_key`.
+    pub fn new(payload: PeerCertificatePayload, private_key: &PrivateKey) -> Self {
+        let signature = Verified::sign(private_key, &payload).into();
+        Self { payload, signature }
+    }
+
+    /// Peer the certificate is issued for
+    #[inline]
+    pub fn peer(&self) -> &PeerId {
+        &self.payload.peer
+    }
+
+    /// Account of the peer operator
+    #[inline]
+    pub fn operator(&self) -> &AccountId {
+        &self.payload.operator
+    }
+
+    /// Verify the certificate is signed by the peer it's issued for.
+    ///
+    /// # Errors
+    /// If the signature isn't made with the key of the peer
+    pub fn verify(&self) -> Result<(), iroha_crypto::Error> {
+        self.signature
+            .verify(&self.payload.peer.public_key, &self.payload)
+            .map(drop)
+    }
+}
+
 impl PartialEq for PeerId {
     fn eq(&self, other: &Self) -> bool {
         // Comparison is done by public key only.
@@ -115,5 +201,5 @@ impl Registered for Peer {
 
 /// The prelude re-exports most commonly used traits, structs and macros from this crate.
 pub mod prelude {
-    pub use super::{Peer, PeerId};
+    pub use super::{Peer, PeerCertificate, PeerCertificatePayload, PeerId};
 }
@@ -0,0 +1,78 @@
+//! Structures, traits and impls related to `ProxyToken`s.
+//!
+//! An account grants a proxy token to another account, e.g. a custodial service, which can
+//! then submit transactions on behalf of the granting account signed with its own key.
+//! Such transactions name the proxy as their [`submitter`](crate::transaction::SignedTransaction::submitter)
+//! and are accepted only if every instruction is of a kind covered by the token.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use iroha_data_model_derive::model;
+
+pub use self::model::*;
+use crate::{account::AccountId, isi::InstructionType};
+
+#[model]
+mod model {
+    use derive_more::Display;
+    use getset::{CopyGetters, Getters};
+    use iroha_schema::IntoSchema;
+    use parity_scale_codec::{Decode, Encode};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// Permission of the proxy to submit transactions on behalf of the account.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{proxy} ON BEHALF OF {account}")]
+    #[ffi_type]
+    pub struct ProxyToken {
+        /// Account which granted the token and on behalf of which the transactions are submitted.
+        #[getset(get = "pub")]
+        pub account: AccountId,
+        /// Account which submits the transactions and signs them with its key.
+        #[getset(get = "pub")]
+        pub proxy: AccountId,
+        /// Kinds of instructions the proxy can submit on behalf of the account.
+        #[getset(get = "pub")]
+        pub instructions: Vec<InstructionType>,
+        /// Time since the Unix epoch (in ms) starting from which the token is no longer valid,
+        /// [`None`] if the token is valid until revoked.
+        #[getset(get_copy = "pub")]
+        pub expires_at_ms: Option<u64>,
+    }
+}
+
+impl ProxyToken {
+    /// Whether the token is valid at `now_ms` since the Unix epoch
+    pub fn is_valid_at(&self, now_ms: u64) -> bool {
+        self.expires_at_ms
+            .map_or(true, |expires_at_ms| now_ms < expires_at_ms)
+    }
+
+    /// Whether instructions of the given kind can be submitted with the token
+    pub fn covers(&self, instruction: InstructionType) -> bool {
+        self.instructions.contains(&instruction)
+    }
+}
+
+/// The prelude re-exports most commonly used traits, structs and macros from this module.
+pub mod prelude {
+    pub use super::ProxyToken;
+}
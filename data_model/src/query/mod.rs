@@ -6,8 +6,8 @@
 use alloc::{boxed::Box, format, string::String, vec::Vec};
 
 use derive_more::Constructor;
-use iroha_crypto::{PublicKey, SignatureOf};
-use iroha_data_model_derive::model;
+use iroha_crypto::{PublicKey, SignatureOf, Unverified, Verified};
+use iroha_data_model_derive::{model, ValidatedDecode};
 use iroha_macro::FromVariant;
 use iroha_primitives::{json::JsonString, numeric::Numeric};
 use iroha_schema::IntoSchema;
@@ -18,21 +18,22 @@ use serde::{Deserialize, Serialize};
 
 pub use self::model::*;
 use self::{
-    account::*, asset::*, block::*, domain::*, executor::*, peer::*, permission::*, predicate::*,
-    role::*, transaction::*, trigger::*,
+    account::*, asset::*, block::*, domain::*, event::*, executor::*, peer::*, permission::*,
+    predicate::*, role::*, transaction::*, trigger::*,
 };
 use crate::{
     account::{Account, AccountId},
-    asset::{Asset, AssetDefinition},
-    block::{BlockHeader, SignedBlock},
+    asset::{Asset, AssetDefinition, StoreEntry},
+    block::{reward::BlockReward, state_diff::BlockStateDiff, BlockHeader, SignedBlock},
     domain::Domain,
+    events::{trigger_completed::TriggerReceipt, ArchivedEvent},
     parameter::{Parameter, Parameters},
-    peer::Peer,
+    peer::{Peer, PeerCertificate},
     permission::Permission,
     role::{Role, RoleId},
     seal::Sealed,
-    transaction::{CommittedTransaction, SignedTransaction},
-    trigger::TriggerId,
+    transaction::{CommittedTransaction, IdempotencyRecord, SignedTransaction},
+    trigger::{TriggerDetails, TriggerId},
 };
 
 pub mod builder;
@@ -104,6 +105,11 @@ mod model {
         FindTransactions(QueryWithFilterFor<FindTransactions>),
         FindBlocks(QueryWithFilterFor<FindBlocks>),
         FindBlockHeaders(QueryWithFilterFor<FindBlockHeaders>),
+        FindEvents(QueryWithFilterFor<FindEvents>),
+        FindStoreEntries(QueryWithFilterFor<FindStoreEntries>),
+        FindTransactionsByAppId(QueryWithFilterFor<FindTransactionsByAppId>),
+        FindAllActiveTriggers(QueryWithFilterFor<FindAllActiveTriggers>),
+        FindTriggersByDomain(QueryWithFilterFor<FindTriggersByDomain>),
     }
 
     /// An enum of all possible iterable query batches.
@@ -126,6 +132,9 @@ mod model {
         TriggerId(Vec<TriggerId>),
         Block(Vec<SignedBlock>),
         BlockHeader(Vec<BlockHeader>),
+        ArchivedEvent(Vec<ArchivedEvent>),
+        StoreEntry(Vec<StoreEntry>),
+        TriggerDetails(Vec<TriggerDetails>),
     }
 
     /// An enum of all possible singular queries
@@ -147,6 +156,11 @@ mod model {
 
         FindTransactionByHash(FindTransactionByHash),
         FindBlockHeaderByHash(FindBlockHeaderByHash),
+        FindBlockStateDiff(FindBlockStateDiff),
+        FindIdempotencyRecord(FindIdempotencyRecord),
+        FindTriggerReceipts(FindTriggerReceipts),
+        FindPeerCertificate(FindPeerCertificate),
+        FindBlockRewards(FindBlockRewards),
     }
 
     /// An enum of all possible singular query outputs
@@ -161,6 +175,11 @@ mod model {
         Parameters(Parameters),
         Transaction(TransactionQueryOutput),
         BlockHeader(BlockHeader),
+        BlockStateDiff(BlockStateDiff),
+        IdempotencyRecord(IdempotencyRecord),
+        TriggerReceipts(Vec<TriggerReceipt>),
+        PeerCertificate(PeerCertificate),
+        BlockRewards(Vec<BlockReward>),
     }
 
     /// The results of a single iterable query request.
@@ -215,12 +234,12 @@ mod model {
 
     /// A signature of [`QueryRequestWithAuthority`] to be used in [`SignedQueryV1`]
     #[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
-    pub struct QuerySignature(pub SignatureOf<QueryRequestWithAuthority>);
+    pub struct QuerySignature(pub Unverified<SignatureOf<QueryRequestWithAuthority>>);
 
     declare_versioned!(SignedQuery 1..2, Debug, Clone, FromVariant, IntoSchema);
 
     /// A signed and authorized query request
-    #[derive(Debug, Clone, Encode, Serialize, IntoSchema)]
+    #[derive(Debug, Clone, Encode, Serialize, ValidatedDecode, IntoSchema)]
     #[version_with_scale(version = 1, versioned_alias = "SignedQuery")]
     pub struct SignedQueryV1 {
         pub signature: QuerySignature,
@@ -279,6 +298,9 @@ impl QueryOutputBatchBox {
             (Self::TriggerId(v1), Self::TriggerId(v2)) => v1.extend(v2),
             (Self::Block(v1), Self::Block(v2)) => v1.extend(v2),
             (Self::BlockHeader(v1), Self::BlockHeader(v2)) => v1.extend(v2),
+            (Self::ArchivedEvent(v1), Self::ArchivedEvent(v2)) => v1.extend(v2),
+            (Self::StoreEntry(v1), Self::StoreEntry(v2)) => v1.extend(v2),
+            (Self::TriggerDetails(v1), Self::TriggerDetails(v2)) => v1.extend(v2),
             _ => panic!("Cannot extend different types of IterableQueryOutputBatchBox"),
         }
     }
@@ -300,6 +322,9 @@ impl QueryOutputBatchBox {
             Self::TriggerId(v) => v.len(),
             Self::Block(v) => v.len(),
             Self::BlockHeader(v) => v.len(),
+            Self::ArchivedEvent(v) => v.len(),
+            Self::StoreEntry(v) => v.len(),
+            Self::TriggerDetails(v) => v.len(),
         }
     }
 }
@@ -338,10 +363,10 @@ impl QueryRequestWithAuthority {
     #[inline]
     #[must_use]
     pub fn sign(self, key_pair: &iroha_crypto::KeyPair) -> SignedQuery {
-        let signature = SignatureOf::new(key_pair.private_key(), &self);
+        let signature = Verified::sign(key_pair.private_key(), &self);
 
         SignedQueryV1 {
-            signature: QuerySignature(signature),
+            signature: QuerySignature(signature.into()),
             payload: self,
         }
         .into()
@@ -363,49 +388,17 @@ impl SignedQuery {
 }
 
 mod candidate {
-    use parity_scale_codec::Input;
-
     use super::*;
 
-    #[derive(Decode, Deserialize)]
-    struct SignedQueryCandidate {
-        signature: QuerySignature,
-        payload: QueryRequestWithAuthority,
-    }
-
-    impl SignedQueryCandidate {
-        fn validate(self) -> Result<SignedQueryV1, &'static str> {
+    impl SignedQueryV1 {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
             let QuerySignature(signature) = &self.signature;
 
             signature
                 .verify(&self.payload.authority.signatory, &self.payload)
                 .map_err(|_| "Query request signature is not valid")?;
 
-            Ok(SignedQueryV1 {
-                payload: self.payload,
-                signature: self.signature,
-            })
-        }
-    }
-
-    impl Decode for SignedQueryV1 {
-        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            SignedQueryCandidate::decode(input)?
-                .validate()
-                .map_err(Into::into)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for SignedQueryV1 {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            use serde::de::Error as _;
-
-            SignedQueryCandidate::deserialize(deserializer)?
-                .validate()
-                .map_err(D::Error::custom)
+            Ok(self)
         }
     }
 
@@ -418,8 +411,7 @@ mod candidate {
         use crate::{
             account::AccountId,
             query::{
-                candidate::SignedQueryCandidate, FindExecutorDataModel, QueryRequest,
-                QuerySignature, SignedQuery, SingularQueryBox,
+                FindExecutorDataModel, QueryRequest, QuerySignature, SignedQuery, SingularQueryBox,
             },
         };
 
@@ -460,35 +452,25 @@ mod candidate {
             .with_authority(ALICE_ID.clone())
             .sign(&ALICE_KEYPAIR);
 
-            let candidate = SignedQueryCandidate {
-                signature: signed_query.signature,
-                payload: signed_query.payload,
-            };
-
-            candidate.validate().unwrap();
+            signed_query.validate().unwrap();
         }
 
         #[test]
         fn invalid_signature() {
-            let SignedQuery::V1(signed_query) = QueryRequest::Singular(
+            let SignedQuery::V1(mut signed_query) = QueryRequest::Singular(
                 SingularQueryBox::FindExecutorDataModel(FindExecutorDataModel),
             )
             .with_authority(ALICE_ID.clone())
             .sign(&ALICE_KEYPAIR);
 
-            let mut candidate = SignedQueryCandidate {
-                signature: signed_query.signature,
-                payload: signed_query.payload,
-            };
-
             // corrupt the signature by changing a single byte in an encoded signature
-            let mut signature_bytes = candidate.signature.encode();
+            let mut signature_bytes = signed_query.signature.encode();
             let idx = signature_bytes.len() - 1;
             signature_bytes[idx] = signature_bytes[idx].wrapping_add(1);
-            candidate.signature = QuerySignature::decode_all(&mut &signature_bytes[..]).unwrap();
+            signed_query.signature = QuerySignature::decode_all(&mut &signature_bytes[..]).unwrap();
 
             assert_eq!(
-                candidate.validate().unwrap_err(),
+                signed_query.validate().unwrap_err(),
                 "Query request signature is not valid"
             );
         }
@@ -502,13 +484,8 @@ mod candidate {
             .with_authority(ALICE_ID.clone())
             .sign(&BOB_KEYPAIR);
 
-            let candidate = SignedQueryCandidate {
-                signature: signed_query.signature,
-                payload: signed_query.payload,
-            };
-
             assert_eq!(
-                candidate.validate().unwrap_err(),
+                signed_query.validate().unwrap_err(),
                 "Query request signature is not valid"
             );
         }
@@ -553,14 +530,19 @@ impl_iter_queries! {
     FindAccounts => crate::account::Account,
     FindAssets => crate::asset::Asset,
     FindAssetsDefinitions => crate::asset::AssetDefinition,
+    FindStoreEntries => crate::asset::StoreEntry,
     FindDomains => crate::domain::Domain,
     FindPeers => crate::peer::Peer,
     FindActiveTriggerIds => crate::trigger::TriggerId,
+    FindAllActiveTriggers => crate::trigger::TriggerDetails,
+    FindTriggersByDomain => crate::trigger::TriggerDetails,
     FindTransactions => TransactionQueryOutput,
     FindTransactionsByAccountId => TransactionQueryOutput,
+    FindTransactionsByAppId => TransactionQueryOutput,
     FindAccountsWithAsset => crate::account::Account,
     FindBlockHeaders => crate::block::BlockHeader,
     FindBlocks => SignedBlock,
+    FindEvents => ArchivedEvent,
 }
 
 impl_singular_queries! {
@@ -573,8 +555,13 @@ impl_singular_queries! {
     FindParameters => crate::parameter::Parameters,
     FindTriggerById => crate::trigger::Trigger,
     FindTriggerMetadata => JsonString,
+    FindTriggerReceipts => Vec<crate::events::trigger_completed::TriggerReceipt>,
     FindTransactionByHash => TransactionQueryOutput,
     FindBlockHeaderByHash => crate::block::BlockHeader,
+    FindBlockStateDiff => crate::block::state_diff::BlockStateDiff,
+    FindBlockRewards => Vec<crate::block::reward::BlockReward>,
+    FindIdempotencyRecord => crate::transaction::IdempotencyRecord,
+    FindPeerCertificate => crate::peer::PeerCertificate,
     FindExecutorDataModel => crate::executor::ExecutorDataModel,
 }
 
@@ -788,6 +775,18 @@ pub mod asset {
             pub key: Name,
         }
 
+        /// [`FindStoreEntries`] Iroha Query lists the key-value pairs stored in the asset
+        /// with the keys within the range, sorted by key in ascending order.
+        #[derive(Display)]
+        #[display(fmt = "Find entries with `{range}` keys in `{id}` asset")]
+        #[ffi_type]
+        pub struct FindStoreEntries {
+            /// `Id` of an [`Asset`] acting as [`Store`](crate::asset::AssetValue::Store).
+            pub id: AssetId,
+            /// Range of the keys of the key-value pairs.
+            pub range: KeyRange,
+        }
+
         /// [`FindAssetDefinitionMetadata`] Iroha Query gets [`AssetDefinitionId`] and key as input and finds [`MetadataValue`]
         /// of the key-value pair stored in this asset definition.
         #[derive(Display)]
@@ -805,7 +804,7 @@ pub mod asset {
     pub mod prelude {
         pub use super::{
             FindAssetDefinitionMetadata, FindAssetMetadata, FindAssetQuantityById, FindAssets,
-            FindAssetsDefinitions, FindTotalAssetQuantityByAssetDefinitionId,
+            FindAssetsDefinitions, FindStoreEntries, FindTotalAssetQuantityByAssetDefinitionId,
         };
     }
 }
@@ -855,6 +854,7 @@ pub mod peer {
     use alloc::{format, string::String, vec::Vec};
 
     use derive_more::Display;
+    use iroha_crypto::PublicKey;
 
     queries! {
         /// [`FindPeers`] Iroha Query finds all trusted [`Peer`]s presented in current Iroha [`Peer`].
@@ -862,11 +862,23 @@ pub mod peer {
         #[display(fmt = "Find all peers")]
         #[ffi_type]
         pub struct FindPeers;
+
+        /// [`FindPeerCertificate`] Iroha Query finds the certificate registered for the peer
+        /// with the given public key.
+        #[derive(Display)]
+        #[display(fmt = "Find certificate of peer with `{public_key}` key")]
+        #[repr(transparent)]
+        // SAFETY: `FindPeerCertificate` has no trap representation in `PublicKey`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindPeerCertificate {
+            /// Public key of the peer.
+            pub public_key: PublicKey,
+        }
     }
 
     /// The prelude re-exports most commonly used traits, structs and macros from this crate.
     pub mod prelude {
-        pub use super::FindPeers;
+        pub use super::{FindPeerCertificate, FindPeers};
     }
 }
 
@@ -902,10 +914,11 @@ pub mod trigger {
     //! Trigger-related queries.
     #[cfg(not(feature = "std"))]
     use alloc::{format, string::String, vec::Vec};
+    use core::num::NonZeroU64;
 
     use derive_more::Display;
 
-    use crate::{trigger::TriggerId, Name};
+    use crate::{domain::DomainId, trigger::TriggerId, Name};
 
     queries! {
         /// Find all currently active (as in not disabled and/or expired)
@@ -915,7 +928,28 @@ pub mod trigger {
         #[ffi_type]
         pub struct FindActiveTriggerIds;
 
+        /// Find the details of all currently active triggers.
+        #[derive(Copy, Display)]
+        #[display(fmt = "Find all triggers")]
+        #[ffi_type]
+        pub struct FindAllActiveTriggers;
+
+        /// Find the details of the triggers whose authority is an account of the domain.
+        #[derive(Display)]
+        #[display(fmt = "Find triggers of `{domain}` domain")]
+        #[repr(transparent)]
+        // SAFETY: `FindTriggersByDomain` has no trap representation in `DomainId`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindTriggersByDomain {
+            /// Domain of the accounts executing the triggers.
+            pub domain: DomainId,
+        }
+
         /// Find Trigger given its ID.
+        ///
+        /// Unlike [`FindAllActiveTriggers`], the output includes the executable
+        /// and the metadata, so the default executor only permits it to the owner of
+        /// the trigger and the accounts which can inspect any trigger.
         #[derive(Display)]
         #[display(fmt = "Find `{id}` trigger")]
         #[repr(transparent)]
@@ -936,11 +970,26 @@ pub mod trigger {
             /// The key inside the metadata dictionary to be returned.
             pub key: Name,
         }
+
+        /// Find receipts of the triggers executed while applying the block.
+        /// Triggers are listed in the order they were executed.
+        #[derive(Copy, Display)]
+        #[display(fmt = "Find trigger receipts of block at `{height}` height")]
+        #[repr(transparent)]
+        // SAFETY: `FindTriggerReceipts` has no trap representation in `NonZeroU64`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindTriggerReceipts {
+            /// Block height.
+            pub height: NonZeroU64,
+        }
     }
 
     pub mod prelude {
         //! Prelude Re-exports most commonly used traits, structs and macros from this crate.
-        pub use super::{FindActiveTriggerIds, FindTriggerById, FindTriggerMetadata};
+        pub use super::{
+            FindActiveTriggerIds, FindAllActiveTriggers, FindTriggerById, FindTriggerMetadata,
+            FindTriggerReceipts, FindTriggersByDomain,
+        };
     }
 }
 
@@ -955,7 +1004,7 @@ pub mod transaction {
     use derive_more::Display;
     use iroha_crypto::HashOf;
 
-    use crate::{account::AccountId, transaction::SignedTransaction};
+    use crate::{account::AccountId, app::AppId, name::Name, transaction::SignedTransaction};
 
     queries! {
         /// [`FindTransactions`] Iroha Query lists all transactions included in a blockchain
@@ -976,6 +1025,18 @@ pub mod transaction {
             pub account: AccountId,
         }
 
+        /// [`FindTransactionsByAppId`] Iroha Query finds all transactions included in a blockchain
+        /// which are attributed to the app
+        #[derive(Display)]
+        #[display(fmt = "Find all transactions of `{app}` app")]
+        #[repr(transparent)]
+        // SAFETY: `FindTransactionsByAppId` has no trap representation in `AppId`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindTransactionsByAppId {
+            /// App to which the transactions are attributed.
+            pub app: AppId,
+        }
+
         /// [`FindTransactionByHash`] Iroha Query finds a transaction (if any)
         /// with corresponding hash value
         #[derive(Copy, Display)]
@@ -987,11 +1048,26 @@ pub mod transaction {
             /// Transaction hash.
             pub hash: HashOf<SignedTransaction>,
         }
+
+        /// [`FindIdempotencyRecord`] Iroha Query finds the committed transaction which used
+        /// the idempotency key, unless the key can already be used again
+        #[derive(Display)]
+        #[display(fmt = "Find usage of `{key}` idempotency key by `{authority}` account")]
+        #[ffi_type]
+        pub struct FindIdempotencyRecord {
+            /// Account which submitted the transaction.
+            pub authority: AccountId,
+            /// Idempotency key of the transaction.
+            pub key: Name,
+        }
     }
 
     /// The prelude re-exports most commonly used traits, structs and macros from this crate.
     pub mod prelude {
-        pub use super::{FindTransactionByHash, FindTransactions, FindTransactionsByAccountId};
+        pub use super::{
+            FindIdempotencyRecord, FindTransactionByHash, FindTransactions,
+            FindTransactionsByAccountId, FindTransactionsByAppId,
+        };
     }
 }
 
@@ -1002,6 +1078,7 @@ pub mod block {
 
     #[cfg(not(feature = "std"))]
     use alloc::{format, string::String, vec::Vec};
+    use core::num::NonZeroU64;
 
     use derive_more::Display;
     use iroha_crypto::HashOf;
@@ -1033,17 +1110,91 @@ pub mod block {
             /// Block hash.
             pub hash: HashOf<SignedBlock>,
         }
+
+        /// [`FindBlockStateDiff`] Iroha Query finds changes of the world state made by the block.
+        /// Diffs are only recorded if enabled in the peer configuration.
+        #[derive(Copy, Display)]
+        #[display(fmt = "Find state diff of block at `{height}` height")]
+        #[repr(transparent)]
+        // SAFETY: `FindBlockStateDiff` has no trap representation in `NonZeroU64`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindBlockStateDiff {
+            /// Block height.
+            pub height: NonZeroU64,
+        }
+
+        /// [`FindBlockRewards`] Iroha Query finds the fees paid out at the end of the block.
+        /// Payouts are listed in the order they were made.
+        #[derive(Copy, Display)]
+        #[display(fmt = "Find rewards of block at `{height}` height")]
+        #[repr(transparent)]
+        // SAFETY: `FindBlockRewards` has no trap representation in `NonZeroU64`
+        #[ffi_type(unsafe {robust})]
+        pub struct FindBlockRewards {
+            /// Block height.
+            pub height: NonZeroU64,
+        }
+    }
+
+    /// The prelude re-exports most commonly used traits, structs and macros from this crate.
+    pub mod prelude {
+        pub use super::{
+            FindBlockHeaderByHash, FindBlockHeaders, FindBlockRewards, FindBlockStateDiff,
+            FindBlocks,
+        };
+    }
+}
+
+pub mod event {
+    //! Queries related to the events archived by the peer.
+
+    #![allow(clippy::missing_inline_in_public_items)]
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::String, vec::Vec};
+    use core::num::NonZeroU64;
+
+    use derive_more::Display;
+
+    use crate::events::EventFilterBox;
+
+    queries! {
+        /// [`FindEvents`] Iroha Query lists the events emitted while applying the blocks
+        /// within the height range, sorted by height in ascending order.
+        /// Events are only archived if enabled in the peer configuration.
+        #[derive(Display)]
+        #[display(fmt = "Find events emitted by blocks at `{height_range}` heights")]
+        #[ffi_type]
+        pub struct FindEvents {
+            /// Filter the events have to match.
+            pub filter: EventFilterBox,
+            /// Heights of the blocks which emitted the events.
+            pub height_range: HeightRange,
+        }
+
+        /// Inclusive range of block heights.
+        #[derive(Copy, Display)]
+        #[display(fmt = "{start}..={end}")]
+        #[ffi_type]
+        pub struct HeightRange {
+            /// Height of the first block.
+            pub start: NonZeroU64,
+            /// Height of the last block.
+            pub end: NonZeroU64,
+        }
     }
 
     /// The prelude re-exports most commonly used traits, structs and macros from this crate.
     pub mod prelude {
-        pub use super::{FindBlockHeaderByHash, FindBlockHeaders, FindBlocks};
+        pub use super::{FindEvents, HeightRange};
     }
 }
 
 pub mod error {
     //! Module containing errors that can occur during query execution
 
+    use core::num::NonZeroU64;
+
     use iroha_crypto::HashOf;
     use iroha_data_model_derive::model;
     use iroha_macro::FromVariant;
@@ -1093,6 +1244,8 @@ pub mod error {
             InvalidSingularParameters,
             /// Reached limit of parallel queries. Either wait for previous queries to complete, or increase the limit in the config.
             CapacityLimit,
+            /// Query cost exceeded the remaining query budget of the account. Either wait for the budget to be replenished, or narrow the query.
+            QueryCostExceeded,
         }
 
         /// Type assertion error
@@ -1138,6 +1291,20 @@ pub mod error {
             Permission(Permission),
             /// Failed to find public key: `{0}`
             PublicKey(PublicKey),
+            /// State diff of block at height `{0}` not found
+            BlockStateDiff(NonZeroU64),
+            /// Escrow with id `{0}` not found
+            Escrow(EscrowId),
+            /// Idempotency key `{0}` is not in use
+            IdempotencyKey(Name),
+            /// Events of block at height `{0}` are not archived
+            EventArchive(NonZeroU64),
+            /// Block at height `{0}` is not committed
+            BlockHeight(NonZeroU64),
+            /// Certificate of peer with key `{0}` is not registered
+            PeerCertificate(PublicKey),
+            /// App with id `{0}` not found
+            App(AppId),
         }
     }
 }
@@ -1147,8 +1314,9 @@ pub mod error {
 pub mod prelude {
     pub use super::{
         account::prelude::*, asset::prelude::*, block::prelude::*, builder::prelude::*,
-        domain::prelude::*, executor::prelude::*, parameters::prelude::*, peer::prelude::*,
-        permission::prelude::*, predicate::prelude::*, role::prelude::*, transaction::prelude::*,
-        trigger::prelude::*, QueryBox, QueryRequest, SingularQueryBox, TransactionQueryOutput,
+        domain::prelude::*, event::prelude::*, executor::prelude::*, parameters::prelude::*,
+        peer::prelude::*, permission::prelude::*, predicate::prelude::*, role::prelude::*,
+        transaction::prelude::*, trigger::prelude::*, QueryBox, QueryRequest, SingularQueryBox,
+        TransactionQueryOutput,
     };
 }
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use super::impl_predicate_box;
 use crate::{
-    asset::{Asset, AssetDefinition, AssetDefinitionId, AssetId, AssetValue},
+    asset::{Asset, AssetDefinition, AssetDefinitionId, AssetId, AssetValue, StoreEntry},
     query::{
         predicate::{
             predicate_ast_extensions::AstPredicateExt as _,
@@ -136,10 +136,28 @@ impl EvaluatePredicate<AssetDefinitionId> for AssetDefinitionIdPredicateBox {
     }
 }
 
+/// A predicate that can be applied to a [`StoreEntry`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub enum StoreEntryPredicateBox {
+    // projections
+    /// Checks if a predicate applies to the key of the input.
+    Key(StringPredicateBox),
+}
+
+impl_predicate_box!(StoreEntry: StoreEntryPredicateBox);
+
+impl EvaluatePredicate<StoreEntry> for StoreEntryPredicateBox {
+    fn applies(&self, input: &StoreEntry) -> bool {
+        match self {
+            StoreEntryPredicateBox::Key(key) => key.applies(&input.key),
+        }
+    }
+}
+
 pub mod prelude {
     //! Re-export all predicate boxes for a glob import `(::*)`
     pub use super::{
         AssetDefinitionIdPredicateBox, AssetDefinitionPredicateBox, AssetIdPredicateBox,
-        AssetPredicateBox, AssetValuePredicateBox,
+        AssetPredicateBox, AssetValuePredicateBox, StoreEntryPredicateBox,
     };
 }
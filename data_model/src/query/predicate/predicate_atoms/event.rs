@@ -0,0 +1,38 @@
+//! This module contains predicates for archived events, mirroring [`crate::events`].
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use iroha_schema::IntoSchema;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use super::impl_predicate_box;
+use crate::{
+    events::ArchivedEvent,
+    query::predicate::{
+        predicate_ast_extensions::AstPredicateExt as _,
+        predicate_combinators::{AndAstPredicate, NotAstPredicate, OrAstPredicate},
+        projectors::BaseProjector,
+        AstPredicate, CompoundPredicate, EvaluatePredicate, HasPredicateBox, HasPrototype,
+    },
+};
+
+/// A predicate that can be applied to an [`ArchivedEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub enum ArchivedEventPredicateBox {
+    // nothing here yet
+}
+
+impl_predicate_box!(ArchivedEvent: ArchivedEventPredicateBox);
+
+impl EvaluatePredicate<ArchivedEvent> for ArchivedEventPredicateBox {
+    fn applies(&self, _input: &ArchivedEvent) -> bool {
+        match *self {}
+    }
+}
+
+pub mod prelude {
+    //! Re-export all predicate boxes for a glob import `(::*)`
+    pub use super::ArchivedEventPredicateBox;
+}
@@ -10,6 +10,7 @@ pub mod account;
 pub mod asset;
 pub mod block;
 pub mod domain;
+pub mod event;
 pub mod parameter;
 pub mod peer;
 pub mod permission;
@@ -177,7 +178,8 @@ pub mod prelude {
     //! Re-export all predicate boxes for a glob import `(::*)`
     pub use super::{
         account::prelude::*, asset::prelude::*, block::prelude::*, domain::prelude::*,
-        parameter::prelude::*, peer::prelude::*, permission::prelude::*, role::prelude::*,
-        trigger::prelude::*, MetadataPredicateBox, PublicKeyPredicateBox, StringPredicateBox,
+        event::prelude::*, parameter::prelude::*, peer::prelude::*, permission::prelude::*,
+        role::prelude::*, trigger::prelude::*, MetadataPredicateBox, PublicKeyPredicateBox,
+        StringPredicateBox,
     };
 }
@@ -9,10 +9,10 @@ use serde::{Deserialize, Serialize};
 
 use super::impl_predicate_box;
 use crate::{
-    prelude::{Trigger, TriggerId},
+    prelude::{Trigger, TriggerDetails, TriggerId},
     query::predicate::{
         predicate_ast_extensions::AstPredicateExt as _,
-        predicate_atoms::StringPredicateBox,
+        predicate_atoms::{account::AccountIdPredicateBox, StringPredicateBox},
         predicate_combinators::{AndAstPredicate, NotAstPredicate, OrAstPredicate},
         projectors::BaseProjector,
         AstPredicate, CompoundPredicate, EvaluatePredicate, HasPredicateBox, HasPrototype,
@@ -59,7 +59,28 @@ impl EvaluatePredicate<Trigger> for TriggerPredicateBox {
     }
 }
 
+/// A predicate that can be applied to a [`TriggerDetails`].
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub enum TriggerDetailsPredicateBox {
+    // projections
+    /// Checks if a predicate applies to the ID of the input.
+    Id(TriggerIdPredicateBox),
+    /// Checks if a predicate applies to the authority of the input.
+    Authority(AccountIdPredicateBox),
+}
+
+impl_predicate_box!(TriggerDetails: TriggerDetailsPredicateBox);
+
+impl EvaluatePredicate<TriggerDetails> for TriggerDetailsPredicateBox {
+    fn applies(&self, input: &TriggerDetails) -> bool {
+        match self {
+            TriggerDetailsPredicateBox::Id(id) => id.applies(&input.id),
+            TriggerDetailsPredicateBox::Authority(authority) => authority.applies(&input.authority),
+        }
+    }
+}
+
 pub mod prelude {
     //! Re-export all predicate boxes for a glob import `(::*)`
-    pub use super::{TriggerIdPredicateBox, TriggerPredicateBox};
+    pub use super::{TriggerDetailsPredicateBox, TriggerIdPredicateBox, TriggerPredicateBox};
 }
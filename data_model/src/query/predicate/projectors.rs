@@ -12,11 +12,11 @@ use crate::query::predicate::{
         account::{AccountIdPredicateBox, AccountPredicateBox},
         asset::{
             AssetDefinitionIdPredicateBox, AssetDefinitionPredicateBox, AssetIdPredicateBox,
-            AssetPredicateBox, AssetValuePredicateBox,
+            AssetPredicateBox, AssetValuePredicateBox, StoreEntryPredicateBox,
         },
         domain::{DomainIdPredicateBox, DomainPredicateBox},
         role::{RoleIdPredicateBox, RolePredicateBox},
-        trigger::{TriggerIdPredicateBox, TriggerPredicateBox},
+        trigger::{TriggerDetailsPredicateBox, TriggerIdPredicateBox, TriggerPredicateBox},
         MetadataPredicateBox, PublicKeyPredicateBox, StringPredicateBox,
     },
     predicate_combinators::{AndAstPredicate, NotAstPredicate, OrAstPredicate},
@@ -174,6 +174,9 @@ proj!(AssetDefinitionMetadataProjector(AssetDefinitionMetadataProjection): Metad
 proj!(AssetIdProjector(AssetIdProjection): AssetIdPredicateBox => AssetPredicateBox::Id);
 proj!(AssetValueProjector(AssetValueProjection): AssetValuePredicateBox => AssetPredicateBox::Value);
 
+// projections on StoreEntry
+proj!(StoreEntryKeyProjector(StoreEntryKeyProjection): StringPredicateBox => StoreEntryPredicateBox::Key);
+
 // projections on DomainId
 proj!(DomainIdNameProjector(DomainIdNameProjection): StringPredicateBox => DomainIdPredicateBox::Name);
 
@@ -192,3 +195,7 @@ proj!(TriggerIdNameProjector(TriggerIdNameProjection): StringPredicateBox => Tri
 
 // projections in Trigger
 proj!(TriggerIdProjector(TriggerIdProjection): TriggerIdPredicateBox => TriggerPredicateBox::Id);
+
+// projections in TriggerDetails
+proj!(TriggerDetailsIdProjector(TriggerDetailsIdProjection): TriggerIdPredicateBox => TriggerDetailsPredicateBox::Id);
+proj!(TriggerDetailsAuthorityProjector(TriggerDetailsAuthorityProjection): AccountIdPredicateBox => TriggerDetailsPredicateBox::Authority);
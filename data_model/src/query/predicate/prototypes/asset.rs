@@ -9,13 +9,13 @@ use crate::{
         predicate::{
             predicate_atoms::asset::{
                 AssetDefinitionIdPredicateBox, AssetDefinitionPredicateBox, AssetIdPredicateBox,
-                AssetPredicateBox, AssetValuePredicateBox,
+                AssetPredicateBox, AssetValuePredicateBox, StoreEntryPredicateBox,
             },
             projectors::{
                 AssetDefinitionIdDomainIdProjector, AssetDefinitionIdNameProjector,
                 AssetDefinitionIdProjector, AssetDefinitionMetadataProjector,
                 AssetIdAccountIdProjector, AssetIdDefinitionIdProjector, AssetIdProjector,
-                AssetValueProjector,
+                AssetValueProjector, StoreEntryKeyProjector,
             },
             prototypes::{account::AccountIdPrototype, domain::DomainIdPrototype, ObjectProjector},
         },
@@ -97,3 +97,12 @@ pub struct AssetValuePrototype<Projector> {
 }
 
 impl_prototype!(AssetValuePrototype: AssetValuePredicateBox);
+
+/// A prototype of [`crate::asset::StoreEntry`] for predicate construction.
+#[derive(Default, Copy, Clone)]
+pub struct StoreEntryPrototype<Projector> {
+    /// Build a predicate on key of this [`crate::asset::StoreEntry`]
+    pub key: StringPrototype<StoreEntryKeyProjector<Projector>>,
+}
+
+impl_prototype!(StoreEntryPrototype: StoreEntryPredicateBox);
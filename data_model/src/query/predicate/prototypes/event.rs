@@ -0,0 +1,17 @@
+//! Event-related prototypes, mirroring types in [`crate::events`].
+
+use core::marker::PhantomData;
+
+use super::impl_prototype;
+use crate::query::predicate::{
+    predicate_atoms::event::ArchivedEventPredicateBox, projectors::ObjectProjector, AstPredicate,
+    HasPrototype,
+};
+
+/// A prototype of [`crate::events::ArchivedEvent`] for predicate construction.
+#[derive(Default, Copy, Clone)]
+pub struct ArchivedEventPrototype<Projector> {
+    phantom: PhantomData<Projector>,
+}
+
+impl_prototype!(ArchivedEventPrototype: ArchivedEventPredicateBox);
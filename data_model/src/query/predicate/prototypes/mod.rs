@@ -6,6 +6,7 @@ pub mod account;
 pub mod asset;
 pub mod block;
 pub mod domain;
+pub mod event;
 pub mod parameter;
 pub mod peer;
 pub mod permission;
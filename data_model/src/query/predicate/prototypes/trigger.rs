@@ -4,9 +4,14 @@ use super::impl_prototype;
 use crate::{
     prelude::TriggerId,
     query::predicate::{
-        predicate_atoms::trigger::{TriggerIdPredicateBox, TriggerPredicateBox},
-        projectors::{ObjectProjector, TriggerIdProjector},
-        prototypes::StringPrototype,
+        predicate_atoms::trigger::{
+            TriggerDetailsPredicateBox, TriggerIdPredicateBox, TriggerPredicateBox,
+        },
+        projectors::{
+            ObjectProjector, TriggerDetailsAuthorityProjector, TriggerDetailsIdProjector,
+            TriggerIdProjector,
+        },
+        prototypes::{account::AccountIdPrototype, StringPrototype},
         AstPredicate, HasPrototype,
     },
 };
@@ -38,3 +43,14 @@ pub struct TriggerPrototype<Projector> {
 }
 
 impl_prototype!(TriggerPrototype: TriggerPredicateBox);
+
+/// A prototype of [`crate::trigger::TriggerDetails`] for predicate construction.
+#[derive(Default, Copy, Clone)]
+pub struct TriggerDetailsPrototype<Projector> {
+    /// Build a predicate on ID of this [`crate::trigger::TriggerDetails`]
+    pub id: TriggerIdPrototype<TriggerDetailsIdProjector<Projector>>,
+    /// Build a predicate on authority of this [`crate::trigger::TriggerDetails`]
+    pub authority: AccountIdPrototype<TriggerDetailsAuthorityProjector<Projector>>,
+}
+
+impl_prototype!(TriggerDetailsPrototype: TriggerDetailsPredicateBox);
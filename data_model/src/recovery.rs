@@ -0,0 +1,107 @@
+//! Structures, traits and impls related to the recovery of `Account`s.
+//!
+//! The owner of an account designates guardian accounts, which can move the account to a new
+//! signatory once its key is lost. A guardian initiates the recovery, which can be finalized
+//! only after the challenge window of the policy has passed. Until then the owner can veto
+//! the recovery with the current key.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use iroha_crypto::PublicKey;
+use iroha_data_model_derive::model;
+
+pub use self::model::*;
+use crate::account::AccountId;
+
+#[model]
+mod model {
+    use getset::{CopyGetters, Getters};
+    use iroha_schema::IntoSchema;
+    use parity_scale_codec::{Decode, Encode};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// Guardians allowed to recover an account together with the pending recovery, if any.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct RecoveryPolicy {
+        /// Account protected by the policy.
+        #[getset(get = "pub")]
+        pub account: AccountId,
+        /// Accounts which can initiate and finalize the recovery.
+        #[getset(get = "pub")]
+        pub guardians: Vec<AccountId>,
+        /// Time (in ms) during which the owner can veto an initiated recovery.
+        #[getset(get_copy = "pub")]
+        pub delay_ms: u64,
+        /// Recovery waiting for the end of its challenge window.
+        #[getset(get = "pub")]
+        pub pending: Option<PendingRecovery>,
+    }
+
+    /// Recovery initiated by a guardian, see [`InitiateRecovery`](crate::isi::InitiateRecovery).
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct PendingRecovery {
+        /// Guardian which initiated the recovery.
+        #[getset(get = "pub")]
+        pub guardian: AccountId,
+        /// Signatory the account is moved to once the recovery is finalized.
+        #[getset(get = "pub")]
+        pub new_signatory: PublicKey,
+        /// Time since the Unix epoch (in ms) starting from which the recovery can be finalized.
+        #[getset(get_copy = "pub")]
+        pub finalizable_at_ms: u64,
+    }
+}
+
+impl RecoveryPolicy {
+    /// Check whether `account` is one of the guardians.
+    #[inline]
+    pub fn is_guardian(&self, account: &AccountId) -> bool {
+        self.guardians.contains(account)
+    }
+}
+
+impl PendingRecovery {
+    /// Check whether the challenge window of the recovery has passed at the given time.
+    #[inline]
+    pub fn is_finalizable(&self, now_ms: u64) -> bool {
+        now_ms >= self.finalizable_at_ms
+    }
+}
+
+/// The prelude re-exports most commonly used traits, structs and macros from this module.
+pub mod prelude {
+    pub use super::{PendingRecovery, RecoveryPolicy};
+}
@@ -11,9 +11,10 @@ use core::{
 use derive_more::{DebugCustom, Display};
 #[cfg(feature = "http")]
 pub use http::*;
-use iroha_crypto::SignatureOf;
-use iroha_data_model_derive::model;
+use iroha_crypto::{SignatureOf, Unverified, Verified};
+use iroha_data_model_derive::{model, ValidatedDecode};
 use iroha_macro::FromVariant;
+use iroha_primitives::numeric::Numeric;
 use iroha_schema::IntoSchema;
 use iroha_version::{declare_versioned, version};
 use parity_scale_codec::{Decode, Encode};
@@ -22,14 +23,16 @@ use serde::{Deserialize, Serialize};
 pub use self::model::*;
 use crate::{
     account::AccountId,
+    app::AppId,
     isi::{Instruction, InstructionBox},
     metadata::Metadata,
+    name::Name,
     ChainId,
 };
 
 #[model]
 mod model {
-    use getset::Getters;
+    use getset::{CopyGetters, Getters};
 
     use super::*;
     use crate::account::AccountId;
@@ -111,9 +114,67 @@ mod model {
         pub instructions: Executable,
         /// If transaction is not committed by this time it will be dropped.
         pub time_to_live_ms: Option<NonZeroU64>,
+        /// Unix time in milliseconds before which the transaction can't be included in a block.
+        /// Time to live of a scheduled transaction is counted from this time.
+        pub not_before_ms: Option<NonZeroU64>,
         /// Random value to make different hashes for transactions which occur repeatedly and simultaneously.
         pub nonce: Option<NonZeroU32>,
-        /// Store for additional information.
+        /// Key of the operation performed by the transaction. Transactions of the same authority
+        /// reusing the key of a recently committed transaction are rejected.
+        pub idempotency_key: Option<Name>,
+        /// Arbitrary client data (e.g. a memo or a reference of an external system).
+        ///
+        /// It is hashed and signed together with the rest of the payload and is
+        /// reported in transaction queries and pipeline events, but it has no
+        /// effect on the execution of the transaction.
+        pub metadata: Metadata,
+    }
+
+    /// Payload of [`SignedTransactionV2`]
+    ///
+    /// Unlike [`TransactionPayload`], the nonce and the fee cap are always set by the client.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub(crate) struct TransactionPayloadV2 {
+        /// Unique id of the blockchain. Used for simple replay attack protection.
+        pub chain: ChainId,
+        /// Account ID of transaction creator.
+        pub authority: AccountId,
+        /// Creation timestamp (unix time in milliseconds).
+        pub creation_time_ms: u64,
+        /// ISI or a `WebAssembly` smart contract.
+        pub instructions: Executable,
+        /// If transaction is not committed by this time it will be dropped.
+        pub time_to_live_ms: Option<NonZeroU64>,
+        /// Unix time in milliseconds before which the transaction can't be included in a block.
+        /// Time to live of a scheduled transaction is counted from this time.
+        pub not_before_ms: Option<NonZeroU64>,
+        /// Value chosen by the client to make different hashes for transactions which
+        /// occur repeatedly and simultaneously, `0` if the client doesn't care.
+        pub nonce: u32,
+        /// Maximum fee the authority agrees to pay for the transaction.
+        pub fee_cap: Numeric,
+        /// Free-form note of the client, e.g. a payment reference.
+        /// Counted towards the metadata size limit.
+        pub memo: Option<String>,
+        /// Key of the operation performed by the transaction. Transactions of the same authority
+        /// reusing the key of a recently committed transaction are rejected.
+        pub idempotency_key: Option<Name>,
+        /// Account which signs and submits the transaction on behalf of the authority,
+        /// see [`ProxyToken`](crate::proxy::ProxyToken). [`None`] if the authority signs it.
+        pub submitter: Option<AccountId>,
+        /// Arbitrary client data.
         pub metadata: Metadata,
     }
 
@@ -131,7 +192,7 @@ mod model {
         Serialize,
         IntoSchema,
     )]
-    pub struct TransactionSignature(pub SignatureOf<TransactionPayload>);
+    pub struct TransactionSignature(pub Unverified<SignatureOf<TransactionPayload>>);
 
     /// Transaction that contains a signature
     ///
@@ -141,7 +202,17 @@ mod model {
     /// The peer verifies the signature and checks the limits.
     #[version(version = 1, versioned_alias = "SignedTransaction")]
     #[derive(
-        Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Serialize, IntoSchema,
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Encode,
+        Serialize,
+        ValidatedDecode,
+        IntoSchema,
     )]
     #[cfg_attr(not(feature = "std"), display(fmt = "Signed transaction"))]
     #[cfg_attr(feature = "std", display(fmt = "{}", "self.hash()"))]
@@ -153,6 +224,31 @@ mod model {
         pub(super) payload: TransactionPayload,
     }
 
+    /// Transaction with an explicit chain id, nonce, fee cap and memo
+    #[version(version = 2, versioned_alias = "SignedTransaction")]
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Encode,
+        Serialize,
+        ValidatedDecode,
+        IntoSchema,
+    )]
+    #[cfg_attr(not(feature = "std"), display(fmt = "Signed transaction"))]
+    #[cfg_attr(feature = "std", display(fmt = "{}", "self.hash()"))]
+    #[ffi_type]
+    pub struct SignedTransactionV2 {
+        /// Signature of [`Self::payload`].
+        pub(super) signature: Unverified<SignatureOf<TransactionPayloadV2>>,
+        /// Payload of the transaction.
+        pub(super) payload: TransactionPayloadV2,
+    }
+
     /// Transaction Value used in Instructions and Queries
     #[derive(
         Debug,
@@ -177,6 +273,186 @@ mod model {
         /// Reason of rejection
         pub error: Option<error::TransactionRejectionReason>,
     }
+
+    /// Committed transaction which used an idempotency key
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct IdempotencyRecord {
+        /// Hash of the transaction which used the key
+        #[getset(get = "pub")]
+        pub transaction: iroha_crypto::HashOf<SignedTransaction>,
+        /// Time since the Unix epoch (in ms) starting from which the key can be used again
+        #[getset(get_copy = "pub")]
+        pub expires_at_ms: u64,
+    }
+
+    /// Outcome of executing a transaction on top of the latest committed state
+    /// without committing it
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct TransactionSimulation {
+        /// Fuel consumed by the executor and the smart contract
+        #[getset(get_copy = "pub")]
+        pub fuel: u64,
+        /// Events which would be emitted by the transaction
+        #[getset(get = "pub")]
+        pub events: Vec<crate::events::EventBox>,
+        /// Triggers which would be executed because of the transaction
+        #[getset(get = "pub")]
+        pub triggers: Vec<crate::trigger::TriggerId>,
+        /// Reason the transaction would be rejected for
+        #[getset(get = "pub")]
+        pub rejection: Option<error::TransactionRejectionReason>,
+        /// Instructions executed by the transaction in order, if the trace was requested
+        #[getset(get = "pub")]
+        pub trace: Option<Vec<InstructionTrace>>,
+    }
+
+    /// Record of a single instruction executed during a traced simulation
+    #[derive(
+        Debug, Clone, PartialEq, Eq, Getters, Decode, Encode, Deserialize, Serialize, IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct InstructionTrace {
+        /// Instruction as it was executed, either by the executor or by the smart contract
+        #[getset(get = "pub")]
+        pub instruction: InstructionBox,
+        /// Events emitted by the instruction
+        #[getset(get = "pub")]
+        pub events: Vec<crate::events::EventBox>,
+        /// Error the instruction failed with
+        #[getset(get = "pub")]
+        pub error: Option<crate::isi::error::InstructionExecutionError>,
+    }
+
+    /// Status of a transaction reported by the peer it was submitted to.
+    ///
+    /// Submitting a transaction the peer already knows of isn't an error,
+    /// the peer reports what has become of the transaction instead.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type(opaque)]
+    pub enum SubmissionStatus {
+        /// Transaction was admitted into the queue
+        Accepted,
+        /// Transaction was already in the queue
+        Queued,
+        /// Transaction was stored as valid in the block of the given height
+        Committed(NonZeroU64),
+        /// Transaction was stored as invalid in a block
+        Rejected(RejectedSubmission),
+    }
+
+    /// Transaction stored as invalid in a block, see [`SubmissionStatus::Rejected`]
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        CopyGetters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    pub struct RejectedSubmission {
+        /// Height of the block the transaction is stored in
+        #[getset(get_copy = "pub")]
+        pub height: NonZeroU64,
+        /// Reason the transaction was rejected for
+        #[getset(get = "pub")]
+        pub reason: error::TransactionRejectionReason,
+    }
+
+    /// Request to withdraw a transaction which is still in the queue
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    #[getset(get = "pub")]
+    pub struct CancelTransaction {
+        /// Hash of the transaction to cancel
+        pub hash: iroha_crypto::HashOf<SignedTransaction>,
+    }
+
+    /// [`CancelTransaction`] signed by the authority of the cancelled transaction
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[ffi_type]
+    #[getset(get = "pub")]
+    pub struct SignedCancelTransaction {
+        /// Cancellation request
+        pub payload: CancelTransaction,
+        /// Signature of [`Self::payload`] made by the authority of the cancelled transaction
+        pub signature: Unverified<SignatureOf<CancelTransaction>>,
+    }
 }
 
 impl<A: Instruction> FromIterator<A> for Executable {
@@ -217,74 +493,143 @@ impl WasmSmartContract {
 }
 
 #[cfg(any(feature = "ffi_export", feature = "ffi_import"))]
-declare_versioned!(SignedTransaction 1..2, Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, FromVariant, iroha_ffi::FfiType, IntoSchema);
+declare_versioned!(SignedTransaction 1..3, Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, FromVariant, iroha_ffi::FfiType, IntoSchema);
 #[cfg(all(not(feature = "ffi_export"), not(feature = "ffi_import")))]
-declare_versioned!(SignedTransaction 1..2, Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, FromVariant, IntoSchema);
+declare_versioned!(SignedTransaction 1..3, Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, FromVariant, IntoSchema);
+
+/// Access a field present in the payloads of all versions
+macro_rules! payload_field {
+    ($tx:expr, $field:ident) => {
+        match $tx {
+            SignedTransaction::V1(tx) => &tx.payload.$field,
+            SignedTransaction::V2(tx) => &tx.payload.$field,
+        }
+    };
+}
 
 impl SignedTransaction {
-    /// Transaction payload. Used for tests
-    #[cfg(feature = "transparent_api")]
-    pub fn payload(&self) -> &TransactionPayload {
-        let SignedTransaction::V1(tx) = self;
-        &tx.payload
-    }
-
     /// Return transaction instructions
     #[inline]
     pub fn instructions(&self) -> &Executable {
-        let SignedTransaction::V1(tx) = self;
-        &tx.payload.instructions
+        payload_field!(self, instructions)
     }
 
     /// Return transaction authority
     #[inline]
     pub fn authority(&self) -> &AccountId {
-        let SignedTransaction::V1(tx) = self;
-        &tx.payload.authority
+        payload_field!(self, authority)
     }
 
     /// Return transaction metadata.
     #[inline]
     pub fn metadata(&self) -> &Metadata {
-        let SignedTransaction::V1(tx) = self;
-        &tx.payload.metadata
+        payload_field!(self, metadata)
+    }
+
+    /// App to which the transaction is attributed in its metadata, see [`AppId::from_metadata`].
+    #[inline]
+    pub fn app_id(&self) -> Option<Result<AppId, String>> {
+        AppId::from_metadata(self.metadata())
     }
 
     /// Creation timestamp as [`core::time::Duration`]
     #[inline]
     pub fn creation_time(&self) -> Duration {
-        let SignedTransaction::V1(tx) = self;
-        Duration::from_millis(tx.payload.creation_time_ms)
+        Duration::from_millis(*payload_field!(self, creation_time_ms))
     }
 
     /// If transaction is not committed by this time it will be dropped.
     #[inline]
     pub fn time_to_live(&self) -> Option<Duration> {
-        let SignedTransaction::V1(tx) = self;
-        tx.payload
-            .time_to_live_ms
-            .map(|ttl| Duration::from_millis(ttl.into()))
+        (*payload_field!(self, time_to_live_ms)).map(|ttl| Duration::from_millis(ttl.into()))
+    }
+
+    /// Time since the Unix epoch before which the transaction can't be included in a block.
+    #[inline]
+    pub fn not_before(&self) -> Option<Duration> {
+        (*payload_field!(self, not_before_ms))
+            .map(|not_before| Duration::from_millis(not_before.into()))
     }
 
-    /// Transaction nonce
+    /// Whether the transaction can be included in a block created at `block_time`
+    #[inline]
+    pub fn is_eligible_at(&self, block_time: Duration) -> bool {
+        self.not_before()
+            .map_or(true, |not_before| not_before <= block_time)
+    }
+
+    /// Transaction nonce, [`None`] if the client didn't set it
     #[inline]
     pub fn nonce(&self) -> Option<NonZeroU32> {
-        let SignedTransaction::V1(tx) = self;
-        tx.payload.nonce
+        match self {
+            SignedTransaction::V1(tx) => tx.payload.nonce,
+            SignedTransaction::V2(tx) => NonZeroU32::new(tx.payload.nonce),
+        }
+    }
+
+    /// Maximum fee the authority agrees to pay, [`None`] for transactions
+    /// of version 1 which predate fees
+    #[inline]
+    pub fn fee_cap(&self) -> Option<Numeric> {
+        match self {
+            SignedTransaction::V1(_) => None,
+            SignedTransaction::V2(tx) => Some(tx.payload.fee_cap),
+        }
+    }
+
+    /// Note of the client attached to the transaction
+    #[inline]
+    pub fn memo(&self) -> Option<&str> {
+        match self {
+            SignedTransaction::V1(_) => None,
+            SignedTransaction::V2(tx) => tx.payload.memo.as_deref(),
+        }
+    }
+
+    /// Key of the operation performed by the transaction
+    #[inline]
+    pub fn idempotency_key(&self) -> Option<&Name> {
+        payload_field!(self, idempotency_key).as_ref()
+    }
+
+    /// Proxy which submitted the transaction on behalf of the authority,
+    /// [`None`] if the transaction is submitted by the authority itself
+    #[inline]
+    pub fn submitter(&self) -> Option<&AccountId> {
+        match self {
+            SignedTransaction::V1(_) => None,
+            SignedTransaction::V2(tx) => tx.payload.submitter.as_ref(),
+        }
+    }
+
+    /// Account with the key of which the transaction is signed
+    #[inline]
+    pub fn signer(&self) -> &AccountId {
+        self.submitter().unwrap_or_else(|| self.authority())
     }
 
     /// Transaction chain id
     #[inline]
     pub fn chain(&self) -> &ChainId {
-        let SignedTransaction::V1(tx) = self;
-        &tx.payload.chain
+        payload_field!(self, chain)
     }
 
     /// Return the transaction signature
     #[inline]
-    pub fn signature(&self) -> &TransactionSignature {
-        let SignedTransaction::V1(tx) = self;
-        &tx.signature
+    pub fn signature(&self) -> &iroha_crypto::Signature {
+        match self {
+            SignedTransaction::V1(tx) => tx.signature.0.as_unverified(),
+            SignedTransaction::V2(tx) => tx.signature.as_unverified(),
+        }
+    }
+
+    /// Version of the transaction format
+    #[inline]
+    pub fn transaction_version(&self) -> TransactionVersion {
+        match self {
+            SignedTransaction::V1(_) => TransactionVersion::V1,
+            SignedTransaction::V2(_) => TransactionVersion::V2,
+        }
     }
 
     /// Calculate transaction [`Hash`](`iroha_crypto::HashOf`).
@@ -297,18 +642,137 @@ impl SignedTransaction {
 #[cfg(feature = "transparent_api")]
 impl From<SignedTransaction> for (AccountId, Executable) {
     fn from(source: SignedTransaction) -> Self {
-        let SignedTransaction::V1(tx) = source;
-        (tx.payload.authority, tx.payload.instructions)
+        match source {
+            SignedTransaction::V1(tx) => (tx.payload.authority, tx.payload.instructions),
+            SignedTransaction::V2(tx) => (tx.payload.authority, tx.payload.instructions),
+        }
+    }
+}
+
+impl CancelTransaction {
+    /// Request to cancel the transaction with the given `hash`
+    pub const fn new(hash: iroha_crypto::HashOf<SignedTransaction>) -> Self {
+        Self { hash }
+    }
+
+    /// Sign the request with the key of the authority of the cancelled transaction
+    #[must_use]
+    pub fn sign(self, private_key: &iroha_crypto::PrivateKey) -> SignedCancelTransaction {
+        SignedCancelTransaction {
+            signature: Verified::sign(private_key, &self).into(),
+            payload: self,
+        }
+    }
+}
+
+impl SignedCancelTransaction {
+    /// Check that the request is signed by `authority`
+    ///
+    /// # Errors
+    /// Fails if the signature wasn't made by the signatory of `authority`
+    pub fn verify(&self, authority: &AccountId) -> Result<(), iroha_crypto::Error> {
+        self.signature
+            .verify(authority.signatory(), &self.payload)
+            .map(drop)
     }
 }
 
 impl SignedTransactionV1 {
+    /// Transaction can only be constructed with a signature that is known to be valid
+    fn new(
+        payload: TransactionPayload,
+        signature: Verified<SignatureOf<TransactionPayload>>,
+    ) -> Self {
+        Self {
+            signature: TransactionSignature(signature.into()),
+            payload,
+        }
+    }
+
     #[cfg(feature = "std")]
     fn hash(&self) -> iroha_crypto::HashOf<SignedTransaction> {
         iroha_crypto::HashOf::from_untyped_unchecked(iroha_crypto::HashOf::new(self).into())
     }
 }
 
+impl SignedTransactionV2 {
+    /// Transaction can only be constructed with a signature that is known to be valid
+    fn new(
+        payload: TransactionPayloadV2,
+        signature: Verified<SignatureOf<TransactionPayloadV2>>,
+    ) -> Self {
+        Self {
+            signature: signature.into(),
+            payload,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn hash(&self) -> iroha_crypto::HashOf<SignedTransaction> {
+        iroha_crypto::HashOf::from_untyped_unchecked(iroha_crypto::HashOf::new(self).into())
+    }
+}
+
+/// Format version of [`SignedTransaction`]
+#[derive(
+    Debug,
+    Display,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Decode,
+    Encode,
+    Deserialize,
+    Serialize,
+)]
+#[repr(u8)]
+pub enum TransactionVersion {
+    /// [`SignedTransactionV1`]
+    #[display(fmt = "1")]
+    V1 = 1,
+    /// [`SignedTransactionV2`]
+    #[default]
+    #[display(fmt = "2")]
+    V2 = 2,
+}
+
+impl From<TransactionPayloadV2> for TransactionPayload {
+    /// Fee cap, memo and submitter have no counterpart in version 1 and are dropped
+    fn from(payload: TransactionPayloadV2) -> Self {
+        Self {
+            chain: payload.chain,
+            authority: payload.authority,
+            creation_time_ms: payload.creation_time_ms,
+            instructions: payload.instructions,
+            time_to_live_ms: payload.time_to_live_ms,
+            not_before_ms: payload.not_before_ms,
+            nonce: NonZeroU32::new(payload.nonce),
+            idempotency_key: payload.idempotency_key,
+            metadata: payload.metadata,
+        }
+    }
+}
+
+impl CommittedTransaction {
+    /// App to which the transaction is attributed, [`None`] if the transaction
+    /// doesn't name an app or was rejected before the app was verified.
+    pub fn app_id(&self) -> Option<AppId> {
+        if self
+            .error
+            .as_ref()
+            .is_some_and(error::TransactionRejectionReason::precedes_app_verification)
+        {
+            return None;
+        }
+        self.value.app_id().and_then(Result::ok)
+    }
+}
+
 impl AsRef<SignedTransaction> for CommittedTransaction {
     fn as_ref(&self) -> &SignedTransaction {
         &self.value
@@ -316,25 +780,14 @@ impl AsRef<SignedTransaction> for CommittedTransaction {
 }
 
 mod candidate {
-    use parity_scale_codec::Input;
-
     use super::*;
 
-    #[derive(Decode, Deserialize)]
-    struct SignedTransactionCandidate {
-        signature: TransactionSignature,
-        payload: TransactionPayload,
-    }
-
-    impl SignedTransactionCandidate {
-        fn validate(self) -> Result<SignedTransactionV1, &'static str> {
+    impl SignedTransactionV1 {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
             self.validate_instructions()?;
-            self.validate_signature()?;
+            let signature = self.validate_signature()?;
 
-            Ok(SignedTransactionV1 {
-                signature: self.signature,
-                payload: self.payload,
-            })
+            Ok(Self::new(self.payload, signature))
         }
 
         fn validate_instructions(&self) -> Result<(), &'static str> {
@@ -347,34 +800,35 @@ mod candidate {
             Ok(())
         }
 
-        fn validate_signature(&self) -> Result<(), &'static str> {
+        fn validate_signature(
+            &self,
+        ) -> Result<Verified<SignatureOf<TransactionPayload>>, &'static str> {
             let TransactionSignature(signature) = &self.signature;
 
             signature
                 .verify(&self.payload.authority.signatory, &self.payload)
-                .map_err(|_| "Transaction signature is invalid")?;
-
-            Ok(())
+                .map_err(|_| "Transaction signature is invalid")
         }
     }
 
-    impl Decode for SignedTransactionV1 {
-        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-            SignedTransactionCandidate::decode(input)?
-                .validate()
-                .map_err(Into::into)
-        }
-    }
-    impl<'de> Deserialize<'de> for SignedTransactionV1 {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            use serde::de::Error as _;
+    impl SignedTransactionV2 {
+        pub(super) fn validate(self) -> Result<Self, &'static str> {
+            if let Executable::Instructions(instructions) = &self.payload.instructions {
+                if instructions.is_empty() {
+                    return Err("Transaction is empty");
+                }
+            }
+            let signer = self
+                .payload
+                .submitter
+                .as_ref()
+                .unwrap_or(&self.payload.authority);
+            let signature = self
+                .signature
+                .verify(&signer.signatory, &self.payload)
+                .map_err(|_| "Transaction signature is invalid")?;
 
-            SignedTransactionCandidate::deserialize(deserializer)?
-                .validate()
-                .map_err(D::Error::custom)
+            Ok(Self::new(self.payload, signature))
         }
     }
 }
@@ -500,6 +954,68 @@ pub mod error {
             pub reason: String,
         }
 
+        /// Transaction wasn't admitted into the queue of the peer, because the queue is full.
+        ///
+        /// Submitting the transaction again is likely to succeed after some time,
+        /// when committed transactions leave the queue.
+        #[derive(
+            Debug,
+            Display,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[display(
+            fmt = "Transaction queue is full ({queue_len} transactions), retry after {retry_after_ms} ms"
+        )]
+        #[ffi_type]
+        pub struct QueueFull {
+            /// Time after which the transaction should be submitted again
+            pub retry_after_ms: u64,
+            /// Number of transactions in the queue
+            pub queue_len: u64,
+        }
+
+        /// Transaction wasn't accepted, because the peer is partitioned from a quorum
+        /// of the other peers and can't tell whether its state is the latest one.
+        ///
+        /// The transaction should be submitted to another peer.
+        #[derive(
+            Debug,
+            Display,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[display(
+            fmt = "Peer is degraded: only {reachable_peers} out of the quorum of {quorum} peers are reachable for {partitioned_for_ms} ms"
+        )]
+        #[ffi_type]
+        pub struct PeerDegraded {
+            /// Time since the quorum of peers became unreachable
+            pub partitioned_for_ms: u64,
+            /// Number of peers of the commit topology reachable by the peer, including itself
+            pub reachable_peers: u64,
+            /// Number of peers required to commit a block
+            pub quorum: u64,
+        }
+
         /// The reason for rejecting transaction which happened because of transaction.
         #[derive(
             Debug,
@@ -541,6 +1057,58 @@ pub mod error {
             InstructionExecution(#[cfg_attr(feature = "std", source)] InstructionExecutionFail),
             /// Failure in WebAssembly execution
             WasmExecution(#[cfg_attr(feature = "std", source)] WasmExecutionFail),
+            /// Idempotency key `{0}` was already used by a committed transaction
+            DuplicateIdempotencyKey(
+                #[skip_from]
+                #[skip_try_from]
+                Name,
+            ),
+            /// Account `{0}` is frozen
+            AccountFrozen(
+                #[skip_from]
+                #[skip_try_from]
+                AccountId,
+            ),
+            /// Transaction metadata names an invalid app: {0}
+            InvalidAppId(
+                #[skip_from]
+                #[skip_try_from]
+                String,
+            ),
+            /// Transaction is attributed to app `{0}`, which is not registered
+            UnknownApp(
+                #[skip_from]
+                #[skip_try_from]
+                AppId,
+            ),
+            /// Transaction is attributed to app `{0}`, but isn't signed with the key of the app
+            AppKeyMismatch(
+                #[skip_from]
+                #[skip_try_from]
+                AppId,
+            ),
+            /// Submitter can't act on behalf of the authority: {0}
+            ProxyNotAuthorized(
+                #[skip_from]
+                #[skip_try_from]
+                String,
+            ),
+        }
+    }
+
+    impl TransactionRejectionReason {
+        /// Whether the transaction was rejected before the app named in its metadata
+        /// was verified, in which case the transaction isn't attributed to the app.
+        pub fn precedes_app_verification(&self) -> bool {
+            matches!(
+                self,
+                Self::AccountDoesNotExist(_)
+                    | Self::AccountFrozen(_)
+                    | Self::ProxyNotAuthorized(_)
+                    | Self::InvalidAppId(_)
+                    | Self::UnknownApp(_)
+                    | Self::AppKeyMismatch(_)
+            )
         }
     }
 
@@ -561,6 +1129,26 @@ pub mod error {
                 SetParameter(_) => "set parameter",
                 Upgrade(_) => "upgrade",
                 Log(_) => "log",
+                LockEscrow(_) => "lock escrow",
+                ClaimEscrow(_) => "claim escrow",
+                RefundEscrow(_) => "refund escrow",
+                TransferAll(_) => "transfer all",
+                BurnAll(_) => "burn all",
+                Freeze(_) => "freeze",
+                Unfreeze(_) => "unfreeze",
+                DelegateToTrigger(_) => "delegate to trigger",
+                RevokeTriggerDelegation(_) => "revoke trigger delegation",
+                RemoveKeyRange(_) => "remove key range",
+                RegisterPeerCertificate(_) => "register peer certificate",
+                SetRecoveryPolicy(_) => "set recovery policy",
+                InitiateRecovery(_) => "initiate recovery",
+                VetoRecovery(_) => "veto recovery",
+                FinalizeRecovery(_) => "finalize recovery",
+                TransferMultiple(_) => "transfer multiple",
+                RegisterApp(_) => "register app",
+                UnregisterApp(_) => "unregister app",
+                GrantProxy(_) => "grant proxy",
+                RevokeProxy(_) => "revoke proxy",
                 Custom(_) => "custom",
             };
             write!(
@@ -580,10 +1168,19 @@ pub mod error {
     #[cfg(feature = "std")]
     impl std::error::Error for WasmExecutionFail {}
 
+    #[cfg(feature = "std")]
+    impl std::error::Error for QueueFull {}
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for PeerDegraded {}
+
     pub mod prelude {
         //! The prelude re-exports most commonly used traits, structs and macros from this module.
 
-        pub use super::{InstructionExecutionFail, TransactionRejectionReason, WasmExecutionFail};
+        pub use super::{
+            InstructionExecutionFail, PeerDegraded, QueueFull, TransactionRejectionReason,
+            WasmExecutionFail,
+        };
     }
 }
 
@@ -597,12 +1194,15 @@ mod http {
         use super::*;
 
         /// Structure that represents the initial state of a transaction before the transaction receives any signatures.
-        #[derive(Debug, Clone)]
-        #[repr(transparent)]
+        #[derive(Debug, Clone, Decode, Encode)]
         #[must_use]
         pub struct TransactionBuilder {
             /// [`Transaction`] payload.
-            pub(super) payload: TransactionPayload,
+            ///
+            /// Fields missing in [`Self::version`] are dropped when the transaction is signed.
+            pub(super) payload: TransactionPayloadV2,
+            /// Version of the transaction to build.
+            pub(super) version: TransactionVersion,
         }
     }
 
@@ -610,15 +1210,21 @@ mod http {
         #[cfg(feature = "std")]
         fn new_with_time(chain: ChainId, authority: AccountId, creation_time_ms: u64) -> Self {
             Self {
-                payload: TransactionPayload {
+                payload: TransactionPayloadV2 {
                     chain,
                     authority,
                     creation_time_ms,
-                    nonce: None,
+                    nonce: 0,
+                    fee_cap: Numeric::ZERO,
+                    memo: None,
+                    idempotency_key: None,
+                    submitter: None,
                     time_to_live_ms: None,
+                    not_before_ms: None,
                     instructions: Vec::<InstructionBox>::new().into(),
                     metadata: Metadata::default(),
                 },
+                version: TransactionVersion::default(),
             }
         }
 
@@ -691,7 +1297,44 @@ mod http {
 
         /// Set nonce for [`Transaction`]
         pub fn set_nonce(&mut self, nonce: NonZeroU32) -> &mut Self {
-            self.payload.nonce = Some(nonce);
+            self.payload.nonce = nonce.get();
+            self
+        }
+
+        /// Set the maximum fee the authority agrees to pay for [`Transaction`]
+        pub fn set_fee_cap(&mut self, fee_cap: Numeric) -> &mut Self {
+            self.payload.fee_cap = fee_cap;
+            self
+        }
+
+        /// Attach a free-form note to [`Transaction`]
+        pub fn set_memo(&mut self, memo: impl Into<String>) -> &mut Self {
+            self.payload.memo = Some(memo.into());
+            self
+        }
+
+        /// Build a transaction of the given `version`, e.g. to submit it to peers
+        /// which don't accept the latest version yet.
+        ///
+        /// Fee cap, memo and submitter are dropped from transactions of version 1.
+        pub fn set_version(&mut self, version: TransactionVersion) -> &mut Self {
+            self.version = version;
+            self
+        }
+
+        /// Set idempotency key for [`Transaction`]
+        pub fn set_idempotency_key(&mut self, key: Name) -> &mut Self {
+            self.payload.idempotency_key = Some(key);
+            self
+        }
+
+        /// Submit [`Transaction`] on behalf of the authority by the `submitter`,
+        /// which then signs it instead of the authority.
+        ///
+        /// The authority must have granted a [`ProxyToken`](crate::proxy::ProxyToken)
+        /// covering the instructions of the transaction to the submitter.
+        pub fn set_submitter(&mut self, submitter: AccountId) -> &mut Self {
+            self.payload.submitter = Some(submitter);
             self
         }
 
@@ -712,6 +1355,17 @@ mod http {
             self
         }
 
+        /// Schedule [`Transaction`] so that it isn't included in a block created
+        /// before `not_before` since the Unix epoch
+        pub fn set_not_before(&mut self, not_before: Duration) -> &mut Self {
+            let not_before: u64 = not_before
+                .as_millis()
+                .try_into()
+                .expect("INTERNAL BUG: Unix timestamp exceedes u64::MAX");
+            self.payload.not_before_ms = NonZeroU64::new(not_before);
+            self
+        }
+
         /// Set creation time of transaction
         pub fn set_creation_time(&mut self, value: Duration) -> &mut Self {
             self.payload.creation_time_ms = u64::try_from(value.as_millis())
@@ -719,16 +1373,70 @@ mod http {
             self
         }
 
+        /// Account on behalf of which the transaction is submitted
+        pub fn authority(&self) -> &AccountId {
+            &self.payload.authority
+        }
+
+        /// Account which must sign the transaction, i.e. the submitter if it is set
+        /// and the authority otherwise
+        pub fn signer(&self) -> &AccountId {
+            self.payload
+                .submitter
+                .as_ref()
+                .unwrap_or(&self.payload.authority)
+        }
+
+        /// Hash of the transaction payload, which is what the signer signs
+        pub fn signing_hash(&self) -> iroha_crypto::Hash {
+            match self.version {
+                TransactionVersion::V1 => {
+                    iroha_crypto::HashOf::new(&TransactionPayload::from(self.payload.clone()))
+                        .into()
+                }
+                TransactionVersion::V2 => iroha_crypto::HashOf::new(&self.payload).into(),
+            }
+        }
+
+        /// Complete the transaction with the `signature` of [`Self::signing_hash`]
+        /// made elsewhere, e.g. by a hardware wallet.
+        ///
+        /// # Errors
+        /// If the signature isn't made by the key of [`Self::signer`]
+        pub fn with_signature(
+            self,
+            signature: iroha_crypto::Signature,
+        ) -> Result<SignedTransaction, iroha_crypto::Error> {
+            Ok(match self.version {
+                TransactionVersion::V1 => {
+                    let payload = TransactionPayload::from(self.payload);
+                    let signature = Unverified::new(SignatureOf::from_signature(signature))
+                        .verify(&payload.authority.signatory, &payload)?;
+                    SignedTransactionV1::new(payload, signature).into()
+                }
+                TransactionVersion::V2 => {
+                    let signer = self.signer().signatory.clone();
+                    let signature = Unverified::new(SignatureOf::from_signature(signature))
+                        .verify(&signer, &self.payload)?;
+                    SignedTransactionV2::new(self.payload, signature).into()
+                }
+            })
+        }
+
         /// Sign transaction with provided key pair.
         #[must_use]
         pub fn sign(self, private_key: &iroha_crypto::PrivateKey) -> SignedTransaction {
-            let signature = TransactionSignature(SignatureOf::new(private_key, &self.payload));
-
-            SignedTransactionV1 {
-                signature,
-                payload: self.payload,
+            match self.version {
+                TransactionVersion::V1 => {
+                    let payload = TransactionPayload::from(self.payload);
+                    let signature = Verified::sign(private_key, &payload);
+                    SignedTransactionV1::new(payload, signature).into()
+                }
+                TransactionVersion::V2 => {
+                    let signature = Verified::sign(private_key, &self.payload);
+                    SignedTransactionV2::new(self.payload, signature).into()
+                }
             }
-            .into()
         }
     }
 }
@@ -738,7 +1446,9 @@ pub mod prelude {
     #[cfg(feature = "http")]
     pub use super::http::TransactionBuilder;
     pub use super::{
-        error::prelude::*, CommittedTransaction, Executable, SignedTransaction, WasmSmartContract,
+        error::prelude::*, CancelTransaction, CommittedTransaction, Executable, IdempotencyRecord,
+        InstructionTrace, RejectedSubmission, SignedCancelTransaction, SignedTransaction,
+        SubmissionStatus, TransactionSimulation, TransactionVersion, WasmSmartContract,
     };
 }
 
@@ -754,4 +1464,74 @@ mod tests {
         let contract = WasmSmartContract::from_compiled(vec![0, 1, 2, 3, 4]);
         assert_eq!(format!("{contract:?}"), "WASM binary(len = 5)");
     }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn transactions_of_both_versions_are_decoded() {
+        use iroha_crypto::KeyPair;
+        use parity_scale_codec::DecodeAll;
+
+        use crate::isi::Log;
+
+        let key_pair = KeyPair::random();
+        let authority =
+            AccountId::new("wonderland".parse().unwrap(), key_pair.public_key().clone());
+        let mut builder = TransactionBuilder::new(ChainId::from("0"), authority)
+            .with_instructions([Log::new(crate::Level::INFO, "hello".to_owned())]);
+        builder
+            .set_nonce(NonZeroU32::new(7).unwrap())
+            .set_fee_cap(Numeric::new(15, 1))
+            .set_memo("invoice 42");
+
+        let v2 = builder.clone().sign(key_pair.private_key());
+        let decoded = SignedTransaction::decode_all(&mut v2.encode().as_slice()).unwrap();
+        assert_eq!(decoded, v2);
+        assert_eq!(decoded.transaction_version(), TransactionVersion::V2);
+        assert_eq!(decoded.fee_cap(), Some(Numeric::new(15, 1)));
+        assert_eq!(decoded.memo(), Some("invoice 42"));
+
+        builder.set_version(TransactionVersion::V1);
+        let v1 = builder.sign(key_pair.private_key());
+        let decoded = SignedTransaction::decode_all(&mut v1.encode().as_slice()).unwrap();
+        assert_eq!(decoded, v1);
+        assert_eq!(decoded.transaction_version(), TransactionVersion::V1);
+        assert_eq!(decoded.nonce(), NonZeroU32::new(7));
+        assert_eq!(decoded.fee_cap(), None);
+        assert_eq!(decoded.memo(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn proxy_transaction_is_signed_by_submitter() {
+        use iroha_crypto::KeyPair;
+        use parity_scale_codec::DecodeAll;
+
+        use crate::isi::Log;
+
+        let authority = AccountId::new(
+            "wonderland".parse().unwrap(),
+            KeyPair::random().public_key().clone(),
+        );
+        let submitter_key_pair = KeyPair::random();
+        let submitter = AccountId::new(
+            "wonderland".parse().unwrap(),
+            submitter_key_pair.public_key().clone(),
+        );
+        let mut builder = TransactionBuilder::new(ChainId::from("0"), authority.clone())
+            .with_instructions([Log::new(crate::Level::INFO, "hello".to_owned())]);
+        builder.set_submitter(submitter.clone());
+        assert_eq!(builder.signer(), &submitter);
+
+        let tx = builder.sign(submitter_key_pair.private_key());
+        let decoded = SignedTransaction::decode_all(&mut tx.encode().as_slice()).unwrap();
+        assert_eq!(decoded.authority(), &authority);
+        assert_eq!(decoded.submitter(), Some(&submitter));
+        assert_eq!(decoded.signer(), &submitter);
+
+        let mut builder = TransactionBuilder::new(ChainId::from("0"), authority)
+            .with_instructions([Log::new(crate::Level::INFO, "hello".to_owned())]);
+        builder.set_submitter(submitter);
+        let tx = builder.sign(KeyPair::random().private_key());
+        assert!(SignedTransaction::decode_all(&mut tx.encode().as_slice()).is_err());
+    }
 }
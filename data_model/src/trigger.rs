@@ -9,6 +9,7 @@ use core::cmp;
 
 use derive_more::{Constructor, Display, FromStr};
 use getset::Getters;
+use iroha_crypto::Hash;
 use iroha_data_model_derive::{model, IdEqOrdHash};
 use iroha_macro::ffi_impl_opaque;
 use iroha_schema::IntoSchema;
@@ -65,6 +66,28 @@ mod model {
         /// Action to be performed when the trigger matches.
         pub action: action::Action,
     }
+
+    /// Details of a registered [`Trigger`] which anyone may inspect.
+    ///
+    /// The executable is only identified by its hash and the metadata is left out,
+    /// see [`crate::query::trigger::FindTriggerById`] to get the whole trigger.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, Getters, Decode, Encode, Deserialize, Serialize, IntoSchema,
+    )]
+    #[getset(get = "pub")]
+    #[ffi_type]
+    pub struct TriggerDetails {
+        /// [`Id`] of the [`Trigger`].
+        pub id: TriggerId,
+        /// Account executing the action of the trigger.
+        pub authority: crate::account::AccountId,
+        /// Defines events which trigger the action.
+        pub filter: EventFilterBox,
+        /// Number of the remaining executions of the action.
+        pub repeats: action::Repeats,
+        /// Hash of the SCALE encoded WASM smart contract or instructions of the action.
+        pub executable_hash: Hash,
+    }
 }
 
 #[ffi_impl_opaque]
@@ -93,7 +116,7 @@ impl Registered for Trigger {
 pub mod action {
     //! Contains trigger action and common trait for all actions
 
-    use iroha_data_model_derive::model;
+    use iroha_data_model_derive::{model, ValidatedDecode};
 
     pub use self::model::*;
     use super::*;
@@ -115,7 +138,7 @@ pub mod action {
         /// triggers without gaps, the `Executable` wrapped in the action must
         /// be run before any of the ISIs are pushed into the queue of the
         /// next block.
-        #[derive(Debug, Clone, PartialEq, Eq, Encode, Serialize, IntoSchema)]
+        #[derive(Debug, Clone, PartialEq, Eq, Encode, Serialize, ValidatedDecode, IntoSchema)]
         #[ffi_type]
         pub struct Action {
             /// The executable linked to this action
@@ -186,7 +209,7 @@ pub mod action {
             authority: AccountId,
             filter: impl Into<EventFilterBox>,
         ) -> Self {
-            let action = candidate::ActionCandidate {
+            let action = Self {
                 executable: executable.into(),
                 repeats: repeats.into(),
                 // TODO: At this point the authority is meaningless.
@@ -250,52 +273,15 @@ pub mod action {
     }
 
     mod candidate {
-        use parity_scale_codec::Input;
-
         use super::*;
 
-        #[derive(Decode, Deserialize)]
-        pub(super) struct ActionCandidate {
-            pub executable: Executable,
-            pub repeats: Repeats,
-            pub authority: AccountId,
-            pub filter: EventFilterBox,
-            pub metadata: Metadata,
-        }
-
-        impl ActionCandidate {
-            pub(super) fn validate(self) -> Result<Action, &'static str> {
+        impl Action {
+            pub(super) fn validate(self) -> Result<Self, &'static str> {
                 if matches!(self.filter, EventFilterBox::TriggerCompleted(_)) {
                     return Err("TriggerCompleted cannot be used as filter for triggering actions");
                 }
 
-                Ok(Action {
-                    executable: self.executable,
-                    repeats: self.repeats,
-                    authority: self.authority,
-                    filter: self.filter,
-                    metadata: self.metadata,
-                })
-            }
-        }
-
-        impl Decode for Action {
-            fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
-                ActionCandidate::decode(input)?
-                    .validate()
-                    .map_err(Into::into)
-            }
-        }
-        impl<'de> Deserialize<'de> for Action {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                use serde::de::Error as _;
-
-                ActionCandidate::deserialize(deserializer)?
-                    .validate()
-                    .map_err(D::Error::custom)
+                Ok(self)
             }
         }
     }
@@ -309,5 +295,5 @@ pub mod action {
 pub mod prelude {
     //! Re-exports of commonly used types.
 
-    pub use super::{action::prelude::*, Trigger, TriggerId};
+    pub use super::{action::prelude::*, Trigger, TriggerDetails, TriggerId};
 }
@@ -47,6 +47,26 @@ pub trait Visit {
         visit_execute_trigger(&ExecuteTrigger),
         visit_set_parameter(&SetParameter),
         visit_log(&Log),
+        visit_lock_escrow(&LockEscrow),
+        visit_claim_escrow(&ClaimEscrow),
+        visit_refund_escrow(&RefundEscrow),
+        visit_transfer_all(&TransferAll),
+        visit_burn_all(&BurnAll),
+        visit_freeze(&Freeze),
+        visit_unfreeze(&Unfreeze),
+        visit_delegate_to_trigger(&DelegateToTrigger),
+        visit_revoke_trigger_delegation(&RevokeTriggerDelegation),
+        visit_remove_key_range(&RemoveKeyRange),
+        visit_register_peer_certificate(&RegisterPeerCertificate),
+        visit_set_recovery_policy(&SetRecoveryPolicy),
+        visit_initiate_recovery(&InitiateRecovery),
+        visit_veto_recovery(&VetoRecovery),
+        visit_finalize_recovery(&FinalizeRecovery),
+        visit_transfer_multiple(&TransferMultiple),
+        visit_register_app(&RegisterApp),
+        visit_unregister_app(&UnregisterApp),
+        visit_grant_proxy(&GrantProxy),
+        visit_revoke_proxy(&RevokeProxy),
         visit_custom(&CustomInstruction),
 
         // Visit SingularQueryBox
@@ -60,25 +80,35 @@ pub trait Visit {
         visit_find_asset_metadata(&FindAssetMetadata),
         visit_find_asset_definition_metadata(&FindAssetDefinitionMetadata),
         visit_find_trigger_metadata(&FindTriggerMetadata),
+        visit_find_trigger_receipts(&FindTriggerReceipts),
+        visit_find_peer_certificate(&FindPeerCertificate),
         visit_find_transaction_by_hash(&FindTransactionByHash),
         visit_find_block_header_by_hash(&FindBlockHeaderByHash),
+        visit_find_block_state_diff(&FindBlockStateDiff),
+        visit_find_block_rewards(&FindBlockRewards),
+        visit_find_idempotency_record(&FindIdempotencyRecord),
 
         // Visit IterableQueryBox
         visit_find_domains(&QueryWithFilterFor<FindDomains>),
         visit_find_accounts(&QueryWithFilterFor<FindAccounts>),
         visit_find_assets(&QueryWithFilterFor<FindAssets>),
         visit_find_assets_definitions(&QueryWithFilterFor<FindAssetsDefinitions>),
+        visit_find_store_entries(&QueryWithFilterFor<FindStoreEntries>),
         visit_find_roles(&QueryWithFilterFor<FindRoles>),
         visit_find_role_ids(&QueryWithFilterFor<FindRoleIds>),
         visit_find_permissions_by_account_id(&QueryWithFilterFor<FindPermissionsByAccountId>),
         visit_find_roles_by_account_id(&QueryWithFilterFor<FindRolesByAccountId>),
         visit_find_transactions_by_account_id(&QueryWithFilterFor<FindTransactionsByAccountId>),
+        visit_find_transactions_by_app_id(&QueryWithFilterFor<FindTransactionsByAppId>),
         visit_find_accounts_with_asset(&QueryWithFilterFor<FindAccountsWithAsset>),
         visit_find_peers(&QueryWithFilterFor<FindPeers>),
         visit_find_active_trigger_ids(&QueryWithFilterFor<FindActiveTriggerIds>),
+        visit_find_all_active_triggers(&QueryWithFilterFor<FindAllActiveTriggers>),
+        visit_find_triggers_by_domain(&QueryWithFilterFor<FindTriggersByDomain>),
         visit_find_transactions(&QueryWithFilterFor<FindTransactions>),
         visit_find_blocks(&QueryWithFilterFor<FindBlocks>),
         visit_find_block_headers(&QueryWithFilterFor<FindBlockHeaders>),
+        visit_find_events(&QueryWithFilterFor<FindEvents>),
 
         // Visit RegisterBox
         visit_register_peer(&Register<Peer>),
@@ -178,8 +208,13 @@ pub fn visit_singular_query<V: Visit + ?Sized>(
         visit_find_asset_metadata(FindAssetMetadata),
         visit_find_asset_definition_metadata(FindAssetDefinitionMetadata),
         visit_find_trigger_metadata(FindTriggerMetadata),
+        visit_find_trigger_receipts(FindTriggerReceipts),
+        visit_find_peer_certificate(FindPeerCertificate),
         visit_find_transaction_by_hash(FindTransactionByHash),
         visit_find_block_header_by_hash(FindBlockHeaderByHash),
+        visit_find_block_state_diff(FindBlockStateDiff),
+        visit_find_block_rewards(FindBlockRewards),
+        visit_find_idempotency_record(FindIdempotencyRecord),
     }
 }
 
@@ -201,17 +236,22 @@ pub fn visit_iter_query<V: Visit + ?Sized>(
         visit_find_accounts(FindAccounts),
         visit_find_assets(FindAssets),
         visit_find_assets_definitions(FindAssetsDefinitions),
+        visit_find_store_entries(FindStoreEntries),
         visit_find_roles(FindRoles),
         visit_find_role_ids(FindRoleIds),
         visit_find_permissions_by_account_id(FindPermissionsByAccountId),
         visit_find_roles_by_account_id(FindRolesByAccountId),
         visit_find_transactions_by_account_id(FindTransactionsByAccountId),
+        visit_find_transactions_by_app_id(FindTransactionsByAppId),
         visit_find_accounts_with_asset(FindAccountsWithAsset),
         visit_find_peers(FindPeers),
         visit_find_active_trigger_ids(FindActiveTriggerIds),
+        visit_find_all_active_triggers(FindAllActiveTriggers),
+        visit_find_triggers_by_domain(FindTriggersByDomain),
         visit_find_transactions(FindTransactions),
         visit_find_block_headers(FindBlockHeaders),
         visit_find_blocks(FindBlocks),
+        visit_find_events(FindEvents),
     }
 }
 
@@ -263,6 +303,60 @@ pub fn visit_instruction<V: Visit + ?Sized>(
             visitor.visit_unregister(authority, variant_value)
         }
         InstructionBox::Upgrade(variant_value) => visitor.visit_upgrade(authority, variant_value),
+        InstructionBox::LockEscrow(variant_value) => {
+            visitor.visit_lock_escrow(authority, variant_value)
+        }
+        InstructionBox::ClaimEscrow(variant_value) => {
+            visitor.visit_claim_escrow(authority, variant_value)
+        }
+        InstructionBox::RefundEscrow(variant_value) => {
+            visitor.visit_refund_escrow(authority, variant_value)
+        }
+        InstructionBox::TransferAll(variant_value) => {
+            visitor.visit_transfer_all(authority, variant_value)
+        }
+        InstructionBox::BurnAll(variant_value) => visitor.visit_burn_all(authority, variant_value),
+        InstructionBox::Freeze(variant_value) => visitor.visit_freeze(authority, variant_value),
+        InstructionBox::Unfreeze(variant_value) => visitor.visit_unfreeze(authority, variant_value),
+        InstructionBox::DelegateToTrigger(variant_value) => {
+            visitor.visit_delegate_to_trigger(authority, variant_value)
+        }
+        InstructionBox::RevokeTriggerDelegation(variant_value) => {
+            visitor.visit_revoke_trigger_delegation(authority, variant_value)
+        }
+        InstructionBox::RemoveKeyRange(variant_value) => {
+            visitor.visit_remove_key_range(authority, variant_value)
+        }
+        InstructionBox::RegisterPeerCertificate(variant_value) => {
+            visitor.visit_register_peer_certificate(authority, variant_value)
+        }
+        InstructionBox::SetRecoveryPolicy(variant_value) => {
+            visitor.visit_set_recovery_policy(authority, variant_value)
+        }
+        InstructionBox::InitiateRecovery(variant_value) => {
+            visitor.visit_initiate_recovery(authority, variant_value)
+        }
+        InstructionBox::VetoRecovery(variant_value) => {
+            visitor.visit_veto_recovery(authority, variant_value)
+        }
+        InstructionBox::FinalizeRecovery(variant_value) => {
+            visitor.visit_finalize_recovery(authority, variant_value)
+        }
+        InstructionBox::TransferMultiple(variant_value) => {
+            visitor.visit_transfer_multiple(authority, variant_value)
+        }
+        InstructionBox::RegisterApp(variant_value) => {
+            visitor.visit_register_app(authority, variant_value)
+        }
+        InstructionBox::UnregisterApp(variant_value) => {
+            visitor.visit_unregister_app(authority, variant_value)
+        }
+        InstructionBox::GrantProxy(variant_value) => {
+            visitor.visit_grant_proxy(authority, variant_value)
+        }
+        InstructionBox::RevokeProxy(variant_value) => {
+            visitor.visit_revoke_proxy(authority, variant_value)
+        }
         InstructionBox::Custom(custom) => visitor.visit_custom(authority, custom),
     }
 }
@@ -434,6 +528,26 @@ leaf_visitors! {
     visit_set_parameter(&SetParameter),
     visit_execute_trigger(&ExecuteTrigger),
     visit_log(&Log),
+    visit_lock_escrow(&LockEscrow),
+    visit_claim_escrow(&ClaimEscrow),
+    visit_refund_escrow(&RefundEscrow),
+    visit_transfer_all(&TransferAll),
+    visit_burn_all(&BurnAll),
+    visit_freeze(&Freeze),
+    visit_unfreeze(&Unfreeze),
+    visit_delegate_to_trigger(&DelegateToTrigger),
+    visit_revoke_trigger_delegation(&RevokeTriggerDelegation),
+    visit_remove_key_range(&RemoveKeyRange),
+    visit_register_peer_certificate(&RegisterPeerCertificate),
+    visit_set_recovery_policy(&SetRecoveryPolicy),
+    visit_initiate_recovery(&InitiateRecovery),
+    visit_veto_recovery(&VetoRecovery),
+    visit_finalize_recovery(&FinalizeRecovery),
+    visit_transfer_multiple(&TransferMultiple),
+    visit_register_app(&RegisterApp),
+    visit_unregister_app(&UnregisterApp),
+    visit_grant_proxy(&GrantProxy),
+    visit_revoke_proxy(&RevokeProxy),
     visit_custom(&CustomInstruction),
 
     // Singular Quert visitors
@@ -447,23 +561,33 @@ leaf_visitors! {
     visit_find_asset_metadata(&FindAssetMetadata),
     visit_find_asset_definition_metadata(&FindAssetDefinitionMetadata),
     visit_find_trigger_metadata(&FindTriggerMetadata),
+    visit_find_trigger_receipts(&FindTriggerReceipts),
+    visit_find_peer_certificate(&FindPeerCertificate),
     visit_find_transaction_by_hash(&FindTransactionByHash),
     visit_find_block_header_by_hash(&FindBlockHeaderByHash),
+    visit_find_block_state_diff(&FindBlockStateDiff),
+    visit_find_block_rewards(&FindBlockRewards),
+    visit_find_idempotency_record(&FindIdempotencyRecord),
 
     // Iterable Query visitors
     visit_find_domains(&QueryWithFilterFor<FindDomains>),
     visit_find_accounts(&QueryWithFilterFor<FindAccounts>),
     visit_find_assets(&QueryWithFilterFor<FindAssets>),
     visit_find_assets_definitions(&QueryWithFilterFor<FindAssetsDefinitions>),
+    visit_find_store_entries(&QueryWithFilterFor<FindStoreEntries>),
     visit_find_roles(&QueryWithFilterFor<FindRoles>),
     visit_find_role_ids(&QueryWithFilterFor<FindRoleIds>),
     visit_find_permissions_by_account_id(&QueryWithFilterFor<FindPermissionsByAccountId>),
     visit_find_roles_by_account_id(&QueryWithFilterFor<FindRolesByAccountId>),
     visit_find_transactions_by_account_id(&QueryWithFilterFor<FindTransactionsByAccountId>),
+    visit_find_transactions_by_app_id(&QueryWithFilterFor<FindTransactionsByAppId>),
     visit_find_accounts_with_asset(&QueryWithFilterFor<FindAccountsWithAsset>),
     visit_find_peers(&QueryWithFilterFor<FindPeers>),
     visit_find_active_trigger_ids(&QueryWithFilterFor<FindActiveTriggerIds>),
+    visit_find_all_active_triggers(&QueryWithFilterFor<FindAllActiveTriggers>),
+    visit_find_triggers_by_domain(&QueryWithFilterFor<FindTriggersByDomain>),
     visit_find_transactions(&QueryWithFilterFor<FindTransactions>),
     visit_find_blocks(&QueryWithFilterFor<FindBlocks>),
     visit_find_block_headers(&QueryWithFilterFor<FindBlockHeaders>),
+    visit_find_events(&QueryWithFilterFor<FindEvents>),
 }
@@ -0,0 +1,135 @@
+//! Decoding of malformed and malicious input received from the network
+//! must fail gracefully instead of panicking or exhausting resources.
+use iroha_crypto::KeyPair;
+use iroha_data_model::{isi::Log, prelude::*, Level};
+use iroha_version::scale::{decode_all_with_limits, DecodeLimits, DecodeVersioned};
+use parity_scale_codec::{Compact, Encode};
+
+type Predicate = CompoundPredicate<AccountPredicateBox>;
+
+/// Simple deterministic generator, so that failures can be reproduced
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn transaction() -> SignedTransaction {
+    let key_pair = KeyPair::random();
+    let authority: AccountId = format!("{}@wonderland", key_pair.public_key())
+        .parse()
+        .unwrap();
+    TransactionBuilder::new(
+        ChainId::from("00000000-0000-0000-0000-000000000000"),
+        authority,
+    )
+    .with_instructions([Log::new(Level::INFO, "decode limits".to_owned())])
+    .sign(key_pair.private_key())
+}
+
+fn nested_predicate(depth: usize) -> Predicate {
+    (0..depth).fold(Predicate::PASS, |predicate, _| {
+        Predicate::Not(Box::new(predicate))
+    })
+}
+
+#[test]
+fn random_input_is_rejected() {
+    let mut rng = XorShift(0x5EED);
+
+    for _ in 0..10_000 {
+        let len = rng.below(512);
+        let input = rng.bytes(len);
+
+        let _ = decode_all_with_limits::<SignedTransaction>(&input, DecodeLimits::DEFAULT);
+        let _ = decode_all_with_limits::<SignedBlock>(&input, DecodeLimits::DEFAULT);
+        let _ = decode_all_with_limits::<Predicate>(&input, DecodeLimits::DEFAULT);
+        let _ = SignedTransaction::decode_all_versioned_with_limits(&input, DecodeLimits::DEFAULT);
+    }
+}
+
+#[test]
+fn mutated_transaction_is_decoded_without_panic() {
+    let mut rng = XorShift(0xDEC0DE);
+    let encoded = transaction().encode();
+
+    for _ in 0..10_000 {
+        let mut input = encoded.clone();
+        for _ in 0..=rng.below(4) {
+            let idx = rng.below(input.len());
+            input[idx] = rng.next() as u8;
+        }
+        if rng.below(4) == 0 {
+            input.truncate(rng.below(input.len()));
+        }
+
+        let _ = decode_all_with_limits::<SignedTransaction>(&input, DecodeLimits::DEFAULT);
+    }
+}
+
+#[test]
+fn huge_declared_length_is_rejected() {
+    let mut input = Compact(u32::MAX).encode();
+    input.extend([0; 16]);
+
+    assert!(decode_all_with_limits::<Vec<u8>>(&input, DecodeLimits::DEFAULT).is_err());
+    assert!(
+        decode_all_with_limits::<Vec<SignedTransaction>>(&input, DecodeLimits::DEFAULT).is_err()
+    );
+    assert!(decode_all_with_limits::<String>(&input, DecodeLimits::DEFAULT).is_err());
+}
+
+#[test]
+fn too_deep_nesting_is_rejected() {
+    let limits = DecodeLimits {
+        max_depth: 64,
+        ..DecodeLimits::DEFAULT
+    };
+
+    let shallow = nested_predicate(16);
+    let decoded = decode_all_with_limits::<Predicate>(&shallow.encode(), limits).unwrap();
+    assert_eq!(decoded, shallow);
+
+    let deep = nested_predicate(1_000);
+    assert!(decode_all_with_limits::<Predicate>(&deep.encode(), limits).is_err());
+}
+
+#[test]
+fn too_long_input_is_rejected() {
+    let encoded = transaction().encode();
+    let limits = DecodeLimits {
+        max_len: encoded.len() - 1,
+        ..DecodeLimits::DEFAULT
+    };
+
+    assert!(decode_all_with_limits::<SignedTransaction>(&encoded, DecodeLimits::DEFAULT).is_ok());
+    assert!(decode_all_with_limits::<SignedTransaction>(&encoded, limits).is_err());
+}
+
+#[test]
+fn unsupported_version_is_rejected() {
+    let mut encoded = transaction().encode();
+    assert!(
+        SignedTransaction::decode_all_versioned_with_limits(&encoded, DecodeLimits::DEFAULT)
+            .is_ok()
+    );
+
+    encoded[0] = 42;
+    assert!(matches!(
+        SignedTransaction::decode_all_versioned_with_limits(&encoded, DecodeLimits::DEFAULT),
+        Err(iroha_version::error::Error::UnsupportedVersion(_))
+    ));
+}
@@ -1,11 +1,13 @@
 //! Genesis-related logic and constructs. Contains the [`GenesisBlock`],
 //! [`RawGenesisTransaction`] and the [`GenesisBuilder`] structures.
 use std::{
+    collections::BTreeSet,
     fmt::Debug,
     fs,
     fs::File,
     io::BufReader,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use eyre::{eyre, Result, WrapErr};
@@ -13,6 +15,11 @@ use iroha_crypto::{KeyPair, PublicKey};
 use iroha_data_model::{
     block::SignedBlock, isi::Instruction, parameter::Parameter, peer::Peer, prelude::*,
 };
+use iroha_executor_data_model::permission::{
+    asset::CanMintAssetWithDefinition, event::CanSubscribeToAllEvents,
+    executor::CanUpgradeExecutor, parameter::CanSetParameters, peer::CanUnregisterAnyPeer,
+    role::CanUnregisterAnyRole,
+};
 use iroha_schema::IntoSchema;
 use once_cell::sync::Lazy;
 use parity_scale_codec::{Decode, Encode};
@@ -43,6 +50,9 @@ pub struct RawGenesisTransaction {
     #[serde(default)]
     parameters: Vec<Parameter>,
     instructions: Vec<InstructionBox>,
+    /// Accounts administering the network
+    #[serde(default)]
+    system_accounts: Vec<SystemAccount>,
     /// Initial topology
     topology: Vec<PeerId>,
 }
@@ -106,6 +116,7 @@ impl RawGenesisTransaction {
         let executor = get_executor(&self.executor.0)?;
         let genesis = build_and_sign_genesis(
             self.instructions,
+            self.system_accounts,
             executor,
             self.chain,
             genesis_key_pair,
@@ -118,6 +129,7 @@ impl RawGenesisTransaction {
 
 fn build_and_sign_genesis(
     instructions: Vec<InstructionBox>,
+    system_accounts: Vec<SystemAccount>,
     executor: Executor,
     chain_id: ChainId,
     genesis_key_pair: &KeyPair,
@@ -126,6 +138,7 @@ fn build_and_sign_genesis(
 ) -> GenesisBlock {
     let transactions = build_transactions(
         instructions,
+        system_accounts,
         executor,
         parameters,
         topology,
@@ -138,6 +151,7 @@ fn build_and_sign_genesis(
 
 fn build_transactions(
     instructions: Vec<InstructionBox>,
+    system_accounts: Vec<SystemAccount>,
     executor: Executor,
     parameters: Vec<Parameter>,
     topology: Vec<PeerId>,
@@ -174,9 +188,19 @@ fn build_transactions(
         transactions.push(parameters);
     }
     if !instructions.is_empty() {
-        let transaction_instructions = build_transaction(instructions, chain_id, genesis_key_pair);
+        let transaction_instructions =
+            build_transaction(instructions, chain_id.clone(), genesis_key_pair);
         transactions.push(transaction_instructions);
     }
+    // After the instructions, which may register the domains of the system accounts
+    if !system_accounts.is_empty() {
+        let register_system_accounts = build_transaction(
+            SystemAccount::into_instructions(system_accounts),
+            chain_id,
+            genesis_key_pair,
+        );
+        transactions.push(register_system_accounts);
+    }
     transactions
 }
 
@@ -208,6 +232,7 @@ fn get_executor(file: &Path) -> Result<Executor> {
 pub struct GenesisBuilder {
     instructions: Vec<InstructionBox>,
     parameters: Vec<Parameter>,
+    system_accounts: Vec<SystemAccount>,
 }
 
 /// `Domain` subsection of the [`GenesisBuilder`]. Makes
@@ -217,6 +242,7 @@ pub struct GenesisBuilder {
 pub struct GenesisDomainBuilder {
     instructions: Vec<InstructionBox>,
     parameters: Vec<Parameter>,
+    system_accounts: Vec<SystemAccount>,
     domain_id: DomainId,
 }
 
@@ -240,6 +266,7 @@ impl GenesisBuilder {
         GenesisDomainBuilder {
             instructions: self.instructions,
             parameters: self.parameters,
+            system_accounts: self.system_accounts,
             domain_id,
         }
     }
@@ -250,12 +277,24 @@ impl GenesisBuilder {
         self
     }
 
+    /// Register the [`Faucet`] trigger and grant its owner the permission to mint
+    pub fn faucet(mut self, faucet: Faucet) -> Self {
+        self.instructions.extend(faucet.into_instructions());
+        self
+    }
+
     /// Add parameter to the end of parameter list
     pub fn append_parameter(mut self, parameter: Parameter) -> Self {
         self.parameters.push(parameter);
         self
     }
 
+    /// Register the [`SystemAccount`] and grant it its roles
+    pub fn system_account(mut self, account: SystemAccount) -> Self {
+        self.system_accounts.push(account);
+        self
+    }
+
     /// Finish building, sign, and produce a [`GenesisBlock`].
     pub fn build_and_sign(
         self,
@@ -266,6 +305,7 @@ impl GenesisBuilder {
     ) -> GenesisBlock {
         build_and_sign_genesis(
             self.instructions,
+            self.system_accounts,
             executor_blob,
             chain_id,
             genesis_key_pair,
@@ -283,6 +323,7 @@ impl GenesisBuilder {
     ) -> RawGenesisTransaction {
         RawGenesisTransaction {
             instructions: self.instructions,
+            system_accounts: self.system_accounts,
             executor: ExecutorPath(executor_file),
             parameters: self.parameters,
             chain: chain_id,
@@ -298,6 +339,7 @@ impl GenesisDomainBuilder {
         GenesisBuilder {
             instructions: self.instructions,
             parameters: self.parameters,
+            system_accounts: self.system_accounts,
         }
     }
 
@@ -324,6 +366,202 @@ impl GenesisDomainBuilder {
     }
 }
 
+/// Rate-limited faucet for test networks.
+///
+/// An account requests funds by setting [`Faucet::REQUEST_KEY`] in its own metadata.
+/// The faucet trigger then mints the configured amount of the asset to this account,
+/// unless it has already done so within the cooldown period.
+///
+/// The trigger logic is the `faucet_trigger` sample compiled to WASM,
+/// its settings are stored in the trigger metadata.
+#[derive(Debug, Clone)]
+pub struct Faucet {
+    id: TriggerId,
+    wasm: WasmSmartContract,
+    owner: AccountId,
+    asset_definition: AssetDefinitionId,
+    amount: Numeric,
+    cooldown: Duration,
+}
+
+impl Faucet {
+    /// Account metadata key which requests funds from the faucet
+    pub const REQUEST_KEY: &'static str = "faucet_request";
+    /// Trigger metadata key of the [`AssetDefinitionId`] to mint
+    pub const ASSET_KEY: &'static str = "asset";
+    /// Trigger metadata key of the [`Numeric`] amount minted per request
+    pub const AMOUNT_KEY: &'static str = "amount";
+    /// Trigger metadata key of the minimal time between two mints to the same account, in milliseconds
+    pub const COOLDOWN_MS_KEY: &'static str = "cooldown_ms";
+
+    /// Construct a faucet executing `wasm` on behalf of `owner`,
+    /// which mints `amount` of `asset_definition` at most once per `cooldown` to every account.
+    pub fn new(
+        id: TriggerId,
+        wasm: WasmSmartContract,
+        owner: AccountId,
+        asset_definition: AssetDefinitionId,
+        amount: Numeric,
+        cooldown: Duration,
+    ) -> Self {
+        Self {
+            id,
+            wasm,
+            owner,
+            asset_definition,
+            amount,
+            cooldown,
+        }
+    }
+
+    /// Instructions registering the faucet trigger and granting its owner the permission to mint
+    pub fn into_instructions(self) -> [InstructionBox; 2] {
+        let mut metadata = Metadata::default();
+        metadata.insert(
+            Self::ASSET_KEY.parse().expect("valid name"),
+            JsonString::new(&self.asset_definition),
+        );
+        metadata.insert(
+            Self::AMOUNT_KEY.parse().expect("valid name"),
+            JsonString::new(self.amount),
+        );
+        metadata.insert(
+            Self::COOLDOWN_MS_KEY.parse().expect("valid name"),
+            u64::try_from(self.cooldown.as_millis()).expect("cooldown should fit into u64"),
+        );
+
+        let action = Action::new(
+            self.wasm,
+            Repeats::Indefinitely,
+            self.owner.clone(),
+            AccountEventFilter::new().for_events(AccountEventSet::MetadataInserted),
+        )
+        .with_metadata(metadata);
+
+        [
+            Register::trigger(Trigger::new(self.id, action)).into(),
+            Grant::account_permission(
+                CanMintAssetWithDefinition {
+                    asset_definition: self.asset_definition,
+                },
+                self.owner,
+            )
+            .into(),
+        ]
+    }
+}
+
+/// Well-known role bundling the permissions needed to administer the network
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    IntoSchema,
+    Encode,
+    Decode,
+)]
+pub enum SystemRole {
+    /// Unregisters peers and roles, and watches all events of the network
+    NetworkAdmin,
+    /// Sets the chain parameters
+    ParameterSetter,
+    /// Upgrades the executor
+    ExecutorUpgrader,
+}
+
+impl SystemRole {
+    /// Id of the role registered in genesis
+    pub fn id(self) -> RoleId {
+        let name = match self {
+            Self::NetworkAdmin => "NETWORK_ADMIN",
+            Self::ParameterSetter => "PARAMETER_SETTER",
+            Self::ExecutorUpgrader => "EXECUTOR_UPGRADER",
+        };
+        name.parse().expect("valid role name")
+    }
+
+    /// Role with the bundled permissions
+    pub fn role(self) -> NewRole {
+        let role = Role::new(self.id());
+        match self {
+            Self::NetworkAdmin => role
+                .add_permission(CanUnregisterAnyPeer)
+                .add_permission(CanUnregisterAnyRole)
+                .add_permission(CanSubscribeToAllEvents),
+            Self::ParameterSetter => role.add_permission(CanSetParameters),
+            Self::ExecutorUpgrader => role.add_permission(CanUpgradeExecutor),
+        }
+    }
+}
+
+/// Account administering the network, e.g. an admin or a treasury.
+///
+/// It is registered in genesis like any other account and granted the
+/// [`SystemRole`]s, which are registered in genesis once for all system accounts.
+/// The domain of the account has to be registered beforehand.
+#[derive(Debug, Clone, Serialize, Deserialize, IntoSchema, Encode, Decode)]
+pub struct SystemAccount {
+    /// Account to register
+    id: AccountId,
+    /// Roles to grant to the account
+    roles: Vec<SystemRole>,
+    /// Metadata of the account
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+impl SystemAccount {
+    /// Construct a system account `id` with the given `roles`
+    pub fn new(id: AccountId, roles: impl IntoIterator<Item = SystemRole>) -> Self {
+        Self {
+            id,
+            roles: roles.into_iter().collect(),
+            metadata: Metadata::default(),
+        }
+    }
+
+    /// Set the metadata of the account
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Instructions registering the roles used by the `accounts`,
+    /// then registering the accounts and granting them their roles
+    pub fn into_instructions(accounts: Vec<Self>) -> Vec<InstructionBox> {
+        let roles = accounts
+            .iter()
+            .flat_map(|account| account.roles.iter().copied())
+            .collect::<BTreeSet<_>>();
+
+        let register_roles = roles
+            .into_iter()
+            .map(|role| Register::role(role.role()).into());
+        let register_accounts = accounts.into_iter().flat_map(|account| {
+            let register =
+                Register::account(Account::new(account.id.clone()).with_metadata(account.metadata))
+                    .into();
+            let grant_roles = account
+                .roles
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(move |role| Grant::role(role.id(), account.id.clone()).into());
+            core::iter::once(register).chain(grant_roles)
+        });
+
+        register_roles.chain(register_accounts).collect()
+    }
+}
+
 impl Encode for ExecutorPath {
     fn encode(&self) -> Vec<u8> {
         self.0
@@ -484,4 +722,46 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn system_accounts_share_roles() {
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let admin_id = AccountId::new(domain_id.clone(), ALICE_KEYPAIR.public_key().clone());
+        let treasury_id = AccountId::new(domain_id, BOB_KEYPAIR.public_key().clone());
+
+        let genesis = GenesisBuilder::default()
+            .domain("wonderland".parse().unwrap())
+            .finish_domain()
+            .system_account(SystemAccount::new(
+                admin_id.clone(),
+                [SystemRole::NetworkAdmin, SystemRole::ParameterSetter],
+            ))
+            .system_account(SystemAccount::new(
+                treasury_id.clone(),
+                [SystemRole::ParameterSetter],
+            ))
+            .build_and_sign(
+                dummy_executor(),
+                ChainId::from("00000000-0000-0000-0000-000000000000"),
+                &KeyPair::random(),
+                vec![],
+            );
+
+        let transactions = genesis.0.transactions().collect::<Vec<_>>();
+        assert_eq!(transactions.len(), 3);
+        let Executable::Instructions(instructions) = transactions[2].value.instructions() else {
+            panic!("Expected instructions");
+        };
+
+        let expected: Vec<InstructionBox> = vec![
+            Register::role(SystemRole::NetworkAdmin.role()).into(),
+            Register::role(SystemRole::ParameterSetter.role()).into(),
+            Register::account(Account::new(admin_id.clone())).into(),
+            Grant::role(SystemRole::NetworkAdmin.id(), admin_id.clone()).into(),
+            Grant::role(SystemRole::ParameterSetter.id(), admin_id).into(),
+            Register::account(Account::new(treasury_id.clone())).into(),
+            Grant::role(SystemRole::ParameterSetter.id(), treasury_id).into(),
+        ];
+        assert_eq!(instructions.iter().cloned().collect::<Vec<_>>(), expected);
+    }
 }
@@ -0,0 +1,288 @@
+//! Verification of the chain by clients which don't run a peer, e.g. on mobile or embedded devices.
+//!
+//! A [`LightClient`] starts from a trusted genesis block together with the genesis topology and
+//! follows the chain block by block. Every block has to extend the previous one and carry the
+//! signatures of a quorum of the peers in the roles the consensus assigned them for the block.
+//! The topology is then rearranged the same way the peers rearrange it, taking the peers
+//! registered and unregistered by the block into account.
+//!
+//! Peer changes are only observed in the instructions of the committed transactions. If the
+//! peers are changed otherwise, e.g. by a trigger or a WASM smart contract, the following blocks
+//! fail the verification and the client has to start again from a trusted topology.
+//!
+//! Block headers don't commit to the world state yet, so proofs of individual accounts and
+//! assets can't be verified until headers carry a state root.
+
+pub mod topology;
+
+use core::num::NonZeroU64;
+
+use iroha_crypto::{HashOf, MerkleTree, PublicKey};
+use iroha_data_model::{
+    block::{BlockHeader, BlockSignature, SignedBlock},
+    isi::{InstructionBox, RegisterBox, UnregisterBox},
+    peer::PeerId,
+    transaction::{Executable, SignedTransaction},
+};
+
+pub use self::topology::{Role, Topology};
+
+/// Error of the verification of a block
+#[derive(Debug, displaydoc::Display, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Expected block at height {expected}, got {actual}
+    UnexpectedHeight {
+        /// Height following the latest verified block
+        expected: NonZeroU64,
+        /// Height of the block
+        actual: NonZeroU64,
+    },
+    /// Block doesn't extend the latest verified block {expected}
+    PrevBlockHashMismatch {
+        /// Hash of the latest verified block
+        expected: HashOf<SignedBlock>,
+        /// Hash of the previous block in the header
+        actual: Option<HashOf<SignedBlock>>,
+    },
+    /// Transactions of the block don't match the transactions hash in the header
+    TransactionsHashMismatch,
+    /// Block isn't a genesis block
+    NotGenesis,
+    /// Genesis block must be signed only with the genesis private key
+    InvalidGenesisSignature,
+    /// There are no peers left in the topology
+    EmptyTopology,
+    /// {0}
+    Signature(#[from] SignatureError),
+}
+
+/// Error of the verification of the block signatures
+#[derive(Debug, displaydoc::Display, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    /// The block doesn't have the leader signature
+    LeaderMissing,
+    /// The block doesn't have the proxy tail signature
+    ProxyTailMissing,
+    /// Block signatory {signatory} doesn't have a voting role in the topology
+    UnknownSignatory {
+        /// Index of the signatory in the topology
+        signatory: u64,
+    },
+    /// Block was signed by the same peer multiple times
+    DuplicateSignatures {
+        /// Index of the signatory in the topology
+        signatory: u64,
+    },
+    /// Signature of {signatory} doesn't correspond to the block payload
+    InvalidSignature {
+        /// Index of the signatory in the topology
+        signatory: u64,
+    },
+    /// The block doesn't have enough signatures to be committed (`votes_count` out of `min_votes_for_commit`)
+    NotEnoughSignatures {
+        /// Number of signatures of the block
+        votes_count: usize,
+        /// Minimal required number of signatures
+        min_votes_for_commit: usize,
+    },
+}
+
+/// Follower of the chain verifying the blocks without executing them, see the [crate documentation](crate)
+#[derive(Debug, Clone)]
+pub struct LightClient {
+    latest_header: BlockHeader,
+    latest_hash: HashOf<SignedBlock>,
+    topology: Topology,
+    /// Registered peers in the order of registration
+    peers: Vec<PeerId>,
+}
+
+impl LightClient {
+    /// Start following the chain from the trusted `genesis` block registering the `topology`
+    ///
+    /// # Errors
+    /// - If `genesis` isn't a genesis block
+    /// - If `genesis` isn't signed only by `genesis_public_key`
+    /// - If the transactions of `genesis` don't match its header
+    /// - If `topology` is empty
+    pub fn new(
+        genesis: &SignedBlock,
+        genesis_public_key: &PublicKey,
+        topology: impl IntoIterator<Item = PeerId>,
+    ) -> Result<Self, Error> {
+        let header = genesis.header();
+        if header.height().get() != 1 || header.prev_block_hash().is_some() {
+            return Err(Error::NotGenesis);
+        }
+        verify_transactions_hash(genesis)?;
+
+        let mut signatures = genesis.signatures();
+        match (signatures.next(), signatures.next()) {
+            (Some(signature @ BlockSignature(0, _)), None) => {
+                genesis
+                    .verify_signature(signature, genesis_public_key)
+                    .map_err(|_err| Error::InvalidGenesisSignature)?;
+            }
+            _ => return Err(Error::InvalidGenesisSignature),
+        }
+
+        let mut topology = Topology::new(topology);
+        if topology.is_empty() {
+            return Err(Error::EmptyTopology);
+        }
+        let peers = topology.peers().to_vec();
+        topology.block_committed([0], &peers);
+
+        Ok(Self {
+            latest_header: header.clone(),
+            latest_hash: genesis.hash(),
+            topology,
+            peers,
+        })
+    }
+
+    /// Header of the latest verified block
+    pub fn latest_header(&self) -> &BlockHeader {
+        &self.latest_header
+    }
+
+    /// Hash of the latest verified block
+    pub fn latest_hash(&self) -> HashOf<SignedBlock> {
+        self.latest_hash
+    }
+
+    /// Topology expected to sign the next block, before any view changes
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    /// Verify the block following the latest verified one and advance to it
+    ///
+    /// # Errors
+    /// - If the block doesn't extend the latest verified block
+    /// - If the transactions of the block don't match its header
+    /// - If the block isn't signed by a quorum of the peers in their roles, see [`SignatureError`]
+    pub fn verify_block(&mut self, block: &SignedBlock) -> Result<(), Error> {
+        let header = block.header();
+        let expected = self
+            .latest_header
+            .height()
+            .checked_add(1)
+            .expect("INTERNAL BUG: Block height exceeds u64::MAX");
+        if header.height() != expected {
+            return Err(Error::UnexpectedHeight {
+                expected,
+                actual: header.height(),
+            });
+        }
+        if header.prev_block_hash() != Some(self.latest_hash) {
+            return Err(Error::PrevBlockHashMismatch {
+                expected: self.latest_hash,
+                actual: header.prev_block_hash(),
+            });
+        }
+        verify_transactions_hash(block)?;
+
+        if self.topology.is_empty() {
+            return Err(Error::EmptyTopology);
+        }
+        let view_change_index = header.view_change_index() as usize;
+        let mut topology = self.topology.rotated(view_change_index);
+        verify_signatures(block, &topology, view_change_index)?;
+
+        block
+            .transactions()
+            .filter(|tx| tx.error().is_none())
+            .for_each(|tx| self.apply_peer_changes(tx.as_ref()));
+        topology.block_committed(
+            block.signatures().map(|signature| {
+                usize::try_from(signature.0).expect("INTERNAL BUG: Signatory was verified")
+            }),
+            &self.peers,
+        );
+
+        self.topology = topology;
+        self.latest_header = header.clone();
+        self.latest_hash = block.hash();
+        Ok(())
+    }
+
+    fn apply_peer_changes(&mut self, tx: &SignedTransaction) {
+        let Executable::Instructions(instructions) = tx.instructions() else {
+            return;
+        };
+        for instruction in instructions {
+            match instruction {
+                InstructionBox::Register(RegisterBox::Peer(register)) => {
+                    if !self.peers.contains(&register.object.id) {
+                        self.peers.push(register.object.id.clone());
+                    }
+                }
+                InstructionBox::Unregister(UnregisterBox::Peer(unregister)) => {
+                    self.peers.retain(|peer| *peer != unregister.object);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn verify_transactions_hash(block: &SignedBlock) -> Result<(), Error> {
+    let transactions_hash = block
+        .transactions()
+        .map(|tx| tx.as_ref().hash())
+        .collect::<MerkleTree<_>>()
+        .hash();
+    if transactions_hash != Some(block.header().transactions_hash()) {
+        return Err(Error::TransactionsHashMismatch);
+    }
+    Ok(())
+}
+
+/// Check the signatures the same way the peers do before committing a block
+fn verify_signatures(
+    block: &SignedBlock,
+    topology: &Topology,
+    view_change_index: usize,
+) -> Result<(), SignatureError> {
+    if block.signatures().next().map(|signature| signature.0)
+        != Some(topology.leader_index() as u64)
+    {
+        return Err(SignatureError::LeaderMissing);
+    }
+    if block.signatures().next_back().map(|signature| signature.0)
+        != Some(topology.proxy_tail_index() as u64)
+    {
+        return Err(SignatureError::ProxyTailMissing);
+    }
+
+    let mut signatories = Vec::with_capacity(block.signatures().len());
+    for signature in block.signatures() {
+        let signatory = signature.0;
+        let index = usize::try_from(signatory)
+            .map_err(|_err| SignatureError::UnknownSignatory { signatory })?;
+        match topology.role(index) {
+            Some(Role::Leader | Role::ValidatingPeer | Role::ProxyTail) => {}
+            Some(Role::ObservingPeer) if view_change_index >= 1 => {}
+            _ => return Err(SignatureError::UnknownSignatory { signatory }),
+        }
+        if signatories.contains(&signatory) {
+            return Err(SignatureError::DuplicateSignatures { signatory });
+        }
+        signatories.push(signatory);
+
+        block
+            .verify_signature(signature, topology.peers()[index].public_key())
+            .map_err(|_err| SignatureError::InvalidSignature { signatory })?;
+    }
+
+    let votes_count = signatories.len();
+    if votes_count < topology.min_votes_for_commit() {
+        return Err(SignatureError::NotEnoughSignatures {
+            votes_count,
+            min_votes_for_commit: topology.min_votes_for_commit(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Ordering of the peers which defines their roles in the consensus.
+//!
+//! Peers reorder the topology after every committed block and the light client has to follow
+//! them exactly, otherwise it would expect the signatures of the wrong peers.
+
+use iroha_data_model::peer::PeerId;
+
+/// Ordered set of the peers, see `iroha_core::sumeragi::network_topology::Topology`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Topology(Vec<PeerId>);
+
+/// Role of a peer in the consensus round of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Peer proposing the block
+    Leader,
+    /// Peer voting for the block
+    ValidatingPeer,
+    /// Peer collecting the votes and committing the block
+    ProxyTail,
+    /// Peer which only votes after a view change
+    ObservingPeer,
+}
+
+impl Topology {
+    /// Create a new topology, ignoring the repeated peers
+    pub fn new(peers: impl IntoIterator<Item = PeerId>) -> Self {
+        let mut topology = Vec::new();
+        for peer in peers {
+            if !topology.contains(&peer) {
+                topology.push(peer);
+            }
+        }
+        Self(topology)
+    }
+
+    /// Peers in the order of their roles
+    pub fn peers(&self) -> &[PeerId] {
+        &self.0
+    }
+
+    /// Whether there are no peers in the topology
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// How many faulty peers can this topology tolerate.
+    pub fn max_faults(&self) -> usize {
+        (self.0.len().saturating_sub(1)) / 3
+    }
+
+    /// The required amount of votes to commit a block with this topology.
+    pub fn min_votes_for_commit(&self) -> usize {
+        let len = self.0.len();
+
+        if len > 3 {
+            self.max_faults() * 2 + 1
+        } else {
+            len
+        }
+    }
+
+    /// Index of the leader
+    #[allow(clippy::unused_self)] // In order to be consistent with `proxy_tail_index` method
+    pub const fn leader_index(&self) -> usize {
+        0
+    }
+
+    /// Index of the proxy tail
+    pub fn proxy_tail_index(&self) -> usize {
+        self.min_votes_for_commit().saturating_sub(1)
+    }
+
+    /// Role of the peer at `index`, if there is one
+    pub fn role(&self, index: usize) -> Option<Role> {
+        match index {
+            _ if index >= self.0.len() => None,
+            _ if index == self.leader_index() => Some(Role::Leader),
+            _ if index < self.proxy_tail_index() => Some(Role::ValidatingPeer),
+            _ if index == self.proxy_tail_index() => Some(Role::ProxyTail),
+            _ => Some(Role::ObservingPeer),
+        }
+    }
+
+    /// Topology of the consensus round after `view_change_index` view changes
+    #[must_use]
+    pub fn rotated(&self, view_change_index: usize) -> Self {
+        let mut topology = self.clone();
+        if let Some(rem) = view_change_index.checked_rem(topology.0.len()) {
+            topology.0.rotate_left(rem);
+        }
+        topology
+    }
+
+    /// Rearrange the peers after a block signed by `signatories` has been committed,
+    /// leaving only the `peers` registered after the block
+    pub(crate) fn block_committed(
+        &mut self,
+        signatories: impl IntoIterator<Item = usize>,
+        peers: &[PeerId],
+    ) {
+        // Signatories are lifted up to the top of the set A, preserving the local order
+        let signatories: Vec<usize> = signatories.into_iter().collect();
+        let mut topology = core::mem::take(&mut self.0)
+            .into_iter()
+            .enumerate()
+            .collect::<Vec<_>>();
+        topology.sort_by_key(|(index, _)| !signatories.contains(index));
+        self.0 = topology.into_iter().map(|(_, peer)| peer).collect();
+
+        let rotate_at = self.min_votes_for_commit();
+        if rotate_at > 0 {
+            self.0[..rotate_at].rotate_left(1);
+        }
+
+        self.0.retain(|peer| peers.contains(peer));
+        for peer in peers {
+            if !self.0.contains(peer) {
+                self.0.push(peer.clone());
+            }
+        }
+    }
+}
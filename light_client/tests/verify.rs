@@ -0,0 +1,221 @@
+use iroha_crypto::{KeyPair, MerkleTree};
+use iroha_data_model::{
+    block::{BlockHeader, BlockPayload, SignedBlock},
+    prelude::*,
+    transaction::CommittedTransaction,
+};
+use iroha_light_client::{Error, LightClient, SignatureError};
+use iroha_primitives::addr::socket_addr;
+
+struct Chain {
+    genesis_key_pair: KeyPair,
+    peer_key_pairs: Vec<KeyPair>,
+    genesis: SignedBlock,
+}
+
+impl Chain {
+    fn new(peers: usize) -> Self {
+        let genesis_key_pair = KeyPair::random();
+        let peer_key_pairs = (0..peers).map(|_| KeyPair::random()).collect::<Vec<_>>();
+        let register_peers = peer_key_pairs
+            .iter()
+            .map(|key_pair| Register::peer(Peer::new(peer_id(key_pair))))
+            .collect::<Vec<_>>();
+        let genesis = SignedBlock::genesis(
+            vec![transaction(&genesis_key_pair, register_peers)],
+            genesis_key_pair.private_key(),
+        );
+
+        Self {
+            genesis_key_pair,
+            peer_key_pairs,
+            genesis,
+        }
+    }
+
+    fn light_client(&self) -> LightClient {
+        LightClient::new(
+            &self.genesis,
+            self.genesis_key_pair.public_key(),
+            self.peer_key_pairs.iter().map(peer_id),
+        )
+        .expect("Genesis is valid")
+    }
+
+    fn key_pair(&self, peer: &PeerId) -> &KeyPair {
+        self.peer_key_pairs
+            .iter()
+            .find(|key_pair| key_pair.public_key() == peer.public_key())
+            .expect("Peer is known")
+    }
+
+    /// Block following the latest verified block of `client`, signed by the peers at `signatories`
+    fn next_block(
+        &self,
+        client: &LightClient,
+        instructions: Vec<Register<Peer>>,
+        signatories: &[usize],
+    ) -> SignedBlock {
+        let transactions = vec![CommittedTransaction {
+            value: transaction(&self.genesis_key_pair, instructions),
+            error: None,
+        }];
+        let payload = BlockPayload {
+            header: BlockHeader {
+                height: client.latest_header().height().checked_add(1).unwrap(),
+                prev_block_hash: Some(client.latest_hash()),
+                transactions_hash: transactions
+                    .iter()
+                    .map(|tx| tx.as_ref().hash())
+                    .collect::<MerkleTree<_>>()
+                    .hash()
+                    .unwrap(),
+                creation_time_ms: 0,
+                view_change_index: 0,
+                consensus_estimation_ms: 4_000,
+                protocol_version: 1,
+            },
+            transactions,
+        };
+
+        let peers = client.topology().peers();
+        let (leader, others) = signatories.split_first().expect("Block has a leader");
+        let mut block = payload.sign(self.key_pair(&peers[*leader]).private_key());
+        for &signatory in others {
+            block.sign(self.key_pair(&peers[signatory]).private_key(), signatory);
+        }
+        block
+    }
+}
+
+fn peer_id(key_pair: &KeyPair) -> PeerId {
+    PeerId::new(socket_addr!(127.0.0.1:1337), key_pair.public_key().clone())
+}
+
+fn transaction(key_pair: &KeyPair, instructions: Vec<Register<Peer>>) -> SignedTransaction {
+    let authority = AccountId::new("genesis".parse().unwrap(), key_pair.public_key().clone());
+    TransactionBuilder::new(
+        ChainId::from("00000000-0000-0000-0000-000000000000"),
+        authority,
+    )
+    .with_instructions(instructions)
+    .sign(key_pair.private_key())
+}
+
+#[test]
+fn block_signed_by_quorum_is_verified() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+    let peers = chain.peer_key_pairs.iter().map(peer_id).collect::<Vec<_>>();
+    // Set A of the genesis topology is rotated once the genesis is committed
+    assert_eq!(
+        client.topology().peers(),
+        [&peers[1], &peers[2], &peers[0], &peers[3]].map(Clone::clone)
+    );
+
+    let block = chain.next_block(&client, Vec::new(), &[0, 1, 2]);
+    client.verify_block(&block).unwrap();
+
+    assert_eq!(client.latest_hash(), block.hash());
+    assert_eq!(
+        client.topology().peers(),
+        [&peers[2], &peers[0], &peers[1], &peers[3]].map(Clone::clone)
+    );
+}
+
+#[test]
+fn block_without_quorum_is_rejected() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+
+    let block = chain.next_block(&client, Vec::new(), &[0, 2]);
+
+    assert_eq!(
+        client.verify_block(&block),
+        Err(Error::Signature(SignatureError::NotEnoughSignatures {
+            votes_count: 2,
+            min_votes_for_commit: 3,
+        }))
+    );
+}
+
+#[test]
+fn observing_peer_can_not_vote_without_view_change() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+
+    let block = chain.next_block(&client, Vec::new(), &[0, 3, 1, 2]);
+
+    assert_eq!(
+        client.verify_block(&block),
+        Err(Error::Signature(SignatureError::UnknownSignatory {
+            signatory: 3
+        }))
+    );
+}
+
+#[test]
+fn forged_signature_is_rejected() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+
+    let mut block = chain.next_block(&client, Vec::new(), &[0, 1]);
+    block.sign(KeyPair::random().private_key(), 2);
+
+    assert_eq!(
+        client.verify_block(&block),
+        Err(Error::Signature(SignatureError::InvalidSignature {
+            signatory: 2
+        }))
+    );
+}
+
+#[test]
+fn block_not_extending_latest_block_is_rejected() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+    let block = chain.next_block(&client, Vec::new(), &[0, 1, 2]);
+    client.verify_block(&block).unwrap();
+
+    let mut fork = chain.light_client();
+    let new_peer = Register::peer(Peer::new(peer_id(&KeyPair::random())));
+    let block = chain.next_block(&fork, vec![new_peer], &[0, 1, 2]);
+    fork.verify_block(&block).unwrap();
+    let fork_block = chain.next_block(&fork, Vec::new(), &[0, 1, 2]);
+
+    assert_eq!(
+        client.verify_block(&fork_block),
+        Err(Error::PrevBlockHashMismatch {
+            expected: client.latest_hash(),
+            actual: Some(fork.latest_hash()),
+        })
+    );
+
+    let unrelated = Chain::new(4);
+    let unrelated_client = unrelated.light_client();
+    let unrelated_block = unrelated.next_block(&unrelated_client, Vec::new(), &[0, 1, 2]);
+    assert_eq!(
+        client.verify_block(&unrelated_block),
+        Err(Error::UnexpectedHeight {
+            expected: 3.try_into().unwrap(),
+            actual: 2.try_into().unwrap(),
+        })
+    );
+}
+
+#[test]
+fn registered_peer_joins_topology() {
+    let chain = Chain::new(4);
+    let mut client = chain.light_client();
+    let new_peer = peer_id(&KeyPair::random());
+
+    let block = chain.next_block(
+        &client,
+        vec![Register::peer(Peer::new(new_peer.clone()))],
+        &[0, 1, 2],
+    );
+    client.verify_block(&block).unwrap();
+
+    assert_eq!(client.topology().peers().len(), 5);
+    assert_eq!(client.topology().peers().last(), Some(&new_peer));
+}
@@ -0,0 +1,325 @@
+//! Crate with the `log` attribute macro of `iroha_logger`
+
+use manyhow::{bail, manyhow, Result};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{FnArg, ItemFn, Pat, ReturnType};
+
+/// Instrument a function with a span, see [`tracing::instrument`](https://docs.rs/tracing/latest/tracing/attr.instrument.html).
+///
+/// For very hot functions, creating a span on every call is too expensive. Two more options limit
+/// which calls are logged, so that the instrumentation can stay compiled in:
+///
+/// - `sample = <RATE>` logs only a `RATE` share of the calls, e.g. `sample = 0.01` logs every 100th call;
+/// - `when = "<EXPR>"` logs only the calls for which the expression, which can use the arguments, is true.
+///
+/// ```ignore
+/// #[log(skip_all, fields(height = block.header().height()), sample = 0.01)]
+/// fn apply(block: &SignedBlock) { /* ... */ }
+///
+/// #[log(skip(tx), when = "tx.instructions().len() > 100")]
+/// fn validate(tx: &SignedTransaction, limits: Limits) { /* ... */ }
+/// ```
+///
+/// Together with these options, only `name`, `level`, `skip`, `skip_all` and `fields` are supported.
+/// Arguments are recorded with their `Debug` implementation unless skipped, apart from `self`
+/// which has to be recorded in `fields` if needed.
+#[manyhow]
+#[proc_macro_attribute]
+pub fn log(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let mut sample = None;
+    let mut when = None;
+    let mut instrument_args = Vec::new();
+    for arg in split_by_commas(args) {
+        match option_name(&arg).as_deref() {
+            Some("sample") => sample = Some(parse_sample(&arg)?),
+            Some("when") => when = Some(option_value::<syn::LitStr>(&arg)?.parse::<syn::Expr>()?),
+            _ => instrument_args.push(arg),
+        }
+    }
+
+    if sample.is_none() && when.is_none() {
+        return Ok(quote! {
+            #[::iroha_logger::__private::tracing::instrument(#(#instrument_args),*)]
+            #item
+        });
+    }
+
+    let item: ItemFn = syn::parse2(item)?;
+    let span = SpanArgs::parse(instrument_args, &item)?;
+    Ok(conditionally_instrumented(
+        item,
+        &span,
+        when.as_ref(),
+        sample,
+    ))
+}
+
+/// Arguments of `tracing::instrument` which are supported together with `sample` and `when`
+struct SpanArgs {
+    name: syn::LitStr,
+    level: TokenStream,
+    fields: Vec<TokenStream>,
+}
+
+impl SpanArgs {
+    fn parse(args: Vec<TokenStream>, item: &ItemFn) -> Result<Self> {
+        let mut name = syn::LitStr::new(&item.sig.ident.to_string(), item.sig.ident.span());
+        let mut level = parse_level("info", Span::call_site())?;
+        let mut skip_all = false;
+        let mut skip = Vec::new();
+        let mut fields = Vec::new();
+
+        for arg in args {
+            // Like `tracing::instrument`, a lone string is the name of the span
+            if let Ok(lit) = syn::parse2::<syn::LitStr>(arg.clone()) {
+                name = lit;
+                continue;
+            }
+            match option_name(&arg).as_deref() {
+                Some("name") => name = option_value(&arg)?,
+                Some("level") => {
+                    let lit: syn::Lit = option_value(&arg)?;
+                    level = match &lit {
+                        syn::Lit::Str(lit) => parse_level(&lit.value(), lit.span())?,
+                        syn::Lit::Int(lit) => parse_level(lit.base10_digits(), lit.span())?,
+                        _ => bail!(lit, "expected the level as a string or a number"),
+                    };
+                }
+                Some("skip_all") => skip_all = true,
+                Some("skip") => skip.extend(
+                    split_by_commas(list_contents(&arg)?).map(|ident| ident.to_string()),
+                ),
+                Some("fields") => fields.extend(split_by_commas(list_contents(&arg)?).map(
+                    |field| {
+                        let has_value = field.clone().into_iter().any(
+                            |token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == '='),
+                        );
+                        if has_value {
+                            field
+                        } else {
+                            // Declared to be recorded later, as `tracing::instrument` does
+                            quote! { #field = ::iroha_logger::__private::tracing::field::Empty }
+                        }
+                    },
+                )),
+                _ => bail!(
+                    arg,
+                    "only `name`, `level`, `skip`, `skip_all` and `fields` are supported together with `sample` or `when`"
+                ),
+            }
+        }
+
+        let arguments = item
+            .sig
+            .inputs
+            .iter()
+            .filter(|_| !skip_all)
+            .filter_map(|input| match input {
+                FnArg::Typed(typed) => match &*typed.pat {
+                    Pat::Ident(pat) => Some(&pat.ident),
+                    _ => None,
+                },
+                FnArg::Receiver(_) => None,
+            })
+            .filter(|ident| !skip.contains(&ident.to_string()))
+            .map(|ident| quote! { #ident = ?#ident });
+
+        Ok(Self {
+            name,
+            level,
+            fields: arguments.chain(fields).collect(),
+        })
+    }
+}
+
+fn conditionally_instrumented(
+    ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    }: ItemFn,
+    SpanArgs {
+        name,
+        level,
+        fields,
+    }: &SpanArgs,
+    when: Option<&syn::Expr>,
+    sample: Option<u64>,
+) -> TokenStream {
+    let when = when.map(|condition| quote! { && (#condition) });
+    let sample = sample.map(|period| {
+        quote! {
+            && {
+                static IROHA_LOG_CALLS: ::core::sync::atomic::AtomicU64 =
+                    ::core::sync::atomic::AtomicU64::new(0);
+                ::iroha_logger::__private::sampled(&IROHA_LOG_CALLS, #period)
+            }
+        }
+    });
+    let span = quote! {
+        let iroha_log_span = if ::iroha_logger::__private::tracing::level_enabled!(#level) #when #sample {
+            ::iroha_logger::__private::tracing::span!(#level, #name #(, #fields)*)
+        } else {
+            ::iroha_logger::__private::tracing::Span::none()
+        };
+    };
+
+    let body = if sig.asyncness.is_some() {
+        let output = match &sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, output) => quote! { #output },
+        };
+        quote! {
+            #span
+            // The output is annotated for `?` in the body to know the error type to convert into
+            ::iroha_logger::__private::tracing::Instrument::instrument(
+                async move {
+                    let iroha_log_output: #output = #block;
+                    iroha_log_output
+                },
+                iroha_log_span,
+            )
+            .await
+        }
+    } else {
+        quote! {
+            #span
+            let _iroha_log_guard = iroha_log_span.entered();
+            #block
+        }
+    };
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #body
+        }
+    }
+}
+
+/// Period of the sampled calls for the rate given in `sample = <RATE>`
+fn parse_sample(arg: &TokenStream) -> Result<u64> {
+    let lit: syn::Lit = option_value(arg)?;
+    let rate: f64 = match &lit {
+        syn::Lit::Float(lit) => lit.base10_parse()?,
+        syn::Lit::Int(lit) => lit.base10_parse()?,
+        _ => bail!(lit, "expected the sampling rate as a number"),
+    };
+    if !(rate > 0.0 && rate <= 1.0) {
+        bail!(lit, "the sampling rate has to be in `(0, 1]`");
+    }
+    // The rate is in `(0, 1]`, so the period is a positive whole number
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok((1.0 / rate).round() as u64)
+}
+
+fn parse_level(level: &str, span: Span) -> Result<TokenStream> {
+    let level = match level.to_ascii_lowercase().as_str() {
+        "trace" | "5" => quote! { TRACE },
+        "debug" | "4" => quote! { DEBUG },
+        "info" | "3" => quote! { INFO },
+        "warn" | "2" => quote! { WARN },
+        "error" | "1" => quote! { ERROR },
+        _ => bail!(span, "unknown level `{}`", level),
+    };
+    Ok(quote! { ::iroha_logger::__private::tracing::Level::#level })
+}
+
+/// Split the arguments of an attribute at the top level commas
+fn split_by_commas(args: TokenStream) -> impl Iterator<Item = TokenStream> {
+    let mut segments = vec![TokenStream::new()];
+    for token in args {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => segments.push(TokenStream::new()),
+            token => segments
+                .last_mut()
+                .expect("There is always a segment")
+                .extend([token]),
+        }
+    }
+    segments.into_iter().filter(|segment| !segment.is_empty())
+}
+
+/// `name` of an argument which is either `name`, `name = value` or `name(...)`
+fn option_name(arg: &TokenStream) -> Option<String> {
+    match arg.clone().into_iter().next() {
+        Some(TokenTree::Ident(ident)) => Some(ident.to_string()),
+        _ => None,
+    }
+}
+
+/// `value` of an argument of the form `name = value`
+fn option_value<T: syn::parse::Parse>(arg: &TokenStream) -> Result<T> {
+    let mut tokens = arg.clone().into_iter();
+    let name = tokens.next();
+    match tokens.next() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+            Ok(syn::parse2(tokens.collect())?)
+        }
+        _ => bail!(
+            arg,
+            "expected `{} = <VALUE>`",
+            name.map(|name| name.to_token_stream()).unwrap_or_default()
+        ),
+    }
+}
+
+/// Contents of the parentheses of an argument of the form `name(...)`
+fn list_contents(arg: &TokenStream) -> Result<TokenStream> {
+    let mut tokens = arg.clone().into_iter().skip(1);
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None)
+            if group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+        {
+            Ok(group.stream())
+        }
+        _ => bail!(arg, "expected a list in parentheses"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampling_rate_is_turned_into_period() {
+        assert_eq!(parse_sample(&quote! { sample = 0.01 }).unwrap(), 100);
+        assert_eq!(parse_sample(&quote! { sample = 1 }).unwrap(), 1);
+        assert!(parse_sample(&quote! { sample = 0.0 }).is_err());
+        assert!(parse_sample(&quote! { sample = 2 }).is_err());
+    }
+
+    #[test]
+    fn arguments_are_split_at_top_level_commas() {
+        let args = split_by_commas(quote! { skip(a, b), fields(c = f(1, 2)), sample = 0.5 })
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            ["skip (a , b)", "fields (c = f (1 , 2))", "sample = 0.5"]
+        );
+    }
+
+    #[test]
+    fn skipped_arguments_are_not_recorded() {
+        let item: ItemFn = syn::parse_quote! {
+            fn hot(&self, block: &Block, limits: Limits, (a, b): (u8, u8)) {}
+        };
+        let args = vec![quote! { skip(block) }, quote! { fields(height) }];
+        let span = SpanArgs::parse(args, &item).unwrap();
+
+        assert_eq!(span.name.value(), "hot");
+        assert_eq!(
+            span.fields
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "limits = ? limits",
+                "height = :: iroha_logger :: __private :: tracing :: field :: Empty"
+            ]
+        );
+    }
+}
@@ -17,10 +17,11 @@ pub use iroha_config::{
     logger::{Format, Level},
     parameters::actual::{DevTelemetry as DevTelemetryConfig, Logger as Config},
 };
+pub use iroha_logger_derive::log;
 use tracing::subscriber::set_global_default;
 pub use tracing::{
-    debug, debug_span, error, error_span, info, info_span, instrument as log, trace, trace_span,
-    warn, warn_span, Instrument,
+    debug, debug_span, error, error_span, info, info_span, trace, trace_span, warn, warn_span,
+    Instrument,
 };
 pub use tracing_futures::Instrument as InstrumentFutures;
 pub use tracing_subscriber::reload::Error as ReloadError;
@@ -306,5 +307,20 @@ pub fn install_panic_hook() -> Result<(), Report> {
 pub mod prelude {
     //! Module with most used items. Needs to be imported when using `log` macro to avoid `tracing` crate dependency
 
-    pub use tracing::{self, debug, error, info, instrument as log, span, trace, warn, Span};
+    pub use iroha_logger_derive::log;
+    pub use tracing::{self, debug, error, info, span, trace, warn, Span};
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by the code generated by the [`log`](crate::log) macro
+
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub use tracing;
+
+    /// Whether the call counted by `calls` is sampled, i.e. is one in `period` calls
+    pub fn sampled(calls: &AtomicU64, period: u64) -> bool {
+        calls.fetch_add(1, Ordering::Relaxed) % period == 0
+    }
 }
@@ -0,0 +1,81 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use iroha_logger::log;
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, prelude::*, Layer};
+
+/// Counts the spans which are created
+#[derive(Clone, Default)]
+struct SpanCounter(Arc<AtomicUsize>);
+
+impl<S: Subscriber> Layer<S> for SpanCounter {
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl SpanCounter {
+    fn spans_of(&self, f: impl FnOnce()) -> usize {
+        let subscriber = tracing_subscriber::registry().with(self.clone());
+        tracing::subscriber::with_default(subscriber, f);
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[log(sample = 0.25)]
+fn sampled(i: usize) -> usize {
+    i
+}
+
+#[log(skip_all, when = "items.len() > 2")]
+fn conditional(items: &[u8]) -> usize {
+    items.len()
+}
+
+#[log(when = "fail")]
+async fn fallible(fail: bool) -> Result<(), std::fmt::Error> {
+    if fail {
+        Err(std::fmt::Error)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn every_sampled_call_is_logged() {
+    let spans = SpanCounter::default().spans_of(|| {
+        for i in 0..100 {
+            assert_eq!(sampled(i), i);
+        }
+    });
+
+    assert_eq!(spans, 25);
+}
+
+#[test]
+fn calls_are_logged_when_condition_holds() {
+    let spans = SpanCounter::default().spans_of(|| {
+        assert_eq!(conditional(&[1, 2]), 2);
+        assert_eq!(conditional(&[1, 2, 3]), 3);
+        assert_eq!(conditional(&[]), 0);
+    });
+
+    assert_eq!(spans, 1);
+}
+
+#[test]
+fn async_calls_are_logged_when_condition_holds() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let spans = SpanCounter::default().spans_of(|| {
+        runtime.block_on(async {
+            assert!(fallible(true).await.is_err());
+            assert!(fallible(false).await.is_ok());
+        });
+    });
+
+    assert_eq!(spans, 1);
+}
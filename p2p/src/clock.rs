@@ -0,0 +1,88 @@
+//! Clock skew between the peers.
+//!
+//! Peers periodically send each other [`Heartbeat`]s stamped with their clocks and reply with
+//! [`HeartbeatAck`]s. Like NTP does, the skew is estimated assuming that the heartbeat takes
+//! as long to reach the peer as the acknowledgement takes to come back, so that the time spent
+//! on the wire cancels out.
+//!
+//! Skewed clocks make peers reject each other's transactions as expired or dated in the future,
+//! see the `clock_skew_tolerance` of the transaction queue.
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use iroha_crypto::PublicKey;
+use parity_scale_codec::{Decode, Encode};
+
+/// The first protocol version which sends [`Heartbeat`]s
+pub const HEARTBEAT_PROTOCOL_VERSION: u8 = 4;
+
+/// Clock skews of the connected peers in milliseconds, positive if the clock of the peer is ahead
+pub type ClockSkews = HashMap<PublicKey, i64>;
+
+/// Request to confirm the connection is alive, stamped with the clock of the sender
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Heartbeat {
+    /// Time the heartbeat was sent at, in milliseconds since the Unix epoch
+    pub sent_at_ms: u64,
+}
+
+/// Response to a [`Heartbeat`], stamped with the clock of the responder
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeartbeatAck {
+    /// [`Heartbeat::sent_at_ms`] of the acknowledged heartbeat
+    pub heartbeat_sent_at_ms: u64,
+    /// Time the heartbeat was received at, in milliseconds since the Unix epoch
+    pub received_at_ms: u64,
+    /// Time the acknowledgement was sent at, in milliseconds since the Unix epoch
+    pub sent_at_ms: u64,
+}
+
+/// The message to report the clock skew measured for the [`Peer`](crate::peer) with the given public key.
+#[derive(Clone, Debug)]
+pub struct ClockSkewMeasured {
+    /// Public key of the peer
+    pub public_key: PublicKey,
+    /// Skew in milliseconds, positive if the clock of the peer is ahead
+    pub skew_ms: i64,
+}
+
+impl Heartbeat {
+    /// Heartbeat sent right now
+    pub fn now() -> Self {
+        Self {
+            sent_at_ms: unix_time_ms(),
+        }
+    }
+
+    /// Acknowledge the heartbeat which was received at `received_at_ms`
+    pub fn ack(self, received_at_ms: u64) -> HeartbeatAck {
+        HeartbeatAck {
+            heartbeat_sent_at_ms: self.sent_at_ms,
+            received_at_ms,
+            sent_at_ms: unix_time_ms(),
+        }
+    }
+}
+
+impl HeartbeatAck {
+    /// Skew of the clock of the responder in milliseconds, positive if it is ahead of the local clock,
+    /// given that the acknowledgement was received at `received_at_ms` by the local clock
+    pub fn clock_skew_ms(&self, received_at_ms: u64) -> i64 {
+        let outbound = i128::from(self.received_at_ms) - i128::from(self.heartbeat_sent_at_ms);
+        let inbound = i128::from(self.sent_at_ms) - i128::from(received_at_ms);
+        let skew = (outbound + inbound) / 2;
+        i64::try_from(skew).unwrap_or(if skew > 0 { i64::MAX } else { i64::MIN })
+    }
+}
+
+/// Time of the local clock in milliseconds since the Unix epoch
+pub fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Failed to get the current system time")
+        .as_millis()
+        .try_into()
+        .expect("Time should fit into u64")
+}
@@ -0,0 +1,153 @@
+//! Fault injection for resilience testing.
+//!
+//! Every message the network sends to a peer is first passed through [`Faults::apply`], which may
+//! drop it, delay it or corrupt its payload according to the configured plan. The socket-backed
+//! network consults the process-wide [`global`] plan, so all peers started in the same process
+//! (e.g. by the test network) are affected. Consensus messages travel through the network, so
+//! partitioning the peers or dropping their messages is how Byzantine scenarios are set up.
+
+use std::{
+    collections::HashSet,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use iroha_crypto::PublicKey;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::boilerplate::*;
+
+/// What happens to a message, see [`Faults::apply`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fate<T> {
+    /// Send the (possibly corrupted) message right away
+    Deliver(T),
+    /// Send the (possibly corrupted) message after a delay
+    Delay(Duration, T),
+    /// Don't send the message
+    Drop,
+}
+
+/// Shared plan of the faults to inject. Clones control the same plan.
+#[derive(Debug, Clone)]
+pub struct Faults(Arc<Mutex<Plan>>);
+
+#[derive(Debug)]
+struct Plan {
+    rng: StdRng,
+    drop_probability: f64,
+    corrupt_probability: f64,
+    delay: Option<RangeInclusive<Duration>>,
+    /// Peers of different groups can't communicate, peers outside of all groups aren't affected
+    partitions: Vec<HashSet<PublicKey>>,
+}
+
+/// Plan used by the socket-backed network, nothing is injected until it's configured
+pub fn global() -> &'static Faults {
+    static GLOBAL: OnceLock<Faults> = OnceLock::new();
+    GLOBAL.get_or_init(|| Faults::new(rand::random()))
+}
+
+impl Faults {
+    /// Plan injecting no faults, whose random decisions are derived from `seed`
+    pub fn new(seed: u64) -> Self {
+        Self(Arc::new(Mutex::new(Plan {
+            rng: StdRng::seed_from_u64(seed),
+            drop_probability: 0.0,
+            corrupt_probability: 0.0,
+            delay: None,
+            partitions: Vec::new(),
+        })))
+    }
+
+    /// Drop each message with the given `probability`
+    ///
+    /// # Panics
+    /// If `probability` isn't within `0.0..=1.0`
+    pub fn drop_messages(&self, probability: f64) {
+        assert!((0.0..=1.0).contains(&probability), "Not a probability");
+        self.plan().drop_probability = probability;
+    }
+
+    /// Flip a random bit in the payload of each message with the given `probability`
+    ///
+    /// # Panics
+    /// If `probability` isn't within `0.0..=1.0`
+    pub fn corrupt_messages(&self, probability: f64) {
+        assert!((0.0..=1.0).contains(&probability), "Not a probability");
+        self.plan().corrupt_probability = probability;
+    }
+
+    /// Delay each message by a duration drawn uniformly from `delay`
+    pub fn delay_messages(&self, delay: RangeInclusive<Duration>) {
+        self.plan().delay = Some(delay);
+    }
+
+    /// Split peers into `groups` which can't communicate with each other.
+    /// Replaces the previous partition, peers outside of all groups aren't affected.
+    pub fn partition<G>(&self, groups: impl IntoIterator<Item = G>)
+    where
+        G: IntoIterator<Item = PublicKey>,
+    {
+        self.plan().partitions = groups
+            .into_iter()
+            .map(|group| group.into_iter().collect())
+            .collect();
+    }
+
+    /// Remove the partition, see [`Self::partition`]
+    pub fn heal(&self) {
+        self.plan().partitions.clear();
+    }
+
+    /// Stop injecting any faults
+    pub fn clear(&self) {
+        let mut plan = self.plan();
+        plan.drop_probability = 0.0;
+        plan.corrupt_probability = 0.0;
+        plan.delay = None;
+        plan.partitions.clear();
+    }
+
+    /// Decide the fate of `data` sent from peer `from` to peer `to`.
+    ///
+    /// Corrupted messages which no longer decode are dropped,
+    /// as the receiver wouldn't be able to make sense of them anyway.
+    pub fn apply<T: Pload>(&self, from: &PublicKey, to: &PublicKey, data: T) -> Fate<T> {
+        let mut guard = self.plan();
+        let plan = &mut *guard;
+        if plan.is_partitioned(from, to) || plan.rng.gen_bool(plan.drop_probability) {
+            return Fate::Drop;
+        }
+        let data = if plan.rng.gen_bool(plan.corrupt_probability) {
+            let mut bytes = data.encode();
+            if bytes.is_empty() {
+                return Fate::Drop;
+            }
+            let bit = plan.rng.gen_range(0..bytes.len() * 8);
+            bytes[bit / 8] ^= 1 << (bit % 8);
+            match T::decode(&mut bytes.as_slice()) {
+                Ok(data) => data,
+                Err(_) => return Fate::Drop,
+            }
+        } else {
+            data
+        };
+        match plan.delay.clone() {
+            Some(delay) => Fate::Delay(plan.rng.gen_range(delay), data),
+            None => Fate::Deliver(data),
+        }
+    }
+
+    fn plan(&self) -> std::sync::MutexGuard<'_, Plan> {
+        self.0.lock().expect("Lock isn't poisoned")
+    }
+}
+
+impl Plan {
+    fn is_partitioned(&self, from: &PublicKey, to: &PublicKey) -> bool {
+        let group_of = |key| self.partitions.iter().position(|group| group.contains(key));
+        matches!((group_of(from), group_of(to)), (Some(a), Some(b)) if a != b)
+    }
+}
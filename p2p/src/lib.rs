@@ -3,6 +3,7 @@
 //! Cryptography are chosen in this module, and encapsulated.
 use std::{io, net::AddrParseError};
 
+pub use clock::{ClockSkewMeasured, ClockSkews};
 use iroha_crypto::{
     blake2::{
         digest::{Update, VariableOutput},
@@ -13,10 +14,20 @@ use iroha_crypto::{
 };
 pub use network::message::*;
 use parity_scale_codec::{Decode, Encode};
+pub use reputation::{Misbehavior, PeerScores, ReportMisbehavior};
 use thiserror::Error;
-
+pub use traffic::{Classify, TrafficClass};
+
+pub mod clock;
+#[cfg(feature = "fault-injection")]
+pub mod faults;
+#[cfg(feature = "in-memory")]
+pub mod memory;
+pub mod message;
 pub mod network;
 pub mod peer;
+pub mod reputation;
+pub mod traffic;
 
 /// The main type to use for secure communication.
 pub type NetworkHandle<T> = network::NetworkBaseHandle<T, X25519Sha256, ChaCha20Poly1305>;
@@ -30,8 +41,8 @@ pub mod boilerplate {
     use super::*;
 
     /// Shorthand for traits required for payload
-    pub trait Pload: Encode + Decode + Send + Clone + 'static {}
-    impl<T> Pload for T where T: Encode + Decode + Send + Clone + 'static {}
+    pub trait Pload: Encode + Decode + Send + Clone + Classify + 'static {}
+    impl<T> Pload for T where T: Encode + Decode + Send + Clone + Classify + 'static {}
 
     /// Shorthand for traits required for key exchange
     pub trait Kex: KeyExchangeScheme + Send + 'static {}
@@ -61,6 +72,88 @@ pub enum Error {
     Addr(#[from] AddrParseError),
     /// Connection reset by peer in the middle of message transfer
     ConnectionResetByPeer,
+    /// Message of {size} bytes exceeds the limit of {max_len} bytes
+    MessageTooLarge {
+        /// Size of the message declared by the peer
+        size: usize,
+        /// Maximal allowed size of the message
+        max_len: usize,
+    },
+    /// Peer has been idle for {0:?}
+    Idle(std::time::Duration),
+    /// Network dropped the channels of the peer
+    NetworkClosed,
+}
+
+/// Stage of the peer connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum Stage {
+    /// Establishing the connection and exchanging the keys
+    #[display(fmt = "handshake")]
+    Handshake,
+    /// Receiving messages from the peer
+    #[display(fmt = "read")]
+    Read,
+    /// Sending messages to the peer
+    #[display(fmt = "write")]
+    Write,
+}
+
+/// Peer connection failed during {stage}: {error}
+#[derive(Debug, Error, displaydoc::Display)]
+pub struct PeerError {
+    /// Stage the error happened at
+    pub stage: Stage,
+    /// What went wrong
+    #[source]
+    pub error: Error,
+}
+
+/// How the network reacts to a [`PeerError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Drop the offending message but keep the connection, penalizing the peer
+    DropMessage(Misbehavior),
+    /// Close the connection, it is re-established on the next topology update
+    Retry,
+    /// Close the connection and penalize the peer, which is banned once its score drops too low
+    Ban(Misbehavior),
+}
+
+impl PeerError {
+    /// Construct [`Self`] out of the `error` happened at `stage`
+    pub fn new(stage: Stage, error: impl Into<Error>) -> Self {
+        Self {
+            stage,
+            error: error.into(),
+        }
+    }
+
+    /// Decide how the network should react to the error
+    pub fn policy(&self) -> Policy {
+        match (&self.error, self.stage) {
+            // Malformed message is skipped, so that the following ones can be read
+            (Error::ParityScale(_), Stage::Read) => {
+                Policy::DropMessage(Misbehavior::MalformedMessage)
+            }
+            (
+                Error::ParityScale(_)
+                | Error::SymmetricEncryption(_)
+                | Error::MessageTooLarge { .. },
+                _,
+            ) => Policy::Ban(Misbehavior::MalformedMessage),
+            (Error::Keys(_), _) => Policy::Ban(Misbehavior::InvalidSignature),
+            (Error::Format | Error::Field, _) => Policy::Ban(Misbehavior::ProtocolViolation),
+            (
+                Error::Io(_)
+                | Error::Addr(_)
+                | Error::ConnectionResetByPeer
+                | Error::Idle(_)
+                | Error::NetworkClosed,
+                _,
+            ) => Policy::Retry,
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -120,6 +213,13 @@ pub(crate) mod unbounded_with_len {
             Some(message)
         }
 
+        #[cfg(feature = "in-memory")]
+        pub fn try_recv(&mut self) -> Option<T> {
+            let message = self.receiver.try_recv().ok()?;
+            self.len.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+            Some(message)
+        }
+
         pub fn len(&self) -> usize {
             self.len
                 .load(std::sync::atomic::Ordering::SeqCst)
@@ -0,0 +1,130 @@
+//! Network handles which aren't backed by any sockets.
+//!
+//! [`NetworkBaseHandle::in_memory`](crate::network::NetworkBaseHandle::in_memory) returns a regular
+//! handle together with an [`Endpoint`] which receives everything sent through the handle and
+//! decides which peers are online and which messages reach the subscribers. It's up to the owner
+//! of the endpoints to carry messages between them, e.g. to simulate a network in tests.
+
+use futures::{stream::FuturesUnordered, StreamExt};
+use tokio::sync::{mpsc, watch};
+
+use crate::{
+    boilerplate::*, network::message::NetworkMessage, peer::message::PeerMessage,
+    unbounded_with_len, BanPeer, Broadcast, ClockSkews, OnlinePeers, PeerScores, Post,
+    ReportMisbehavior, UpdateCertificates, UpdateTopology,
+};
+
+/// Message sent through the handle of an [`Endpoint`]
+#[derive(Debug, Clone)]
+pub enum Outgoing<T> {
+    /// Message to a single peer
+    Post(Post<T>),
+    /// Message to all online peers
+    Broadcast(Broadcast<T>),
+    /// New set of peers the handle owner wants to be connected to
+    UpdateTopology(UpdateTopology),
+    /// Request to disconnect a peer for a while
+    BanPeer(BanPeer),
+    /// Certificates registered by the peers
+    UpdateCertificates(UpdateCertificates),
+    /// Report of a misbehaving peer
+    ReportMisbehavior(ReportMisbehavior),
+    /// Request to stop the network
+    Shutdown,
+}
+
+/// Receiving side of an in-memory network handle, see the [module documentation](self)
+pub struct Endpoint<T: Pload> {
+    pub(crate) subscribers_to_peers_messages: Vec<mpsc::Sender<PeerMessage<T>>>,
+    pub(crate) subscribe_to_peers_messages_receiver:
+        mpsc::UnboundedReceiver<mpsc::Sender<PeerMessage<T>>>,
+    pub(crate) online_peers_sender: watch::Sender<OnlinePeers>,
+    pub(crate) peer_scores_sender: watch::Sender<PeerScores>,
+    pub(crate) clock_skews_sender: watch::Sender<ClockSkews>,
+    pub(crate) update_topology_receiver: mpsc::UnboundedReceiver<UpdateTopology>,
+    pub(crate) network_message_receiver: unbounded_with_len::Receiver<NetworkMessage<T>>,
+}
+
+impl<T: Pload> core::fmt::Debug for Endpoint<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Endpoint")
+            .field(
+                "subscribers_to_peers_messages",
+                &self.subscribers_to_peers_messages.len(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Pload> Endpoint<T> {
+    /// Next message sent through the handle, `None` if there are no pending messages.
+    ///
+    /// Topology updates are returned before the rest of the messages,
+    /// same as they're prioritized by the socket-backed network.
+    pub fn try_recv(&mut self) -> Option<Outgoing<T>> {
+        self.accept_subscribers();
+        if let Ok(update_topology) = self.update_topology_receiver.try_recv() {
+            return Some(Outgoing::UpdateTopology(update_topology));
+        }
+        let outgoing = match self.network_message_receiver.try_recv()? {
+            NetworkMessage::Post(post) => Outgoing::Post(post),
+            NetworkMessage::Broadcast(broadcast) => Outgoing::Broadcast(broadcast),
+            NetworkMessage::BanPeer(ban) => Outgoing::BanPeer(ban),
+            NetworkMessage::UpdateCertificates(update) => Outgoing::UpdateCertificates(update),
+            NetworkMessage::ReportMisbehavior(report) => Outgoing::ReportMisbehavior(report),
+            NetworkMessage::Shutdown => Outgoing::Shutdown,
+        };
+        Some(outgoing)
+    }
+
+    /// Pass a message received from another peer to the subscribers of the handle
+    pub async fn deliver(&mut self, message: PeerMessage<T>) {
+        self.accept_subscribers();
+        if self.subscribers_to_peers_messages.is_empty() {
+            iroha_logger::warn!("No subscribers to send message to");
+            return;
+        }
+        self.subscribers_to_peers_messages = self
+            .subscribers_to_peers_messages
+            .drain(..)
+            .zip(core::iter::repeat(message))
+            .map(|(subscriber, message)| async move {
+                let is_ok = subscriber.send(message).await.is_ok();
+                (subscriber, is_ok)
+            })
+            .collect::<FuturesUnordered<_>>()
+            .filter_map(|(subscriber, is_ok)| futures::future::ready(is_ok.then_some(subscriber)))
+            .collect::<Vec<_>>()
+            .await;
+    }
+
+    /// Set the peers reported as online by the handle
+    pub fn set_online_peers(&self, online_peers: OnlinePeers) {
+        self.online_peers_sender.send_if_modified(|current| {
+            let is_modified = *current != online_peers;
+            *current = online_peers;
+            is_modified
+        });
+    }
+
+    /// Set the scores reported by the handle
+    pub fn set_peer_scores(&self, peer_scores: PeerScores) {
+        self.peer_scores_sender.send_replace(peer_scores);
+    }
+
+    /// Set the clock skews reported by the handle
+    pub fn set_clock_skews(&self, clock_skews: ClockSkews) {
+        self.clock_skews_sender.send_replace(clock_skews);
+    }
+
+    /// Whether all handles were dropped, so that nothing else will be sent through them
+    pub fn is_closed(&self) -> bool {
+        self.online_peers_sender.is_closed()
+    }
+
+    fn accept_subscribers(&mut self) {
+        while let Ok(subscriber) = self.subscribe_to_peers_messages_receiver.try_recv() {
+            self.subscribers_to_peers_messages.push(subscriber);
+        }
+    }
+}
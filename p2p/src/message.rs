@@ -0,0 +1,204 @@
+//! Wire format of the messages exchanged by connected peers.
+//!
+//! Every message is wrapped into an [`Envelope`] tagged with the protocol version
+//! of the sender and the kind of the message. Peers reply to each other using the
+//! lowest of their versions, so a peer keeps talking to peers running the
+//! [previous version](MIN_PROTOCOL_VERSION) of the protocol, which sends bare
+//! [`Message`]s without the envelope.
+//!
+//! Version history:
+//! 1. Bare messages
+//! 2. Messages are wrapped into the [`Envelope`]
+//! 3. Peers present their [`PeerCertificate`] right after the handshake
+//! 4. Peers exchange [`Heartbeat`]s to estimate the skew of their clocks
+use iroha_data_model::peer::PeerCertificate;
+use iroha_version::scale::{decode_all_with_limits, DecodeLimits};
+use parity_scale_codec::{Decode, Encode};
+
+use crate::clock::{Heartbeat, HeartbeatAck};
+
+/// Version of the protocol spoken by this peer
+pub const PROTOCOL_VERSION: u8 = 4;
+/// The oldest version of the protocol this peer can talk to.
+///
+/// Messages of this version are sent without the [`Envelope`].
+pub const MIN_PROTOCOL_VERSION: u8 = 1;
+
+/// Either message or ping
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub enum Message<T> {
+    /// Payload passed to the network subscribers
+    Data(T),
+    /// Request to confirm the connection is alive
+    Ping,
+    /// Response to [`Message::Ping`]
+    Pong,
+    /// Connection is about to be closed gracefully by the sender
+    Goodbye,
+    /// Certificate the sender is registered with, sent right after the handshake
+    Certificate(PeerCertificate),
+    /// Periodic message carrying the clock of the sender
+    Heartbeat(Heartbeat),
+    /// Response to [`Message::Heartbeat`]
+    HeartbeatAck(HeartbeatAck),
+}
+
+/// Kind of the message carried by the [`Envelope`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MessageKind {
+    /// [`Message::Data`]
+    Data = 0,
+    /// [`Message::Ping`]
+    Ping = 1,
+    /// [`Message::Pong`]
+    Pong = 2,
+    /// [`Message::Goodbye`]
+    Goodbye = 3,
+    /// [`Message::Certificate`]
+    Certificate = 4,
+    /// [`Message::Heartbeat`]
+    Heartbeat = 5,
+    /// [`Message::HeartbeatAck`]
+    HeartbeatAck = 6,
+}
+
+impl TryFrom<u8> for MessageKind {
+    type Error = u8;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        match kind {
+            0 => Ok(Self::Data),
+            1 => Ok(Self::Ping),
+            2 => Ok(Self::Pong),
+            3 => Ok(Self::Goodbye),
+            4 => Ok(Self::Certificate),
+            5 => Ok(Self::Heartbeat),
+            6 => Ok(Self::HeartbeatAck),
+            unknown => Err(unknown),
+        }
+    }
+}
+
+/// Message tagged with the protocol version and the message kind
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Envelope {
+    /// Protocol version of the sender
+    pub version: u8,
+    /// Kind of the message, see [`MessageKind`].
+    ///
+    /// Kept raw, so that kinds introduced in the newer versions can be skipped.
+    pub kind: u8,
+    /// SCALE-encoded message of the given kind
+    pub payload: Vec<u8>,
+}
+
+/// Message decoded from the wire
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Received<T> {
+    /// Message of a known kind
+    Message {
+        /// Protocol version of the sender
+        version: u8,
+        /// Decoded message
+        message: Message<T>,
+    },
+    /// Message of a kind this peer doesn't know about
+    UnknownKind {
+        /// Protocol version of the sender
+        version: u8,
+        /// Raw kind of the message
+        kind: u8,
+    },
+}
+
+impl<T> Message<T> {
+    /// Kind of the message
+    pub const fn kind(&self) -> MessageKind {
+        match self {
+            Self::Data(_) => MessageKind::Data,
+            Self::Ping => MessageKind::Ping,
+            Self::Pong => MessageKind::Pong,
+            Self::Goodbye => MessageKind::Goodbye,
+            Self::Certificate(_) => MessageKind::Certificate,
+            Self::Heartbeat(_) => MessageKind::Heartbeat,
+            Self::HeartbeatAck(_) => MessageKind::HeartbeatAck,
+        }
+    }
+}
+
+impl<T: Encode> Message<T> {
+    /// Encode the message into `buffer` in the format of the given protocol `version`
+    pub fn encode_versioned_to(&self, version: u8, buffer: &mut Vec<u8>) {
+        if version <= MIN_PROTOCOL_VERSION {
+            self.encode_to(buffer);
+            return;
+        }
+
+        let payload = match self {
+            Self::Data(data) => data.encode(),
+            Self::Certificate(certificate) => certificate.encode(),
+            Self::Heartbeat(heartbeat) => heartbeat.encode(),
+            Self::HeartbeatAck(ack) => ack.encode(),
+            Self::Ping | Self::Pong | Self::Goodbye => Vec::new(),
+        };
+        Envelope {
+            version: PROTOCOL_VERSION,
+            kind: self.kind() as u8,
+            payload,
+        }
+        .encode_to(buffer);
+    }
+}
+
+impl<T: Decode> Received<T> {
+    /// Decode the message sent using any protocol version starting from [`MIN_PROTOCOL_VERSION`].
+    ///
+    /// # Errors
+    /// Fails if `input` is neither a valid [`Envelope`] nor a valid bare [`Message`]
+    pub fn decode(input: &[u8]) -> Result<Self, parity_scale_codec::Error> {
+        // NOTE: bare messages start with the variant index, which is at most `MIN_PROTOCOL_VERSION`
+        // except for `Pong` and `Goodbye`, which are too short to be an envelope.
+        let enveloped = input
+            .first()
+            .is_some_and(|version| *version > MIN_PROTOCOL_VERSION);
+        if enveloped {
+            if let Ok(envelope) = decode_all_with_limits::<Envelope>(input, DecodeLimits::DEFAULT) {
+                return Self::open(envelope);
+            }
+        }
+
+        decode_all_with_limits(input, DecodeLimits::DEFAULT).map(|message| Self::Message {
+            version: MIN_PROTOCOL_VERSION,
+            message,
+        })
+    }
+
+    fn open(
+        Envelope {
+            version,
+            kind,
+            payload,
+        }: Envelope,
+    ) -> Result<Self, parity_scale_codec::Error> {
+        let message = match MessageKind::try_from(kind) {
+            Ok(MessageKind::Data) => {
+                Message::Data(decode_all_with_limits(&payload, DecodeLimits::DEFAULT)?)
+            }
+            Ok(MessageKind::Ping) => Message::Ping,
+            Ok(MessageKind::Pong) => Message::Pong,
+            Ok(MessageKind::Goodbye) => Message::Goodbye,
+            Ok(MessageKind::Certificate) => {
+                Message::Certificate(decode_all_with_limits(&payload, DecodeLimits::DEFAULT)?)
+            }
+            Ok(MessageKind::Heartbeat) => {
+                Message::Heartbeat(decode_all_with_limits(&payload, DecodeLimits::DEFAULT)?)
+            }
+            Ok(MessageKind::HeartbeatAck) => {
+                Message::HeartbeatAck(decode_all_with_limits(&payload, DecodeLimits::DEFAULT)?)
+            }
+            Err(kind) => return Ok(Self::UnknownKind { version, kind }),
+        };
+        Ok(Self::Message { version, message })
+    }
+}
@@ -9,7 +9,7 @@ use std::{
 use futures::{stream::FuturesUnordered, StreamExt};
 use iroha_config::parameters::actual::Network as Config;
 use iroha_crypto::{KeyPair, PublicKey};
-use iroha_data_model::prelude::PeerId;
+use iroha_data_model::{peer::PeerCertificate, prelude::PeerId};
 use iroha_logger::prelude::*;
 use iroha_primitives::addr::SocketAddr;
 use parity_scale_codec::Encode as _;
@@ -26,7 +26,10 @@ use crate::{
         message::*,
         Connection, ConnectionId,
     },
-    unbounded_with_len, Broadcast, Error, NetworkMessage, OnlinePeers, Post, UpdateTopology,
+    reputation::Reputation,
+    unbounded_with_len, BanPeer, Broadcast, ClockSkewMeasured, ClockSkews, Error, Misbehavior,
+    NetworkMessage, OnlinePeers, PeerError, PeerScores, Policy, Post, ReportMisbehavior,
+    UpdateCertificates, UpdateTopology,
 };
 
 /// [`NetworkBase`] actor handle.
@@ -37,9 +40,13 @@ use crate::{
 #[debug(fmt = "core::any::type_name::<Self>()")]
 pub struct NetworkBaseHandle<T: Pload, K: Kex, E: Enc> {
     /// Sender to subscribe for messages received form other peers in the network
-    subscribe_to_peers_messages_sender: mpsc::UnboundedSender<mpsc::Sender<T>>,
+    subscribe_to_peers_messages_sender: mpsc::UnboundedSender<mpsc::Sender<PeerMessage<T>>>,
     /// Receiver of `OnlinePeer` message
     online_peers_receiver: watch::Receiver<OnlinePeers>,
+    /// Receiver of the scores of misbehaving peers
+    peer_scores_receiver: watch::Receiver<PeerScores>,
+    /// Receiver of the clock skews of the connected peers
+    clock_skews_receiver: watch::Receiver<ClockSkews>,
     /// [`UpdateTopology`] message sender
     update_topology_sender: mpsc::UnboundedSender<UpdateTopology>,
     /// Sender of [`NetworkMessage`] message
@@ -55,6 +62,8 @@ impl<T: Pload, K: Kex, E: Enc> Clone for NetworkBaseHandle<T, K, E> {
         Self {
             subscribe_to_peers_messages_sender: self.subscribe_to_peers_messages_sender.clone(),
             online_peers_receiver: self.online_peers_receiver.clone(),
+            peer_scores_receiver: self.peer_scores_receiver.clone(),
+            clock_skews_receiver: self.clock_skews_receiver.clone(),
             update_topology_sender: self.update_topology_sender.clone(),
             network_message_sender: self.network_message_sender.clone(),
             _key_exchange: core::marker::PhantomData::<K>,
@@ -69,17 +78,33 @@ impl<T: Pload, K: Kex + Sync, E: Enc + Sync> NetworkBaseHandle<T, K, E> {
     /// # Errors
     /// - If binding to address fail
     #[log(skip(key_pair))]
-    pub async fn start(
+    pub async fn start(key_pair: KeyPair, config: Config) -> Result<Self, Error> {
+        // TODO: enhance the error by reporting the origin of `listen_addr`
+        let listener =
+            TcpListener::bind(config.address.value().to_socket_addrs()?.as_slice()).await?;
+        iroha_logger::info!("Network bound to listener");
+        Ok(Self::start_with_listener(key_pair, config, listener))
+    }
+
+    /// Start network peer accepting connections on the already bound `listener`,
+    /// e.g. one inherited from the service manager, and return handle to it.
+    ///
+    /// The configured address is still advertised to the other peers.
+    pub fn start_with_listener(
         key_pair: KeyPair,
         Config {
             address: listen_addr,
             idle_timeout,
+            reputation_ban_threshold,
+            reputation_ban_duration,
+            clock_skew_warn_threshold,
+            ..
         }: Config,
-    ) -> Result<Self, Error> {
-        // TODO: enhance the error by reporting the origin of `listen_addr`
-        let listener = TcpListener::bind(listen_addr.value().to_socket_addrs()?.as_slice()).await?;
-        iroha_logger::info!("Network bound to listener");
+        listener: TcpListener,
+    ) -> Self {
         let (online_peers_sender, online_peers_receiver) = watch::channel(HashSet::new());
+        let (peer_scores_sender, peer_scores_receiver) = watch::channel(HashMap::new());
+        let (clock_skews_sender, clock_skews_receiver) = watch::channel(HashMap::new());
         let (subscribe_to_peers_messages_sender, subscribe_to_peers_messages_receiver) =
             mpsc::unbounded_channel();
         let (update_topology_sender, update_topology_receiver) = mpsc::unbounded_channel();
@@ -104,23 +129,67 @@ impl<T: Pload, K: Kex + Sync, E: Enc + Sync> NetworkBaseHandle<T, K, E> {
             service_message_sender,
             current_conn_id: 0,
             current_topology: HashMap::new(),
+            certificates: HashMap::new(),
+            banned_peers: HashMap::new(),
+            reputation: Reputation::new(reputation_ban_threshold),
+            reputation_ban_duration,
+            peer_scores_sender,
+            clock_skews: HashMap::new(),
+            clock_skews_sender,
+            clock_skew_warn_threshold,
             idle_timeout,
             _key_exchange: core::marker::PhantomData::<K>,
             _encryptor: core::marker::PhantomData::<E>,
         };
         tokio::task::spawn(network.run());
-        Ok(Self {
+        Self {
             subscribe_to_peers_messages_sender,
             online_peers_receiver,
+            peer_scores_receiver,
+            clock_skews_receiver,
             update_topology_sender,
             network_message_sender,
             _key_exchange: core::marker::PhantomData,
             _encryptor: core::marker::PhantomData,
-        })
+        }
+    }
+
+    /// Create a handle which isn't backed by any sockets.
+    /// Everything sent through it is received by the returned [`Endpoint`](crate::memory::Endpoint) instead.
+    #[cfg(feature = "in-memory")]
+    pub fn in_memory() -> (Self, crate::memory::Endpoint<T>) {
+        let (online_peers_sender, online_peers_receiver) = watch::channel(HashSet::new());
+        let (peer_scores_sender, peer_scores_receiver) = watch::channel(HashMap::new());
+        let (clock_skews_sender, clock_skews_receiver) = watch::channel(HashMap::new());
+        let (subscribe_to_peers_messages_sender, subscribe_to_peers_messages_receiver) =
+            mpsc::unbounded_channel();
+        let (update_topology_sender, update_topology_receiver) = mpsc::unbounded_channel();
+        let (network_message_sender, network_message_receiver) =
+            unbounded_with_len::unbounded_channel();
+        let endpoint = crate::memory::Endpoint {
+            subscribers_to_peers_messages: Vec::new(),
+            subscribe_to_peers_messages_receiver,
+            online_peers_sender,
+            peer_scores_sender,
+            clock_skews_sender,
+            update_topology_receiver,
+            network_message_receiver,
+        };
+        let handle = Self {
+            subscribe_to_peers_messages_sender,
+            online_peers_receiver,
+            peer_scores_receiver,
+            clock_skews_receiver,
+            update_topology_sender,
+            network_message_sender,
+            _key_exchange: core::marker::PhantomData,
+            _encryptor: core::marker::PhantomData,
+        };
+        (handle, endpoint)
     }
 
     /// Subscribe to messages received from other peers in the network
-    pub fn subscribe_to_peers_messages(&self, sender: mpsc::Sender<T>) {
+    pub fn subscribe_to_peers_messages(&self, sender: mpsc::Sender<PeerMessage<T>>) {
         self.subscribe_to_peers_messages_sender
             .send(sender)
             .expect("NetworkBase must accept messages until there is at least one handle to it")
@@ -149,11 +218,56 @@ impl<T: Pload, K: Kex + Sync, E: Enc + Sync> NetworkBaseHandle<T, K, E> {
             .expect("NetworkBase must accept messages until there is at least one handle to it")
     }
 
+    /// Send [`UpdateCertificates`] message on network actor.
+    pub fn update_certificates(&self, certificates: UpdateCertificates) {
+        self.network_message_sender
+            .send(NetworkMessage::UpdateCertificates(certificates))
+            .map_err(|_| ())
+            .expect("NetworkBase must accept messages until there is at least one handle to it")
+    }
+
+    /// Send [`BanPeer`] message on network actor.
+    pub fn ban_peer(&self, ban: BanPeer) {
+        self.network_message_sender
+            .send(NetworkMessage::BanPeer(ban))
+            .map_err(|_| ())
+            .expect("NetworkBase must accept messages until there is at least one handle to it")
+    }
+
+    /// Send [`ReportMisbehavior`] message on network actor.
+    ///
+    /// The peer is banned once its score drops below the configured threshold.
+    pub fn report_misbehavior(&self, report: ReportMisbehavior) {
+        self.network_message_sender
+            .send(NetworkMessage::ReportMisbehavior(report))
+            .map_err(|_| ())
+            .expect("NetworkBase must accept messages until there is at least one handle to it")
+    }
+
+    /// Close connections with all peers (saying goodbye to each of them)
+    /// and stop the network actor.
+    ///
+    /// Messages sent through this or any other handle afterwards are silently dropped.
+    pub fn shutdown(&self) {
+        // NOTE: the network actor might be already gone, that's fine
+        let _ = self.network_message_sender.send(NetworkMessage::Shutdown);
+    }
+
     /// Receive latest update of [`OnlinePeers`]
     pub fn online_peers<P>(&self, f: impl FnOnce(&OnlinePeers) -> P) -> P {
         f(&self.online_peers_receiver.borrow())
     }
 
+    /// Receive latest update of [`PeerScores`]
+    pub fn peer_scores<P>(&self, f: impl FnOnce(&PeerScores) -> P) -> P {
+        f(&self.peer_scores_receiver.borrow())
+    }
+
+    /// Receive latest update of [`ClockSkews`]
+    pub fn clock_skews<P>(&self, f: impl FnOnce(&ClockSkews) -> P) -> P {
+        f(&self.clock_skews_receiver.borrow())
+    }
+
     /// Wait for update of [`OnlinePeers`].
     pub async fn wait_online_peers_update<P>(
         &mut self,
@@ -180,9 +294,9 @@ struct NetworkBase<T: Pload, K: Kex, E: Enc> {
     /// Our app-level key pair
     key_pair: KeyPair,
     /// Recipients of messages received from other peers in the network.
-    subscribers_to_peers_messages: Vec<mpsc::Sender<T>>,
+    subscribers_to_peers_messages: Vec<mpsc::Sender<PeerMessage<T>>>,
     /// Receiver to subscribe for messages received from other peers in the network.
-    subscribe_to_peers_messages_receiver: mpsc::UnboundedReceiver<mpsc::Sender<T>>,
+    subscribe_to_peers_messages_receiver: mpsc::UnboundedReceiver<mpsc::Sender<PeerMessage<T>>>,
     /// Sender of `OnlinePeer` message
     online_peers_sender: watch::Sender<OnlinePeers>,
     /// [`UpdateTopology`] message receiver
@@ -202,6 +316,22 @@ struct NetworkBase<T: Pload, K: Kex, E: Enc> {
     /// Current topology
     /// Bool determines who is responsible for initiating connection
     current_topology: HashMap<PeerId, bool>,
+    /// Certificates registered on-chain by the peers of the network
+    certificates: HashMap<PublicKey, PeerCertificate>,
+    /// Peer addresses which are not allowed to connect until the given instant
+    banned_peers: HashMap<SocketAddr, tokio::time::Instant>,
+    /// Scores of the peers which have misbehaved recently
+    reputation: Reputation,
+    /// For how long peers are banned once their score drops below the threshold
+    reputation_ban_duration: Duration,
+    /// Sender of the scores of misbehaving peers
+    peer_scores_sender: watch::Sender<PeerScores>,
+    /// Clock skews of the connected peers measured last
+    clock_skews: ClockSkews,
+    /// Sender of the clock skews of the connected peers
+    clock_skews_sender: watch::Sender<ClockSkews>,
+    /// Clock skew above which a warning is logged
+    clock_skew_warn_threshold: Duration,
     /// Duration after which terminate connection with idle peer
     idle_timeout: Duration,
     /// Key exchange used by network
@@ -230,7 +360,8 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
                 }
                 // Frequency of update is relatively low, so it won't block other tasks from execution
                 _ = update_topology_interval.tick() => {
-                    self.update_topology()
+                    self.update_topology();
+                    self.recover_reputation();
                 }
                 // Every peer produce small amount of service messages so this shouldn't starve other tasks
                 Some(service_message) = self.service_message_receiver.recv() => {
@@ -241,6 +372,15 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
                         ServiceMessage::Connected(connected) => {
                             self.peer_connected(connected);
                         }
+                        ServiceMessage::Misbehaved(report) => {
+                            self.report_misbehavior(report);
+                        }
+                        ServiceMessage::Certified(certified) => {
+                            self.peer_certified(certified);
+                        }
+                        ServiceMessage::ClockSkew(measured) => {
+                            self.clock_skew_measured(measured);
+                        }
                     }
                 }
                 // Because network messages is responses to incoming messages or relatively low rate messages
@@ -257,6 +397,13 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
                     match network_message {
                         NetworkMessage::Post(post) => self.post(post),
                         NetworkMessage::Broadcast(broadcast) => self.broadcast(broadcast),
+                        NetworkMessage::BanPeer(ban) => self.ban_peer(ban),
+                        NetworkMessage::UpdateCertificates(update) => self.update_certificates(update),
+                        NetworkMessage::ReportMisbehavior(report) => self.report_misbehavior(report),
+                        NetworkMessage::Shutdown => {
+                            self.shutdown();
+                            break;
+                        }
                     }
                 }
                 // Accept incoming peer connections
@@ -312,12 +459,16 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
     }
 
     fn update_topology(&mut self) {
+        let now = tokio::time::Instant::now();
+        self.banned_peers.retain(|_, until| *until > now);
+
         let to_connect = self
             .current_topology
             .iter()
             // Peer is not connected but should
             .filter_map(|(peer, is_active)| {
                 (!self.peers.contains_key(&peer.public_key)
+                    && !self.banned_peers.contains_key(&peer.address)
                     && !self
                         .connecting_peers
                         .values()
@@ -374,6 +525,7 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
 
         let peer_id = PeerId::new(peer.p2p_addr, public_key.clone());
         Self::remove_online_peer(&self.online_peers_sender, &peer_id);
+        self.forget_clock_skew(public_key);
     }
 
     #[log(skip_all, fields(peer=%peer_id, conn_id=connection_id, disambiguator=disambiguator))]
@@ -394,6 +546,11 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
             return;
         }
 
+        if self.is_banned(&peer_id.address) {
+            iroha_logger::debug!(%peer_id, "Banned peer is trying to connect");
+            return;
+        }
+
         //  Insert peer if peer not in peers yet or replace peer if it's disambiguator value is smaller than new one (simultaneous connections resolution rule)
         match self.peers.get(&peer_id.public_key) {
             Some(peer) if peer.disambiguator > disambiguator => {
@@ -416,29 +573,118 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
             p2p_addr: peer_id.address.clone(),
             disambiguator,
         };
-        let _ = peer_message_sender.send(self.peer_message_sender.clone());
+        let certificate = self.certificates.get(self.key_pair.public_key()).cloned();
+        let _ = peer_message_sender.send((self.peer_message_sender.clone(), certificate));
         self.peers.insert(peer_id.public_key().clone(), ref_peer);
         Self::add_online_peer(&self.online_peers_sender, peer_id);
     }
 
-    fn peer_terminated(&mut self, Terminated { peer_id, conn_id }: Terminated) {
-        self.connecting_peers.remove(&conn_id);
+    fn peer_terminated(
+        &mut self,
+        Terminated {
+            peer_id,
+            conn_id,
+            error,
+        }: Terminated,
+    ) {
+        let connecting_to = self.connecting_peers.remove(&conn_id);
+        if let Some(error) = error {
+            // Handshake with the peer we are connecting to might fail before its id is known
+            let public_key = peer_id
+                .as_ref()
+                .map(|peer_id| peer_id.public_key().clone())
+                .or(connecting_to);
+            self.peer_failed(public_key, conn_id, &error);
+        }
         if let Some(peer_id) = peer_id {
             if let Some(peer) = self.peers.get(&peer_id.public_key) {
                 if peer.conn_id == conn_id {
                     iroha_logger::debug!(conn_id, peer=%peer_id, "Peer terminated");
                     self.peers.remove(&peer_id.public_key);
                     Self::remove_online_peer(&self.online_peers_sender, &peer_id);
+                    self.forget_clock_skew(&peer_id.public_key);
                 }
             }
         }
     }
 
+    fn update_certificates(&mut self, UpdateCertificates(certificates): UpdateCertificates) {
+        iroha_logger::debug!(
+            count = certificates.len(),
+            "Network receive new peer certificates"
+        );
+        self.certificates = certificates
+            .into_iter()
+            .map(|certificate| (certificate.peer().public_key().clone(), certificate))
+            .collect();
+    }
+
+    /// Check the certificate presented by the peer against the registered one
+    fn peer_certified(
+        &mut self,
+        Certified {
+            peer_id,
+            certificate,
+        }: Certified,
+    ) {
+        let is_valid = certificate.peer().public_key() == peer_id.public_key()
+            && certificate.verify().is_ok()
+            && self
+                .certificates
+                .get(peer_id.public_key())
+                .map_or(true, |registered| *registered == certificate);
+        if !is_valid {
+            iroha_logger::warn!(peer=%peer_id, %certificate, "Peer presented invalid certificate");
+            self.report_misbehavior(ReportMisbehavior {
+                public_key: peer_id.public_key().clone(),
+                misbehavior: Misbehavior::InvalidCertificate,
+            });
+            return;
+        }
+        if self.certificates.contains_key(peer_id.public_key()) {
+            iroha_logger::debug!(peer=%peer_id, operator=%certificate.operator(), "Peer presented registered certificate");
+        } else {
+            // NOTE: the certificate might not have been committed yet
+            iroha_logger::debug!(peer=%peer_id, operator=%certificate.operator(), "Peer presented certificate which isn't registered");
+        }
+    }
+
+    /// Apply the [`Policy`] of the error which terminated the peer
+    fn peer_failed(
+        &mut self,
+        public_key: Option<PublicKey>,
+        conn_id: ConnectionId,
+        error: &PeerError,
+    ) {
+        match (error.policy(), public_key) {
+            (Policy::Ban(misbehavior) | Policy::DropMessage(misbehavior), Some(public_key)) => {
+                iroha_logger::warn!(conn_id, %public_key, %error, "Peer connection failed");
+                self.report_misbehavior(ReportMisbehavior {
+                    public_key,
+                    misbehavior,
+                });
+            }
+            (Policy::Ban(_) | Policy::DropMessage(_), None) => {
+                iroha_logger::warn!(conn_id, %error, "Connection with unknown peer failed");
+            }
+            (Policy::Retry, public_key) => {
+                iroha_logger::debug!(conn_id, ?public_key, %error, "Peer connection failed, reconnecting on the next topology update");
+            }
+        }
+    }
+
     fn post(&mut self, Post { data, peer_id }: Post<T>) {
         iroha_logger::trace!(peer=%peer_id, "Post message");
         match self.peers.get(&peer_id.public_key) {
             Some(peer) => {
-                if peer.handle.post(data).is_err() {
+                if Self::post_to_peer(
+                    self.key_pair.public_key(),
+                    &peer_id.public_key,
+                    &peer.handle,
+                    data,
+                )
+                .is_err()
+                {
                     iroha_logger::error!(peer=%peer_id, "Failed to send message to peer");
                     self.peers.remove(&peer_id.public_key);
                     Self::remove_online_peer(&self.online_peers_sender, &peer_id);
@@ -457,10 +703,18 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
         let Self {
             peers,
             online_peers_sender,
+            key_pair,
             ..
         } = self;
         peers.retain(|public_key, ref_peer| {
-            if ref_peer.handle.post(data.clone()).is_err() {
+            if Self::post_to_peer(
+                key_pair.public_key(),
+                public_key,
+                &ref_peer.handle,
+                data.clone(),
+            )
+            .is_err()
+            {
                 let peer_id = PeerId::new(ref_peer.p2p_addr.clone(), public_key.clone());
                 iroha_logger::error!(peer=%peer_id, "Failed to send message to peer");
                 Self::remove_online_peer(online_peers_sender, &peer_id);
@@ -471,9 +725,9 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
         });
     }
 
-    async fn peer_message(&mut self, PeerMessage(peer_id, msg): PeerMessage<T>) {
+    async fn peer_message(&mut self, msg: PeerMessage<T>) {
         // TODO: consider broadcast channel instead
-        iroha_logger::trace!(peer=%peer_id, "Received peer message");
+        iroha_logger::trace!(peer=%msg.0, "Received peer message");
         if self.subscribers_to_peers_messages.is_empty() {
             iroha_logger::warn!("No subscribers to send message to");
             return;
@@ -494,7 +748,159 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
             .await;
     }
 
-    fn subscribe_to_peers_messages(&mut self, subscriber: mpsc::Sender<T>) {
+    /// Post `data` to the peer with `their_key` through its `handle`,
+    /// unless the injected faults decide otherwise.
+    #[cfg_attr(not(feature = "fault-injection"), allow(unused_variables))]
+    fn post_to_peer(
+        our_key: &PublicKey,
+        their_key: &PublicKey,
+        handle: &PeerHandle<T>,
+        data: T,
+    ) -> Result<(), mpsc::error::SendError<T>> {
+        #[cfg(feature = "fault-injection")]
+        let data = match crate::faults::global().apply(our_key, their_key, data) {
+            crate::faults::Fate::Deliver(data) => data,
+            crate::faults::Fate::Delay(delay, data) => {
+                let handle = handle.clone();
+                tokio::task::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    // NOTE: the peer might be gone by then, that's fine
+                    let _ = handle.post(data);
+                });
+                return Ok(());
+            }
+            crate::faults::Fate::Drop => return Ok(()),
+        };
+        handle.post(data)
+    }
+
+    /// Disconnect peers with the given address and refuse connections with them until the ban expires.
+    fn ban_peer(&mut self, BanPeer { address, duration }: BanPeer) {
+        iroha_logger::info!(%address, ?duration, "Banning peer");
+        let now = tokio::time::Instant::now();
+        let Some(banned_until) = now.checked_add(duration) else {
+            iroha_logger::error!(
+                %address,
+                ?duration,
+                "Ban duration overflows the clock, ignoring the ban"
+            );
+            return;
+        };
+        self.banned_peers.insert(address.clone(), banned_until);
+
+        let to_disconnect = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.p2p_addr == address)
+            .map(|(public_key, _)| public_key.clone())
+            .collect::<Vec<_>>();
+        for public_key in to_disconnect {
+            self.disconnect_peer(&public_key);
+        }
+    }
+
+    /// Decrease the score of the peer and ban it if the score drops below the threshold.
+    fn report_misbehavior(
+        &mut self,
+        ReportMisbehavior {
+            public_key,
+            misbehavior,
+        }: ReportMisbehavior,
+    ) {
+        let address = self
+            .peers
+            .get(&public_key)
+            .map(|peer| peer.p2p_addr.clone())
+            .or_else(|| {
+                self.current_topology
+                    .keys()
+                    .find(|peer_id| *peer_id.public_key() == public_key)
+                    .map(|peer_id| peer_id.address.clone())
+            });
+        let Some(address) = address else {
+            iroha_logger::debug!(%public_key, ?misbehavior, "Misbehavior of unknown peer is ignored");
+            return;
+        };
+
+        iroha_logger::warn!(%address, %public_key, ?misbehavior, "Peer misbehaved");
+        let now = tokio::time::Instant::now();
+        if self.reputation.report(&public_key, misbehavior, now) {
+            self.ban_peer(BanPeer {
+                address,
+                duration: self.reputation_ban_duration,
+            });
+        }
+        self.peer_scores_sender
+            .send_replace(self.reputation.scores());
+    }
+
+    fn clock_skew_measured(
+        &mut self,
+        ClockSkewMeasured {
+            public_key,
+            skew_ms,
+        }: ClockSkewMeasured,
+    ) {
+        let threshold = self.clock_skew_warn_threshold;
+        let is_skewed = |skew_ms: i64| u128::from(skew_ms.unsigned_abs()) > threshold.as_millis();
+        // Log once the skew crosses the threshold rather than on every heartbeat
+        let was_skewed = self
+            .clock_skews
+            .get(&public_key)
+            .is_some_and(|skew_ms| is_skewed(*skew_ms));
+        match (was_skewed, is_skewed(skew_ms)) {
+            (false, true) => iroha_logger::warn!(
+                %public_key,
+                skew_ms,
+                ?threshold,
+                "Clock of the peer is skewed, transactions might be rejected as expired or dated in the future"
+            ),
+            (true, false) => {
+                iroha_logger::info!(%public_key, skew_ms, "Clock of the peer is no longer skewed");
+            }
+            _ => {}
+        }
+
+        self.clock_skews.insert(public_key, skew_ms);
+        self.clock_skews_sender
+            .send_replace(self.clock_skews.clone());
+    }
+
+    fn forget_clock_skew(&mut self, public_key: &PublicKey) {
+        if self.clock_skews.remove(public_key).is_some() {
+            self.clock_skews_sender
+                .send_replace(self.clock_skews.clone());
+        }
+    }
+
+    fn recover_reputation(&mut self) {
+        if self.reputation.recover(tokio::time::Instant::now()) {
+            self.peer_scores_sender
+                .send_replace(self.reputation.scores());
+        }
+    }
+
+    fn is_banned(&self, address: &SocketAddr) -> bool {
+        self.banned_peers
+            .get(address)
+            .is_some_and(|until| *until > tokio::time::Instant::now())
+    }
+
+    /// Drop handles to all peers, which makes them say goodbye and terminate.
+    fn shutdown(&mut self) {
+        iroha_logger::info!(
+            peers = self.peers.len(),
+            "Network is being shut down, closing connections"
+        );
+        self.current_topology.clear();
+        self.connecting_peers.clear();
+        for (public_key, peer) in self.peers.drain() {
+            let peer_id = PeerId::new(peer.p2p_addr, public_key);
+            Self::remove_online_peer(&self.online_peers_sender, &peer_id);
+        }
+    }
+
+    fn subscribe_to_peers_messages(&mut self, subscriber: mpsc::Sender<PeerMessage<T>>) {
         self.subscribers_to_peers_messages.push(subscriber);
         iroha_logger::trace!(
             subscribers = self.subscribers_to_peers_messages.len(),
@@ -529,6 +935,13 @@ pub mod message {
     #[derive(Clone, Debug)]
     pub struct UpdateTopology(pub OnlinePeers);
 
+    /// The message that is sent to [`NetworkBase`] to update the certificates registered by the peers.
+    ///
+    /// The certificate of this peer is presented to the others, while the certificates
+    /// they present are checked against the registered ones.
+    #[derive(Clone, Debug)]
+    pub struct UpdateCertificates(pub Vec<PeerCertificate>);
+
     /// The message to be sent to the other [`Peer`].
     #[derive(Clone, Debug)]
     pub struct Post<T> {
@@ -545,10 +958,23 @@ pub mod message {
         pub data: T,
     }
 
+    /// The message to temporarily ban the [`Peer`] with the given address.
+    #[derive(Clone, Debug)]
+    pub struct BanPeer {
+        /// Address of the peer to ban
+        pub address: SocketAddr,
+        /// For how long the peer is banned
+        pub duration: Duration,
+    }
+
     /// Message send to network by other actors.
     pub(crate) enum NetworkMessage<T> {
         Post(Post<T>),
         Broadcast(Broadcast<T>),
+        BanPeer(BanPeer),
+        UpdateCertificates(UpdateCertificates),
+        ReportMisbehavior(ReportMisbehavior),
+        Shutdown,
     }
 }
 
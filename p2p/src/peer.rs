@@ -2,8 +2,9 @@
 
 use bytes::{Buf, BufMut, BytesMut};
 use iroha_data_model::prelude::PeerId;
+use iroha_version::scale::{decode_all_with_limits, DecodeLimits};
 use message::*;
-use parity_scale_codec::{DecodeAll, Encode};
+use parity_scale_codec::Encode;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::{
@@ -75,6 +76,7 @@ pub mod handles {
     }
 
     /// Peer actor handle.
+    #[derive(Clone)]
     pub struct PeerHandle<T: Pload> {
         // NOTE: it's ok for this channel to be unbounded.
         // Because post messages originate inside the system and their rate is configurable..
@@ -96,7 +98,6 @@ mod run {
     //! Module with peer [`run`] function.
 
     use iroha_logger::prelude::*;
-    use parity_scale_codec::Decode;
     use tokio::time::Instant;
 
     use super::{
@@ -105,7 +106,13 @@ mod run {
         state::{ConnectedFrom, Connecting, Ready},
         *,
     };
-    use crate::unbounded_with_len;
+    use crate::{
+        clock::{self, ClockSkewMeasured, Heartbeat, HEARTBEAT_PROTOCOL_VERSION},
+        message::{Message, Received, PROTOCOL_VERSION},
+        reputation::{Misbehavior, ReportMisbehavior},
+        traffic::{TrafficClass, TrafficScheduler},
+        unbounded_with_len, PeerError, Policy, Stage,
+    };
 
     /// Peer task.
     #[allow(clippy::too_many_lines)]
@@ -123,19 +130,12 @@ mod run {
         iroha_logger::trace!("Peer created");
 
         // Insure proper termination from every execution path.
-        async {
+        let result: Result<(), PeerError> = async {
             // Try to do handshake process
-            let peer = match tokio::time::timeout(idle_timeout, peer.handshake()).await {
-                Ok(Ok(ready)) => ready,
-                Ok(Err(error)) => {
-                    iroha_logger::error!(%error, "Failure during handshake.");
-                    return;
-                },
-                Err(_) => {
-                    iroha_logger::error!(timeout=?idle_timeout, "Other peer has been idle during handshake");
-                    return;
-                }
-            };
+            let peer = tokio::time::timeout(idle_timeout, peer.handshake())
+                .await
+                .map_err(|_| PeerError::new(Stage::Handshake, Error::Idle(idle_timeout)))?
+                .map_err(|error| PeerError::new(Stage::Handshake, error))?;
 
             let Ready {
                 peer_id: new_peer_id,
@@ -157,7 +157,7 @@ mod run {
             let (post_sender, mut post_receiver) = unbounded_with_len::unbounded_channel();
             let (peer_message_sender, peer_message_receiver) = oneshot::channel();
             let ready_peer_handle = handles::PeerHandle { post_sender };
-            if service_message_sender
+            service_message_sender
                 .send(ServiceMessage::Connected(Connected {
                     connection_id,
                     peer_id: peer_id.clone(),
@@ -166,95 +166,156 @@ mod run {
                     disambiguator,
                 }))
                 .await
-                .is_err()
-            {
-                iroha_logger::error!(
-                    "Peer is ready, but network dropped connection sender."
-                );
-                return;
-            }
-            let Ok(peer_message_sender) = peer_message_receiver.await else {
+                .map_err(|_| PeerError::new(Stage::Handshake, Error::NetworkClosed))?;
+            let Ok((peer_message_sender, certificate)) = peer_message_receiver.await else {
                 // NOTE: this is not considered as error, because network might decide not to connect peer.
                 iroha_logger::debug!(
                     "Network decide not to connect peer."
                 );
-                return;
+                return Ok(());
             };
 
             iroha_logger::trace!("Peer connected");
 
             let mut message_reader = MessageReader::new(read, cryptographer.clone());
             let mut message_sender = MessageSender::new(write, cryptographer);
+            if let Some(certificate) = certificate {
+                // Peers of older versions skip the message of unknown kind
+                message_sender
+                    .prepare_message(Message::<T>::Certificate(certificate))
+                    .map_err(|error| PeerError::new(Stage::Write, error))?;
+            }
 
             let mut idle_interval = tokio::time::interval_at(Instant::now() + idle_timeout, idle_timeout);
             let mut ping_interval = tokio::time::interval_at(Instant::now() + idle_timeout / 2, idle_timeout / 2);
+            // Unlike pings, heartbeats are sent even if the connection is busy, to keep measuring the clock skew
+            let mut heartbeat_interval = tokio::time::interval(idle_timeout / 2);
 
             loop {
                 tokio::select! {
+                    _ = heartbeat_interval.tick() => {
+                        // Peers of older versions don't know heartbeats
+                        if message_sender.version >= HEARTBEAT_PROTOCOL_VERSION {
+                            message_sender
+                                .prepare_message(Message::<T>::Heartbeat(Heartbeat::now()))
+                                .map_err(|error| PeerError::new(Stage::Write, error))?;
+                        }
+                    }
                     _ = ping_interval.tick() => {
                         iroha_logger::trace!(
                             ping_period=?ping_interval.period(),
                             "The connection has been idle, pinging to check if it's alive"
                         );
-                        if let Err(error) = message_sender.prepare_message(Message::<T>::Ping) {
-                            iroha_logger::error!(%error, "Failed to encrypt message.");
-                            break;
-                        }
+                        message_sender
+                            .prepare_message(Message::<T>::Ping)
+                            .map_err(|error| PeerError::new(Stage::Write, error))?;
                     }
                     _ = idle_interval.tick() => {
-                        iroha_logger::error!(
-                            timeout=?idle_interval.period(),
-                            "Didn't receive anything from the peer within given timeout, abandoning this connection"
-                        );
-                        break;
+                        return Err(PeerError::new(Stage::Read, Error::Idle(idle_interval.period())));
                     }
                     msg = post_receiver.recv() => {
                         let Some(msg) = msg else {
-                            iroha_logger::debug!("Peer handle dropped.");
-                            break;
+                            iroha_logger::debug!("Peer handle dropped. Saying goodbye to the peer.");
+                            message_sender
+                                .prepare_message(Message::<T>::Goodbye)
+                                .map_err(|error| PeerError::new(Stage::Write, error))?;
+                            match tokio::time::timeout(idle_timeout, message_sender.flush()).await {
+                                Ok(Ok(())) => {}
+                                Ok(Err(error)) => iroha_logger::debug!(%error, "Failed to deliver goodbye to the peer."),
+                                Err(_) => iroha_logger::debug!(timeout=?idle_timeout, "Peer didn't accept goodbye in time."),
+                            }
+                            return Ok(());
                         };
                         iroha_logger::trace!("Post message");
                         let post_receiver_len = post_receiver.len();
                         if post_receiver_len > 100 {
                             iroha_logger::warn!(size=post_receiver_len, "Peer post messages are pilling up");
                         }
-                        if let Err(error) = message_sender.prepare_message(Message::Data(msg)) {
-                            iroha_logger::error!(%error, "Failed to encrypt message.");
-                            break;
-                        }
+                        message_sender
+                            .prepare_message(Message::Data(msg))
+                            .map_err(|error| PeerError::new(Stage::Write, error))?;
                     }
                     msg = message_reader.read_message() => {
                         let msg = match msg {
-                            Ok(Some(msg)) => {
-                                msg
+                            Ok(Some(Received::Message { version, message })) => {
+                                message_sender.version = version.min(PROTOCOL_VERSION);
+                                message
                             },
+                            Ok(Some(Received::UnknownKind { version, kind })) => {
+                                message_sender.version = version.min(PROTOCOL_VERSION);
+                                if version > PROTOCOL_VERSION {
+                                    iroha_logger::debug!(version, kind, "Peer sent message of a kind from the newer protocol version, ignoring it.");
+                                    idle_interval.reset();
+                                    ping_interval.reset();
+                                    continue;
+                                }
+                                iroha_logger::warn!(version, kind, "Peer sent message of unknown kind, ignoring it.");
+                                report(&service_message_sender, peer_id, Misbehavior::ProtocolViolation).await?;
+                                continue;
+                            }
                             Ok(None) => {
                                 iroha_logger::debug!("Peer send whole message and close connection");
-                                break;
+                                return Ok(());
                             }
                             Err(error) => {
-                                iroha_logger::error!(?error, "Error while reading message from peer.");
-                                break;
+                                let error = PeerError::new(Stage::Read, error);
+                                let Policy::DropMessage(misbehavior) = error.policy() else {
+                                    return Err(error);
+                                };
+                                iroha_logger::warn!(%error, "Dropping message sent by the peer.");
+                                report(&service_message_sender, peer_id, misbehavior).await?;
+                                continue;
                             }
                         };
                         match msg {
                             Message::Ping => {
                                 iroha_logger::trace!("Received peer ping");
-                                if let Err(error) = message_sender.prepare_message(Message::<T>::Pong) {
-                                    iroha_logger::error!(%error, "Failed to encrypt message.");
-                                    break;
-                                }
+                                message_sender
+                                    .prepare_message(Message::<T>::Pong)
+                                    .map_err(|error| PeerError::new(Stage::Write, error))?;
                             },
                             Message::Pong => {
                                 iroha_logger::trace!("Received peer pong");
                             }
+                            Message::Heartbeat(heartbeat) => {
+                                iroha_logger::trace!("Received peer heartbeat");
+                                let ack = heartbeat.ack(clock::unix_time_ms());
+                                message_sender
+                                    .prepare_message(Message::<T>::HeartbeatAck(ack))
+                                    .map_err(|error| PeerError::new(Stage::Write, error))?;
+                            }
+                            Message::HeartbeatAck(ack) => {
+                                let skew_ms = ack.clock_skew_ms(clock::unix_time_ms());
+                                iroha_logger::trace!(skew_ms, "Received peer heartbeat acknowledgement");
+                                service_message_sender
+                                    .send(ServiceMessage::ClockSkew(ClockSkewMeasured {
+                                        public_key: peer_id.public_key().clone(),
+                                        skew_ms,
+                                    }))
+                                    .await
+                                    .map_err(|_| PeerError::new(Stage::Read, Error::NetworkClosed))?;
+                            }
+                            Message::Goodbye => {
+                                iroha_logger::debug!("Peer said goodbye, closing connection");
+                                return Ok(());
+                            }
+                            Message::Certificate(certificate) => {
+                                iroha_logger::trace!("Received peer certificate");
+                                service_message_sender
+                                    .send(ServiceMessage::Certified(Certified {
+                                        peer_id: peer_id.clone(),
+                                        certificate,
+                                    }))
+                                    .await
+                                    .map_err(|_| PeerError::new(Stage::Read, Error::NetworkClosed))?;
+                            }
                             Message::Data(msg) => {
                                 iroha_logger::trace!("Received peer message");
                                 let peer_message = PeerMessage(peer_id.clone(), msg);
-                                if peer_message_sender.send(peer_message).await.is_err() {
-                                    iroha_logger::error!("Network dropped peer message channel.");
-                                    break;
-                                }
+                                peer_message_sender
+                                    .send(peer_message)
+                                    .await
+                                    .map_err(|_| PeerError::new(Stage::Read, Error::NetworkClosed))?;
                             }
                         };
                         // Reset idle and ping timeout as peer received message from another peer
@@ -262,23 +323,45 @@ mod run {
                         ping_interval.reset();
                     }
                     result = message_sender.send() => {
-                        if let Err(error) = result {
-                            iroha_logger::error!(%error, "Failed to send message to peer.");
-                            break;
-                        }
+                        result.map_err(|error| PeerError::new(Stage::Write, error))?;
                     }
-                    else => break,
+                    else => return Ok(()),
                 }
                 tokio::task::yield_now().await;
             }
         }.await;
 
-        iroha_logger::debug!("Peer is terminated.");
+        match &result {
+            Ok(()) => iroha_logger::debug!("Peer is terminated."),
+            Err(error) => {
+                iroha_logger::debug!(%error, policy=?error.policy(), "Peer is terminated.")
+            }
+        }
         let _ = service_message_sender
-            .send(ServiceMessage::Terminated(Terminated { peer_id, conn_id }))
+            .send(ServiceMessage::Terminated(Terminated {
+                peer_id,
+                conn_id,
+                error: result.err(),
+            }))
             .await;
     }
 
+    /// Report misbehavior of the peer to the network
+    async fn report<T: Pload>(
+        service_message_sender: &mpsc::Sender<ServiceMessage<T>>,
+        peer_id: &PeerId,
+        misbehavior: Misbehavior,
+    ) -> Result<(), PeerError> {
+        let report = ReportMisbehavior {
+            public_key: peer_id.public_key().clone(),
+            misbehavior,
+        };
+        service_message_sender
+            .send(ServiceMessage::Misbehaved(report))
+            .await
+            .map_err(|_| PeerError::new(Stage::Read, Error::NetworkClosed))
+    }
+
     /// Args to pass inside [`run`] function.
     pub(super) struct RunPeerArgs<T: Pload, P> {
         pub peer: P,
@@ -328,7 +411,7 @@ mod run {
         /// - Fail in case reading from stream fails
         /// - Connection is closed by there is still unfinished message in buffer
         /// - Forward errors from [`Self::parse_message`]
-        async fn read_message<T: Pload>(&mut self) -> Result<Option<T>, Error> {
+        async fn read_message<T: Pload>(&mut self) -> Result<Option<Received<T>>, Error> {
             loop {
                 // Try to get full message
                 if let Some(msg) = self.parse_message()? {
@@ -348,14 +431,21 @@ mod run {
         ///
         /// # Errors
         /// - Fail to decrypt message
-        /// - Fail to decode message
-        fn parse_message<T: Pload>(&mut self) -> Result<Option<T>, Error> {
+        /// - Fail to decode message, the message is skipped in this case
+        fn parse_message<T: Pload>(&mut self) -> Result<Option<Received<T>>, Error> {
             let mut buf = &self.buffer[..];
             if buf.remaining() < Self::U32_SIZE {
                 // Not enough data to read u32
                 return Ok(None);
             }
             let size = buf.get_u32() as usize;
+            if size > DecodeLimits::DEFAULT.max_len {
+                // Don't wait for the message which would be rejected anyway
+                return Err(Error::MessageTooLarge {
+                    size,
+                    max_len: DecodeLimits::DEFAULT.max_len,
+                });
+            }
             if buf.remaining() < size {
                 // Not enough data to read the whole data
                 return Ok(None);
@@ -363,11 +453,12 @@ mod run {
 
             let data = &buf[..size];
             let decrypted = self.cryptographer.decrypt(data)?;
-            let decoded = DecodeAll::decode_all(&mut decrypted.as_slice())?;
+            let decoded = Received::decode(&decrypted);
 
+            // Malformed message is skipped as well, so that the following ones can be read
             self.buffer.advance(size + Self::U32_SIZE);
 
-            Ok(Some(decoded))
+            Ok(Some(decoded?))
         }
     }
 
@@ -376,8 +467,12 @@ mod run {
         cryptographer: Cryptographer<E>,
         /// Reusable buffer to encode messages
         buffer: Vec<u8>,
-        /// Queue of encrypted messages waiting to be sent
+        /// Encrypted messages waiting to be sent, queued per traffic class
+        scheduler: TrafficScheduler<Vec<u8>>,
+        /// Bytes of the message being sent
         queue: BytesMut,
+        /// Protocol version used to encode messages, the lowest of the local and the remote ones
+        version: u8,
     }
 
     impl<E: Enc> MessageSender<E> {
@@ -389,37 +484,65 @@ mod run {
                 cryptographer,
                 // TODO: eyeball decision of default buffer size of 1 KB, should be benchmarked and optimized
                 buffer: Vec::with_capacity(1024),
+                scheduler: TrafficScheduler::default(),
                 queue: BytesMut::with_capacity(1024),
+                version: PROTOCOL_VERSION,
             }
         }
 
-        /// Prepare message for the delivery and put it into the queue to be sent later
+        /// Prepare message for the delivery and put it into the queue of its traffic class
+        /// to be sent later
         ///
         /// # Errors
         /// - If encryption fail.
-        fn prepare_message<T: Pload>(&mut self, msg: T) -> Result<(), Error> {
+        fn prepare_message<T: Pload>(&mut self, msg: Message<T>) -> Result<(), Error> {
+            let class = match &msg {
+                Message::Data(data) => data.traffic_class(),
+                Message::Goodbye => {
+                    // The peer stops reading after goodbye, so everything queued must go first
+                    while let Some(frame) = self.scheduler.pop() {
+                        self.queue.extend_from_slice(&frame);
+                    }
+                    TrafficClass::Consensus
+                }
+                Message::Ping
+                | Message::Pong
+                | Message::Certificate(_)
+                | Message::Heartbeat(_)
+                | Message::HeartbeatAck(_) => TrafficClass::Consensus,
+            };
+
             // Start with fresh buffer
             self.buffer.clear();
-            msg.encode_to(&mut self.buffer);
+            msg.encode_versioned_to(self.version, &mut self.buffer);
             let encrypted = self.cryptographer.encrypt(&self.buffer)?;
 
             let size = encrypted.len();
-            self.queue.reserve(size + Self::U32_SIZE);
+            let mut frame = Vec::with_capacity(size + Self::U32_SIZE);
             #[allow(clippy::cast_possible_truncation)]
-            self.queue.put_u32(size as u32);
-            self.queue.put_slice(encrypted.as_slice());
+            frame.put_u32(size as u32);
+            frame.put_slice(encrypted.as_slice());
+            self.scheduler.push(class, frame.len(), frame);
             Ok(())
         }
 
         /// Send bytes of byte-encoded messages piled up in the message queue so far.
         /// On the other side peer will collect bytes and recreate original messages from them.
         ///
+        /// Once the message being sent is written completely, the next one is picked by the
+        /// [`TrafficScheduler`], so that messages of the higher classes overtake the backlog.
+        ///
         /// Sends only as much data as the underlying writer will accept in one `.write` call,
         /// so must be called in a loop to ensure everything will get sent.
         ///
         /// # Errors
         /// - If write to `stream` fail.
         async fn send(&mut self) -> Result<(), Error> {
+            if self.queue.is_empty() {
+                if let Some(frame) = self.scheduler.pop() {
+                    self.queue.extend_from_slice(&frame);
+                }
+            }
             let chunk = self.queue.chunk();
             if !chunk.is_empty() {
                 let n = self.write.write(chunk).await?;
@@ -427,14 +550,18 @@ mod run {
             }
             Ok(())
         }
-    }
 
-    /// Either message or ping
-    #[derive(Encode, Decode, Clone, Debug)]
-    enum Message<T> {
-        Data(T),
-        Ping,
-        Pong,
+        /// Send everything piled up in the message queue so far.
+        ///
+        /// # Errors
+        /// - If write to `stream` fail.
+        async fn flush(&mut self) -> Result<(), Error> {
+            while !self.queue.is_empty() || !self.scheduler.is_empty() {
+                self.send().await?;
+            }
+            self.write.flush().await?;
+            Ok(())
+        }
     }
 }
 
@@ -631,7 +758,7 @@ mod state {
             let data = cryptographer.decrypt(data.as_slice())?;
 
             let (remote_pub_key, signature): (PublicKey, Signature) =
-                DecodeAll::decode_all(&mut data.as_slice())?;
+                decode_all_with_limits(&data, DecodeLimits::DEFAULT)?;
 
             // Swap order of keys since we are verifying for other peer order remote/local keys is reversed
             let payload = create_payload::<K>(&kx_remote_pk, &kx_local_pk);
@@ -742,7 +869,10 @@ mod handshake {
 pub mod message {
     //! Module for peer messages
 
+    use iroha_data_model::peer::PeerCertificate;
+
     use super::*;
+    use crate::{clock::ClockSkewMeasured, reputation::ReportMisbehavior, PeerError};
 
     /// Connection and Handshake was successful
     pub struct Connected<T: Pload> {
@@ -752,13 +882,16 @@ pub mod message {
         pub connection_id: ConnectionId,
         /// Handle for peer to send messages and terminate command
         pub ready_peer_handle: handles::PeerHandle<T>,
-        /// Channel to send peer messages channel
-        pub peer_message_sender: oneshot::Sender<mpsc::Sender<PeerMessage<T>>>,
+        /// Channel to send peer messages channel together with the certificate
+        /// to present to the peer
+        pub peer_message_sender:
+            oneshot::Sender<(mpsc::Sender<PeerMessage<T>>, Option<PeerCertificate>)>,
         /// Disambiguator of connection (equal for both peers)
         pub disambiguator: u64,
     }
 
     /// Messages received from Peer
+    #[derive(Clone)]
     pub struct PeerMessage<T: Pload>(pub PeerId, pub T);
 
     /// Peer faced error or `Terminate` message, send to indicate that it is terminated
@@ -767,6 +900,16 @@ pub mod message {
         pub peer_id: Option<PeerId>,
         /// Connection Id
         pub conn_id: ConnectionId,
+        /// Error the peer failed with, `None` if the connection was closed gracefully
+        pub error: Option<PeerError>,
+    }
+
+    /// Peer presented its certificate
+    pub struct Certified {
+        /// Peer Id
+        pub peer_id: PeerId,
+        /// Certificate sent by the peer
+        pub certificate: PeerCertificate,
     }
 
     /// Messages sent by peer during connection process
@@ -775,6 +918,12 @@ pub mod message {
         Connected(Connected<T>),
         /// Peer faced error or `Terminate` message, send to indicate that it is terminated
         Terminated(Terminated),
+        /// Peer sent something it shouldn't have
+        Misbehaved(ReportMisbehavior),
+        /// Peer presented its certificate
+        Certified(Certified),
+        /// Clock skew of the peer was measured
+        ClockSkew(ClockSkewMeasured),
     }
 }
 
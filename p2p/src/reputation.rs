@@ -0,0 +1,121 @@
+//! Reputation of the peers.
+//!
+//! Every peer starts with the score of `0`. Misbehavior decreases the score of the peer,
+//! while time recovers it back up to `0`. The peer is banned once its score drops
+//! below the configured threshold.
+use std::{collections::HashMap, num::NonZeroU32, time::Duration};
+
+use iroha_crypto::PublicKey;
+use tokio::time::Instant;
+
+/// Score recovered by a peer every second
+pub const RECOVERY_PER_SECOND: i64 = 1;
+
+/// Scores of the peers which have misbehaved recently
+pub type PeerScores = HashMap<PublicKey, i64>;
+
+/// Kind of the peer misbehavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Peer sent a message which can't be decoded
+    MalformedMessage,
+    /// Peer sent data with an invalid signature
+    InvalidSignature,
+    /// Peer sent a message which doesn't follow the protocol
+    ProtocolViolation,
+    /// Peer presented a certificate which doesn't match the registered one
+    InvalidCertificate,
+}
+
+impl Misbehavior {
+    /// Amount by which the score of the peer is decreased
+    pub const fn penalty(self) -> i64 {
+        match self {
+            Self::MalformedMessage => 20,
+            Self::InvalidSignature => 25,
+            Self::ProtocolViolation => 10,
+            Self::InvalidCertificate => 25,
+        }
+    }
+}
+
+/// The message to report misbehavior of the [`Peer`](crate::peer) with the given public key.
+#[derive(Clone, Debug)]
+pub struct ReportMisbehavior {
+    /// Public key of the misbehaving peer
+    pub public_key: PublicKey,
+    /// What the peer did
+    pub misbehavior: Misbehavior,
+}
+
+/// Tracker of the peer scores
+#[derive(Debug)]
+pub struct Reputation {
+    ban_threshold: i64,
+    /// Score of the peer and the instant it was last updated at
+    scores: HashMap<PublicKey, (i64, Instant)>,
+}
+
+impl Reputation {
+    /// Construct [`Self`] which bans peers when their score drops below `-ban_threshold`
+    pub fn new(ban_threshold: NonZeroU32) -> Self {
+        Self {
+            ban_threshold: -i64::from(ban_threshold.get()),
+            scores: HashMap::new(),
+        }
+    }
+
+    /// Decrease the score of the peer.
+    ///
+    /// Return `true` if the peer has to be banned, in which case its score is reset.
+    pub fn report(
+        &mut self,
+        public_key: &PublicKey,
+        misbehavior: Misbehavior,
+        now: Instant,
+    ) -> bool {
+        let (score, updated_at) = self.scores.entry(public_key.clone()).or_insert((0, now));
+        Self::recover_score(score, updated_at, now);
+        *score -= misbehavior.penalty();
+
+        if *score < self.ban_threshold {
+            self.scores.remove(public_key);
+            return true;
+        }
+        false
+    }
+
+    /// Recover scores of the peers, forgetting the ones which have fully recovered.
+    ///
+    /// Return `true` if any of the scores has changed.
+    pub fn recover(&mut self, now: Instant) -> bool {
+        let mut changed = false;
+        self.scores.retain(|_, (score, updated_at)| {
+            changed |= Self::recover_score(score, updated_at, now);
+            *score < 0
+        });
+        changed
+    }
+
+    /// Current scores of the peers which have misbehaved recently
+    pub fn scores(&self) -> PeerScores {
+        self.scores
+            .iter()
+            .map(|(public_key, (score, _))| (public_key.clone(), *score))
+            .collect()
+    }
+
+    fn recover_score(score: &mut i64, updated_at: &mut Instant, now: Instant) -> bool {
+        let seconds = now.saturating_duration_since(*updated_at).as_secs();
+        if seconds == 0 {
+            return false;
+        }
+
+        let recovered = i64::try_from(seconds)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(RECOVERY_PER_SECOND);
+        *score = score.saturating_add(recovered).min(0);
+        *updated_at += Duration::from_secs(seconds);
+        true
+    }
+}
@@ -0,0 +1,111 @@
+//! Scheduling of the outgoing traffic of a peer connection.
+//!
+//! Every message sent to a peer belongs to a [`TrafficClass`]. Messages waiting to be written
+//! to the socket are queued per class and the [`TrafficScheduler`] picks the next one using
+//! self-clocked weighted fair queuing: a message is tagged with the virtual time at which it
+//! would finish if every class got bandwidth in proportion to its [weight](TrafficClass::weight),
+//! and the message with the earliest tag is sent first.
+//!
+//! A message which is already being written isn't interrupted, but consensus messages overtake
+//! any backlog of bulk data, so that catching up peers don't starve view changes and block
+//! signatures. Lower classes still get their share of the bandwidth.
+use std::collections::VecDeque;
+
+/// Class of the traffic, which determines the share of bandwidth the messages get
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrafficClass {
+    /// Messages the consensus depends on, e.g. block votes and view change proofs.
+    ///
+    /// Connection control messages like pings belong here as well.
+    Consensus,
+    /// Messages without particular requirements, e.g. transaction gossip
+    Regular,
+    /// Bulk data which can wait, e.g. blocks sent to a peer which is catching up
+    Sync,
+}
+
+impl TrafficClass {
+    /// All classes, from the highest priority to the lowest
+    pub const ALL: [Self; 3] = [Self::Consensus, Self::Regular, Self::Sync];
+
+    /// Share of the bandwidth the class gets relative to the other classes
+    pub const fn weight(self) -> u64 {
+        match self {
+            Self::Consensus => 16,
+            Self::Regular => 4,
+            Self::Sync => 1,
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Assignment of the messages to the [`TrafficClass`]es
+pub trait Classify {
+    /// Class of the traffic the message belongs to
+    fn traffic_class(&self) -> TrafficClass {
+        TrafficClass::Regular
+    }
+}
+
+/// Weighted fair queue of the messages waiting to be sent, see the [module documentation](self)
+#[derive(Debug)]
+pub struct TrafficScheduler<F> {
+    queues: [VecDeque<(u64, F)>; TrafficClass::ALL.len()],
+    /// Finish tag of the last message queued per class
+    last_finish: [u64; TrafficClass::ALL.len()],
+    /// Finish tag of the last message handed out to be sent
+    virtual_time: u64,
+}
+
+impl<F> Default for TrafficScheduler<F> {
+    fn default() -> Self {
+        Self {
+            queues: Default::default(),
+            last_finish: [0; TrafficClass::ALL.len()],
+            virtual_time: 0,
+        }
+    }
+}
+
+impl<F> TrafficScheduler<F> {
+    /// Queue the `frame` of `size` bytes belonging to the `class`
+    pub fn push(&mut self, class: TrafficClass, size: usize, frame: F) {
+        let index = class.index();
+        let start = self.virtual_time.max(self.last_finish[index]);
+        let cost = (size as u64).max(1).div_ceil(class.weight());
+        let finish = start.saturating_add(cost);
+        self.last_finish[index] = finish;
+        self.queues[index].push_back((finish, frame));
+    }
+
+    /// Take the frame which should be sent next, if any
+    pub fn pop(&mut self) -> Option<F> {
+        // Ties are resolved in favor of the higher class
+        let index = TrafficClass::ALL
+            .iter()
+            .filter_map(|class| {
+                let index = class.index();
+                self.queues[index]
+                    .front()
+                    .map(|(finish, _)| (*finish, index))
+            })
+            .min()?
+            .1;
+        let (finish, frame) = self.queues[index].pop_front()?;
+        self.virtual_time = finish;
+        Some(frame)
+    }
+
+    /// Number of the frames of the `class` waiting to be sent
+    pub fn len(&self, class: TrafficClass) -> usize {
+        self.queues[class.index()].len()
+    }
+
+    /// Whether there are no frames waiting to be sent
+    pub fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+}
@@ -0,0 +1,30 @@
+use iroha_crypto::KeyPair;
+use iroha_p2p::faults::{Fate, Faults};
+
+#[test]
+fn partition_blocks_only_between_groups() {
+    let [a, b, c, d] = core::array::from_fn(|_| KeyPair::random().into_parts().0);
+    let faults = Faults::new(0);
+    faults.partition([vec![a.clone()], vec![b.clone(), c.clone()]]);
+
+    assert_eq!(faults.apply(&a, &b, 1_u32), Fate::Drop);
+    assert_eq!(faults.apply(&b, &c, 1_u32), Fate::Deliver(1));
+    assert_eq!(faults.apply(&a, &d, 1_u32), Fate::Deliver(1));
+
+    faults.heal();
+    assert_eq!(faults.apply(&a, &b, 1_u32), Fate::Deliver(1));
+}
+
+#[test]
+fn corrupted_payload_differs() {
+    let [a, b] = core::array::from_fn(|_| KeyPair::random().into_parts().0);
+    let faults = Faults::new(0);
+    faults.corrupt_messages(1.0);
+
+    for _ in 0..16 {
+        match faults.apply(&a, &b, 0_u64) {
+            Fate::Deliver(data) => assert_ne!(data, 0),
+            fate => panic!("Unexpected {fate:?}"),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use iroha_crypto::KeyPair;
+use iroha_data_model::{
+    account::AccountId,
+    metadata::Metadata,
+    peer::{PeerCertificate, PeerCertificatePayload, PeerId},
+};
+use iroha_p2p::{
+    clock::{Heartbeat, HeartbeatAck},
+    message::{Envelope, Message, MessageKind, Received, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION},
+};
+use parity_scale_codec::Encode;
+
+fn messages() -> [Message<String>; 4] {
+    [
+        Message::Data("hello".to_owned()),
+        Message::Ping,
+        Message::Pong,
+        Message::Goodbye,
+    ]
+}
+
+fn encode(message: &Message<String>, version: u8) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    message.encode_versioned_to(version, &mut buffer);
+    buffer
+}
+
+#[test]
+fn messages_are_enveloped() {
+    for message in messages() {
+        let encoded = encode(&message, PROTOCOL_VERSION);
+        assert_eq!(encoded[0], PROTOCOL_VERSION);
+        assert_eq!(encoded[1], message.kind() as u8);
+
+        assert_eq!(
+            Received::decode(&encoded).unwrap(),
+            Received::Message {
+                version: PROTOCOL_VERSION,
+                message
+            }
+        );
+    }
+}
+
+#[test]
+fn previous_version_is_understood() {
+    for message in messages() {
+        // Previous version sends messages without the envelope
+        let legacy = message.encode();
+        assert_eq!(encode(&message, MIN_PROTOCOL_VERSION), legacy);
+
+        assert_eq!(
+            Received::decode(&legacy).unwrap(),
+            Received::Message {
+                version: MIN_PROTOCOL_VERSION,
+                message
+            }
+        );
+    }
+}
+
+#[test]
+fn certificate_is_enveloped() {
+    let key_pair = KeyPair::random();
+    let payload = PeerCertificatePayload {
+        peer: PeerId::new(
+            "127.0.0.1:1337".parse().unwrap(),
+            key_pair.public_key().clone(),
+        ),
+        operator: AccountId::new("wonderland".parse().unwrap(), key_pair.public_key().clone()),
+        metadata: Metadata::default(),
+    };
+    let message =
+        Message::<String>::Certificate(PeerCertificate::new(payload, key_pair.private_key()));
+
+    let encoded = encode(&message, PROTOCOL_VERSION);
+    assert_eq!(encoded[1], MessageKind::Certificate as u8);
+    assert_eq!(
+        Received::decode(&encoded).unwrap(),
+        Received::Message {
+            version: PROTOCOL_VERSION,
+            message
+        }
+    );
+}
+
+#[test]
+fn heartbeats_are_enveloped() {
+    let heartbeat = Heartbeat { sent_at_ms: 1_000 };
+    let ack = heartbeat.ack(1_250);
+    for (message, kind) in [
+        (
+            Message::<String>::Heartbeat(heartbeat),
+            MessageKind::Heartbeat,
+        ),
+        (Message::HeartbeatAck(ack), MessageKind::HeartbeatAck),
+    ] {
+        let encoded = encode(&message, PROTOCOL_VERSION);
+        assert_eq!(encoded[1], kind as u8);
+        assert_eq!(
+            Received::decode(&encoded).unwrap(),
+            Received::Message {
+                version: PROTOCOL_VERSION,
+                message
+            }
+        );
+    }
+}
+
+#[test]
+fn clock_skew_is_estimated_from_heartbeat() {
+    // Peer clock is 300ms ahead, each way takes 50ms
+    let ack = HeartbeatAck {
+        heartbeat_sent_at_ms: 1_000,
+        received_at_ms: 1_350,
+        sent_at_ms: 1_360,
+    };
+    assert_eq!(ack.clock_skew_ms(1_110), 300);
+
+    // Peer clock is 300ms behind
+    let ack = HeartbeatAck {
+        heartbeat_sent_at_ms: 1_000,
+        received_at_ms: 750,
+        sent_at_ms: 760,
+    };
+    assert_eq!(ack.clock_skew_ms(1_110), -300);
+}
+
+#[test]
+fn envelope_of_previous_version_is_understood() {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION - 1,
+        kind: MessageKind::Data as u8,
+        payload: "hello".to_owned().encode(),
+    };
+
+    assert_eq!(
+        Received::decode(&envelope.encode()).unwrap(),
+        Received::Message {
+            version: PROTOCOL_VERSION - 1,
+            message: Message::Data("hello".to_owned())
+        }
+    );
+    // Replies to such peer are enveloped as well
+    assert_eq!(
+        encode(&Message::Data("hello".to_owned()), PROTOCOL_VERSION - 1)[0],
+        PROTOCOL_VERSION
+    );
+}
+
+#[test]
+fn unknown_kind_is_reported() {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION + 1,
+        kind: 42,
+        payload: vec![1, 2, 3],
+    };
+    assert!(MessageKind::try_from(envelope.kind).is_err());
+
+    assert_eq!(
+        Received::<String>::decode(&envelope.encode()).unwrap(),
+        Received::UnknownKind {
+            version: PROTOCOL_VERSION + 1,
+            kind: 42
+        }
+    );
+}
+
+#[test]
+fn newer_version_of_known_kind_is_understood() {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION + 1,
+        kind: MessageKind::Data as u8,
+        payload: "hello".to_owned().encode(),
+    };
+
+    assert_eq!(
+        Received::decode(&envelope.encode()).unwrap(),
+        Received::Message {
+            version: PROTOCOL_VERSION + 1,
+            message: Message::Data("hello".to_owned())
+        }
+    );
+}
+
+#[test]
+fn malformed_payload_is_rejected() {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION,
+        kind: MessageKind::Data as u8,
+        payload: vec![0xFF],
+    };
+
+    assert!(Received::<String>::decode(&envelope.encode()).is_err());
+}
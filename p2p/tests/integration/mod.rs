@@ -1 +1,6 @@
+#[cfg(feature = "fault-injection")]
+mod faults;
+mod message;
 mod p2p;
+mod reputation;
+mod traffic;
@@ -8,12 +8,12 @@ use std::{
 };
 
 use futures::{prelude::*, stream::FuturesUnordered, task::AtomicWaker};
-use iroha_config::parameters::actual::Network as Config;
+use iroha_config::parameters::{actual::Network as Config, defaults};
 use iroha_config_base::WithOrigin;
 use iroha_crypto::KeyPair;
 use iroha_data_model::prelude::PeerId;
 use iroha_logger::{prelude::*, test_logger};
-use iroha_p2p::{network::message::*, NetworkHandle};
+use iroha_p2p::{network::message::*, peer::message::PeerMessage, Classify, NetworkHandle};
 use iroha_primitives::addr::socket_addr;
 use parity_scale_codec::{Decode, Encode};
 use tokio::{
@@ -24,6 +24,8 @@ use tokio::{
 #[derive(Clone, Debug, Decode, Encode)]
 struct TestMessage(String);
 
+impl Classify for TestMessage {}
+
 fn setup_logger() {
     test_logger();
 }
@@ -44,6 +46,10 @@ async fn network_create() {
     let config = Config {
         address: WithOrigin::inline(address.clone()),
         idle_timeout,
+        reputation_ban_threshold: defaults::network::REPUTATION_BAN_THRESHOLD,
+        reputation_ban_duration: defaults::network::REPUTATION_BAN_DURATION,
+        clock_skew_warn_threshold: defaults::network::CLOCK_SKEW_WARN_THRESHOLD,
+        partition_timeout: defaults::network::PARTITION_TIMEOUT,
     };
     let network = NetworkHandle::start(key_pair, config).await.unwrap();
     tokio::time::sleep(delay).await;
@@ -117,18 +123,18 @@ impl Future for WaitForN {
 #[derive(Debug)]
 pub struct TestActor {
     messages: WaitForN,
-    receiver: mpsc::Receiver<TestMessage>,
+    receiver: mpsc::Receiver<PeerMessage<TestMessage>>,
 }
 
 impl TestActor {
-    fn start(messages: WaitForN) -> mpsc::Sender<TestMessage> {
+    fn start(messages: WaitForN) -> mpsc::Sender<PeerMessage<TestMessage>> {
         let (sender, receiver) = mpsc::channel(10);
         let mut test_actor = Self { messages, receiver };
         tokio::task::spawn(async move {
             loop {
                 tokio::select! {
-                    Some(msg) = test_actor.receiver.recv() => {
-                        info!(?msg, "Actor received message");
+                    Some(PeerMessage(peer_id, msg)) = test_actor.receiver.recv() => {
+                        info!(%peer_id, ?msg, "Actor received message");
                         test_actor.messages.inc();
                     },
                     else => break,
@@ -155,6 +161,10 @@ async fn two_networks() {
     let config1 = Config {
         address: WithOrigin::inline(address1.clone()),
         idle_timeout,
+        reputation_ban_threshold: defaults::network::REPUTATION_BAN_THRESHOLD,
+        reputation_ban_duration: defaults::network::REPUTATION_BAN_DURATION,
+        clock_skew_warn_threshold: defaults::network::CLOCK_SKEW_WARN_THRESHOLD,
+        partition_timeout: defaults::network::PARTITION_TIMEOUT,
     };
     let mut network1 = NetworkHandle::start(key_pair1, config1).await.unwrap();
 
@@ -163,6 +173,10 @@ async fn two_networks() {
     let config2 = Config {
         address: WithOrigin::inline(address2.clone()),
         idle_timeout,
+        reputation_ban_threshold: defaults::network::REPUTATION_BAN_THRESHOLD,
+        reputation_ban_duration: defaults::network::REPUTATION_BAN_DURATION,
+        clock_skew_warn_threshold: defaults::network::CLOCK_SKEW_WARN_THRESHOLD,
+        partition_timeout: defaults::network::PARTITION_TIMEOUT,
     };
     let network2 = NetworkHandle::start(key_pair2, config2).await.unwrap();
 
@@ -301,6 +315,10 @@ async fn start_network(
     let config = Config {
         address: WithOrigin::inline(address),
         idle_timeout,
+        reputation_ban_threshold: defaults::network::REPUTATION_BAN_THRESHOLD,
+        reputation_ban_duration: defaults::network::REPUTATION_BAN_DURATION,
+        clock_skew_warn_threshold: defaults::network::CLOCK_SKEW_WARN_THRESHOLD,
+        partition_timeout: defaults::network::PARTITION_TIMEOUT,
     };
     let mut network = NetworkHandle::start(key_pair, config).await.unwrap();
     network.subscribe_to_peers_messages(actor);
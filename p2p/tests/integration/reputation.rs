@@ -0,0 +1,51 @@
+use std::num::NonZeroU32;
+
+use iroha_crypto::KeyPair;
+use iroha_p2p::{
+    reputation::{Reputation, RECOVERY_PER_SECOND},
+    Misbehavior,
+};
+use tokio::time::{Duration, Instant};
+
+#[test]
+fn peer_is_banned_once_score_drops_below_threshold() {
+    let public_key = KeyPair::random().into_parts().0;
+    let mut reputation = Reputation::new(NonZeroU32::new(30).unwrap());
+    let now = Instant::now();
+
+    assert!(!reputation.report(&public_key, Misbehavior::ProtocolViolation, now));
+    assert!(!reputation.report(&public_key, Misbehavior::MalformedMessage, now));
+    assert_eq!(reputation.scores()[&public_key], -30);
+
+    assert!(reputation.report(&public_key, Misbehavior::ProtocolViolation, now));
+    // Score is reset after the ban
+    assert!(reputation.scores().is_empty());
+}
+
+#[test]
+fn score_recovers_over_time() {
+    let public_key = KeyPair::random().into_parts().0;
+    let mut reputation = Reputation::new(NonZeroU32::new(30).unwrap());
+    let now = Instant::now();
+
+    assert!(!reputation.report(&public_key, Misbehavior::InvalidSignature, now));
+    assert!(!reputation.recover(now + Duration::from_millis(500)));
+    assert_eq!(reputation.scores()[&public_key], -25);
+
+    assert!(reputation.recover(now + Duration::from_secs(5)));
+    assert_eq!(
+        reputation.scores()[&public_key],
+        -25 + 5 * RECOVERY_PER_SECOND
+    );
+
+    // Thanks to the recovery this report doesn't lead to the ban
+    assert!(!reputation.report(
+        &public_key,
+        Misbehavior::ProtocolViolation,
+        now + Duration::from_secs(10)
+    ));
+
+    // Fully recovered peers are forgotten
+    assert!(reputation.recover(now + Duration::from_secs(60)));
+    assert!(reputation.scores().is_empty());
+}
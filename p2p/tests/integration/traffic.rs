@@ -0,0 +1,45 @@
+use iroha_p2p::traffic::{TrafficClass, TrafficScheduler};
+
+#[test]
+fn consensus_message_overtakes_sync_backlog() {
+    let mut scheduler = TrafficScheduler::default();
+    for block in 0..10 {
+        scheduler.push(TrafficClass::Sync, 1_000_000, format!("block {block}"));
+    }
+
+    assert_eq!(scheduler.pop().as_deref(), Some("block 0"));
+    scheduler.push(TrafficClass::Consensus, 1_000, "vote".to_owned());
+    assert_eq!(scheduler.pop().as_deref(), Some("vote"));
+    assert_eq!(scheduler.pop().as_deref(), Some("block 1"));
+}
+
+#[test]
+fn bandwidth_is_shared_according_to_weights() {
+    let mut scheduler = TrafficScheduler::default();
+    for _ in 0..100 {
+        scheduler.push(TrafficClass::Consensus, 100, TrafficClass::Consensus);
+        scheduler.push(TrafficClass::Regular, 100, TrafficClass::Regular);
+        scheduler.push(TrafficClass::Sync, 100, TrafficClass::Sync);
+    }
+
+    let sent: Vec<_> = (0..42).map_while(|_| scheduler.pop()).collect();
+    let count = |class| sent.iter().filter(|sent| **sent == class).count();
+    assert_eq!(count(TrafficClass::Consensus), 32);
+    assert_eq!(count(TrafficClass::Regular), 8);
+    assert_eq!(count(TrafficClass::Sync), 2);
+}
+
+#[test]
+fn messages_of_the_same_class_keep_their_order() {
+    let mut scheduler = TrafficScheduler::default();
+    scheduler.push(TrafficClass::Regular, 10_000, 1);
+    scheduler.push(TrafficClass::Regular, 10, 2);
+    scheduler.push(TrafficClass::Regular, 1_000, 3);
+
+    assert_eq!(scheduler.len(TrafficClass::Regular), 3);
+    assert_eq!(
+        core::iter::from_fn(|| scheduler.pop()).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert!(scheduler.is_empty());
+}
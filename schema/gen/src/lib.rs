@@ -46,12 +46,16 @@ pub fn build_schemas() -> MetaMap {
 
         // Event stream
         EventMessage,
-        EventSubscriptionRequest,
+        SignedEventSubscriptionRequest,
 
         // Block stream
         BlockMessage,
         BlockSubscriptionRequest,
 
+        // Webhooks
+        SignedWebhookRequest,
+        WebhookStatus,
+
         // Never referenced, but present in type signature. Like `PhantomData<X>`
         MerkleTree<SignedTransaction>,
 
@@ -65,6 +69,7 @@ pub fn build_schemas() -> MetaMap {
         permission::account::CanUnregisterAccount,
         permission::account::CanSetKeyValueInAccount,
         permission::account::CanRemoveKeyValueInAccount,
+        permission::account::CanFreezeAnyAccount,
         permission::asset_definition::CanUnregisterAssetDefinition,
         permission::asset_definition::CanSetKeyValueInAssetDefinition,
         permission::asset_definition::CanRemoveKeyValueInAssetDefinition,
@@ -80,6 +85,7 @@ pub fn build_schemas() -> MetaMap {
         permission::asset::CanSetKeyValueInUserAsset,
         permission::asset::CanRemoveKeyValueInUserAsset,
         permission::parameter::CanSetParameters,
+        permission::event::CanSubscribeToAllEvents,
         permission::role::CanUnregisterAnyRole,
         permission::trigger::CanRegisterUserTrigger,
         permission::trigger::CanExecuteUserTrigger,
@@ -88,6 +94,7 @@ pub fn build_schemas() -> MetaMap {
         permission::trigger::CanBurnUserTrigger,
         permission::trigger::CanSetKeyValueInTrigger,
         permission::trigger::CanRemoveKeyValueInTrigger,
+        permission::trigger::CanInspectAnyTrigger,
         permission::executor::CanUpgradeExecutor,
 
         // Genesis file - used by SDKs to generate the genesis block
@@ -105,9 +112,14 @@ types!(
     AccountIdPredicateBox,
     AccountPermissionChanged,
     AccountPredicateBox,
+    AccountRecovered,
     AccountRoleChanged,
     Action,
     Algorithm,
+    App,
+    AppId,
+    ArchivedEvent,
+    ArchivedEventPredicateBox,
     Asset,
     AssetChanged,
     AssetDefinition,
@@ -125,6 +137,7 @@ types!(
     AssetId,
     AssetIdPredicateBox,
     AssetPredicateBox,
+    AssetMovement,
     AssetTransferBox,
     AssetType,
     AssetValue,
@@ -138,10 +151,13 @@ types!(
     BlockParameters,
     BlockPayload,
     BlockRejectionReason,
+    BlockReward,
     BlockSignature,
+    BlockStateDiff,
     BlockStatus,
     BlockSubscriptionRequest,
     Box<CompoundPredicate<AccountPredicateBox>>,
+    Box<CompoundPredicate<ArchivedEventPredicateBox>>,
     Box<CompoundPredicate<AssetDefinitionPredicateBox>>,
     Box<CompoundPredicate<AssetPredicateBox>>,
     Box<CompoundPredicate<BlockHeaderPredicateBox>>,
@@ -151,19 +167,25 @@ types!(
     Box<CompoundPredicate<RoleIdPredicateBox>>,
     Box<CompoundPredicate<RolePredicateBox>>,
     Box<CompoundPredicate<SignedBlockPredicateBox>>,
+    Box<CompoundPredicate<StoreEntryPredicateBox>>,
     Box<CompoundPredicate<TransactionQueryOutputPredicateBox>>,
+    Box<CompoundPredicate<TriggerDetailsPredicateBox>>,
     Box<CompoundPredicate<TriggerIdPredicateBox>>,
     Box<TransactionRejectionReason>,
     BTreeMap<CustomParameterId, CustomParameter>,
     BTreeMap<Name, JsonString>,
     BTreeSet<Permission>,
     BTreeSet<String>,
+    BurnAll,
     BurnBox,
     Burn<Numeric, Asset>,
     Burn<u32, Trigger>,
+    CancelTransaction,
     ChainId,
+    ClaimEscrow,
     CommittedTransaction,
     CompoundPredicate<AccountPredicateBox>,
+    CompoundPredicate<ArchivedEventPredicateBox>,
     CompoundPredicate<AssetDefinitionPredicateBox>,
     CompoundPredicate<AssetPredicateBox>,
     CompoundPredicate<BlockHeaderPredicateBox>,
@@ -173,7 +195,9 @@ types!(
     CompoundPredicate<RoleIdPredicateBox>,
     CompoundPredicate<RolePredicateBox>,
     CompoundPredicate<SignedBlockPredicateBox>,
+    CompoundPredicate<StoreEntryPredicateBox>,
     CompoundPredicate<TransactionQueryOutputPredicateBox>,
+    CompoundPredicate<TriggerDetailsPredicateBox>,
     CompoundPredicate<TriggerIdPredicateBox>,
     ConfigurationEvent,
     ConfigurationEventFilter,
@@ -185,7 +209,10 @@ types!(
     CustomParameterId,
     DataEvent,
     DataEventFilter,
+    DelegateToTrigger,
     Domain,
+    DomainDenial,
+    DomainDenialReason,
     DomainEvent,
     DomainEventFilter,
     DomainEventSet,
@@ -193,6 +220,12 @@ types!(
     DomainIdPredicateBox,
     DomainOwnerChanged,
     DomainPredicateBox,
+    Escrow,
+    EscrowError,
+    EscrowEvent,
+    EscrowEventFilter,
+    EscrowEventSet,
+    EscrowId,
     EventBox,
     EventFilterBox,
     EventMessage,
@@ -209,54 +242,76 @@ types!(
     ExecutorEventSet,
     ExecutorPath,
     ExecutorUpgrade,
+    FeeDistribution,
+    FeeParameter,
+    FeeParameters,
     FetchSize,
+    FinalizeRecovery,
     FindAccountMetadata,
     FindAccountsWithAsset,
     FindAccounts,
     FindActiveTriggerIds,
+    FindAllActiveTriggers,
     FindAssets,
     FindAssetsDefinitions,
     FindBlockHeaders,
     FindBlocks,
     FindDomains,
+    FindEvents,
     FindParameters,
+    FindPeerCertificate,
     FindPeers,
     FindRoleIds,
     FindRoles,
+    FindStoreEntries,
     FindTransactions,
     FindAssetDefinitionMetadata,
     FindAssetMetadata,
     FindAssetQuantityById,
     FindBlockHeaderByHash,
+    FindBlockRewards,
+    FindBlockStateDiff,
     FindDomainMetadata,
     FindError,
     FindExecutorDataModel,
+    FindIdempotencyRecord,
     FindPermissionsByAccountId,
     FindRolesByAccountId,
     FindTotalAssetQuantityByAssetDefinitionId,
     FindTransactionByHash,
     FindTransactionsByAccountId,
+    FindTransactionsByAppId,
     FindTriggerById,
     FindTriggerMetadata,
+    FindTriggerReceipts,
+    FindTriggersByDomain,
     ForwardCursor,
+    Freeze,
     GrantBox,
     Grant<Permission, Account>,
     Grant<Permission, Role>,
     Grant<RoleId, Account>,
+    GrantProxy,
     Hash,
     HashOf<MerkleTree<SignedTransaction>>,
     HashOf<SignedBlock>,
     HashOf<SignedTransaction>,
+    HeightRange,
     IdBox,
+    IdempotencyRecord,
+    InitiateRecovery,
     InstructionBox,
     InstructionEvaluationError,
     InstructionExecutionError,
     InstructionExecutionFail,
+    InstructionTrace,
     InstructionType,
     InvalidParameterError,
     IpfsPath,
     Ipv4Addr,
     Ipv6Addr,
+    KeyRange,
+    ProtocolUpgrade,
     QueryBox,
     QueryOutput,
     QueryOutputBatchBox,
@@ -264,21 +319,27 @@ types!(
     QueryWithFilter<FindAccountsWithAsset, AccountPredicateBox>,
     QueryWithFilter<FindAccounts, AccountPredicateBox>,
     QueryWithFilter<FindActiveTriggerIds, TriggerIdPredicateBox>,
+    QueryWithFilter<FindAllActiveTriggers, TriggerDetailsPredicateBox>,
     QueryWithFilter<FindAssets, AssetPredicateBox>,
     QueryWithFilter<FindAssetsDefinitions, AssetDefinitionPredicateBox>,
     QueryWithFilter<FindBlockHeaders, BlockHeaderPredicateBox>,
     QueryWithFilter<FindBlocks, SignedBlockPredicateBox>,
     QueryWithFilter<FindDomains, DomainPredicateBox>,
+    QueryWithFilter<FindEvents, ArchivedEventPredicateBox>,
     QueryWithFilter<FindPeers, PeerPredicateBox>,
     QueryWithFilter<FindRoleIds, RoleIdPredicateBox>,
     QueryWithFilter<FindRoles, RolePredicateBox>,
+    QueryWithFilter<FindStoreEntries, StoreEntryPredicateBox>,
     QueryWithFilter<FindTransactions, TransactionQueryOutputPredicateBox>,
     QueryWithFilter<FindPermissionsByAccountId, PermissionPredicateBox>,
     QueryWithFilter<FindRolesByAccountId, RoleIdPredicateBox>,
     QueryWithFilter<FindTransactionsByAccountId, TransactionQueryOutputPredicateBox>,
+    QueryWithFilter<FindTransactionsByAppId, TransactionQueryOutputPredicateBox>,
+    QueryWithFilter<FindTriggersByDomain, TriggerDetailsPredicateBox>,
     QueryWithParams,
     JsonString,
     Level,
+    LockEscrow,
     Log,
     MathError,
     MerkleTree<SignedTransaction>,
@@ -296,6 +357,9 @@ types!(
     Mint<u32, Trigger>,
     Mismatch<AssetType>,
     Name,
+    NameCharset,
+    NameParameter,
+    NameParameters,
     NewAccount,
     NewAssetDefinition,
     NewDomain,
@@ -304,27 +368,42 @@ types!(
     NonZeroU64,
     Numeric,
     NumericSpec,
+    Option<Account>,
     Option<AccountId>,
+    Option<AppId>,
+    Option<Asset>,
+    Option<AssetDefinition>,
     Option<AssetDefinitionId>,
     Option<AssetId>,
     Option<BlockStatus>,
+    Option<Domain>,
     Option<DomainId>,
+    Option<Escrow>,
+    Option<EscrowId>,
     Option<ForwardCursor>,
+    Option<Hash>,
     Option<HashOf<SignedBlock>>,
     Option<HashOf<SignedTransaction>>,
+    Option<InstructionExecutionError>,
     Option<IpfsPath>,
     Option<JsonString>,
     Option<Name>,
     Option<NonZeroU32>,
     Option<NonZeroU64>,
     Option<Option<NonZeroU64>>,
+    Option<Parameters>,
+    Option<PendingRecovery>,
+    Option<Peer>,
     Option<PeerId>,
+    Option<Role>,
     Option<RoleId>,
     Option<TimeInterval>,
     Option<TransactionRejectionReason>,
     Option<TransactionStatus>,
     Option<TriggerCompletedOutcomeType>,
     Option<TriggerId>,
+    Option<Vec<InstructionTrace>>,
+    Option<Vec<u8>>,
     Option<u32>,
     Option<u64>,
     Pagination,
@@ -332,6 +411,10 @@ types!(
     ParameterChanged,
     Parameters,
     Peer,
+    PeerCertificate,
+    PeerCertificatePayload,
+    PeerDegraded,
+    PendingRecovery,
     PeerEvent,
     PeerEventFilter,
     PeerEventSet,
@@ -341,6 +424,7 @@ types!(
     PermissionPredicateBox,
     PipelineEventBox,
     PipelineEventFilterBox,
+    ProxyToken,
     PublicKey,
     PublicKeyPredicateBox,
     QueryExecutionFail,
@@ -348,14 +432,22 @@ types!(
     QueryRequestWithAuthority,
     QueryResponse,
     QuerySignature,
+    QueueFull,
+    RecoveryError,
+    RecoveryPolicy,
+    RefundEscrow,
     Register<Account>,
     Register<Asset>,
     Register<AssetDefinition>,
+    RegisterApp,
     RegisterBox,
     Register<Domain>,
     Register<Peer>,
+    RegisterPeerCertificate,
     Register<Role>,
     Register<Trigger>,
+    RejectedSubmission,
+    RemoveKeyRange,
     RemoveKeyValue<Account>,
     RemoveKeyValue<Asset>,
     RemoveKeyValue<AssetDefinition>,
@@ -368,6 +460,8 @@ types!(
     Revoke<Permission, Account>,
     Revoke<Permission, Role>,
     Revoke<RoleId, Account>,
+    RevokeProxy,
+    RevokeTriggerDelegation,
     Role,
     RoleEvent,
     RoleEventFilter,
@@ -383,17 +477,27 @@ types!(
     SetKeyValue<Domain>,
     SetKeyValue<Trigger>,
     SetParameter,
+    SetRecoveryPolicy,
     Signature,
     SignatureOf<BlockPayload>,
+    SignatureOf<CancelTransaction>,
+    SignatureOf<EventSubscriptionRequest>,
+    SignatureOf<PeerCertificatePayload>,
     SignatureOf<QueryRequestWithAuthority>,
     SignatureOf<TransactionPayload>,
+    SignatureOf<TransactionPayloadV2>,
+    SignatureOf<WebhookRequest>,
     SignedBlock,
     SignedBlockPredicateBox,
     SignedBlockV1,
+    SignedCancelTransaction,
+    SignedEventSubscriptionRequest,
     SignedQuery,
     SignedQueryV1,
     SignedTransaction,
     SignedTransactionV1,
+    SignedTransactionV2,
+    SignedWebhookRequest,
     SingularQueryBox,
     SingularQueryOutputBox,
     SmartContractParameter,
@@ -403,10 +507,24 @@ types!(
     SocketAddrV4,
     SocketAddrV6,
     Sorting,
+    StateChange<Account>,
+    StateChange<Asset>,
+    StateChange<AssetDefinition>,
+    StateChange<Domain>,
+    StateChange<Escrow>,
+    StateChange<Parameters>,
+    StateChange<Peer>,
+    StateChange<Role>,
+    StateChangeBox,
+    StoreEntry,
+    StoreEntryPredicateBox,
     String,
     StringPredicateBox,
+    SubmissionStatus,
     SumeragiParameter,
     SumeragiParameters,
+    SystemAccount,
+    SystemRole,
     TimeEvent,
     TimeEventFilter,
     TimeInterval,
@@ -417,45 +535,68 @@ types!(
     TransactionParameter,
     TransactionParameters,
     TransactionPayload,
+    TransactionPayloadV2,
     TransactionQueryOutput,
     TransactionQueryOutputPredicateBox,
     TransactionRejectionReason,
     TransactionSignature,
+    TransactionSimulation,
     TransactionStatus,
     Transfer<Account, AssetDefinitionId, Account>,
     Transfer<Account, DomainId, Account>,
     Transfer<Asset, Metadata, Account>,
     Transfer<Asset, Numeric, Account>,
+    TransferAll,
     TransferBox,
+    TransferMultiple,
     Trigger,
     TriggerCompletedEvent,
     TriggerCompletedEventFilter,
     TriggerCompletedOutcome,
     TriggerCompletedOutcomeType,
+    TriggerDelegationChanged,
+    TriggerDetails,
+    TriggerDetailsPredicateBox,
     TriggerEvent,
     TriggerEventFilter,
     TriggerEventSet,
     TriggerId,
     TriggerIdPredicateBox,
     TriggerNumberOfExecutionsChanged,
+    TriggerReceipt,
     TypeError,
+    Unfreeze,
     Unregister<Account>,
     Unregister<Asset>,
     Unregister<AssetDefinition>,
+    UnregisterApp,
     UnregisterBox,
     Unregister<Domain>,
     Unregister<Peer>,
     Unregister<Role>,
     Unregister<Trigger>,
+    Unverified<SignatureOf<BlockPayload>>,
+    Unverified<SignatureOf<CancelTransaction>>,
+    Unverified<SignatureOf<EventSubscriptionRequest>>,
+    Unverified<SignatureOf<PeerCertificatePayload>>,
+    Unverified<SignatureOf<QueryRequestWithAuthority>>,
+    Unverified<SignatureOf<TransactionPayload>>,
+    Unverified<SignatureOf<TransactionPayloadV2>>,
+    Unverified<SignatureOf<WebhookRequest>>,
     Upgrade,
     ValidationFail,
     Vec<Account>,
+    Vec<AccountId>,
+    Vec<ArchivedEvent>,
     Vec<Asset>,
     Vec<AssetDefinition>,
+    Vec<AssetMovement>,
     Vec<BlockHeader>,
+    Vec<BlockReward>,
     Vec<BlockSignature>,
     Vec<CommittedTransaction>,
     Vec<CompoundPredicate<AccountPredicateBox>>,
+    Vec<CompoundPredicate<ArchivedEventPredicateBox>>,
     Vec<CompoundPredicate<AssetDefinitionPredicateBox>>,
     Vec<CompoundPredicate<AssetPredicateBox>>,
     Vec<CompoundPredicate<BlockHeaderPredicateBox>>,
@@ -465,11 +606,16 @@ types!(
     Vec<CompoundPredicate<RoleIdPredicateBox>>,
     Vec<CompoundPredicate<RolePredicateBox>>,
     Vec<CompoundPredicate<SignedBlockPredicateBox>>,
+    Vec<CompoundPredicate<StoreEntryPredicateBox>>,
     Vec<CompoundPredicate<TransactionQueryOutputPredicateBox>>,
+    Vec<CompoundPredicate<TriggerDetailsPredicateBox>>,
     Vec<CompoundPredicate<TriggerIdPredicateBox>>,
     Vec<Domain>,
+    Vec<EventBox>,
     Vec<EventFilterBox>,
     Vec<InstructionBox>,
+    Vec<InstructionTrace>,
+    Vec<InstructionType>,
     Vec<Parameter>,
     Vec<Peer>,
     Vec<PeerId>,
@@ -477,15 +623,28 @@ types!(
     Vec<Role>,
     Vec<RoleId>,
     Vec<SignedBlock>,
+    Vec<StateChangeBox>,
+    Vec<StoreEntry>,
+    Vec<SystemAccount>,
+    Vec<SystemRole>,
     Vec<TransactionQueryOutput>,
+    Vec<TriggerDetails>,
     Vec<TriggerId>,
+    Vec<TriggerReceipt>,
     Vec<u8>,
+    VetoRecovery,
+    WasmEngine,
     WasmExecutionFail,
     WasmSmartContract,
+    WebhookAction,
+    WebhookRegistration,
+    WebhookRequest,
+    WebhookStatus,
 
     [u16; 8],
     [u8; 32],
     [u8; 4],
+    bool,
     u16,
     u32,
     u64,
@@ -506,6 +665,8 @@ pub mod complete_data_model {
         asset::NewAssetDefinition,
         block::{
             error::BlockRejectionReason,
+            reward::BlockReward,
+            state_diff::{BlockStateDiff, StateChange, StateChangeBox},
             stream::{BlockMessage, BlockSubscriptionRequest},
             BlockHeader, BlockPayload, BlockSignature, SignedBlock, SignedBlockV1,
         },
@@ -515,15 +676,18 @@ pub mod complete_data_model {
         ipfs::IpfsPath,
         isi::{
             error::{
-                InstructionEvaluationError, InstructionExecutionError, InvalidParameterError,
-                MathError, MintabilityError, Mismatch, RepetitionError, TypeError,
+                EscrowError, InstructionEvaluationError, InstructionExecutionError,
+                InvalidParameterError, MathError, MintabilityError, Mismatch, RecoveryError,
+                RepetitionError, TypeError,
             },
             InstructionType,
         },
         parameter::{
-            BlockParameter, BlockParameters, CustomParameter, CustomParameterId, Parameter,
-            Parameters, SmartContractParameter, SmartContractParameters, SumeragiParameter,
-            SumeragiParameters, TransactionParameter, TransactionParameters,
+            BlockParameter, BlockParameters, CustomParameter, CustomParameterId, FeeDistribution,
+            FeeParameter, FeeParameters, NameCharset, NameParameter, NameParameters, Parameter,
+            Parameters, ProtocolUpgrade, SmartContractParameter, SmartContractParameters,
+            SumeragiParameter, SumeragiParameters, TransactionParameter, TransactionParameters,
+            WasmEngine,
         },
         prelude::*,
         query::{
@@ -535,12 +699,12 @@ pub mod complete_data_model {
             SingularQueryOutputBox,
         },
         transaction::{
-            error::TransactionLimitError, SignedTransactionV1, TransactionPayload,
-            TransactionSignature,
+            error::TransactionLimitError, SignedTransactionV1, SignedTransactionV2,
+            TransactionPayload, TransactionPayloadV2, TransactionSignature,
         },
         Level,
     };
-    pub use iroha_genesis::ExecutorPath;
+    pub use iroha_genesis::{ExecutorPath, SystemAccount, SystemRole};
     pub use iroha_primitives::{
         addr::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrHost, SocketAddrV4, SocketAddrV6},
         const_vec::ConstVec,
@@ -600,6 +764,7 @@ mod tests {
         insert_into_test_map!(
             iroha_executor_data_model::permission::account::CanRemoveKeyValueInAccount
         );
+        insert_into_test_map!(iroha_executor_data_model::permission::account::CanFreezeAnyAccount);
         insert_into_test_map!(
             iroha_executor_data_model::permission::asset_definition::CanUnregisterAssetDefinition
         );
@@ -631,6 +796,9 @@ mod tests {
             iroha_executor_data_model::permission::asset::CanRemoveKeyValueInUserAsset
         );
         insert_into_test_map!(iroha_executor_data_model::permission::parameter::CanSetParameters);
+        insert_into_test_map!(
+            iroha_executor_data_model::permission::event::CanSubscribeToAllEvents
+        );
         insert_into_test_map!(iroha_executor_data_model::permission::role::CanUnregisterAnyRole);
         insert_into_test_map!(
             iroha_executor_data_model::permission::trigger::CanRegisterUserTrigger
@@ -649,6 +817,7 @@ mod tests {
         insert_into_test_map!(
             iroha_executor_data_model::permission::trigger::CanRemoveKeyValueInTrigger
         );
+        insert_into_test_map!(iroha_executor_data_model::permission::trigger::CanInspectAnyTrigger);
         insert_into_test_map!(iroha_executor_data_model::permission::executor::CanUpgradeExecutor);
 
         map
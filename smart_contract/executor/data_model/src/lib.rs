@@ -6,6 +6,7 @@ extern crate self as iroha_executor_data_model;
 
 pub mod parameter;
 pub mod permission;
+pub mod policy;
 
 /// An error that might occur while converting a data model object into a native executor type.
 /// Such objects are [`iroha_data_model::permission::Permission`] and [`iroha_data_model::parameter::Parameter`].
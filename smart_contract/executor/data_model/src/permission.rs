@@ -73,6 +73,35 @@ pub mod domain {
             pub domain: DomainId,
         }
     }
+
+    /// Permissions granted to the owner of the domain.
+    ///
+    /// The default executor grants them to the account which registers the domain
+    /// and moves them to the new owner when the domain is transferred.
+    pub fn admin_permissions(domain: &DomainId) -> [iroha_data_model::permission::Permission; 5] {
+        [
+            CanUnregisterDomain {
+                domain: domain.clone(),
+            }
+            .into(),
+            CanSetKeyValueInDomain {
+                domain: domain.clone(),
+            }
+            .into(),
+            CanRemoveKeyValueInDomain {
+                domain: domain.clone(),
+            }
+            .into(),
+            CanRegisterAccountInDomain {
+                domain: domain.clone(),
+            }
+            .into(),
+            CanRegisterAssetDefinitionInDomain {
+                domain: domain.clone(),
+            }
+            .into(),
+        ]
+    }
 }
 
 pub mod asset_definition {
@@ -115,6 +144,10 @@ pub mod account {
             pub account: AccountId,
         }
     }
+    permission! {
+        #[derive(Copy)]
+        pub struct CanFreezeAnyAccount;
+    }
 }
 
 pub mod asset {
@@ -196,6 +229,15 @@ pub mod parameter {
     }
 }
 
+pub mod event {
+    use super::*;
+
+    permission! {
+        #[derive(Copy)]
+        pub struct CanSubscribeToAllEvents;
+    }
+}
+
 pub mod role {
     use super::*;
 
@@ -249,6 +291,11 @@ pub mod trigger {
             pub trigger: TriggerId,
         }
     }
+
+    permission! {
+        #[derive(Copy)]
+        pub struct CanInspectAnyTrigger;
+    }
 }
 
 pub mod executor {
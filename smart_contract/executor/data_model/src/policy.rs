@@ -0,0 +1,55 @@
+//! Policies which domains apply to the operations touching their entities
+
+use iroha_data_model::prelude::*;
+use iroha_schema::IntoSchema;
+use serde::{Deserialize, Serialize};
+
+/// Policy of a domain towards the accounts of other domains.
+///
+/// Kept in the domain metadata under [`DomainPolicy::key`],
+/// domains without a policy are [`DomainPolicy::Open`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, IntoSchema)]
+pub enum DomainPolicy {
+    /// Accounts of any domain may touch the entities of the domain
+    /// as long as they pass the permission checks
+    #[default]
+    Open,
+    /// Only the accounts of the domain and the domain owner may touch the entities of the domain
+    MembersOnly,
+}
+
+impl DomainPolicy {
+    /// Metadata key the policy of a domain is kept under
+    pub fn key() -> Name {
+        "domain_policy"
+            .parse()
+            .expect("INTERNAL BUG: Failed to parse policy key as `Name`")
+    }
+
+    /// Policy of `domain`
+    ///
+    /// # Errors
+    /// Fails if the policy in the domain metadata is malformed
+    pub fn of(domain: &Domain) -> Result<Self, serde_json::Error> {
+        domain
+            .metadata()
+            .get(&Self::key())
+            .map_or(Ok(Self::Open), JsonString::try_into_any)
+    }
+
+    /// Whether `authority` may touch the entities of `domain`
+    pub fn admits(self, domain: &Domain, authority: &AccountId) -> bool {
+        match self {
+            Self::Open => true,
+            Self::MembersOnly => {
+                authority.domain() == domain.id() || domain.owned_by() == authority
+            }
+        }
+    }
+}
+
+impl From<DomainPolicy> for JsonString {
+    fn from(policy: DomainPolicy) -> Self {
+        JsonString::new(policy)
+    }
+}
@@ -118,6 +118,7 @@ pub fn impl_derive_visit(emitter: &mut Emitter, input: &syn::DeriveInput) -> Tok
         "fn visit_instruction(operation: &InstructionBox)",
         "fn visit_register_peer(operation: &Register<Peer>)",
         "fn visit_unregister_peer(operation: &Unregister<Peer>)",
+        "fn visit_register_peer_certificate(operation: &RegisterPeerCertificate)",
         "fn visit_register_domain(operation: &Register<Domain>)",
         "fn visit_unregister_domain(operation: &Unregister<Domain>)",
         "fn visit_transfer_domain(operation: &Transfer<Account, DomainId, Account>)",
@@ -155,9 +156,30 @@ pub fn impl_derive_visit(emitter: &mut Emitter, input: &syn::DeriveInput) -> Tok
         "fn visit_mint_trigger_repetitions(operation: &Mint<u32, Trigger>)",
         "fn visit_burn_trigger_repetitions(operation: &Burn<u32, Trigger>)",
         "fn visit_execute_trigger(operation: &ExecuteTrigger)",
+        "fn visit_find_trigger_by_id(operation: &FindTriggerById)",
+        "fn visit_find_trigger_metadata(operation: &FindTriggerMetadata)",
         "fn visit_set_parameter(operation: &SetParameter)",
         "fn visit_upgrade(operation: &Upgrade)",
         "fn visit_log(operation: &Log)",
+        "fn visit_lock_escrow(operation: &LockEscrow)",
+        "fn visit_claim_escrow(operation: &ClaimEscrow)",
+        "fn visit_refund_escrow(operation: &RefundEscrow)",
+        "fn visit_transfer_all(operation: &TransferAll)",
+        "fn visit_burn_all(operation: &BurnAll)",
+        "fn visit_freeze(operation: &Freeze)",
+        "fn visit_unfreeze(operation: &Unfreeze)",
+        "fn visit_delegate_to_trigger(operation: &DelegateToTrigger)",
+        "fn visit_revoke_trigger_delegation(operation: &RevokeTriggerDelegation)",
+        "fn visit_remove_key_range(operation: &RemoveKeyRange)",
+        "fn visit_set_recovery_policy(operation: &SetRecoveryPolicy)",
+        "fn visit_initiate_recovery(operation: &InitiateRecovery)",
+        "fn visit_veto_recovery(operation: &VetoRecovery)",
+        "fn visit_finalize_recovery(operation: &FinalizeRecovery)",
+        "fn visit_transfer_multiple(operation: &TransferMultiple)",
+        "fn visit_register_app(operation: &RegisterApp)",
+        "fn visit_unregister_app(operation: &UnregisterApp)",
+        "fn visit_grant_proxy(operation: &GrantProxy)",
+        "fn visit_revoke_proxy(operation: &RevokeProxy)",
         "fn visit_custom(operation: &CustomInstruction)",
     ]
     .into_iter()
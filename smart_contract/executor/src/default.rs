@@ -4,13 +4,15 @@
 use alloc::format;
 
 pub use account::{
-    visit_register_account, visit_remove_account_key_value, visit_set_account_key_value,
-    visit_unregister_account,
+    visit_freeze, visit_register_account, visit_remove_account_key_value,
+    visit_set_account_key_value, visit_unfreeze, visit_unregister_account,
 };
+pub use app::{visit_register_app, visit_unregister_app};
 pub use asset::{
-    visit_burn_asset_numeric, visit_mint_asset_numeric, visit_register_asset,
-    visit_remove_asset_key_value, visit_set_asset_key_value, visit_transfer_asset_numeric,
-    visit_transfer_asset_store, visit_unregister_asset,
+    visit_burn_all, visit_burn_asset_numeric, visit_mint_asset_numeric, visit_register_asset,
+    visit_remove_asset_key_value, visit_remove_key_range, visit_set_asset_key_value,
+    visit_transfer_all, visit_transfer_asset_numeric, visit_transfer_asset_store,
+    visit_transfer_multiple, visit_unregister_asset,
 };
 pub use asset_definition::{
     visit_register_asset_definition, visit_remove_asset_definition_key_value,
@@ -22,20 +24,27 @@ pub use domain::{
     visit_register_domain, visit_remove_domain_key_value, visit_set_domain_key_value,
     visit_transfer_domain, visit_unregister_domain,
 };
+pub use escrow::{visit_claim_escrow, visit_lock_escrow, visit_refund_escrow};
 pub use executor::visit_upgrade;
 use iroha_smart_contract::data_model::{prelude::*, visit::Visit};
 pub use log::visit_log;
 pub use parameter::visit_set_parameter;
-pub use peer::{visit_register_peer, visit_unregister_peer};
+pub use peer::{visit_register_peer, visit_register_peer_certificate, visit_unregister_peer};
 pub use permission::{visit_grant_account_permission, visit_revoke_account_permission};
+pub use proxy::{visit_grant_proxy, visit_revoke_proxy};
+pub use recovery::{
+    visit_finalize_recovery, visit_initiate_recovery, visit_set_recovery_policy,
+    visit_veto_recovery,
+};
 pub use role::{
     visit_grant_account_role, visit_grant_role_permission, visit_register_role,
     visit_revoke_account_role, visit_revoke_role_permission, visit_unregister_role,
 };
 pub use trigger::{
-    visit_burn_trigger_repetitions, visit_execute_trigger, visit_mint_trigger_repetitions,
-    visit_register_trigger, visit_remove_trigger_key_value, visit_set_trigger_key_value,
-    visit_unregister_trigger,
+    visit_burn_trigger_repetitions, visit_delegate_to_trigger, visit_execute_trigger,
+    visit_find_trigger_by_id, visit_find_trigger_metadata, visit_mint_trigger_repetitions,
+    visit_register_trigger, visit_remove_trigger_key_value, visit_revoke_trigger_delegation,
+    visit_set_trigger_key_value, visit_unregister_trigger,
 };
 
 use crate::{
@@ -82,6 +91,12 @@ pub fn visit_instruction<V: Validate + Visit + ?Sized>(
     authority: &AccountId,
     isi: &InstructionBox,
 ) {
+    if !is_genesis(executor) {
+        if let Err(err) = domain_policy::validate(authority, isi) {
+            deny!(executor, err);
+        }
+    }
+
     match isi {
         InstructionBox::SetParameter(isi) => {
             executor.visit_set_parameter(authority, isi);
@@ -122,6 +137,66 @@ pub fn visit_instruction<V: Validate + Visit + ?Sized>(
         InstructionBox::Upgrade(isi) => {
             executor.visit_upgrade(authority, isi);
         }
+        InstructionBox::LockEscrow(isi) => {
+            executor.visit_lock_escrow(authority, isi);
+        }
+        InstructionBox::ClaimEscrow(isi) => {
+            executor.visit_claim_escrow(authority, isi);
+        }
+        InstructionBox::RefundEscrow(isi) => {
+            executor.visit_refund_escrow(authority, isi);
+        }
+        InstructionBox::TransferAll(isi) => {
+            executor.visit_transfer_all(authority, isi);
+        }
+        InstructionBox::BurnAll(isi) => {
+            executor.visit_burn_all(authority, isi);
+        }
+        InstructionBox::Freeze(isi) => {
+            executor.visit_freeze(authority, isi);
+        }
+        InstructionBox::Unfreeze(isi) => {
+            executor.visit_unfreeze(authority, isi);
+        }
+        InstructionBox::DelegateToTrigger(isi) => {
+            executor.visit_delegate_to_trigger(authority, isi);
+        }
+        InstructionBox::RevokeTriggerDelegation(isi) => {
+            executor.visit_revoke_trigger_delegation(authority, isi);
+        }
+        InstructionBox::RemoveKeyRange(isi) => {
+            executor.visit_remove_key_range(authority, isi);
+        }
+        InstructionBox::RegisterPeerCertificate(isi) => {
+            executor.visit_register_peer_certificate(authority, isi);
+        }
+        InstructionBox::SetRecoveryPolicy(isi) => {
+            executor.visit_set_recovery_policy(authority, isi);
+        }
+        InstructionBox::InitiateRecovery(isi) => {
+            executor.visit_initiate_recovery(authority, isi);
+        }
+        InstructionBox::VetoRecovery(isi) => {
+            executor.visit_veto_recovery(authority, isi);
+        }
+        InstructionBox::FinalizeRecovery(isi) => {
+            executor.visit_finalize_recovery(authority, isi);
+        }
+        InstructionBox::TransferMultiple(isi) => {
+            executor.visit_transfer_multiple(authority, isi);
+        }
+        InstructionBox::RegisterApp(isi) => {
+            executor.visit_register_app(authority, isi);
+        }
+        InstructionBox::UnregisterApp(isi) => {
+            executor.visit_unregister_app(authority, isi);
+        }
+        InstructionBox::GrantProxy(isi) => {
+            executor.visit_grant_proxy(authority, isi);
+        }
+        InstructionBox::RevokeProxy(isi) => {
+            executor.visit_revoke_proxy(authority, isi);
+        }
         InstructionBox::Custom(isi) => {
             executor.visit_custom(authority, isi);
         }
@@ -156,25 +231,41 @@ pub mod peer {
 
         deny!(executor, "Can't unregister peer");
     }
+
+    pub fn visit_register_peer_certificate<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &RegisterPeerCertificate,
+    ) {
+        if isi.certificate().operator() == authority {
+            execute!(executor, isi);
+        }
+
+        deny!(
+            executor,
+            "Only the operator can register the certificate of the peer"
+        );
+    }
 }
 
 pub mod domain {
     use iroha_executor_data_model::permission::domain::{
-        CanRemoveKeyValueInDomain, CanSetKeyValueInDomain, CanUnregisterDomain,
+        admin_permissions, CanRemoveKeyValueInDomain, CanSetKeyValueInDomain, CanUnregisterDomain,
     };
-    use iroha_smart_contract::data_model::domain::DomainId;
+    use iroha_smart_contract::{data_model::domain::DomainId, ExecuteOnHost};
 
     use super::*;
     use crate::permission::{
-        account::is_account_owner, accounts_permissions, domain::is_domain_owner, roles_permissions,
+        account::is_account_owner, account_permissions, accounts_permissions,
+        domain::is_domain_owner, roles_permissions,
     };
 
     pub fn visit_register_domain<V: Validate + Visit + ?Sized>(
         executor: &mut V,
-        _authority: &AccountId,
+        authority: &AccountId,
         isi: &Register<Domain>,
     ) {
-        execute!(executor, isi)
+        execute_owner_change(executor, isi, isi.object().id(), None, authority);
     }
 
     pub fn visit_unregister_domain<V: Validate + Visit + ?Sized>(
@@ -196,8 +287,6 @@ pub mod domain {
                 can_unregister_domain_token.is_owned_by(authority)
             }
         {
-            use iroha_smart_contract::ExecuteOnHost as _;
-
             for (owner_id, permission) in accounts_permissions() {
                 if is_token_domain_associated(&permission, domain_id) {
                     let isi = Revoke::account_permission(permission, owner_id.clone());
@@ -227,23 +316,68 @@ pub mod domain {
         let source_id = isi.source();
         let domain_id = isi.object();
 
-        if is_genesis(executor) {
-            execute!(executor, isi);
-        }
-        match is_account_owner(source_id, authority) {
-            Err(err) => deny!(executor, err),
-            Ok(true) => execute!(executor, isi),
-            Ok(false) => {}
-        }
-        match is_domain_owner(domain_id, authority) {
-            Err(err) => deny!(executor, err),
-            Ok(true) => execute!(executor, isi),
-            Ok(false) => {}
+        if is_genesis(executor)
+            || match is_account_owner(source_id, authority) {
+                Err(err) => deny!(executor, err),
+                Ok(is_account_owner) => is_account_owner,
+            }
+            || match is_domain_owner(domain_id, authority) {
+                Err(err) => deny!(executor, err),
+                Ok(is_domain_owner) => is_domain_owner,
+            }
+        {
+            execute_owner_change(executor, isi, domain_id, Some(source_id), isi.destination());
+            return;
         }
 
         deny!(executor, "Can't transfer domain of another account");
     }
 
+    /// Execute `isi` making `new_owner` the owner of the domain and move
+    /// the domain admin permissions from `previous_owner` to `new_owner`
+    fn execute_owner_change<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        isi: &impl ExecuteOnHost,
+        domain_id: &DomainId,
+        previous_owner: Option<&AccountId>,
+        new_owner: &AccountId,
+    ) {
+        if executor.verdict().is_err() {
+            return;
+        }
+        if let Err(err) = isi.execute() {
+            executor.deny(err);
+            return;
+        }
+        if previous_owner == Some(new_owner) {
+            return;
+        }
+
+        let permissions = admin_permissions(domain_id);
+        if let Some(previous_owner) = previous_owner {
+            let owned = account_permissions(previous_owner).collect::<alloc::vec::Vec<_>>();
+            for permission in permissions
+                .iter()
+                .filter(|&permission| owned.contains(permission))
+            {
+                let isi = Revoke::account_permission(permission.clone(), previous_owner.clone());
+                if let Err(_err) = isi.execute() {
+                    deny!(executor, "Can't revoke domain admin permission");
+                }
+            }
+        }
+        let owned = account_permissions(new_owner).collect::<alloc::vec::Vec<_>>();
+        for permission in permissions {
+            if owned.contains(&permission) {
+                continue;
+            }
+            let isi = Grant::account_permission(permission, new_owner.clone());
+            if let Err(_err) = isi.execute() {
+                deny!(executor, "Can't grant domain admin permission");
+            }
+        }
+    }
+
     pub fn visit_set_domain_key_value<V: Validate + Visit + ?Sized>(
         executor: &mut V,
         authority: &AccountId,
@@ -378,7 +512,10 @@ pub mod domain {
             | AnyPermission::CanSetKeyValueInTrigger(_)
             | AnyPermission::CanRemoveKeyValueInTrigger(_)
             | AnyPermission::CanUnregisterAnyPeer(_)
+            | AnyPermission::CanFreezeAnyAccount(_)
             | AnyPermission::CanSetParameters(_)
+            | AnyPermission::CanSubscribeToAllEvents(_)
+            | AnyPermission::CanInspectAnyTrigger(_)
             | AnyPermission::CanUnregisterAnyRole(_)
             | AnyPermission::CanUpgradeExecutor(_) => false,
         }
@@ -387,7 +524,10 @@ pub mod domain {
 
 pub mod account {
     use iroha_executor_data_model::permission::{
-        account::{CanRemoveKeyValueInAccount, CanSetKeyValueInAccount, CanUnregisterAccount},
+        account::{
+            CanFreezeAnyAccount, CanRemoveKeyValueInAccount, CanSetKeyValueInAccount,
+            CanUnregisterAccount,
+        },
         domain::CanRegisterAccountInDomain,
     };
 
@@ -518,6 +658,36 @@ pub mod account {
         );
     }
 
+    pub fn visit_freeze<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &Freeze,
+    ) {
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        if CanFreezeAnyAccount.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't freeze account");
+    }
+
+    pub fn visit_unfreeze<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &Unfreeze,
+    ) {
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        if CanFreezeAnyAccount.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't unfreeze account");
+    }
+
     fn is_token_account_associated(permission: &Permission, account_id: &AccountId) -> bool {
         let Ok(permission) = AnyPermission::try_from(permission) else {
             return false;
@@ -552,6 +722,7 @@ pub mod account {
             | AnyPermission::CanSetKeyValueInTrigger(_)
             | AnyPermission::CanRemoveKeyValueInTrigger(_)
             | AnyPermission::CanUnregisterAnyPeer(_)
+            | AnyPermission::CanFreezeAnyAccount(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanSetKeyValueInDomain(_)
             | AnyPermission::CanRemoveKeyValueInDomain(_)
@@ -566,6 +737,8 @@ pub mod account {
             | AnyPermission::CanMintAssetWithDefinition(_)
             | AnyPermission::CanTransferAssetWithDefinition(_)
             | AnyPermission::CanSetParameters(_)
+            | AnyPermission::CanSubscribeToAllEvents(_)
+            | AnyPermission::CanInspectAnyTrigger(_)
             | AnyPermission::CanUnregisterAnyRole(_)
             | AnyPermission::CanUpgradeExecutor(_) => false,
         }
@@ -804,12 +977,15 @@ pub mod asset_definition {
             | AnyPermission::CanSetKeyValueInTrigger(_)
             | AnyPermission::CanRemoveKeyValueInTrigger(_)
             | AnyPermission::CanUnregisterAnyPeer(_)
+            | AnyPermission::CanFreezeAnyAccount(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanSetKeyValueInDomain(_)
             | AnyPermission::CanRemoveKeyValueInDomain(_)
             | AnyPermission::CanRegisterAccountInDomain(_)
             | AnyPermission::CanRegisterAssetDefinitionInDomain(_)
             | AnyPermission::CanSetParameters(_)
+            | AnyPermission::CanSubscribeToAllEvents(_)
+            | AnyPermission::CanInspectAnyTrigger(_)
             | AnyPermission::CanUnregisterAnyRole(_)
             | AnyPermission::CanUpgradeExecutor(_) => false,
         }
@@ -817,6 +993,8 @@ pub mod asset_definition {
 }
 
 pub mod asset {
+    use alloc::collections::BTreeSet;
+
     use iroha_executor_data_model::permission::asset::{
         CanBurnAssetWithDefinition, CanBurnUserAsset, CanMintAssetWithDefinition, CanMintUserAsset,
         CanRegisterAssetWithDefinition, CanRemoveKeyValueInUserAsset, CanSetKeyValueInUserAsset,
@@ -829,7 +1007,10 @@ pub mod asset {
     use iroha_smart_contract_utils::Encode;
 
     use super::*;
-    use crate::permission::{asset::is_asset_owner, asset_definition::is_asset_definition_owner};
+    use crate::permission::{
+        account::is_account_owner, asset::is_asset_owner,
+        asset_definition::is_asset_definition_owner,
+    };
 
     pub fn visit_register_asset<V: Validate + Visit + ?Sized>(
         executor: &mut V,
@@ -1036,6 +1217,117 @@ pub mod asset {
         validate_transfer_asset(executor, authority, isi);
     }
 
+    pub fn visit_transfer_all<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &TransferAll,
+    ) {
+        let asset_id = AssetId::new(isi.asset_definition().clone(), isi.from().clone());
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        match is_asset_owner(&asset_id, authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        match is_asset_definition_owner(asset_id.definition(), authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        let can_transfer_assets_with_definition_token = CanTransferAssetWithDefinition {
+            asset_definition: asset_id.definition().clone(),
+        };
+        if can_transfer_assets_with_definition_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+        let can_transfer_user_asset_token = CanTransferUserAsset { asset: asset_id };
+        if can_transfer_user_asset_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't transfer assets of another account");
+    }
+
+    pub fn visit_transfer_multiple<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &TransferMultiple,
+    ) {
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        match is_account_owner(isi.from(), authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+
+        // Every asset is checked once no matter how many movements it has
+        let asset_definitions: BTreeSet<_> = isi
+            .movements()
+            .iter()
+            .map(AssetMovement::asset_definition)
+            .collect();
+        for asset_definition in asset_definitions {
+            match is_asset_definition_owner(asset_definition, authority) {
+                Err(err) => deny!(executor, err),
+                Ok(true) => continue,
+                Ok(false) => {}
+            }
+            let can_transfer_assets_with_definition_token = CanTransferAssetWithDefinition {
+                asset_definition: asset_definition.clone(),
+            };
+            if can_transfer_assets_with_definition_token.is_owned_by(authority) {
+                continue;
+            }
+            let can_transfer_user_asset_token = CanTransferUserAsset {
+                asset: AssetId::new(asset_definition.clone(), isi.from().clone()),
+            };
+            if can_transfer_user_asset_token.is_owned_by(authority) {
+                continue;
+            }
+
+            deny!(executor, "Can't transfer assets of another account");
+        }
+
+        execute!(executor, isi);
+    }
+
+    pub fn visit_burn_all<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &BurnAll,
+    ) {
+        let asset_id = AssetId::new(isi.asset_definition().clone(), isi.from().clone());
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        match is_asset_owner(&asset_id, authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        match is_asset_definition_owner(asset_id.definition(), authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        let can_burn_assets_with_definition_token = CanBurnAssetWithDefinition {
+            asset_definition: asset_id.definition().clone(),
+        };
+        if can_burn_assets_with_definition_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+        let can_burn_user_asset_token = CanBurnUserAsset { asset: asset_id };
+        if can_burn_user_asset_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't burn assets from another account");
+    }
+
     pub fn visit_set_asset_key_value<V: Validate + Visit + ?Sized>(
         executor: &mut V,
         authority: &AccountId,
@@ -1092,6 +1384,34 @@ pub mod asset {
             "Can't remove value from the asset metadata of another account"
         );
     }
+
+    pub fn visit_remove_key_range<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &RemoveKeyRange,
+    ) {
+        let asset_id = isi.asset();
+
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        match is_asset_owner(asset_id, authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        let can_remove_key_value_in_user_asset_token = CanRemoveKeyValueInUserAsset {
+            asset: asset_id.clone(),
+        };
+        if can_remove_key_value_in_user_asset_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(
+            executor,
+            "Can't remove values from the asset metadata of another account"
+        );
+    }
 }
 
 pub mod parameter {
@@ -1297,8 +1617,9 @@ pub mod role {
 
 pub mod trigger {
     use iroha_executor_data_model::permission::trigger::{
-        CanBurnUserTrigger, CanExecuteUserTrigger, CanMintUserTrigger, CanRegisterUserTrigger,
-        CanRemoveKeyValueInTrigger, CanSetKeyValueInTrigger, CanUnregisterUserTrigger,
+        CanBurnUserTrigger, CanExecuteUserTrigger, CanInspectAnyTrigger, CanMintUserTrigger,
+        CanRegisterUserTrigger, CanRemoveKeyValueInTrigger, CanSetKeyValueInTrigger,
+        CanUnregisterUserTrigger,
     };
     use iroha_smart_contract::data_model::trigger::Trigger;
 
@@ -1466,6 +1787,24 @@ pub mod trigger {
         deny!(executor, "Can't execute trigger owned by another account");
     }
 
+    pub fn visit_delegate_to_trigger<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &DelegateToTrigger,
+    ) {
+        // Account can only delegate its own authority, so its consent is implied
+        execute!(executor, isi)
+    }
+
+    pub fn visit_revoke_trigger_delegation<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &RevokeTriggerDelegation,
+    ) {
+        // Only the delegator can revoke the delegation, which is checked on execution
+        execute!(executor, isi)
+    }
+
     pub fn visit_set_trigger_key_value<V: Validate + Visit + ?Sized>(
         executor: &mut V,
         authority: &AccountId,
@@ -1522,6 +1861,41 @@ pub mod trigger {
         );
     }
 
+    pub fn visit_find_trigger_by_id<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        query: &FindTriggerById,
+    ) {
+        validate_inspect_trigger(executor, authority, &query.id);
+    }
+
+    pub fn visit_find_trigger_metadata<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        query: &FindTriggerMetadata,
+    ) {
+        validate_inspect_trigger(executor, authority, &query.id);
+    }
+
+    /// Executable and metadata of a trigger may only be inspected by its owner,
+    /// the rest of the details of any trigger is public, see [`FindAllActiveTriggers`]
+    fn validate_inspect_trigger<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        trigger_id: &TriggerId,
+    ) {
+        match is_trigger_owner(trigger_id, authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => return,
+            Ok(false) => {}
+        }
+        if CanInspectAnyTrigger.is_owned_by(authority) {
+            return;
+        }
+
+        deny!(executor, "Can't inspect trigger owned by another account");
+    }
+
     fn is_token_trigger_associated(permission: &Permission, trigger_id: &TriggerId) -> bool {
         let Ok(permission) = AnyPermission::try_from(permission) else {
             return false;
@@ -1537,6 +1911,7 @@ pub mod trigger {
             AnyPermission::CanRegisterUserTrigger(_)
             | AnyPermission::CanUnregisterUserTrigger(_)
             | AnyPermission::CanUnregisterAnyPeer(_)
+            | AnyPermission::CanFreezeAnyAccount(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanSetKeyValueInDomain(_)
             | AnyPermission::CanRemoveKeyValueInDomain(_)
@@ -1560,6 +1935,8 @@ pub mod trigger {
             | AnyPermission::CanRemoveKeyValueInUserAsset(_)
             | AnyPermission::CanMintUserAsset(_)
             | AnyPermission::CanSetParameters(_)
+            | AnyPermission::CanSubscribeToAllEvents(_)
+            | AnyPermission::CanInspectAnyTrigger(_)
             | AnyPermission::CanUnregisterAnyRole(_)
             | AnyPermission::CanUpgradeExecutor(_) => false,
         }
@@ -1659,6 +2036,160 @@ pub mod log {
     }
 }
 
+pub mod escrow {
+    use iroha_executor_data_model::permission::asset::{
+        CanTransferAssetWithDefinition, CanTransferUserAsset,
+    };
+
+    use super::*;
+    use crate::permission::{asset::is_asset_owner, asset_definition::is_asset_definition_owner};
+
+    pub fn visit_lock_escrow<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &LockEscrow,
+    ) {
+        // Locking funds is a transfer to the escrow, so it's validated the same way
+        let asset_id = isi.source();
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        match is_asset_owner(asset_id, authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        match is_asset_definition_owner(asset_id.definition(), authority) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        let can_transfer_assets_with_definition_token = CanTransferAssetWithDefinition {
+            asset_definition: asset_id.definition().clone(),
+        };
+        if can_transfer_assets_with_definition_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+        let can_transfer_user_asset_token = CanTransferUserAsset {
+            asset: asset_id.clone(),
+        };
+        if can_transfer_user_asset_token.is_owned_by(authority) {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't lock assets of another account in escrow");
+    }
+
+    pub fn visit_claim_escrow<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &ClaimEscrow,
+    ) {
+        // Only the recipient can claim the escrow, which is enforced on execution
+        execute!(executor, isi)
+    }
+
+    pub fn visit_refund_escrow<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &RefundEscrow,
+    ) {
+        // Only the owner can refund the escrow, which is enforced on execution
+        execute!(executor, isi)
+    }
+}
+
+pub mod recovery {
+    use super::*;
+
+    pub fn visit_set_recovery_policy<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        authority: &AccountId,
+        isi: &SetRecoveryPolicy,
+    ) {
+        if is_genesis(executor) {
+            execute!(executor, isi);
+        }
+        // Guardians can take over the account, so only the account itself can choose them
+        if isi.account() == authority {
+            execute!(executor, isi);
+        }
+
+        deny!(executor, "Can't set recovery policy of another account");
+    }
+
+    pub fn visit_initiate_recovery<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &InitiateRecovery,
+    ) {
+        // Only a guardian can initiate the recovery, which is enforced on execution
+        execute!(executor, isi)
+    }
+
+    pub fn visit_veto_recovery<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &VetoRecovery,
+    ) {
+        // Only the account being recovered can veto the recovery, which is enforced on execution
+        execute!(executor, isi)
+    }
+
+    pub fn visit_finalize_recovery<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &FinalizeRecovery,
+    ) {
+        // Only a guardian can finalize the recovery, which is enforced on execution
+        execute!(executor, isi)
+    }
+}
+
+pub mod app {
+    use super::*;
+
+    pub fn visit_register_app<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &RegisterApp,
+    ) {
+        // Any account can register an app, which it then owns
+        execute!(executor, isi)
+    }
+
+    pub fn visit_unregister_app<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &UnregisterApp,
+    ) {
+        // Only the owner can unregister the app, which is enforced on execution
+        execute!(executor, isi)
+    }
+}
+
+pub mod proxy {
+    use super::*;
+
+    pub fn visit_grant_proxy<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &GrantProxy,
+    ) {
+        // Any account can let a proxy act on its own behalf
+        execute!(executor, isi)
+    }
+
+    pub fn visit_revoke_proxy<V: Validate + Visit + ?Sized>(
+        executor: &mut V,
+        _authority: &AccountId,
+        isi: &RevokeProxy,
+    ) {
+        // Only tokens granted by the authority are revoked, which is enforced on execution
+        execute!(executor, isi)
+    }
+}
+
 pub mod custom {
     use super::*;
 
@@ -1674,6 +2205,208 @@ pub mod custom {
     }
 }
 
+pub mod domain_policy {
+    //! Cross-domain validation of instructions.
+    //!
+    //! Permission checks of an instruction are made against the domain of its object,
+    //! while the instruction may touch entities of other domains too, e.g. transfer an asset
+    //! to an account of another domain. Every domain touched by an instruction therefore
+    //! also applies its [`DomainPolicy`] to the authority.
+
+    use alloc::collections::BTreeSet;
+
+    use iroha_executor_data_model::policy::DomainPolicy;
+    use iroha_smart_contract::{
+        data_model::{
+            executor::Result,
+            query::builder::{QueryBuilderExt, SingleQueryError},
+        },
+        query,
+    };
+
+    use super::*;
+
+    /// Check `isi` against the policy of every domain it touches, see [`touched_domains`]
+    ///
+    /// # Errors
+    /// - If the policy of one of the domains doesn't admit `authority`
+    /// - If the policy of one of the domains is malformed
+    /// - If the domains can't be queried
+    pub fn validate(authority: &AccountId, isi: &InstructionBox) -> Result {
+        for domain_id in touched_domains(isi) {
+            if authority.domain() == &domain_id {
+                continue;
+            }
+            let domain = query(FindDomains)
+                .filter_with(|domain| domain.id.eq(domain_id.clone()))
+                .execute_single_opt()
+                .map_err(|err| match err {
+                    SingleQueryError::QueryError(err) => err,
+                    _ => unreachable!(),
+                })?;
+            // Missing domains are reported by the instruction execution
+            let Some(domain) = domain else {
+                continue;
+            };
+
+            let reason = match DomainPolicy::of(&domain) {
+                Ok(policy) if policy.admits(&domain, authority) => continue,
+                Ok(_) => DomainDenialReason::MembersOnly,
+                Err(_) => DomainDenialReason::MalformedPolicy,
+            };
+            return Err(DomainDenial {
+                domain: domain_id,
+                reason,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Domains of the accounts, assets, asset definitions and domains read or written by `isi`.
+    ///
+    /// Peers, roles and triggers don't belong to a domain and aren't accounted for.
+    pub fn touched_domains(isi: &InstructionBox) -> BTreeSet<DomainId> {
+        let mut domains = BTreeSet::new();
+        match isi {
+            InstructionBox::Register(RegisterBox::Domain(isi)) => {
+                domains.insert(isi.object().id().clone());
+            }
+            InstructionBox::Register(RegisterBox::Account(isi)) => {
+                domains.insert(isi.object().id().domain().clone());
+            }
+            InstructionBox::Register(RegisterBox::AssetDefinition(isi)) => {
+                domains.insert(isi.object().id().domain().clone());
+            }
+            InstructionBox::Register(RegisterBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.object().id());
+            }
+            InstructionBox::Unregister(UnregisterBox::Domain(isi)) => {
+                domains.insert(isi.object().clone());
+            }
+            InstructionBox::Unregister(UnregisterBox::Account(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::Unregister(UnregisterBox::AssetDefinition(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::Unregister(UnregisterBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.object());
+            }
+            InstructionBox::Mint(MintBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.destination());
+            }
+            InstructionBox::Burn(BurnBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.destination());
+            }
+            InstructionBox::Transfer(TransferBox::Domain(isi)) => {
+                domains.insert(isi.object().clone());
+                domains.insert(isi.source().domain().clone());
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Transfer(TransferBox::AssetDefinition(isi)) => {
+                domains.insert(isi.object().domain().clone());
+                domains.insert(isi.source().domain().clone());
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Transfer(TransferBox::Asset(AssetTransferBox::Numeric(isi))) => {
+                insert_asset(&mut domains, isi.source());
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Transfer(TransferBox::Asset(AssetTransferBox::Store(isi))) => {
+                insert_asset(&mut domains, isi.source());
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::SetKeyValue(SetKeyValueBox::Domain(isi)) => {
+                domains.insert(isi.object().clone());
+            }
+            InstructionBox::SetKeyValue(SetKeyValueBox::Account(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::SetKeyValue(SetKeyValueBox::AssetDefinition(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::SetKeyValue(SetKeyValueBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.object());
+            }
+            InstructionBox::RemoveKeyValue(RemoveKeyValueBox::Domain(isi)) => {
+                domains.insert(isi.object().clone());
+            }
+            InstructionBox::RemoveKeyValue(RemoveKeyValueBox::Account(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::RemoveKeyValue(RemoveKeyValueBox::AssetDefinition(isi)) => {
+                domains.insert(isi.object().domain().clone());
+            }
+            InstructionBox::RemoveKeyValue(RemoveKeyValueBox::Asset(isi)) => {
+                insert_asset(&mut domains, isi.object());
+            }
+            InstructionBox::Grant(GrantBox::Permission(isi)) => {
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Grant(GrantBox::Role(isi)) => {
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Revoke(RevokeBox::Permission(isi)) => {
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::Revoke(RevokeBox::Role(isi)) => {
+                domains.insert(isi.destination().domain().clone());
+            }
+            InstructionBox::LockEscrow(isi) => {
+                insert_asset(&mut domains, isi.source());
+                domains.insert(isi.recipient().domain().clone());
+            }
+            InstructionBox::TransferAll(isi) => {
+                domains.insert(isi.asset_definition().domain().clone());
+                domains.insert(isi.from().domain().clone());
+                domains.insert(isi.to().domain().clone());
+            }
+            InstructionBox::BurnAll(isi) => {
+                domains.insert(isi.asset_definition().domain().clone());
+                domains.insert(isi.from().domain().clone());
+            }
+            InstructionBox::TransferMultiple(isi) => {
+                domains.insert(isi.from().domain().clone());
+                for movement in isi.movements() {
+                    domains.insert(movement.asset_definition().domain().clone());
+                    domains.insert(movement.to().domain().clone());
+                }
+            }
+            InstructionBox::Freeze(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::Unfreeze(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::SetRecoveryPolicy(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::InitiateRecovery(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::VetoRecovery(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::FinalizeRecovery(isi) => {
+                domains.insert(isi.account().domain().clone());
+            }
+            InstructionBox::GrantProxy(isi) => {
+                domains.insert(isi.proxy().domain().clone());
+            }
+            InstructionBox::RemoveKeyRange(isi) => insert_asset(&mut domains, isi.asset()),
+            _ => {}
+        }
+        domains
+    }
+
+    fn insert_asset(domains: &mut BTreeSet<DomainId>, asset_id: &AssetId) {
+        domains.insert(asset_id.account().domain().clone());
+        domains.insert(asset_id.definition().domain().clone());
+    }
+}
+
 fn is_genesis<V: Validate + Visit + ?Sized>(executor: &V) -> bool {
     executor.block_height() == 0
 }
@@ -95,6 +95,7 @@ declare_permissions! {
     iroha_executor_data_model::permission::account::{CanUnregisterAccount},
     iroha_executor_data_model::permission::account::{CanSetKeyValueInAccount},
     iroha_executor_data_model::permission::account::{CanRemoveKeyValueInAccount},
+    iroha_executor_data_model::permission::account::{CanFreezeAnyAccount},
 
     iroha_executor_data_model::permission::asset_definition::{CanUnregisterAssetDefinition},
     iroha_executor_data_model::permission::asset_definition::{CanSetKeyValueInAssetDefinition},
@@ -113,6 +114,7 @@ declare_permissions! {
     iroha_executor_data_model::permission::asset::{CanRemoveKeyValueInUserAsset},
 
     iroha_executor_data_model::permission::parameter::{CanSetParameters},
+    iroha_executor_data_model::permission::event::{CanSubscribeToAllEvents},
     iroha_executor_data_model::permission::role::{CanUnregisterAnyRole},
 
     iroha_executor_data_model::permission::trigger::{CanRegisterUserTrigger},
@@ -122,6 +124,7 @@ declare_permissions! {
     iroha_executor_data_model::permission::trigger::{CanBurnUserTrigger},
     iroha_executor_data_model::permission::trigger::{CanSetKeyValueInTrigger},
     iroha_executor_data_model::permission::trigger::{CanRemoveKeyValueInTrigger},
+    iroha_executor_data_model::permission::trigger::{CanInspectAnyTrigger},
 
     iroha_executor_data_model::permission::executor::{CanUpgradeExecutor},
 }
@@ -237,6 +240,37 @@ mod parameter {
     }
 }
 
+mod event {
+    //! Module with pass conditions for event related tokens
+    use iroha_executor_data_model::permission::event::CanSubscribeToAllEvents;
+
+    use super::*;
+
+    impl ValidateGrantRevoke for CanSubscribeToAllEvents {
+        fn validate_grant(&self, authority: &AccountId, _block_height: u64) -> Result {
+            if CanSubscribeToAllEvents.is_owned_by(authority) {
+                return Ok(());
+            }
+
+            Err(ValidationFail::NotPermitted(
+                "Current authority doesn't have the permission to subscribe to all events, therefore it can't grant it to another account"
+                    .to_owned()
+            ))
+        }
+
+        fn validate_revoke(&self, authority: &AccountId, _block_height: u64) -> Result {
+            if CanSubscribeToAllEvents.is_owned_by(authority) {
+                return Ok(());
+            }
+
+            Err(ValidationFail::NotPermitted(
+                "Current authority doesn't have the permission to subscribe to all events, therefore it can't revoke it from another account"
+                    .to_owned()
+            ))
+        }
+    }
+}
+
 pub mod asset {
     //! Module with pass conditions for asset related tokens
 
@@ -521,7 +555,8 @@ pub mod account {
     //! Module with pass conditions for asset related tokens
 
     use iroha_executor_data_model::permission::account::{
-        CanRemoveKeyValueInAccount, CanSetKeyValueInAccount, CanUnregisterAccount,
+        CanFreezeAnyAccount, CanRemoveKeyValueInAccount, CanSetKeyValueInAccount,
+        CanUnregisterAccount,
     };
 
     use super::*;
@@ -589,6 +624,15 @@ pub mod account {
         }
     }
 
+    impl ValidateGrantRevoke for CanFreezeAnyAccount {
+        fn validate_grant(&self, authority: &AccountId, block_height: u64) -> Result {
+            OnlyGenesis::from(self).validate(authority, block_height)
+        }
+        fn validate_revoke(&self, authority: &AccountId, block_height: u64) -> Result {
+            OnlyGenesis::from(self).validate(authority, block_height)
+        }
+    }
+
     macro_rules! impl_froms {
         ($($name:ty),+ $(,)?) => {$(
             impl<'t> From<&'t $name> for Owner<'t> {
@@ -611,8 +655,9 @@ pub mod account {
 pub mod trigger {
     //! Module with pass conditions for trigger related tokens
     use iroha_executor_data_model::permission::trigger::{
-        CanBurnUserTrigger, CanExecuteUserTrigger, CanMintUserTrigger, CanRegisterUserTrigger,
-        CanRemoveKeyValueInTrigger, CanSetKeyValueInTrigger, CanUnregisterUserTrigger,
+        CanBurnUserTrigger, CanExecuteUserTrigger, CanInspectAnyTrigger, CanMintUserTrigger,
+        CanRegisterUserTrigger, CanRemoveKeyValueInTrigger, CanSetKeyValueInTrigger,
+        CanUnregisterUserTrigger,
     };
     use iroha_smart_contract::query_single;
 
@@ -720,6 +765,30 @@ pub mod trigger {
         }
     }
 
+    impl ValidateGrantRevoke for CanInspectAnyTrigger {
+        fn validate_grant(&self, authority: &AccountId, _block_height: u64) -> Result {
+            if CanInspectAnyTrigger.is_owned_by(authority) {
+                return Ok(());
+            }
+
+            Err(ValidationFail::NotPermitted(
+                "Current authority doesn't have the permission to inspect any trigger, therefore it can't grant it to another account"
+                    .to_owned()
+            ))
+        }
+
+        fn validate_revoke(&self, authority: &AccountId, _block_height: u64) -> Result {
+            if CanInspectAnyTrigger.is_owned_by(authority) {
+                return Ok(());
+            }
+
+            Err(ValidationFail::NotPermitted(
+                "Current authority doesn't have the permission to inspect any trigger, therefore it can't revoke it from another account"
+                    .to_owned()
+            ))
+        }
+    }
+
     macro_rules! impl_froms {
         ($($name:ty),+ $(,)?) => {$(
             impl<'t> From<&'t $name> for Owner<'t> {
@@ -890,14 +959,20 @@ pub(crate) fn accounts_permissions() -> impl Iterator<Item = (AccountId, Permiss
         .dbg_expect("INTERNAL BUG: `FindAllAccounts` must never fail")
         .map(|account| account.dbg_expect("Failed to get account from cursor"))
         .flat_map(|account| {
-            query(FindPermissionsByAccountId::new(account.id().clone()))
-                .execute()
-                .dbg_expect("INTERNAL BUG: `FindPermissionsByAccountId` must never fail")
-                .map(|token| token.dbg_expect("Failed to get permission from cursor"))
-                .map(move |token| (account.id().clone(), token))
+            account_permissions(account.id()).map(move |token| (account.id().clone(), token))
         })
 }
 
+/// Iterator over permission tokens of the account
+pub(crate) fn account_permissions(
+    account_id: &AccountId,
+) -> impl Iterator<Item = PermissionObject> {
+    query(FindPermissionsByAccountId::new(account_id.clone()))
+        .execute()
+        .dbg_expect("INTERNAL BUG: `FindPermissionsByAccountId` must never fail")
+        .map(|token| token.dbg_expect("Failed to get permission from cursor"))
+}
+
 /// Iterator over all roles and theirs permission tokens
 pub(crate) fn roles_permissions() -> impl Iterator<Item = (RoleId, PermissionObject)> {
     query(FindRoles)
@@ -0,0 +1,148 @@
+//! Signed reports of the peer state for public dashboards.
+//!
+//! Metrics reported to telemetry are self-reported, so a dashboard has no way to tell whether they
+//! came from the peer they claim to. Peers therefore periodically publish a [`SignedAttestation`]
+//! of their state as the `attestation` field of [`PEER_ATTESTATION`](crate::msg::PEER_ATTESTATION)
+//! messages, signed with the peer key, which the dashboard can check with
+//! [`SignedAttestation::verify_registered`] against the keys of the registered peers.
+
+use iroha_crypto::{Hash, KeyPair, PublicKey, SignatureOf, Unverified, Verified};
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// State of a peer at some point in time
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct Attestation {
+    /// Key of the attesting peer
+    pub peer: PublicKey,
+    /// Height of the latest block whose state is attested
+    pub height: u64,
+    /// Digest of the world state at `height`
+    pub state_digest: Hash,
+    /// Version of the peer software
+    pub version: String,
+    /// Time since the peer was started, in milliseconds
+    pub uptime_ms: u64,
+    /// Unix time the attestation was made at, in milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// [`Attestation`] signed by the attesting peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    /// Signed attestation
+    pub attestation: Attestation,
+    /// Signature of [`Self::attestation`] made with the key of [`Attestation::peer`]
+    pub signature: Unverified<SignatureOf<Attestation>>,
+}
+
+/// Error of verifying a [`SignedAttestation`]
+#[derive(Debug, displaydoc::Display, thiserror::Error)]
+pub enum AttestationError {
+    /// Attestation is made by a peer which isn't registered
+    UnregisteredPeer,
+    /// Attestation isn't signed by the attesting peer
+    InvalidSignature(#[source] iroha_crypto::Error),
+}
+
+/// Version of the peer software as reported to telemetry
+pub fn version() -> String {
+    format!(
+        "{}-{}-{}",
+        env!("CARGO_PKG_VERSION"),
+        env!("VERGEN_GIT_SHA"),
+        env!("VERGEN_CARGO_TARGET_TRIPLE")
+    )
+}
+
+impl Attestation {
+    /// Sign the attestation with `key_pair`, which must be the key pair of [`Self::peer`]
+    /// for the signature to be valid
+    pub fn sign(self, key_pair: &KeyPair) -> SignedAttestation {
+        SignedAttestation {
+            signature: Verified::sign(key_pair.private_key(), &self).into(),
+            attestation: self,
+        }
+    }
+}
+
+impl SignedAttestation {
+    /// Check that the attestation is signed by the attesting peer
+    ///
+    /// # Errors
+    /// If the signature doesn't match the attestation or the key of the peer
+    pub fn verify(&self) -> Result<(), AttestationError> {
+        self.signature
+            .verify(&self.attestation.peer, &self.attestation)
+            .map(drop)
+            .map_err(AttestationError::InvalidSignature)
+    }
+
+    /// Check that the attestation is signed by one of the `registered` peers
+    ///
+    /// # Errors
+    /// - If the attesting peer isn't one of the `registered` peers
+    /// - If the signature doesn't match the attestation or the key of the peer
+    pub fn verify_registered<'peer>(
+        &self,
+        registered: impl IntoIterator<Item = &'peer PublicKey>,
+    ) -> Result<(), AttestationError> {
+        if !registered
+            .into_iter()
+            .any(|peer| *peer == self.attestation.peer)
+        {
+            return Err(AttestationError::UnregisteredPeer);
+        }
+        self.verify()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation(peer: &KeyPair) -> Attestation {
+        Attestation {
+            peer: peer.public_key().clone(),
+            height: 10,
+            state_digest: Hash::new(b"state"),
+            version: version(),
+            uptime_ms: 1_000,
+            timestamp_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn signed_attestation_is_verified() {
+        let peer = KeyPair::random();
+        let signed = attestation(&peer).sign(&peer);
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let signed: SignedAttestation = serde_json::from_str(&json).unwrap();
+        signed.verify_registered([peer.public_key()]).unwrap();
+    }
+
+    #[test]
+    fn tampered_attestation_is_rejected() {
+        let peer = KeyPair::random();
+        let mut signed = attestation(&peer).sign(&peer);
+        signed.attestation.height += 1;
+
+        assert!(matches!(
+            signed.verify(),
+            Err(AttestationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn attestation_of_unregistered_peer_is_rejected() {
+        let peer = KeyPair::random();
+        let impostor = KeyPair::random();
+        let signed = attestation(&impostor).sign(&impostor);
+
+        assert!(matches!(
+            signed.verify_registered([peer.public_key()]),
+            Err(AttestationError::UnregisteredPeer)
+        ));
+    }
+}
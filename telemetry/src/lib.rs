@@ -1,5 +1,6 @@
 //! Crate with Iroha telemetry processing
 
+pub mod attestation;
 #[cfg(feature = "dev-telemetry")]
 pub mod dev;
 pub mod futures;
@@ -17,4 +18,10 @@ pub mod msg {
 
     /// The message that is sent to the telemetry when the node is initialized
     pub const SYSTEM_CONNECTED: &str = "system.connected";
+    /// The message that is sent to the telemetry when a block is committed
+    pub const BLOCK_IMPORT: &str = "block.import";
+    /// The message that is sent to the telemetry when the world state digest differs from the quorum's
+    pub const STATE_DIVERGENCE: &str = "state.divergence";
+    /// The message that is sent to the telemetry with a signed attestation of the peer state
+    pub const PEER_ATTESTATION: &str = "peer.attestation";
 }
@@ -1,11 +1,16 @@
 //! [`Metrics`] and [`Status`]-related logic and functions.
 
-use std::{ops::Deref, time::Duration};
+use std::{
+    ops::Deref,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use parity_scale_codec::{Compact, Decode, Encode};
 use prometheus::{
     core::{AtomicU64, GenericGauge, GenericGaugeVec},
-    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts,
+    Registry,
 };
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +18,10 @@ use serde::{Deserialize, Serialize};
 pub type DroppedMessagesCounter = IntCounter;
 /// Type for reporting view change index of current round
 pub type ViewChangesGauge = GenericGauge<AtomicU64>;
+/// Type for reporting time between consecutive block commits
+pub type CommitTimeHistogram = Histogram;
+/// Type for reporting properties of the latest committed block
+pub type BlockGauge = GenericGauge<AtomicU64>;
 
 /// Thin wrapper around duration that `impl`s [`Default`]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -45,6 +54,17 @@ impl Decode for Uptime {
     }
 }
 
+/// Digest of the world state at some block height
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Encode, Decode)]
+pub struct StateDigest {
+    /// Height of the block after which the digest was computed
+    #[codec(compact)]
+    pub height: u64,
+    /// Hash of the canonical encoding of the world state
+    #[serde(with = "hex::serde")]
+    pub hash: [u8; 32],
+}
+
 /// Response body for GET status request
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, Encode, Decode)]
 pub struct Status {
@@ -68,6 +88,13 @@ pub struct Status {
     /// Number of the transactions in the queue
     #[codec(compact)]
     pub queue_size: u64,
+    /// Maximum number of the transactions in the queue
+    #[codec(compact)]
+    pub queue_capacity: u64,
+    /// The latest computed digest of the world state
+    pub state_digest: Option<StateDigest>,
+    /// Whether the peer is partitioned from a quorum of peers and rejects transactions
+    pub degraded: bool,
 }
 
 impl<T: Deref<Target = Metrics>> From<&T> for Status {
@@ -85,6 +112,12 @@ impl<T: Deref<Target = Metrics>> From<&T> for Status {
                 .try_into()
                 .expect("INTERNAL BUG: Number of view changes exceeds u32::MAX"),
             queue_size: val.queue_size.get(),
+            queue_capacity: val.queue_capacity.get(),
+            state_digest: *val
+                .state_digest
+                .read()
+                .expect("INTERNAL BUG: state digest lock is poisoned"),
+            degraded: val.degraded.get() != 0,
         }
     }
 }
@@ -114,8 +147,62 @@ pub struct Metrics {
     pub view_changes: ViewChangesGauge,
     /// Number of transactions in the queue
     pub queue_size: GenericGauge<AtomicU64>,
+    /// Maximum number of transactions in the queue
+    pub queue_capacity: GenericGauge<AtomicU64>,
+    /// Number of transactions denied admission to the queue by the authority filter
+    pub queue_denied_txs: GenericGauge<AtomicU64>,
+    /// Number of transactions admitted and rejected by each queue admission check
+    pub queue_admission: IntGaugeVec,
     /// Number of sumeragi dropped messages
     pub dropped_messages: DroppedMessagesCounter,
+    /// Time between consecutive block commits in milliseconds
+    pub commit_time_ms: CommitTimeHistogram,
+    /// Number of signatures of the latest committed block
+    pub block_signatures: BlockGauge,
+    /// Number of transactions in the latest committed block
+    pub block_transactions: BlockGauge,
+    /// Size of the latest committed block in bytes
+    pub block_size_bytes: BlockGauge,
+    /// Number of instructions of every type executed in the latest profiled block
+    pub block_isi_count: IntGaugeVec,
+    /// Time spent executing instructions of every type in the latest profiled block
+    pub block_isi_time_us: IntGaugeVec,
+    /// Time the latest profiled block waited for the state lock to be committed
+    pub block_state_lock_wait_us: GenericGauge<AtomicU64>,
+    /// Height of the latest computed world state digest
+    pub state_digest_height: GenericGauge<AtomicU64>,
+    /// Number of times the world state digest differed from the one of the quorum
+    pub state_divergences: IntCounter,
+    /// Number of queries answered from the query cache
+    pub query_cache_hits: IntCounter,
+    /// Number of cacheable queries which had to be executed
+    pub query_cache_misses: IntCounter,
+    /// Number of query results in the query cache
+    pub query_cache_size: GenericGauge<AtomicU64>,
+    /// Number of smart contracts found compiled in the wasm module cache
+    pub wasm_module_cache_hits: GenericGauge<AtomicU64>,
+    /// Number of smart contracts which had to be compiled
+    pub wasm_module_cache_misses: GenericGauge<AtomicU64>,
+    /// Number of compiled smart contracts in the wasm module cache
+    pub wasm_module_cache_size: GenericGauge<AtomicU64>,
+    /// Compilation time saved by the wasm module cache
+    pub wasm_compile_time_saved_us: GenericGauge<AtomicU64>,
+    /// Number of transactions found rejected in the validation cache
+    pub tx_validation_cache_hits: GenericGauge<AtomicU64>,
+    /// Number of transactions which had to be validated
+    pub tx_validation_cache_misses: GenericGauge<AtomicU64>,
+    /// Reputation scores of the peers which have misbehaved recently
+    pub peer_reputation: IntGaugeVec,
+    /// Clock skews of the connected peers in milliseconds
+    pub peer_clock_skew_ms: IntGaugeVec,
+    /// Number of entries of every collection of the world state
+    pub state_entries: IntGaugeVec,
+    /// Estimated memory used by every collection of the world state
+    pub state_memory_bytes: IntGaugeVec,
+    /// Whether the peer is partitioned from a quorum of peers and runs in the degraded mode
+    pub degraded: GenericGauge<AtomicU64>,
+    /// The latest computed world state digest, only reported in [`Status`]
+    pub state_digest: Arc<RwLock<Option<StateDigest>>>,
     /// Internal use only. Needed for generating the response.
     registry: Registry,
 }
@@ -164,8 +251,160 @@ impl Default for Metrics {
         .expect("Infallible");
         let queue_size = GenericGauge::new("queue_size", "Number of the transactions in the queue")
             .expect("Infallible");
+        let queue_capacity = GenericGauge::new(
+            "queue_capacity",
+            "Maximum number of the transactions in the queue",
+        )
+        .expect("Infallible");
+        let queue_denied_txs = GenericGauge::new(
+            "queue_denied_txs",
+            "Number of the transactions denied admission to the queue by the authority filter",
+        )
+        .expect("Infallible");
+        let queue_admission = IntGaugeVec::new(
+            Opts::new(
+                "queue_admission",
+                "Number of the transactions admitted and rejected by each queue admission check",
+            ),
+            &["check", "outcome"],
+        )
+        .expect("Infallible");
         let dropped_messages =
             IntCounter::new("dropped_messages", "Sumeragi dropped messages").expect("Infallible");
+        let commit_time_ms = Histogram::with_opts(
+            HistogramOpts::new(
+                "commit_time_ms",
+                "Time from creating or receiving a block for voting till its commit in milliseconds",
+            )
+            .buckets(prometheus::exponential_buckets(50.0, 2.0, 10).expect("Infallible")),
+        )
+        .expect("Infallible");
+        let block_signatures = GenericGauge::new(
+            "block_signatures",
+            "Number of signatures of the latest committed block",
+        )
+        .expect("Infallible");
+        let block_transactions = GenericGauge::new(
+            "block_transactions",
+            "Number of transactions in the latest committed block",
+        )
+        .expect("Infallible");
+        let block_size_bytes = GenericGauge::new(
+            "block_size_bytes",
+            "Size of the latest committed block in bytes",
+        )
+        .expect("Infallible");
+        let block_isi_count = IntGaugeVec::new(
+            Opts::new(
+                "block_isi_count",
+                "Number of instructions of every type executed in the latest profiled block",
+            ),
+            &["type"],
+        )
+        .expect("Infallible");
+        let block_isi_time_us = IntGaugeVec::new(
+            Opts::new(
+                "block_isi_time_us",
+                "Time in microseconds spent executing instructions of every type in the latest profiled block",
+            ),
+            &["type"],
+        )
+        .expect("Infallible");
+        let block_state_lock_wait_us = GenericGauge::new(
+            "block_state_lock_wait_us",
+            "Time in microseconds the latest profiled block waited for the state lock to be committed",
+        )
+        .expect("Infallible");
+        let state_digest_height = GenericGauge::new(
+            "state_digest_height",
+            "Height of the latest computed world state digest",
+        )
+        .expect("Infallible");
+        let state_divergences = IntCounter::new(
+            "state_divergences",
+            "Number of times the world state digest differed from the one of the quorum",
+        )
+        .expect("Infallible");
+        let query_cache_hits =
+            IntCounter::new("query_cache_hits", "Queries answered from the query cache")
+                .expect("Infallible");
+        let query_cache_misses = IntCounter::new(
+            "query_cache_misses",
+            "Cacheable queries which had to be executed",
+        )
+        .expect("Infallible");
+        let query_cache_size = GenericGauge::new(
+            "query_cache_size",
+            "Number of query results in the query cache",
+        )
+        .expect("Infallible");
+        let wasm_module_cache_hits = GenericGauge::new(
+            "wasm_module_cache_hits",
+            "Smart contracts found compiled in the wasm module cache",
+        )
+        .expect("Infallible");
+        let wasm_module_cache_misses = GenericGauge::new(
+            "wasm_module_cache_misses",
+            "Smart contracts which had to be compiled",
+        )
+        .expect("Infallible");
+        let wasm_module_cache_size = GenericGauge::new(
+            "wasm_module_cache_size",
+            "Number of compiled smart contracts in the wasm module cache",
+        )
+        .expect("Infallible");
+        let wasm_compile_time_saved_us = GenericGauge::new(
+            "wasm_compile_time_saved_us",
+            "Time in microseconds saved by not compiling the cached smart contracts",
+        )
+        .expect("Infallible");
+        let tx_validation_cache_hits = GenericGauge::new(
+            "tx_validation_cache_hits",
+            "Transactions not executed again since they were rejected on the same state",
+        )
+        .expect("Infallible");
+        let tx_validation_cache_misses = GenericGauge::new(
+            "tx_validation_cache_misses",
+            "Transactions which had to be validated",
+        )
+        .expect("Infallible");
+        let peer_reputation = IntGaugeVec::new(
+            Opts::new(
+                "peer_reputation",
+                "Reputation scores of the peers which have misbehaved recently",
+            ),
+            &["peer"],
+        )
+        .expect("Infallible");
+        let peer_clock_skew_ms = IntGaugeVec::new(
+            Opts::new(
+                "peer_clock_skew_ms",
+                "Clock skews of the connected peers in milliseconds, positive if the clock of the peer is ahead",
+            ),
+            &["peer"],
+        )
+        .expect("Infallible");
+        let state_entries = IntGaugeVec::new(
+            Opts::new(
+                "state_entries",
+                "Number of entries of every collection of the world state",
+            ),
+            &["collection"],
+        )
+        .expect("Infallible");
+        let state_memory_bytes = IntGaugeVec::new(
+            Opts::new(
+                "state_memory_bytes",
+                "Estimated memory in bytes used by every collection of the world state",
+            ),
+            &["collection"],
+        )
+        .expect("Infallible");
+        let degraded = GenericGauge::new(
+            "degraded",
+            "Whether the peer is partitioned from a quorum of peers and rejects transactions",
+        )
+        .expect("Infallible");
         let registry = Registry::new();
 
         macro_rules! register {
@@ -190,7 +429,33 @@ impl Default for Metrics {
             isi_times,
             view_changes,
             queue_size,
-            dropped_messages
+            queue_capacity,
+            queue_denied_txs,
+            queue_admission,
+            dropped_messages,
+            commit_time_ms,
+            block_signatures,
+            block_transactions,
+            block_size_bytes,
+            block_isi_count,
+            block_isi_time_us,
+            block_state_lock_wait_us,
+            state_digest_height,
+            state_divergences,
+            query_cache_hits,
+            query_cache_misses,
+            query_cache_size,
+            wasm_module_cache_hits,
+            wasm_module_cache_misses,
+            wasm_module_cache_size,
+            wasm_compile_time_saved_us,
+            tx_validation_cache_hits,
+            tx_validation_cache_misses,
+            peer_reputation,
+            peer_clock_skew_ms,
+            state_entries,
+            state_memory_bytes,
+            degraded
         );
 
         Self {
@@ -205,7 +470,34 @@ impl Default for Metrics {
             isi_times,
             view_changes,
             queue_size,
+            queue_capacity,
+            queue_denied_txs,
+            queue_admission,
             dropped_messages,
+            commit_time_ms,
+            block_signatures,
+            block_transactions,
+            block_size_bytes,
+            block_isi_count,
+            block_isi_time_us,
+            block_state_lock_wait_us,
+            state_digest_height,
+            state_divergences,
+            query_cache_hits,
+            query_cache_misses,
+            query_cache_size,
+            wasm_module_cache_hits,
+            wasm_module_cache_misses,
+            wasm_module_cache_size,
+            wasm_compile_time_saved_us,
+            tx_validation_cache_hits,
+            tx_validation_cache_misses,
+            peer_reputation,
+            peer_clock_skew_ms,
+            state_entries,
+            state_memory_bytes,
+            degraded,
+            state_digest: Arc::default(),
             registry,
         }
     }
@@ -254,6 +546,12 @@ mod test {
             uptime: Uptime(Duration::new(5, 937_000_000)),
             view_changes: 2,
             queue_size: 18,
+            queue_capacity: 32,
+            state_digest: Some(StateDigest {
+                height: 5,
+                hash: [0x11; 32],
+            }),
+            degraded: false,
         }
     }
 
@@ -275,7 +573,13 @@ mod test {
                 "nanos": 937000000
               },
               "view_changes": 2,
-              "queue_size": 18
+              "queue_size": 18,
+              "queue_capacity": 32,
+              "state_digest": {
+                "height": 5,
+                "hash": "1111111111111111111111111111111111111111111111111111111111111111"
+              },
+              "degraded": false
             }"#]];
         expected.assert_eq(&actual);
     }
@@ -288,7 +592,9 @@ mod test {
         let actual = hex::encode_upper(bytes);
         // CAUTION: if this is outdated, make sure to update the documentation:
         // https://hyperledger.github.io/iroha-2-docs/api/torii-endpoints#status
-        let expected = expect_test::expect!["10147C0C14407CD9370848"];
+        let expected = expect_test::expect![
+            "10147C0C14407CD93708488001141111111111111111111111111111111111111111111111111111111111111100"
+        ];
         expected.assert_eq(&actual);
     }
 }
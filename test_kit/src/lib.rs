@@ -0,0 +1,527 @@
+//! In-process network simulator for tests.
+//!
+//! [`SimNetwork`] hands out [`NetworkHandle`]s which aren't backed by sockets and carries the
+//! messages sent through them itself, on a [`VirtualClock`] instead of the wall clock. Handles of
+//! [`iroha_core::NetworkMessage`](https://docs.rs/iroha_core) payload are regular `IrohaNetwork`s,
+//! so several peer cores can be wired together without spawning real peers.
+//!
+//! Message latencies are drawn from an RNG seeded with [`SimNetwork::seed`], and so are the keys of
+//! [generated](SimNetwork::generate_peer) peers. Given the same sequence of messages, a run with the
+//! same seed delivers them in the same order at the same virtual time, so a failure observed with
+//! some seed can be reproduced by setting [`SEED_ENV`] to it, see [`SimNetwork::from_env`].
+//!
+//! Faults (dropped, delayed and corrupted messages, partitions) are injected into the simulated
+//! traffic through [`SimNetwork::faults`]. Peers backed by sockets are controlled through
+//! [`faults::global`] instead, given they're built with the `fault-injection` feature.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    ops::RangeInclusive,
+    time::{Duration, SystemTime},
+};
+
+use iroha_crypto::{Algorithm, KeyPair};
+use iroha_data_model::peer::PeerId;
+use iroha_logger::prelude::*;
+pub use iroha_p2p::faults;
+use iroha_p2p::{
+    boilerplate::Pload,
+    faults::{Fate, Faults},
+    memory::{Endpoint, Outgoing},
+    peer::message::PeerMessage,
+    Broadcast, NetworkHandle, OnlinePeers, Post, UpdateTopology,
+};
+use iroha_primitives::{
+    addr::SocketAddr,
+    time::{MockTimeHandle, TimeSource},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Environment variable to take the seed of [`SimNetwork::from_env`] from
+pub const SEED_ENV: &str = "IROHA_TEST_SEED";
+/// Default range of the latencies of the messages
+pub const DEFAULT_LATENCY: RangeInclusive<Duration> =
+    Duration::from_millis(1)..=Duration::from_millis(10);
+
+/// Clock which only moves when it's advanced
+#[derive(Clone)]
+pub struct VirtualClock {
+    handle: MockTimeHandle,
+}
+
+impl core::fmt::Debug for VirtualClock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("VirtualClock").field(&self.now()).finish()
+    }
+}
+
+impl VirtualClock {
+    /// Clock set to the given unix time
+    pub fn starting_at(unix_time: Duration) -> Self {
+        Self {
+            handle: MockTimeHandle::new(unix_time),
+        }
+    }
+
+    /// Current unix time
+    pub fn now(&self) -> Duration {
+        self.time_source().get_unix_time()
+    }
+
+    /// Time source following this clock, to be passed to the components which expire things
+    /// (e.g. `Queue::with_time_source`), so that their TTLs are counted on virtual time
+    pub fn time_source(&self) -> TimeSource {
+        self.handle.source()
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.handle.advance(duration);
+    }
+
+    fn advance_to(&self, unix_time: Duration) {
+        if unix_time > self.now() {
+            self.handle.set(unix_time);
+        }
+    }
+}
+
+impl Default for VirtualClock {
+    /// Clock set to the current system time, so that it's interchangeable with the system clock
+    fn default() -> Self {
+        Self::starting_at(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("Failed to get the current system time"),
+        )
+    }
+}
+
+/// Network of in-memory peers, see the [crate documentation](crate)
+#[derive(Debug)]
+pub struct SimNetwork<T: Pload> {
+    seed: u64,
+    rng: StdRng,
+    clock: VirtualClock,
+    latency: RangeInclusive<Duration>,
+    faults: Faults,
+    peers: BTreeMap<PeerId, SimPeer<T>>,
+    /// Messages on their way, earliest first
+    in_flight: BinaryHeap<Reverse<InFlight<T>>>,
+    /// Number of messages sent so far, used to order messages delivered at the same time
+    sent: u64,
+}
+
+#[derive(Debug)]
+struct SimPeer<T: Pload> {
+    endpoint: Endpoint<T>,
+    /// Peers this peer wants to be connected to
+    topology: OnlinePeers,
+    is_shut_down: bool,
+}
+
+struct InFlight<T> {
+    deliver_at: Duration,
+    seq: u64,
+    from: PeerId,
+    to: PeerId,
+    data: T,
+}
+
+impl<T> core::fmt::Debug for InFlight<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InFlight")
+            .field("deliver_at", &self.deliver_at)
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> PartialEq for InFlight<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl<T> Eq for InFlight<T> {}
+
+impl<T> PartialOrd for InFlight<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for InFlight<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.deliver_at, self.seq).cmp(&(other.deliver_at, other.seq))
+    }
+}
+
+impl<T: Pload> SimNetwork<T> {
+    /// Empty network whose randomness is derived from `seed`
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            seed,
+            faults: Faults::new(rng.gen()),
+            rng,
+            clock: VirtualClock::default(),
+            latency: DEFAULT_LATENCY,
+            peers: BTreeMap::new(),
+            in_flight: BinaryHeap::new(),
+            sent: 0,
+        }
+    }
+
+    /// Empty network seeded from [`SEED_ENV`], or with a random seed if it isn't set.
+    /// The seed is logged, so that a failing run can be repeated.
+    ///
+    /// # Panics
+    /// If [`SEED_ENV`] is set to something other than a number
+    pub fn from_env() -> Self {
+        let seed = std::env::var(SEED_ENV).map_or_else(
+            |_| rand::random(),
+            |seed| {
+                seed.parse()
+                    .unwrap_or_else(|_| panic!("{SEED_ENV} must be a number, got `{seed}`"))
+            },
+        );
+        info!(
+            seed,
+            "Simulating network, set {SEED_ENV}={seed} to reproduce"
+        );
+        Self::new(seed)
+    }
+
+    /// Draw latencies of the messages from `latency`. [`DEFAULT_LATENCY`] by default.
+    #[must_use]
+    pub fn with_latency(mut self, latency: RangeInclusive<Duration>) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Seed the network was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Clock the network runs on
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Faults injected into the messages sent from now on
+    pub fn faults(&self) -> &Faults {
+        &self.faults
+    }
+
+    /// Key pair and id of a new peer, derived from the seed of the network.
+    /// The peer still has to be [added](Self::add_peer).
+    pub fn generate_peer(&mut self) -> (PeerId, KeyPair) {
+        let key_pair =
+            KeyPair::from_seed(self.rng.gen::<[u8; 32]>().to_vec(), Algorithm::default());
+        // Addresses aren't used to route messages, peers are told apart by their keys
+        let port = u16::try_from(self.peers.len() + 1).expect("Too many peers");
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+        let peer_id = PeerId::new(address, key_pair.public_key().clone());
+        (peer_id, key_pair)
+    }
+
+    /// Connect a peer to the network and return its handle.
+    ///
+    /// Same as with real peers, two peers are online for each other
+    /// once both have the other one in their topology.
+    ///
+    /// # Panics
+    /// If a peer with the same public key has already been added
+    pub fn add_peer(&mut self, peer_id: PeerId) -> NetworkHandle<T> {
+        let (handle, endpoint) = NetworkHandle::in_memory();
+        let peer = SimPeer {
+            endpoint,
+            topology: OnlinePeers::new(),
+            is_shut_down: false,
+        };
+        assert!(
+            self.peers.insert(peer_id, peer).is_none(),
+            "Peer is already in the network"
+        );
+        handle
+    }
+
+    /// Carry messages until `duration` of virtual time passes.
+    ///
+    /// Only the messages which have been sent by the time the handle owners are given a chance
+    /// to run are carried, so the owners must be driven on the same runtime (or otherwise be done
+    /// with their reaction to the delivered messages) for the run to be reproducible.
+    pub async fn run_for(&mut self, duration: Duration) {
+        let until = self.clock.now() + duration;
+        loop {
+            self.collect_outgoing();
+            match self.in_flight.peek() {
+                Some(Reverse(next)) if next.deliver_at <= until => {}
+                _ => break,
+            }
+            let Reverse(message) = self.in_flight.pop().expect("Just peeked");
+            self.clock.advance_to(message.deliver_at);
+            self.deliver(message).await;
+            // Let the receivers react to the message before collecting what they've sent
+            tokio::task::yield_now().await;
+        }
+        self.clock.advance_to(until);
+        self.collect_outgoing();
+    }
+
+    /// Number of messages on their way
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Whether `a` and `b` are connected to each other
+    pub fn are_connected(&self, a: &PeerId, b: &PeerId) -> bool {
+        let (Some(peer_a), Some(peer_b)) = (self.peers.get(a), self.peers.get(b)) else {
+            return false;
+        };
+        a != b
+            && !peer_a.is_shut_down
+            && !peer_b.is_shut_down
+            && peer_a.topology.contains(b)
+            && peer_b.topology.contains(a)
+    }
+
+    /// Take everything sent through the handles and put the messages on their way
+    fn collect_outgoing(&mut self) {
+        let mut outgoing = Vec::new();
+        for (peer_id, peer) in &mut self.peers {
+            while let Some(message) = peer.endpoint.try_recv() {
+                outgoing.push((peer_id.clone(), message));
+            }
+        }
+
+        for (from, message) in outgoing {
+            match message {
+                Outgoing::Post(Post { data, peer_id }) => self.send(from, peer_id, data),
+                Outgoing::Broadcast(Broadcast { data }) => {
+                    let recipients = self
+                        .peers
+                        .keys()
+                        .filter(|to| self.are_connected(&from, to))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    for to in recipients {
+                        self.send(from.clone(), to, data.clone());
+                    }
+                }
+                Outgoing::UpdateTopology(UpdateTopology(topology)) => {
+                    if let Some(peer) = self.peers.get_mut(&from) {
+                        peer.topology = topology;
+                    }
+                }
+                Outgoing::Shutdown => {
+                    if let Some(peer) = self.peers.get_mut(&from) {
+                        peer.is_shut_down = true;
+                    }
+                }
+                Outgoing::BanPeer(_) | Outgoing::ReportMisbehavior(_) => {
+                    trace!(peer=%from, "Ignoring request to ban a peer");
+                }
+                Outgoing::UpdateCertificates(_) => {
+                    trace!(peer=%from, "Ignoring peer certificates, handshakes aren't simulated");
+                }
+            }
+        }
+
+        for (peer_id, peer) in &self.peers {
+            let online_peers = self
+                .peers
+                .keys()
+                .filter(|other| self.are_connected(peer_id, other))
+                .cloned()
+                .collect();
+            peer.endpoint.set_online_peers(online_peers);
+        }
+    }
+
+    fn send(&mut self, from: PeerId, to: PeerId, data: T) {
+        if !self.are_connected(&from, &to) {
+            trace!(%from, %to, "Dropping message to a peer which isn't connected");
+            return;
+        }
+        let (delay, data) = match self.faults.apply(from.public_key(), to.public_key(), data) {
+            Fate::Deliver(data) => (Duration::ZERO, data),
+            Fate::Delay(delay, data) => (delay, data),
+            Fate::Drop => {
+                trace!(%from, %to, "Dropping message as a fault");
+                return;
+            }
+        };
+        let latency = self.rng.gen_range(self.latency.clone());
+        self.in_flight.push(Reverse(InFlight {
+            deliver_at: self.clock.now() + latency + delay,
+            seq: self.sent,
+            from,
+            to,
+            data,
+        }));
+        self.sent += 1;
+    }
+
+    async fn deliver(&mut self, message: InFlight<T>) {
+        // The connection might have been dropped while the message was on its way
+        if !self.are_connected(&message.from, &message.to) {
+            trace!(from=%message.from, to=%message.to, "Dropping message to a peer which disconnected");
+            return;
+        }
+        let peer = self
+            .peers
+            .get_mut(&message.to)
+            .expect("Connected peers are in the network");
+        peer.endpoint
+            .deliver(PeerMessage(message.from, message.data))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use parity_scale_codec::{Decode, Encode};
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+    struct Ping(u32);
+
+    impl iroha_p2p::Classify for Ping {}
+
+    fn connect(handles: &[(PeerId, NetworkHandle<Ping>)]) {
+        let topology = handles
+            .iter()
+            .map(|(peer_id, _)| peer_id.clone())
+            .collect::<HashSet<_>>();
+        for (_, handle) in handles {
+            handle.update_topology(UpdateTopology(topology.clone()));
+        }
+    }
+
+    fn network(seed: u64, peers: usize) -> (SimNetwork<Ping>, Vec<(PeerId, NetworkHandle<Ping>)>) {
+        let mut network = SimNetwork::new(seed);
+        let handles = (0..peers)
+            .map(|_| {
+                let (peer_id, _) = network.generate_peer();
+                let handle = network.add_peer(peer_id.clone());
+                (peer_id, handle)
+            })
+            .collect::<Vec<_>>();
+        (network, handles)
+    }
+
+    #[tokio::test]
+    async fn messages_arrive_after_latency() {
+        let (network, handles) = network(0, 2);
+        let mut network = network.with_latency(Duration::from_secs(1)..=Duration::from_secs(1));
+        let (sender, mut receiver) = mpsc::channel(8);
+        handles[1].1.subscribe_to_peers_messages(sender);
+        connect(&handles);
+        network.run_for(Duration::ZERO).await;
+        assert!(handles[0]
+            .1
+            .online_peers(|peers| peers.contains(&handles[1].0)));
+
+        let start = network.clock().now();
+        handles[0].1.post(Post {
+            data: Ping(1),
+            peer_id: handles[1].0.clone(),
+        });
+        network.run_for(Duration::from_millis(999)).await;
+        assert!(receiver.try_recv().is_err());
+
+        network.run_for(Duration::from_millis(1)).await;
+        let PeerMessage(from, data) = receiver.try_recv().expect("Message is delivered");
+        assert_eq!((from, data), (handles[0].0.clone(), Ping(1)));
+        assert_eq!(network.clock().now() - start, Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn messages_need_topology_of_both_peers() {
+        let (mut network, handles) = network(0, 2);
+        let (sender, mut receiver) = mpsc::channel(8);
+        handles[1].1.subscribe_to_peers_messages(sender);
+        handles[0]
+            .1
+            .update_topology(UpdateTopology(HashSet::from([handles[1].0.clone()])));
+        handles[0].1.broadcast(Broadcast { data: Ping(1) });
+        network.run_for(Duration::from_secs(1)).await;
+
+        assert!(handles[0].1.online_peers(HashSet::is_empty));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn partitioned_peers_dont_hear_each_other() {
+        let (mut network, handles) = network(0, 3);
+        let mut receivers = handles
+            .iter()
+            .map(|(_, handle)| {
+                let (sender, receiver) = mpsc::channel(8);
+                handle.subscribe_to_peers_messages(sender);
+                receiver
+            })
+            .collect::<Vec<_>>();
+        connect(&handles);
+        network.faults().partition([
+            vec![handles[0].0.public_key().clone()],
+            vec![
+                handles[1].0.public_key().clone(),
+                handles[2].0.public_key().clone(),
+            ],
+        ]);
+        network.run_for(Duration::ZERO).await;
+
+        handles[1].1.broadcast(Broadcast { data: Ping(1) });
+        network.run_for(Duration::from_secs(1)).await;
+        assert!(receivers[0].try_recv().is_err());
+        assert!(receivers[2].try_recv().is_ok());
+
+        network.faults().heal();
+        handles[1].1.broadcast(Broadcast { data: Ping(2) });
+        network.run_for(Duration::from_secs(1)).await;
+        assert!(receivers[0].try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn same_seed_same_order() {
+        async fn arrivals(seed: u64) -> Vec<(PeerId, Ping, Duration)> {
+            let (mut network, handles) = network(seed, 4);
+            let (sender, mut receiver) = mpsc::channel(64);
+            handles[0].1.subscribe_to_peers_messages(sender);
+            connect(&handles);
+            network.run_for(Duration::ZERO).await;
+            for (i, (_, handle)) in handles.iter().enumerate().skip(1) {
+                for j in 0..4 {
+                    handle.broadcast(Broadcast {
+                        data: Ping(u32::try_from(i * 10 + j).unwrap()),
+                    });
+                }
+            }
+
+            let start = network.clock().now();
+            let mut arrivals = Vec::new();
+            while network.in_flight() > 0 || arrivals.is_empty() {
+                network.run_for(Duration::from_millis(1)).await;
+                while let Ok(PeerMessage(from, data)) = receiver.try_recv() {
+                    arrivals.push((from, data, network.clock().now() - start));
+                }
+            }
+            arrivals
+        }
+
+        let first = arrivals(42).await;
+        assert_eq!(first.len(), 12);
+        assert_eq!(first, arrivals(42).await);
+        assert_ne!(first, arrivals(43).await);
+    }
+}
@@ -0,0 +1,234 @@
+//! End-to-end throughput benchmark. For usage run with `--help`.
+//!
+//! Starts a local network, submits a transaction workload through every peer
+//! and reports TPS and commit latency percentiles as JSON.
+
+mod report;
+mod workload;
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    num::{NonZeroU32, NonZeroU64},
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use iroha::{
+    client::Client,
+    crypto::HashOf,
+    data_model::{
+        events::pipeline::{PipelineEventBox, TransactionEventFilter, TransactionStatus},
+        parameter::BlockParameter,
+        prelude::*,
+    },
+};
+use test_network::{wait_for_genesis_committed_with_max_retries, Network};
+
+use crate::{
+    report::{Latency, Report},
+    workload::Workload,
+};
+
+/// Measure TPS and transaction latency of a local network
+#[derive(Parser, Debug)]
+#[clap(author, version, about)]
+struct Args {
+    /// Number of peers in the network
+    #[clap(long, default_value_t = 4)]
+    peers: u32,
+    /// Kind of submitted transactions
+    #[clap(long, value_enum, default_value_t = Workload::Mint)]
+    workload: Workload,
+    /// Total number of submitted transactions, split evenly between the peers
+    #[clap(long, default_value_t = 4096)]
+    transactions: u32,
+    /// Interval in microseconds between transactions submitted to the same peer
+    #[clap(long, default_value_t = 0)]
+    interval_us: u64,
+    /// Maximum number of transactions in a block
+    #[clap(long, default_value_t = nonzero_ext::nonzero!(512_u64))]
+    max_transactions_in_block: NonZeroU64,
+    /// Seconds to wait for the submitted transactions to be committed
+    #[clap(long, default_value_t = 120)]
+    timeout_secs: u64,
+    /// Number of attempts to wait for the genesis to be committed
+    #[clap(long, default_value_t = 30)]
+    genesis_max_retries: u32,
+    /// Path to write the report to, printed to stdout if not set
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+/// Final status of a submitted transaction
+struct Outcome {
+    hash: HashOf<SignedTransaction>,
+    at: Instant,
+    approved: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let (_rt, network, client) = Network::start_test_with_runtime(args.peers, None);
+    let clients = network.clients();
+    wait_for_genesis_committed_with_max_retries(&clients, 0, args.genesis_max_retries);
+
+    client.submit_blocking(SetParameter::new(Parameter::Block(
+        BlockParameter::MaxTransactions(args.max_transactions_in_block),
+    )))?;
+    args.workload.prepare(&client, args.transactions)?;
+
+    let outcomes = spawn_outcome_listener(&client)?;
+
+    let started = Instant::now();
+    let submitted = submit_workload(&args, clients)?;
+
+    let deadline = started + Duration::from_secs(args.timeout_secs);
+    let mut finished = HashMap::new();
+    while finished.len() < submitted.len() {
+        let Some(timeout) = deadline.checked_duration_since(Instant::now()) else {
+            iroha_logger::warn!("Timed out waiting for the transactions to be committed");
+            break;
+        };
+        let Ok(outcome) = outcomes.recv_timeout(timeout) else {
+            iroha_logger::warn!("Stopped receiving the statuses of the transactions");
+            break;
+        };
+        if submitted.contains_key(&outcome.hash) {
+            finished.insert(outcome.hash, outcome);
+        }
+    }
+
+    let report = make_report(&args, &submitted, &finished, started);
+    match &args.output {
+        Some(path) => serde_json::to_writer_pretty(
+            File::create(path).wrap_err("Failed to create the report file")?,
+            &report,
+        )?,
+        None => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+/// Listen to the pipeline events and forward the final statuses of the transactions
+fn spawn_outcome_listener(client: &Client) -> Result<mpsc::Receiver<Outcome>> {
+    let events = client.listen_for_events([TransactionEventFilter::new()])?;
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for event in events {
+            let Ok(EventBox::Pipeline(PipelineEventBox::Transaction(event))) = event else {
+                continue;
+            };
+            let approved = match event.status() {
+                TransactionStatus::Approved => true,
+                TransactionStatus::Rejected(_)
+                | TransactionStatus::Expired
+                | TransactionStatus::Cancelled => false,
+                TransactionStatus::Queued => continue,
+            };
+            let outcome = Outcome {
+                hash: *event.hash(),
+                at: Instant::now(),
+                approved,
+            };
+            if sender.send(outcome).is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+/// Submit the workload through every client concurrently, returning the submission instants
+fn submit_workload(
+    args: &Args,
+    clients: Vec<Client>,
+) -> Result<HashMap<HashOf<SignedTransaction>, Instant>> {
+    let peers = u32::try_from(clients.len()).wrap_err("Too many peers")?;
+    let per_peer = args.transactions / peers;
+    let workload = args.workload;
+    let interval = Duration::from_micros(args.interval_us);
+
+    let handles = clients
+        .into_iter()
+        .zip(0..)
+        .map(|(client, peer_index)| {
+            thread::spawn(move || {
+                let mut submitted = Vec::new();
+                for index in peer_index * per_peer..(peer_index + 1) * per_peer {
+                    let mut transaction =
+                        TransactionBuilder::new(client.chain.clone(), client.account.clone())
+                            .with_instructions([workload.instruction(index)]);
+                    // Nonce keeps the hashes of the same instructions distinct
+                    transaction.set_nonce(NonZeroU32::new(index + 1).expect("Not zero"));
+                    let transaction = client.sign_transaction(transaction);
+
+                    let at = Instant::now();
+                    match client.submit_transaction(&transaction) {
+                        Ok(hash) => submitted.push((hash, at)),
+                        Err(error) => {
+                            iroha_logger::error!(?error, "Failed to submit transaction");
+                        }
+                    }
+                    thread::sleep(interval);
+                }
+                submitted
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut submitted = HashMap::new();
+    for handle in handles {
+        submitted.extend(handle.join().expect("Transaction submitter panicked"));
+    }
+    Ok(submitted)
+}
+
+fn make_report(
+    args: &Args,
+    submitted: &HashMap<HashOf<SignedTransaction>, Instant>,
+    finished: &HashMap<HashOf<SignedTransaction>, Outcome>,
+    started: Instant,
+) -> Report {
+    let approved = finished
+        .values()
+        .filter(|outcome| outcome.approved)
+        .collect::<Vec<_>>();
+    let rejected = finished.len() - approved.len();
+    let elapsed = finished
+        .values()
+        .map(|outcome| outcome.at.duration_since(started))
+        .max()
+        .unwrap_or_default();
+    let latencies = approved
+        .iter()
+        .map(|outcome| outcome.at.duration_since(submitted[&outcome.hash]))
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let tps = if elapsed.is_zero() {
+        0.0
+    } else {
+        approved.len() as f64 / elapsed.as_secs_f64()
+    };
+
+    Report {
+        workload: args.workload,
+        peers: args.peers,
+        submitted: submitted.len(),
+        approved: approved.len(),
+        rejected,
+        lost: submitted.len() - finished.len(),
+        elapsed_ms: elapsed.as_millis(),
+        tps,
+        latency_ms: Latency::from_samples(latencies),
+    }
+}
@@ -0,0 +1,82 @@
+//! Machine-readable summary of a benchmark run
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::workload::Workload;
+
+/// Result of a benchmark run
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Workload the transactions were made of
+    pub workload: Workload,
+    /// Number of peers in the network
+    pub peers: u32,
+    /// Number of submitted transactions
+    pub submitted: usize,
+    /// Number of transactions committed as valid
+    pub approved: usize,
+    /// Number of transactions committed as invalid
+    pub rejected: usize,
+    /// Number of transactions which expired or weren't committed before the timeout
+    pub lost: usize,
+    /// Time from the first submission to the last commit, in milliseconds
+    pub elapsed_ms: u128,
+    /// Approved transactions per second
+    pub tps: f64,
+    /// Latency from submission to commit of the approved transactions
+    pub latency_ms: Latency,
+}
+
+/// Percentiles of the transaction latency, in milliseconds
+#[derive(Debug, Default, Serialize)]
+pub struct Latency {
+    pub min: u128,
+    pub p50: u128,
+    pub p90: u128,
+    pub p99: u128,
+    pub max: u128,
+}
+
+impl Latency {
+    /// Compute the percentiles of `latencies`
+    pub fn from_samples(mut latencies: Vec<Duration>) -> Self {
+        if latencies.is_empty() {
+            return Self::default();
+        }
+        latencies.sort_unstable();
+
+        let percentile = |p: usize| {
+            // Nearest-rank method
+            let rank = (latencies.len() * p).div_ceil(100).max(1);
+            latencies[rank - 1].as_millis()
+        };
+
+        Self {
+            min: latencies[0].as_millis(),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: latencies[latencies.len() - 1].as_millis(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_percentiles() {
+        let latencies = (1..=100).map(Duration::from_millis).collect();
+
+        let latency = Latency::from_samples(latencies);
+
+        assert_eq!(latency.min, 1);
+        assert_eq!(latency.p50, 50);
+        assert_eq!(latency.p90, 90);
+        assert_eq!(latency.p99, 99);
+        assert_eq!(latency.max, 100);
+    }
+}
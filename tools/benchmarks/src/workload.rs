@@ -0,0 +1,55 @@
+//! Transaction workloads submitted by the benchmark
+
+use clap::ValueEnum;
+use eyre::Result;
+use iroha::{client::Client, data_model::prelude::*};
+use serde::Serialize;
+use test_samples::{ALICE_ID, BOB_ID};
+
+/// Kind of instructions making up the benchmark transactions
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Workload {
+    /// Transfer roses from Alice to Bob
+    Transfer,
+    /// Mint roses to Alice
+    Mint,
+    /// Set a distinct key in the metadata of Alice
+    Metadata,
+}
+
+impl Workload {
+    /// Submit the transactions the workload depends on, e.g. mint the assets to transfer
+    ///
+    /// # Errors
+    /// If the preparatory transaction is rejected
+    pub fn prepare(self, client: &Client, transactions: u32) -> Result<()> {
+        match self {
+            Self::Transfer => {
+                client.submit_blocking(Mint::asset_numeric(transactions, rose_of_alice()))?;
+            }
+            Self::Mint | Self::Metadata => {}
+        }
+        Ok(())
+    }
+
+    /// Instruction of the `index`-th transaction of the workload
+    pub fn instruction(self, index: u32) -> InstructionBox {
+        match self {
+            Self::Transfer => {
+                Transfer::asset_numeric(rose_of_alice(), 1_u32, BOB_ID.clone()).into()
+            }
+            Self::Mint => Mint::asset_numeric(1_u32, rose_of_alice()).into(),
+            Self::Metadata => SetKeyValue::account(
+                ALICE_ID.clone(),
+                format!("benchmark_{index}").parse().expect("Valid"),
+                index,
+            )
+            .into(),
+        }
+    }
+}
+
+fn rose_of_alice() -> AssetId {
+    AssetId::new("rose#wonderland".parse().expect("Valid"), ALICE_ID.clone())
+}
@@ -8,6 +8,7 @@ use color_eyre::eyre::WrapErr as _;
 use iroha_data_model::{isi::InstructionBox, parameter::Parameters, prelude::*};
 use iroha_executor_data_model::permission::{
     account::{CanRemoveKeyValueInAccount, CanSetKeyValueInAccount},
+    event::CanSubscribeToAllEvents,
     parameter::CanSetParameters,
 };
 use iroha_genesis::{GenesisBuilder, RawGenesisTransaction, GENESIS_DOMAIN_ID};
@@ -117,6 +118,8 @@ pub fn generate_default(
     );
     let grant_permission_to_set_parameters =
         Grant::account_permission(CanSetParameters, ALICE_ID.clone());
+    let grant_permission_to_subscribe_to_all_events =
+        Grant::account_permission(CanSubscribeToAllEvents, ALICE_ID.clone());
     let transfer_rose_ownership = Transfer::asset_definition(
         genesis_account_id.clone(),
         "rose#wonderland".parse()?,
@@ -151,6 +154,7 @@ pub fn generate_default(
         transfer_rose_ownership.into(),
         transfer_wonderland_ownership.into(),
         grant_permission_to_set_parameters.into(),
+        grant_permission_to_subscribe_to_all_events.into(),
     ]
     .into_iter()
     .chain(std::iter::once(register_user_metadata_access))
@@ -3,8 +3,6 @@ use std::path::{Path, PathBuf};
 
 use clap::{Parser, Subcommand};
 use iroha_core::kura::{BlockIndex, BlockStore};
-use iroha_data_model::block::SignedBlock;
-use iroha_version::scale::DecodeVersioned;
 
 /// Kura inspector
 #[derive(Parser)]
@@ -29,6 +27,13 @@ enum Command {
         #[clap(short = 'n', long, default_value_t = 1)]
         length: u64,
     },
+    /// Rewrite all the blocks with the given compression level.
+    /// The peer using the block store must be stopped.
+    Compress {
+        /// Zstd compression level, `0` decompresses the blocks
+        #[clap(short, long, default_value_t = 3)]
+        level: u8,
+    },
 }
 
 fn main() {
@@ -46,11 +51,12 @@ fn main() {
             from_height.unwrap_or(u64::MAX),
             length,
         ),
+        Command::Compress { level } => compress_blockchain(&args.path_to_block_store, level),
     }
 }
 
-fn print_blockchain(block_store_path: &Path, from_height: u64, block_count: u64) {
-    let mut block_store_path: std::borrow::Cow<'_, Path> = block_store_path.into();
+fn block_store_dir(path: &Path) -> std::borrow::Cow<'_, Path> {
+    let mut block_store_path: std::borrow::Cow<'_, Path> = path.into();
 
     if let Some(os_str_file_name) = block_store_path.file_name() {
         let file_name_str = os_str_file_name.to_str().unwrap_or("");
@@ -59,6 +65,21 @@ fn print_blockchain(block_store_path: &Path, from_height: u64, block_count: u64)
         }
     }
 
+    block_store_path
+}
+
+fn compress_blockchain(block_store_path: &Path, level: u8) {
+    let block_store_path = block_store_dir(block_store_path);
+    let mut block_store = BlockStore::new(&block_store_path).with_compression_level(level);
+
+    let block_count = block_store.migrate_compression().unwrap_or_else(|error| {
+        panic!("Failed to migrate block store {block_store_path:?}: {error}")
+    });
+    println!("Rewrote {block_count} blocks with compression level {level}.");
+}
+
+fn print_blockchain(block_store_path: &Path, from_height: u64, block_count: u64) {
+    let block_store_path = block_store_dir(block_store_path);
     let block_store = BlockStore::new(&block_store_path);
 
     let index_count = block_store
@@ -124,7 +145,7 @@ fn print_blockchain(block_store_path: &Path, from_height: u64, block_count: u64)
         block_store
             .read_block_data(idx.start, &mut block_buf)
             .unwrap_or_else(|_| panic!("Failed to read block № {} data.", meta_index + 1));
-        let block = SignedBlock::decode_all_versioned(&block_buf)
+        let block = BlockStore::decode_block(&block_buf)
             .unwrap_or_else(|_| panic!("Failed to decode block № {}", meta_index + 1));
         println!("Block#{} :", meta_index + 1);
         println!("{block:#?}");
@@ -10,6 +10,15 @@ pub mod uri {
     pub const QUERY: &str = "/query";
     /// Transaction URI is used to handle incoming ISI requests.
     pub const TRANSACTION: &str = "/transaction";
+    /// Transaction simulation URI is used to predict the outcome of an unsigned transaction.
+    pub const TRANSACTION_SIMULATION: &str = "/transaction/simulate";
+    /// Scheduled transactions URI is used to list the transactions of an account held in the queue
+    /// until the time they are scheduled for.
+    pub const SCHEDULED_TRANSACTIONS: &str = "/transaction/scheduled";
+    /// Transaction cancellation URI is used to withdraw a transaction which is still in the queue.
+    pub const CANCEL_TRANSACTION: &str = "/transaction/cancel";
+    /// Transaction versions URI is used to find out which transaction versions the peer accepts.
+    pub const TRANSACTION_VERSIONS: &str = "/transaction/versions";
     /// Block URI is used to handle incoming Block requests.
     pub const CONSENSUS: &str = "/consensus";
     /// Health URI is used to handle incoming Healthcheck requests.
@@ -20,6 +29,8 @@ pub mod uri {
     pub const SUBSCRIPTION: &str = "/events";
     /// The web socket uri used to subscribe to blocks stream.
     pub const BLOCKS_STREAM: &str = "/block/stream";
+    /// Webhooks URI is used to register webhooks and query their delivery status.
+    pub const WEBHOOKS: &str = "/webhooks";
     /// The URI for local config changing inspecting
     pub const CONFIGURATION: &str = "/configuration";
     /// URI to report status for administration
@@ -33,4 +44,30 @@ pub mod uri {
     pub const API_VERSION: &str = "/api_version";
     /// URI for getting cpu profile
     pub const PROFILE: &str = "/debug/pprof/profile";
+    /// Admin URI to temporarily ban a peer
+    pub const ADMIN_BAN_PEER: &str = "/admin/ban_peer";
+    /// Admin URI to force resynchronization of blocks from a given height
+    pub const ADMIN_RESYNC: &str = "/admin/resync";
+    /// Admin URI to dump the queue contents and consensus round state
+    pub const ADMIN_DUMP: &str = "/admin/dump";
+    /// Admin URI to get the consensus round state
+    pub const ADMIN_CONSENSUS_STATUS: &str = "/admin/consensus";
+    /// Admin URI to list active event and block subscriptions
+    pub const ADMIN_SUBSCRIPTIONS: &str = "/admin/subscriptions";
+    /// Admin URI to terminate an event or block subscription
+    pub const ADMIN_TERMINATE_SUBSCRIPTION: &str = "/admin/subscriptions/terminate";
+}
+
+pub mod header {
+    //! HTTP headers that Torii adds to its responses.
+
+    /// Cost of the query, see `query_cost_budget` in the Torii configuration
+    pub const QUERY_COST: &str = "x-iroha-query-cost";
+    /// Query budget left to the account after the query, if queries are metered
+    pub const QUERY_BUDGET_REMAINING: &str = "x-iroha-query-budget-remaining";
+    /// JSON of the cursor to continue an iterable query with, if its results are returned as CSV
+    pub const QUERY_CONTINUE_CURSOR: &str = "x-iroha-query-continue-cursor";
+    /// Milliseconds the peer has been partitioned from a quorum of peers for,
+    /// if it answers the query in the degraded mode and its state might be stale
+    pub const STALE_FOR_MS: &str = "x-iroha-stale-for-ms";
 }
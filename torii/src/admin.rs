@@ -0,0 +1,504 @@
+//! Administration API of the peer.
+//!
+//! It is served on a separate listener and only enabled if the `admin` section is present
+//! in the configuration. Every request body is a JSON object with a `timestamp_ms` field,
+//! signed with the private key matching the configured public key. The hex-encoded
+//! signature of the raw body is passed in the [`SIGNATURE_HEADER`] header.
+
+use std::{
+    collections::BTreeSet,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::post,
+    Router,
+};
+use iroha_config::parameters::actual::Admin as Config;
+use iroha_core::{
+    block_sync::BlockSynchronizerHandle,
+    queue::Queue,
+    state::{State, StateReadOnly},
+    sumeragi::{RoundStatus, SumeragiHandle},
+    IrohaNetwork,
+};
+use iroha_crypto::{Hash, PublicKey, Signature};
+use iroha_data_model::transaction::SignedTransaction;
+use iroha_p2p::BanPeer;
+use iroha_primitives::addr::SocketAddr;
+use iroha_torii_const::uri;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::subscriptions::{SubscriptionId, SubscriptionInfo, Subscriptions};
+
+/// Header carrying the hex-encoded signature of the request body
+pub const SIGNATURE_HEADER: &str = "x-iroha-admin-signature";
+
+/// How far the request timestamp may diverge from the local time
+const REQUEST_VALIDITY_MS: u64 = 30_000;
+
+/// Longest ban which can be requested
+const MAX_BAN_DURATION_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Administration API of the peer
+pub struct AdminApi {
+    address: SocketAddr,
+    network: IrohaNetwork,
+    sumeragi: SumeragiHandle,
+    block_sync: BlockSynchronizerHandle,
+    queue: Arc<Queue>,
+    state: Arc<State>,
+    authenticator: Authenticator,
+}
+
+/// Checks signatures and freshness of the admin requests
+struct Authenticator {
+    public_key: PublicKey,
+    /// Requests accepted within the validity window, used to reject replays
+    seen_requests: Mutex<BTreeSet<(u64, Hash)>>,
+}
+
+/// Request body wrapper common to all admin requests
+#[derive(Deserialize)]
+struct Signed<T> {
+    timestamp_ms: u64,
+    #[serde(flatten)]
+    request: T,
+}
+
+/// Temporarily ban a peer
+#[derive(Deserialize)]
+struct BanPeerRequest {
+    address: SocketAddr,
+    duration_ms: u64,
+}
+
+/// Request blocks starting from the given height from all online peers
+#[derive(Deserialize)]
+struct ResyncRequest {
+    height: NonZeroUsize,
+}
+
+/// Dump the state of the peer
+#[derive(Deserialize)]
+struct DumpRequest {}
+
+/// Response to [`DumpRequest`]
+#[derive(Serialize)]
+struct Dump {
+    round: RoundStatus,
+    queue: Vec<SignedTransaction>,
+}
+
+/// Get the consensus round state
+#[derive(Deserialize)]
+struct ConsensusStatusRequest {}
+
+/// List active event and block subscriptions
+#[derive(Deserialize)]
+struct SubscriptionsRequest {}
+
+/// Close the stream of the given subscription
+#[derive(Deserialize)]
+struct TerminateSubscriptionRequest {
+    id: SubscriptionId,
+}
+
+impl AdminApi {
+    /// Construct [`AdminApi`].
+    pub fn new(
+        config: Config,
+        network: IrohaNetwork,
+        sumeragi: SumeragiHandle,
+        block_sync: BlockSynchronizerHandle,
+        queue: Arc<Queue>,
+        state: Arc<State>,
+    ) -> Self {
+        Self {
+            address: config.address,
+            network,
+            sumeragi,
+            block_sync,
+            queue,
+            state,
+            authenticator: Authenticator::new(config.public_key),
+        }
+    }
+
+    pub(crate) fn address(&self) -> &SocketAddr {
+        &self.address
+    }
+
+    pub(crate) fn create_router(self: &Arc<Self>, subscriptions: &Arc<Subscriptions>) -> Router {
+        Router::new()
+            .route(
+                uri::ADMIN_BAN_PEER,
+                post({
+                    let admin = Arc::clone(self);
+                    move |headers: HeaderMap, body: Bytes| {
+                        core::future::ready(admin.handle_ban_peer(&headers, &body))
+                    }
+                }),
+            )
+            .route(
+                uri::ADMIN_RESYNC,
+                post({
+                    let admin = Arc::clone(self);
+                    move |headers: HeaderMap, body: Bytes| async move {
+                        admin.handle_resync(&headers, &body).await
+                    }
+                }),
+            )
+            .route(
+                uri::ADMIN_DUMP,
+                post({
+                    let admin = Arc::clone(self);
+                    move |headers: HeaderMap, body: Bytes| {
+                        core::future::ready(admin.handle_dump(&headers, &body))
+                    }
+                }),
+            )
+            .route(
+                uri::ADMIN_CONSENSUS_STATUS,
+                post({
+                    let admin = Arc::clone(self);
+                    move |headers: HeaderMap, body: Bytes| {
+                        core::future::ready(admin.handle_consensus_status(&headers, &body))
+                    }
+                }),
+            )
+            .route(
+                uri::ADMIN_SUBSCRIPTIONS,
+                post({
+                    let admin = Arc::clone(self);
+                    let subscriptions = Arc::clone(subscriptions);
+                    move |headers: HeaderMap, body: Bytes| {
+                        core::future::ready(admin.handle_subscriptions(
+                            &headers,
+                            &body,
+                            &subscriptions,
+                        ))
+                    }
+                }),
+            )
+            .route(
+                uri::ADMIN_TERMINATE_SUBSCRIPTION,
+                post({
+                    let admin = Arc::clone(self);
+                    let subscriptions = Arc::clone(subscriptions);
+                    move |headers: HeaderMap, body: Bytes| {
+                        core::future::ready(admin.handle_terminate_subscription(
+                            &headers,
+                            &body,
+                            &subscriptions,
+                        ))
+                    }
+                }),
+            )
+    }
+
+    fn handle_ban_peer(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), Error> {
+        let BanPeerRequest {
+            address,
+            duration_ms,
+        } = self.authenticate(headers, body)?;
+        let duration = ban_duration(duration_ms)?;
+
+        iroha_logger::info!(%address, duration_ms, "Admin request to ban peer");
+        self.network.ban_peer(BanPeer { address, duration });
+
+        Ok(())
+    }
+
+    async fn handle_resync(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), Error> {
+        let ResyncRequest { height } = self.authenticate(headers, body)?;
+
+        check_resync_height(height, self.state.view().height())?;
+
+        iroha_logger::info!(%height, "Admin request to resync");
+        self.block_sync.resync_from(height).await;
+
+        Ok(())
+    }
+
+    fn handle_dump(&self, headers: &HeaderMap, body: &[u8]) -> Result<Json<Dump>, Error> {
+        let DumpRequest {} = self.authenticate(headers, body)?;
+
+        let queue = self
+            .queue
+            .all_transactions(&self.state.view())
+            .map(SignedTransaction::from)
+            .collect();
+
+        Ok(Json(Dump {
+            round: self.sumeragi.round_status(),
+            queue,
+        }))
+    }
+
+    fn handle_consensus_status(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Json<RoundStatus>, Error> {
+        let ConsensusStatusRequest {} = self.authenticate(headers, body)?;
+
+        Ok(Json(self.sumeragi.round_status()))
+    }
+
+    fn handle_subscriptions(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        subscriptions: &Subscriptions,
+    ) -> Result<Json<Vec<SubscriptionInfo>>, Error> {
+        let SubscriptionsRequest {} = self.authenticate(headers, body)?;
+
+        Ok(Json(subscriptions.list()))
+    }
+
+    fn handle_terminate_subscription(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        subscriptions: &Subscriptions,
+    ) -> Result<(), Error> {
+        let TerminateSubscriptionRequest { id } = self.authenticate(headers, body)?;
+
+        iroha_logger::info!(id, "Admin request to terminate subscription");
+        if !subscriptions.terminate(id) {
+            return Err(Error::UnknownSubscription(id));
+        }
+
+        Ok(())
+    }
+
+    /// Check request signature and freshness, then parse its body.
+    fn authenticate<T: DeserializeOwned>(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<T, Error> {
+        let now_ms = u64::try_from(self.queue.time_source().get_unix_time().as_millis())
+            .expect("Unix timestamp exceeds u64::MAX");
+        self.authenticator.authenticate(headers, body, now_ms)
+    }
+}
+
+impl Authenticator {
+    fn new(public_key: PublicKey) -> Self {
+        Self {
+            public_key,
+            seen_requests: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Check request signature and freshness at `now_ms`, then parse its body.
+    fn authenticate<T: DeserializeOwned>(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now_ms: u64,
+    ) -> Result<T, Error> {
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(Error::MissingSignature)?;
+        Signature::from_hex(signature)
+            .map_err(|_| Error::BadSignature)?
+            .verify(&self.public_key, body)
+            .map_err(|_| Error::BadSignature)?;
+
+        let Signed {
+            timestamp_ms,
+            request,
+        } = serde_json::from_slice::<Signed<T>>(body).map_err(Error::BadRequest)?;
+
+        if now_ms.abs_diff(timestamp_ms) > REQUEST_VALIDITY_MS {
+            return Err(Error::Expired);
+        }
+
+        let mut seen_requests = self
+            .seen_requests
+            .lock()
+            .expect("INTERNAL BUG: admin API lock is poisoned");
+        let oldest_valid_ms = now_ms.saturating_sub(REQUEST_VALIDITY_MS);
+        seen_requests.retain(|(timestamp_ms, _)| *timestamp_ms >= oldest_valid_ms);
+        if !seen_requests.insert((timestamp_ms, Hash::new(body))) {
+            return Err(Error::Replayed);
+        }
+
+        Ok(request)
+    }
+}
+
+/// Check that blocks from `height` can be resynced at `latest_height`
+fn check_resync_height(height: NonZeroUsize, latest_height: usize) -> Result<(), Error> {
+    if height.get() > latest_height + 1 {
+        return Err(Error::HeightAboveLatest);
+    }
+    // Committed blocks aren't rolled back, only the latest one can be replaced by a soft fork
+    if height.get() < latest_height {
+        return Err(Error::HeightBelowLatest);
+    }
+
+    Ok(())
+}
+
+/// Duration of the requested ban, unless it is longer than allowed
+fn ban_duration(duration_ms: u64) -> Result<Duration, Error> {
+    if duration_ms > MAX_BAN_DURATION_MS {
+        return Err(Error::BanTooLong {
+            max_ms: MAX_BAN_DURATION_MS,
+        });
+    }
+
+    Ok(Duration::from_millis(duration_ms))
+}
+
+/// Admin API errors.
+#[derive(Debug, thiserror::Error, displaydoc::Display)]
+pub enum Error {
+    /// Request signature is missing
+    MissingSignature,
+    /// Request signature is invalid
+    BadSignature,
+    /// Failed to parse request body
+    BadRequest(#[source] serde_json::Error),
+    /// Request timestamp is outside of the validity window
+    Expired,
+    /// Request has already been handled
+    Replayed,
+    /// Requested height is above the latest block
+    HeightAboveLatest,
+    /// Requested height is below the latest block, committed blocks can't be resynced
+    HeightBelowLatest,
+    /// Subscription with id `{0}` is not active
+    UnknownSubscription(SubscriptionId),
+    /// Requested ban is longer than {max_ms} ms
+    BanTooLong {
+        /// Longest ban which can be requested
+        max_ms: u64,
+    },
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        (self.status_code(), self.to_string()).into_response()
+    }
+}
+
+impl Error {
+    fn status_code(&self) -> StatusCode {
+        use Error::*;
+
+        match self {
+            MissingSignature | BadSignature | Expired | Replayed => StatusCode::UNAUTHORIZED,
+            BadRequest(_) | HeightAboveLatest | HeightBelowLatest | BanTooLong { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            UnknownSubscription(_) => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_crypto::KeyPair;
+
+    use super::*;
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+
+    fn signed_request(key_pair: &KeyPair, body: &str) -> HeaderMap {
+        let signature = Signature::new(key_pair.private_key(), body.as_bytes());
+        let signature = serde_json::to_value(signature).unwrap()["payload"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        headers
+    }
+
+    fn ban_request(duration_ms: u64) -> String {
+        format!(
+            r#"{{"timestamp_ms":{NOW_MS},"address":"127.0.0.1:1337","duration_ms":{duration_ms}}}"#
+        )
+    }
+
+    #[test]
+    fn request_signed_by_another_key_is_rejected() {
+        let authenticator = Authenticator::new(KeyPair::random().into_parts().0);
+        let body = ban_request(1_000);
+        let headers = signed_request(&KeyPair::random(), &body);
+
+        let error = authenticator
+            .authenticate::<BanPeerRequest>(&headers, body.as_bytes(), NOW_MS)
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::BadSignature));
+        assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn replayed_request_is_rejected() {
+        let key_pair = KeyPair::random();
+        let authenticator = Authenticator::new(key_pair.public_key().clone());
+        let body = ban_request(1_000);
+        let headers = signed_request(&key_pair, &body);
+
+        authenticator
+            .authenticate::<BanPeerRequest>(&headers, body.as_bytes(), NOW_MS)
+            .unwrap();
+        let error = authenticator
+            .authenticate::<BanPeerRequest>(&headers, body.as_bytes(), NOW_MS + 1)
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::Replayed));
+        assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn oversized_ban_duration_is_rejected() {
+        let key_pair = KeyPair::random();
+        let authenticator = Authenticator::new(key_pair.public_key().clone());
+        let body = ban_request(u64::MAX);
+        let headers = signed_request(&key_pair, &body);
+
+        let BanPeerRequest { duration_ms, .. } = authenticator
+            .authenticate(&headers, body.as_bytes(), NOW_MS)
+            .unwrap();
+        let error = ban_duration(duration_ms).unwrap_err();
+        assert!(matches!(error, Error::BanTooLong { .. }));
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+
+        assert_eq!(
+            ban_duration(MAX_BAN_DURATION_MS).unwrap(),
+            Duration::from_millis(MAX_BAN_DURATION_MS)
+        );
+    }
+
+    #[test]
+    fn resync_is_only_allowed_from_latest_or_next_block() {
+        let height = |height| NonZeroUsize::new(height).unwrap();
+
+        assert!(check_resync_height(height(5), 5).is_ok());
+        assert!(check_resync_height(height(6), 5).is_ok());
+        assert!(matches!(
+            check_resync_height(height(4), 5),
+            Err(Error::HeightBelowLatest)
+        ));
+        assert!(matches!(
+            check_resync_height(height(7), 5),
+            Err(Error::HeightAboveLatest)
+        ));
+        assert!(check_resync_height(height(1), 0).is_ok());
+    }
+}
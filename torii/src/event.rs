@@ -1,15 +1,21 @@
 //! Iroha is a quite dynamic system so many events can happen.
 //! This module contains descriptions of such an events and
 //! utility Iroha Special Instructions to work with them.
+use async_trait::async_trait;
 use axum::extract::ws::WebSocket;
 use futures::TryStreamExt;
-use iroha_data_model::events::prelude::*;
+use iroha_core::{
+    event_sink::EventSink,
+    state::{State, StateReadOnly, WorldReadOnly},
+};
+use iroha_data_model::{account::AccountId, events::prelude::*, permission::Permission};
+use iroha_executor_data_model::permission::event::CanSubscribeToAllEvents;
 use iroha_macro::error::ErrorTryFromEnum;
 
 use crate::stream::{self, Sink, Stream, StreamMessage as _};
 
 /// Type of Stream error
-pub type StreamError = stream::Error<<WebSocket as Stream<EventSubscriptionRequest>>::Err>;
+pub type StreamError = stream::Error<<WebSocket as Stream<SignedEventSubscriptionRequest>>::Err>;
 
 /// Type of error for `Consumer`
 #[derive(thiserror::Error, Debug)]
@@ -28,6 +34,20 @@ pub enum Error {
     /// Error that occurs than `WebSocket::next()` call returns `None`
     #[error("Can't receive message from stream")]
     CantReceiveMessage,
+    /// Subscription request isn't signed by its authority
+    #[error("Subscription request isn't signed by `{0}`")]
+    InvalidSignature(AccountId),
+    /// Authority of the subscription request isn't registered
+    #[error("Subscriber `{0}` isn't registered")]
+    UnknownAuthority(AccountId),
+    /// Authority isn't permitted to receive events matching the filter
+    #[error("`{authority}` isn't permitted to subscribe to events outside of its domain with `{filter:?}`")]
+    FilterNotPermitted {
+        /// Authority of the subscription request
+        authority: AccountId,
+        /// Filter matching events outside of the authority's domain
+        filter: Box<EventFilterBox>,
+    },
 }
 
 impl From<StreamError> for Error {
@@ -39,11 +59,64 @@ impl From<StreamError> for Error {
 /// Result type for `Consumer`
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Check that the subscriber may receive the events matching the requested filters.
+///
+/// # Errors
+/// If the request isn't signed by its registered authority or any of the filters isn't permitted,
+/// see [`authorize_filters`]
+pub fn authorize(
+    request: &SignedEventSubscriptionRequest,
+    state: &impl StateReadOnly,
+) -> Result<()> {
+    let EventSubscriptionRequest { authority, filters } = &request.payload;
+    request
+        .verify()
+        .map_err(|_| Error::InvalidSignature(authority.clone()))?;
+
+    authorize_filters(authority, filters, state)
+}
+
+/// Check that `authority` may receive the events matching `filters`.
+///
+/// Accounts with [`CanSubscribeToAllEvents`] may subscribe to any events, other accounts
+/// only to the events of their own domain, see [`EventFilterBox::is_scoped_to_domain`].
+///
+/// # Errors
+/// If the authority isn't registered or any of the filters isn't permitted
+pub fn authorize_filters(
+    authority: &AccountId,
+    filters: &[EventFilterBox],
+    state: &impl StateReadOnly,
+) -> Result<()> {
+    let can_subscribe_to_all_events = Permission::from(CanSubscribeToAllEvents);
+    if state
+        .world()
+        .account_permissions_iter(authority)
+        .map_err(|_| Error::UnknownAuthority(authority.clone()))?
+        .any(|permission| *permission == can_subscribe_to_all_events)
+    {
+        return Ok(());
+    }
+
+    if let Some(filter) = filters
+        .iter()
+        .find(|filter| !filter.is_scoped_to_domain(authority.domain()))
+    {
+        return Err(Error::FilterNotPermitted {
+            authority: authority.clone(),
+            filter: Box::new(filter.clone()),
+        });
+    }
+
+    Ok(())
+}
+
 /// Consumer for Iroha `Event`(s).
 /// Passes the events over the corresponding connection `stream` if they match the `filter`.
 #[derive(Debug)]
 pub struct Consumer {
     stream: WebSocket,
+    authority: AccountId,
     filters: Vec<EventFilterBox>,
 }
 
@@ -51,27 +124,49 @@ impl Consumer {
     /// Constructs [`Consumer`], which consumes `Event`s and forwards it through the `stream`.
     ///
     /// # Errors
-    /// Can fail due to timeout or without message at websocket or during decoding request
+    /// - Can fail due to timeout or without message at websocket or during decoding request
+    /// - If the subscriber isn't authorized to receive the requested events, see [`authorize`]
     #[iroha_futures::telemetry_future]
-    pub async fn new(mut stream: WebSocket) -> Result<Self> {
-        let EventSubscriptionRequest(filters) =
-            Stream::<EventSubscriptionRequest>::recv(&mut stream).await?;
-        Ok(Consumer { stream, filters })
+    pub async fn new(mut stream: WebSocket, state: &State) -> Result<Self> {
+        let request = Stream::<SignedEventSubscriptionRequest>::recv(&mut stream).await?;
+        authorize(&request, &state.view())?;
+
+        let EventSubscriptionRequest { authority, filters } = request.payload;
+        Ok(Consumer {
+            stream,
+            authority,
+            filters,
+        })
+    }
+
+    /// Account which requested the subscription
+    pub fn authority(&self) -> &AccountId {
+        &self.authority
+    }
+
+    /// Filters requested by the subscriber
+    pub fn filters(&self) -> &[EventFilterBox] {
+        &self.filters
     }
 
     /// Forwards the `event` over the `stream` if it matches the `filter`.
+    /// Returns whether the event was sent.
     ///
     /// # Errors
     /// Can fail due to timeout or sending event. Also receiving might fail
     #[iroha_futures::telemetry_future]
-    pub async fn consume(&mut self, event: EventBox) -> Result<()> {
+    pub async fn consume(&mut self, event: EventBox) -> Result<bool> {
         if !self.filters.iter().any(|filter| filter.matches(&event)) {
-            return Ok(());
+            return Ok(false);
         }
 
-        Sink::<_>::send(&mut self.stream, EventMessage(event))
-            .await
-            .map_err(Into::into)
+        self.send(event).await?;
+        Ok(true)
+    }
+
+    async fn send(&mut self, event: EventBox) -> Result<()> {
+        Sink::<_>::send(&mut self.stream, EventMessage(event)).await?;
+        Ok(())
     }
 
     /// Listen for `Close` message in loop
@@ -96,3 +191,16 @@ impl Consumer {
         self.stream.close().await.map_err(Into::into)
     }
 }
+
+/// Built-in sink delivering events to a websocket subscriber.
+/// Filtering by the subscription request is done by [`Consumer::consume`].
+#[async_trait]
+impl EventSink for Consumer {
+    fn name(&self) -> &str {
+        "websocket"
+    }
+
+    async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()> {
+        self.send(event.clone()).await.map_err(Into::into)
+    }
+}
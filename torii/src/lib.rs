@@ -7,9 +7,10 @@
 
 use std::{fmt::Debug, net::ToSocketAddrs, sync::Arc, time::Duration};
 
+pub use admin::AdminApi;
 use axum::{
     extract::{DefaultBodyLimit, WebSocketUpgrade},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
@@ -19,21 +20,28 @@ use futures::{stream::FuturesUnordered, StreamExt, TryStreamExt};
 use iroha_config::{
     base::{util::Bytes, WithOrigin},
     parameters::actual::Torii as Config,
+    peer::Role,
 };
 #[cfg(feature = "telemetry")]
 use iroha_core::metrics::MetricsReporter;
 use iroha_core::{
     kiso::{Error as KisoError, KisoHandle},
     kura::Kura,
+    partition::PartitionStatus,
     prelude::*,
-    query::store::LiveQueryStoreHandle,
+    query::{cache::QueryCacheHandle, quota::QueryQuotas, store::LiveQueryStoreHandle},
     queue::{self, Queue},
     state::State,
     EventsSender,
 };
-use iroha_data_model::ChainId;
+use iroha_data_model::{
+    account::AccountId,
+    transaction::error::{PeerDegraded, QueueFull},
+    ChainId,
+};
 use iroha_primitives::addr::SocketAddr;
 use iroha_torii_const::uri;
+use subscriptions::Subscriptions;
 use tokio::{net::TcpListener, sync::Notify, task};
 use tower_http::{
     timeout::TimeoutLayer,
@@ -43,12 +51,17 @@ use utils::{
     extractors::{ExtractAccept, ScaleVersioned},
     Scale,
 };
+use webhook::Webhooks;
 
 #[macro_use]
 pub(crate) mod utils;
+pub mod admin;
 mod event;
+mod query_format;
 mod routing;
 mod stream;
+mod subscriptions;
+pub mod webhook;
 
 const SERVER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -60,10 +73,19 @@ pub struct Torii {
     events: EventsSender,
     notify_shutdown: Arc<Notify>,
     query_service: LiveQueryStoreHandle,
+    query_cache: Option<QueryCacheHandle>,
+    query_quotas: Option<QueryQuotas>,
     kura: Arc<Kura>,
     transaction_max_content_len: Bytes<u64>,
     address: WithOrigin<SocketAddr>,
+    listener: Option<TcpListener>,
+    admin_listener: Option<TcpListener>,
+    role: Role,
+    partition: PartitionStatus,
     state: Arc<State>,
+    admin: Option<Arc<AdminApi>>,
+    subscriptions: Arc<Subscriptions>,
+    webhooks: Arc<Webhooks>,
     #[cfg(feature = "telemetry")]
     metrics_reporter: MetricsReporter,
 }
@@ -75,14 +97,27 @@ impl Torii {
         chain_id: ChainId,
         kiso: KisoHandle,
         config: Config,
+        role: Role,
+        partition: PartitionStatus,
         queue: Arc<Queue>,
         events: EventsSender,
         notify_shutdown: Arc<Notify>,
         query_service: LiveQueryStoreHandle,
+        query_cache: Option<QueryCacheHandle>,
+        query_quotas: Option<QueryQuotas>,
         kura: Arc<Kura>,
         state: Arc<State>,
+        admin: Option<AdminApi>,
         #[cfg(feature = "telemetry")] metrics_reporter: MetricsReporter,
     ) -> Self {
+        let subscriptions = Arc::<Subscriptions>::default();
+        let webhooks = Webhooks::new(
+            &config,
+            events.clone(),
+            Arc::clone(&subscriptions),
+            Arc::clone(&notify_shutdown),
+        );
+
         Self {
             chain_id: Arc::new(chain_id),
             kiso,
@@ -90,15 +125,39 @@ impl Torii {
             events,
             notify_shutdown,
             query_service,
+            query_cache,
+            query_quotas,
             kura,
             state,
+            admin: admin.map(Arc::new),
+            subscriptions,
+            webhooks: Arc::new(webhooks),
             #[cfg(feature = "telemetry")]
             metrics_reporter,
             address: config.address,
+            listener: None,
+            admin_listener: None,
+            role,
+            partition,
             transaction_max_content_len: config.max_content_len,
         }
     }
 
+    /// Serve the API on the already bound `listener` instead of binding to the configured address,
+    /// e.g. on one inherited from the service manager.
+    #[must_use]
+    pub fn with_listener(mut self, listener: TcpListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    /// Serve the administration API on the already bound `listener`, see [`Self::with_listener`].
+    #[must_use]
+    pub fn with_admin_listener(mut self, listener: TcpListener) -> Self {
+        self.admin_listener = Some(listener);
+        self
+    }
+
     /// Helper function to create router. This router can be tested without starting up an HTTP server
     #[allow(clippy::too_many_lines)]
     fn create_api_router(&self) -> axum::Router {
@@ -110,6 +169,13 @@ impl Torii {
                     let kiso = self.kiso.clone();
                     move || routing::handle_get_configuration(kiso)
                 }),
+            )
+            .route(
+                uri::TRANSACTION_VERSIONS,
+                get({
+                    let state = self.state.clone();
+                    move || routing::handle_transaction_versions(state)
+                }),
             );
 
         #[cfg(feature = "telemetry")]
@@ -171,10 +237,36 @@ impl Torii {
                 uri::TRANSACTION,
                 post({
                     let chain_id = self.chain_id.clone();
+                    let role = self.role;
+                    let partition = self.partition.clone();
                     let queue = self.queue.clone();
                     let state = self.state.clone();
                     move |ScaleVersioned(transaction): ScaleVersioned<_>| {
-                        routing::handle_transaction(chain_id, queue, state, transaction)
+                        routing::handle_transaction(
+                            chain_id,
+                            role,
+                            partition,
+                            queue,
+                            state,
+                            transaction,
+                        )
+                    }
+                })
+                .layer(DefaultBodyLimit::max(
+                    self.transaction_max_content_len
+                        .get()
+                        .try_into()
+                        .expect("should't exceed usize"),
+                )),
+            )
+            .route(
+                uri::TRANSACTION_SIMULATION,
+                post({
+                    let chain_id = self.chain_id.clone();
+                    let state = self.state.clone();
+                    move |axum::extract::Query(params): axum::extract::Query<_>,
+                          Scale(transaction): Scale<_>| {
+                        routing::handle_transaction_simulation(chain_id, state, params, transaction)
                     }
                 })
                 .layer(DefaultBodyLimit::max(
@@ -184,13 +276,44 @@ impl Torii {
                         .expect("should't exceed usize"),
                 )),
             )
+            .route(
+                uri::SCHEDULED_TRANSACTIONS,
+                post({
+                    let queue = self.queue.clone();
+                    move |Scale(authority): Scale<_>| {
+                        routing::handle_scheduled_transactions(queue, authority)
+                    }
+                }),
+            )
+            .route(
+                uri::CANCEL_TRANSACTION,
+                post({
+                    let role = self.role;
+                    let queue = self.queue.clone();
+                    move |Scale(cancellation): Scale<_>| {
+                        routing::handle_cancel_transaction(role, queue, cancellation)
+                    }
+                }),
+            )
             .route(
                 uri::QUERY,
                 post({
                     let query_service = self.query_service.clone();
+                    let query_cache = self.query_cache.clone();
+                    let query_quotas = self.query_quotas.clone();
+                    let partition = self.partition.clone();
                     let state = self.state.clone();
-                    move |ScaleVersioned(query_request): ScaleVersioned<_>| {
-                        routing::handle_queries(query_service, state, query_request)
+                    move |accept: Option<ExtractAccept>,
+                          ScaleVersioned(query_request): ScaleVersioned<_>| {
+                        routing::handle_queries(
+                            query_service,
+                            query_cache,
+                            query_quotas,
+                            partition,
+                            state,
+                            accept.map(|extract| extract.0),
+                            query_request,
+                        )
                     }
                 }),
             )
@@ -200,6 +323,16 @@ impl Torii {
                     let kiso = self.kiso.clone();
                     move |Json(config): Json<_>| routing::handle_post_configuration(kiso, config)
                 }),
+            )
+            .route(
+                uri::WEBHOOKS,
+                post({
+                    let webhooks = self.webhooks.clone();
+                    let state = self.state.clone();
+                    move |Scale(request): Scale<_>| {
+                        routing::handle_webhooks(webhooks, state, request)
+                    }
+                }),
             );
 
         let router = router
@@ -207,10 +340,17 @@ impl Torii {
                 uri::SUBSCRIPTION,
                 get({
                     let events = self.events.clone();
+                    let subscriptions = self.subscriptions.clone();
+                    let state = self.state.clone();
                     move |ws: WebSocketUpgrade| {
                         core::future::ready(ws.on_upgrade(|ws| async move {
-                            if let Err(error) =
-                                routing::subscription::handle_subscription(events, ws).await
+                            if let Err(error) = routing::subscription::handle_subscription(
+                                events,
+                                subscriptions,
+                                state,
+                                ws,
+                            )
+                            .await
                             {
                                 iroha_logger::error!(%error, "Failure during event streaming");
                             }
@@ -222,9 +362,13 @@ impl Torii {
                 uri::BLOCKS_STREAM,
                 post({
                     let kura = self.kura.clone();
+                    let state = self.state.clone();
+                    let subscriptions = self.subscriptions.clone();
                     move |ws: WebSocketUpgrade| {
                         core::future::ready(ws.on_upgrade(|ws| async move {
-                            if let Err(error) = routing::handle_blocks_stream(kura, ws).await {
+                            if let Err(error) =
+                                routing::handle_blocks_stream(kura, state, subscriptions, ws).await
+                            {
                                 iroha_logger::error!(%error, "Failure during block streaming");
                             }
                         }))
@@ -245,15 +389,16 @@ impl Torii {
     ///
     /// # Errors
     /// Can fail due to listening to network or if http server fails
-    async fn start_api(self: Arc<Self>) -> eyre::Result<Vec<task::JoinHandle<eyre::Result<()>>>> {
-        let torii_address = self.address.value();
+    async fn start_api(
+        self: Arc<Self>,
+        listener: Option<TcpListener>,
+    ) -> eyre::Result<Vec<task::JoinHandle<eyre::Result<()>>>> {
+        let listeners = match listener {
+            Some(listener) => vec![listener],
+            None => bind(self.address.value()).await?,
+        };
 
-        let handles = torii_address
-            .to_socket_addrs()?
-            .map(TcpListener::bind)
-            .collect::<FuturesUnordered<_>>()
-            .try_collect::<Vec<TcpListener>>()
-            .await?
+        let handles = listeners
             .into_iter()
             .map(|listener| {
                 let torii = Arc::clone(&self);
@@ -274,25 +419,70 @@ impl Torii {
         Ok(handles)
     }
 
+    /// Start the administration API on its own listener
+    async fn start_admin_api(
+        self: Arc<Self>,
+        admin: Arc<AdminApi>,
+        listener: Option<TcpListener>,
+    ) -> eyre::Result<Vec<task::JoinHandle<eyre::Result<()>>>> {
+        let listeners = match listener {
+            Some(listener) => vec![listener],
+            None => bind(admin.address()).await?,
+        };
+
+        let handles = listeners
+            .into_iter()
+            .map(|listener| {
+                let torii = Arc::clone(&self);
+                let admin_router = admin.create_router(&torii.subscriptions);
+
+                let signal = async move { torii.notify_shutdown.notified().await };
+
+                let serve_fut = async move {
+                    axum::serve(listener, admin_router)
+                        .with_graceful_shutdown(signal)
+                        .await
+                        .map_err(eyre::Report::from)
+                };
+                task::spawn(serve_fut)
+            })
+            .collect();
+
+        Ok(handles)
+    }
+
     /// To handle incoming requests `Torii` should be started first.
     ///
     /// # Errors
     /// Can fail due to listening to network or if http server fails
     #[iroha_futures::telemetry_future]
     pub async fn start(
-        self,
+        mut self,
     ) -> error_stack::Result<impl core::future::Future<Output = ()>, eyre::Report> {
+        let listener = self.listener.take();
+        let admin_listener = self.admin_listener.take();
         let torii = Arc::new(self);
         let mut handles = vec![];
 
         handles.extend(
             Arc::clone(&torii)
-                .start_api()
+                .start_api(listener)
                 .await
                 .into_report()
                 .map_err(|err| err.attach_printable(torii.address.clone().into_attachment()))?,
         );
 
+        if let Some(admin) = torii.admin.clone() {
+            iroha_logger::info!(address=%admin.address(), "Starting admin API");
+            handles.extend(
+                Arc::clone(&torii)
+                    .start_admin_api(Arc::clone(&admin), admin_listener)
+                    .await
+                    .into_report()
+                    .map_err(|err| err.attach_printable(admin.address().clone()))?,
+            );
+        }
+
         let run = handles
             .into_iter()
             .collect::<FuturesUnordered<_>>()
@@ -313,6 +503,16 @@ impl Torii {
     }
 }
 
+/// Bind to every address `address` resolves to
+async fn bind(address: &SocketAddr) -> std::io::Result<Vec<TcpListener>> {
+    address
+        .to_socket_addrs()?
+        .map(TcpListener::bind)
+        .collect::<FuturesUnordered<_>>()
+        .try_collect()
+        .await
+}
+
 /// Torii errors.
 #[derive(Debug, thiserror::Error, displaydoc::Display)]
 pub enum Error {
@@ -324,6 +524,18 @@ pub enum Error {
     Config(#[source] eyre::Report),
     /// Failed to push into queue
     PushIntoQueue(#[from] Box<queue::Error>),
+    /// Failed to cancel transaction: {0}
+    CancelTransaction(#[from] queue::CancelError),
+    /// Transactions of `{0}` are denied by this peer
+    AuthorityDenied(AccountId),
+    /// {0}
+    QueueFull(QueueFull),
+    /// Peer is a read replica and doesn't accept transactions
+    ReadReplica,
+    /// {0}
+    PeerDegraded(PeerDegraded),
+    /// Failed to handle webhook request: {0}
+    Webhook(#[from] webhook::Error),
     #[cfg(feature = "telemetry")]
     /// Failed to get Prometheus metrics
     Prometheus(#[source] eyre::Report),
@@ -337,12 +549,28 @@ pub enum Error {
     ConfigurationFailure(#[from] KisoError),
     /// Failed to find status segment by provided path
     StatusSegmentNotFound(#[source] eyre::Report),
+    /// None of the accepted formats of query responses is supported: `{0}`
+    NotAcceptable(String),
+    /// CSV is only available for the results of iterable queries
+    NotTabular,
 }
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         match self {
             Self::Query(err) => (Self::query_status_code(&err), utils::Scale(err)).into_response(),
+            Self::QueueFull(err) => {
+                let retry_after_secs = err.retry_after_ms.div_ceil(1000);
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                    utils::Scale(err),
+                )
+                    .into_response()
+            }
+            Self::PeerDegraded(err) => {
+                (StatusCode::SERVICE_UNAVAILABLE, utils::Scale(err)).into_response()
+            }
             _ => (self.status_code(), self.to_string()).into_response(),
         }
     }
@@ -356,15 +584,34 @@ impl Error {
             Query(e) => Self::query_status_code(e),
             AcceptTransaction(_) => StatusCode::BAD_REQUEST,
             Config(_) | StatusSegmentNotFound(_) => StatusCode::NOT_FOUND,
+            NotAcceptable(_) | NotTabular => StatusCode::NOT_ACCEPTABLE,
+            QueueFull(_) => StatusCode::TOO_MANY_REQUESTS,
+            PeerDegraded(_) => StatusCode::SERVICE_UNAVAILABLE,
             PushIntoQueue(err) => match **err {
                 queue::Error::Full => StatusCode::INTERNAL_SERVER_ERROR,
+                queue::Error::Closed => StatusCode::SERVICE_UNAVAILABLE,
                 _ => StatusCode::BAD_REQUEST,
             },
+            AuthorityDenied(_) | ReadReplica => StatusCode::FORBIDDEN,
+            CancelTransaction(err) => match err {
+                queue::CancelError::NotFound => StatusCode::NOT_FOUND,
+                queue::CancelError::InvalidSignature => StatusCode::FORBIDDEN,
+            },
             #[cfg(feature = "telemetry")]
             Prometheus(_) | StatusFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
             #[cfg(feature = "profiling")]
             Pprof(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ConfigurationFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Webhook(err) => match err {
+                webhook::Error::InvalidSignature(_) | webhook::Error::NotPermitted(_) => {
+                    StatusCode::FORBIDDEN
+                }
+                webhook::Error::NoFilters | webhook::Error::InvalidUrl(_) => {
+                    StatusCode::BAD_REQUEST
+                }
+                webhook::Error::TooManyWebhooks { .. } => StatusCode::TOO_MANY_REQUESTS,
+                webhook::Error::NotFound(_) => StatusCode::NOT_FOUND,
+            },
         }
     }
 
@@ -375,7 +622,7 @@ impl Error {
         };
 
         match validation_error {
-            NotPermitted(_) => StatusCode::FORBIDDEN,
+            NotPermitted(_) | DomainDenied(_) => StatusCode::FORBIDDEN,
             QueryFailed(query_error)
             | InstructionFailed(InstructionExecutionError::Query(query_error)) => match query_error
             {
@@ -383,7 +630,7 @@ impl Error {
                     StatusCode::BAD_REQUEST
                 }
                 Find(_) => StatusCode::NOT_FOUND,
-                CapacityLimit => StatusCode::TOO_MANY_REQUESTS,
+                CapacityLimit | QueryCostExceeded => StatusCode::TOO_MANY_REQUESTS,
             },
             TooComplex => StatusCode::UNPROCESSABLE_ENTITY,
             InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
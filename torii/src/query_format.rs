@@ -0,0 +1,305 @@
+//! Formats of query responses, negotiated with the `Accept` header.
+//!
+//! Queries are answered in SCALE unless the client asks for JSON or, for iterable queries, CSV.
+//! The batches of iterable queries are encoded into the response body element by element,
+//! so that the whole encoded batch isn't buffered in memory next to the batch itself.
+//!
+//! In CSV, every element of the batch is a row. Nested objects are flattened into columns
+//! named by the dotted path of the field, e.g. `id.definition`, and other nested values are
+//! written as JSON. The columns are those of the first element of the batch. The cursor to
+//! continue the query with is returned in the [`QUERY_CONTINUE_CURSOR`](header::QUERY_CONTINUE_CURSOR)
+//! header as JSON.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header::CONTENT_TYPE, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use iroha_data_model::query::{QueryOutput, QueryOutputBatchBox, QueryResponse};
+use iroha_torii_const::header;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{utils, Error};
+
+const JSON_MIME_TYPE: &str = "application/json";
+const CSV_MIME_TYPE: &str = "text/csv";
+
+/// Format of a query response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// SCALE, understood by the client library
+    Scale,
+    /// JSON, as the data model serializes the response
+    Json,
+    /// CSV, only for iterable queries
+    Csv,
+}
+
+impl Format {
+    /// The first of the formats listed in the `Accept` header which is supported,
+    /// SCALE if the header is missing.
+    ///
+    /// # Errors
+    /// If none of the listed formats is supported
+    pub fn negotiate(accept: Option<&HeaderValue>) -> Result<Self, Error> {
+        let Some(accept) = accept else {
+            return Ok(Self::Scale);
+        };
+        let accept = String::from_utf8_lossy(accept.as_bytes());
+
+        accept
+            .split(',')
+            .filter_map(|media_range| {
+                let mime = media_range.split(';').next().unwrap_or_default().trim();
+                match mime {
+                    "*/*" | "application/*" | utils::PARITY_SCALE_MIME_TYPE => Some(Self::Scale),
+                    JSON_MIME_TYPE => Some(Self::Json),
+                    "text/*" | CSV_MIME_TYPE => Some(Self::Csv),
+                    _ => None,
+                }
+            })
+            .next()
+            .ok_or_else(|| Error::NotAcceptable(accept.to_string()))
+    }
+
+    /// Encode `response` in this format
+    ///
+    /// # Errors
+    /// If the response is not tabular, but CSV is requested
+    pub fn encode(self, response: QueryResponse) -> Result<Response, Error> {
+        match (self, response) {
+            (Self::Scale, response) => Ok(utils::Scale(response).into_response()),
+            (Self::Json, QueryResponse::Singular(output)) => {
+                Ok(Json(QueryResponse::Singular(output)).into_response())
+            }
+            (Self::Json, QueryResponse::Iterable(output)) => {
+                Ok(streamed(JSON_MIME_TYPE, json_chunks(output)))
+            }
+            (Self::Csv, QueryResponse::Singular(_)) => Err(Error::NotTabular),
+            (Self::Csv, QueryResponse::Iterable(output)) => {
+                let (batch, continue_cursor) = output.into_parts();
+                let mut response = streamed(CSV_MIME_TYPE, csv_chunks(batch));
+                if let Some(cursor) = continue_cursor {
+                    let cursor = serde_json::to_string(&cursor)
+                        .expect("INTERNAL BUG: Failed to serialize the cursor");
+                    response.headers_mut().insert(
+                        header::QUERY_CONTINUE_CURSOR,
+                        HeaderValue::try_from(cursor)
+                            .expect("INTERNAL BUG: JSON of the cursor is not a valid header"),
+                    );
+                }
+                Ok(response)
+            }
+        }
+    }
+}
+
+type Rows = Box<dyn Iterator<Item = serde_json::Result<Value>> + Send>;
+type Chunks = Box<dyn Iterator<Item = serde_json::Result<Bytes>> + Send>;
+
+fn streamed(mime_type: &'static str, chunks: Chunks) -> Response {
+    let mut response = Body::from_stream(futures::stream::iter(chunks)).into_response();
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(mime_type));
+    response
+}
+
+/// Name of the variant of `batch` and its elements
+fn rows(batch: QueryOutputBatchBox) -> (&'static str, Rows) {
+    fn erase<T: Serialize + Send + 'static>(items: Vec<T>) -> Rows {
+        Box::new(items.into_iter().map(serde_json::to_value))
+    }
+
+    macro_rules! rows {
+        ($($variant:ident),* $(,)?) => {
+            match batch {
+                $(QueryOutputBatchBox::$variant(items) => (stringify!($variant), erase(items)),)*
+            }
+        };
+    }
+
+    rows!(
+        Domain,
+        Account,
+        Asset,
+        AssetDefinition,
+        Role,
+        Parameter,
+        Permission,
+        Transaction,
+        Peer,
+        RoleId,
+        TriggerId,
+        Block,
+        BlockHeader,
+        ArchivedEvent,
+        StoreEntry,
+        TriggerDetails,
+    )
+}
+
+/// The same JSON as the one of the whole [`QueryResponse::Iterable`]
+fn json_chunks(output: QueryOutput) -> Chunks {
+    let (batch, continue_cursor) = output.into_parts();
+    let (variant, rows) = rows(batch);
+
+    let head = format!(r#"{{"Iterable":{{"batch":{{"{variant}":["#);
+    let tail = serde_json::to_string(&continue_cursor)
+        .map(|cursor| format!(r#"]}},"continue_cursor":{cursor}}}}}"#));
+    let rows = rows
+        .enumerate()
+        .map(|(i, row)| -> serde_json::Result<Bytes> {
+            let mut chunk = if i == 0 { Vec::new() } else { vec![b','] };
+            serde_json::to_writer(&mut chunk, &row?)?;
+            Ok(chunk.into())
+        });
+
+    Box::new(
+        core::iter::once(Ok(Bytes::from(head)))
+            .chain(rows)
+            .chain(core::iter::once(tail.map(Bytes::from))),
+    )
+}
+
+fn csv_chunks(batch: QueryOutputBatchBox) -> Chunks {
+    let (_variant, rows) = rows(batch);
+    let mut rows = rows.map(|row| row.map(|row| flatten(&row)));
+
+    let first = match rows.next() {
+        None => return Box::new(core::iter::empty()),
+        Some(Err(error)) => return Box::new(core::iter::once(Err(error))),
+        Some(Ok(first)) => first,
+    };
+    let columns = first.keys().cloned().collect::<Vec<_>>();
+
+    let header = csv_line(columns.iter().map(String::as_str));
+    let rows =
+        core::iter::once(Ok(first))
+            .chain(rows)
+            .map(move |row| -> serde_json::Result<Bytes> {
+                let row = row?;
+                Ok(csv_line(
+                    columns
+                        .iter()
+                        .map(|column| row.get(column).map_or("", String::as_str)),
+                ))
+            });
+
+    Box::new(core::iter::once(Ok(header)).chain(rows))
+}
+
+/// Fields of `row` by their dotted path; a row which is not an object is a single `value` field
+fn flatten(row: &Value) -> BTreeMap<String, String> {
+    fn flatten_into(prefix: Option<&str>, value: &Value, fields: &mut BTreeMap<String, String>) {
+        match value {
+            Value::Object(object) if !object.is_empty() => {
+                for (key, value) in object {
+                    let path =
+                        prefix.map_or_else(|| key.clone(), |prefix| format!("{prefix}.{key}"));
+                    flatten_into(Some(&path), value, fields);
+                }
+            }
+            value => {
+                let cell = match value {
+                    Value::Null => String::new(),
+                    Value::String(string) => string.clone(),
+                    value => value.to_string(),
+                };
+                fields.insert(prefix.unwrap_or("value").to_owned(), cell);
+            }
+        }
+    }
+
+    let mut fields = BTreeMap::new();
+    flatten_into(None, row, &mut fields);
+    fields
+}
+
+/// Line of the comma separated `cells`, quoted as RFC 4180 prescribes
+fn csv_line<'cell>(cells: impl Iterator<Item = &'cell str>) -> Bytes {
+    let mut line = String::new();
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            line.push(',');
+        }
+        if cell.contains([',', '"', '\r', '\n']) {
+            line.push('"');
+            line.push_str(&cell.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(cell);
+        }
+    }
+    line.push_str("\r\n");
+    line.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    fn concat(chunks: Chunks) -> String {
+        let bytes = chunks
+            .map(|chunk| chunk.unwrap().to_vec())
+            .collect::<Vec<_>>()
+            .concat();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn format_is_negotiated_in_order_of_preference() {
+        let negotiate =
+            |accept: &'static str| Format::negotiate(Some(&HeaderValue::from_static(accept)));
+
+        assert_eq!(Format::negotiate(None).unwrap(), Format::Scale);
+        assert_eq!(negotiate("*/*").unwrap(), Format::Scale);
+        assert_eq!(
+            negotiate("text/html, text/csv;q=0.9, application/json").unwrap(),
+            Format::Csv
+        );
+        assert_eq!(negotiate("application/json").unwrap(), Format::Json);
+        assert!(matches!(
+            negotiate("text/html"),
+            Err(Error::NotAcceptable(accept)) if accept == "text/html"
+        ));
+    }
+
+    #[test]
+    fn streamed_json_is_the_json_of_the_response() {
+        let roles = ["alice", "bob"]
+            .into_iter()
+            .map(|name| name.parse::<RoleId>().unwrap())
+            .collect::<Vec<_>>();
+        let output = QueryOutput::new(QueryOutputBatchBox::RoleId(roles), None);
+
+        let streamed: Value = serde_json::from_str(&concat(json_chunks(output.clone()))).unwrap();
+        assert_eq!(
+            streamed,
+            serde_json::to_value(QueryResponse::Iterable(output)).unwrap()
+        );
+    }
+
+    #[test]
+    fn csv_rows_are_flattened_by_the_columns_of_the_first_row() {
+        let first = flatten(&json!({"id": "rose##alice@wonderland", "value": {"Numeric": "13"}}));
+        assert_eq!(first.keys().collect::<Vec<_>>(), ["id", "value.Numeric"]);
+
+        let header = csv_line(first.keys().map(String::as_str));
+        assert_eq!(header, "id,value.Numeric\r\n");
+        assert_eq!(
+            csv_line(["a,b", "say \"hi\"", ""].into_iter()),
+            "\"a,b\",\"say \"\"hi\"\"\",\r\n"
+        );
+
+        let batch = QueryOutputBatchBox::RoleId(vec!["alice".parse().unwrap()]);
+        assert_eq!(concat(csv_chunks(batch)), "value\r\nalice\r\n");
+    }
+}
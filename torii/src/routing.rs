@@ -2,58 +2,211 @@
 //! Iroha you should add it here by creating a `handle_*` function,
 //! and add it to impl Torii.
 
-use axum::extract::ws::WebSocket;
+use std::num::NonZeroU64;
+
+use axum::{
+    extract::ws::WebSocket,
+    http::{HeaderMap, HeaderValue},
+};
 #[cfg(feature = "telemetry")]
 use eyre::{eyre, WrapErr};
 use futures::TryStreamExt;
 use iroha_config::client_api::ConfigDTO;
-use iroha_core::{query::store::LiveQueryStoreHandle, smartcontracts::query::ValidQueryRequest};
+use iroha_core::{
+    query::{cache::QueryCacheHandle, quota::QueryQuotas, store::LiveQueryStoreHandle},
+    smartcontracts::query::ValidQueryRequest,
+    tx::{AcceptTransactionFail, TransactionExecutor},
+};
 use iroha_data_model::{
     block::{
         stream::{BlockMessage, BlockSubscriptionRequest},
         SignedBlock,
     },
+    isi::error::Mismatch,
     prelude::*,
-    query::{QueryRequestWithAuthority, QueryResponse, SignedQuery},
+    query::{error::QueryExecutionFail, QueryRequestWithAuthority, SignedQuery},
 };
 #[cfg(feature = "telemetry")]
 use iroha_telemetry::metrics::Status;
+use iroha_torii_const::header;
 use stream::StreamMessage as _;
 use tokio::task;
 
 use super::*;
-use crate::stream::{Sink, Stream};
+use crate::{
+    query_format,
+    stream::{Sink, Stream},
+    subscriptions::{SubscriptionKind, Subscriptions},
+};
 
 #[iroha_futures::telemetry_future]
 pub async fn handle_transaction(
     chain_id: Arc<ChainId>,
+    role: Role,
+    partition: PartitionStatus,
     queue: Arc<Queue>,
     state: Arc<State>,
     transaction: SignedTransaction,
-) -> Result<()> {
+) -> Result<Scale<SubmissionStatus>> {
+    if role == Role::ReadReplica {
+        return Err(Error::ReadReplica);
+    }
+    // Transactions can't be committed without the quorum anyway
+    if let Some(degraded) = partition.degraded() {
+        return Err(Error::PeerDegraded(degraded));
+    }
+
     let state_view = state.view();
+    // Submitting the same transaction again only reports what has become of it
+    let hash = transaction.hash();
+    if let Some(status) = known_submission_status(&queue, &state_view, &hash) {
+        iroha_logger::debug!(tx_hash=%hash, ?status, "Transaction is already known");
+        return Ok(Scale(status));
+    }
+
     let transaction_limits = state_view.world().parameters().transaction;
     let transaction = AcceptedTransaction::accept(transaction, &chain_id, transaction_limits)
         .map_err(Error::AcceptTransaction)?;
+    transaction
+        .check_version(transaction_limits, state_view.height() as u64 + 1)
+        .map_err(|err| Error::AcceptTransaction(AcceptTransactionFail::TransactionLimit(err)))?;
     queue
         .push(transaction, &state_view)
-        .map_err(|queue::Failure { tx, err }| {
+        .map(|()| Scale(SubmissionStatus::Accepted))
+        .or_else(|queue::Failure { tx, err }| {
+            // The same transaction might have been pushed concurrently
+            if matches!(err, queue::Error::IsInQueue | queue::Error::InBlockchain) {
+                if let Some(status) = known_submission_status(&queue, &state_view, &hash) {
+                    return Ok(Scale(status));
+                }
+            }
+
             iroha_logger::warn!(
                 tx_hash=%tx.as_ref().hash(), ?err,
                 "Failed to push into queue"
             );
 
-            Box::new(err)
+            Err(match err {
+                queue::Error::AuthorityDenied => {
+                    Error::AuthorityDenied(tx.as_ref().authority().clone())
+                }
+                queue::Error::Full => {
+                    // Transactions leave the queue once the next block is committed
+                    let sumeragi = state_view.world().parameters().sumeragi;
+                    let retry_after = sumeragi.block_time() + sumeragi.commit_time();
+                    Error::QueueFull(QueueFull {
+                        retry_after_ms: retry_after.as_millis().try_into().unwrap_or(u64::MAX),
+                        queue_len: queue.tx_len() as u64,
+                    })
+                }
+                err => Error::PushIntoQueue(Box::new(err)),
+            })
         })
-        .map_err(Error::PushIntoQueue)
+}
+
+/// Status of the transaction with the given `hash` if it is in the queue or in the blockchain
+fn known_submission_status(
+    queue: &Queue,
+    state_view: &StateView,
+    hash: &HashOf<SignedTransaction>,
+) -> Option<SubmissionStatus> {
+    let Some((height, transaction)) = state_view.committed_transaction(hash) else {
+        return queue.contains(hash).then_some(SubmissionStatus::Queued);
+    };
+    let height = NonZeroU64::try_from(height).expect("Block height should fit into u64");
+
+    Some(match transaction.error {
+        None => SubmissionStatus::Committed(height),
+        Some(reason) => SubmissionStatus::Rejected(RejectedSubmission { height, reason }),
+    })
+}
+
+/// Query params of the transaction simulation
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct SimulationParams {
+    /// Record every executed instruction into [`TransactionSimulation::trace`]
+    #[serde(default)]
+    trace: bool,
+}
+
+#[iroha_futures::telemetry_future]
+pub async fn handle_transaction_simulation(
+    chain_id: Arc<ChainId>,
+    state: Arc<State>,
+    SimulationParams { trace }: SimulationParams,
+    transaction: TransactionBuilder,
+) -> Result<Scale<TransactionSimulation>> {
+    // Signatures aren't verified during simulation
+    let transaction = transaction.sign(KeyPair::random().private_key());
+    if transaction.chain() != &*chain_id {
+        return Err(Error::AcceptTransaction(
+            AcceptTransactionFail::ChainIdMismatch(Mismatch {
+                expected: (*chain_id).clone(),
+                actual: transaction.chain().clone(),
+            }),
+        ));
+    }
+
+    let simulation = task::spawn_blocking(move || {
+        let transaction_limits = state.view().world().parameters().transaction;
+        TransactionExecutor::new(transaction_limits).simulate(transaction, &state, trace)
+    })
+    .await
+    .expect("Failed to join transaction simulation task");
+
+    Ok(Scale(simulation))
+}
+
+#[iroha_futures::telemetry_future]
+pub async fn handle_scheduled_transactions(
+    queue: Arc<Queue>,
+    authority: AccountId,
+) -> Result<Scale<Vec<SignedTransaction>>> {
+    let transactions = queue
+        .scheduled_transactions(&authority)
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Scale(transactions))
+}
+
+#[iroha_futures::telemetry_future]
+pub async fn handle_webhooks(
+    webhooks: Arc<Webhooks>,
+    state: Arc<State>,
+    request: SignedWebhookRequest,
+) -> Result<Scale<WebhookStatus>> {
+    let status = webhooks.handle(request, &state.view())?;
+    Ok(Scale(status))
+}
+
+#[iroha_futures::telemetry_future]
+pub async fn handle_cancel_transaction(
+    role: Role,
+    queue: Arc<Queue>,
+    cancellation: SignedCancelTransaction,
+) -> Result<()> {
+    if role == Role::ReadReplica {
+        return Err(Error::ReadReplica);
+    }
+
+    queue.cancel(cancellation).map_err(Error::CancelTransaction)
 }
 
 #[iroha_futures::telemetry_future]
 pub async fn handle_queries(
     live_query_store: LiveQueryStoreHandle,
+    query_cache: Option<QueryCacheHandle>,
+    query_quotas: Option<QueryQuotas>,
+    partition: PartitionStatus,
     state: Arc<State>,
+    accept: Option<HeaderValue>,
     query: SignedQuery,
-) -> Result<Scale<QueryResponse>> {
+) -> Result<(HeaderMap, Response)> {
+    // Negotiated before the query is executed, so that its cost isn't charged in vain
+    let format = query_format::Format::negotiate(accept.as_ref())?;
+
     let handle = task::spawn_blocking(move || {
         let state_view = state.view();
 
@@ -62,15 +215,45 @@ pub async fn handle_queries(
         let authority = query.authority.clone();
 
         let valid_query = ValidQueryRequest::validate_for_client(query, &state_view)?;
-        let response = valid_query.execute(&live_query_store, &state_view, &authority)?;
+        let budget = query_quotas
+            .as_ref()
+            .map(|quotas| quotas.remaining(&authority));
+        let result = valid_query.execute_metered(
+            query_cache.as_ref(),
+            &live_query_store,
+            &state_view,
+            &authority,
+            budget,
+        );
+
+        let mut headers = HeaderMap::new();
+        if let (Some(quotas), Some(budget)) = (&query_quotas, budget) {
+            let cost = match &result {
+                Ok((_, cost)) => *cost,
+                // The query has visited as many entities as the account could afford
+                Err(QueryExecutionFail::QueryCostExceeded) => budget,
+                Err(_) => 0,
+            };
+            quotas.charge(&authority, cost);
+            headers.insert(
+                header::QUERY_BUDGET_REMAINING,
+                HeaderValue::from(budget.saturating_sub(cost)),
+            );
+        }
+        let (response, cost) = result?;
+        headers.insert(header::QUERY_COST, HeaderValue::from(cost));
+        if let Some(degraded) = partition.degraded() {
+            headers.insert(
+                header::STALE_FOR_MS,
+                HeaderValue::from(degraded.partitioned_for_ms),
+            );
+        }
 
-        Ok::<_, ValidationFail>(response)
+        Ok::<_, ValidationFail>((headers, response))
     });
-    handle
-        .await
-        .expect("Failed to join query handling task")
-        .map(Scale)
-        .map_err(Into::into)
+    let (headers, response) = handle.await.expect("Failed to join query handling task")?;
+
+    Ok((headers, format.encode(response)?))
 }
 
 pub async fn handle_health() -> &'static str {
@@ -99,9 +282,15 @@ pub async fn handle_post_configuration(
 }
 
 #[iroha_futures::telemetry_future]
-pub async fn handle_blocks_stream(kura: Arc<Kura>, mut stream: WebSocket) -> eyre::Result<()> {
+pub async fn handle_blocks_stream(
+    kura: Arc<Kura>,
+    state: Arc<State>,
+    subscriptions: Arc<Subscriptions>,
+    mut stream: WebSocket,
+) -> eyre::Result<()> {
     let BlockSubscriptionRequest(mut from_height) =
         Stream::<BlockSubscriptionRequest>::recv(&mut stream).await?;
+    let subscription = subscriptions.register(SubscriptionKind::Blocks { from_height });
 
     let mut interval = tokio::time::interval(std::time::Duration::from_millis(10));
     loop {
@@ -125,13 +314,20 @@ pub async fn handle_blocks_stream(kura: Arc<Kura>, mut stream: WebSocket) -> eyr
                     Err(err) => return Err(err)
                 }
             }
+            () = subscription.terminated() => {
+                iroha_logger::info!(id = subscription.id(), "Block subscription terminated");
+                return stream.close().await.map_err(Into::into);
+            }
             // This branch sends blocks
             _ = interval.tick() => {
                 if let Some(block) = kura.get_block_by_height(from_height.try_into().expect("INTERNAL BUG: Number of blocks exceeds usize::MAX")) {
                     // TODO: to avoid clone `BlockMessage` could be split into sending and receiving parts
                     Sink::<BlockMessage>::send(&mut stream, BlockMessage(SignedBlock::clone(&block))).await?;
+                    subscription.delivered();
                     from_height = from_height.checked_add(1).expect("Maximum block height is achieved.");
                 }
+                let height = state.view().height() as u64;
+                subscription.set_backlog((height + 1).saturating_sub(from_height.get()));
             }
             // Else branch to prevent panic i.e. I don't know what
             // this does.
@@ -144,7 +340,7 @@ pub mod subscription {
     //! Contains the `handle_subscription` functions and used for general routing.
 
     use super::*;
-    use crate::event;
+    use crate::{event, subscriptions::SubscriptionHandle};
 
     /// Type for any error during subscription handling
     #[derive(Debug, displaydoc::Display, thiserror::Error)]
@@ -157,6 +353,8 @@ pub mod subscription {
         WebSocket(#[from] axum::Error),
         /// A `Close` message is received. Not strictly an Error
         CloseMessage,
+        /// Subscription is terminated by the operator. Not strictly an Error
+        Terminated,
     }
 
     impl From<event::Error> for Error {
@@ -177,13 +375,24 @@ pub mod subscription {
     /// Handle subscription request
     ///
     /// Subscribes `stream` for `events` filtered by filter that is
-    /// received through the `stream`
+    /// received through the `stream`, if the subscriber is authorized to receive them
     #[iroha_futures::telemetry_future]
-    pub async fn handle_subscription(events: EventsSender, stream: WebSocket) -> eyre::Result<()> {
-        let mut consumer = event::Consumer::new(stream).await?;
-
-        match subscribe_forever(events, &mut consumer).await {
-            Ok(()) | Err(Error::CloseMessage) => consumer.close_stream().await.map_err(Into::into),
+    pub async fn handle_subscription(
+        events: EventsSender,
+        subscriptions: Arc<Subscriptions>,
+        state: Arc<State>,
+        stream: WebSocket,
+    ) -> eyre::Result<()> {
+        let mut consumer = event::Consumer::new(stream, &state).await?;
+        let subscription = subscriptions.register(SubscriptionKind::Events {
+            authority: consumer.authority().clone(),
+            filters: consumer.filters().to_vec(),
+        });
+
+        match subscribe_forever(events, &mut consumer, &subscription).await {
+            Ok(()) | Err(Error::CloseMessage | Error::Terminated) => {
+                consumer.close_stream().await.map_err(Into::into)
+            }
             Err(err) => Err(err.into()),
         }
     }
@@ -192,7 +401,11 @@ pub mod subscription {
     ///
     /// Ideally should return `Result<!>` cause it either runs forever
     /// either returns `Err` variant
-    async fn subscribe_forever(events: EventsSender, consumer: &mut event::Consumer) -> Result<()> {
+    async fn subscribe_forever(
+        events: EventsSender,
+        consumer: &mut event::Consumer,
+        subscription: &SubscriptionHandle,
+    ) -> Result<()> {
         let mut events = events.subscribe();
 
         loop {
@@ -204,11 +417,18 @@ pub mod subscription {
                         Err(err) => return Err(err.into())
                     }
                 }
+                () = subscription.terminated() => {
+                    iroha_logger::info!(id = subscription.id(), "Event subscription terminated");
+                    return Err(Error::Terminated);
+                }
                 // This branch catches and sends events
                 event = events.recv() => {
                     let event = event?;
                     iroha_logger::trace!(?event);
-                    consumer.consume(event).await?;
+                    if consumer.consume(event).await? {
+                        subscription.delivered();
+                    }
+                    subscription.set_backlog(events.len() as u64);
                 }
                 // Else branch to prevent panic
                 else => ()
@@ -217,6 +437,19 @@ pub mod subscription {
     }
 }
 
+#[iroha_futures::telemetry_future]
+pub async fn handle_transaction_versions(state: Arc<State>) -> Json<Vec<TransactionVersion>> {
+    let state_view = state.view();
+    let transaction_limits = state_view.world().parameters().transaction;
+    let next_height = state_view.height() as u64 + 1;
+    let versions = [TransactionVersion::V1, TransactionVersion::V2]
+        .into_iter()
+        .filter(|version| transaction_limits.accepts_version(*version, next_height))
+        .collect();
+
+    Json(versions)
+}
+
 #[iroha_futures::telemetry_future]
 #[cfg(feature = "telemetry")]
 pub async fn handle_version(state: Arc<State>) -> String {
@@ -7,7 +7,6 @@ use core::{result::Result, time::Duration};
 use axum::extract::ws::Message;
 use futures::{SinkExt, StreamExt};
 use iroha_version::prelude::*;
-use parity_scale_codec::DecodeAll;
 
 #[cfg(test)]
 const TIMEOUT: Duration = Duration::from_millis(10_000);
@@ -77,7 +76,7 @@ where
 
 /// Trait for reading custom messages from stream
 #[async_trait::async_trait]
-pub trait Stream<R: DecodeAll>:
+pub trait Stream<R: Decode>:
     StreamExt<Item = std::result::Result<Self::Message, Self::Err>> + Unpin
 {
     /// Error type returned by the stream
@@ -99,7 +98,7 @@ pub trait Stream<R: DecodeAll>:
         }
 
         if let Some(binary) = subscription_request_message.try_binary() {
-            Ok(R::decode_all(&mut binary.as_slice())?)
+            Ok(decode_all_with_limits(&binary, DecodeLimits::DEFAULT)?)
         } else {
             Err(Error::NonBinaryMessage)
         }
@@ -134,7 +133,7 @@ where
 }
 
 #[async_trait::async_trait]
-impl<M: DecodeAll> Stream<M> for axum::extract::ws::WebSocket {
+impl<M: Decode> Stream<M> for axum::extract::ws::WebSocket {
     type Err = axum::Error;
     type Message = axum::extract::ws::Message;
 }
@@ -0,0 +1,198 @@
+//! Registry of the event and block stream subscriptions and the webhooks served by Torii.
+//!
+//! Lets peer operators see who is subscribed to what and terminate
+//! subscriptions through the [administration API](crate::admin).
+
+use std::{
+    collections::BTreeMap,
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use iroha_data_model::{account::AccountId, events::EventFilterBox};
+use serde::Serialize;
+use tokio::sync::Notify;
+
+/// Identifier of a subscription, unique within the lifetime of the peer
+pub type SubscriptionId = u64;
+
+/// What the subscriber is receiving
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionKind {
+    /// Events matching any of the filters
+    Events {
+        /// Account which requested the subscription
+        authority: AccountId,
+        /// Filters requested by the subscriber
+        filters: Vec<EventFilterBox>,
+    },
+    /// Committed blocks
+    Blocks {
+        /// Height of the first requested block
+        from_height: NonZeroU64,
+    },
+    /// Events matching any of the filters posted to a webhook
+    Webhook {
+        /// Account which registered the webhook
+        authority: AccountId,
+        /// Filters of the webhook
+        filters: Vec<EventFilterBox>,
+        /// URL the events are posted to
+        url: String,
+    },
+}
+
+/// Snapshot of an active subscription
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionInfo {
+    /// Identifier to terminate the subscription with
+    pub id: SubscriptionId,
+    /// What the subscriber is receiving
+    pub kind: SubscriptionKind,
+    /// Time since the subscription was established
+    pub age_ms: u64,
+    /// Number of events or blocks sent to the subscriber
+    pub delivered: u64,
+    /// Number of events or blocks waiting to be sent to the subscriber
+    pub backlog: u64,
+}
+
+/// Active subscriptions of the peer
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    next_id: AtomicU64,
+    active: Mutex<BTreeMap<SubscriptionId, Arc<Subscription>>>,
+}
+
+#[derive(Debug)]
+struct Subscription {
+    kind: SubscriptionKind,
+    started_at: Instant,
+    delivered: AtomicU64,
+    backlog: AtomicU64,
+    terminate: Notify,
+}
+
+/// Handle of a registered subscription held by the stream handler.
+///
+/// The subscription is unregistered once the handle is dropped.
+#[derive(Debug)]
+pub struct SubscriptionHandle {
+    id: SubscriptionId,
+    subscription: Arc<Subscription>,
+    registry: Arc<Subscriptions>,
+}
+
+impl Subscriptions {
+    /// Register a new subscription of the given `kind`
+    pub fn register(self: &Arc<Self>, kind: SubscriptionKind) -> SubscriptionHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let subscription = Arc::new(Subscription {
+            kind,
+            started_at: Instant::now(),
+            delivered: AtomicU64::new(0),
+            backlog: AtomicU64::new(0),
+            terminate: Notify::new(),
+        });
+        self.lock().insert(id, Arc::clone(&subscription));
+
+        SubscriptionHandle {
+            id,
+            subscription,
+            registry: Arc::clone(self),
+        }
+    }
+
+    /// List the active subscriptions ordered by their ids
+    pub fn list(&self) -> Vec<SubscriptionInfo> {
+        self.lock()
+            .iter()
+            .map(|(id, subscription)| SubscriptionInfo {
+                id: *id,
+                kind: subscription.kind.clone(),
+                age_ms: subscription
+                    .started_at
+                    .elapsed()
+                    .as_millis()
+                    .try_into()
+                    .unwrap_or(u64::MAX),
+                delivered: subscription.delivered.load(Ordering::Relaxed),
+                backlog: subscription.backlog.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Ask the handler of the subscription `id` to close the stream.
+    ///
+    /// Returns `false` if there is no such subscription.
+    pub fn terminate(&self, id: SubscriptionId) -> bool {
+        self.lock()
+            .get(&id)
+            .map(|subscription| subscription.terminate.notify_one())
+            .is_some()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<SubscriptionId, Arc<Subscription>>> {
+        self.active
+            .lock()
+            .expect("INTERNAL BUG: subscriptions lock is poisoned")
+    }
+}
+
+impl SubscriptionHandle {
+    /// Identifier of the subscription
+    pub fn id(&self) -> SubscriptionId {
+        self.id
+    }
+
+    /// Record that an event or a block was sent to the subscriber
+    pub fn delivered(&self) {
+        self.subscription.delivered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the number of events or blocks waiting to be sent to the subscriber
+    pub fn set_backlog(&self, backlog: u64) {
+        self.subscription.backlog.store(backlog, Ordering::Relaxed);
+    }
+
+    /// Resolves once the subscription is terminated by the operator
+    pub async fn terminated(&self) {
+        self.subscription.terminate.notified().await;
+    }
+}
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_is_listed_until_dropped() {
+        let subscriptions = Arc::new(Subscriptions::default());
+        let handle = subscriptions.register(SubscriptionKind::Blocks {
+            from_height: nonzero_ext::nonzero!(1_u64),
+        });
+        handle.delivered();
+        handle.set_backlog(3);
+
+        let [info] = subscriptions.list().try_into().unwrap();
+        assert_eq!(info.id, handle.id());
+        assert_eq!((info.delivered, info.backlog), (1, 3));
+
+        assert!(subscriptions.terminate(handle.id()));
+
+        drop(handle);
+        assert!(subscriptions.list().is_empty());
+        assert!(!subscriptions.terminate(0));
+    }
+}
@@ -42,7 +42,7 @@ pub mod extractors {
     where
         Bytes: FromRequest<S>,
         S: Send + Sync,
-        T: DecodeVersioned,
+        T: DecodeVersioned + Decode,
     {
         type Rejection = Response;
 
@@ -51,7 +51,7 @@ pub mod extractors {
                 .await
                 .map_err(IntoResponse::into_response)?;
 
-            T::decode_all_versioned(&body)
+            T::decode_all_versioned_with_limits(&body, DecodeLimits::DEFAULT)
                 .map(ScaleVersioned)
                 .map_err(|err| {
                     (
@@ -63,6 +63,32 @@ pub mod extractors {
         }
     }
 
+    #[async_trait]
+    impl<S, T> FromRequest<S> for Scale<T>
+    where
+        Bytes: FromRequest<S>,
+        S: Send + Sync,
+        T: Decode,
+    {
+        type Rejection = Response;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let body = Bytes::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            iroha_version::scale::decode_all_with_limits(&body, DecodeLimits::DEFAULT)
+                .map(Scale)
+                .map_err(|err| {
+                    (
+                        axum::http::StatusCode::BAD_REQUEST,
+                        format!("Could not decode request: {err}"),
+                    )
+                        .into_response()
+                })
+        }
+    }
+
     /// Extractor of Accept header
     pub struct ExtractAccept(pub HeaderValue);
 
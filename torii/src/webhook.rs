@@ -0,0 +1,363 @@
+//! Webhooks notifying external services about events.
+//!
+//! An account registers a webhook with a [`SignedWebhookRequest`] listing the event filters,
+//! the URL and a secret. Events matching any of the filters are posted to the URL as JSON,
+//! with the hex-encoded HMAC-SHA256 of the body keyed by the secret in the [`SIGNATURE_HEADER`]
+//! header. Failed deliveries are retried with exponential backoff and the event is dropped once
+//! the retries are exhausted.
+//!
+//! Webhooks are only kept in memory. They are listed among the [subscriptions](crate::subscriptions)
+//! of the peer and have to be registered again after the peer restarts.
+
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac as _};
+use iroha_config::{event_sink::EventFormat, parameters::actual::Torii as Config};
+use iroha_core::{
+    event_sink::{self, EventSink},
+    state::StateReadOnly,
+    EventsSender,
+};
+use iroha_data_model::{account::AccountId, events::prelude::*};
+use sha2::Sha256;
+use tokio::sync::{broadcast, Notify};
+
+use crate::{
+    event,
+    subscriptions::{SubscriptionHandle, SubscriptionKind, Subscriptions},
+};
+
+/// Header carrying the hex-encoded HMAC-SHA256 of the notification body
+pub const SIGNATURE_HEADER: &str = "x-iroha-webhook-signature";
+
+/// Timeout of a single delivery attempt
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Error of a webhook request
+#[derive(Debug, displaydoc::Display, thiserror::Error)]
+pub enum Error {
+    /// Webhook request isn't signed by `{0}`
+    InvalidSignature(AccountId),
+    /// {0}
+    NotPermitted(#[from] event::Error),
+    /// Webhook has to have at least one filter
+    NoFilters,
+    /// `{0}` isn't an HTTP(S) URL
+    InvalidUrl(String),
+    /// `{authority}` already has {max} webhooks registered
+    TooManyWebhooks {
+        /// Authority of the request
+        authority: AccountId,
+        /// Maximum number of webhooks of a single account
+        max: usize,
+    },
+    /// Webhook {0} isn't registered by the authority of the request
+    NotFound(WebhookId),
+}
+
+/// Webhooks registered on the peer
+pub struct Webhooks {
+    per_account: NonZeroUsize,
+    max_retries: u32,
+    retry_delay: Duration,
+    events: EventsSender,
+    subscriptions: Arc<Subscriptions>,
+    notify_shutdown: Arc<Notify>,
+    agent: ureq::Agent,
+    registered: Arc<Mutex<BTreeMap<WebhookId, Arc<Delivery>>>>,
+}
+
+/// Registered webhook and the status of the deliveries to it
+#[derive(Debug)]
+struct Delivery {
+    authority: AccountId,
+    url: String,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    pending: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Webhooks {
+    /// Construct [`Webhooks`] notifying about the events sent to `events`
+    pub fn new(
+        config: &Config,
+        events: EventsSender,
+        subscriptions: Arc<Subscriptions>,
+        notify_shutdown: Arc<Notify>,
+    ) -> Self {
+        Self {
+            per_account: config.webhooks_per_account,
+            max_retries: config.webhook_max_retries,
+            retry_delay: config.webhook_retry_delay,
+            events,
+            subscriptions,
+            notify_shutdown,
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            registered: Arc::default(),
+        }
+    }
+
+    /// Perform the action of the signed `request`
+    ///
+    /// # Errors
+    /// - If the request isn't signed by its authority
+    /// - If the webhook to register is invalid or notifies about events the authority may not
+    ///   subscribe to, see [`event::authorize_filters`]
+    /// - If the authority has already registered the maximum number of webhooks
+    /// - If the requested webhook isn't registered by the authority
+    pub fn handle(
+        &self,
+        request: SignedWebhookRequest,
+        state: &impl StateReadOnly,
+    ) -> Result<WebhookStatus, Error> {
+        request
+            .verify()
+            .map_err(|_| Error::InvalidSignature(request.payload.authority.clone()))?;
+
+        let WebhookRequest { authority, action } = request.payload;
+        match action {
+            WebhookAction::Register(registration) => self.register(authority, registration, state),
+            WebhookAction::Status(id) => Ok(self.find(&authority, id)?.status(id)),
+            WebhookAction::Unregister(id) => {
+                let delivery = self.find(&authority, id)?;
+                lock(&self.registered).remove(&id);
+                self.subscriptions.terminate(id);
+                Ok(delivery.status(id))
+            }
+        }
+    }
+
+    fn register(
+        &self,
+        authority: AccountId,
+        WebhookRegistration {
+            filters,
+            url,
+            secret,
+        }: WebhookRegistration,
+        state: &impl StateReadOnly,
+    ) -> Result<WebhookStatus, Error> {
+        if filters.is_empty() {
+            return Err(Error::NoFilters);
+        }
+        event::authorize_filters(&authority, &filters, state)?;
+        if !url::Url::parse(&url).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+            return Err(Error::InvalidUrl(url));
+        }
+
+        let mut registered = lock(&self.registered);
+        let count = registered
+            .values()
+            .filter(|delivery| delivery.authority == authority)
+            .count();
+        if count >= self.per_account.get() {
+            return Err(Error::TooManyWebhooks {
+                authority,
+                max: self.per_account.get(),
+            });
+        }
+
+        let subscription = self.subscriptions.register(SubscriptionKind::Webhook {
+            authority: authority.clone(),
+            filters: filters.clone(),
+            url: url.clone(),
+        });
+        let id = subscription.id();
+        let delivery = Arc::new(Delivery {
+            authority,
+            url: url.clone(),
+            delivered: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            pending: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+        });
+        registered.insert(id, Arc::clone(&delivery));
+        drop(registered);
+
+        let forwarder = WebhookForwarder {
+            sink: WebhookSink {
+                name: format!("webhook {id}"),
+                url,
+                secret: secret.into_bytes(),
+                agent: self.agent.clone(),
+            },
+            filters,
+            max_retries: self.max_retries,
+            retry_delay: self.retry_delay,
+            receiver: self.events.subscribe(),
+            delivery: Arc::clone(&delivery),
+            subscription,
+            registered: Arc::clone(&self.registered),
+            notify_shutdown: Arc::clone(&self.notify_shutdown),
+        };
+        tokio::task::spawn(forwarder.run());
+
+        Ok(delivery.status(id))
+    }
+
+    fn find(&self, authority: &AccountId, id: WebhookId) -> Result<Arc<Delivery>, Error> {
+        lock(&self.registered)
+            .get(&id)
+            .filter(|delivery| delivery.authority == *authority)
+            .cloned()
+            .ok_or(Error::NotFound(id))
+    }
+}
+
+fn lock(
+    registered: &Mutex<BTreeMap<WebhookId, Arc<Delivery>>>,
+) -> std::sync::MutexGuard<'_, BTreeMap<WebhookId, Arc<Delivery>>> {
+    registered
+        .lock()
+        .expect("INTERNAL BUG: webhooks lock is poisoned")
+}
+
+impl Delivery {
+    fn status(&self, id: WebhookId) -> WebhookStatus {
+        WebhookStatus {
+            id,
+            url: self.url.clone(),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            pending: self.pending.load(Ordering::Relaxed),
+            last_error: self
+                .last_error
+                .lock()
+                .expect("INTERNAL BUG: webhook status lock is poisoned")
+                .clone(),
+        }
+    }
+
+    fn set_last_error(&self, error: String) {
+        *self
+            .last_error
+            .lock()
+            .expect("INTERNAL BUG: webhook status lock is poisoned") = Some(error);
+    }
+}
+
+/// Hex-encoded HMAC-SHA256 of `body` keyed by `secret`, sent in the [`SIGNATURE_HEADER`]
+pub fn signature(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Sink posting events to the URL of a webhook
+struct WebhookSink {
+    name: String,
+    url: String,
+    secret: Vec<u8>,
+    agent: ureq::Agent,
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn deliver(&mut self, event: &EventBox) -> eyre::Result<()> {
+        let body = event_sink::encode(event, EventFormat::Json)?;
+        let request = self
+            .agent
+            .post(&self.url)
+            .set("content-type", "application/json")
+            .set(SIGNATURE_HEADER, &signature(&self.secret, &body));
+
+        tokio::task::spawn_blocking(move || request.send_bytes(&body))
+            .await?
+            .map_err(|error| eyre::eyre!("{error}"))?;
+        Ok(())
+    }
+}
+
+/// Delivers the events matching the filters of a single webhook
+struct WebhookForwarder {
+    sink: WebhookSink,
+    filters: Vec<EventFilterBox>,
+    max_retries: u32,
+    retry_delay: Duration,
+    receiver: broadcast::Receiver<EventBox>,
+    delivery: Arc<Delivery>,
+    subscription: SubscriptionHandle,
+    registered: Arc<Mutex<BTreeMap<WebhookId, Arc<Delivery>>>>,
+    notify_shutdown: Arc<Notify>,
+}
+
+impl WebhookForwarder {
+    async fn run(mut self) {
+        let id = self.subscription.id();
+        loop {
+            tokio::select! {
+                event = self.receiver.recv() => match event {
+                    Ok(event) if self.filters.iter().any(|filter| filter.matches(&event)) => {
+                        self.deliver(&event).await;
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        iroha_logger::warn!(webhook = id, skipped, "Webhook is lagging behind, events are dropped");
+                        self.delivery.set_last_error(format!("Lagging behind, {skipped} events were skipped"));
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                () = self.subscription.terminated() => break,
+                () = self.notify_shutdown.notified() => break,
+            }
+
+            let backlog = self.receiver.len() as u64;
+            self.subscription.set_backlog(backlog);
+            self.delivery.pending.store(backlog, Ordering::Relaxed);
+        }
+
+        lock(&self.registered).remove(&id);
+    }
+
+    async fn deliver(&mut self, event: &EventBox) {
+        let mut delay = self.retry_delay;
+        for attempt in 0..=self.max_retries {
+            match self.sink.deliver(event).await {
+                Ok(()) => {
+                    self.delivery.delivered.fetch_add(1, Ordering::Relaxed);
+                    self.subscription.delivered();
+                    return;
+                }
+                Err(error) if attempt < self.max_retries => {
+                    iroha_logger::warn!(webhook = self.sink.name(), attempt, %error, "Failed to deliver event, retrying");
+                    self.delivery.set_last_error(error.to_string());
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                }
+                Err(error) => {
+                    iroha_logger::error!(webhook = self.sink.name(), %error, "Failed to deliver event, dropping it");
+                    self.delivery.set_last_error(error.to_string());
+                    self.delivery.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_is_hmac_sha256_of_body() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            signature(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -50,6 +50,9 @@ pub mod error {
         /// Parity SCALE (de)serialization issue
         #[cfg(feature = "scale")]
         ParityScale(String),
+        /// Parity SCALE decoding issue at a known position of the input
+        #[cfg(feature = "scale")]
+        ParityScaleAt(Box<DecodeError>),
         /// Problem with parsing integers
         ParseInt,
         /// Input version unsupported
@@ -98,6 +101,8 @@ pub mod error {
                 Self::Serde => "JSON (de)serialization issue".to_owned(),
                 #[cfg(feature = "scale")]
                 Self::ParityScale(x) => format!("Parity SCALE (de)serialization issue: {x}"),
+                #[cfg(feature = "scale")]
+                Self::ParityScaleAt(x) => format!("Parity SCALE decoding issue: {x}"),
                 Self::ParseInt => "Issue with parsing integers".to_owned(),
                 Self::UnsupportedVersion(v) => {
                     format!("Input version {} is unsupported", v.version)
@@ -160,6 +165,32 @@ impl UnsupportedVersion {
     }
 }
 
+/// Failure to decode Parity SCALE input, pinpointed for debugging of foreign encoders.
+#[cfg(feature = "scale")]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct DecodeError {
+    /// Type which was expected to be decoded
+    pub expected: String,
+    /// Number of bytes successfully read before the failure
+    pub offset: u64,
+    /// Length of the whole input
+    pub len: u64,
+    /// Cause reported by the codec, naming the field which couldn't be decoded
+    pub reason: String,
+}
+
+#[cfg(feature = "scale")]
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "could not decode `{}` at byte {} of {}: {}",
+            self.expected, self.offset, self.len, self.reason
+        )
+    }
+}
+
 /// Raw versioned content, serialized.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "scale", derive(Encode, Decode))]
@@ -175,11 +206,79 @@ pub enum RawVersioned {
 #[cfg(feature = "scale")]
 pub mod scale {
     #[cfg(not(feature = "std"))]
-    use alloc::vec::Vec;
+    use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+    use parity_scale_codec::{Decode, DecodeAll, DecodeLimit, Encode, Input};
+
+    use super::{
+        error::{Error, Result},
+        DecodeError, RawVersioned, UnsupportedVersion, Version,
+    };
+
+    /// Limits on the resources spent decoding data received from untrusted sources.
+    ///
+    /// Length of collections isn't limited explicitly: decoding from a slice never
+    /// preallocates more elements than the remaining input can hold, so every
+    /// collection is bounded by [`Self::max_len`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct DecodeLimits {
+        /// Maximal length of the input in bytes
+        pub max_len: usize,
+        /// Maximal nesting depth of the decoded value
+        pub max_depth: u32,
+    }
+
+    impl DecodeLimits {
+        /// Limits suitable for the data received over the network
+        pub const DEFAULT: Self = Self {
+            max_len: 64 * 2_usize.pow(20),
+            max_depth: 256,
+        };
+    }
 
-    use parity_scale_codec::{DecodeAll, Encode};
+    impl Default for DecodeLimits {
+        fn default() -> Self {
+            Self::DEFAULT
+        }
+    }
 
-    use super::{error::Result, Version};
+    /// Analog of `decode_all` which respects `limits`.
+    ///
+    /// # Errors
+    /// - Input is longer than [`DecodeLimits::max_len`]
+    /// - Value is nested deeper than [`DecodeLimits::max_depth`]
+    /// - Input won't have enough bytes for decoding
+    /// - Input has extra bytes
+    pub fn decode_all_with_limits<T: Decode>(
+        input: &[u8],
+        limits: DecodeLimits,
+    ) -> core::result::Result<T, parity_scale_codec::Error> {
+        if input.len() > limits.max_len {
+            return Err("Input exceeds the length limit".into());
+        }
+        T::decode_all_with_depth_limit(limits.max_depth, &mut &*input)
+    }
+
+    /// Input which remembers how many bytes have been read, to report where decoding failed
+    struct TrackedInput<'a> {
+        input: &'a [u8],
+        offset: usize,
+    }
+
+    impl Input for TrackedInput<'_> {
+        fn remaining_len(
+            &mut self,
+        ) -> core::result::Result<Option<usize>, parity_scale_codec::Error> {
+            Ok(Some(self.input.len() - self.offset))
+        }
+
+        fn read(&mut self, into: &mut [u8]) -> core::result::Result<(), parity_scale_codec::Error> {
+            let mut remaining = &self.input[self.offset..];
+            remaining.read(into)?;
+            self.offset += into.len();
+            Ok(())
+        }
+    }
 
     /// [`Decode`] versioned analog.
     pub trait DecodeVersioned: DecodeAll + Version {
@@ -190,6 +289,47 @@ pub mod scale {
         /// - Input won't have enough bytes for decoding
         /// - Input has extra bytes
         fn decode_all_versioned(input: &[u8]) -> Result<Self>;
+
+        /// Analog of [`Self::decode_all_versioned`] for untrusted input, see [`decode_all_with_limits`].
+        ///
+        /// Decoding failures are reported as [`Error::ParityScaleAt`] with the offset of the failure.
+        ///
+        /// # Errors
+        /// - Version is unsupported
+        /// - Input violates `limits`
+        /// - Input won't have enough bytes for decoding
+        /// - Input has extra bytes
+        fn decode_all_versioned_with_limits(input: &[u8], limits: DecodeLimits) -> Result<Self>
+        where
+            Self: Decode,
+        {
+            let version = *input.first().ok_or(Error::NotVersioned)?;
+            if !Self::supported_versions().contains(&version) {
+                return Err(Error::UnsupportedVersion(Box::new(
+                    UnsupportedVersion::new(version, RawVersioned::ScaleBytes(input.to_vec())),
+                )));
+            }
+            if input.len() > limits.max_len {
+                return Err(
+                    parity_scale_codec::Error::from("Input exceeds the length limit").into(),
+                );
+            }
+
+            let mut tracked = TrackedInput { input, offset: 0 };
+            let value =
+                Self::decode_with_depth_limit(limits.max_depth, &mut tracked).map_err(|error| {
+                    Error::ParityScaleAt(Box::new(DecodeError {
+                        expected: core::any::type_name::<Self>().to_string(),
+                        offset: tracked.offset as u64,
+                        len: input.len() as u64,
+                        reason: error.to_string(),
+                    }))
+                })?;
+            match input.len() - tracked.offset {
+                0 => Ok(value),
+                extra => Err(Error::ExtraBytesLeft(extra as u64)),
+            }
+        }
     }
 
     /// [`Encode`] versioned analog.
@@ -265,4 +405,44 @@ mod tests {
         assert!(!VersionedContainer(10).is_supported());
         assert!(!VersionedContainer(11).is_supported());
     }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn decode_error_reports_offset() {
+        use self::scale::{DecodeLimits, DecodeVersioned};
+
+        #[derive(Debug, Encode, Decode)]
+        struct Versioned {
+            version: u8,
+            value: u64,
+        }
+
+        impl Version for Versioned {
+            fn version(&self) -> u8 {
+                self.version
+            }
+
+            fn supported_versions() -> Range<u8> {
+                1..2
+            }
+        }
+
+        impl DecodeVersioned for Versioned {
+            fn decode_all_versioned(input: &[u8]) -> error::Result<Self> {
+                Self::decode_all_versioned_with_limits(input, DecodeLimits::DEFAULT)
+            }
+        }
+
+        let error = Versioned::decode_all_versioned(&[1, 2, 3]).unwrap_err();
+
+        let error::Error::ParityScaleAt(error) = error else {
+            panic!("Unexpected error: {error}");
+        };
+        assert_eq!(error.offset, 1);
+        assert_eq!(error.len, 3);
+        assert!(error.expected.ends_with("Versioned"));
+
+        let error = Versioned::decode_all_versioned(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 42]).unwrap_err();
+        assert!(matches!(error, error::Error::ExtraBytesLeft(1)));
+    }
 }
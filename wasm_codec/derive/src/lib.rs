@@ -1,5 +1,5 @@
 //! This crate provides [`wrap`] and [`wrap_signature`] attribute macros to wrap a host-defined
-//! function into another function which signature will be compatible with `wasmtime` crate to be
+//! function into another function which signature will be compatible with the WASM engine to be
 //! successfully exported.
 
 use std::ops::Deref;
@@ -35,7 +35,7 @@ impl syn::parse::Parse for StateAttr {
 }
 
 /// Macro to wrap function with normal parameters and return value to another one which will
-/// meet `wasmtime` specifications.
+/// meet the WASM engine specifications.
 ///
 /// Describing all possible input and output signatures would be a very big table,
 /// so see detailed signature by expanding generated code (i.e. with `cargo expand`).
@@ -43,10 +43,10 @@ impl syn::parse::Parse for StateAttr {
 /// # Key notes
 ///
 /// 1. If there is something to encode or decode (input or output) generated signature will always
-/// return `Result<..., wasmtime::Error>`
+/// return `Result<..., engine::Error>`
 /// 2. If your function returns `T` on success, then generated function will return
-/// `Result<WasmUsize, wasmtime::Error>`, where `WasmUsize` is the offset of encoded `T` prefixed with length
-/// 3. If your function returns [`Result`] with `wasmtime::Error` on [`Err`], generated function will pop it up
+/// `Result<WasmUsize, engine::Error>`, where `WasmUsize` is the offset of encoded `T` prefixed with length
+/// 3. If your function returns [`Result`] with `engine::Error` on [`Err`], generated function will pop it up
 /// 4. If your function returns [`Result`] with custom error, then it will be encoded into memory (as in 2)
 /// 5. You can receive constant or mutable reference to *state* as the second parameter of your function
 /// 6. You can have only two function parameters, where second is reserved for *state*,
@@ -127,7 +127,7 @@ fn impl_wrap_fn(
 }
 
 /// Macro to wrap trait function signature with normal parameters and return value
-/// to another one which will meet `wasmtime` specifications.
+/// to another one which will meet the WASM engine specifications.
 ///
 /// See [`wrap`] for more details.
 #[manyhow]
@@ -210,7 +210,7 @@ fn gen_params(
             quote! {}
         };
         params.push(parse_quote! {
-            #mutability caller: ::wasmtime::Caller<#state_ty>
+            #mutability caller: ::iroha_wasm_codec::engine::Caller<#state_ty>
         });
     }
 
@@ -233,11 +233,11 @@ fn gen_output(
 ) -> syn::Type {
     match (param, return_type) {
         (None, None) => parse_quote! { () },
-        (Some(_), None | Some(ReturnType::Result(None, ErrType::WasmtimeError))) => parse_quote! {
-            ::wasmtime::Result<()>
+        (Some(_), None | Some(ReturnType::Result(None, ErrType::EngineError))) => parse_quote! {
+            ::iroha_wasm_codec::engine::Result<()>
         },
         (_, _) => parse_quote! {
-            ::wasmtime::Result<iroha_wasm_codec::WasmUsize>
+            ::iroha_wasm_codec::engine::Result<iroha_wasm_codec::WasmUsize>
         },
     }
 }
@@ -303,31 +303,31 @@ fn gen_body(
         // foo() =>
         // foo()
         //
-        // foo() -> Result<(), wasmtime::Error> =>
-        // foo() -> Result<(), wasmtime::Error>
-        (None, None | Some(ReturnType::Result(None, ErrType::WasmtimeError))) => quote! {
+        // foo() -> Result<(), engine::Error> =>
+        // foo() -> Result<(), engine::Error>
+        (None, None | Some(ReturnType::Result(None, ErrType::EngineError))) => quote! {
             Self::#inner_fn_ident(#pass_state)
         },
         // foo() -> RetType
         // | foo() -> Result<(), ErrType>
         // | foo() -> Result<OkType, ErrType> =>
-        // foo() -> Result<WasmUsize, wasmtime::Error>
+        // foo() -> Result<WasmUsize, engine::Error>
         (None, Some(ReturnType::Other(_) | ReturnType::Result(_, ErrType::Other(_)))) => quote! {
             let value = Self::#inner_fn_ident(#pass_state);
             #get_memory
             #get_alloc
             ::iroha_wasm_codec::encode_into_memory(&value, &memory, &alloc_fn, &mut caller)
         },
-        // foo() -> Result<OkType, wasmtime::Error> =>
-        // foo() -> Result<WasmUsize, wasmtime::Error>
-        (None, Some(ReturnType::Result(Some(ok_type), ErrType::WasmtimeError))) => quote! {
+        // foo() -> Result<OkType, engine::Error> =>
+        // foo() -> Result<WasmUsize, engine::Error>
+        (None, Some(ReturnType::Result(Some(ok_type), ErrType::EngineError))) => quote! {
             let value: #ok_type = Self::#inner_fn_ident(#pass_state)?;
             #get_memory
             #get_alloc
             ::iroha_wasm_codec::encode_into_memory(&value, &memory, &alloc_fn, &mut caller)
         },
         // foo(Param) =>
-        // foo(WasmUsize, WasmUsize) -> Result<(), wasmtime::Error>
+        // foo(WasmUsize, WasmUsize) -> Result<(), engine::Error>
         (Some(_param_ty), None) => quote! {
             #get_memory
             #decode_param
@@ -335,9 +335,9 @@ fn gen_body(
             Self::#inner_fn_ident(param, #pass_state);
             Ok(())
         },
-        // foo(Param) -> Result<(), wasmtime::Error> =>
-        // foo(WasmUsize, WasmUsize) -> Result<(), wasmtime::Error>
-        (Some(_param_ty), Some(ReturnType::Result(None, ErrType::WasmtimeError))) => quote! {
+        // foo(Param) -> Result<(), engine::Error> =>
+        // foo(WasmUsize, WasmUsize) -> Result<(), engine::Error>
+        (Some(_param_ty), Some(ReturnType::Result(None, ErrType::EngineError))) => quote! {
             #get_memory
             #decode_param
 
@@ -346,7 +346,7 @@ fn gen_body(
         // foo(Param) -> RetType
         // | foo(Param) -> Result<(), ErrType>
         // | foo(Param) -> Result<OkType, ErrType> =>
-        // foo(WasmUsize, WasmUsize) -> Result<WasmUsize, WasmtimeError>
+        // foo(WasmUsize, WasmUsize) -> Result<WasmUsize, engine::Error>
         (
             Some(_param_ty),
             Some(ReturnType::Other(_) | ReturnType::Result(_, ErrType::Other(_))),
@@ -358,9 +358,9 @@ fn gen_body(
             let value = Self::#inner_fn_ident(param, #pass_state);
             ::iroha_wasm_codec::encode_into_memory(&value, &memory, &alloc_fn, &mut caller)
         },
-        // foo(Param) -> Result<OkType, wasmtime::Error> =>
-        // foo(WasmUsize, WasmUsize) -> Result<WasmUsize, wasmtime::Error>
-        (Some(_param_ty), Some(ReturnType::Result(Some(ok_type), ErrType::WasmtimeError))) => {
+        // foo(Param) -> Result<OkType, engine::Error> =>
+        // foo(WasmUsize, WasmUsize) -> Result<WasmUsize, engine::Error>
+        (Some(_param_ty), Some(ReturnType::Result(Some(ok_type), ErrType::EngineError))) => {
             quote! {
                 #get_memory
                 #get_alloc
@@ -397,9 +397,9 @@ enum ReturnType {
 
 /// Classified error type
 enum ErrType {
-    /// `wasmtime::Error` error type
-    WasmtimeError,
-    /// Something other than `wasmtime::Error`
+    /// `engine::Error` error type
+    EngineError,
+    /// Something other than `engine::Error`
     #[allow(dead_code)] // May be used in future
     Other(syn::Type),
 }
@@ -457,8 +457,8 @@ fn classify_fn(emitter: &mut Emitter, fn_sig: &syn::Signature) -> Option<FnClass
         return None;
     };
 
-    let err_type = if err_type_last_segment.ident == "WasmtimeError" {
-        ErrType::WasmtimeError
+    let err_type = if err_type_last_segment.ident == "EngineError" {
+        ErrType::EngineError
     } else {
         ErrType::Other(err_type.clone())
     };
@@ -0,0 +1,118 @@
+//! WASM engine the host executes the modules with.
+//!
+//! Modules are either compiled to native code by `wasmtime` (the `wasmtime` feature, enabled by
+//! default) or interpreted by `wasmi` (the `wasmi` feature), e.g. on embedded devices or where
+//! the executable memory pages of a JIT aren't allowed. Exactly one of the features has to be
+//! enabled, so `wasmi` builds have to disable the default features.
+//!
+//! Both engines expose the same API for the types re-exported from this module, so the host
+//! functions are written only once. The [`Backend`] trait covers the parts in which they differ.
+//!
+//! Both engines meter the execution with fuel and fail with [`TrapKind::ExecutionLimits`] once
+//! it runs out, but they charge different amounts of fuel for the same code. The fuel consumed
+//! by the smart contracts is a part of the chain state, so all peers of a network have to run
+//! the same engine. The engine of the network is recorded in its parameters and peers refuse to
+//! validate the blocks of a network running another engine.
+
+#[cfg(not(any(feature = "wasmtime", feature = "wasmi")))]
+compile_error!("Either the `wasmtime` or the `wasmi` feature has to be enabled");
+#[cfg(all(feature = "wasmtime", feature = "wasmi"))]
+compile_error!(
+    "The `wasmtime` and `wasmi` features are mutually exclusive, disable the default features to use `wasmi`"
+);
+
+#[cfg(feature = "wasmi")]
+pub mod wasmi;
+#[cfg(feature = "wasmtime")]
+pub mod wasmtime;
+
+#[cfg(feature = "wasmi")]
+pub use ::wasmi::{
+    AsContext, AsContextMut, Caller, Engine, Extern, Instance, Linker, Memory, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc, WasmParams, WasmResults,
+};
+#[cfg(feature = "wasmtime")]
+pub use ::wasmtime::{
+    AsContext, AsContextMut, Caller, Engine, Extern, Instance, Linker, Memory, Store, StoreLimits,
+    StoreLimitsBuilder, TypedFunc, WasmParams, WasmResults,
+};
+
+#[cfg(feature = "wasmi")]
+pub use self::wasmi::Wasmi as Selected;
+#[cfg(feature = "wasmtime")]
+pub use self::wasmtime::Wasmtime as Selected;
+
+/// Compiled module of the selected engine
+pub type Module = <Selected as Backend>::Module;
+
+/// Error of the selected engine
+pub type Error = <Selected as Backend>::Error;
+
+/// [`Result`](core::result::Result) with the [`Error`] of the selected engine
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Error of an out of bounds memory access in the selected engine
+pub type MemoryAccessError = <Selected as Backend>::MemoryAccessError;
+
+/// Kind of the failure of a function exported from a module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrapKind {
+    /// Function imported from the host failed
+    Host,
+    /// Module ran out of fuel, stack or memory
+    ExecutionLimits,
+    /// Module trapped for any other reason, e.g. reached `unreachable`
+    Other,
+}
+
+/// WASM engine the host can execute the modules with
+pub trait Backend {
+    /// Name of the engine, e.g. to be reported in logs
+    const NAME: &'static str;
+
+    /// Engine compiling the modules
+    type Engine: Clone + Send + Sync + 'static;
+    /// Compiled module
+    type Module: Clone + Send + Sync + 'static;
+    /// Linker of the host functions with the state `T`
+    type Linker<T>;
+    /// Store of the module instances with the state `T`
+    type Store<T>;
+    /// Instance of a module
+    type Instance;
+    /// Error of the engine
+    type Error: core::fmt::Debug + core::fmt::Display + Send + Sync + 'static;
+    /// Error of an out of bounds memory access
+    type MemoryAccessError: core::fmt::Debug + core::fmt::Display + Send + Sync + 'static;
+
+    /// Create an engine metering the execution with fuel
+    ///
+    /// # Errors
+    ///
+    /// If the engine can't be configured
+    fn create_engine() -> Result<Self::Engine, Self::Error>;
+
+    /// Compile the module from its binary or text format
+    ///
+    /// # Errors
+    ///
+    /// If the module isn't valid
+    fn load_module(engine: &Self::Engine, bytes: &[u8]) -> Result<Self::Module, Self::Error>;
+
+    /// Instantiate the `module` linking it with the host functions of the `linker`
+    ///
+    /// # Errors
+    ///
+    /// If the module imports functions the linker doesn't define or its start function fails
+    fn instantiate<T>(
+        linker: &Self::Linker<T>,
+        store: &mut Self::Store<T>,
+        module: &Self::Module,
+    ) -> Result<Self::Instance, Self::Error>;
+
+    /// Classify the failure of a function exported from a module
+    fn trap_kind(error: &Self::Error) -> TrapKind;
+
+    /// Wrap the `error` of a host function to be returned to the engine
+    fn host_error<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self::Error;
+}
@@ -0,0 +1,75 @@
+//! [`Backend`] interpreting the modules with `wasmi`
+
+use std::sync::Arc;
+
+use ::wasmi::{
+    core::{HostError, TrapCode},
+    errors::MemoryError,
+    Config, Engine, Error, Instance, Linker, Module, Store,
+};
+
+use super::{Backend, TrapKind};
+
+/// `wasmi` engine
+#[derive(Debug, Clone, Copy)]
+pub struct Wasmi;
+
+/// Error of a host function, see [`Backend::host_error`]
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct HostFailure(Box<dyn std::error::Error + Send + Sync>);
+
+impl HostError for HostFailure {}
+
+impl Backend for Wasmi {
+    const NAME: &'static str = "wasmi";
+
+    type Engine = Engine;
+    // Unlike in `wasmtime`, cloning a module would copy its data segments
+    type Module = Arc<Module>;
+    type Linker<T> = Linker<T>;
+    type Store<T> = Store<T>;
+    type Instance = Instance;
+    type Error = Error;
+    type MemoryAccessError = MemoryError;
+
+    fn create_engine() -> Result<Engine, Error> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        Ok(Engine::new(&config))
+    }
+
+    fn load_module(engine: &Engine, bytes: &[u8]) -> Result<Arc<Module>, Error> {
+        // `wasmtime` accepts the text format as well
+        let bytes = wat::parse_bytes(bytes).map_err(Self::host_error)?;
+        Module::new(engine, &bytes[..]).map(Arc::new)
+    }
+
+    fn instantiate<T>(
+        linker: &Linker<T>,
+        store: &mut Store<T>,
+        module: &Arc<Module>,
+    ) -> Result<Instance, Error> {
+        linker.instantiate(&mut *store, module)?.start(&mut *store)
+    }
+
+    fn trap_kind(error: &Error) -> TrapKind {
+        match error.as_trap_code() {
+            Some(
+                TrapCode::StackOverflow
+                | TrapCode::MemoryOutOfBounds
+                | TrapCode::TableOutOfBounds
+                | TrapCode::IndirectCallToNull
+                | TrapCode::OutOfFuel
+                | TrapCode::GrowthOperationLimited,
+            ) => TrapKind::ExecutionLimits,
+            Some(_) => TrapKind::Other,
+            None if error.downcast_ref::<HostFailure>().is_some() => TrapKind::Host,
+            None => TrapKind::Other,
+        }
+    }
+
+    fn host_error<E: std::error::Error + Send + Sync + 'static>(error: E) -> Error {
+        Error::host(HostFailure(Box::new(error)))
+    }
+}
@@ -0,0 +1,62 @@
+//! [`Backend`] compiling the modules to native code with `wasmtime`
+
+use ::wasmtime::{Config, Engine, Error, Instance, Linker, MemoryAccessError, Module, Store, Trap};
+
+use super::{Backend, TrapKind};
+
+/// `wasmtime` engine
+#[derive(Debug, Clone, Copy)]
+pub struct Wasmtime;
+
+impl Backend for Wasmtime {
+    const NAME: &'static str = "wasmtime";
+
+    type Engine = Engine;
+    type Module = Module;
+    type Linker<T> = Linker<T>;
+    type Store<T> = Store<T>;
+    type Instance = Instance;
+    type Error = Error;
+    type MemoryAccessError = MemoryAccessError;
+
+    fn create_engine() -> Result<Engine, Error> {
+        let mut config = Config::new();
+        config.consume_fuel(true).cache_config_load_default()?;
+        #[cfg(feature = "profiling")]
+        {
+            config.profiler(::wasmtime::ProfilingStrategy::PerfMap);
+        }
+        Engine::new(&config)
+    }
+
+    fn load_module(engine: &Engine, bytes: &[u8]) -> Result<Module, Error> {
+        Module::new(engine, bytes)
+    }
+
+    fn instantiate<T>(
+        linker: &Linker<T>,
+        store: &mut Store<T>,
+        module: &Module,
+    ) -> Result<Instance, Error> {
+        linker.instantiate(store, module)
+    }
+
+    fn trap_kind(error: &Error) -> TrapKind {
+        match error.downcast_ref::<Trap>() {
+            Some(
+                Trap::StackOverflow
+                | Trap::MemoryOutOfBounds
+                | Trap::TableOutOfBounds
+                | Trap::IndirectCallToNull
+                | Trap::OutOfFuel
+                | Trap::Interrupt,
+            ) => TrapKind::ExecutionLimits,
+            Some(_) => TrapKind::Other,
+            None => TrapKind::Host,
+        }
+    }
+
+    fn host_error<E: std::error::Error + Send + Sync + 'static>(error: E) -> Error {
+        Error::new(error)
+    }
+}
@@ -1,8 +1,11 @@
 //! This crate provides utils for encoding/decoding objects between Iroha host and Wasm smart contracts.
 
+pub mod engine;
+
 pub use iroha_core_wasm_codec_derive::{wrap, wrap_trait_fn};
 use parity_scale_codec::{DecodeAll, Encode, Error as ParityError};
-use wasmtime::Result;
+
+use self::engine::{AsContext, AsContextMut, Backend as _, Memory, Result, Selected, TypedFunc};
 
 /// [`usize`] of wasm
 pub type WasmUsize = u32;
@@ -15,7 +18,20 @@ pub enum Error {
     DecodeAll(#[from] ParityError),
     /// Represents an out of bounds memory access.
     #[error("failed to access memory: {0}")]
-    MemoryAccess(#[from] wasmtime::MemoryAccessError),
+    // Not a `#[source]` as `wasmi` memory errors don't implement `std::error::Error`
+    MemoryAccess(engine::MemoryAccessError),
+}
+
+impl From<engine::MemoryAccessError> for Error {
+    fn from(error: engine::MemoryAccessError) -> Self {
+        Self::MemoryAccess(error)
+    }
+}
+
+impl Error {
+    fn into_host_error(error: impl Into<Self>) -> engine::Error {
+        Selected::host_error(error.into())
+    }
 }
 
 /// Decode object from the given `memory` at the given `offset` with the given `len`
@@ -26,9 +42,9 @@ pub enum Error {
 ///
 /// # Errors
 ///
-/// Fails with [`Error`] which will be converted into [`wasmtime::Error`] if decoding fails.
-pub fn decode_from_memory<C: wasmtime::AsContext, T: DecodeAll>(
-    memory: &wasmtime::Memory,
+/// Fails with [`Error`] which will be converted into [`engine::Error`] if decoding fails.
+pub fn decode_from_memory<C: AsContext, T: DecodeAll>(
+    memory: &Memory,
     context: &C,
     offset: WasmUsize,
     len: WasmUsize,
@@ -36,7 +52,7 @@ pub fn decode_from_memory<C: wasmtime::AsContext, T: DecodeAll>(
     // Accessing memory as a byte slice to avoid the use of unsafe
     let mem_range = offset as usize..(offset + len) as usize;
     let mut bytes = &memory.data(context)[mem_range];
-    T::decode_all(&mut bytes).map_err(Into::into)
+    T::decode_all(&mut bytes).map_err(Error::into_host_error)
 }
 
 /// Decode the object from a given pointer where first element is the size of the object
@@ -50,12 +66,9 @@ pub fn decode_from_memory<C: wasmtime::AsContext, T: DecodeAll>(
 ///
 /// - Failed to decode object
 /// - Failed to call `dealloc_fn`
-pub fn decode_with_length_prefix_from_memory<
-    C: wasmtime::AsContextMut,
-    T: DecodeAll + std::fmt::Debug,
->(
-    memory: &wasmtime::Memory,
-    dealloc_fn: &wasmtime::TypedFunc<(WasmUsize, WasmUsize), ()>,
+pub fn decode_with_length_prefix_from_memory<C: AsContextMut, T: DecodeAll + std::fmt::Debug>(
+    memory: &Memory,
+    dealloc_fn: &TypedFunc<(WasmUsize, WasmUsize), ()>,
     mut context: &mut C,
     offset: WasmUsize,
 ) -> Result<T> {
@@ -74,7 +87,8 @@ pub fn decode_with_length_prefix_from_memory<
         ..(offset + len).try_into().expect(U32_TO_USIZE_ERROR_MES)];
 
     let obj =
-        T::decode_all(&mut &bytes[len_size_bytes.try_into().expect(U32_TO_USIZE_ERROR_MES)..])?;
+        T::decode_all(&mut &bytes[len_size_bytes.try_into().expect(U32_TO_USIZE_ERROR_MES)..])
+            .map_err(Error::into_host_error)?;
 
     dealloc_fn.call(&mut context, (offset, len))?;
     Ok(obj)
@@ -90,9 +104,9 @@ pub fn decode_with_length_prefix_from_memory<
 /// - If failed to write into the `memory`
 pub fn encode_into_memory<T: Encode>(
     obj: &T,
-    memory: &wasmtime::Memory,
-    alloc_fn: &wasmtime::TypedFunc<WasmUsize, WasmUsize>,
-    mut context: impl wasmtime::AsContextMut,
+    memory: &Memory,
+    alloc_fn: &TypedFunc<WasmUsize, WasmUsize>,
+    mut context: impl AsContextMut,
 ) -> Result<WasmUsize> {
     let bytes = encode_with_length_prefix(obj);
 
@@ -106,7 +120,9 @@ pub fn encode_into_memory<T: Encode>(
         .try_into()
         .expect("`u32` should always fit in `usize`");
 
-    memory.write(&mut context, offset_usize, &bytes)?;
+    memory
+        .write(&mut context, offset_usize, &bytes)
+        .map_err(Error::into_host_error)?;
 
     Ok(offset)
 }
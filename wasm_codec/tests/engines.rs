@@ -0,0 +1,110 @@
+//! Both engines have to agree on the outcome of the same module, so that a network
+//! can't tell which engine its peers run other than by the fuel consumed.
+//!
+//! Only one engine is compiled in at a time, so the outcomes are pinned and the tests
+//! have to pass both with the default features and with `--no-default-features --features wasmi`.
+
+use iroha_wasm_codec::engine::{Backend, Caller, Linker, Selected, Store, TrapKind};
+
+const FUEL: u64 = 1_000_000;
+
+const MODULE: &str = r#"
+    (module
+        (import "iroha" "fail" (func $fail))
+        (memory (export "memory") 1)
+
+        ;; Sum of 1..=n wrapping on overflow
+        (func (export "sum") (param $n i64) (result i64)
+            (local $acc i64)
+            (block $done
+                (loop $next
+                    (br_if $done (i64.eqz (local.get $n)))
+                    (local.set $acc (i64.add (local.get $acc) (local.get $n)))
+                    (local.set $n (i64.sub (local.get $n) (i64.const 1)))
+                    (br $next)))
+            local.get $acc)
+
+        (func (export "spin") (param i64) (result i64)
+            (loop $forever (br $forever))
+            i64.const 0)
+
+        (func (export "out_of_bounds") (param i64) (result i64)
+            (i64.load (i32.const 65536)))
+
+        (func (export "unreachable") (param i64) (result i64)
+            unreachable)
+
+        (func (export "host_failure") (param i64) (result i64)
+            call $fail
+            i64.const 0))
+"#;
+
+#[derive(Debug, thiserror::Error)]
+#[error("host function failed")]
+struct HostFunctionFailed;
+
+/// Outcome of calling a function exported from [`MODULE`]
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Returned(i64),
+    Trapped(TrapKind),
+}
+
+fn call(func: &str, arg: i64) -> Outcome {
+    let engine = Selected::create_engine().expect("Engine should be created");
+    let module = Selected::load_module(&engine, MODULE.as_bytes()).expect("Module should be valid");
+
+    let mut linker = Linker::<()>::new(&engine);
+    linker
+        .func_wrap("iroha", "fail", |_: Caller<()>| {
+            Err::<(), _>(Selected::host_error(HostFunctionFailed))
+        })
+        .expect("Host function should be defined");
+
+    let mut store = Store::new(&engine, ());
+    store.set_fuel(FUEL).expect("Fuel should be enabled");
+    let instance =
+        Selected::instantiate(&linker, &mut store, &module).expect("Module should be instantiated");
+
+    instance
+        .get_typed_func::<i64, i64>(&mut store, func)
+        .expect("Function should be exported")
+        .call(&mut store, arg)
+        .map_or_else(
+            |error| Outcome::Trapped(Selected::trap_kind(&error)),
+            Outcome::Returned,
+        )
+}
+
+#[test]
+fn same_result() {
+    assert_eq!(call("sum", 0), Outcome::Returned(0));
+    assert_eq!(call("sum", 1_000), Outcome::Returned(500_500));
+}
+
+#[test]
+fn same_trap_on_fuel_exhaustion() {
+    assert_eq!(call("spin", 0), Outcome::Trapped(TrapKind::ExecutionLimits));
+    assert_eq!(
+        call("sum", i64::MAX),
+        Outcome::Trapped(TrapKind::ExecutionLimits)
+    );
+}
+
+#[test]
+fn same_trap_on_out_of_bounds_memory_access() {
+    assert_eq!(
+        call("out_of_bounds", 0),
+        Outcome::Trapped(TrapKind::ExecutionLimits)
+    );
+}
+
+#[test]
+fn same_trap_on_unreachable() {
+    assert_eq!(call("unreachable", 0), Outcome::Trapped(TrapKind::Other));
+}
+
+#[test]
+fn same_trap_on_host_failure() {
+    assert_eq!(call("host_failure", 0), Outcome::Trapped(TrapKind::Host));
+}
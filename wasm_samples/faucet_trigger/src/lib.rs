@@ -0,0 +1,83 @@
+//! Trigger which mints an asset to every account requesting it, at most once per cooldown period.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(not(test))]
+extern crate panic_halt;
+
+use alloc::format;
+
+use dlmalloc::GlobalDlmalloc;
+use iroha_trigger::{
+    prelude::*,
+    smart_contract::{query, query_single},
+};
+
+#[global_allocator]
+static ALLOC: GlobalDlmalloc = GlobalDlmalloc;
+
+getrandom::register_custom_getrandom!(iroha_trigger::stub_getrandom);
+
+/// Account metadata key which requests funds
+const REQUEST_KEY: &str = "faucet_request";
+
+/// Mint the configured amount to the account which inserted the request key into its metadata
+#[iroha_trigger::main]
+fn main(id: TriggerId, _owner: AccountId, event: EventBox) {
+    let EventBox::Data(DataEvent::Domain(DomainEvent::Account(AccountEvent::MetadataInserted(
+        request,
+    )))) = event
+    else {
+        return;
+    };
+    if request.key().as_ref() != REQUEST_KEY {
+        return;
+    }
+    let account_id = request.target().clone();
+
+    let asset_definition: AssetDefinitionId = trigger_setting(&id, "asset");
+    let amount: Numeric = trigger_setting(&id, "amount");
+    let cooldown_ms: u64 = trigger_setting(&id, "cooldown_ms");
+
+    let now_ms = latest_block_time_ms();
+    let claim_key: Name = format!("claim_{}_{}", account_id.signatory(), account_id.domain())
+        .parse()
+        .dbg_unwrap();
+
+    if let Ok(last_claim_ms) = query_single(FindTriggerMetadata::new(id.clone(), claim_key.clone()))
+    {
+        let last_claim_ms: u64 = last_claim_ms.try_into_any().dbg_unwrap();
+        if now_ms < last_claim_ms.saturating_add(cooldown_ms) {
+            iroha_trigger::log::info!("Faucet request is rate-limited");
+            return;
+        }
+    }
+
+    Mint::asset_numeric(amount, AssetId::new(asset_definition, account_id))
+        .execute()
+        .dbg_expect("Failed to mint faucet funds");
+    SetKeyValue::trigger(id, claim_key, now_ms)
+        .execute()
+        .dbg_expect("Failed to record faucet claim");
+}
+
+fn trigger_setting<T: serde::de::DeserializeOwned>(id: &TriggerId, key: &str) -> T {
+    query_single(FindTriggerMetadata::new(
+        id.clone(),
+        key.parse().dbg_unwrap(),
+    ))
+    .dbg_expect("Faucet setting is missing")
+    .try_into_any()
+    .dbg_expect("Faucet setting is malformed")
+}
+
+fn latest_block_time_ms() -> u64 {
+    let header = query(FindBlockHeaders)
+        .execute()
+        .dbg_unwrap()
+        .next()
+        .dbg_expect("At least genesis block should be committed")
+        .dbg_unwrap();
+    u64::try_from(header.creation_time().as_millis()).dbg_unwrap()
+}